@@ -29,6 +29,7 @@ Usage:
 import io
 import logging
 import os
+import re
 import sys
 from datetime import datetime
 from pathlib import Path
@@ -154,6 +155,61 @@ class JsonRpcSafeFormatter(logging.Formatter):
         return prefixed
 
 
+class SecretRedactingFilter(logging.Filter):
+    """
+    A filter that masks API key and OAuth token values in log records.
+
+    Plugin host stderr ends up in the app log file, so key values passed
+    in the environment (and anything shaped like a provider key or a
+    secret assignment) are replaced with "[REDACTED]" before a handler
+    formats the record.
+    """
+
+    MASK = "[REDACTED]"
+
+    KEY_PATTERNS = [
+        re.compile(r"\bsk-[A-Za-z0-9_\-]{16,}"),
+        re.compile(r"\bAIza[0-9A-Za-z_\-]{35}"),
+        re.compile(r"\bgsk_[A-Za-z0-9]{20,}"),
+        re.compile(r"\bhf_[A-Za-z0-9]{30,}"),
+        re.compile(r"\bgh[pousr]_[A-Za-z0-9]{36,}"),
+        re.compile(r"\bxox[abprs]-[A-Za-z0-9\-]{10,}"),
+    ]
+
+    NAMED_PATTERNS = [
+        re.compile(
+            r"(?i)(?P<name>\b[A-Z0-9_]*(?:APIKEY|API_KEY|SECRET|TOKEN|PASSWORD|PASSPHRASE)[A-Z0-9_]*"
+            r"[\"']?\s*[=:]\s*[\"']?)(?P<value>[^\s\"',}]{8,})"
+        ),
+        re.compile(r"(?i)(?P<name>\bbearer\s+)(?P<value>[A-Za-z0-9._~+/\-]{16,}=*)"),
+    ]
+
+    def redact(self, text: str) -> str:
+        """Mask key values from the environment and key-shaped text."""
+        secrets = [
+            value
+            for name, value in os.environ.items()
+            if name.startswith((API_KEY_ENV_PREFIX, OAUTH_TOKEN_ENV_PREFIX)) and len(value) >= 8
+        ]
+        for secret in sorted(secrets, key=len, reverse=True):
+            text = text.replace(secret, self.MASK)
+        for pattern in self.KEY_PATTERNS:
+            text = pattern.sub(self.MASK, text)
+        for pattern in self.NAMED_PATTERNS:
+            text = pattern.sub(rf"\g<name>{self.MASK}", text)
+        return text
+
+    def filter(self, record: logging.LogRecord) -> bool:
+        """Redact the message (and traceback) of a record in place."""
+        record.msg = self.redact(record.getMessage())
+        record.args = None
+        if record.exc_info and not record.exc_text:
+            record.exc_text = logging.Formatter().formatException(record.exc_info)
+        if record.exc_text:
+            record.exc_text = self.redact(record.exc_text)
+        return True
+
+
 def configure_host_logging(
     level: str = "INFO", log_format: str | None = None, date_format: str | None = None, use_json_safe: bool = False
 ) -> logging.Logger:
@@ -200,6 +256,7 @@ def configure_host_logging(
         formatter = logging.Formatter(fmt=fmt, datefmt=datefmt)
 
     handler.setFormatter(formatter)
+    handler.addFilter(SecretRedactingFilter())
 
     # Add handler to root logger
     root_logger.addHandler(handler)
@@ -269,11 +326,64 @@ def add_file_logging(
 
     formatter = logging.Formatter(fmt=DEFAULT_LOG_FORMAT, datefmt=DEFAULT_DATE_FORMAT)
     file_handler.setFormatter(formatter)
+    file_handler.addFilter(SecretRedactingFilter())
 
     # Add to root logger
     root_logger.addHandler(file_handler)
 
 
+# ============================================
+# API KEYS
+# ============================================
+
+# Prefix of the key variables set by the Tauri backend when it spawns the host
+API_KEY_ENV_PREFIX = "APP_FACTORY_APIKEY_"
+
+# Prefix of the OAuth access token variables set by the Tauri backend
+OAUTH_TOKEN_ENV_PREFIX = "APP_FACTORY_OAUTH_"
+
+
+def get_api_key(service: str) -> Optional[str]:
+    """
+    Get the active API key for a service.
+
+    Keys are passed by the Tauri backend as environment variables when it
+    spawns the host, so plugins never request key values over IPC. After
+    keys change, the backend restarts the host to pass the new values.
+
+    Args:
+        service: Service name (e.g. "openai", "gemini")
+
+    Returns:
+        The key, or None if the service has no active key
+
+    Example:
+        >>> api_key = get_api_key("openai")
+    """
+    return os.environ.get(f"{API_KEY_ENV_PREFIX}{service.upper()}") or None
+
+
+def get_oauth_token(service: str) -> Optional[str]:
+    """
+    Get the OAuth access token for a service connected in App Factory.
+
+    The token is the one that was current when the host was spawned; the
+    backend refreshes tokens before they expire and restarts hosts on
+    `refresh_plugin_secrets`.
+
+    Args:
+        service: OAuth provider id (e.g. "drive")
+
+    Returns:
+        The access token, or None if the service is not connected
+
+    Example:
+        >>> token = get_oauth_token("drive")
+        >>> headers = {"Authorization": f"Bearer {token}"}
+    """
+    return os.environ.get(f"{OAUTH_TOKEN_ENV_PREFIX}{service.upper()}") or None
+
+
 # ============================================
 # SHUTDOWN LOGGING
 # ============================================
@@ -371,6 +481,12 @@ __all__ = [
     "log_shutdown_info",
     "StderrHandler",
     "JsonRpcSafeFormatter",
+    "SecretRedactingFilter",
+    # API keys
+    "get_api_key",
+    "get_oauth_token",
+    "API_KEY_ENV_PREFIX",
+    "OAUTH_TOKEN_ENV_PREFIX",
     # Constants
     "LOGGER_PREFIX",
     "DEFAULT_LOG_FORMAT",
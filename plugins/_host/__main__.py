@@ -19,6 +19,8 @@ Dependencies:
     - D027: shutdown.py (ShutdownHandler, ShutdownReason)
     - D028: isolation.py (IsolatedExecutor)
     - D029: __init__.py (configure_host_logging, _configure_unbuffered_stdout)
+    - sandbox.py (restrictions for sandboxed hosts)
+    - heartbeat.py (periodic `$/heartbeat` notifications)
 
 Usage:
     python -m plugins._host [--plugins-dir ./plugins] [--config-dir ./config] [--log-level INFO]
@@ -75,6 +77,7 @@ except Exception:
 import argparse
 import asyncio
 import json
+import threading
 from datetime import datetime
 from pathlib import Path
 from typing import Any
@@ -92,12 +95,15 @@ from . import (
 )
 
 # Import core components
+from .heartbeat import Heartbeat
 from .isolation import IsolatedExecutor, set_executor
+from .host_rpc import get_client
 from .manager import PluginManager, set_manager
 from .protocol import (
     ErrorCodes,
     JsonRpcRouter,
 )
+from .sandbox import install_from_env as install_sandbox_from_env
 from .shutdown import (
     ShutdownHandler,
     ShutdownReason,
@@ -110,6 +116,9 @@ from .shutdown import (
 
 logger = get_logger("main")
 
+# Serializes writes to stdout; heartbeats are sent from their own thread
+_stdout_lock = threading.Lock()
+
 
 # ============================================
 # JSON-RPC I/O FUNCTIONS
@@ -125,13 +134,14 @@ def send_response(response: dict[str, Any]) -> None:
 
     Note:
         Uses compact JSON (no whitespace) and explicit flush
-        for reliable IPC communication.
+        for reliable IPC communication. Safe to call from any thread.
     """
     try:
         line = json.dumps(response, ensure_ascii=False, separators=(",", ":"))
-        sys.stdout.write(line)
-        sys.stdout.write("\n")
-        sys.stdout.flush()
+        with _stdout_lock:
+            sys.stdout.write(line)
+            sys.stdout.write("\n")
+            sys.stdout.flush()
     except Exception as e:
         logger.error(f"Failed to send response: {e}")
 
@@ -241,6 +251,29 @@ async def run_read_loop(router: JsonRpcRouter, shutdown_handler: ShutdownHandler
 
     request_count = 0
 
+    # Requests run as tasks so the loop keeps reading while a plugin waits
+    # on a host request (see host_rpc.py)
+    host_rpc = get_client()
+    host_rpc.attach(send_response)
+    tasks: set[asyncio.Task[None]] = set()
+
+    async def handle(request: Any) -> None:
+        try:
+            response = await router.handle_request(request)
+
+            # Send response (skip for notifications)
+            if response and not request.is_notification:
+                send_response(response.to_dict())
+
+        except Exception as e:
+            logger.exception(f"Error handling request: {e}")
+            if not request.is_notification:
+                send_error(request.id, ErrorCodes.INTERNAL_ERROR, f"Internal error: {type(e).__name__}: {str(e)}")
+        finally:
+            # Complete request tracking
+            if request.id is not None:
+                shutdown_handler.request_completed(request.id)
+
     while not shutdown_handler.is_shutdown_requested():
         try:
             # Read a line from stdin with timeout
@@ -276,6 +309,10 @@ async def run_read_loop(router: JsonRpcRouter, shutdown_handler: ShutdownHandler
                 send_error(None, ErrorCodes.PARSE_ERROR, f"Parse error: {e}")
                 continue
 
+            # Responses to our own requests
+            if host_rpc.route_response(data):
+                continue
+
             # Parse as JSON-RPC request
             request, error_response = router.parse_request(line)
 
@@ -292,31 +329,23 @@ async def run_read_loop(router: JsonRpcRouter, shutdown_handler: ShutdownHandler
             if request.id is not None:
                 shutdown_handler.request_started(request.id)
 
-            try:
-                # Check for shutdown method (handle specially)
-                if request.method == "shutdown":
+            # Check for shutdown method (handle specially)
+            if request.method == "shutdown":
+                try:
                     result = await handle_shutdown_method(
                         request.params if isinstance(request.params, dict) else {}, request.id, shutdown_handler
                     )
                     if not request.is_notification:
                         send_result(request.id, result)
-                    break  # Exit loop after shutdown
-
-                # Route to handler
-                response = await router.handle_request(request)
-
-                # Send response (skip for notifications)
-                if response and not request.is_notification:
-                    send_response(response.to_dict())
+                finally:
+                    if request.id is not None:
+                        shutdown_handler.request_completed(request.id)
+                break  # Exit loop after shutdown
 
-            except Exception as e:
-                logger.exception(f"Error handling request: {e}")
-                if not request.is_notification:
-                    send_error(request.id, ErrorCodes.INTERNAL_ERROR, f"Internal error: {type(e).__name__}: {str(e)}")
-            finally:
-                # Complete request tracking
-                if request.id is not None:
-                    shutdown_handler.request_completed(request.id)
+            # Route to handler
+            task = asyncio.create_task(handle(request))
+            tasks.add(task)
+            task.add_done_callback(tasks.discard)
 
         except asyncio.CancelledError:
             logger.info("Read loop cancelled")
@@ -326,6 +355,10 @@ async def run_read_loop(router: JsonRpcRouter, shutdown_handler: ShutdownHandler
             logger.exception(f"Unexpected error in read loop: {e}")
             send_error(None, ErrorCodes.INTERNAL_ERROR, f"Read loop error: {e}")
 
+    host_rpc.detach()
+    if tasks:
+        await asyncio.gather(*tasks, return_exceptions=True)
+
     logger.info(f"Read loop ended after {request_count} requests")
 
 
@@ -390,6 +423,10 @@ def run_sync_read_loop(
                 send_error(None, ErrorCodes.PARSE_ERROR, f"Parse error: {e}")
                 continue
 
+            # Late responses to host requests (reverse calls are disabled here)
+            if get_client().route_response(data):
+                continue
+
             # Parse as JSON-RPC request
             request, error_response = router.parse_request(line)
 
@@ -482,6 +519,9 @@ async def async_main(args: argparse.Namespace) -> int:
     configure_host_logging(level=args.log_level)
     log_startup_info(logger)
 
+    if install_sandbox_from_env():
+        logger.info("Sandboxed: network access and writes to plugin directories are blocked")
+
     logger.info(f"Plugins directory: {args.plugins_dir}")
     logger.info(f"Config directory: {args.config_dir}")
 
@@ -535,6 +575,8 @@ async def async_main(args: argparse.Namespace) -> int:
 
     # Run read loop
     exit_code = 0
+    heartbeat = Heartbeat.from_env(send_response, manager, shutdown_handler)
+    heartbeat.start()
 
     try:
         if args.sync_mode:
@@ -549,6 +591,8 @@ async def async_main(args: argparse.Namespace) -> int:
     except Exception as e:
         logger.exception(f"Fatal error: {e}")
         exit_code = 1
+    finally:
+        heartbeat.stop()
 
     # Perform graceful shutdown
     if shutdown_handler.is_shutdown_requested():
@@ -579,6 +623,9 @@ def sync_main(args: argparse.Namespace) -> int:
     configure_host_logging(level=args.log_level)
     log_startup_info(logger)
 
+    if install_sandbox_from_env():
+        logger.info("Sandboxed: network access and writes to plugin directories are blocked")
+
     logger.info("Running in SYNCHRONOUS mode (Windows-compatible)")
     logger.info(f"Plugins directory: {args.plugins_dir}")
     logger.info(f"Config directory: {args.config_dir}")
@@ -650,8 +697,14 @@ def sync_main(args: argparse.Namespace) -> int:
                 except Exception as e:
                     logger.warning(f"Failed to auto-load {plugin.name}: {e}")
 
-        # Run synchronous read loop with the event loop
-        run_sync_read_loop(router, shutdown_handler, event_loop=loop)
+        # Run synchronous read loop with the event loop; heartbeats keep
+        # going while a plugin call blocks it
+        heartbeat = Heartbeat.from_env(send_response, manager, shutdown_handler)
+        heartbeat.start()
+        try:
+            run_sync_read_loop(router, shutdown_handler, event_loop=loop)
+        finally:
+            heartbeat.stop()
 
         # Perform graceful shutdown
         if shutdown_handler.is_shutdown_requested():
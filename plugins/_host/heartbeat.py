@@ -0,0 +1,148 @@
+"""
+plugins/_host/heartbeat.py
+==========================
+Heartbeat notifications from the plugin host to the Tauri backend.
+
+The backend pings the host every few seconds, but a ping waits behind the
+requests the host is working on, so a host busy with a long plugin call
+looks the same as a hung one. The heartbeat runs on its own thread and
+sends a notification at a fixed interval regardless of what the read loop
+is doing:
+
+    {"jsonrpc": "2.0", "method": "$/heartbeat",
+     "params": {"seq": 12, "timestamp": 1718000000.5,
+                "pending_requests": 1, "loaded_plugins": ["stt_whisper"]}}
+
+The backend marks the host degraded after a few missed heartbeats, and
+does not count a failed ping while the latest heartbeat reports pending
+requests (src-tauri/src/ipc/health.rs).
+
+Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+
+Configuration:
+    The backend sets APP_FACTORY_HEARTBEAT_SECS to the interval in seconds
+    (setting ipc.heartbeat_interval_secs). Unset or 0 disables heartbeats.
+
+Usage:
+    >>> heartbeat = Heartbeat.from_env(send_response, manager, shutdown_handler)
+    >>> heartbeat.start()
+    >>> ...
+    >>> heartbeat.stop()
+"""
+
+import logging
+import os
+import threading
+import time
+from collections.abc import Callable
+from typing import Any
+
+# ============================================
+# CONSTANTS
+# ============================================
+
+# Notification method of heartbeats
+HEARTBEAT_METHOD = "$/heartbeat"
+
+# Environment variable with the interval in seconds (0 disables)
+HEARTBEAT_ENV = "APP_FACTORY_HEARTBEAT_SECS"
+
+logger = logging.getLogger(__name__)
+
+
+# ============================================
+# HEARTBEAT
+# ============================================
+
+
+class Heartbeat:
+    """Sends `$/heartbeat` notifications from a daemon thread."""
+
+    def __init__(
+        self,
+        send: Callable[[dict[str, Any]], None],
+        interval: float,
+        pending_requests: Callable[[], int],
+        loaded_plugins: Callable[[], list[str]],
+    ) -> None:
+        """
+        Args:
+            send: Writes a JSON-RPC message to stdout (must be thread-safe)
+            interval: Seconds between heartbeats (0 or less disables them)
+            pending_requests: Counts the requests being processed
+            loaded_plugins: Lists the names of the loaded plugins
+        """
+        self._send = send
+        self.interval = interval
+        self._pending_requests = pending_requests
+        self._loaded_plugins = loaded_plugins
+        self._seq = 0
+        self._stop = threading.Event()
+        self._thread: threading.Thread | None = None
+
+    @classmethod
+    def from_env(cls, send: Callable[[dict[str, Any]], None], manager: Any, shutdown_handler: Any) -> "Heartbeat":
+        """
+        Create a heartbeat with the interval from APP_FACTORY_HEARTBEAT_SECS.
+
+        Args:
+            send: Writes a JSON-RPC message to stdout
+            manager: PluginManager reporting the loaded plugins
+            shutdown_handler: ShutdownHandler tracking in-flight requests
+        """
+        try:
+            interval = float(os.environ.get(HEARTBEAT_ENV, "0"))
+        except ValueError:
+            logger.warning(f"Invalid {HEARTBEAT_ENV}, heartbeats disabled")
+            interval = 0.0
+        return cls(
+            send,
+            interval,
+            pending_requests=lambda: shutdown_handler.get_state().in_flight_count,
+            loaded_plugins=lambda: sorted(manager.plugin_names),
+        )
+
+    @property
+    def enabled(self) -> bool:
+        """Whether heartbeats are sent."""
+        return self.interval > 0
+
+    def beat(self) -> None:
+        """Send one heartbeat now."""
+        self._seq += 1
+        try:
+            params = {
+                "seq": self._seq,
+                "timestamp": time.time(),
+                "pending_requests": self._pending_requests(),
+                "loaded_plugins": self._loaded_plugins(),
+            }
+        except Exception as e:
+            # Plugin state changing under us; the next beat catches up
+            logger.debug(f"Heartbeat state unavailable: {e}")
+            params = {"seq": self._seq, "timestamp": time.time()}
+        self._send({"jsonrpc": "2.0", "method": HEARTBEAT_METHOD, "params": params})
+
+    def start(self) -> None:
+        """Start sending heartbeats (no-op if disabled or running)."""
+        if not self.enabled or self._thread is not None:
+            return
+        self._stop.clear()
+        self._thread = threading.Thread(target=self._run, name="heartbeat", daemon=True)
+        self._thread.start()
+        logger.info(f"Sending heartbeats every {self.interval:g}s")
+
+    def stop(self) -> None:
+        """Stop sending heartbeats."""
+        self._stop.set()
+        if self._thread is not None:
+            self._thread.join(timeout=self.interval + 1)
+            self._thread = None
+
+    def _run(self) -> None:
+        # The first beat tells the backend heartbeats are coming
+        while True:
+            self.beat()
+            if self._stop.wait(self.interval):
+                break
@@ -0,0 +1,508 @@
+"""
+plugins/_host/host_rpc.py
+=========================
+Requests from the plugin host to the Tauri backend (reverse RPC).
+
+The host normally only answers requests. A few operations need the app,
+for example asking the user to grant a permission, so the host can also
+send JSON-RPC requests over stdout. Ids are strings prefixed with "host-"
+so they never collide with the backend's numeric ids. The read loop hands
+every response it receives to `route_response`.
+
+Reverse calls need the read loop to keep reading while a plugin method is
+running, which the async read loop does. In the synchronous read loop
+(Windows default) `call` raises, and `request_permission` only reports the
+permissions granted when the plugin was loaded.
+
+Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+
+Permissions:
+    Plugins declare what they need in manifest.json:
+
+        "permissions": ["network", "filesystem"]
+
+    The backend asks the user before loading the plugin and passes the
+    granted permissions with plugin/load. A plugin can check (or ask for) a
+    declared permission at runtime:
+
+        >>> from plugins._host.host_rpc import request_permission
+        >>> if not await request_permission("microphone"):
+        ...     raise PermissionError("Microphone access was denied")
+
+Callbacks:
+    Plugins can also ask the app for things it manages. The method
+    namespace picks the handler on the Rust side (src-tauri/src/reverse_rpc.rs),
+    and namespaces other than artifacts need a declared, granted permission:
+
+        >>> from plugins._host.host_rpc import get_secret, http_fetch
+        >>> api_key = await get_secret("openai")                     # "secrets"
+        >>> response = await http_fetch("https://example.com/model")  # "network"
+
+Progress:
+    A plugin method can report progress of the request it is serving. The
+    host sends a `progress/{request id}` notification, which the backend
+    forwards to the caller:
+
+        {"jsonrpc": "2.0", "method": "progress/42",
+         "params": {"percentage": 40.0, "message": "Loading weights", "stage": "load"}}
+
+        >>> from plugins._host.host_rpc import report_progress
+        >>> report_progress(40, "Loading weights", stage="load")
+
+Data:
+    Each plugin has its own data directory in the app data dir for caches,
+    weights, and scratch files (src-tauri/src/plugin_data.rs). The backend
+    passes it with plugin/load; write there instead of the plugin folder:
+
+        >>> from plugins._host.host_rpc import plugin_data_dir
+        >>> cache = plugin_data_dir() / "cache"
+
+Events:
+    The backend sends `events/set_topics` with the topics the app listens
+    to. `emit_event` sends a notification whose method is the topic, and
+    skips it when nobody listens:
+
+        >>> from plugins._host.host_rpc import emit_event
+        >>> emit_event("stt/partial", {"text": "hello"})
+
+Offline:
+    When the user switches the app to offline mode, the backend refuses
+    requests to other machines (`http_fetch` fails with OFFLINE). Hosts
+    start with APP_FACTORY_OFFLINE=1 and get `host/set_offline` when the
+    mode changes. Plugins that reach the network themselves should check
+    it and fall back to local models or cached data instead of timing out:
+
+        >>> from plugins._host.host_rpc import is_offline
+        >>> if is_offline() and not weights_path.exists():
+        ...     raise RuntimeError("Model weights are not downloaded and the app is offline")
+"""
+
+import asyncio
+import contextvars
+import itertools
+import logging
+import os
+import tempfile
+from collections.abc import Callable
+from pathlib import Path
+from typing import Any
+
+logger = logging.getLogger(__name__)
+
+# ============================================
+# CONSTANTS
+# ============================================
+
+# Method the backend answers with {"granted": bool}
+PERMISSION_REQUEST_METHOD = "host/request_permission"
+
+# Callback methods answered by the backend (see reverse_rpc.rs)
+SECRET_GET_METHOD = "secrets/get"
+ARTIFACT_GET_METHOD = "artifacts/get"
+DIALOG_OPEN_METHOD = "dialog/open"
+DIALOG_SAVE_METHOD = "dialog/save"
+HTTP_FETCH_METHOD = "http/fetch"
+
+# Permission categories a manifest may declare
+PERMISSIONS = ("filesystem", "network", "subprocess", "microphone", "secrets")
+
+# Seconds to wait for a reply (the user may be looking at a prompt)
+DEFAULT_CALL_TIMEOUT = 300.0
+
+# Notification method prefix for progress; the request id follows it
+PROGRESS_METHOD_PREFIX = "progress/"
+
+# Method the backend sends with {"topics": [...]} when listeners change
+EVENT_TOPICS_METHOD = "events/set_topics"
+
+# Method the backend sends with {"offline": bool} when offline mode changes
+OFFLINE_METHOD = "host/set_offline"
+
+# Environment variable set to "1" when the host starts in offline mode
+OFFLINE_ENV = "APP_FACTORY_OFFLINE"
+
+# Parent of the data directories used when the backend passed none
+FALLBACK_DATA_DIR = Path(tempfile.gettempdir()) / "app_factory_plugin_data"
+
+# Plugin whose code is currently running (set around plugin calls)
+current_plugin: contextvars.ContextVar[str | None] = contextvars.ContextVar("current_plugin", default=None)
+
+# Id of the request the running plugin code is serving (set around plugin calls)
+current_request: contextvars.ContextVar[str | int | None] = contextvars.ContextVar("current_request", default=None)
+
+
+# ============================================
+# CLIENT
+# ============================================
+
+
+class HostRpcError(RuntimeError):
+    """The backend answered a host request with an error."""
+
+
+class HostRpcClient:
+    """Sends requests to the backend and matches their responses."""
+
+    def __init__(self) -> None:
+        self._send: Callable[[dict[str, Any]], None] | None = None
+        self._pending: dict[str, asyncio.Future[Any]] = {}
+        self._ids = itertools.count(1)
+        self._granted: dict[str, set[str]] = {}
+        self._data_dirs: dict[str, Path] = {}
+        self._topics: set[str] = set()
+        self._offline = os.environ.get(OFFLINE_ENV) == "1"
+
+    @property
+    def available(self) -> bool:
+        """Whether a concurrent read loop can deliver responses."""
+        return self._send is not None
+
+    def attach(self, send: Callable[[dict[str, Any]], None]) -> None:
+        """Enable reverse calls, writing messages with `send`."""
+        self._send = send
+
+    def detach(self) -> None:
+        """Disable reverse calls and fail the ones still waiting."""
+        self._send = None
+        for future in self._pending.values():
+            if not future.done():
+                future.set_exception(HostRpcError("Read loop stopped"))
+        self._pending.clear()
+
+    async def call(self, method: str, params: dict[str, Any], timeout: float = DEFAULT_CALL_TIMEOUT) -> Any:
+        """
+        Send a request to the backend and wait for the result.
+
+        Raises:
+            RuntimeError: Reverse calls are not available in this read loop
+            HostRpcError: The backend returned an error
+            TimeoutError: No reply within `timeout` seconds
+        """
+        if self._send is None:
+            raise RuntimeError("Host requests need the async read loop")
+
+        request_id = f"host-{next(self._ids)}"
+        future: asyncio.Future[Any] = asyncio.get_running_loop().create_future()
+        self._pending[request_id] = future
+        try:
+            self._send({"jsonrpc": "2.0", "id": request_id, "method": method, "params": params})
+            return await asyncio.wait_for(future, timeout=timeout)
+        finally:
+            self._pending.pop(request_id, None)
+
+    def notify(self, method: str, params: dict[str, Any]) -> bool:
+        """
+        Send a notification to the backend (no reply).
+
+        Returns:
+            False if no read loop is attached to write it
+        """
+        if self._send is None:
+            return False
+        self._send({"jsonrpc": "2.0", "method": method, "params": params})
+        return True
+
+    def route_response(self, message: Any) -> bool:
+        """
+        Deliver a response to a pending host request.
+
+        Returns:
+            True if the message was a response to a host request
+        """
+        if not isinstance(message, dict) or "method" in message:
+            return False
+        request_id = message.get("id")
+        if not isinstance(request_id, str) or not request_id.startswith("host-"):
+            return False
+
+        future = self._pending.get(request_id)
+        if future is None or future.done():
+            logger.warning(f"Response for unknown host request: {request_id}")
+            return True
+
+        error = message.get("error")
+        if error:
+            future.set_exception(HostRpcError(error.get("message", "Host request failed")))
+        else:
+            future.set_result(message.get("result"))
+        return True
+
+    def set_granted(self, plugin: str, permissions: list[str]) -> None:
+        """Record the permissions granted when a plugin was loaded."""
+        self._granted[plugin] = set(permissions)
+
+    def grant(self, plugin: str, permission: str) -> None:
+        """Record a permission granted at runtime."""
+        self._granted.setdefault(plugin, set()).add(permission)
+
+    def is_granted(self, plugin: str, permission: str) -> bool:
+        """Whether a permission was granted to a plugin."""
+        return permission in self._granted.get(plugin, set())
+
+    def set_data_dir(self, plugin: str, path: str | None) -> None:
+        """Record the data directory the backend provisioned for a plugin."""
+        if path:
+            self._data_dirs[plugin] = Path(path)
+        else:
+            self._data_dirs.pop(plugin, None)
+
+    def data_dir(self, plugin: str) -> Path | None:
+        """Data directory of a plugin, if the backend passed one."""
+        return self._data_dirs.get(plugin)
+
+    def set_topics(self, topics: list[str]) -> None:
+        """Replace the event topics the app listens to."""
+        self._topics = set(topics)
+
+    def has_listeners(self, topic: str) -> bool:
+        """Whether the app listens to an event topic."""
+        return topic in self._topics
+
+    def set_offline(self, offline: bool) -> None:
+        """Record whether the app is in offline mode."""
+        self._offline = offline
+
+    @property
+    def offline(self) -> bool:
+        """Whether the app is in offline mode."""
+        return self._offline
+
+
+_client = HostRpcClient()
+
+
+def get_client() -> HostRpcClient:
+    """Get the process-wide client."""
+    return _client
+
+
+# ============================================
+# PERMISSIONS
+# ============================================
+
+
+async def request_permission(permission: str) -> bool:
+    """
+    Check or ask for a permission for the calling plugin.
+
+    Permissions granted at load time are answered locally. Otherwise the
+    backend prompts the user for declared permissions (and denies
+    undeclared ones), remembering the answer.
+
+    Args:
+        permission: One of PERMISSIONS
+
+    Returns:
+        True if the plugin may use the capability
+
+    Raises:
+        ValueError: Unknown permission
+        RuntimeError: Called outside plugin code
+    """
+    if permission not in PERMISSIONS:
+        raise ValueError(f"Unknown permission: {permission}")
+    plugin = current_plugin.get()
+    if plugin is None:
+        raise RuntimeError("request_permission must be called from plugin code")
+
+    if _client.is_granted(plugin, permission):
+        return True
+    if not _client.available:
+        logger.warning(f"{plugin} requested {permission}, which was not granted at load time")
+        return False
+
+    result = await _client.call(PERMISSION_REQUEST_METHOD, {"plugin": plugin, "permission": permission})
+    granted = bool(result and result.get("granted"))
+    if granted:
+        _client.grant(plugin, permission)
+    return granted
+
+
+# ============================================
+# DATA
+# ============================================
+
+
+def plugin_data_dir() -> Path:
+    """
+    Get the calling plugin's data directory, creating it if needed.
+
+    Without a backend (tests, the plugin test runner) a folder in the
+    system temp directory is used.
+
+    Returns:
+        Directory the plugin may write to
+
+    Raises:
+        RuntimeError: Called outside plugin code
+    """
+    plugin = current_plugin.get()
+    if plugin is None:
+        raise RuntimeError("plugin_data_dir must be called from plugin code")
+    path = _client.data_dir(plugin) or FALLBACK_DATA_DIR / plugin
+    path.mkdir(parents=True, exist_ok=True)
+    return path
+
+
+# ============================================
+# CALLBACKS
+# ============================================
+
+
+async def _plugin_call(method: str, params: dict[str, Any]) -> Any:
+    """Send a callback on behalf of the calling plugin."""
+    plugin = current_plugin.get()
+    if plugin is None:
+        raise RuntimeError(f"{method} must be called from plugin code")
+    return await _client.call(method, {"plugin": plugin, **params})
+
+
+async def get_secret(service: str, profile: str | None = None) -> str | None:
+    """
+    Read the active API key of a service (needs the "secrets" permission).
+
+    Returns:
+        The key, or None if the service has no active key
+    """
+    result = await _plugin_call(SECRET_GET_METHOD, {"service": service, "profile": profile})
+    value = result.get("value") if result else None
+    return str(value) if value is not None else None
+
+
+async def get_artifact(hash: str | None = None, key: str | None = None) -> dict[str, Any] | None:
+    """
+    Look up a cached artifact by content hash or cache key.
+
+    Returns:
+        Artifact metadata (including its `path`), or None if not cached
+    """
+    if hash is None and key is None:
+        raise ValueError("Pass hash or key")
+    meta: dict[str, Any] | None = await _plugin_call(ARTIFACT_GET_METHOD, {"hash": hash, "key": key})
+    return meta
+
+
+async def open_file_dialog(
+    title: str | None = None,
+    filters: list[dict[str, Any]] | None = None,
+    directory: bool = False,
+    multiple: bool = False,
+) -> list[str]:
+    """
+    Ask the user to pick files or a folder (needs the "filesystem" permission).
+
+    Args:
+        filters: [{"name": "Audio", "extensions": ["wav", "mp3"]}]
+
+    Returns:
+        The chosen paths (empty if cancelled)
+    """
+    params = {"title": title, "filters": filters or [], "directory": directory, "multiple": multiple}
+    result = await _plugin_call(DIALOG_OPEN_METHOD, params)
+    return [str(path) for path in result.get("paths", [])]
+
+
+async def save_file_dialog(
+    title: str | None = None,
+    filters: list[dict[str, Any]] | None = None,
+    file_name: str | None = None,
+) -> str | None:
+    """
+    Ask the user where to save a file (needs the "filesystem" permission).
+
+    Returns:
+        The chosen path, or None if cancelled
+    """
+    params = {"title": title, "filters": filters or [], "file_name": file_name}
+    result = await _plugin_call(DIALOG_SAVE_METHOD, params)
+    paths = result.get("paths", [])
+    return str(paths[0]) if paths else None
+
+
+async def http_fetch(
+    url: str,
+    method: str = "GET",
+    headers: dict[str, str] | None = None,
+    body: str | None = None,
+    timeout: float | None = None,
+) -> dict[str, Any]:
+    """
+    Make an HTTP request through the app (needs the "network" permission).
+
+    Returns:
+        {"status": int, "headers": {...}, "body": str}
+    """
+    params: dict[str, Any] = {"url": url, "method": method, "headers": headers or {}, "body": body}
+    if timeout is not None:
+        params["timeout_secs"] = max(1, int(timeout))
+    response: dict[str, Any] = await _plugin_call(HTTP_FETCH_METHOD, params)
+    return response
+
+
+# ============================================
+# PROGRESS
+# ============================================
+
+
+def report_progress(percentage: float, message: str | None = None, stage: str | None = None) -> bool:
+    """
+    Report progress of the request the calling plugin code is serving.
+
+    Args:
+        percentage: Completion from 0 to 100 (clamped)
+        message: What is happening now
+        stage: Named phase, e.g. "download" or "load"
+
+    Returns:
+        True if the notification was sent (False outside a request or in
+        the synchronous read loop)
+    """
+    request_id = current_request.get()
+    if request_id is None:
+        logger.debug("report_progress called outside a request")
+        return False
+
+    params: dict[str, Any] = {"percentage": min(max(float(percentage), 0.0), 100.0)}
+    if message is not None:
+        params["message"] = message
+    if stage is not None:
+        params["stage"] = stage
+    return _client.notify(f"{PROGRESS_METHOD_PREFIX}{request_id}", params)
+
+
+# ============================================
+# EVENTS
+# ============================================
+
+
+def emit_event(topic: str, params: dict[str, Any]) -> bool:
+    """
+    Send an event to the app if it listens to the topic.
+
+    Args:
+        topic: Event topic, used as the notification method
+        params: Event payload
+
+    Returns:
+        True if the event was sent (False without listeners or in the
+        synchronous read loop)
+    """
+    if not _client.has_listeners(topic):
+        return False
+    return _client.notify(topic, params)
+
+
+# ============================================
+# OFFLINE MODE
+# ============================================
+
+
+def is_offline() -> bool:
+    """
+    Whether the app is in offline mode.
+
+    Requests to other machines fail while it is on, so plugins should skip
+    downloads and remote APIs and use what is available locally.
+    """
+    return _client.offline
@@ -6,6 +6,16 @@ Plugin lifecycle manager with hot-swap support.
 Manages plugin lifecycle: load, unload, hot-swap, and health-check.
 Acts as the central coordinator between discovery, validation, and loading.
 
+State migration (driven by the app during plugin_swap / plugin_update):
+    Plugins may implement three optional hooks, sync or async:
+        - capture_state() -> dict          : state to carry over
+        - migrate_state(old_version, new_version, state) : adopt the state of
+          an older (or different) plugin; raise to reject it
+        - restore_state(state)             : take back its own state after a
+          failed upgrade was rolled back
+    `export_state`, `migrate_state` and `restore_state` below call them;
+    plugins without a hook are skipped.
+
 Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
 Protocol: JSON-RPC 2.0 over stdin/stdout
 
@@ -17,6 +27,7 @@ Dependencies:
 """
 
 import asyncio
+import inspect
 import logging
 import time
 from collections.abc import Callable
@@ -408,6 +419,54 @@ class PluginManager:
         # Load fresh
         return await self.load_plugin(name, config, auto_initialize=True)
 
+    def _require_loaded(self, name: str) -> LoadedPlugin:
+        loaded = self._plugins.get(name)
+        if not loaded:
+            raise RuntimeError(f"Plugin not loaded: {name}")
+        return loaded
+
+    @staticmethod
+    async def _call_hook(instance: PluginBase, hook: str, *args: Any) -> tuple[bool, Any]:
+        """Call an optional plugin hook; (False, None) if the plugin has none."""
+        method = getattr(instance, hook, None)
+        if not callable(method):
+            return False, None
+        result = method(*args)
+        if inspect.isawaitable(result):
+            result = await result
+        return True, result
+
+    async def export_state(self, name: str) -> dict[str, Any]:
+        """
+        Capture a loaded plugin's state for migration.
+
+        Returns:
+            {"plugin", "version", "state"}; state is None without a capture_state hook
+        """
+        loaded = self._require_loaded(name)
+        _, state = await self._call_hook(loaded.instance, "capture_state")
+        return {"plugin": name, "version": loaded.manifest.version, "state": state}
+
+    async def migrate_state(self, name: str, old_version: str | None, state: Any) -> dict[str, Any]:
+        """
+        Hand exported state to a newly loaded plugin.
+
+        Raises:
+            Whatever the plugin's migrate_state raises (the app rolls back)
+        """
+        loaded = self._require_loaded(name)
+        new_version = loaded.manifest.version
+        migrated, _ = await self._call_hook(loaded.instance, "migrate_state", old_version, new_version, state)
+        if migrated:
+            logger.info(f"Migrated state of {name}: {old_version} -> {new_version}")
+        return {"plugin": name, "old_version": old_version, "new_version": new_version, "migrated": migrated}
+
+    async def restore_state(self, name: str, state: Any) -> dict[str, Any]:
+        """Give a plugin back the state it exported (after a rollback)."""
+        loaded = self._require_loaded(name)
+        restored, _ = await self._call_hook(loaded.instance, "restore_state", state)
+        return {"plugin": name, "restored": restored}
+
     async def hot_swap(self, old_name: str, new_name: str, new_config: dict[str, Any] | None = None) -> HotSwapResult:
         """
         Hot-swap one plugin for another with rollback support.
@@ -24,6 +24,9 @@ Supported Methods:
         - plugin/unload    : Unload a plugin
         - plugin/swap      : Hot-swap one plugin for another
         - plugin/health    : Health check a plugin
+        - plugin/export_state  : Capture a plugin's state for migration
+        - plugin/migrate_state : Hand exported state to an upgraded plugin
+        - plugin/restore_state : Give a plugin back its state after a rollback
 
     Plugin Execution:
         - <contract>/<method>  : Route to plugin method
@@ -34,6 +37,7 @@ Supported Methods:
         - ping             : Health check (returns "pong")
         - shutdown         : Initiate graceful shutdown
         - status           : Get host status
+        - rpc/schemas      : JSON Schemas of method params/results (validated by the app)
 """
 
 import asyncio
@@ -44,6 +48,8 @@ from dataclasses import dataclass, field
 from datetime import datetime
 from typing import Any, Optional
 
+from .host_rpc import EVENT_TOPICS_METHOD, OFFLINE_METHOD, current_plugin, current_request, get_client
+
 logger = logging.getLogger(__name__)
 
 
@@ -229,6 +235,23 @@ class ErrorCodes:
     RESOURCE_EXHAUSTED = -32050
     DEPENDENCY_MISSING = -32051
     MODEL_NOT_FOUND = -32052
+    EXECUTION_TIMEOUT = -32060
+    PLUGIN_EXCEPTION = -32061
+
+
+class PluginExecutionError(RuntimeError):
+    """
+    A plugin method failed inside the isolated executor.
+
+    Reported with the executor's error code (e.g. PLUGIN_EXCEPTION) so the
+    app can tell plugin faults from host errors and reload a plugin that
+    keeps failing.
+    """
+
+    def __init__(self, message: str, code: int, data: dict[str, Any] | None = None):
+        super().__init__(message)
+        self.code = code
+        self.data = data
 
 
 # ============================================
@@ -238,6 +261,69 @@ class ErrorCodes:
 # Handler signature: (params, request_id) -> result
 MethodHandler = Callable[[dict[str, Any] | None, str | int | None], Coroutine[Any, Any, Any]]
 
+# Reflection method listing the params/result schemas of registered methods
+SCHEMAS_METHOD = "rpc/schemas"
+
+# Schemas of the built-in methods (JSON Schema draft 7)
+_PLUGIN_NAME_SCHEMA: dict[str, Any] = {"type": "string", "minLength": 1}
+_PERMISSIONS_SCHEMA: dict[str, Any] = {"type": "array", "items": {"type": "string"}}
+_BUILTIN_SCHEMAS: dict[str, dict[str, Any]] = {
+    "ping": {"result": {"const": "pong"}},
+    "plugin/list": {"result": {"type": "array"}},
+    "plugin/load": {
+        "params": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+                "name": _PLUGIN_NAME_SCHEMA,
+                "config": {"type": "object"},
+                "permissions": _PERMISSIONS_SCHEMA,
+                "data_dir": {"type": "string"},
+            },
+        }
+    },
+    "plugin/unload": {
+        "params": {"type": "object", "required": ["name"], "properties": {"name": _PLUGIN_NAME_SCHEMA}},
+        "result": {"type": "object", "required": ["success", "plugin"]},
+    },
+    "plugin/swap": {
+        "params": {
+            "type": "object",
+            "required": ["old", "new"],
+            "properties": {
+                "old": _PLUGIN_NAME_SCHEMA,
+                "new": _PLUGIN_NAME_SCHEMA,
+                "config": {"type": "object"},
+                "permissions": _PERMISSIONS_SCHEMA,
+                "data_dir": {"type": "string"},
+            },
+        }
+    },
+    "plugin/health": {"params": {"type": "object", "properties": {"name": _PLUGIN_NAME_SCHEMA}}},
+    "plugin/export_state": {
+        "params": {"type": "object", "required": ["name"], "properties": {"name": _PLUGIN_NAME_SCHEMA}},
+        "result": {"type": "object", "required": ["plugin", "version", "state"]},
+    },
+    "plugin/migrate_state": {
+        "params": {
+            "type": "object",
+            "required": ["name", "state"],
+            "properties": {"name": _PLUGIN_NAME_SCHEMA, "old_version": {"type": ["string", "null"]}},
+        },
+        "result": {"type": "object", "required": ["migrated"]},
+    },
+    "plugin/restore_state": {
+        "params": {"type": "object", "required": ["name", "state"], "properties": {"name": _PLUGIN_NAME_SCHEMA}},
+        "result": {"type": "object", "required": ["restored"]},
+    },
+    EVENT_TOPICS_METHOD: {
+        "params": {
+            "type": "object",
+            "properties": {"topics": {"type": "array", "items": {"type": "string"}}},
+        }
+    },
+}
+
 
 @dataclass
 class MethodRegistration:
@@ -248,6 +334,8 @@ class MethodRegistration:
     requires_plugin: bool = False
     contract: str | None = None
     timeout_seconds: float | None = None
+    params_schema: dict[str, Any] | None = None
+    result_schema: dict[str, Any] | None = None
 
 
 # ============================================
@@ -353,7 +441,14 @@ class JsonRpcRouter:
                 raise ValueError("Missing 'name' parameter")
 
             config = params.get("config", {}) if params else {}
-            loaded = await self.manager.load_plugin(name, config=config)
+            # Permissions the backend granted from the manifest (see host_rpc.py)
+            get_client().set_granted(name, params.get("permissions", []))
+            get_client().set_data_dir(name, params.get("data_dir"))
+            token = current_plugin.set(name)
+            try:
+                loaded = await self.manager.load_plugin(name, config=config)
+            finally:
+                current_plugin.reset(token)
 
             if loaded:
                 return loaded.to_dict()
@@ -390,7 +485,13 @@ class JsonRpcRouter:
                 raise ValueError("Missing 'old' or 'new' parameter")
 
             config = params.get("config", {}) if params else {}
-            result = await self.manager.hot_swap(old_name, new_name, new_config=config)
+            get_client().set_granted(new_name, params.get("permissions", []))
+            get_client().set_data_dir(new_name, params.get("data_dir"))
+            token = current_plugin.set(new_name)
+            try:
+                result = await self.manager.hot_swap(old_name, new_name, new_config=config)
+            finally:
+                current_plugin.reset(token)
 
             return result.to_dict()
 
@@ -430,7 +531,104 @@ class JsonRpcRouter:
             handler=handle_plugin_health, description="Health check plugins"
         )
 
-    def method(self, name: str, description: str = "", timeout: float | None = None):
+        # plugin/export_state, plugin/migrate_state, plugin/restore_state -
+        # state carried across upgrades (see manager.py)
+        def require_name(params):
+            if not self.manager:
+                raise RuntimeError("Plugin manager not available")
+            name = params.get("name") if params else None
+            if not name:
+                raise ValueError("Missing 'name' parameter")
+            return name
+
+        async def handle_plugin_export_state(params, id):
+            return await self.manager.export_state(require_name(params))
+
+        async def handle_plugin_migrate_state(params, id):
+            name = require_name(params)
+            token = current_plugin.set(name)
+            try:
+                return await self.manager.migrate_state(name, params.get("old_version"), params.get("state"))
+            finally:
+                current_plugin.reset(token)
+
+        async def handle_plugin_restore_state(params, id):
+            name = require_name(params)
+            token = current_plugin.set(name)
+            try:
+                return await self.manager.restore_state(name, params.get("state"))
+            finally:
+                current_plugin.reset(token)
+
+        self._methods["plugin/export_state"] = MethodRegistration(
+            handler=handle_plugin_export_state, description="Capture a plugin's state for migration"
+        )
+        self._methods["plugin/migrate_state"] = MethodRegistration(
+            handler=handle_plugin_migrate_state, description="Hand exported state to an upgraded plugin"
+        )
+        self._methods["plugin/restore_state"] = MethodRegistration(
+            handler=handle_plugin_restore_state, description="Give a plugin back its exported state"
+        )
+
+        # events/set_topics - topics the app listens to (see host_rpc.emit_event)
+        async def handle_set_topics(params, id):
+            topics = params.get("topics", []) if params else []
+            get_client().set_topics(topics)
+            return {"topics": sorted(topics)}
+
+        self._methods[EVENT_TOPICS_METHOD] = MethodRegistration(
+            handler=handle_set_topics, description="Set the event topics the app listens to"
+        )
+
+        # host/set_offline - offline mode switched (see host_rpc.is_offline)
+        async def handle_set_offline(params, id):
+            offline = bool(params.get("offline", False)) if params else False
+            get_client().set_offline(offline)
+            logger.info(f"Offline mode {'on' if offline else 'off'}")
+            return {"offline": offline}
+
+        self._methods[OFFLINE_METHOD] = MethodRegistration(
+            handler=handle_set_offline, description="Switch offline mode"
+        )
+
+        # rpc/schemas - params/result schemas the app validates calls against
+        async def handle_schemas(params, id):
+            return self.schemas()
+
+        self._methods[SCHEMAS_METHOD] = MethodRegistration(
+            handler=handle_schemas, description="JSON Schemas of method params and results"
+        )
+
+        for name, schemas in _BUILTIN_SCHEMAS.items():
+            self._methods[name].params_schema = schemas.get("params")
+            self._methods[name].result_schema = schemas.get("result")
+
+    def schemas(self) -> dict[str, dict[str, Any]]:
+        """
+        Get the schemas of the methods that declare any.
+
+        Returns:
+            Mapping of method name to {"params": schema, "result": schema}
+        """
+        result: dict[str, dict[str, Any]] = {}
+        for name, registration in self._methods.items():
+            entry = {
+                key: schema
+                for key, schema in (("params", registration.params_schema), ("result", registration.result_schema))
+                if schema is not None
+            }
+            if entry:
+                result[name] = entry
+        return result
+
+    def method(
+        self,
+        name: str,
+        description: str = "",
+        timeout: float | None = None,
+        params_schema: dict[str, Any] | None = None,
+        result_schema: dict[str, Any] | None = None,
+    ):
         """
         Decorator to register a method handler.
 
@@ -438,6 +636,8 @@ class JsonRpcRouter:
             name: Method name
             description: Method description
             timeout: Timeout in seconds
+            params_schema: JSON Schema of the params (validated by the app)
+            result_schema: JSON Schema of the result (validated by the app)
 
         Usage:
             @router.method("my/method")
@@ -446,7 +646,13 @@ class JsonRpcRouter:
         """
 
         def decorator(handler: MethodHandler) -> MethodHandler:
-            self._methods[name] = MethodRegistration(handler=handler, description=description, timeout_seconds=timeout)
+            self._methods[name] = MethodRegistration(
+                handler=handler,
+                description=description,
+                timeout_seconds=timeout,
+                params_schema=params_schema,
+                result_schema=result_schema,
+            )
             return handler
 
         return decorator
@@ -459,6 +665,8 @@ class JsonRpcRouter:
         requires_plugin: bool = False,
         contract: str | None = None,
         timeout: float | None = None,
+        params_schema: dict[str, Any] | None = None,
+        result_schema: dict[str, Any] | None = None,
     ) -> None:
         """
         Register a method handler.
@@ -470,6 +678,8 @@ class JsonRpcRouter:
             requires_plugin: Whether method requires plugin to be loaded
             contract: Contract type for plugin method routing
             timeout: Timeout in seconds
+            params_schema: JSON Schema of the params (validated by the app)
+            result_schema: JSON Schema of the result (validated by the app)
         """
         self._methods[name] = MethodRegistration(
             handler=handler,
@@ -477,6 +687,8 @@ class JsonRpcRouter:
             requires_plugin=requires_plugin,
             contract=contract,
             timeout_seconds=timeout,
+            params_schema=params_schema,
+            result_schema=result_schema,
         )
         logger.debug(f"Registered method: {name}")
 
@@ -577,24 +789,35 @@ class JsonRpcRouter:
         if not callable(method):
             raise RuntimeError(f"Method not callable: {method_name}")
 
-        # Invoke with isolation if executor available
-        if self.executor:
-            from .isolation import ExecutionResult
-
-            result: ExecutionResult = await self.executor.execute(
-                plugin_name=loaded.name,
-                method=method_name,
-                callable=lambda: method(**(params or {})),
-                call_id=request_id,
-            )
-
-            if result.success:
-                return result.result
+        # Let host_rpc know which plugin is asking and which request it serves
+        token = current_plugin.set(loaded.name)
+        request_token = current_request.set(request_id)
+        try:
+            # Invoke with isolation if executor available
+            if self.executor:
+                from .isolation import ExecutionResult
+
+                result: ExecutionResult = await self.executor.execute(
+                    plugin_name=loaded.name,
+                    method=method_name,
+                    callable=lambda: method(**(params or {})),
+                    call_id=request_id,
+                )
+
+                if result.success:
+                    return result.result
+                else:
+                    raise PluginExecutionError(
+                        result.error_message or "Plugin exception",
+                        result.error_code or ErrorCodes.PLUGIN_EXCEPTION,
+                        result.error_data,
+                    )
             else:
-                raise RuntimeError(result.error_message)
-        else:
-            # Direct call without isolation
-            return await method(**(params or {}))
+                # Direct call without isolation
+                return await method(**(params or {}))
+        finally:
+            current_request.reset(request_token)
+            current_plugin.reset(token)
 
     async def handle_request(self, request: JsonRpcRequest) -> JsonRpcResponse | None:
         """
@@ -656,6 +879,10 @@ class JsonRpcRouter:
             self._error_count += 1
             return JsonRpcResponse.error_response(id=request.id, code=ErrorCodes.INVALID_PARAMS, message=str(e))
 
+        except PluginExecutionError as e:
+            self._error_count += 1
+            return JsonRpcResponse.error_response(id=request.id, code=e.code, message=str(e), data=e.data)
+
         except RuntimeError as e:
             self._error_count += 1
             error_msg = str(e)
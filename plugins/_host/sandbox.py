@@ -0,0 +1,155 @@
+"""
+plugins/_host/sandbox.py
+========================
+In-process restrictions for plugin hosts started with the sandboxed
+isolation profile.
+
+The backend starts an untrusted plugin in its own host with a scrubbed
+environment and, where the OS allows it, without network access and with
+the plugin directory mounted read-only (see src-tauri/src/ipc/sandbox.rs).
+It also sets APP_FACTORY_SANDBOX=1 so the host blocks the same things from
+inside Python, which is all the isolation there is on platforms without an
+OS-level sandbox:
+
+    - connecting or binding sockets to anything but loopback and Unix sockets
+    - resolving host names
+    - opening files for writing, creating, deleting or renaming files under
+      the directories listed in APP_FACTORY_SANDBOX_READ_ONLY
+
+Blocked operations raise PermissionError. The hook is installed with
+sys.addaudithook and cannot be removed; it does not follow child processes.
+Plugins that need the network can still go through the app (http_fetch in
+host_rpc.py), which asks the user for the "network" permission.
+
+Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+
+Usage:
+    >>> from plugins._host.sandbox import install_from_env
+    >>> if install_from_env():
+    ...     logger.info("Sandboxed")
+"""
+
+import ipaddress
+import os
+import sys
+from pathlib import Path
+from typing import Any
+
+# ============================================
+# CONSTANTS
+# ============================================
+
+# Set to "1" by the backend for sandboxed hosts
+SANDBOX_ENV = "APP_FACTORY_SANDBOX"
+
+# Directories (os.pathsep-separated) the host must not write to
+READ_ONLY_ENV = "APP_FACTORY_SANDBOX_READ_ONLY"
+
+# Audit events carrying a socket address as their second argument
+_SOCKET_EVENTS = frozenset({"socket.connect", "socket.bind", "socket.sendto"})
+
+# Audit events resolving names
+_RESOLVE_EVENTS = frozenset({"socket.getaddrinfo", "socket.gethostbyname", "socket.gethostbyaddr"})
+
+# Audit events modifying the paths in their arguments
+_PATH_EVENTS = frozenset({"os.remove", "os.rename", "os.rmdir", "os.mkdir", "os.truncate", "os.chmod", "os.link"})
+
+# open() flags that write
+_WRITE_FLAGS = os.O_WRONLY | os.O_RDWR | os.O_APPEND | os.O_CREAT | os.O_TRUNC
+
+_installed = False
+
+
+# ============================================
+# HELPERS
+# ============================================
+
+
+def _is_local_address(address: Any) -> bool:
+    """Whether a socket address is a Unix socket or on the loopback interface."""
+    if not isinstance(address, tuple):
+        # AF_UNIX paths (str/bytes) and other non-IP families
+        return True
+    host = address[0] if address else None
+    if isinstance(host, bytes):
+        host = host.decode(errors="replace")
+    if host in (None, "", "localhost"):
+        return True
+    try:
+        return ipaddress.ip_address(str(host).split("%")[0]).is_loopback
+    except ValueError:
+        return False
+
+
+def _is_write(mode: Any, flags: Any) -> bool:
+    """Whether an open() audit event opens for writing."""
+    if isinstance(mode, str) and any(c in mode for c in "wax+"):
+        return True
+    return isinstance(flags, int) and flags & _WRITE_FLAGS != 0
+
+
+def _within(path: Any, roots: tuple[Path, ...]) -> bool:
+    """Whether a path argument lies under one of the roots."""
+    if isinstance(path, int) or path is None:
+        return False
+    try:
+        resolved = Path(os.fsdecode(path)).resolve()
+    except (TypeError, ValueError, OSError):
+        return False
+    return any(resolved == root or root in resolved.parents for root in roots)
+
+
+# ============================================
+# INSTALLATION
+# ============================================
+
+
+def install(read_only: list[Path]) -> None:
+    """
+    Block network access and writes under the given directories.
+
+    Args:
+        read_only: Directories the host must not modify
+    """
+    global _installed
+    if _installed:
+        return
+    roots = tuple(path.resolve() for path in read_only)
+
+    def hook(event: str, args: tuple[Any, ...]) -> None:
+        if event in _SOCKET_EVENTS:
+            if len(args) > 1 and not _is_local_address(args[1]):
+                raise PermissionError(f"Network access is blocked in the sandbox ({event})")
+        elif event in _RESOLVE_EVENTS:
+            if args and not _is_local_address((args[0],)):
+                raise PermissionError(f"Network access is blocked in the sandbox ({event})")
+        elif event == "open":
+            if len(args) > 2 and _is_write(args[1], args[2]) and _within(args[0], roots):
+                raise PermissionError(f"Writing to {os.fsdecode(args[0])} is blocked in the sandbox")
+        elif event in _PATH_EVENTS:
+            paths = args[:2] if event in ("os.rename", "os.link") else args[:1]
+            for path in paths:
+                if _within(path, roots):
+                    raise PermissionError(f"Modifying {os.fsdecode(path)} is blocked in the sandbox")
+
+    sys.addaudithook(hook)
+    _installed = True
+
+
+def install_from_env() -> bool:
+    """
+    Install the restrictions if the backend started this host sandboxed.
+
+    Returns:
+        True if the host is sandboxed
+    """
+    if os.environ.get(SANDBOX_ENV) != "1":
+        return False
+    read_only = [Path(entry) for entry in os.environ.get(READ_ONLY_ENV, "").split(os.pathsep) if entry]
+    install(read_only)
+    return True
+
+
+def is_sandboxed() -> bool:
+    """Whether the restrictions are installed in this process."""
+    return _installed
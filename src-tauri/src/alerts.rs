@@ -0,0 +1,358 @@
+//! src-tauri/src/alerts.rs
+//! =======================
+//! Alerts when the plugin host becomes unhealthy.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Kiosk and unattended deployments need to notice failures without
+//! watching logs. This module follows `HealthMonitor` state changes and
+//! raises an alert when the plugin host becomes degraded, crashes, or has
+//! used up its respawn attempts. Each configured action runs per alert:
+//!
+//! - `alerts.notify`: desktop notification
+//! - `alerts.webhook_url`: JSON POST to a (typically local) URL; skipped
+//!   in offline mode unless the URL is on this machine
+//! - `alerts.file`: JSON line appended to a file (relative paths are
+//!   resolved against the app log directory)
+//!
+//! The settings live in the settings store and can be set in the
+//! `[alerts]` section of app_factory.toml; they are read at startup.
+//! Alerts of the same kind within `ALERT_COOLDOWN` are dropped so a
+//! flapping host doesn't flood the channels.
+//!
+//! Usage:
+//!     ```rust
+//!     let alerts = HealthAlerts::new(startup_settings.alerts.clone(), paths.log_dir());
+//!     tauri::async_runtime::spawn(alerts.watch(app.handle(), state.inner().clone()));
+//!     ```
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::sync::broadcast;
+
+use crate::ipc::health::{HealthEvent, SubprocessState};
+use crate::ipc::manager::IpcManagerState;
+use crate::offline;
+use crate::proxy;
+use crate::settings::AlertSettings;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Minimum time between two alerts of the same kind
+const ALERT_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+/// Timeout for webhook calls
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Title of desktop notifications
+const NOTIFICATION_TITLE: &str = "App Factory";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Why an alert was raised.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    /// Health checks are failing
+    Degraded,
+    /// The plugin host exited unexpectedly
+    Crashed,
+    /// The plugin host crashed with no respawn attempts left
+    RespawnLimitExceeded,
+}
+
+impl AlertKind {
+    /// Human-readable description.
+    fn message(self) -> &'static str {
+        match self {
+            Self::Degraded => "The plugin host is degraded: health checks are failing.",
+            Self::Crashed => "The plugin host crashed.",
+            Self::RespawnLimitExceeded => {
+                "The plugin host crashed and will not be restarted (respawn limit reached)."
+            }
+        }
+    }
+}
+
+/// An alert as sent to the webhook and written to the alert file.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthAlert {
+    /// Why it was raised
+    pub kind: AlertKind,
+    /// Human-readable description
+    pub message: String,
+    /// State transition that raised it (`RUNNING -> DEGRADED`)
+    pub transition: String,
+    /// When it was raised (RFC 3339)
+    pub at: String,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Alerts raised by a health event.
+fn alert_kinds(event: &HealthEvent, respawn_limit_exceeded: bool) -> Vec<AlertKind> {
+    match event {
+        HealthEvent::StateChanged {
+            to: SubprocessState::Degraded,
+            ..
+        } => vec![AlertKind::Degraded],
+        HealthEvent::StateChanged {
+            to: SubprocessState::Crashed,
+            ..
+        } if respawn_limit_exceeded => vec![AlertKind::Crashed, AlertKind::RespawnLimitExceeded],
+        HealthEvent::StateChanged {
+            to: SubprocessState::Crashed,
+            ..
+        } => vec![AlertKind::Crashed],
+        _ => Vec::new(),
+    }
+}
+
+// ============================================
+// HEALTH ALERTS
+// ============================================
+
+/// Alert dispatcher.
+///
+/// Cloning shares the cooldown state.
+#[derive(Clone)]
+pub struct HealthAlerts {
+    /// Configured actions
+    settings: AlertSettings,
+    /// Alert file path (None disables it)
+    file: Option<PathBuf>,
+    /// When each kind was last sent
+    last_sent: Arc<Mutex<HashMap<AlertKind, Instant>>>,
+    /// HTTP client for the webhook
+    client: reqwest::Client,
+}
+
+impl HealthAlerts {
+    /// Create a dispatcher; `log_dir` anchors a relative `alerts.file`.
+    pub fn new(settings: AlertSettings, log_dir: Option<PathBuf>) -> Self {
+        let file = Some(PathBuf::from(&settings.file))
+            .filter(|path| !path.as_os_str().is_empty())
+            .and_then(|path| {
+                if path.is_absolute() {
+                    Some(path)
+                } else {
+                    log_dir.map(|dir| dir.join(path))
+                }
+            });
+        Self {
+            settings,
+            file,
+            last_sent: Arc::new(Mutex::new(HashMap::new())),
+            client: proxy::client(),
+        }
+    }
+
+    /// Whether any action is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.settings.notify || !self.settings.webhook_url.is_empty() || self.file.is_some()
+    }
+
+    /// Raise alerts for the manager's health events until the channel closes.
+    pub async fn watch(self, app: AppHandle, state: IpcManagerState) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut events = state.health().subscribe();
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let HealthEvent::StateChanged { from, to } = event else {
+                continue;
+            };
+            let kinds = alert_kinds(&event, state.health().respawn_limit_exceeded());
+            for kind in kinds {
+                if !self.should_send(kind, Instant::now()) {
+                    log::debug!("Skipping {kind:?} alert (cooldown)");
+                    continue;
+                }
+                let alert = HealthAlert {
+                    kind,
+                    message: kind.message().to_string(),
+                    transition: format!("{from} -> {to}"),
+                    at: chrono::Utc::now().to_rfc3339(),
+                };
+                self.send(&app, &alert).await;
+            }
+        }
+    }
+
+    /// Record that `kind` is sent at `now`, unless one was sent within the cooldown.
+    fn should_send(&self, kind: AlertKind, now: Instant) -> bool {
+        let mut last_sent = self.last_sent.lock().unwrap();
+        if last_sent
+            .get(&kind)
+            .is_some_and(|at| now.duration_since(*at) < ALERT_COOLDOWN)
+        {
+            return false;
+        }
+        last_sent.insert(kind, now);
+        true
+    }
+
+    /// Run every configured action; failures are logged.
+    async fn send(&self, app: &AppHandle, alert: &HealthAlert) {
+        log::warn!("Health alert: {}", alert.message);
+
+        if self.settings.notify {
+            if let Err(e) =
+                tauri::api::notification::Notification::new(&app.config().tauri.bundle.identifier)
+                    .title(NOTIFICATION_TITLE)
+                    .body(&alert.message)
+                    .show()
+            {
+                log::warn!("Failed to show alert notification: {e}");
+            }
+        }
+
+        if let Some(ref path) = self.file {
+            if let Err(e) = append_alert(path, alert) {
+                log::warn!("{e}");
+            }
+        }
+
+        if !self.settings.webhook_url.is_empty() {
+            if let Err(e) = self.call_webhook(alert).await {
+                log::warn!("{e}");
+            }
+        }
+    }
+
+    /// POST an alert to the webhook.
+    async fn call_webhook(&self, alert: &HealthAlert) -> Result<(), String> {
+        let url = reqwest::Url::parse(&self.settings.webhook_url)
+            .map_err(|e| format!("Invalid alert webhook URL: {e}"))?;
+        offline::check_url(&url).map_err(|e| format!("Alert webhook skipped: {e}"))?;
+
+        let body = serde_json::to_vec(alert).unwrap_or_default();
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .timeout(WEBHOOK_TIMEOUT)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to call alert webhook: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!("Alert webhook returned HTTP {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Append an alert to the alert file as a JSON line.
+fn append_alert(path: &Path, alert: &HealthAlert) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let line = serde_json::to_string(alert).map_err(|e| e.to_string())?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Failed to open alert file {path:?}: {e}"))?;
+    writeln!(file, "{line}").map_err(|e| format!("Failed to write alert file {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed(from: SubprocessState, to: SubprocessState) -> HealthEvent {
+        HealthEvent::StateChanged { from, to }
+    }
+
+    #[test]
+    fn test_alert_kinds() {
+        use SubprocessState::{Crashed, Degraded, Running, Stopped};
+
+        assert_eq!(
+            alert_kinds(&changed(Running, Degraded), false),
+            vec![AlertKind::Degraded]
+        );
+        assert_eq!(
+            alert_kinds(&changed(Running, Crashed), true),
+            vec![AlertKind::Crashed, AlertKind::RespawnLimitExceeded]
+        );
+        assert!(alert_kinds(&changed(Degraded, Running), false).is_empty());
+        assert!(alert_kinds(&changed(Running, Stopped), true).is_empty());
+        assert!(alert_kinds(&HealthEvent::CheckSucceeded, true).is_empty());
+    }
+
+    #[test]
+    fn test_cooldown_and_alert_file() {
+        let dir = std::env::temp_dir().join(format!("af_alerts_{}", uuid::Uuid::new_v4()));
+        let alerts = HealthAlerts::new(
+            AlertSettings {
+                file: "alerts.jsonl".to_string(),
+                ..AlertSettings::default()
+            },
+            Some(dir.clone()),
+        );
+        assert!(alerts.is_enabled());
+        assert!(!HealthAlerts::new(AlertSettings::default(), Some(dir.clone())).is_enabled());
+
+        let now = Instant::now();
+        assert!(alerts.should_send(AlertKind::Crashed, now));
+        assert!(!alerts.should_send(AlertKind::Crashed, now + Duration::from_secs(60)));
+        assert!(alerts.should_send(AlertKind::Degraded, now));
+        assert!(alerts.should_send(AlertKind::Crashed, now + ALERT_COOLDOWN));
+
+        let alert = HealthAlert {
+            kind: AlertKind::Crashed,
+            message: AlertKind::Crashed.message().to_string(),
+            transition: "RUNNING -> CRASHED".to_string(),
+            at: chrono::Utc::now().to_rfc3339(),
+        };
+        append_alert(alerts.file.as_ref().unwrap(), &alert).unwrap();
+        append_alert(alerts.file.as_ref().unwrap(), &alert).unwrap();
+        let content = std::fs::read_to_string(dir.join("alerts.jsonl")).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.contains("\"kind\":\"crashed\""));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_blocked_offline() {
+        let alerts = HealthAlerts::new(
+            AlertSettings {
+                webhook_url: "https://alerts.example.invalid/hook".to_string(),
+                ..AlertSettings::default()
+            },
+            None,
+        );
+        let alert = HealthAlert {
+            kind: AlertKind::Degraded,
+            message: AlertKind::Degraded.message().to_string(),
+            transition: "RUNNING -> DEGRADED".to_string(),
+            at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        offline::set_offline_for_test(true);
+        let error = alerts.call_webhook(&alert).await.unwrap_err();
+        assert!(error.contains("Offline mode"));
+    }
+}
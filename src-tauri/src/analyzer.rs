@@ -0,0 +1,488 @@
+//! src-tauri/src/analyzer.rs
+//! ==========================
+//! Component metadata extraction from TSX, for the UI builder's property panels.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Components are top-level functions or `const` arrow/function
+//! expressions with a capitalized name, optionally wrapped in a call such
+//! as `React.memo(...)` or `forwardRef(...)`. Only exported components are
+//! reported; generated files that export nothing report every component.
+//!
+//! Props come from the first parameter's type annotation (or the type
+//! argument of `React.FC<Props>`). Interfaces, type aliases, `extends`
+//! and intersections declared in the same file are followed; types from
+//! other modules are reported by name only. Defaults come from
+//! destructuring in the parameter list (`{ size = 'md' }: Props`).
+//!
+//! Usage:
+//!     ```rust
+//!     let components = analyzer::analyze(source)?;
+//!     for prop in &components[0].props {
+//!         println!("{}: {:?} = {:?}", prop.name, prop.type_annotation, prop.default_value);
+//!     }
+//!     ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use swc_common::{sync::Lrc, FileName, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    Decl, DefaultDecl, EsVersion, ExportSpecifier, Expr, ModuleDecl, ModuleExportName, ModuleItem,
+    ObjectPatProp, Pat, Stmt, TsInterfaceDecl, TsType, TsTypeElement, TsUnionOrIntersectionType,
+};
+use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// How deep type references are followed when collecting props
+const MAX_TYPE_DEPTH: usize = 8;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A component found in the source.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentInfo {
+    /// Component name (`default` for an anonymous default export)
+    pub name: String,
+    /// Whether the component is exported
+    pub exported: bool,
+    /// Whether the component is the default export
+    pub default_export: bool,
+    /// Source text of the props type (e.g. `ButtonProps`), if annotated
+    pub props_type: Option<String>,
+    /// Props, in declaration order
+    pub props: Vec<PropInfo>,
+}
+
+/// A component prop.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PropInfo {
+    /// Prop name
+    pub name: String,
+    /// Source text of the prop type (None if untyped)
+    pub type_annotation: Option<String>,
+    /// Whether the prop is optional (`?:`)
+    pub optional: bool,
+    /// Default value, when it is a literal
+    pub default_value: Option<Value>,
+    /// Source text of the default, for any default expression
+    pub default_expression: Option<String>,
+}
+
+/// A component candidate before export filtering.
+struct Candidate<'m> {
+    name: String,
+    param: Option<&'m Pat>,
+    /// `P` in `const X: React.FC<P> = ...`
+    fc_props: Option<&'m TsType>,
+    exported: bool,
+    default_export: bool,
+}
+
+/// A type declaration props can be read from.
+enum TypeDecl<'m> {
+    Interface(&'m TsInterfaceDecl),
+    Alias(&'m TsType),
+}
+
+// ============================================
+// ANALYSIS
+// ============================================
+
+/// Extract component names, props and defaults from a TSX source.
+pub fn analyze(code: &str) -> Result<Vec<ComponentInfo>, String> {
+    let cm: Lrc<SourceMap> = Lrc::default();
+    let fm = cm.new_source_file(FileName::Custom("input.tsx".into()).into(), code.to_string());
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsSyntax {
+            tsx: true,
+            ..Default::default()
+        }),
+        EsVersion::EsNext,
+        StringInput::from(&*fm),
+        None,
+    );
+    let module = Parser::new_from(lexer)
+        .parse_module()
+        .map_err(|e| format!("Parse error: {}", e.kind().msg()))?;
+
+    let base = fm.start_pos.0;
+    let text = |span: Span| &code[(span.lo.0 - base) as usize..(span.hi.0 - base) as usize];
+
+    let mut types: HashMap<String, TypeDecl> = HashMap::new();
+    let mut candidates: Vec<Candidate> = Vec::new();
+    let mut exported_names: Vec<(String, bool)> = Vec::new();
+
+    for item in &module.body {
+        let (decl, exported) = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => (decl, false),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => (&export.decl, true),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export)) => {
+                if let DefaultDecl::Fn(f) = &export.decl {
+                    candidates.push(Candidate {
+                        name: f.ident.as_ref().map_or("default", |id| text(id.span)).to_string(),
+                        param: f.function.params.first().map(|p| &p.pat),
+                        fc_props: None,
+                        exported: true,
+                        default_export: true,
+                    });
+                }
+                continue;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(export)) => {
+                match &*export.expr {
+                    Expr::Ident(id) => exported_names.push((text(id.span).to_string(), true)),
+                    expr => {
+                        if let Some(param) = component_param(expr) {
+                            candidates.push(Candidate {
+                                name: "default".to_string(),
+                                param,
+                                fc_props: None,
+                                exported: true,
+                                default_export: true,
+                            });
+                        }
+                    }
+                }
+                continue;
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(named)) if named.src.is_none() => {
+                for specifier in &named.specifiers {
+                    if let ExportSpecifier::Named(s) = specifier {
+                        if let ModuleExportName::Ident(id) = &s.orig {
+                            let default = s.exported.as_ref().is_some_and(|e| match e {
+                                ModuleExportName::Ident(e) => text(e.span) == "default",
+                                ModuleExportName::Str(_) => false,
+                            });
+                            exported_names.push((text(id.span).to_string(), default));
+                        }
+                    }
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        match decl {
+            Decl::TsInterface(interface) => {
+                types.insert(text(interface.id.span).to_string(), TypeDecl::Interface(interface));
+            }
+            Decl::TsTypeAlias(alias) => {
+                types.insert(text(alias.id.span).to_string(), TypeDecl::Alias(&alias.type_ann));
+            }
+            Decl::Fn(f) if is_component_name(text(f.ident.span)) => candidates.push(Candidate {
+                name: text(f.ident.span).to_string(),
+                param: f.function.params.first().map(|p| &p.pat),
+                fc_props: None,
+                exported,
+                default_export: false,
+            }),
+            Decl::Var(var) => {
+                for declarator in &var.decls {
+                    let Pat::Ident(binding) = &declarator.name else {
+                        continue;
+                    };
+                    let name = text(binding.id.span);
+                    let Some(param) = declarator.init.as_deref().and_then(component_param) else {
+                        continue;
+                    };
+                    if !is_component_name(name) {
+                        continue;
+                    }
+                    let fc_props = binding
+                        .type_ann
+                        .as_ref()
+                        .and_then(|ann| fc_type_argument(&ann.type_ann, &text));
+                    candidates.push(Candidate {
+                        name: name.to_string(),
+                        param,
+                        fc_props,
+                        exported,
+                        default_export: false,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    for (name, default) in exported_names {
+        for candidate in candidates.iter_mut().filter(|c| c.name == name) {
+            candidate.exported = true;
+            candidate.default_export |= default;
+        }
+    }
+    if candidates.iter().any(|c| c.exported) {
+        candidates.retain(|c| c.exported);
+    }
+
+    Ok(candidates
+        .into_iter()
+        .map(|candidate| {
+            let annotation = candidate.param.and_then(param_type).or(candidate.fc_props);
+            let mut props = annotation
+                .map(|ty| type_props(ty, &types, &text, 0))
+                .unwrap_or_default();
+
+            for (name, default) in candidate.param.map(|pat| destructured(pat, &text)).unwrap_or_default() {
+                let index = props.iter().position(|p| p.name == name).unwrap_or_else(|| {
+                    props.push(PropInfo {
+                        name: name.clone(),
+                        type_annotation: None,
+                        optional: true,
+                        default_value: None,
+                        default_expression: None,
+                    });
+                    props.len() - 1
+                });
+                if let Some(default) = default {
+                    props[index].default_value = literal_value(&default);
+                    props[index].default_expression = Some(default);
+                }
+            }
+
+            ComponentInfo {
+                name: candidate.name,
+                exported: candidate.exported,
+                default_export: candidate.default_export,
+                props_type: annotation.map(|ty| text(ty.span()).to_string()),
+                props,
+            }
+        })
+        .collect())
+}
+
+/// Whether a name looks like a component (capitalized).
+fn is_component_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// The first parameter of a component expression, if `expr` is one.
+///
+/// `Some(None)` is a component without parameters. Wrapping calls such as
+/// `React.memo(...)` are looked through.
+fn component_param(expr: &Expr) -> Option<Option<&Pat>> {
+    match expr {
+        Expr::Arrow(arrow) => Some(arrow.params.first()),
+        Expr::Fn(f) => Some(f.function.params.first().map(|p| &p.pat)),
+        Expr::Paren(paren) => component_param(&paren.expr),
+        Expr::Call(call) => call.args.first().and_then(|arg| component_param(&arg.expr)),
+        _ => None,
+    }
+}
+
+/// `P` from a `React.FC<P>` / `FunctionComponent<P>` annotation.
+fn fc_type_argument<'m, 'a>(ty: &'m TsType, text: &impl Fn(Span) -> &'a str) -> Option<&'m TsType> {
+    let TsType::TsTypeRef(reference) = ty else {
+        return None;
+    };
+    let name = text(reference.type_name.span());
+    if !(name.ends_with("FC") || name.ends_with("FunctionComponent")) {
+        return None;
+    }
+    reference.type_params.as_ref()?.params.first().map(|p| &**p)
+}
+
+/// Type annotation of a parameter pattern.
+fn param_type(pat: &Pat) -> Option<&TsType> {
+    match pat {
+        Pat::Ident(binding) => binding.type_ann.as_ref().map(|ann| &*ann.type_ann),
+        Pat::Object(object) => object.type_ann.as_ref().map(|ann| &*ann.type_ann),
+        Pat::Assign(assign) => param_type(&assign.left),
+        _ => None,
+    }
+}
+
+/// Props described by a type, following local declarations.
+fn type_props<'a>(
+    ty: &TsType,
+    types: &HashMap<String, TypeDecl>,
+    text: &impl Fn(Span) -> &'a str,
+    depth: usize,
+) -> Vec<PropInfo> {
+    if depth > MAX_TYPE_DEPTH {
+        return Vec::new();
+    }
+    match ty {
+        TsType::TsTypeLit(literal) => member_props(&literal.members, text),
+        TsType::TsParenthesizedType(paren) => type_props(&paren.type_ann, types, text, depth + 1),
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(i)) => i
+            .types
+            .iter()
+            .flat_map(|ty| type_props(ty, types, text, depth + 1))
+            .collect(),
+        TsType::TsTypeRef(reference) => {
+            named_props(text(reference.type_name.span()), types, text, depth + 1)
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Props of a locally declared interface or type alias.
+fn named_props<'a>(
+    name: &str,
+    types: &HashMap<String, TypeDecl>,
+    text: &impl Fn(Span) -> &'a str,
+    depth: usize,
+) -> Vec<PropInfo> {
+    match types.get(name) {
+        Some(TypeDecl::Interface(interface)) => {
+            let mut props: Vec<PropInfo> = interface
+                .extends
+                .iter()
+                .flat_map(|parent| named_props(text(parent.expr.span()), types, text, depth + 1))
+                .collect();
+            for prop in member_props(&interface.body.body, text) {
+                // Redeclared members override inherited ones
+                props.retain(|p| p.name != prop.name);
+                props.push(prop);
+            }
+            props
+        }
+        Some(TypeDecl::Alias(ty)) => type_props(ty, types, text, depth),
+        None => Vec::new(),
+    }
+}
+
+/// Props for the members of an interface body or type literal.
+fn member_props<'a>(members: &[TsTypeElement], text: &impl Fn(Span) -> &'a str) -> Vec<PropInfo> {
+    let key_name = |key: &Expr| text(key.span()).trim_matches(|c| c == '"' || c == '\'').to_string();
+    members
+        .iter()
+        .filter_map(|member| {
+            let (name, optional, type_annotation) = match member {
+                TsTypeElement::TsPropertySignature(p) => (
+                    key_name(&p.key),
+                    p.optional,
+                    p.type_ann.as_ref().map(|ann| text(ann.type_ann.span()).to_string()),
+                ),
+                TsTypeElement::TsMethodSignature(m) => (
+                    key_name(&m.key),
+                    m.optional,
+                    Some(text(m.span).trim_end_matches([';', ',']).to_string()),
+                ),
+                _ => return None,
+            };
+            Some(PropInfo {
+                name,
+                type_annotation,
+                optional,
+                default_value: None,
+                default_expression: None,
+            })
+        })
+        .collect()
+}
+
+/// Destructured prop names and the source text of their defaults.
+fn destructured<'a>(pat: &Pat, text: &impl Fn(Span) -> &'a str) -> Vec<(String, Option<String>)> {
+    match pat {
+        Pat::Object(object) => object
+            .props
+            .iter()
+            .filter_map(|prop| match prop {
+                ObjectPatProp::Assign(assign) => Some((
+                    text(assign.key.id.span).to_string(),
+                    assign.value.as_ref().map(|value| text(value.span()).to_string()),
+                )),
+                ObjectPatProp::KeyValue(kv) => {
+                    let default = match &*kv.value {
+                        Pat::Assign(assign) => Some(text(assign.right.span()).to_string()),
+                        _ => None,
+                    };
+                    Some((text(kv.key.span()).to_string(), default))
+                }
+                ObjectPatProp::Rest(_) => None,
+            })
+            .collect(),
+        Pat::Assign(assign) => destructured(&assign.left, text),
+        _ => Vec::new(),
+    }
+}
+
+/// JSON value of a literal default (`'md'`, `3`, `true`, `[1, 2]`, ...).
+fn literal_value(expression: &str) -> Option<Value> {
+    if let Ok(value) = serde_json::from_str(expression) {
+        return Some(value);
+    }
+    let quoted = |q: char| expression.len() >= 2 && expression.starts_with(q) && expression.ends_with(q);
+    if quoted('\'') || (quoted('`') && !expression.contains("${")) {
+        return Some(Value::String(expression[1..expression.len() - 1].to_string()));
+    }
+    None
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_analyze_exported_component() {
+        let code = r#"
+            interface BaseProps { id?: string; }
+            interface ButtonProps extends BaseProps {
+                label: string;
+                size?: 'sm' | 'md' | 'lg';
+                onClick?(event: MouseEvent): void;
+            }
+            const Helper = () => null;
+            export const Button = ({ label, size = 'md', id }: ButtonProps) => <button id={id}>{label}</button>;
+        "#;
+
+        let components = analyze(code).unwrap();
+        assert_eq!(components.len(), 1);
+        let button = &components[0];
+        assert_eq!(button.name, "Button");
+        assert_eq!(button.props_type.as_deref(), Some("ButtonProps"));
+
+        let names: Vec<&str> = button.props.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["id", "label", "size", "onClick"]);
+        let size = &button.props[2];
+        assert!(size.optional);
+        assert_eq!(size.type_annotation.as_deref(), Some("'sm' | 'md' | 'lg'"));
+        assert_eq!(size.default_value, Some(json!("md")));
+        assert!(!button.props[1].optional);
+    }
+
+    #[test]
+    fn test_analyze_fc_and_default_export() {
+        let code = r#"
+            type CardProps = { title: string } & { count?: number };
+            const Card: React.FC<CardProps> = ({ title, count = 3 }) => <div>{title}{count}</div>;
+            function Panel(props: { open: boolean }) { return null; }
+            export default Card;
+        "#;
+
+        let components = analyze(code).unwrap();
+        assert_eq!(components.len(), 1);
+        let card = &components[0];
+        assert!(card.default_export);
+        assert_eq!(card.props.len(), 2);
+        assert_eq!(card.props[1].default_value, Some(json!(3)));
+
+        // Nothing exported: every component is reported
+        let components = analyze("function Panel(props: { open: boolean }) { return null; }").unwrap();
+        assert_eq!(components[0].name, "Panel");
+        assert!(!components[0].exported);
+        assert_eq!(components[0].props[0].type_annotation.as_deref(), Some("boolean"));
+    }
+
+    #[test]
+    fn test_literal_value() {
+        assert_eq!(literal_value("'md'"), Some(json!("md")));
+        assert_eq!(literal_value("[1, 2]"), Some(json!([1, 2])));
+        assert_eq!(literal_value("false"), Some(json!(false)));
+        assert_eq!(literal_value("() => {}"), None);
+        assert_eq!(literal_value("`a${b}`"), None);
+    }
+}
@@ -0,0 +1,428 @@
+//! src-tauri/src/app_config.rs
+//! ===========================
+//! Startup configuration from `app_factory.toml`, environment variables,
+//! and command-line flags.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Values are layered, later layers winning per key:
+//!
+//! 1. `settings.json` (see settings.rs)
+//! 2. `app_factory.toml` in the app config directory
+//! 3. `app_factory.toml` in the project root
+//! 4. `APP_FACTORY_*` environment variables (see `OVERRIDES`)
+//! 5. Command-line flags (see `OVERRIDES`, plus `--set section.key=value`)
+//!
+//! `--config <path>` (or `APP_FACTORY_CONFIG`) replaces steps 2 and 3 with
+//! a single file. The `[python]`, `[ipc]`, and `[alerts]` sections accept
+//! the same keys as the settings store and are validated the same way; `[logging]`
+//! accepts `level`, `[crash]` accepts `submit_url` (see crash.rs), and
+//! `[compiler]` accepts any `CompileOptions` field as the default for
+//! compile commands. Invalid values are logged and skipped. The configuration is read once at startup.
+//!
+//! ```toml
+//! [python]
+//! path = ".venv/bin/python"
+//! args = ["-X", "utf8"]
+//!
+//! [ipc]
+//! timeout_secs = 120
+//! auto_respawn = false
+//!
+//! [logging]
+//! level = "debug"
+//!
+//! [compiler]
+//! target = "es2017"
+//! cdn = "esm.sh"
+//! ```
+//!
+//! Usage:
+//!     ```rust
+//!     let app_config = AppConfig::load(&project_root, config_dir.as_deref());
+//!     let config = app_config.apply(&settings.get()).to_ipc_config();
+//!     ```
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::commands::compiler::CompileOptions;
+use crate::logging::parse_level;
+use crate::settings::{key_to_pointer, validate_setting, Settings};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// File name of the configuration file
+pub const CONFIG_FILE: &str = "app_factory.toml";
+
+/// Environment variable naming an explicit configuration file
+const CONFIG_ENV: &str = "APP_FACTORY_CONFIG";
+
+/// Flag naming an explicit configuration file
+const CONFIG_FLAG: &str = "--config";
+
+/// Flag setting any key (`--set ipc.timeout_secs=120`)
+const SET_FLAG: &str = "--set";
+
+/// Keys with a dedicated environment variable and flag.
+const OVERRIDES: &[Override] = &[
+    Override::new("python.path", "APP_FACTORY_PYTHON", "--python"),
+    Override::new(
+        "python.module",
+        "APP_FACTORY_PYTHON_MODULE",
+        "--python-module",
+    ),
+    Override::new(
+        "ipc.timeout_secs",
+        "APP_FACTORY_TIMEOUT_SECS",
+        "--timeout-secs",
+    ),
+    Override::new(
+        "ipc.auto_respawn",
+        "APP_FACTORY_AUTO_RESPAWN",
+        "--auto-respawn",
+    ),
+    Override::new(
+        "ipc.max_respawn_attempts",
+        "APP_FACTORY_MAX_RESPAWN_ATTEMPTS",
+        "--max-respawn-attempts",
+    ),
+    Override::new("logging.level", "APP_FACTORY_LOG_LEVEL", "--log-level"),
+    Override::new(
+        "compiler.target",
+        "APP_FACTORY_COMPILE_TARGET",
+        "--compile-target",
+    ),
+    Override::new(
+        "compiler.minify",
+        "APP_FACTORY_COMPILE_MINIFY",
+        "--compile-minify",
+    ),
+];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A key settable from the environment and the command line.
+struct Override {
+    /// Dotted key
+    key: &'static str,
+    /// Environment variable
+    env: &'static str,
+    /// Command-line flag
+    flag: &'static str,
+}
+
+impl Override {
+    const fn new(key: &'static str, env: &'static str, flag: &'static str) -> Self {
+        Self { key, env, flag }
+    }
+}
+
+/// Resolved startup configuration.
+#[derive(Debug, Clone, Default)]
+pub struct AppConfig {
+    /// Validated setting values by dotted key (`python.*`, `ipc.*`, ...)
+    settings: BTreeMap<String, Value>,
+    /// Log level (`logging.level`)
+    log_level: Option<String>,
+    /// Crash report endpoint (`crash.submit_url`)
+    crash_submit_url: Option<String>,
+    /// Default compile options (`[compiler]`)
+    compiler: Option<CompileOptions>,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Values of `flag` in `args` (`--flag value` or `--flag=value`).
+pub fn flag_values<'a>(args: &'a [String], flag: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    args.iter().enumerate().filter_map(move |(i, arg)| {
+        if arg == flag {
+            args.get(i + 1).map(String::as_str)
+        } else {
+            arg.strip_prefix(flag)?.strip_prefix('=')
+        }
+    })
+}
+
+/// Parse an environment or flag value: booleans and integers are typed,
+/// anything else is a string.
+fn parse_scalar(raw: &str) -> toml::Value {
+    let raw = raw.trim();
+    match raw {
+        "true" => toml::Value::Boolean(true),
+        "false" => toml::Value::Boolean(false),
+        _ => raw.parse::<i64>().map_or_else(
+            |_| toml::Value::String(raw.to_string()),
+            toml::Value::Integer,
+        ),
+    }
+}
+
+/// Read a configuration file into `values` as dotted keys.
+fn read_file(path: &Path, values: &mut BTreeMap<String, toml::Value>) -> Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+    let table: toml::Table =
+        toml::from_str(&content).map_err(|e| format!("Invalid configuration {path:?}: {e}"))?;
+
+    let mut file_values = BTreeMap::new();
+    for (section, body) in table {
+        let toml::Value::Table(fields) = body else {
+            return Err(format!("{path:?}: `{section}` must be a table"));
+        };
+        for (field, value) in fields {
+            file_values.insert(format!("{section}.{field}"), value);
+        }
+    }
+    values.extend(file_values);
+    Ok(())
+}
+
+/// Configuration files to read, lowest precedence first.
+fn config_files<F>(
+    project_root: &Path,
+    config_dir: Option<&Path>,
+    env: &F,
+    args: &[String],
+) -> Vec<PathBuf>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    if let Some(explicit) = flag_values(args, CONFIG_FLAG)
+        .last()
+        .map(str::to_string)
+        .or_else(|| env(CONFIG_ENV))
+    {
+        return vec![PathBuf::from(explicit)];
+    }
+
+    config_dir
+        .into_iter()
+        .chain([project_root])
+        .map(|dir| dir.join(CONFIG_FILE))
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+// ============================================
+// APP CONFIG
+// ============================================
+
+impl AppConfig {
+    /// Load the configuration for this process (files, environment, and
+    /// `std::env::args`).
+    pub fn load(project_root: &Path, config_dir: Option<&Path>) -> Self {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        Self::resolve(
+            project_root,
+            config_dir,
+            &|name| std::env::var(name).ok(),
+            &args,
+        )
+    }
+
+    /// Layer files, environment, and flags into a validated configuration.
+    fn resolve<F>(project_root: &Path, config_dir: Option<&Path>, env: &F, args: &[String]) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut values = BTreeMap::new();
+
+        for path in config_files(project_root, config_dir, env, args) {
+            match read_file(&path, &mut values) {
+                Ok(()) => log::info!("Loaded configuration from {path:?}"),
+                Err(e) => log::warn!("Ignoring configuration file: {e}"),
+            }
+        }
+
+        for item in OVERRIDES {
+            if let Some(raw) = env(item.env) {
+                values.insert(item.key.to_string(), parse_scalar(&raw));
+            }
+        }
+
+        for item in OVERRIDES {
+            if let Some(raw) = flag_values(args, item.flag).last() {
+                values.insert(item.key.to_string(), parse_scalar(raw));
+            }
+        }
+        for assignment in flag_values(args, SET_FLAG) {
+            match assignment.split_once('=') {
+                Some((key, raw)) => {
+                    values.insert(key.trim().to_string(), parse_scalar(raw));
+                }
+                None => log::warn!("Ignoring {SET_FLAG} {assignment}: expected key=value"),
+            }
+        }
+
+        Self::from_values(values)
+    }
+
+    /// Validate dotted-key values by section.
+    fn from_values(values: BTreeMap<String, toml::Value>) -> Self {
+        let mut config = Self::default();
+        let mut compiler = toml::Table::new();
+
+        for (key, value) in values {
+            if let Some(field) = key.strip_prefix("compiler.") {
+                compiler.insert(field.to_string(), value);
+            } else if key == "logging.level" {
+                match value.as_str().map(|level| (level, parse_level(level))) {
+                    Some((level, Ok(_))) => config.log_level = Some(level.to_string()),
+                    Some((_, Err(e))) => log::warn!("Ignoring logging.level: {e}"),
+                    None => log::warn!("Ignoring logging.level: must be a string"),
+                }
+            } else if key == "crash.submit_url" {
+                match value.as_str() {
+                    Some(url) if url.starts_with("https://") => {
+                        config.crash_submit_url = Some(url.to_string());
+                    }
+                    _ => log::warn!("Ignoring crash.submit_url: must be an https URL"),
+                }
+            } else {
+                let json = serde_json::to_value(&value).unwrap_or(Value::Null);
+                match validate_setting(&key, &json) {
+                    Ok(()) => {
+                        config.settings.insert(key, json);
+                    }
+                    Err(e) => log::warn!("Ignoring configuration value: {e}"),
+                }
+            }
+        }
+
+        if !compiler.is_empty() {
+            match toml::Value::Table(compiler).try_into::<CompileOptions>() {
+                Ok(options) => config.compiler = Some(options),
+                Err(e) => log::warn!("Ignoring [compiler] configuration: {e}"),
+            }
+        }
+
+        config
+    }
+
+    /// Apply the configured values on top of `settings`.
+    pub fn apply(&self, settings: &Settings) -> Settings {
+        let Ok(mut all) = serde_json::to_value(settings) else {
+            return settings.clone();
+        };
+        for (key, value) in &self.settings {
+            if let Some(slot) = all.pointer_mut(&key_to_pointer(key)) {
+                *slot = value.clone();
+            }
+        }
+        serde_json::from_value(all).unwrap_or_else(|e| {
+            log::warn!("Ignoring configuration values: {e}");
+            settings.clone()
+        })
+    }
+
+    /// Configured log level, if any.
+    pub fn log_level(&self) -> Option<&str> {
+        self.log_level.as_deref()
+    }
+
+    /// Configured crash report endpoint, if any.
+    pub fn crash_submit_url(&self) -> Option<String> {
+        self.crash_submit_url.clone()
+    }
+
+    /// Configured compile defaults, if any.
+    pub fn compiler(&self) -> Option<&CompileOptions> {
+        self.compiler.as_ref()
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::compiler::CompileTarget;
+    use crate::test_util::temp_dir;
+    use std::collections::HashMap;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_layering_order() {
+        let config_dir = temp_dir("app_config");
+        let project_root = temp_dir("app_config");
+        fs::write(
+            config_dir.join(CONFIG_FILE),
+            "[python]\npath = \"python3\"\nmodule = \"custom._host\"\n\
+             [ipc]\ntimeout_secs = 10\nauto_respawn = false\n",
+        )
+        .unwrap();
+        fs::write(project_root.join(CONFIG_FILE), "[ipc]\ntimeout_secs = 20\n").unwrap();
+
+        let env: HashMap<&str, &str> = [
+            ("APP_FACTORY_TIMEOUT_SECS", "30"),
+            ("APP_FACTORY_PYTHON", "/env/python"),
+        ]
+        .into();
+        let config = AppConfig::resolve(
+            &project_root,
+            Some(&config_dir),
+            &|name| env.get(name).map(|v| (*v).to_string()),
+            &args(&[
+                "--python=/cli/python",
+                "--set",
+                "ipc.max_respawn_attempts=7",
+            ]),
+        );
+        let settings = config.apply(&Settings::default());
+
+        assert_eq!(settings.python.path, "/cli/python");
+        assert_eq!(settings.python.module, "custom._host");
+        assert_eq!(settings.ipc.timeout_secs, 30);
+        assert!(!settings.ipc.auto_respawn);
+        assert_eq!(settings.ipc.max_respawn_attempts, 7);
+    }
+
+    #[test]
+    fn test_explicit_file_and_invalid_values() {
+        let dir = temp_dir("app_config");
+        let path = dir.join("custom.toml");
+        fs::write(
+            &path,
+            "[ipc]\ntimeout_secs = 0\nmemory_warning_mb = 512\n\
+             [logging]\nlevel = \"loud\"\n\
+             [compiler]\ntarget = \"es2017\"\nminify = true\n",
+        )
+        .unwrap();
+        // Would be picked up without --config
+        fs::write(dir.join(CONFIG_FILE), "[ipc]\nmemory_warning_mb = 1\n").unwrap();
+
+        let config = AppConfig::resolve(
+            &dir,
+            None,
+            &|_| None,
+            &args(&["--config", path.to_str().unwrap(), "--log-level", "debug"]),
+        );
+        let settings = config.apply(&Settings::default());
+
+        // Out-of-range timeout is skipped, valid keys still apply
+        assert_eq!(
+            settings.ipc.timeout_secs,
+            Settings::default().ipc.timeout_secs
+        );
+        assert_eq!(settings.ipc.memory_warning_mb, 512);
+        assert_eq!(config.log_level(), Some("debug"));
+
+        let compiler = config.compiler().unwrap();
+        assert_eq!(compiler.target, CompileTarget::Es2017);
+        assert!(compiler.minify);
+        assert!(!compiler.keep_comments);
+    }
+}
@@ -0,0 +1,449 @@
+//! src-tauri/src/apps.rs
+//! =====================
+//! Persisted generated-app definitions with version history.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Every save of a generated app (component tree, TSX source, compiled JS
+//! and asset references) appends an immutable version to `app_versions`
+//! (see storage.rs). Saving an unchanged definition is a no-op, and
+//! rolling back copies an old version forward as a new one so history is
+//! never rewritten.
+//!
+//! Usage:
+//!     ```rust
+//!     let v1 = apps::save(&storage, None, &definition, Some("Initial"))?;
+//!     let v2 = apps::save(&storage, Some(&v1.app_id), &edited, None)?;
+//!     let diff = apps::diff(&storage, &v1.app_id, 1, 2)?;
+//!     apps::rollback(&storage, &v1.app_id, 1)?;
+//!     ```
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use similar::TextDiff;
+use std::collections::BTreeMap;
+
+use crate::storage::Storage;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A generated app as produced by the factory.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppDefinition {
+    /// Display name
+    pub name: String,
+    /// Component tree as emitted by the generator
+    #[serde(default)]
+    pub component_tree: Value,
+    /// TSX source
+    #[serde(default)]
+    pub source: String,
+    /// Compiled JavaScript (optional; see compile_tsx)
+    #[serde(default)]
+    pub compiled: Option<String>,
+    /// Asset path → artifact hash (see artifacts.rs)
+    #[serde(default)]
+    pub assets: BTreeMap<String, String>,
+}
+
+/// Summary of a stored app.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppSummary {
+    /// App id
+    pub id: String,
+    /// Name of the current version
+    pub name: String,
+    /// Current version number
+    pub current_version: u32,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+    /// Timestamp of the latest version (RFC 3339)
+    pub updated_at: String,
+}
+
+/// Metadata of one version.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppVersionInfo {
+    /// App id
+    pub app_id: String,
+    /// Version number (starting at 1)
+    pub version: u32,
+    /// Commit-style message
+    pub message: Option<String>,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+}
+
+/// A full version of an app.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppSnapshot {
+    /// Version metadata
+    #[serde(flatten)]
+    pub info: AppVersionInfo,
+    /// The definition at this version
+    pub definition: AppDefinition,
+}
+
+/// Differences between two versions.
+#[derive(Debug, Clone, Serialize)]
+pub struct AppDiff {
+    /// App id
+    pub app_id: String,
+    /// Base version
+    pub from: u32,
+    /// Target version
+    pub to: u32,
+    /// Whether the name changed
+    pub name_changed: bool,
+    /// Unified diff of the TSX source (empty if unchanged)
+    pub source_diff: String,
+    /// Whether the compiled output changed
+    pub compiled_changed: bool,
+    /// Whether the component tree changed
+    pub component_tree_changed: bool,
+    /// Asset paths added in `to`
+    pub assets_added: Vec<String>,
+    /// Asset paths removed in `to`
+    pub assets_removed: Vec<String>,
+    /// Asset paths whose content changed
+    pub assets_changed: Vec<String>,
+}
+
+// ============================================
+// OPERATIONS
+// ============================================
+
+/// Save a definition as a new version.
+///
+/// `id = None` creates a new app. Saving a definition identical to the
+/// current version returns the current version unchanged.
+pub fn save(
+    storage: &Storage,
+    id: Option<&str>,
+    definition: &AppDefinition,
+    message: Option<&str>,
+) -> Result<AppVersionInfo, String> {
+    let now = Utc::now().to_rfc3339();
+    let body = serde_json::to_string(definition).map_err(|e| e.to_string())?;
+
+    let mut conn = storage.conn();
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+
+    let (app_id, version) = if let Some(id) = id {
+        let current: u32 = tx
+            .query_row(
+                "SELECT current_version FROM apps WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read app: {e}"))?
+            .ok_or_else(|| format!("Unknown app: {id}"))?;
+
+        let unchanged = load_version(&tx, id, current)?
+            .filter(|snapshot| &snapshot.definition == definition);
+        if let Some(snapshot) = unchanged {
+            return Ok(snapshot.info);
+        }
+
+        tx.execute(
+            "UPDATE apps SET name = ?2, current_version = ?3, updated_at = ?4 WHERE id = ?1",
+            params![id, definition.name, current + 1, now],
+        )
+        .map_err(|e| format!("Failed to update app: {e}"))?;
+        (id.to_string(), current + 1)
+    } else {
+        let id = uuid::Uuid::new_v4().to_string();
+        tx.execute(
+            "INSERT INTO apps (id, name, current_version, created_at, updated_at)
+             VALUES (?1, ?2, 1, ?3, ?3)",
+            params![id, definition.name, now],
+        )
+        .map_err(|e| format!("Failed to create app: {e}"))?;
+        (id, 1)
+    };
+
+    tx.execute(
+        "INSERT INTO app_versions (app_id, version, definition, message, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![app_id, version, body, message, now],
+    )
+    .map_err(|e| format!("Failed to save version: {e}"))?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    log::info!("Saved app {app_id} v{version}");
+    Ok(AppVersionInfo {
+        app_id,
+        version,
+        message: message.map(str::to_string),
+        created_at: now,
+    })
+}
+
+/// Load an app at a version (the current version when `None`).
+pub fn load(
+    storage: &Storage,
+    id: &str,
+    version: Option<u32>,
+) -> Result<Option<AppSnapshot>, String> {
+    let conn = storage.conn();
+    let version = match version {
+        Some(version) => version,
+        None => {
+            let current = conn
+                .query_row(
+                    "SELECT current_version FROM apps WHERE id = ?1",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .optional()
+                .map_err(|e| format!("Failed to read app: {e}"))?;
+            match current {
+                Some(current) => current,
+                None => return Ok(None),
+            }
+        }
+    };
+    load_version(&conn, id, version)
+}
+
+/// List stored apps, most recently updated first.
+pub fn list(storage: &Storage) -> Result<Vec<AppSummary>, String> {
+    let conn = storage.conn();
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, current_version, created_at, updated_at
+             FROM apps ORDER BY updated_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let apps = stmt
+        .query_map([], |row| {
+            Ok(AppSummary {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                current_version: row.get(2)?,
+                created_at: row.get(3)?,
+                updated_at: row.get(4)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("Failed to list apps: {e}"))?;
+
+    Ok(apps)
+}
+
+/// List an app's versions, newest first.
+pub fn versions(storage: &Storage, id: &str) -> Result<Vec<AppVersionInfo>, String> {
+    let conn = storage.conn();
+    let mut stmt = conn
+        .prepare(
+            "SELECT app_id, version, message, created_at
+             FROM app_versions WHERE app_id = ?1 ORDER BY version DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let versions = stmt
+        .query_map(params![id], row_to_info)
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("Failed to list versions: {e}"))?;
+
+    Ok(versions)
+}
+
+/// Compare two versions of an app.
+pub fn diff(storage: &Storage, id: &str, from: u32, to: u32) -> Result<AppDiff, String> {
+    let fetch = |version| {
+        load(storage, id, Some(version))?
+            .map(|snapshot| snapshot.definition)
+            .ok_or_else(|| format!("Unknown version: {id} v{version}"))
+    };
+    let old = fetch(from)?;
+    let new = fetch(to)?;
+
+    let source_diff = if old.source == new.source {
+        String::new()
+    } else {
+        TextDiff::from_lines(&old.source, &new.source)
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("v{from}"), &format!("v{to}"))
+            .to_string()
+    };
+
+    let assets_added = new
+        .assets
+        .keys()
+        .filter(|path| !old.assets.contains_key(*path))
+        .cloned()
+        .collect();
+    let assets_removed = old
+        .assets
+        .keys()
+        .filter(|path| !new.assets.contains_key(*path))
+        .cloned()
+        .collect();
+    let assets_changed = new
+        .assets
+        .iter()
+        .filter(|(path, hash)| old.assets.get(*path).is_some_and(|old| old != *hash))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    Ok(AppDiff {
+        app_id: id.to_string(),
+        from,
+        to,
+        name_changed: old.name != new.name,
+        source_diff,
+        compiled_changed: old.compiled != new.compiled,
+        component_tree_changed: old.component_tree != new.component_tree,
+        assets_added,
+        assets_removed,
+        assets_changed,
+    })
+}
+
+/// Restore an old version by saving it as the newest version.
+pub fn rollback(storage: &Storage, id: &str, version: u32) -> Result<AppVersionInfo, String> {
+    let snapshot = load(storage, id, Some(version))?
+        .ok_or_else(|| format!("Unknown version: {id} v{version}"))?;
+    save(
+        storage,
+        Some(id),
+        &snapshot.definition,
+        Some(&format!("Rollback to v{version}")),
+    )
+}
+
+/// Delete an app and all its versions.
+///
+/// # Returns
+///
+/// `true` if the app existed.
+pub fn delete(storage: &Storage, id: &str) -> Result<bool, String> {
+    storage
+        .conn()
+        .execute("DELETE FROM apps WHERE id = ?1", params![id])
+        .map(|n| n > 0)
+        .map_err(|e| format!("Failed to delete app: {e}"))
+}
+
+/// Load one version using an already locked connection.
+fn load_version(
+    conn: &Connection,
+    id: &str,
+    version: u32,
+) -> Result<Option<AppSnapshot>, String> {
+    let row = conn
+        .query_row(
+            "SELECT app_id, version, message, created_at, definition
+             FROM app_versions WHERE app_id = ?1 AND version = ?2",
+            params![id, version],
+            |row| Ok((row_to_info(row)?, row.get::<_, String>(4)?)),
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read version: {e}"))?;
+
+    row.map(|(info, body)| {
+        serde_json::from_str(&body)
+            .map(|definition| AppSnapshot { info, definition })
+            .map_err(|e| format!("Corrupt definition for {id} v{version}: {e}"))
+    })
+    .transpose()
+}
+
+/// Map an `app_versions` row to an `AppVersionInfo`.
+fn row_to_info(row: &rusqlite::Row<'_>) -> rusqlite::Result<AppVersionInfo> {
+    Ok(AppVersionInfo {
+        app_id: row.get(0)?,
+        version: row.get(1)?,
+        message: row.get(2)?,
+        created_at: row.get(3)?,
+    })
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition(source: &str) -> AppDefinition {
+        AppDefinition {
+            name: "Todo".to_string(),
+            component_tree: json!({"type": "App"}),
+            source: source.to_string(),
+            compiled: None,
+            assets: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_save_creates_versions() {
+        let storage = Storage::open(None).unwrap();
+        let v1 = save(&storage, None, &definition("a\n"), Some("Initial")).unwrap();
+        assert_eq!(v1.version, 1);
+
+        // Unchanged definitions don't create a version
+        let same = save(&storage, Some(&v1.app_id), &definition("a\n"), None).unwrap();
+        assert_eq!(same, v1);
+
+        let v2 = save(&storage, Some(&v1.app_id), &definition("b\n"), None).unwrap();
+        assert_eq!(v2.version, 2);
+
+        let current = load(&storage, &v1.app_id, None).unwrap().unwrap();
+        assert_eq!(current.definition.source, "b\n");
+        assert_eq!(versions(&storage, &v1.app_id).unwrap().len(), 2);
+        assert_eq!(list(&storage).unwrap()[0].current_version, 2);
+
+        assert!(save(&storage, Some("missing"), &definition("x"), None).is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let storage = Storage::open(None).unwrap();
+        let mut def = definition("one\ntwo\n");
+        def.assets.insert("logo.png".to_string(), "h1".to_string());
+        def.assets.insert("old.css".to_string(), "h2".to_string());
+        let v1 = save(&storage, None, &def, None).unwrap();
+
+        def.source = "one\nthree\n".to_string();
+        def.assets.insert("logo.png".to_string(), "h3".to_string());
+        def.assets.remove("old.css");
+        def.assets.insert("new.css".to_string(), "h4".to_string());
+        save(&storage, Some(&v1.app_id), &def, None).unwrap();
+
+        let diff = diff(&storage, &v1.app_id, 1, 2).unwrap();
+        assert!(diff.source_diff.contains("-two"));
+        assert!(diff.source_diff.contains("+three"));
+        assert!(!diff.name_changed);
+        assert_eq!(diff.assets_added, vec!["new.css"]);
+        assert_eq!(diff.assets_removed, vec!["old.css"]);
+        assert_eq!(diff.assets_changed, vec!["logo.png"]);
+    }
+
+    #[test]
+    fn test_rollback_and_delete() {
+        let storage = Storage::open(None).unwrap();
+        let v1 = save(&storage, None, &definition("a"), None).unwrap();
+        save(&storage, Some(&v1.app_id), &definition("b"), None).unwrap();
+
+        let v3 = rollback(&storage, &v1.app_id, 1).unwrap();
+        assert_eq!(v3.version, 3);
+        assert_eq!(v3.message.as_deref(), Some("Rollback to v1"));
+        let current = load(&storage, &v1.app_id, None).unwrap().unwrap();
+        assert_eq!(current.definition.source, "a");
+
+        assert!(delete(&storage, &v1.app_id).unwrap());
+        assert!(load(&storage, &v1.app_id, Some(1)).unwrap().is_none());
+        assert!(versions(&storage, &v1.app_id).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,472 @@
+//! src-tauri/src/artifacts.rs
+//! ==========================
+//! Content-addressed cache for generated artifacts.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Generated assets (compiled JS, synthesized audio, rendered images) are
+//! stored under their SHA-256 content hash in the app cache directory:
+//!
+//!     artifacts/
+//!         index.json
+//!         objects/ab/ab12...ef
+//!
+//! An optional cache `key` (e.g. a hash of the generator inputs) maps to
+//! the stored content, so callers can look up a previous result before
+//! redoing expensive work. When the total size exceeds the limit, the
+//! least recently used unpinned artifacts are evicted. Content larger than
+//! the limit is refused instead of being written and evicted at once.
+//!
+//! Usage:
+//!     ```rust
+//!     let store = ArtifactStore::open(cache_dir);
+//!     let meta = store.put(js.as_bytes(), "js", Some("tsx:1f3a..."))?;
+//!     let hit = store.lookup("tsx:1f3a...");
+//!     store.pin(&meta.hash, true)?;
+//!     ```
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Default cache size limit (1 GB)
+pub const DEFAULT_MAX_ARTIFACT_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Index file name inside the artifact directory
+const INDEX_FILE: &str = "index.json";
+
+/// Object directory name inside the artifact directory
+const OBJECTS_DIR: &str = "objects";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Metadata for a stored artifact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactMeta {
+    /// SHA-256 of the content (lowercase hex)
+    pub hash: String,
+    /// Artifact kind (e.g. js, audio, image)
+    pub kind: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Cache keys pointing at this content
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Pinned artifacts are never evicted
+    #[serde(default)]
+    pub pinned: bool,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+    /// Last access timestamp (RFC 3339)
+    pub last_accessed: String,
+    /// Absolute path of the stored object (not persisted)
+    #[serde(skip_deserializing)]
+    pub path: String,
+}
+
+/// Aggregate cache statistics.
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtifactStats {
+    /// Number of artifacts
+    pub count: usize,
+    /// Total size in bytes
+    pub total_bytes: u64,
+    /// Size of pinned artifacts in bytes
+    pub pinned_bytes: u64,
+    /// Size limit in bytes
+    pub max_bytes: u64,
+}
+
+/// Compute the SHA-256 of a byte slice as lowercase hex.
+pub fn content_hash(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+// ============================================
+// ARTIFACT STORE
+// ============================================
+
+/// Content-addressed artifact store.
+#[derive(Debug, Clone)]
+pub struct ArtifactStore {
+    /// Artifact directory (None disables the store)
+    root: Option<PathBuf>,
+
+    /// Metadata keyed by content hash
+    index: Arc<RwLock<HashMap<String, ArtifactMeta>>>,
+
+    /// Size limit before LRU eviction
+    max_bytes: u64,
+}
+
+impl ArtifactStore {
+    /// Open the store in `root`, loading the existing index.
+    pub fn open(root: Option<PathBuf>) -> Self {
+        let index = root
+            .as_deref()
+            .map(read_index)
+            .unwrap_or_default();
+
+        let store = Self {
+            root,
+            index: Arc::new(RwLock::new(HashMap::new())),
+            max_bytes: DEFAULT_MAX_ARTIFACT_BYTES,
+        };
+
+        // Drop index entries whose objects have disappeared
+        let entries = index
+            .into_iter()
+            .filter_map(|mut meta| {
+                let path = store.object_path(&meta.hash)?;
+                if !path.is_file() {
+                    return None;
+                }
+                meta.path = path.to_string_lossy().to_string();
+                Some((meta.hash.clone(), meta))
+            })
+            .collect();
+        *store.index.write().unwrap() = entries;
+
+        store
+    }
+
+    /// Set the size limit.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Path of the object file for a hash.
+    fn object_path(&self, hash: &str) -> Option<PathBuf> {
+        let root = self.root.as_ref()?;
+        let prefix = hash.get(..2)?;
+        Some(root.join(OBJECTS_DIR).join(prefix).join(hash))
+    }
+
+    /// Store content, returning its metadata.
+    ///
+    /// Storing content that already exists only updates its keys and
+    /// access time. Fails for content larger than the size limit.
+    pub fn put(&self, data: &[u8], kind: &str, key: Option<&str>) -> Result<ArtifactMeta, String> {
+        let size = u64::try_from(data.len()).unwrap_or(u64::MAX);
+        self.check_size(size)?;
+        let hash = content_hash(data);
+        let path = self
+            .object_path(&hash)
+            .ok_or_else(|| "No artifact cache directory available".to_string())?;
+        let now = Utc::now().to_rfc3339();
+
+        let meta = {
+            let mut index = self.index.write().unwrap();
+
+            // Keys are unique: move the key to the new content
+            if let Some(key) = key {
+                for meta in index.values_mut() {
+                    meta.keys.retain(|k| k != key);
+                }
+            }
+
+            if !path.is_file() {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+                }
+                fs::write(&path, data).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+            }
+
+            let meta = index.entry(hash.clone()).or_insert_with(|| ArtifactMeta {
+                hash: hash.clone(),
+                kind: kind.to_string(),
+                size,
+                keys: Vec::new(),
+                pinned: false,
+                created_at: now.clone(),
+                last_accessed: now.clone(),
+                path: path.to_string_lossy().to_string(),
+            });
+            meta.last_accessed = now;
+            if let Some(key) = key {
+                meta.keys.push(key.to_string());
+            }
+            meta.clone()
+        };
+
+        self.evict();
+        self.persist();
+        log::debug!("Artifact stored: {} ({} bytes)", meta.hash, meta.size);
+        Ok(meta)
+    }
+
+    /// Store the contents of a file.
+    pub fn put_file(&self, source: &Path, kind: &str, key: Option<&str>) -> Result<ArtifactMeta, String> {
+        let size = fs::metadata(source)
+            .map_err(|e| format!("Failed to read {source:?}: {e}"))?
+            .len();
+        self.check_size(size)?;
+        let data = fs::read(source).map_err(|e| format!("Failed to read {source:?}: {e}"))?;
+        self.put(&data, kind, key)
+    }
+
+    /// Fail if content of `size` bytes can never fit in the cache.
+    fn check_size(&self, size: u64) -> Result<(), String> {
+        if size > self.max_bytes {
+            return Err(format!(
+                "Artifact of {size} bytes exceeds the cache limit of {} bytes",
+                self.max_bytes
+            ));
+        }
+        Ok(())
+    }
+
+    /// Get an artifact by hash, updating its access time.
+    pub fn get(&self, hash: &str) -> Option<ArtifactMeta> {
+        let mut index = self.index.write().unwrap();
+        let meta = index.get_mut(hash)?;
+        meta.last_accessed = Utc::now().to_rfc3339();
+        Some(meta.clone())
+    }
+
+    /// Find the artifact stored under a cache key.
+    pub fn lookup(&self, key: &str) -> Option<ArtifactMeta> {
+        let hash = self
+            .index
+            .read()
+            .unwrap()
+            .values()
+            .find(|m| m.keys.iter().any(|k| k == key))
+            .map(|m| m.hash.clone())?;
+        self.get(&hash)
+    }
+
+    /// Read an artifact's content.
+    pub fn read(&self, hash: &str) -> Result<Vec<u8>, String> {
+        let meta = self.get(hash).ok_or_else(|| format!("Unknown artifact: {hash}"))?;
+        fs::read(&meta.path).map_err(|e| format!("Failed to read artifact {hash}: {e}"))
+    }
+
+    /// List artifacts, optionally filtered by kind (most recently used first).
+    pub fn list(&self, kind: Option<&str>) -> Vec<ArtifactMeta> {
+        let mut items: Vec<ArtifactMeta> = self
+            .index
+            .read()
+            .unwrap()
+            .values()
+            .filter(|m| kind.is_none() || kind == Some(m.kind.as_str()))
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| b.last_accessed.cmp(&a.last_accessed));
+        items
+    }
+
+    /// Pin or unpin an artifact.
+    pub fn pin(&self, hash: &str, pinned: bool) -> Result<ArtifactMeta, String> {
+        let meta = {
+            let mut index = self.index.write().unwrap();
+            let meta = index
+                .get_mut(hash)
+                .ok_or_else(|| format!("Unknown artifact: {hash}"))?;
+            meta.pinned = pinned;
+            meta.clone()
+        };
+        self.persist();
+        Ok(meta)
+    }
+
+    /// Remove one artifact (even if pinned), or all unpinned artifacts.
+    ///
+    /// # Returns
+    ///
+    /// Number of artifacts removed.
+    pub fn purge(&self, hash: Option<&str>) -> Result<usize, String> {
+        let removed: Vec<ArtifactMeta> = {
+            let mut index = self.index.write().unwrap();
+            match hash {
+                Some(h) => vec![index
+                    .remove(h)
+                    .ok_or_else(|| format!("Unknown artifact: {h}"))?],
+                None => {
+                    let unpinned: Vec<String> = index
+                        .values()
+                        .filter(|m| !m.pinned)
+                        .map(|m| m.hash.clone())
+                        .collect();
+                    unpinned.iter().filter_map(|h| index.remove(h)).collect()
+                }
+            }
+        };
+
+        for meta in &removed {
+            let _ = fs::remove_file(&meta.path);
+        }
+        self.persist();
+
+        log::info!("Purged {} artifact(s)", removed.len());
+        Ok(removed.len())
+    }
+
+    /// Get aggregate statistics.
+    pub fn stats(&self) -> ArtifactStats {
+        let index = self.index.read().unwrap();
+        ArtifactStats {
+            count: index.len(),
+            total_bytes: index.values().map(|m| m.size).sum(),
+            pinned_bytes: index.values().filter(|m| m.pinned).map(|m| m.size).sum(),
+            max_bytes: self.max_bytes,
+        }
+    }
+
+    /// Evict least recently used unpinned artifacts until under the limit.
+    fn evict(&self) {
+        let mut index = self.index.write().unwrap();
+        let mut total: u64 = index.values().map(|m| m.size).sum();
+        if total <= self.max_bytes {
+            return;
+        }
+
+        let mut candidates: Vec<(String, String, u64)> = index
+            .values()
+            .filter(|m| !m.pinned)
+            .map(|m| (m.last_accessed.clone(), m.hash.clone(), m.size))
+            .collect();
+        candidates.sort();
+
+        for (_, hash, size) in candidates {
+            if total <= self.max_bytes {
+                break;
+            }
+            if let Some(meta) = index.remove(&hash) {
+                let _ = fs::remove_file(&meta.path);
+                total -= size;
+                log::debug!("Evicted artifact {hash} ({size} bytes)");
+            }
+        }
+    }
+
+    /// Write the index to disk.
+    fn persist(&self) {
+        let Some(ref root) = self.root else {
+            return;
+        };
+        let items = self.list(None);
+        if let Err(e) = write_index(root, &items) {
+            log::warn!("Failed to persist artifact index: {e}");
+        }
+    }
+}
+
+// ============================================
+// PERSISTENCE
+// ============================================
+
+/// Read the index, returning an empty list if missing or invalid.
+fn read_index(root: &Path) -> Vec<ArtifactMeta> {
+    let path = root.join(INDEX_FILE);
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid artifact index {path:?}: {e}");
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Write the index to disk.
+fn write_index(root: &Path, items: &[ArtifactMeta]) -> Result<(), String> {
+    fs::create_dir_all(root).map_err(|e| format!("Failed to create {root:?}: {e}"))?;
+    let path = root.join(INDEX_FILE);
+    let content = serde_json::to_string_pretty(items)
+        .map_err(|e| format!("Failed to serialize artifact index: {e}"))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_put_is_content_addressed() {
+        let store = ArtifactStore::open(Some(temp_dir("artifacts")));
+
+        let a = store.put(b"console.log(1)", "js", Some("tsx:a")).unwrap();
+        let b = store.put(b"console.log(1)", "js", Some("tsx:b")).unwrap();
+
+        assert_eq!(a.hash, b.hash);
+        assert_eq!(store.stats().count, 1);
+        assert_eq!(store.lookup("tsx:a").unwrap().hash, a.hash);
+        assert_eq!(store.read(&a.hash).unwrap(), b"console.log(1)");
+    }
+
+    #[test]
+    fn test_eviction_skips_pinned() {
+        let store = ArtifactStore::open(Some(temp_dir("artifacts"))).with_max_bytes(10);
+
+        let pinned = store.put(b"123456", "bin", None).unwrap();
+        store.pin(&pinned.hash, true).unwrap();
+        store.put(b"abc", "bin", None).unwrap();
+        store.put(b"defgh", "bin", None).unwrap();
+
+        let stats = store.stats();
+        assert!(store.get(&pinned.hash).is_some());
+        assert!(stats.total_bytes <= 10);
+        assert_eq!(stats.pinned_bytes, 6);
+    }
+
+    #[test]
+    fn test_put_larger_than_limit_fails() {
+        let dir = temp_dir("artifacts");
+        let store = ArtifactStore::open(Some(dir.clone())).with_max_bytes(4);
+        let kept = store.put(b"1234", "bin", Some("small")).unwrap();
+
+        assert!(store.put(b"12345", "bin", Some("big")).is_err());
+        let source = dir.join("big.bin");
+        fs::write(&source, b"12345").unwrap();
+        assert!(store.put_file(&source, "bin", None).is_err());
+
+        assert!(store.lookup("big").is_none());
+        assert_eq!(store.lookup("small").unwrap().hash, kept.hash);
+        assert_eq!(store.stats().count, 1);
+        assert!(store
+            .object_path(&content_hash(b"12345"))
+            .is_some_and(|p| !p.exists()));
+    }
+
+    #[test]
+    fn test_purge_unpinned() {
+        let store = ArtifactStore::open(Some(temp_dir("artifacts")));
+        let keep = store.put(b"keep", "js", None).unwrap();
+        store.pin(&keep.hash, true).unwrap();
+        store.put(b"drop", "js", None).unwrap();
+
+        assert_eq!(store.purge(None).unwrap(), 1);
+        assert_eq!(store.list(None).len(), 1);
+        assert_eq!(store.purge(Some(&keep.hash)).unwrap(), 1);
+        assert!(store.purge(Some(&keep.hash)).is_err());
+    }
+
+    #[test]
+    fn test_index_persisted_across_opens() {
+        let dir = temp_dir("artifacts");
+        let store = ArtifactStore::open(Some(dir.clone()));
+        let meta = store.put(b"audio bytes", "audio", Some("tts:hello")).unwrap();
+
+        let reopened = ArtifactStore::open(Some(dir));
+        let found = reopened.lookup("tts:hello").unwrap();
+        assert_eq!(found.hash, meta.hash);
+        assert_eq!(found.path, meta.path);
+    }
+}
@@ -0,0 +1,707 @@
+//! src-tauri/src/bundler.rs
+//! ========================
+//! Multi-file compilation and bundling for generated apps.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Starting from an entry file, every reachable module in a set of virtual
+//! files is compiled with `compile_tsx_internal` and wrapped in a function
+//! registered under its path. Import and export declarations are rewritten
+//! to calls into a small runtime (`__require`, `__export`, ...), so the
+//! output is a single script:
+//!
+//! - `esm`: bare imports (e.g. `react`) stay as top-level ES imports and the
+//!   entry's statically known exports are re-exported.
+//! - `iife`: bare imports are read from globals (`react` → `React` by
+//!   default) and the entry's exports are assigned to `global_name`.
+//!
+//! Relative imports resolve like a bundler would: exact path, then the
+//! `.tsx/.ts/.jsx/.js/.mjs` extensions, then `index.*`. JSON files become
+//! a default export and CSS files inject a `<style>` element. Names
+//! re-exported with `export *` are only reachable at runtime, not as
+//! static ESM exports of the bundle.
+//!
+//! Usage:
+//!     ```rust
+//!     let files = BTreeMap::from([("src/App.tsx".into(), app), ("src/main.tsx".into(), main)]);
+//!     let js = bundler::bundle(&files, "src/main.tsx", &BundleOptions::default())?;
+//!     ```
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use swc_common::{sync::Lrc, FileName, SourceMap, Span, Spanned};
+use swc_ecma_ast::{
+    Decl, DefaultDecl, EsVersion, ExportSpecifier, ImportSpecifier, ModuleDecl, ModuleExportName,
+    ModuleItem, ObjectPatProp, Pat,
+};
+use swc_ecma_parser::{lexer::Lexer, EsSyntax, Parser, StringInput, Syntax};
+
+use crate::commands::compiler::{compile_tsx_internal, CompileOptions};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Extensions compiled as scripts, in resolution order
+const SCRIPT_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mjs"];
+
+/// Extensions picked up when bundling a directory
+const SOURCE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mjs", "json", "css"];
+
+/// Directories skipped when bundling a directory
+const SKIPPED_DIRS: &[&str] = &["node_modules", "dist", "build"];
+
+/// Globals used for common externals in `iife` bundles
+const DEFAULT_GLOBALS: &[(&str, &str)] = &[
+    ("react", "React"),
+    ("react-dom", "ReactDOM"),
+    ("react-dom/client", "ReactDOM"),
+];
+
+/// Module runtime shared by both output formats (ES5-compatible)
+const RUNTIME: &str = r#"var __cache = {};
+function __require(id) {
+  if (Object.prototype.hasOwnProperty.call(__externals, id)) return __externals[id];
+  if (__cache[id]) return __cache[id];
+  var exports = (__cache[id] = {});
+  __modules[id](exports);
+  return exports;
+}
+function __export(exports, getters) {
+  for (var name in getters) {
+    Object.defineProperty(exports, name, { enumerable: true, get: getters[name] });
+  }
+}
+function __exportAll(exports, source) {
+  Object.keys(source).forEach(function (name) {
+    if (name === "default" || name in exports) return;
+    Object.defineProperty(exports, name, { enumerable: true, get: function () { return source[name]; } });
+  });
+}
+function __default(m) {
+  return m && typeof m === "object" && "default" in m ? m.default : m;
+}
+"#;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Output module format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BundleFormat {
+    /// ES module with external imports kept as `import` statements
+    #[default]
+    Esm,
+    /// Self-executing script reading externals from globals
+    Iife,
+}
+
+/// Options for `bundle`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BundleOptions {
+    /// Per-file code generation options
+    #[serde(flatten)]
+    pub compile: CompileOptions,
+    /// Output format
+    pub format: BundleFormat,
+    /// External module → global variable (`iife` only)
+    pub globals: HashMap<String, String>,
+    /// Variable receiving the entry's exports (`iife` only)
+    pub global_name: Option<String>,
+}
+
+/// A resolved import.
+enum Dependency {
+    /// Another file in the bundle
+    Internal(String),
+    /// A bare specifier left to the host (e.g. `react`)
+    External(String),
+}
+
+impl Dependency {
+    /// Module id used with `__require`
+    fn id(&self) -> &str {
+        match self {
+            Self::Internal(id) | Self::External(id) => id,
+        }
+    }
+}
+
+/// A module rewritten for the bundle runtime.
+struct BundledModule {
+    /// Function body
+    body: String,
+    /// Imports in source order
+    dependencies: Vec<Dependency>,
+    /// Statically known export names
+    exports: Vec<String>,
+}
+
+// ============================================
+// BUNDLING
+// ============================================
+
+/// Bundle `files` (path → source) starting at `entry`.
+///
+/// Only modules reachable from the entry are included.
+pub fn bundle(
+    files: &BTreeMap<String, String>,
+    entry: &str,
+    options: &BundleOptions,
+) -> Result<String, String> {
+    let entry = normalize_path(entry).ok_or_else(|| format!("Invalid entry: {entry}"))?;
+    if !files.contains_key(&entry) {
+        return Err(format!("Entry not found: {entry}"));
+    }
+
+    let mut modules = BTreeMap::new();
+    let mut externals = BTreeSet::new();
+    let mut entry_exports = Vec::new();
+    let mut seen = HashSet::new();
+    let mut pending = vec![entry.clone()];
+
+    while let Some(id) = pending.pop() {
+        if !seen.insert(id.clone()) {
+            continue;
+        }
+        let module = bundle_module(&id, &files[&id], files, &options.compile)?;
+        for dependency in module.dependencies {
+            match dependency {
+                Dependency::Internal(dep) => pending.push(dep),
+                Dependency::External(dep) => {
+                    externals.insert(dep);
+                }
+            }
+        }
+        if id == entry {
+            entry_exports = module.exports;
+        }
+        modules.insert(id, module.body);
+    }
+
+    let mut registry = String::from("var __modules = {\n");
+    for (id, body) in &modules {
+        let _ = writeln!(registry, "{}: function (exports) {{\n{body}\n}},", quote(id));
+    }
+    registry.push_str("};\n");
+
+    let output = match options.format {
+        BundleFormat::Esm => {
+            let mut out = String::new();
+            let mut map = Vec::new();
+            for (index, external) in externals.iter().enumerate() {
+                let _ = writeln!(out, "import * as __ext{index} from {};", quote(external));
+                map.push(format!("{}: __ext{index}", quote(external)));
+            }
+            let _ = writeln!(out, "var __externals = {{ {} }};", map.join(", "));
+            out.push_str(&registry);
+            out.push_str(RUNTIME);
+            let _ = writeln!(out, "var __entry = __require({});", quote(&entry));
+
+            if entry_exports.iter().any(|name| name == "default") {
+                out.push_str("export default __entry.default;\n");
+            }
+            let named: Vec<&str> = entry_exports
+                .iter()
+                .map(String::as_str)
+                .filter(|name| *name != "default" && is_identifier(name))
+                .collect();
+            if !named.is_empty() {
+                let _ = writeln!(out, "export var {{ {} }} = __entry;", named.join(", "));
+            }
+            out
+        }
+        BundleFormat::Iife => {
+            let mut map = Vec::new();
+            for external in &externals {
+                let global = options
+                    .globals
+                    .get(external)
+                    .map(String::as_str)
+                    .or_else(|| {
+                        DEFAULT_GLOBALS
+                            .iter()
+                            .find(|(module, _)| module == external)
+                            .map(|(_, global)| *global)
+                    })
+                    .ok_or_else(|| format!("No global configured for external '{external}'"))?;
+                map.push(format!("{}: {global}", quote(external)));
+            }
+
+            let mut out = match &options.global_name {
+                Some(name) => format!("var {name} = (function () {{\n"),
+                None => "(function () {\n".to_string(),
+            };
+            let _ = writeln!(out, "var __externals = {{ {} }};", map.join(", "));
+            out.push_str(&registry);
+            out.push_str(RUNTIME);
+            let _ = writeln!(out, "return __require({});", quote(&entry));
+            out.push_str("})();\n");
+            out
+        }
+    };
+
+    log::debug!(
+        "Bundled {} modules ({} externals) from {entry}",
+        modules.len(),
+        externals.len()
+    );
+    Ok(output)
+}
+
+/// Read all source files below `root` into a path → source map.
+pub fn read_project_dir(root: &Path) -> Result<BTreeMap<String, String>, String> {
+    fn walk(
+        root: &Path,
+        dir: &Path,
+        files: &mut BTreeMap<String, String>,
+    ) -> Result<(), String> {
+        let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {dir:?}: {e}"))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() {
+                if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_str()) {
+                    walk(root, &path, files)?;
+                }
+                continue;
+            }
+            let supported = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext));
+            if !supported {
+                continue;
+            }
+            let source =
+                fs::read_to_string(&path).map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|e| e.to_string())?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(relative, source);
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    walk(root, root, &mut files)?;
+    Ok(files)
+}
+
+/// Compile and rewrite one module.
+fn bundle_module(
+    id: &str,
+    source: &str,
+    files: &BTreeMap<String, String>,
+    options: &CompileOptions,
+) -> Result<BundledModule, String> {
+    match extension(id) {
+        "json" => {
+            serde_json::from_str::<serde_json::Value>(source)
+                .map_err(|e| format!("{id}: invalid JSON: {e}"))?;
+            Ok(BundledModule {
+                body: format!(
+                    "__export(exports, {{ \"default\": function () {{ return __json; }} }});\n\
+                     var __json = {source};"
+                ),
+                dependencies: Vec::new(),
+                exports: vec!["default".to_string()],
+            })
+        }
+        "css" => Ok(BundledModule {
+            body: format!(
+                "var style = document.createElement(\"style\");\n\
+                 style.textContent = {};\n\
+                 document.head.appendChild(style);",
+                quote(source)
+            ),
+            dependencies: Vec::new(),
+            exports: Vec::new(),
+        }),
+        _ => {
+            let js = compile_tsx_internal(source, options).map_err(|e| format!("{id}: {e}"))?;
+            rewrite_module(id, &js, files)
+        }
+    }
+}
+
+/// Rewrite the import/export declarations of compiled JavaScript.
+fn rewrite_module(
+    id: &str,
+    js: &str,
+    files: &BTreeMap<String, String>,
+) -> Result<BundledModule, String> {
+    let cm: Lrc<SourceMap> = Lrc::default();
+    let fm = cm.new_source_file(FileName::Custom(id.into()).into(), js.to_string());
+    let lexer = Lexer::new(
+        Syntax::Es(EsSyntax::default()),
+        EsVersion::EsNext,
+        StringInput::from(&*fm),
+        None,
+    );
+    let module = Parser::new_from(lexer)
+        .parse_module()
+        .map_err(|e| format!("{id}: {e:?}"))?;
+
+    let base = fm.start_pos.0;
+    let text = |span: Span| &js[(span.lo.0 - base) as usize..(span.hi.0 - base) as usize];
+    let export_name = |name: &ModuleExportName| match name {
+        ModuleExportName::Ident(ident) => text(ident.span).to_string(),
+        ModuleExportName::Str(s) => unquote(text(s.span)),
+    };
+    // Text of a declaration without its `export` / `export default` prefix
+    let strip_export = |span: Span, default: bool| {
+        let rest = text(span).trim_start_matches("export").trim_start();
+        if default {
+            rest.trim_start_matches("default").trim_start().to_string()
+        } else {
+            rest.to_string()
+        }
+    };
+
+    let mut dependencies = Vec::new();
+    let mut resolve = |specifier: &str| -> Result<String, String> {
+        let dependency = resolve_import(id, specifier, files)?;
+        let call = format!("__require({})", quote(dependency.id()));
+        dependencies.push(dependency);
+        Ok(call)
+    };
+
+    // (start, end, replacement) in source order
+    let mut replacements: Vec<(usize, usize, String)> = Vec::new();
+    // (exported name, expression)
+    let mut getters: Vec<(String, String)> = Vec::new();
+
+    for item in &module.body {
+        let ModuleItem::ModuleDecl(decl) = item else {
+            continue;
+        };
+        let span = decl.span();
+        let replacement = match decl {
+            ModuleDecl::Import(import) => {
+                let require = resolve(&unquote(text(import.src.span)))?;
+                let mut lines = Vec::new();
+                let mut named = Vec::new();
+                for specifier in &import.specifiers {
+                    match specifier {
+                        ImportSpecifier::Default(s) => {
+                            lines.push(format!("var {} = __default({require});", text(s.local.span)));
+                        }
+                        ImportSpecifier::Namespace(s) => {
+                            lines.push(format!("var {} = {require};", text(s.local.span)));
+                        }
+                        ImportSpecifier::Named(s) => {
+                            let local = text(s.local.span).to_string();
+                            let imported = s.imported.as_ref().map_or(local.clone(), export_name);
+                            named.push(format!("{}: {local}", quote(&imported)));
+                        }
+                    }
+                }
+                if !named.is_empty() {
+                    lines.push(format!("var {{ {} }} = {require};", named.join(", ")));
+                }
+                if lines.is_empty() {
+                    lines.push(format!("{require};"));
+                }
+                lines.join("\n")
+            }
+            ModuleDecl::ExportDecl(export) => {
+                let mut names = Vec::new();
+                match &export.decl {
+                    Decl::Fn(f) => names.push(text(f.ident.span).to_string()),
+                    Decl::Class(c) => names.push(text(c.ident.span).to_string()),
+                    Decl::Var(var) => {
+                        for declarator in &var.decls {
+                            collect_bindings(&declarator.name, &text, &mut names);
+                        }
+                    }
+                    _ => {}
+                }
+                getters.extend(names.into_iter().map(|name| (name.clone(), name)));
+                strip_export(span, false)
+            }
+            ModuleDecl::ExportDefaultDecl(export) => {
+                let ident = match &export.decl {
+                    DefaultDecl::Fn(f) => f.ident.as_ref(),
+                    DefaultDecl::Class(c) => c.ident.as_ref(),
+                    DefaultDecl::TsInterfaceDecl(_) => continue,
+                };
+                let rest = strip_export(span, true);
+                match ident {
+                    Some(ident) => {
+                        getters.push(("default".to_string(), text(ident.span).to_string()));
+                        rest
+                    }
+                    None => {
+                        getters.push(("default".to_string(), "__default_export".to_string()));
+                        format!("var __default_export = {rest};")
+                    }
+                }
+            }
+            ModuleDecl::ExportDefaultExpr(_) => {
+                getters.push(("default".to_string(), "__default_export".to_string()));
+                let rest = strip_export(span, true);
+                let rest = rest.trim_end_matches(';');
+                format!("var __default_export = {rest};")
+            }
+            ModuleDecl::ExportNamed(export) => match &export.src {
+                Some(src) => {
+                    let require = resolve(&unquote(text(src.span)))?;
+                    for specifier in &export.specifiers {
+                        match specifier {
+                            ExportSpecifier::Named(s) => {
+                                let orig = export_name(&s.orig);
+                                let exported = s.exported.as_ref().map_or(orig.clone(), export_name);
+                                getters.push((exported, format!("{require}[{}]", quote(&orig))));
+                            }
+                            ExportSpecifier::Namespace(s) => {
+                                getters.push((export_name(&s.name), require.clone()));
+                            }
+                            ExportSpecifier::Default(s) => {
+                                getters.push((
+                                    text(s.exported.span).to_string(),
+                                    format!("__default({require})"),
+                                ));
+                            }
+                        }
+                    }
+                    format!("{require};")
+                }
+                None => {
+                    for specifier in &export.specifiers {
+                        if let ExportSpecifier::Named(s) = specifier {
+                            let orig = export_name(&s.orig);
+                            let exported = s.exported.as_ref().map_or(orig.clone(), export_name);
+                            getters.push((exported, orig));
+                        }
+                    }
+                    String::new()
+                }
+            },
+            ModuleDecl::ExportAll(export) => {
+                let require = resolve(&unquote(text(export.src.span)))?;
+                format!("__exportAll(exports, {require});")
+            }
+            _ => return Err(format!("{id}: unsupported module declaration")),
+        };
+        replacements.push((
+            (span.lo.0 - base) as usize,
+            (span.hi.0 - base) as usize,
+            replacement,
+        ));
+    }
+
+    let mut body = js.to_string();
+    for (start, end, replacement) in replacements.iter().rev() {
+        body.replace_range(*start..*end, replacement);
+    }
+
+    if !getters.is_empty() {
+        let header = getters
+            .iter()
+            .map(|(name, expr)| format!("{}: function () {{ return {expr}; }}", quote(name)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        body = format!("__export(exports, {{ {header} }});\n{body}");
+    }
+
+    Ok(BundledModule {
+        body,
+        dependencies,
+        exports: getters.into_iter().map(|(name, _)| name).collect(),
+    })
+}
+
+/// Collect the names bound by a declaration pattern.
+fn collect_bindings<'a>(pat: &Pat, text: &impl Fn(Span) -> &'a str, names: &mut Vec<String>) {
+    match pat {
+        Pat::Ident(binding) => names.push(text(binding.id.span).to_string()),
+        Pat::Array(array) => {
+            for elem in array.elems.iter().flatten() {
+                collect_bindings(elem, text, names);
+            }
+        }
+        Pat::Object(object) => {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(kv) => collect_bindings(&kv.value, text, names),
+                    ObjectPatProp::Assign(assign) => names.push(text(assign.key.span()).to_string()),
+                    ObjectPatProp::Rest(rest) => collect_bindings(&rest.arg, text, names),
+                }
+            }
+        }
+        Pat::Rest(rest) => collect_bindings(&rest.arg, text, names),
+        Pat::Assign(assign) => collect_bindings(&assign.left, text, names),
+        _ => {}
+    }
+}
+
+// ============================================
+// RESOLUTION
+// ============================================
+
+/// Resolve an import specifier relative to the importing module.
+fn resolve_import(
+    importer: &str,
+    specifier: &str,
+    files: &BTreeMap<String, String>,
+) -> Result<Dependency, String> {
+    let relative = specifier.starts_with("./") || specifier.starts_with("../");
+    if !relative && !specifier.starts_with('/') {
+        return Ok(Dependency::External(specifier.to_string()));
+    }
+
+    let joined = if relative {
+        let dir = importer.rsplit_once('/').map_or("", |(dir, _)| dir);
+        format!("{dir}/{specifier}")
+    } else {
+        specifier.to_string()
+    };
+    let base = normalize_path(&joined)
+        .ok_or_else(|| format!("{importer}: import escapes the project: {specifier}"))?;
+
+    let candidates = std::iter::once(base.clone())
+        .chain(SCRIPT_EXTENSIONS.iter().map(|ext| format!("{base}.{ext}")))
+        .chain(SCRIPT_EXTENSIONS.iter().map(|ext| format!("{base}/index.{ext}")));
+    for candidate in candidates {
+        if files.contains_key(&candidate) {
+            return Ok(Dependency::Internal(candidate));
+        }
+    }
+    Err(format!("{importer}: cannot resolve '{specifier}'"))
+}
+
+/// Normalize a `/`-separated path, resolving `.` and `..`.
+///
+/// Returns `None` if the path climbs above the root.
+fn normalize_path(path: &str) -> Option<String> {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            part => parts.push(part),
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// File extension of a module id.
+fn extension(id: &str) -> &str {
+    id.rsplit_once('.').map_or("", |(_, ext)| ext)
+}
+
+/// Quote a string as a JavaScript string literal.
+fn quote(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+/// Strip the quotes of a JavaScript string literal.
+fn unquote(literal: &str) -> String {
+    literal
+        .get(1..literal.len().saturating_sub(1))
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Whether a name can be used as a binding in `export var { ... }`.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> BTreeMap<String, String> {
+        BTreeMap::from([
+            (
+                "src/main.tsx".to_string(),
+                "import React from 'react';\n\
+                 import App, { title } from './App';\n\
+                 import config from './config.json';\n\
+                 export const name: string = config.name + React.version;\n\
+                 export default function main() { return <App title={title} />; }\n"
+                    .to_string(),
+            ),
+            (
+                "src/App/index.tsx".to_string(),
+                "import React from 'react';\n\
+                 export const title = 'Hi';\n\
+                 export default ({ title }: { title: string }) => <h1>{title}</h1>;\n"
+                    .to_string(),
+            ),
+            ("src/config.json".to_string(), r#"{"name": "demo"}"#.to_string()),
+            ("src/unused.ts".to_string(), "export const x = 1;".to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_bundle_esm() {
+        let js = bundle(&project(), "src/main.tsx", &BundleOptions::default()).unwrap();
+
+        assert!(js.contains("import * as __ext0 from \"react\";"));
+        assert!(js.contains("\"src/App/index.tsx\": function (exports)"));
+        assert!(js.contains("\"src/config.json\": function (exports)"));
+        assert!(!js.contains("src/unused.ts"));
+        assert!(js.contains("export default __entry.default;"));
+        assert!(js.contains("export var { name } = __entry;"));
+        // Module-level import/export declarations are rewritten
+        assert!(!js.contains("from './App'"));
+        assert!(!js.contains("export const"));
+    }
+
+    #[test]
+    fn test_bundle_iife_globals() {
+        let options = BundleOptions {
+            format: BundleFormat::Iife,
+            global_name: Some("DemoApp".to_string()),
+            ..Default::default()
+        };
+        let js = bundle(&project(), "./src/main.tsx", &options).unwrap();
+        assert!(js.starts_with("var DemoApp = (function () {"));
+        assert!(js.contains("\"react\": React"));
+        assert!(!js.contains("import "));
+
+        let files = BTreeMap::from([(
+            "a.ts".to_string(),
+            "import lodash from 'lodash'; export default lodash;".to_string(),
+        )]);
+        let err = bundle(&files, "a.ts", &options).unwrap_err();
+        assert!(err.contains("lodash"));
+    }
+
+    #[test]
+    fn test_resolution_errors() {
+        let files = BTreeMap::from([(
+            "src/a.ts".to_string(),
+            "import './missing';".to_string(),
+        )]);
+        let err = bundle(&files, "src/a.ts", &BundleOptions::default()).unwrap_err();
+        assert!(err.contains("cannot resolve './missing'"));
+
+        assert!(bundle(&files, "nope.ts", &BundleOptions::default()).is_err());
+        assert_eq!(normalize_path("src/./a/../b.ts").as_deref(), Some("src/b.ts"));
+        assert_eq!(normalize_path("../outside.ts"), None);
+    }
+}
@@ -0,0 +1,191 @@
+//! src-tauri/src/commands/apps.rs
+//! ===============================
+//! Tauri commands for saving, loading and versioning generated apps.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const v1 = await invoke('app_save', { definition: { name: 'Todo', source, compiled } });
+//!     await invoke('app_save', { id: v1.app_id, definition: edited, message: 'Dark mode' });
+//!     const diff = await invoke('app_diff', { id: v1.app_id, from: 1, to: 2 });
+//!     await invoke('app_rollback', { id: v1.app_id, version: 1 });
+//!     const app = await invoke('app_load', { id: v1.app_id });
+//!     await invoke('app_export', { id: v1.app_id, dest: '/home/me/todo', template: 'vite' });
+//!     ```
+
+use std::path::Path;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::apps::{self, AppDefinition, AppDiff, AppSnapshot, AppSummary, AppVersionInfo};
+use crate::artifacts::ArtifactStore;
+use crate::export::{self, ExportTemplate};
+use crate::preview::PreviewManager;
+use crate::storage::Storage;
+
+/// Build an app error with the given code.
+fn app_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Reload a running preview of the app with its current version.
+fn refresh_preview(
+    storage: &Storage,
+    artifacts: &ArtifactStore,
+    previews: &PreviewManager,
+    id: &str,
+) {
+    if !previews.is_running(id) {
+        return;
+    }
+    let files = apps::load(storage, id, None).and_then(|snapshot| {
+        let snapshot = snapshot.ok_or_else(|| format!("Unknown app: {id}"))?;
+        export::project_files(&snapshot.definition, artifacts, ExportTemplate::Html)
+    });
+    match files {
+        Ok(files) => {
+            previews.update(id, files);
+        }
+        Err(e) => log::warn!("Failed to refresh preview for {id}: {e}"),
+    }
+}
+
+/// Save a generated app as a new version.
+///
+/// # Arguments
+///
+/// * `id` - App id (omit to create a new app)
+/// * `definition` - Component tree, source, compiled code and assets
+/// * `message` - Optional description of the change
+///
+/// # Returns
+///
+/// The saved version (the current one if nothing changed).
+#[tauri::command]
+pub fn app_save(
+    storage: State<'_, Storage>,
+    artifacts: State<'_, ArtifactStore>,
+    previews: State<'_, PreviewManager>,
+    id: Option<String>,
+    definition: AppDefinition,
+    message: Option<String>,
+) -> CommandResult<AppVersionInfo> {
+    log::info!("Command: app_save id={id:?} name={}", definition.name);
+    let saved = apps::save(&storage, id.as_deref(), &definition, message.as_deref())
+        .map_err(|e| app_error("APP_SAVE_ERROR", e))?;
+    refresh_preview(&storage, &artifacts, &previews, &saved.app_id);
+    Ok(saved)
+}
+
+/// Load an app at a version (the current version when omitted).
+#[tauri::command]
+pub fn app_load(
+    storage: State<'_, Storage>,
+    id: String,
+    version: Option<u32>,
+) -> CommandResult<AppSnapshot> {
+    log::debug!("Command: app_load id={id} version={version:?}");
+    apps::load(&storage, &id, version)
+        .map_err(|e| app_error("APP_ERROR", e))?
+        .ok_or_else(|| app_error("APP_NOT_FOUND", format!("Unknown app or version: {id}")))
+}
+
+/// List saved apps, most recently updated first.
+#[tauri::command]
+pub fn app_list(storage: State<'_, Storage>) -> CommandResult<Vec<AppSummary>> {
+    log::debug!("Command: app_list");
+    apps::list(&storage).map_err(|e| app_error("APP_ERROR", e))
+}
+
+/// List an app's versions, newest first.
+#[tauri::command]
+pub fn app_versions(
+    storage: State<'_, Storage>,
+    id: String,
+) -> CommandResult<Vec<AppVersionInfo>> {
+    log::debug!("Command: app_versions id={id}");
+    apps::versions(&storage, &id).map_err(|e| app_error("APP_ERROR", e))
+}
+
+/// Compare two versions of an app.
+#[tauri::command]
+pub fn app_diff(
+    storage: State<'_, Storage>,
+    id: String,
+    from: u32,
+    to: u32,
+) -> CommandResult<AppDiff> {
+    log::debug!("Command: app_diff id={id} from={from} to={to}");
+    apps::diff(&storage, &id, from, to).map_err(|e| app_error("APP_NOT_FOUND", e))
+}
+
+/// Roll back to a previous version (saved as a new version).
+#[tauri::command]
+pub fn app_rollback(
+    storage: State<'_, Storage>,
+    artifacts: State<'_, ArtifactStore>,
+    previews: State<'_, PreviewManager>,
+    id: String,
+    version: u32,
+) -> CommandResult<AppVersionInfo> {
+    log::info!("Command: app_rollback id={id} version={version}");
+    let restored =
+        apps::rollback(&storage, &id, version).map_err(|e| app_error("APP_NOT_FOUND", e))?;
+    refresh_preview(&storage, &artifacts, &previews, &id);
+    Ok(restored)
+}
+
+/// Delete an app and its version history.
+///
+/// # Returns
+///
+/// `true` if the app existed.
+#[tauri::command]
+pub fn app_delete(storage: State<'_, Storage>, id: String) -> CommandResult<bool> {
+    log::info!("Command: app_delete id={id}");
+    apps::delete(&storage, &id).map_err(|e| app_error("APP_ERROR", e))
+}
+
+/// Export an app as a standalone project.
+///
+/// # Arguments
+///
+/// * `id` - App id
+/// * `dest` - Destination directory (must not exist or be empty)
+/// * `template` - `vite` (default) or `html`
+/// * `version` - Version to export (defaults to the current version)
+///
+/// # Returns
+///
+/// Paths of the written files, relative to `dest`.
+#[tauri::command]
+pub fn app_export(
+    storage: State<'_, Storage>,
+    artifacts: State<'_, ArtifactStore>,
+    id: String,
+    dest: String,
+    template: Option<ExportTemplate>,
+    version: Option<u32>,
+) -> CommandResult<Vec<String>> {
+    log::info!("Command: app_export id={id} dest={dest} template={template:?}");
+
+    let snapshot = apps::load(&storage, &id, version)
+        .map_err(|e| app_error("APP_ERROR", e))?
+        .ok_or_else(|| app_error("APP_NOT_FOUND", format!("Unknown app or version: {id}")))?;
+
+    export::export_app(
+        &snapshot.definition,
+        &artifacts,
+        Path::new(&dest),
+        template.unwrap_or_default(),
+    )
+    .map_err(|e| app_error("APP_EXPORT_ERROR", e))
+}
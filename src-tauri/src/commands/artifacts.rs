@@ -0,0 +1,122 @@
+//! src-tauri/src/commands/artifacts.rs
+//! ====================================
+//! Tauri commands for the content-addressed artifact cache.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const hit = await invoke('artifact_lookup', { key: `tsx:${sourceHash}` });
+//!     const meta = hit ?? await invoke('artifact_put', {
+//!         kind: 'js', key: `tsx:${sourceHash}`, content: compiledJs
+//!     });
+//!     await invoke('artifact_pin', { hash: meta.hash, pinned: true });
+//!     await invoke('artifact_purge');
+//!     ```
+
+use std::path::Path;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::artifacts::{ArtifactMeta, ArtifactStats, ArtifactStore};
+
+/// Build an artifact error with the given code.
+fn artifact_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Store an artifact from text content or from a file.
+///
+/// # Arguments
+///
+/// * `kind` - Artifact kind (e.g. js, audio, image)
+/// * `key` - Cache key for later lookup (optional)
+/// * `content` - UTF-8 content (mutually exclusive with `path`)
+/// * `path` - File to copy into the store (e.g. synthesized audio)
+#[tauri::command]
+pub fn artifact_put(
+    store: State<'_, ArtifactStore>,
+    kind: String,
+    key: Option<String>,
+    content: Option<String>,
+    path: Option<String>,
+) -> CommandResult<ArtifactMeta> {
+    log::debug!("Command: artifact_put kind={kind} key={key:?}");
+
+    let result = match (content, path) {
+        (Some(content), None) => store.put(content.as_bytes(), &kind, key.as_deref()),
+        (None, Some(path)) => store.put_file(Path::new(&path), &kind, key.as_deref()),
+        _ => Err("Provide exactly one of content or path".to_string()),
+    };
+
+    result.map_err(|e| artifact_error("ARTIFACT_ERROR", e))
+}
+
+/// Get an artifact by content hash.
+#[tauri::command]
+pub fn artifact_get(store: State<'_, ArtifactStore>, hash: String) -> CommandResult<ArtifactMeta> {
+    log::debug!("Command: artifact_get hash={hash}");
+    store
+        .get(&hash)
+        .ok_or_else(|| artifact_error("ARTIFACT_NOT_FOUND", format!("Unknown artifact: {hash}")))
+}
+
+/// Find the artifact stored under a cache key (null on miss).
+#[tauri::command]
+pub fn artifact_lookup(
+    store: State<'_, ArtifactStore>,
+    key: String,
+) -> CommandResult<Option<ArtifactMeta>> {
+    log::debug!("Command: artifact_lookup key={key}");
+    Ok(store.lookup(&key))
+}
+
+/// List artifacts, optionally filtered by kind.
+#[tauri::command]
+pub fn artifact_list(
+    store: State<'_, ArtifactStore>,
+    kind: Option<String>,
+) -> CommandResult<Vec<ArtifactMeta>> {
+    log::debug!("Command: artifact_list kind={kind:?}");
+    Ok(store.list(kind.as_deref()))
+}
+
+/// Pin or unpin an artifact (pinned artifacts are never evicted).
+#[tauri::command]
+pub fn artifact_pin(
+    store: State<'_, ArtifactStore>,
+    hash: String,
+    pinned: bool,
+) -> CommandResult<ArtifactMeta> {
+    log::info!("Command: artifact_pin hash={hash} pinned={pinned}");
+    store
+        .pin(&hash, pinned)
+        .map_err(|e| artifact_error("ARTIFACT_NOT_FOUND", e))
+}
+
+/// Remove one artifact, or all unpinned artifacts when no hash is given.
+///
+/// # Returns
+///
+/// Number of artifacts removed.
+#[tauri::command]
+pub fn artifact_purge(store: State<'_, ArtifactStore>, hash: Option<String>) -> CommandResult<usize> {
+    log::info!("Command: artifact_purge hash={hash:?}");
+    store
+        .purge(hash.as_deref())
+        .map_err(|e| artifact_error("ARTIFACT_NOT_FOUND", e))
+}
+
+/// Get cache size statistics.
+#[tauri::command]
+pub fn artifact_stats(store: State<'_, ArtifactStore>) -> CommandResult<ArtifactStats> {
+    log::debug!("Command: artifact_stats");
+    Ok(store.stats())
+}
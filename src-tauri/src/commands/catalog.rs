@@ -0,0 +1,97 @@
+//! src-tauri/src/commands/catalog.rs
+//! ==================================
+//! Tauri commands for the API key service catalog.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The catalog gives the UI provider-specific hints (key shape, where to
+//! create a key) and backs the format check in `add_api_key` and
+//! `update_api_key`. `validate_api_key_format` runs the same check without
+//! storing anything, for feedback while a key is typed.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const services = await invoke('list_services');
+//!     await invoke('add_custom_service', {
+//!         service: { id: 'groq', name: 'Groq', pattern: '^gsk_[A-Za-z0-9]+$' }
+//!     });
+//!     await invoke('validate_api_key_format', { service: 'groq', key: 'gsk_...' });
+//!     await invoke('delete_custom_service', { id: 'groq' });
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::catalog::{CustomService, ServiceCatalog, ServiceInfo};
+
+/// Build a catalog error with the given code.
+fn catalog_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// List built-in and custom services.
+#[tauri::command]
+pub fn list_services(catalog: State<'_, ServiceCatalog>) -> CommandResult<Vec<ServiceInfo>> {
+    log::debug!("Command: list_services");
+    Ok(catalog.list())
+}
+
+/// Define a custom service.
+///
+/// # Arguments
+///
+/// * `service` - Id (lowercase letters and digits), optional name,
+///   validation regex, docs URL, and key hint
+///
+/// # Returns
+///
+/// The created service.
+#[tauri::command]
+pub fn add_custom_service(
+    catalog: State<'_, ServiceCatalog>,
+    service: CustomService,
+) -> CommandResult<ServiceInfo> {
+    log::info!("Command: add_custom_service id={}", service.id);
+    catalog
+        .add_custom(service)
+        .map_err(|e| catalog_error("INVALID_SERVICE", e))
+}
+
+/// Remove a custom service. Keys stored for it are kept.
+#[tauri::command]
+pub fn delete_custom_service(catalog: State<'_, ServiceCatalog>, id: String) -> CommandResult<()> {
+    log::info!("Command: delete_custom_service id={id}");
+    catalog
+        .remove_custom(&id)
+        .map_err(|e| catalog_error("SERVICE_NOT_FOUND", e))
+}
+
+/// Check a key against the format of a service without storing it.
+///
+/// # Arguments
+///
+/// * `service` - Service id
+/// * `key` - Key value
+///
+/// # Returns
+///
+/// Nothing if the key has the expected shape; `INVALID_KEY_FORMAT`
+/// describing the mismatch otherwise.
+#[tauri::command]
+pub fn validate_api_key_format(
+    catalog: State<'_, ServiceCatalog>,
+    service: String,
+    key: String,
+) -> CommandResult<()> {
+    log::debug!("Command: validate_api_key_format service={service}");
+    catalog
+        .validate(&service, &key)
+        .map_err(|e| catalog_error("INVALID_KEY_FORMAT", e))
+}
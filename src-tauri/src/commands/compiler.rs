@@ -12,19 +12,63 @@
 //!     import { invoke } from '@tauri-apps/api/tauri';
 //!
 //!     const result = await invoke<CompileResult>('compile_tsx', {
-//!         code: 'const Button = () => <button>Hello</button>;'
+//!         code: 'const Button = () => <button>Hello</button>;',
+//!         options: { minify: true, target: 'es2017' }
 //!     });
 //!     ```
 
-use serde::Serialize;
-use swc_common::{comments::NoopComments, sync::Lrc, FileName, Globals, Mark, SourceMap, GLOBALS};
-use swc_ecma_ast::{EsVersion, Program};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Duration;
+use swc_common::comments::{Comments, NoopComments, SingleThreadedComments};
+use swc_common::{sync::Lrc, FileName, Globals, Mark, SourceMap, GLOBALS};
+use swc_ecma_ast::{EsVersion, ModuleDecl, ModuleItem, Program};
 use swc_ecma_codegen::{text_writer::JsWriter, Config as CodegenConfig, Emitter};
-use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
+use swc_ecma_parser::{error::Error as ParseError, lexer::Lexer, EsSyntax, Parser, StringInput, Syntax, TsSyntax};
 use swc_ecma_transforms_base::{fixer::fixer, hygiene::hygiene, resolver};
 use swc_ecma_transforms_react::{jsx, Options as JsxOptions, Runtime};
 use swc_ecma_transforms_typescript::strip;
 
+use tauri::{AppHandle, Manager, State};
+
+use super::{error_chain, CommandError, CommandResult};
+use crate::analyzer::{self, ComponentInfo};
+use crate::artifacts::{content_hash, ArtifactStore};
+use crate::bundler::{self, BundleOptions};
+use crate::mdx;
+use crate::typecheck;
+use crate::watch::{CompileWatcher, WatchInfo, UPDATED_EVENT};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Compiled outputs kept in memory (oldest are dropped first)
+const MAX_MEMORY_ENTRIES: usize = 256;
+
+/// Artifact kind used for cached compiler output
+const CACHE_ARTIFACT_KIND: &str = "js";
+
+/// Source lines shown before and after the error line in a code frame
+const CODE_FRAME_CONTEXT: usize = 1;
+
+/// Largest accepted source (2 MB)
+pub const MAX_SOURCE_BYTES: usize = 2 * 1024 * 1024;
+
+/// Deepest accepted bracket and JSX element nesting (the parser recurses per level)
+pub const MAX_NESTING_DEPTH: usize = 256;
+
+/// Wall-clock limit for one compilation
+pub const COMPILE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Stack size of the compiler worker thread
+const COMPILER_STACK_BYTES: usize = 16 * 1024 * 1024;
+
 // ============================================
 // TYPES
 // ============================================
@@ -39,33 +83,678 @@ pub struct CompileResult {
     pub code: Option<String>,
     /// Error message (None if success)
     pub error: Option<String>,
+    /// Located errors, for highlighting in the editor (empty if success)
+    pub diagnostics: Vec<Diagnostic>,
+    /// Machine-readable reason for resource limit failures
+    /// (`INPUT_TOO_LARGE`, `NESTING_TOO_DEEP`, `COMPILE_TIMEOUT`, `COMPILER_CRASHED`)
+    pub error_code: Option<String>,
+    /// Type errors when `typecheck` was requested (None if not checked)
+    pub type_errors: Option<Vec<Diagnostic>>,
 }
 
 impl CompileResult {
     /// Create a successful result
-    fn success(code: String) -> Self {
+    pub fn success(code: String) -> Self {
         Self {
             success: true,
             code: Some(code),
             error: None,
+            diagnostics: Vec::new(),
+            error_code: None,
+            type_errors: None,
         }
     }
 
     /// Create an error result
-    fn error(message: String) -> Self {
+    pub fn error(message: String) -> Self {
         Self {
             success: false,
             code: None,
             error: Some(message),
+            diagnostics: Vec::new(),
+            error_code: None,
+            type_errors: None,
+        }
+    }
+}
+
+impl From<CompileError> for CompileResult {
+    fn from(error: CompileError) -> Self {
+        Self {
+            diagnostics: error.diagnostics,
+            error_code: error.error_code.map(str::to_string),
+            ..Self::error(error.message)
+        }
+    }
+}
+
+/// A compiler error located in the source.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    /// Error message
+    pub message: String,
+    /// Start line (1-based)
+    pub line: usize,
+    /// Start column (1-based, in characters)
+    pub column: usize,
+    /// End line (1-based)
+    pub end_line: usize,
+    /// End column (1-based, exclusive)
+    pub end_column: usize,
+    /// Code frame: the surrounding source lines with the span underlined
+    pub snippet: String,
+}
+
+/// Compilation failure with any located diagnostics.
+#[derive(Debug, Clone)]
+pub struct CompileError {
+    /// Summary message
+    pub message: String,
+    /// Located errors (empty for errors without a source position)
+    pub diagnostics: Vec<Diagnostic>,
+    /// Resource limit that was hit, if any
+    pub error_code: Option<&'static str>,
+}
+
+impl CompileError {
+    /// An error for a resource limit.
+    fn limit(error_code: &'static str, message: String) -> Self {
+        Self {
+            message,
+            diagnostics: Vec::new(),
+            error_code: Some(error_code),
+        }
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<String> for CompileError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            diagnostics: Vec::new(),
+            error_code: None,
+        }
+    }
+}
+
+/// ECMAScript version of the emitted code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompileTarget {
+    Es5,
+    Es2015,
+    Es2016,
+    Es2017,
+    Es2018,
+    Es2019,
+    #[default]
+    Es2020,
+    Es2021,
+    Es2022,
+    EsNext,
+}
+
+impl CompileTarget {
+    /// Corresponding SWC version
+    fn es_version(self) -> EsVersion {
+        match self {
+            Self::Es5 => EsVersion::Es5,
+            Self::Es2015 => EsVersion::Es2015,
+            Self::Es2016 => EsVersion::Es2016,
+            Self::Es2017 => EsVersion::Es2017,
+            Self::Es2018 => EsVersion::Es2018,
+            Self::Es2019 => EsVersion::Es2019,
+            Self::Es2020 => EsVersion::Es2020,
+            Self::Es2021 => EsVersion::Es2021,
+            Self::Es2022 => EsVersion::Es2022,
+            Self::EsNext => EsVersion::EsNext,
+        }
+    }
+}
+
+/// CDN used to resolve bare module specifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CdnProvider {
+    /// https://esm.sh
+    #[serde(rename = "esm.sh")]
+    EsmSh,
+    /// https://unpkg.com (with `?module`)
+    #[serde(rename = "unpkg")]
+    Unpkg,
+}
+
+impl CdnProvider {
+    /// URL of a bare specifier on this CDN
+    fn url(self, specifier: &str) -> String {
+        match self {
+            Self::EsmSh => format!("https://esm.sh/{specifier}"),
+            Self::Unpkg => format!("https://unpkg.com/{specifier}?module"),
         }
     }
 }
 
+/// Code generation options for `compile_tsx`.
+///
+/// All fields are optional. Missing fields take the `[compiler]` defaults
+/// from `app_factory.toml` (see app_config.rs), or else the built-in ones
+/// (ES2020, unminified, comments dropped).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompileOptions {
+    /// Emit compact output (no insignificant whitespace)
+    pub minify: bool,
+    /// Target version for code generation
+    ///
+    /// This selects the emitted syntax; it does not downlevel newer
+    /// syntax used in the source.
+    pub target: CompileTarget,
+    /// Escape non-ASCII characters in strings and identifiers
+    pub ascii_only: bool,
+    /// Keep source comments in the output
+    pub keep_comments: bool,
+    /// Rewrite bare imports (e.g. `react`) to URLs on this CDN
+    pub cdn: Option<CdnProvider>,
+    /// Bare specifier → URL, with import map semantics (keys ending in
+    /// `/` map a prefix). Takes precedence over `cdn`.
+    pub import_map: BTreeMap<String, String>,
+    /// Also type-check the source with `tsc` (see typecheck.rs)
+    ///
+    /// Type errors are reported alongside the output and do not fail the
+    /// compilation. Not part of the cache key, since it doesn't affect
+    /// the emitted code.
+    #[serde(skip_serializing)]
+    pub typecheck: bool,
+}
+
+/// Compile defaults from the startup configuration (unset in tests)
+static DEFAULT_OPTIONS: OnceLock<CompileOptions> = OnceLock::new();
+
+/// Install the startup configuration's compile defaults.
+///
+/// Only the first call has an effect.
+pub fn set_default_options(options: CompileOptions) {
+    let _ = DEFAULT_OPTIONS.set(options);
+}
+
+impl Default for CompileOptions {
+    fn default() -> Self {
+        DEFAULT_OPTIONS.get().cloned().unwrap_or(Self {
+            minify: false,
+            target: CompileTarget::default(),
+            ascii_only: false,
+            keep_comments: false,
+            cdn: None,
+            import_map: BTreeMap::new(),
+            typecheck: false,
+        })
+    }
+}
+
+/// Compile cache hit statistics.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CompileCacheStats {
+    /// Outputs currently held in memory
+    pub memory_entries: usize,
+    /// Lookups served from memory
+    pub memory_hits: u64,
+    /// Lookups served from the on-disk artifact store
+    pub disk_hits: u64,
+    /// Lookups that ran the compiler
+    pub misses: u64,
+    /// Fraction of lookups served from either cache (0 when unused)
+    pub hit_rate: f64,
+}
+
+/// In-memory entries and counters.
+#[derive(Default)]
+struct CacheState {
+    entries: HashMap<String, String>,
+    order: VecDeque<String>,
+    memory_hits: u64,
+    disk_hits: u64,
+    misses: u64,
+}
+
+// ============================================
+// COMPILE CACHE
+// ============================================
+
+/// Cache of `compile_tsx` output keyed by SHA-256 of source + options.
+///
+/// Recent outputs are kept in memory; every output is also stored in the
+/// artifact store under a `tsx:<hash>` key, so the cache survives restarts.
+/// Only successful compilations are cached.
+#[derive(Clone, Default)]
+pub struct CompileCache {
+    state: Arc<Mutex<CacheState>>,
+}
+
+impl CompileCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Compile through the cache.
+    #[tracing::instrument(
+        name = "compile",
+        skip_all,
+        fields(code_len = code.len(), cache = tracing::field::Empty)
+    )]
+    pub fn compile(
+        &self,
+        artifacts: &ArtifactStore,
+        code: &str,
+        options: &CompileOptions,
+    ) -> Result<String, CompileError> {
+        let key = cache_key(code, options);
+        let span = tracing::Span::current();
+
+        {
+            let mut state = self.state.lock().unwrap();
+            if let Some(js_code) = state.entries.get(&key).cloned() {
+                state.memory_hits += 1;
+                span.record("cache", "memory");
+                return Ok(js_code);
+            }
+        }
+
+        let stored = artifacts
+            .lookup(&key)
+            .and_then(|meta| artifacts.read(&meta.hash).ok())
+            .and_then(|data| String::from_utf8(data).ok());
+        if let Some(js_code) = stored {
+            log::debug!("Compile cache disk hit: {key}");
+            span.record("cache", "disk");
+            let mut state = self.state.lock().unwrap();
+            state.disk_hits += 1;
+            state.insert(key, js_code.clone());
+            return Ok(js_code);
+        }
+
+        span.record("cache", "miss");
+        let js_code = compile_tsx_internal(code, options)?;
+        if let Err(e) = artifacts.put(js_code.as_bytes(), CACHE_ARTIFACT_KIND, Some(&key)) {
+            log::debug!("Compile output not persisted: {e}");
+        }
+        let mut state = self.state.lock().unwrap();
+        state.misses += 1;
+        state.insert(key, js_code.clone());
+        Ok(js_code)
+    }
+
+    /// Compile many sources in parallel through the cache.
+    ///
+    /// Results are returned in input order.
+    pub fn compile_batch(
+        &self,
+        artifacts: &ArtifactStore,
+        sources: &[String],
+        options: &CompileOptions,
+    ) -> Vec<Result<String, CompileError>> {
+        sources
+            .par_iter()
+            .map(|code| self.compile(artifacts, code, options))
+            .collect()
+    }
+
+    /// Current hit statistics.
+    pub fn stats(&self) -> CompileCacheStats {
+        let state = self.state.lock().unwrap();
+        let hits = state.memory_hits + state.disk_hits;
+        let lookups = hits + state.misses;
+        #[allow(clippy::cast_precision_loss)]
+        let hit_rate = if lookups == 0 {
+            0.0
+        } else {
+            hits as f64 / lookups as f64
+        };
+        CompileCacheStats {
+            memory_entries: state.entries.len(),
+            memory_hits: state.memory_hits,
+            disk_hits: state.disk_hits,
+            misses: state.misses,
+            hit_rate,
+        }
+    }
+
+    /// Drop all in-memory entries and reset the counters.
+    ///
+    /// Persisted outputs stay in the artifact store (see `artifact_purge`).
+    pub fn clear(&self) {
+        *self.state.lock().unwrap() = CacheState::default();
+    }
+}
+
+impl CacheState {
+    /// Insert an entry, dropping the oldest ones over the limit.
+    fn insert(&mut self, key: String, js_code: String) {
+        if self.entries.insert(key.clone(), js_code).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > MAX_MEMORY_ENTRIES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+/// Cache key for a source and its options.
+///
+/// The crate version is included so a compiler upgrade invalidates
+/// previously persisted outputs.
+fn cache_key(code: &str, options: &CompileOptions) -> String {
+    let options = serde_json::to_string(options).unwrap_or_default();
+    let input = format!("{}\0{options}\0{code}", env!("CARGO_PKG_VERSION"));
+    format!("tsx:{}", content_hash(input.as_bytes()))
+}
+
 // ============================================
 // COMPILATION LOGIC
 // ============================================
 
-/// Compile TSX/TypeScript code to JavaScript.
+/// Compile TSX/TypeScript code to JavaScript, within resource limits.
+///
+/// Oversized or too deeply nested input is rejected up front; the
+/// pipeline then runs on a worker thread (with a large stack) and is
+/// abandoned after `COMPILE_TIMEOUT`. A timed-out worker can't be killed
+/// and finishes in the background, but its result is discarded.
+pub fn compile_tsx_internal(code: &str, options: &CompileOptions) -> Result<String, CompileError> {
+    check_limits(code)?;
+
+    let (tx, rx) = mpsc::channel();
+    let (source, worker_options) = (code.to_string(), options.clone());
+    thread::Builder::new()
+        .name("swc-compile".to_string())
+        .stack_size(COMPILER_STACK_BYTES)
+        .spawn(move || {
+            let _ = tx.send(run_pipeline(&source, &worker_options));
+        })
+        .map_err(|e| format!("Failed to start compiler thread: {e}"))?;
+
+    match rx.recv_timeout(COMPILE_TIMEOUT) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(CompileError::limit(
+            "COMPILE_TIMEOUT",
+            format!("Compilation exceeded {}s", COMPILE_TIMEOUT.as_secs()),
+        )),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(CompileError::limit(
+            "COMPILER_CRASHED",
+            "Compiler crashed on this input".to_string(),
+        )),
+    }
+}
+
+/// Reject input that is too large or too deeply nested to compile safely.
+fn check_limits(code: &str) -> Result<(), CompileError> {
+    if code.len() > MAX_SOURCE_BYTES {
+        return Err(CompileError::limit(
+            "INPUT_TOO_LARGE",
+            format!(
+                "Source is {} bytes; the limit is {MAX_SOURCE_BYTES}",
+                code.len()
+            ),
+        ));
+    }
+
+    if let Some(offset) = nesting_overflow(code) {
+        let line = code[..offset].matches('\n').count() + 1;
+        let line_start = code[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let column = code[line_start..offset].chars().count() + 1;
+        let message = format!("Nesting deeper than {MAX_NESTING_DEPTH} levels");
+        return Err(CompileError {
+            diagnostics: vec![Diagnostic {
+                message: message.clone(),
+                line,
+                column,
+                end_line: line,
+                end_column: column + 1,
+                snippet: code_frame(code, line, column, line, column + 1),
+            }],
+            ..CompileError::limit("NESTING_TOO_DEEP", message)
+        });
+    }
+
+    Ok(())
+}
+
+/// Words after which `/` starts a regex and `<` a JSX element
+const EXPRESSION_KEYWORDS: &[&str] = &[
+    "await",
+    "case",
+    "delete",
+    "do",
+    "else",
+    "in",
+    "instanceof",
+    "new",
+    "of",
+    "return",
+    "throw",
+    "typeof",
+    "void",
+    "yield",
+];
+
+/// An open nesting level seen by `nesting_overflow`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Nesting {
+    /// `(`, `[` or `{` in code
+    Bracket,
+    /// `${` in a template literal
+    Template,
+    /// Name and attributes of a JSX tag
+    JsxTag,
+    /// Children of a JSX element
+    JsxChildren,
+    /// `{` in a JSX attribute or children
+    JsxExpression,
+}
+
+/// Byte offset of the first bracket or JSX element nested deeper than
+/// `MAX_NESTING_DEPTH`.
+///
+/// The parser recurses per level, so this can't ask the parser; it scans
+/// once without recursion instead. Brackets, template substitutions and JSX
+/// elements count; strings, comments, regex literals and JSX text don't.
+/// Like the parser, it tells a regex or JSX element from division or a
+/// comparison by whether the previous token ends an operand.
+fn nesting_overflow(code: &str) -> Option<usize> {
+    let bytes = code.as_bytes();
+    let mut stack = Vec::new();
+    let mut after_operand = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let next = bytes.get(i + 1).copied();
+        match (stack.last(), bytes[i]) {
+            (Some(Nesting::JsxTag), byte) => match byte {
+                b'{' => {
+                    if enter(&mut stack, Nesting::JsxExpression) {
+                        return Some(i);
+                    }
+                    after_operand = false;
+                }
+                b'"' | b'\'' => i = skip_quoted(bytes, i),
+                b'/' if next == Some(b'>') => {
+                    stack.pop();
+                    i += 1;
+                    after_operand = true;
+                }
+                b'>' => {
+                    stack.pop();
+                    stack.push(Nesting::JsxChildren);
+                }
+                // `<T,>` is a type parameter list, not a tag
+                b',' => {
+                    stack.pop();
+                    after_operand = false;
+                }
+                _ => {}
+            },
+            (Some(Nesting::JsxChildren), byte) => match byte {
+                b'<' if next == Some(b'/') => {
+                    stack.pop();
+                    i = code[i..].find('>').map_or(bytes.len(), |n| i + n);
+                    after_operand = true;
+                }
+                b'<' | b'{' => {
+                    let nesting = if byte == b'<' {
+                        Nesting::JsxTag
+                    } else {
+                        Nesting::JsxExpression
+                    };
+                    if enter(&mut stack, nesting) {
+                        return Some(i);
+                    }
+                    after_operand = false;
+                }
+                _ => {}
+            },
+            (_, b'/') if next == Some(b'/') => {
+                i = code[i..].find('\n').map_or(bytes.len(), |n| i + n);
+            }
+            (_, b'/') if next == Some(b'*') => {
+                i = code[i + 2..].find("*/").map_or(bytes.len(), |n| i + n + 3);
+            }
+            (_, b'"' | b'\'') => {
+                i = skip_quoted(bytes, i);
+                after_operand = true;
+            }
+            (_, b'/') if !after_operand => {
+                i = skip_regex(bytes, i);
+                after_operand = true;
+            }
+            (_, b'<')
+                if !after_operand && next.is_some_and(|b| b == b'>' || b.is_ascii_alphabetic()) =>
+            {
+                if enter(&mut stack, Nesting::JsxTag) {
+                    return Some(i);
+                }
+            }
+            (_, b'(' | b'[' | b'{') => {
+                if enter(&mut stack, Nesting::Bracket) {
+                    return Some(i);
+                }
+                after_operand = false;
+            }
+            (_, b')' | b']') => {
+                if stack.last() == Some(&Nesting::Bracket) {
+                    stack.pop();
+                }
+                after_operand = true;
+            }
+            (top, b'}') => {
+                let top = top.copied();
+                if top.is_some() {
+                    stack.pop();
+                }
+                after_operand = true;
+                if top == Some(Nesting::Template) {
+                    let (end, substitution) = skip_template(bytes, i + 1);
+                    if substitution && enter(&mut stack, Nesting::Template) {
+                        return Some(end);
+                    }
+                    i = end;
+                    after_operand = !substitution;
+                }
+            }
+            (_, b'`') => {
+                let (end, substitution) = skip_template(bytes, i + 1);
+                if substitution && enter(&mut stack, Nesting::Template) {
+                    return Some(end);
+                }
+                i = end;
+                after_operand = !substitution;
+            }
+            (_, byte) if is_word_byte(byte) => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|b| !is_word_byte(*b))
+                    .map_or(bytes.len(), |n| i + n);
+                let word = &bytes[i..end];
+                after_operand = !EXPRESSION_KEYWORDS.iter().any(|k| k.as_bytes() == word);
+                i = end - 1;
+            }
+            (_, byte) if byte.is_ascii_whitespace() => {}
+            _ => after_operand = false,
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Open a nesting level; true if that goes past `MAX_NESTING_DEPTH`.
+fn enter(stack: &mut Vec<Nesting>, nesting: Nesting) -> bool {
+    stack.push(nesting);
+    stack.len() > MAX_NESTING_DEPTH
+}
+
+/// Whether a byte belongs to an identifier, keyword or number.
+fn is_word_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'$') || !byte.is_ascii()
+}
+
+/// Index of the quote ending the string that starts at `start` (a line
+/// break ends an unterminated one).
+fn skip_quoted(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i] != quote && bytes[i] != b'\n' {
+        if bytes[i] == b'\\' {
+            i += 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Scan template literal text from `start` to its closing backtick or next
+/// substitution.
+///
+/// # Returns
+///
+/// The index of the backtick, or of the `{` of a `${` and `true`.
+fn skip_template(bytes: &[u8], start: usize) -> (usize, bool) {
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'`' => return (i, false),
+            b'$' if bytes.get(i + 1) == Some(&b'{') => return (i + 1, true),
+            _ => {}
+        }
+        i += 1;
+    }
+    (bytes.len(), false)
+}
+
+/// Index of the `/` ending the regex literal that starts at `start` (a line
+/// break ends an unterminated one).
+fn skip_regex(bytes: &[u8], start: usize) -> usize {
+    let mut in_class = false;
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'[' => in_class = true,
+            b']' => in_class = false,
+            b'/' if !in_class => break,
+            b'\n' => break,
+            _ => {}
+        }
+        i += 1;
+    }
+    i
+}
+
+/// Run the SWC pipeline.
 ///
 /// Pipeline:
 /// 1. Parse TSX/TypeScript source
@@ -75,10 +764,18 @@ impl CompileResult {
 /// 5. Apply hygiene (fix identifier scoping)
 /// 6. Apply fixer (ensure valid syntax)
 /// 7. Generate JavaScript output
-fn compile_tsx_internal(code: &str) -> Result<String, String> {
+fn run_pipeline(code: &str, options: &CompileOptions) -> Result<String, CompileError> {
     // Create source map
     let cm: Lrc<SourceMap> = Lrc::default();
 
+    // Comments are only collected when they should be emitted
+    let comments = SingleThreadedComments::default();
+    let comments: Option<&dyn Comments> = if options.keep_comments {
+        Some(&comments)
+    } else {
+        None
+    };
+
     // Create a virtual file for the source
     let fm = cm.new_source_file(FileName::Custom("input.tsx".into()).into(), code.to_string());
 
@@ -92,20 +789,21 @@ fn compile_tsx_internal(code: &str) -> Result<String, String> {
     });
 
     // Parse the code
-    let lexer = Lexer::new(syntax, EsVersion::Es2020, StringInput::from(&*fm), None);
+    let lexer = Lexer::new(syntax, EsVersion::Es2020, StringInput::from(&*fm), comments);
 
     let mut parser = Parser::new_from(lexer);
+    let parsed = parser.parse_module();
 
-    // Collect parse errors
-    let errors: Vec<_> = parser.take_errors();
-    if !errors.is_empty() {
-        let error_msgs: Vec<String> = errors.iter().map(|e| format!("{e:?}")).collect();
-        return Err(format!("Parse errors: {}", error_msgs.join("; ")));
-    }
-
-    let module = parser
-        .parse_module()
-        .map_err(|e| format!("Parse error: {e:?}"))?;
+    // Recovered errors are reported alongside a fatal one
+    let mut errors = parser.take_errors();
+    let module = match parsed {
+        Ok(module) if errors.is_empty() => module,
+        Ok(_) => return Err(parse_error(&cm, &errors)),
+        Err(e) => {
+            errors.push(e);
+            return Err(parse_error(&cm, &errors));
+        }
+    };
 
     // Wrap in Program for transforms
     let program = Program::Module(module);
@@ -137,13 +835,13 @@ fn compile_tsx_internal(code: &str) -> Result<String, String> {
             // 4. Hygiene: fix identifier contexts
             .apply(hygiene())
             // 5. Fixer: ensure valid output
-            .apply(fixer(None))
+            .apply(fixer(comments))
     });
 
     // Extract module from Program
     let module = match transformed_program {
         Program::Module(m) => m,
-        Program::Script(_) => return Err("Expected module, got script".to_string()),
+        Program::Script(_) => return Err("Expected module, got script".to_string().into()),
     };
 
     // Generate JavaScript code
@@ -151,12 +849,12 @@ fn compile_tsx_internal(code: &str) -> Result<String, String> {
     {
         let mut emitter = Emitter {
             cfg: CodegenConfig::default()
-                .with_target(EsVersion::Es2020)
-                .with_ascii_only(false)
-                .with_minify(false)
-                .with_omit_last_semi(false),
+                .with_target(options.target.es_version())
+                .with_ascii_only(options.ascii_only)
+                .with_minify(options.minify)
+                .with_omit_last_semi(options.minify),
             cm: cm.clone(),
-            comments: None,
+            comments,
             wr: JsWriter::new(cm, "\n", &mut buf, None),
         };
 
@@ -165,7 +863,154 @@ fn compile_tsx_internal(code: &str) -> Result<String, String> {
             .map_err(|e| format!("Emit error: {e:?}"))?;
     }
 
-    String::from_utf8(buf).map_err(|e| format!("UTF-8 error: {e}"))
+    let code = String::from_utf8(buf).map_err(|e| format!("UTF-8 error: {e}"))?;
+
+    if options.cdn.is_some() || !options.import_map.is_empty() {
+        rewrite_bare_imports(&code, options).map_err(CompileError::from)
+    } else {
+        Ok(code)
+    }
+}
+
+/// Build a `CompileError` from parser errors.
+fn parse_error(cm: &SourceMap, errors: &[ParseError]) -> CompileError {
+    let mut diagnostics: Vec<Diagnostic> = errors
+        .iter()
+        .map(|e| {
+            let start = cm.lookup_char_pos(e.span().lo);
+            let end = cm.lookup_char_pos(e.span().hi);
+            let (line, column) = (start.line, start.col.0 + 1);
+            let (end_line, end_column) = (end.line, end.col.0 + 1);
+            Diagnostic {
+                message: e.kind().msg().to_string(),
+                line,
+                column,
+                end_line,
+                end_column,
+                snippet: code_frame(&start.file.src, line, column, end_line, end_column),
+            }
+        })
+        .collect();
+    diagnostics.sort_by_key(|d| (d.line, d.column));
+    diagnostics.dedup();
+
+    let summary: Vec<String> = diagnostics
+        .iter()
+        .map(|d| format!("{} ({}:{})", d.message, d.line, d.column))
+        .collect();
+    CompileError {
+        message: format!("Parse error: {}", summary.join("; ")),
+        diagnostics,
+        error_code: None,
+    }
+}
+
+/// Render a code frame around a span.
+///
+/// ```text
+///   1 | const Button = () => {
+/// > 2 |     return <button>Hi</buton>;
+///     |                       ^^^^^^
+///   3 | };
+/// ```
+pub fn code_frame(
+    source: &str,
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let first = line.saturating_sub(CODE_FRAME_CONTEXT).max(1);
+    let last = (line + CODE_FRAME_CONTEXT).min(lines.len().max(line));
+    let width = last.to_string().len();
+
+    let mut frame = Vec::new();
+    for number in first..=last {
+        let text = lines.get(number - 1).copied().unwrap_or_default();
+        let marker = if number == line { '>' } else { ' ' };
+        frame.push(format!("{marker} {number:>width$} | {text}").trim_end().to_string());
+
+        if number == line {
+            // Underline to the end of the span, or the end of the line if it continues
+            let line_end = text.chars().count() + 1;
+            let until = if end_line == line { end_column } else { line_end };
+            let underline = "^".repeat(until.saturating_sub(column).max(1));
+            let padding = " ".repeat(column - 1);
+            frame.push(format!("  {:>width$} | {padding}{underline}", ""));
+        }
+    }
+    frame.join("\n")
+}
+
+/// Resolve a bare module specifier through the import map, then the CDN.
+///
+/// Relative, absolute and URL specifiers are left alone (`None`).
+fn resolve_bare_specifier(specifier: &str, options: &CompileOptions) -> Option<String> {
+    let is_bare = !(specifier.starts_with('.')
+        || specifier.starts_with('/')
+        || specifier.contains("://")
+        || specifier.starts_with("data:"));
+    if !is_bare {
+        return None;
+    }
+
+    if let Some(url) = options.import_map.get(specifier) {
+        return Some(url.clone());
+    }
+    let prefix = options
+        .import_map
+        .iter()
+        .filter(|(key, _)| key.ends_with('/') && specifier.starts_with(key.as_str()))
+        .max_by_key(|(key, _)| key.len());
+    if let Some((key, url)) = prefix {
+        return Some(format!("{url}{}", &specifier[key.len()..]));
+    }
+
+    options.cdn.map(|cdn| cdn.url(specifier))
+}
+
+/// Rewrite bare import/export specifiers in generated code to URLs.
+fn rewrite_bare_imports(code: &str, options: &CompileOptions) -> Result<String, String> {
+    let cm: Lrc<SourceMap> = Lrc::default();
+    let fm = cm.new_source_file(FileName::Custom("output.js".into()).into(), code.to_string());
+    let lexer = Lexer::new(
+        Syntax::Es(EsSyntax::default()),
+        EsVersion::EsNext,
+        StringInput::from(&*fm),
+        None,
+    );
+    let module = Parser::new_from(lexer)
+        .parse_module()
+        .map_err(|e| format!("Import rewrite error: {e:?}"))?;
+
+    let base = fm.start_pos.0 as usize;
+    let mut replacements = Vec::new();
+    for item in &module.body {
+        let src = match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some(&import.src),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(export)) => export.src.as_ref(),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(export)) => Some(&export.src),
+            _ => None,
+        };
+        let Some(src) = src else {
+            continue;
+        };
+
+        let start = src.span.lo.0 as usize - base;
+        let end = src.span.hi.0 as usize - base;
+        // Strip the quotes of the string literal
+        let specifier = &code[start + 1..end - 1];
+        if let Some(url) = resolve_bare_specifier(specifier, options) {
+            replacements.push((start, end, serde_json::Value::String(url).to_string()));
+        }
+    }
+
+    let mut rewritten = code.to_string();
+    for (start, end, literal) in replacements.iter().rev() {
+        rewritten.replace_range(*start..*end, literal);
+    }
+    Ok(rewritten)
 }
 
 // ============================================
@@ -177,10 +1022,12 @@ fn compile_tsx_internal(code: &str) -> Result<String, String> {
 /// # Arguments
 ///
 /// * `code` - TSX/TypeScript source code to compile
+/// * `options` - Code generation options (optional, see `CompileOptions`)
 ///
 /// # Returns
 ///
-/// `CompileResult` with success status and either compiled code or error message.
+/// `CompileResult` with success status and either compiled code or error
+/// message, plus `type_errors` when `options.typecheck` is set.
 ///
 /// # Example (TypeScript)
 ///
@@ -195,10 +1042,135 @@ fn compile_tsx_internal(code: &str) -> Result<String, String> {
 /// }
 /// ```
 #[tauri::command]
-pub fn compile_tsx(code: &str) -> CompileResult {
+#[allow(clippy::used_underscore_binding)]
+pub async fn compile_tsx(
+    cache: State<'_, CompileCache>,
+    artifacts: State<'_, ArtifactStore>,
+    code: String,
+    options: Option<CompileOptions>,
+) -> CommandResult<CompileResult> {
     log::debug!("Command: compile_tsx (code length: {} chars)", code.len());
 
-    match compile_tsx_internal(code) {
+    compile_blocking(&cache, &artifacts, code, options.unwrap_or_default()).await
+}
+
+/// Compile an MDX document (Markdown with JSX) to a component.
+///
+/// The document is converted to TSX (see mdx.rs) and compiled like
+/// `compile_tsx`; the default export renders the content. Diagnostics
+/// refer to the generated TSX.
+///
+/// # Arguments
+///
+/// * `code` - MDX source
+/// * `options` - Code generation options (see `CompileOptions`)
+///
+/// # Returns
+///
+/// `CompileResult` with the compiled module or an error message.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const result = await invoke<CompileResult>('compile_mdx', {
+///     code: "import { Chart } from './Chart';\n\n# Sales\n\n<Chart data={[1, 2, 3]} />\n"
+/// });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn compile_mdx(
+    cache: State<'_, CompileCache>,
+    artifacts: State<'_, ArtifactStore>,
+    code: String,
+    options: Option<CompileOptions>,
+) -> CommandResult<CompileResult> {
+    log::debug!("Command: compile_mdx (code length: {} chars)", code.len());
+
+    compile_blocking(&cache, &artifacts, mdx::to_tsx(&code), options.unwrap_or_default()).await
+}
+
+/// Compile (and type-check, if requested) on a blocking worker thread.
+///
+/// Keeps the main thread free: type checking runs a subprocess.
+async fn compile_blocking(
+    cache: &CompileCache,
+    artifacts: &ArtifactStore,
+    code: String,
+    options: CompileOptions,
+) -> CommandResult<CompileResult> {
+    let cache = cache.clone();
+    let artifacts = artifacts.clone();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let result = into_compile_result(cache.compile(&artifacts, &code, &options));
+        with_type_errors(result, &code, &options)
+    })
+    .await
+    .map_err(|e| CommandError {
+        code: "COMPILE_ERROR".to_string(),
+        message: e.to_string(),
+        details: None,
+        source_chain: Some(error_chain(&e)),
+    })
+}
+
+/// Compile many TSX/TypeScript sources concurrently.
+///
+/// Sources are compiled on a worker pool sized to the CPU count, sharing
+/// the compile cache with `compile_tsx`.
+///
+/// # Arguments
+///
+/// * `sources` - TSX/TypeScript sources to compile
+/// * `options` - Code generation options applied to every source
+///
+/// # Returns
+///
+/// One `CompileResult` per source, in input order. A failing source does
+/// not affect the others.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const results = await invoke<CompileResult[]>('compile_tsx_batch', {
+///     sources: screens.map((screen) => screen.code),
+///     options: { minify: true }
+/// });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn compile_tsx_batch(
+    cache: State<'_, CompileCache>,
+    artifacts: State<'_, ArtifactStore>,
+    sources: Vec<String>,
+    options: Option<CompileOptions>,
+) -> CommandResult<Vec<CompileResult>> {
+    log::debug!("Command: compile_tsx_batch ({} sources)", sources.len());
+
+    let cache = cache.inner().clone();
+    let artifacts = artifacts.inner().clone();
+    let options = options.unwrap_or_default();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        cache
+            .compile_batch(&artifacts, &sources, &options)
+            .into_par_iter()
+            .zip(sources.par_iter())
+            .map(|(result, code)| with_type_errors(into_compile_result(result), code, &options))
+            .collect()
+    })
+    .await
+    .map_err(|e| CommandError {
+        code: "COMPILE_BATCH_ERROR".to_string(),
+        message: e.to_string(),
+        details: None,
+        source_chain: Some(error_chain(&e)),
+    })
+}
+
+/// Convert a compilation outcome into a `CompileResult`, logging it.
+fn into_compile_result(result: Result<String, CompileError>) -> CompileResult {
+    match result {
         Ok(js_code) => {
             log::debug!(
                 "Compilation succeeded (output length: {} chars)",
@@ -208,11 +1180,186 @@ pub fn compile_tsx(code: &str) -> CompileResult {
         }
         Err(error) => {
             log::warn!("Compilation failed: {error}");
+            error.into()
+        }
+    }
+}
+
+/// Type-check a successfully compiled source when requested.
+///
+/// If `tsc` can't be run the result is returned unchecked (`type_errors`
+/// stays None).
+fn with_type_errors(mut result: CompileResult, code: &str, options: &CompileOptions) -> CompileResult {
+    if options.typecheck && result.success {
+        match typecheck::typecheck(code) {
+            Ok(type_errors) => result.type_errors = Some(type_errors),
+            Err(e) => log::warn!("Type check skipped: {e}"),
+        }
+    }
+    result
+}
+
+/// Compile and bundle a multi-file project into a single script.
+///
+/// # Arguments
+///
+/// * `files` - Virtual files (path → source); mutually exclusive with `dir`
+/// * `dir` - Project directory to read sources from
+/// * `entry` - Entry module path (relative to the project root)
+/// * `options` - Bundle options (`format`, `globals`, `global_name` plus
+///   the `CompileOptions` fields)
+///
+/// # Returns
+///
+/// `CompileResult` with the bundled code or the first error.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const result = await invoke<CompileResult>('compile_project', {
+///     files: { 'src/main.tsx': main, 'src/App.tsx': app },
+///     entry: 'src/main.tsx',
+///     options: { format: 'iife', global_name: 'GeneratedApp', minify: true }
+/// });
+/// ```
+#[tauri::command]
+#[tracing::instrument(name = "compile.project", skip(files, options))]
+pub fn compile_project(
+    files: Option<BTreeMap<String, String>>,
+    dir: Option<String>,
+    entry: String,
+    options: Option<BundleOptions>,
+) -> CompileResult {
+    log::debug!("Command: compile_project entry={entry} dir={dir:?}");
+
+    let files = match (files, dir) {
+        (Some(files), None) => Ok(files),
+        (None, Some(dir)) => bundler::read_project_dir(Path::new(&dir)),
+        _ => Err("Provide exactly one of files or dir".to_string()),
+    };
+
+    match files.and_then(|files| bundler::bundle(&files, &entry, &options.unwrap_or_default())) {
+        Ok(js_code) => CompileResult::success(js_code),
+        Err(error) => {
+            log::warn!("Bundling failed: {error}");
             CompileResult::error(error)
         }
     }
 }
 
+/// Extract component names, props and default values from TSX.
+///
+/// # Arguments
+///
+/// * `code` - TSX source of one or more components
+///
+/// # Returns
+///
+/// The exported components (every component if nothing is exported).
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const [button] = await invoke<ComponentInfo[]>('analyze_component', { code });
+/// button.props.forEach((prop) => renderField(prop.name, prop.type_annotation, prop.default_value));
+/// ```
+#[tauri::command]
+pub fn analyze_component(code: &str) -> CommandResult<Vec<ComponentInfo>> {
+    log::debug!("Command: analyze_component (code length: {} chars)", code.len());
+
+    analyzer::analyze(code).map_err(|e| CommandError {
+        code: "ANALYZE_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// Watch a source directory and recompile changed files.
+///
+/// Every recompiled (or deleted) file is reported with a
+/// `compiler://updated` event (see watch.rs).
+///
+/// # Arguments
+///
+/// * `path` - Directory to watch recursively
+/// * `options` - Code generation options for the recompiled files
+///
+/// # Returns
+///
+/// The watch, whose id is needed to stop it.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const watch = await invoke<WatchInfo>('compile_watch', { path: exportDir });
+/// await listen<CompileUpdate>('compiler://updated', ({ payload }) => reload(payload.path, payload.result));
+/// await invoke('compile_unwatch', { id: watch.id });
+/// ```
+#[tauri::command]
+pub fn compile_watch(
+    app: AppHandle,
+    cache: State<'_, CompileCache>,
+    artifacts: State<'_, ArtifactStore>,
+    watches: State<'_, CompileWatcher>,
+    path: String,
+    options: Option<CompileOptions>,
+) -> CommandResult<WatchInfo> {
+    log::info!("Command: compile_watch path={path}");
+
+    watches
+        .start(
+            Path::new(&path),
+            cache.inner().clone(),
+            artifacts.inner().clone(),
+            options.unwrap_or_default(),
+            move |update| {
+                if let Err(e) = app.emit_all(UPDATED_EVENT, update) {
+                    log::warn!("Failed to emit {UPDATED_EVENT}: {e}");
+                }
+            },
+        )
+        .map_err(|e| CommandError {
+            code: "WATCH_ERROR".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })
+}
+
+/// Stop a compile watch.
+///
+/// # Returns
+///
+/// `true` if the watch existed.
+#[tauri::command]
+pub fn compile_unwatch(watches: State<'_, CompileWatcher>, id: String) -> CommandResult<bool> {
+    log::info!("Command: compile_unwatch id={id}");
+    Ok(watches.stop(&id))
+}
+
+/// List active compile watches.
+#[tauri::command]
+pub fn compile_watch_list(watches: State<'_, CompileWatcher>) -> CommandResult<Vec<WatchInfo>> {
+    log::debug!("Command: compile_watch_list");
+    Ok(watches.list())
+}
+
+/// Get compile cache statistics.
+#[tauri::command]
+pub fn compile_cache_stats(cache: State<'_, CompileCache>) -> CommandResult<CompileCacheStats> {
+    log::debug!("Command: compile_cache_stats");
+    Ok(cache.stats())
+}
+
+/// Clear the in-memory compile cache and reset its statistics.
+#[tauri::command]
+pub fn compile_cache_clear(cache: State<'_, CompileCache>) -> CommandResult<()> {
+    log::info!("Command: compile_cache_clear");
+    cache.clear();
+    Ok(())
+}
+
 // ============================================
 // TESTS
 // ============================================
@@ -221,10 +1368,14 @@ pub fn compile_tsx(code: &str) -> CompileResult {
 mod tests {
     use super::*;
 
+    fn compile(code: &str, options: Option<CompileOptions>) -> CompileResult {
+        into_compile_result(compile_tsx_internal(code, &options.unwrap_or_default()))
+    }
+
     #[test]
     fn test_compile_simple_tsx() {
         let code = r#"const Button = () => <button>Hello</button>;"#;
-        let result = compile_tsx(code);
+        let result = compile(code, None);
 
         assert!(
             result.success,
@@ -243,7 +1394,7 @@ mod tests {
             interface Props { name: string; }
             const Greet = ({ name }: Props) => <div>Hello {name}</div>;
         "#;
-        let result = compile_tsx(code);
+        let result = compile(code, None);
 
         assert!(
             result.success,
@@ -259,7 +1410,7 @@ mod tests {
     #[test]
     fn test_compile_error_handling() {
         let code = r#"const x = <invalid syntax"#;
-        let result = compile_tsx(code);
+        let result = compile(code, None);
 
         assert!(!result.success);
         assert!(result.error.is_some());
@@ -269,7 +1420,7 @@ mod tests {
     #[test]
     fn test_compile_null_component() {
         let code = r#"const X = () => null;"#;
-        let result = compile_tsx(code);
+        let result = compile(code, None);
 
         assert!(
             result.success,
@@ -277,4 +1428,159 @@ mod tests {
             result.error
         );
     }
+
+    #[test]
+    fn test_compile_options() {
+        let code = "// greeting\nconst s = \"h\u{e9}llo\";\nconst f = () => {\n    return s;\n};";
+
+        let default = compile(code, None).code.unwrap();
+        assert!(!default.contains("greeting"));
+
+        let commented = CompileOptions {
+            keep_comments: true,
+            ..Default::default()
+        };
+        assert!(compile(code, Some(commented)).code.unwrap().contains("greeting"));
+
+        let production = CompileOptions {
+            minify: true,
+            ascii_only: true,
+            ..Default::default()
+        };
+        let minified = compile(code, Some(production)).code.unwrap();
+        assert!(minified.len() < default.len());
+        assert!(minified.is_ascii());
+
+        let options: CompileOptions =
+            serde_json::from_value(serde_json::json!({ "target": "es5" })).unwrap();
+        assert_eq!(options.target, CompileTarget::Es5);
+        assert!(!options.minify);
+    }
+
+    #[test]
+    fn test_rewrite_bare_imports() {
+        let code = r#"
+            import { useState } from "react";
+            import helper from "./helper";
+            export * from "lodash/fp";
+            console.log(useState, helper);
+        "#;
+        let options: CompileOptions = serde_json::from_value(serde_json::json!({
+            "cdn": "esm.sh",
+            "import_map": { "lodash/": "https://cdn.example.com/lodash/" }
+        }))
+        .unwrap();
+
+        let js = compile(code, Some(options)).code.unwrap();
+        assert!(js.contains("\"https://esm.sh/react\""));
+        assert!(js.contains("\"https://cdn.example.com/lodash/fp\""));
+        assert!(js.contains("./helper"));
+
+        // Without the options, specifiers are untouched
+        let js = compile(code, None).code.unwrap();
+        assert!(!js.contains("esm.sh"));
+    }
+
+    #[test]
+    fn test_compile_cache() {
+        let dir = std::env::temp_dir().join(format!("af_compile_cache_{}", uuid::Uuid::new_v4()));
+        let artifacts = ArtifactStore::open(Some(dir.clone()));
+        let code = "const X = () => <div>cached</div>;";
+        let defaults = CompileOptions::default();
+        let minified = CompileOptions {
+            minify: true,
+            ..Default::default()
+        };
+
+        let cache = CompileCache::new();
+        let first = cache.compile(&artifacts, code, &defaults).unwrap();
+        assert_eq!(cache.compile(&artifacts, code, &defaults).unwrap(), first);
+        // Different options are a different entry
+        assert_ne!(cache.compile(&artifacts, code, &minified).unwrap(), first);
+        // Errors are not cached
+        assert!(cache.compile(&artifacts, "const x = <invalid syntax", &minified).is_err());
+
+        let stats = cache.stats();
+        assert_eq!((stats.memory_hits, stats.disk_hits, stats.misses), (1, 0, 2));
+        assert_eq!(stats.memory_entries, 2);
+
+        // A fresh cache finds the persisted output
+        let restarted = CompileCache::new();
+        assert_eq!(restarted.compile(&artifacts, code, &defaults).unwrap(), first);
+        assert_eq!(restarted.stats().disk_hits, 1);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_compile_batch() {
+        let sources = vec![
+            "const A = () => <a>one</a>;".to_string(),
+            "const B = <invalid syntax".to_string(),
+            "const C = () => <p>three</p>;".to_string(),
+        ];
+        let cache = CompileCache::new();
+        let results =
+            cache.compile_batch(&ArtifactStore::open(None), &sources, &CompileOptions::default());
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].as_ref().unwrap().contains("one"));
+        assert!(results[1].is_err());
+        assert!(results[2].as_ref().unwrap().contains("three"));
+    }
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let code = "const a = 1;\nconst b = (;\nconst c = 3;";
+        let result = compile(code, None);
+
+        assert!(!result.success);
+        let diagnostic = &result.diagnostics[0];
+        assert_eq!(diagnostic.line, 2);
+        assert!(result.error.unwrap().contains("(2:"));
+        assert!(diagnostic.snippet.contains("> 2 | const b = (;"));
+        assert!(diagnostic.snippet.contains("  1 | const a = 1;"));
+        assert!(diagnostic.snippet.lines().nth(2).unwrap().ends_with('^'));
+    }
+
+    #[test]
+    fn test_resource_limits() {
+        let large = format!("const s = \"{}\";", "a".repeat(MAX_SOURCE_BYTES));
+        let result = compile(&large, None);
+        assert_eq!(result.error_code.as_deref(), Some("INPUT_TOO_LARGE"));
+
+        let deep = format!("const x = {}1{};", "(".repeat(300), ")".repeat(300));
+        let result = compile(&deep, None);
+        assert_eq!(result.error_code.as_deref(), Some("NESTING_TOO_DEEP"));
+        assert_eq!(result.diagnostics[0].column, 11 + MAX_NESTING_DEPTH);
+
+        // Brackets in strings and comments don't count
+        let quoted = format!("// {}\nconst s = '{}';", "{".repeat(300), "[".repeat(300));
+        assert!(compile(&quoted, None).success);
+    }
+
+    #[test]
+    fn test_nesting_overflow() {
+        let nested = |depth: usize, inner: &str| {
+            format!("const x = {}{inner}{};", "(".repeat(depth), ")".repeat(depth))
+        };
+
+        // Brackets in strings, comments, regexes and JSX text don't count
+        let limit = MAX_NESTING_DEPTH;
+        for inner in ["\"(\"", "'{['", "`(`", "/[(]/", "// {\n1", "/* [ */ 1"] {
+            assert_eq!(nesting_overflow(&nested(limit, inner)), None, "{inner}");
+        }
+        assert_eq!(nesting_overflow(&nested(limit - 1, "<p>Don't (</p>")), None);
+        assert!(nesting_overflow(&nested(limit, "`${1}`")).is_some());
+        assert!(nesting_overflow(&nested(limit, "[1]")).is_some());
+        assert_eq!(nesting_overflow("const y = a / b / c;\nconst z = a < b;"), None);
+
+        // JSX elements and expressions count, and JSX text doesn't hide what follows
+        let elements = format!("const x = {}1{};", "<b>".repeat(300), "</b>".repeat(300));
+        assert!(nesting_overflow(&elements).is_some());
+        let after_text = format!("const a = <p>Don't</p>;\n{}", nested(300, "1"));
+        assert!(nesting_overflow(&after_text).is_some());
+        let generic = format!("const id = <T,>(v: T) => v;\n{}", nested(limit, "1"));
+        assert_eq!(nesting_overflow(&generic), None);
+    }
 }
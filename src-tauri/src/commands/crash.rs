@@ -0,0 +1,73 @@
+//! src-tauri/src/commands/crash.rs
+//! ================================
+//! Tauri commands for viewing and submitting crash reports.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Reports are recorded locally for backend panics and plugin host crashes
+//! (see `crate::crash`); the frontend is told about a host crash through the
+//! `ipc/subprocess_crashed` event. Submitting is always an explicit user
+//! action and requires `crash.submit_url` in app_factory.toml.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const reports = await invoke('crash_reports');
+//!     const report = await invoke('crash_report', { id: reports[0].id });
+//!     await invoke('submit_crash_report', { id: report.id });
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::crash::{CrashReport, CrashReporter};
+
+/// Build a crash report error with the given code.
+fn crash_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// List recorded crash reports (newest first).
+#[tauri::command]
+pub fn crash_reports(crash: State<'_, CrashReporter>) -> CommandResult<Vec<CrashReport>> {
+    log::debug!("Command: crash_reports");
+    Ok(crash.list())
+}
+
+/// Get a crash report by id.
+#[tauri::command]
+pub fn crash_report(crash: State<'_, CrashReporter>, id: String) -> CommandResult<CrashReport> {
+    log::debug!("Command: crash_report id={id}");
+    crash
+        .get(&id)
+        .map_err(|e| crash_error("CRASH_REPORT_NOT_FOUND", e))
+}
+
+/// Submit a crash report to the configured endpoint.
+///
+/// # Arguments
+///
+/// * `id` - Report id
+///
+/// # Returns
+///
+/// The report with `submitted_at` set. Fails with `CRASH_SUBMIT_ERROR` if
+/// submission is not configured or the endpoint rejects the report.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn submit_crash_report(
+    crash: State<'_, CrashReporter>,
+    id: String,
+) -> CommandResult<CrashReport> {
+    log::info!("Command: submit_crash_report id={id}");
+    crash
+        .submit(&id)
+        .await
+        .map_err(|e| crash_error("CRASH_SUBMIT_ERROR", e))
+}
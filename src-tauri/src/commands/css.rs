@@ -0,0 +1,212 @@
+//! src-tauri/src/commands/css.rs
+//! ==============================
+//! Tauri command for CSS/SCSS compilation.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! SCSS is compiled to CSS with grass; the result is then parsed by
+//! lightningcss, which adds vendor prefixes for the default browser targets
+//! and optionally minifies. Everything runs in-process, like `compile_tsx`.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const result = await invoke<CompileResult>('compile_css', {
+//!         code: '$accent: #4f46e5; .btn { color: $accent; &:hover { opacity: .8; } }',
+//!         options: { syntax: 'scss', minify: true }
+//!     });
+//!     ```
+
+use lightningcss::stylesheet::{MinifyOptions, ParserOptions, PrinterOptions, StyleSheet};
+use lightningcss::targets::{Browsers, Targets};
+use serde::Deserialize;
+
+use super::compiler::CompileResult;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Encode a browser version the way lightningcss expects (`major << 16`).
+const fn version(major: u32) -> Option<u32> {
+    Some(major << 16)
+}
+
+/// Browsers targeted when autoprefixing (roughly what the preview webviews
+/// on Windows, macOS and Linux support)
+const DEFAULT_BROWSERS: Browsers = Browsers {
+    android: None,
+    chrome: version(90),
+    edge: version(90),
+    firefox: version(88),
+    ie: None,
+    ios_saf: version(14),
+    opera: None,
+    safari: version(14),
+    samsung: None,
+};
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Source syntax.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CssSyntax {
+    /// Plain CSS
+    #[default]
+    Css,
+    /// SCSS (variables, nesting, mixins, ...)
+    Scss,
+}
+
+/// Options for `compile_css`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CssOptions {
+    /// Source syntax
+    pub syntax: CssSyntax,
+    /// Add vendor prefixes for the default browser targets
+    pub autoprefix: bool,
+    /// Minify the output
+    pub minify: bool,
+}
+
+impl Default for CssOptions {
+    fn default() -> Self {
+        Self {
+            syntax: CssSyntax::Css,
+            autoprefix: true,
+            minify: false,
+        }
+    }
+}
+
+// ============================================
+// COMPILATION LOGIC
+// ============================================
+
+/// Compile CSS/SCSS.
+///
+/// Pipeline:
+/// 1. SCSS → CSS (grass), when `syntax` is `scss`
+/// 2. Parse CSS (lightningcss)
+/// 3. Minify/prefix for targets
+/// 4. Print
+fn compile_css_internal(code: &str, options: &CssOptions) -> Result<String, String> {
+    let css = match options.syntax {
+        CssSyntax::Css => code.to_string(),
+        CssSyntax::Scss => grass::from_string(code.to_string(), &grass::Options::default())
+            .map_err(|e| format!("SCSS error: {e}"))?,
+    };
+
+    let mut sheet = StyleSheet::parse(&css, ParserOptions::default())
+        .map_err(|e| format!("CSS parse error: {e}"))?;
+
+    sheet
+        .minify(MinifyOptions {
+            targets: targets(options.autoprefix),
+            ..Default::default()
+        })
+        .map_err(|e| format!("CSS minify error: {e}"))?;
+
+    let output = sheet
+        .to_css(PrinterOptions {
+            minify: options.minify,
+            targets: targets(options.autoprefix),
+            ..Default::default()
+        })
+        .map_err(|e| format!("CSS print error: {e}"))?;
+
+    Ok(output.code)
+}
+
+/// Browser targets for prefixing (none when autoprefixing is off).
+fn targets(autoprefix: bool) -> Targets {
+    if autoprefix {
+        Targets::from(DEFAULT_BROWSERS)
+    } else {
+        Targets::default()
+    }
+}
+
+// ============================================
+// TAURI COMMAND
+// ============================================
+
+/// Compile CSS or SCSS.
+///
+/// # Arguments
+///
+/// * `code` - CSS/SCSS source
+/// * `options` - Syntax, autoprefixing (default on) and minification
+///
+/// # Returns
+///
+/// `CompileResult` with the compiled CSS or an error message.
+#[tauri::command]
+pub fn compile_css(code: &str, options: Option<CssOptions>) -> CompileResult {
+    log::debug!("Command: compile_css (code length: {} chars)", code.len());
+
+    match compile_css_internal(code, &options.unwrap_or_default()) {
+        Ok(css) => CompileResult::success(css),
+        Err(error) => {
+            log::warn!("CSS compilation failed: {error}");
+            CompileResult::error(error)
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compile_scss() {
+        let code = "$accent: #4f46e5;\n.btn { color: $accent; &:hover { color: red; } }";
+        let options = CssOptions {
+            syntax: CssSyntax::Scss,
+            ..Default::default()
+        };
+        let result = compile_css(code, Some(options));
+
+        assert!(result.success, "Expected success, got {:?}", result.error);
+        let css = result.code.unwrap();
+        assert!(css.contains(".btn:hover"));
+        assert!(css.contains("#4f46e5"));
+        assert!(!css.contains('$'));
+    }
+
+    #[test]
+    fn test_autoprefix_and_minify() {
+        let code = ".a {\n  user-select: none;\n}\n";
+
+        let prefixed = compile_css(code, None).code.unwrap();
+        assert!(prefixed.contains("-webkit-user-select"));
+
+        let options = CssOptions {
+            autoprefix: false,
+            minify: true,
+            ..Default::default()
+        };
+        let minified = compile_css(code, Some(options)).code.unwrap();
+        assert_eq!(minified, ".a{user-select:none}");
+    }
+
+    #[test]
+    fn test_compile_error() {
+        let options = CssOptions {
+            syntax: CssSyntax::Scss,
+            ..Default::default()
+        };
+        let result = compile_css(".a { color: $undefined; }", Some(options));
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("SCSS error"));
+    }
+}
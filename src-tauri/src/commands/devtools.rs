@@ -0,0 +1,265 @@
+//! src-tauri/src/commands/devtools.rs
+//! ==================================
+//! Tauri commands for the developer IPC console and fault injection.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The console is a second window listing requests sent to the core plugin
+//! host as they finish (see `crate::devtools`). From it a developer can send
+//! hand-written JSON-RPC calls and replay recorded ones. Calls are routed and
+//! refused like `ipc_call`'s: loading, unloading, and swapping plugins go
+//! through their own commands, and calls to a sandboxed plugin go to its
+//! sandboxed host.
+//!
+//! Fault injection (see `crate::ipc::chaos`) adds latency, drops or
+//! corrupts responses, and kills a plugin host at random, to exercise
+//! timeouts and respawn on purpose.
+//!
+//! Type generation (see `crate::ipc::codegen`) turns the method schemas into
+//! TypeScript interfaces and Rust structs for params and results.
+//!
+//! The commands are only available in debug builds.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!     import { listen } from '@tauri-apps/api/event';
+//!
+//!     await invoke('devtools_open_ipc_console');
+//!
+//!     // In the console window
+//!     const recent = await invoke('devtools_ipc_history', { limit: 200 });
+//!     const unlisten = await listen('devtools/ipc', (e) => append(e.payload));
+//!     await invoke('devtools_ipc_send', { method: 'plugin/list', params: {} });
+//!     await invoke('devtools_ipc_replay', { id: recent[0].id });
+//!
+//!     await invoke('devtools_set_chaos', { config: { drop_rate: 0.1, latency_ms: 500 } });
+//!     const { stats } = await invoke('devtools_chaos_status');
+//!     await invoke('devtools_set_chaos', { config: {} });
+//!
+//!     const { typescript } = await invoke('devtools_generate_ipc_types', {
+//!         outDir: 'src/generated'
+//!     });
+//!     ```
+
+use serde_json::value::RawValue;
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use tauri::{AppHandle, Manager, State, WindowBuilder, WindowUrl};
+
+use super::{host_manager_for, request_manager_for, CommandError, CommandResult};
+use crate::devtools::{self, IpcConsole, IPC_CONSOLE_LABEL, IPC_CONSOLE_URL, MAX_CONSOLE_ENTRIES};
+use crate::ipc::chaos::{ChaosConfig, ChaosStatus};
+use crate::ipc::codegen::{self, GeneratedTypes, RUST_FILE, TYPESCRIPT_FILE};
+use crate::ipc::hosts::HostRegistry;
+use crate::ipc::manager::TrafficEntry;
+use crate::ipc::schemas::SchemaRegistry;
+use crate::workspace::WorkspaceRegistry;
+
+/// Build a devtools `CommandError`.
+fn devtools_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Fail unless developer tools are enabled.
+fn ensure_enabled() -> CommandResult<()> {
+    if devtools::enabled() {
+        Ok(())
+    } else {
+        Err(devtools_error(
+            "DEVTOOLS_DISABLED",
+            "Developer tools are only available in debug builds".to_string(),
+        ))
+    }
+}
+
+/// Open the IPC console window, or focus it if it is already open.
+#[tauri::command]
+pub async fn devtools_open_ipc_console(app: AppHandle) -> CommandResult<()> {
+    log::info!("Command: devtools_open_ipc_console");
+    ensure_enabled()?;
+    if let Some(window) = app.get_window(IPC_CONSOLE_LABEL) {
+        return window
+            .set_focus()
+            .map_err(|e| devtools_error("WINDOW_ERROR", e.to_string()));
+    }
+    WindowBuilder::new(
+        &app,
+        IPC_CONSOLE_LABEL,
+        WindowUrl::App(IPC_CONSOLE_URL.into()),
+    )
+    .title("IPC Console")
+    .inner_size(960.0, 640.0)
+    .build()
+    .map_err(|e| devtools_error("WINDOW_ERROR", e.to_string()))?;
+    Ok(())
+}
+
+/// List recorded requests (most recent first).
+///
+/// # Arguments
+///
+/// * `limit` - Maximum number of entries (optional, defaults to all kept)
+#[tauri::command]
+pub fn devtools_ipc_history(
+    console: State<'_, IpcConsole>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<TrafficEntry>> {
+    log::debug!("Command: devtools_ipc_history");
+    ensure_enabled()?;
+    Ok(console.entries(limit.unwrap_or(MAX_CONSOLE_ENTRIES)))
+}
+
+/// Forget the recorded requests.
+#[tauri::command]
+pub fn devtools_ipc_clear(console: State<'_, IpcConsole>) -> CommandResult<()> {
+    log::info!("Command: devtools_ipc_clear");
+    ensure_enabled()?;
+    console.clear();
+    Ok(())
+}
+
+/// Send a hand-written JSON-RPC call to a plugin host.
+///
+/// Routed like `ipc_call` (see `request_manager_for`).
+///
+/// # Arguments
+///
+/// * `method` - JSON-RPC method
+/// * `params` - Method parameters (optional, defaults to `{}`)
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn devtools_ipc_send(
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    method: String,
+    params: Option<Value>,
+) -> CommandResult<Box<RawValue>> {
+    log::info!("Command: devtools_ipc_send method={method}");
+    ensure_enabled()?;
+    let params = params.unwrap_or(json!({}));
+    let state = request_manager_for(&workspaces, &hosts, None, None, &method, &params).await?;
+    state
+        .call_raw(method, params)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Send a recorded request again with the same method and params.
+///
+/// # Arguments
+///
+/// * `id` - JSON-RPC id of the recorded request
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn devtools_ipc_replay(
+    console: State<'_, IpcConsole>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    id: u64,
+) -> CommandResult<Box<RawValue>> {
+    log::info!("Command: devtools_ipc_replay id={id}");
+    ensure_enabled()?;
+    let entry = console.find(id).ok_or_else(|| {
+        devtools_error(
+            "REQUEST_NOT_FOUND",
+            format!("No recorded request with id {id}"),
+        )
+    })?;
+    let state = request_manager_for(
+        &workspaces,
+        &hosts,
+        None,
+        None,
+        &entry.method,
+        &entry.params,
+    )
+    .await?;
+    state
+        .call_raw(entry.method, entry.params)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Configure fault injection on a plugin host.
+///
+/// Replaces the previous configuration and resets the counts; an empty
+/// config turns fault injection off.
+///
+/// # Arguments
+///
+/// * `workspace` - Workspace id (optional, defaults to the default workspace)
+/// * `host` - Named plugin host (optional, defaults to the core host)
+/// * `config` - Latency, drop/malformed/kill rates (0-1), and optional seed
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn devtools_set_chaos(
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    workspace: Option<String>,
+    host: Option<String>,
+    config: ChaosConfig,
+) -> CommandResult<()> {
+    log::info!("Command: devtools_set_chaos config={config:?}");
+    ensure_enabled()?;
+    let state =
+        host_manager_for(&workspaces, &hosts, workspace.as_deref(), host.as_deref()).await?;
+    state
+        .chaos()
+        .configure(config)
+        .map_err(|e| devtools_error("INVALID_CHAOS_CONFIG", e))
+}
+
+/// Get the fault injection configuration and counts of a plugin host.
+///
+/// # Arguments
+///
+/// * `workspace` - Workspace id (optional, defaults to the default workspace)
+/// * `host` - Named plugin host (optional, defaults to the core host)
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn devtools_chaos_status(
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    workspace: Option<String>,
+    host: Option<String>,
+) -> CommandResult<ChaosStatus> {
+    log::debug!("Command: devtools_chaos_status");
+    ensure_enabled()?;
+    let state =
+        host_manager_for(&workspaces, &hosts, workspace.as_deref(), host.as_deref()).await?;
+    Ok(state.chaos().status())
+}
+
+/// Generate TypeScript and Rust types from the method schemas.
+///
+/// Covers every method with a schema (from `config/ipc_schemas.json` and the
+/// core plugin host).
+///
+/// # Arguments
+///
+/// * `out_dir` - Directory to write `ipcMethods.generated.ts` and
+///   `ipc_methods.rs` to (optional; the sources are returned either way)
+#[tauri::command]
+pub fn devtools_generate_ipc_types(
+    schemas: State<'_, SchemaRegistry>,
+    out_dir: Option<String>,
+) -> CommandResult<GeneratedTypes> {
+    log::info!("Command: devtools_generate_ipc_types out_dir={out_dir:?}");
+    ensure_enabled()?;
+    let generated = codegen::generate(&schemas.snapshot());
+    if let Some(dir) = out_dir {
+        let dir = Path::new(&dir);
+        fs::create_dir_all(dir)
+            .and_then(|()| fs::write(dir.join(TYPESCRIPT_FILE), &generated.typescript))
+            .and_then(|()| fs::write(dir.join(RUST_FILE), &generated.rust))
+            .map_err(|e| devtools_error("IO_ERROR", format!("Failed to write to {dir:?}: {e}")))?;
+    }
+    Ok(generated)
+}
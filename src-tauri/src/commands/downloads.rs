@@ -0,0 +1,164 @@
+//! src-tauri/src/commands/downloads.rs
+//! ====================================
+//! Tauri commands for downloading model weights into the managed cache.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Each download emits throttled `download/<id>/progress` events and a
+//! single `download/<id>/finished` event. The event payload is the full
+//! `Download`.
+//!
+//! `download_file` fetches runtime assets of generated apps into the app's
+//! assets directory, from hosts in `http.allowed_domains` (see
+//! `crate::http_fetch`) and verified against a SHA-256 checksum.
+//!
+//! In offline mode both commands still return files that are already in
+//! place and fail with `OFFLINE` for anything they would have to download.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const dl = await invoke('download_start', {
+//!         url: 'https://example.com/kokoro-v1.onnx',
+//!         sha256: '2cf24dba...'
+//!     });
+//!     const status = await invoke('download_status', { id: dl.id });
+//!     await invoke('download_cancel', { id: dl.id });
+//!
+//!     const asset = await invoke('download_file', {
+//!         url: 'https://cdn.example.com/fonts/inter.woff2',
+//!         dest: 'fonts/inter.woff2',
+//!         sha256: '2cf24dba...'
+//!     });
+//!     ```
+
+use tauri::{AppHandle, Manager, State};
+
+use super::{CommandError, CommandResult};
+use crate::downloads::{Download, DownloadManager};
+use crate::http_fetch::domain_allowed;
+use crate::offline;
+use crate::settings::SettingsStore;
+
+/// Build a download error with the given code.
+fn download_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Build the error of a download that didn't start (`OFFLINE` if offline
+/// mode blocks its URL).
+fn start_error(url: &str, message: String) -> CommandError {
+    let blocked = reqwest::Url::parse(url).is_ok_and(|url| offline::check_url(&url).is_err());
+    download_error(if blocked { "OFFLINE" } else { "DOWNLOAD_ERROR" }, message)
+}
+
+/// Start or resume a download into the managed cache directory.
+///
+/// # Arguments
+///
+/// * `url` - HTTP(S) URL
+/// * `file_name` - Cache file name (optional, defaults to the URL's last segment)
+/// * `sha256` - Expected SHA-256 checksum (optional)
+///
+/// # Returns
+///
+/// The download (already `completed` if a valid cached copy exists),
+/// `OFFLINE`, or `DOWNLOAD_ERROR`.
+#[tauri::command]
+pub fn download_start(
+    app: AppHandle,
+    downloads: State<'_, DownloadManager>,
+    url: String,
+    file_name: Option<String>,
+    sha256: Option<String>,
+) -> CommandResult<Download> {
+    log::info!("Command: download_start url={url}");
+
+    downloads
+        .start(&url, file_name.as_deref(), sha256.as_deref(), move |event, dl| {
+            if let Err(e) = app.emit_all(&event, dl) {
+                log::warn!("Failed to emit {event}: {e}");
+            }
+        })
+        .map_err(|e| start_error(&url, e))
+}
+
+/// Download a file into the assets directory, checked against a checksum.
+///
+/// Progress is reported like for `download_start`. If the file doesn't
+/// match the checksum the download fails and the file (including any
+/// partial data) is deleted.
+///
+/// # Arguments
+///
+/// * `url` - HTTP(S) URL on a host in `http.allowed_domains`
+/// * `dest` - Destination path relative to the assets directory
+/// * `sha256` - Expected SHA-256 checksum
+///
+/// # Returns
+///
+/// The download (already `completed` if a matching file exists),
+/// `DOMAIN_NOT_ALLOWED`, `OFFLINE`, or `DOWNLOAD_ERROR`.
+#[tauri::command]
+pub fn download_file(
+    app: AppHandle,
+    downloads: State<'_, DownloadManager>,
+    settings: State<'_, SettingsStore>,
+    url: String,
+    dest: String,
+    sha256: String,
+) -> CommandResult<Download> {
+    log::info!("Command: download_file url={url} dest={dest}");
+    let host = reqwest::Url::parse(&url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_string));
+    if !host.is_some_and(|host| domain_allowed(&settings.get().http.allowed_domains, &host)) {
+        return Err(download_error(
+            "DOMAIN_NOT_ALLOWED",
+            format!("{url} is not on an allowed domain"),
+        ));
+    }
+
+    downloads
+        .fetch_asset(&url, &dest, &sha256, move |event, dl| {
+            if let Err(e) = app.emit_all(&event, dl) {
+                log::warn!("Failed to emit {event}: {e}");
+            }
+        })
+        .map_err(|e| start_error(&url, e))
+}
+
+/// Get a download by id.
+#[tauri::command]
+pub fn download_status(downloads: State<'_, DownloadManager>, id: String) -> CommandResult<Download> {
+    log::debug!("Command: download_status id={id}");
+    downloads
+        .get(&id)
+        .ok_or_else(|| download_error("DOWNLOAD_NOT_FOUND", format!("Unknown download: {id}")))
+}
+
+/// List downloads started in this session (most recent first).
+#[tauri::command]
+pub fn download_list(downloads: State<'_, DownloadManager>) -> CommandResult<Vec<Download>> {
+    log::debug!("Command: download_list");
+    Ok(downloads.list())
+}
+
+/// Cancel a running download. The partial file is kept so a later
+/// `download_start` with the same URL resumes it.
+#[tauri::command]
+pub fn download_cancel(downloads: State<'_, DownloadManager>, id: String) -> CommandResult<Download> {
+    log::info!("Command: download_cancel id={id}");
+    if downloads.get(&id).is_none() {
+        return Err(download_error("DOWNLOAD_NOT_FOUND", format!("Unknown download: {id}")));
+    }
+    downloads
+        .cancel(&id)
+        .map_err(|e| download_error("DOWNLOAD_CANCEL_ERROR", e))
+}
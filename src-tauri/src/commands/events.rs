@@ -0,0 +1,105 @@
+//! src-tauri/src/commands/events.rs
+//! ================================
+//! Tauri commands for subscribing to plugin host event topics.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Subscribing to a topic makes the core host send its events (see
+//! `crate::events`); they arrive as `events/<topic>` Tauri events.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!     import { listen } from '@tauri-apps/api/event';
+//!
+//!     const unlisten = await listen('events/stt/partial', (e) => setText(e.payload.text));
+//!     await invoke('events_subscribe', { topic: 'stt/partial' });
+//!     // ...
+//!     await invoke('events_unsubscribe', { topic: 'stt/partial' });
+//!     unlisten();
+//!     const stats = await invoke('events_stats');
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::events::{EventSubscriptions, TopicStats};
+use crate::ipc::manager::IpcManagerState;
+
+/// Build an event subscription error with the given code.
+fn events_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Send the topics with listeners to the core host.
+async fn sync_topics(
+    subscriptions: &EventSubscriptions,
+    state: &IpcManagerState,
+) -> CommandResult<()> {
+    state
+        .set_event_topics(subscriptions.topics())
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Subscribe to an event topic.
+///
+/// # Arguments
+///
+/// * `topic` - Event topic (notification method sent by plugins)
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn events_subscribe(
+    subscriptions: State<'_, EventSubscriptions>,
+    state: State<'_, IpcManagerState>,
+    topic: String,
+) -> CommandResult<()> {
+    log::info!("Command: events_subscribe topic={topic}");
+    let first = subscriptions
+        .add_listener(&topic)
+        .map_err(|e| events_error("INVALID_TOPIC", e))?;
+    if first {
+        sync_topics(&subscriptions, &state).await?;
+    }
+    Ok(())
+}
+
+/// Remove a subscription to an event topic.
+///
+/// # Arguments
+///
+/// * `topic` - Event topic passed to `events_subscribe`
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn events_unsubscribe(
+    subscriptions: State<'_, EventSubscriptions>,
+    state: State<'_, IpcManagerState>,
+    topic: String,
+) -> CommandResult<()> {
+    log::info!("Command: events_unsubscribe topic={topic}");
+    let last = subscriptions
+        .remove_listener(&topic)
+        .map_err(|e| events_error("NOT_SUBSCRIBED", e))?;
+    if last {
+        sync_topics(&subscriptions, &state).await?;
+    }
+    Ok(())
+}
+
+/// Get listener counts and delivery stats per topic.
+///
+/// # Returns
+///
+/// Every topic subscribed to since startup, sorted by topic.
+#[tauri::command]
+pub fn events_stats(
+    subscriptions: State<'_, EventSubscriptions>,
+) -> CommandResult<Vec<TopicStats>> {
+    log::debug!("Command: events_stats");
+    Ok(subscriptions.stats())
+}
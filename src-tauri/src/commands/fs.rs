@@ -0,0 +1,200 @@
+//! src-tauri/src/commands/fs.rs
+//! =============================
+//! Tauri commands for file access confined to the project.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Generated apps read and write files through these commands instead of
+//! getting filesystem access in the webview. Paths are relative to the
+//! active project root or absolute; anything outside the project and
+//! `fs.allowed_dirs` fails with `PATH_NOT_ALLOWED` (see `crate::project_fs`).
+//!
+//! `fs_watch` reports changes to files under a directory with debounced
+//! `fs://changed` events (see `crate::fs_watch`), e.g. to re-preview an
+//! exported app while it is edited outside App Factory.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     await invoke('fs_write', { path: 'data/notes.md', contents: '# Notes', createDirs: true });
+//!     const text = await invoke('fs_read', { path: 'data/notes.md' });
+//!     const entries = await invoke('fs_list', { path: 'data' });
+//!     await invoke('fs_delete', { path: 'data', recursive: true });
+//!
+//!     const watch = await invoke('fs_watch', { path: 'export', patterns: ['src/**', '*.css'] });
+//!     await listen('fs://changed', ({ payload }) => rerun(payload.changes));
+//!     await invoke('fs_unwatch', { id: watch.id });
+//!     ```
+
+use tauri::{AppHandle, Manager, State};
+
+use super::{CommandError, CommandResult};
+use crate::fs_watch::{FileWatcher, CHANGED_EVENT};
+use crate::project::ProjectManager;
+use crate::project_fs::{FsEntry, FsError, FsScope};
+use crate::project_tree::PathPatterns;
+use crate::settings::SettingsStore;
+use crate::watch::WatchInfo;
+
+/// The directories the commands may touch right now.
+fn scope(project: &ProjectManager, settings: &SettingsStore) -> FsScope {
+    FsScope::new(&project.current_root(), &settings.get().fs.allowed_dirs)
+}
+
+impl From<FsError> for CommandError {
+    fn from(error: FsError) -> Self {
+        CommandError {
+            code: error.code.to_string(),
+            message: error.message,
+            details: None,
+            source_chain: None,
+        }
+    }
+}
+
+/// Read a text file.
+///
+/// # Arguments
+///
+/// * `path` - File path (relative to the project root, or absolute)
+///
+/// # Returns
+///
+/// The file contents, `PATH_NOT_ALLOWED`, `FILE_TOO_LARGE`, or `FS_ERROR`.
+#[tauri::command]
+pub fn fs_read(
+    project: State<'_, ProjectManager>,
+    settings: State<'_, SettingsStore>,
+    path: String,
+) -> CommandResult<String> {
+    log::debug!("Command: fs_read path={path}");
+    Ok(scope(&project, &settings).read(&path)?)
+}
+
+/// Write a text file, replacing it if it exists.
+///
+/// # Arguments
+///
+/// * `path` - File path (relative to the project root, or absolute)
+/// * `contents` - Text to write
+/// * `create_dirs` - Create missing parent directories (optional, defaults to false)
+///
+/// # Returns
+///
+/// The written file, `PATH_NOT_ALLOWED`, or `FS_ERROR`.
+#[tauri::command]
+pub fn fs_write(
+    project: State<'_, ProjectManager>,
+    settings: State<'_, SettingsStore>,
+    path: String,
+    contents: String,
+    create_dirs: Option<bool>,
+) -> CommandResult<FsEntry> {
+    log::info!("Command: fs_write path={path} bytes={}", contents.len());
+    Ok(scope(&project, &settings).write(&path, &contents, create_dirs.unwrap_or(false))?)
+}
+
+/// List a directory (directories first, then by name).
+///
+/// # Arguments
+///
+/// * `path` - Directory path (optional, defaults to the project root)
+#[tauri::command]
+pub fn fs_list(
+    project: State<'_, ProjectManager>,
+    settings: State<'_, SettingsStore>,
+    path: Option<String>,
+) -> CommandResult<Vec<FsEntry>> {
+    let path = path.unwrap_or_else(|| ".".to_string());
+    log::debug!("Command: fs_list path={path}");
+    Ok(scope(&project, &settings).list(&path)?)
+}
+
+/// Delete a file or directory.
+///
+/// # Arguments
+///
+/// * `path` - Path (relative to the project root, or absolute)
+/// * `recursive` - Delete a directory with its contents (optional, defaults to false)
+///
+/// # Returns
+///
+/// `PATH_NOT_ALLOWED` for paths outside the scope and for the project root
+/// and granted directories themselves, or `FS_ERROR`.
+#[tauri::command]
+pub fn fs_delete(
+    project: State<'_, ProjectManager>,
+    settings: State<'_, SettingsStore>,
+    path: String,
+    recursive: Option<bool>,
+) -> CommandResult<()> {
+    log::info!("Command: fs_delete path={path}");
+    Ok(scope(&project, &settings).delete(&path, recursive.unwrap_or(false))?)
+}
+
+/// Watch a directory for file changes.
+///
+/// Changes are reported in debounced batches with `fs://changed` events.
+///
+/// # Arguments
+///
+/// * `path` - Directory to watch recursively (relative to the project
+///   root, or absolute)
+/// * `patterns` - .gitignore-style patterns selecting the reported files
+///   (optional, defaults to all files)
+///
+/// # Returns
+///
+/// The watch, whose id is needed to stop it, or `PATH_NOT_ALLOWED`,
+/// `INVALID_PATTERN`, or `WATCH_ERROR`.
+#[tauri::command]
+pub fn fs_watch(
+    app: AppHandle,
+    project: State<'_, ProjectManager>,
+    settings: State<'_, SettingsStore>,
+    watches: State<'_, FileWatcher>,
+    path: String,
+    patterns: Option<Vec<String>>,
+) -> CommandResult<WatchInfo> {
+    log::info!("Command: fs_watch path={path}");
+    let dir = scope(&project, &settings).resolve(&path)?;
+    let patterns =
+        PathPatterns::parse(&patterns.unwrap_or_default()).map_err(|e| CommandError {
+            code: "INVALID_PATTERN".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })?;
+
+    watches
+        .start(&dir, patterns, move |batch| {
+            if let Err(e) = app.emit_all(CHANGED_EVENT, batch) {
+                log::warn!("Failed to emit {CHANGED_EVENT}: {e}");
+            }
+        })
+        .map_err(|e| CommandError {
+            code: "WATCH_ERROR".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })
+}
+
+/// Stop a file watch.
+///
+/// # Returns
+///
+/// `true` if the watch existed.
+#[tauri::command]
+pub fn fs_unwatch(watches: State<'_, FileWatcher>, id: String) -> CommandResult<bool> {
+    log::info!("Command: fs_unwatch id={id}");
+    Ok(watches.stop(&id))
+}
+
+/// List active file watches.
+#[tauri::command]
+pub fn fs_watch_list(watches: State<'_, FileWatcher>) -> CommandResult<Vec<WatchInfo>> {
+    log::debug!("Command: fs_watch_list");
+    Ok(watches.list())
+}
@@ -0,0 +1,35 @@
+//! src-tauri/src/commands/hardware.rs
+//! ===================================
+//! Tauri command for hardware capability detection.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const hw = await invoke('detect_hardware');
+//!     const vram = hw.gpus[0]?.vram_bytes ?? 0;
+//!     ```
+
+use super::{CommandError, CommandResult};
+use crate::hardware::{self, HardwareInfo};
+
+/// Detect CPU, memory, GPU, and disk capabilities.
+///
+/// # Returns
+///
+/// Hardware report (GPUs are empty when none can be probed).
+#[tauri::command]
+pub async fn detect_hardware() -> CommandResult<HardwareInfo> {
+    log::info!("Command: detect_hardware");
+
+    tauri::async_runtime::spawn_blocking(hardware::detect_hardware)
+        .await
+        .map_err(|e| CommandError {
+            code: "HARDWARE_DETECTION_ERROR".to_string(),
+            message: e.to_string(),
+            details: None,
+            source_chain: None,
+        })
+}
@@ -0,0 +1,56 @@
+//! src-tauri/src/commands/health_history.rs
+//! ========================================
+//! Tauri command for the persisted plugin host health history.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! `health_check` reports the current state only; this command adds what
+//! happened in previous sessions (crashes, respawns, failure bursts), see
+//! `crate::health_history`.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const week = await invoke('health_history', { days: 7 });
+//!     console.log(`Crashed ${week.crashes} times in the last ${week.days} days`);
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::health_history::{HealthHistory, HealthSummary};
+
+/// Default number of days covered by `health_history`
+const DEFAULT_DAYS: u32 = 7;
+
+/// Convert a health history error into a `CommandError`.
+fn history_error(message: String) -> CommandError {
+    CommandError {
+        code: "HEALTH_HISTORY_ERROR".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Summarize the plugin host health history.
+///
+/// # Arguments
+///
+/// * `days` - Days to cover (optional, defaults to 7)
+///
+/// # Returns
+///
+/// Crash, respawn, degradation, and failed-check counts, and the most
+/// recent events.
+#[tauri::command]
+pub fn health_history(
+    history: State<'_, HealthHistory>,
+    days: Option<u32>,
+) -> CommandResult<HealthSummary> {
+    log::debug!("Command: health_history days={days:?}");
+    history
+        .summary(days.unwrap_or(DEFAULT_DAYS))
+        .map_err(history_error)
+}
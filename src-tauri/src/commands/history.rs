@@ -0,0 +1,136 @@
+//! src-tauri/src/commands/history.rs
+//! ==================================
+//! Tauri commands for persisted LLM chat history.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     await invoke('history_append', { sessionId, role: 'user', content: prompt });
+//!     const page = await invoke('history_list', { sessionId, limit: 50, offset: 0 });
+//!     const sessions = await invoke('history_sessions');
+//!     const hits = await invoke('history_search', { query: 'dark mode' });
+//!     await invoke('history_delete', { sessionId });
+//!     ```
+
+use serde_json::Value;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::history::{self, HistoryHit, HistoryMessage, HistorySession};
+use crate::storage::{Page, Storage};
+
+/// Default page size for list and search commands
+const DEFAULT_PAGE_SIZE: u64 = 50;
+
+/// Build a history error with the given code.
+fn history_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Append a message to a chat session.
+///
+/// # Arguments
+///
+/// * `session_id` - Session id (created on first append)
+/// * `role` - Speaker role (user, assistant, system, tool)
+/// * `content` - Message text
+/// * `metadata` - Optional JSON metadata (model, token counts, ...)
+#[tauri::command]
+pub fn history_append(
+    storage: State<'_, Storage>,
+    session_id: String,
+    role: String,
+    content: String,
+    metadata: Option<Value>,
+) -> CommandResult<HistoryMessage> {
+    log::debug!("Command: history_append session={session_id} role={role}");
+    history::append(&storage, &session_id, &role, &content, metadata.as_ref())
+        .map_err(|e| history_error("HISTORY_ERROR", e))
+}
+
+/// List a session's messages in chronological order.
+///
+/// # Arguments
+///
+/// * `limit` - Page size (default 50)
+/// * `offset` - Number of messages to skip (default 0)
+#[tauri::command]
+pub fn history_list(
+    storage: State<'_, Storage>,
+    session_id: String,
+    limit: Option<u64>,
+    offset: Option<u64>,
+) -> CommandResult<Page<HistoryMessage>> {
+    log::debug!("Command: history_list session={session_id}");
+    history::list_messages(
+        &storage,
+        &session_id,
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        offset.unwrap_or(0),
+    )
+    .map_err(|e| history_error("HISTORY_ERROR", e))
+}
+
+/// List chat sessions, most recently active first.
+#[tauri::command]
+pub fn history_sessions(
+    storage: State<'_, Storage>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+) -> CommandResult<Page<HistorySession>> {
+    log::debug!("Command: history_sessions");
+    history::list_sessions(
+        &storage,
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+        offset.unwrap_or(0),
+    )
+    .map_err(|e| history_error("HISTORY_ERROR", e))
+}
+
+/// Full-text search across chat history.
+///
+/// # Arguments
+///
+/// * `query` - Search terms (all must match)
+/// * `session_id` - Restrict to one session (optional)
+/// * `limit` - Maximum hits (default 50)
+#[tauri::command]
+pub fn history_search(
+    storage: State<'_, Storage>,
+    query: String,
+    session_id: Option<String>,
+    limit: Option<u64>,
+) -> CommandResult<Vec<HistoryHit>> {
+    log::debug!("Command: history_search query={query:?} session={session_id:?}");
+    history::search(
+        &storage,
+        &query,
+        session_id.as_deref(),
+        limit.unwrap_or(DEFAULT_PAGE_SIZE),
+    )
+    .map_err(|e| history_error("HISTORY_SEARCH_ERROR", e))
+}
+
+/// Delete a chat session, or a single message when `message_id` is given.
+///
+/// # Returns
+///
+/// Number of messages deleted.
+#[tauri::command]
+pub fn history_delete(
+    storage: State<'_, Storage>,
+    session_id: String,
+    message_id: Option<i64>,
+) -> CommandResult<usize> {
+    log::info!("Command: history_delete session={session_id} message={message_id:?}");
+    history::delete(&storage, &session_id, message_id)
+        .map_err(|e| history_error("HISTORY_ERROR", e))
+}
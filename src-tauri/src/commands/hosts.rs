@@ -0,0 +1,151 @@
+//! src-tauri/src/commands/hosts.rs
+//! ===============================
+//! Tauri commands for defining named plugin hosts.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A named host runs its own Python plugin host with extra environment
+//! variables (see `crate::ipc::hosts`). The name is passed as the optional
+//! `host` argument of `ipc_call` and `plugin_call`; the host is started on
+//! first use.
+//!
+//! Plugins can also be sandboxed, by their manifest or the user's choice;
+//! they then run in a host of their own (see `crate::ipc::sandbox`).
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     await invoke('host_define', {
+//!         name: 'ml-gpu',
+//!         spec: { env: { CUDA_VISIBLE_DEVICES: '0' } },
+//!     });
+//!     const result = await invoke('plugin_call', {
+//!         host: 'ml-gpu',
+//!         plugin: 'stt_whisper',
+//!         method: 'transcribe',
+//!         args: { path: 'clip.wav' },
+//!     });
+//!     const hosts = await invoke('host_list');
+//!     await invoke('host_remove', { name: 'ml-gpu' });
+//!
+//!     const { declared, effective } = await invoke('plugin_isolation', { plugin: 'scraper' });
+//!     await invoke('set_plugin_isolation', { plugin: 'scraper', isolation: 'sandboxed' });
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::ipc::hosts::{HostInfo, HostRegistry, HostSpec, PluginIsolation};
+use crate::ipc::manager::IpcManagerState;
+use crate::ipc::sandbox::Isolation;
+
+/// Convert a host registry error into a `CommandError`.
+fn host_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Shut down the manager of a host that was redefined or removed.
+async fn retire(manager: Option<IpcManagerState>) {
+    if let Some(manager) = manager {
+        if let Err(e) = manager.shutdown().await {
+            log::warn!("Plugin host shutdown error: {e}");
+        }
+    }
+}
+
+/// List the plugin hosts.
+///
+/// # Returns
+///
+/// The core host followed by the named hosts, with their lifecycle state.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn host_list(hosts: State<'_, HostRegistry>) -> CommandResult<Vec<HostInfo>> {
+    log::debug!("Command: host_list");
+    Ok(hosts.list().await)
+}
+
+/// Define or replace a named plugin host.
+///
+/// A running host with the same name is shut down; the next call starts it
+/// with the new spec.
+///
+/// # Arguments
+///
+/// * `name` - Host name (lowercase letters, digits, `-`, `_`)
+/// * `spec` - Extra environment variables and optional interpreter overrides
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn host_define(
+    hosts: State<'_, HostRegistry>,
+    name: String,
+    spec: HostSpec,
+) -> CommandResult<()> {
+    log::info!("Command: host_define name={name}");
+    let retired = hosts
+        .define(&name, spec)
+        .map_err(|e| host_error("INVALID_HOST", e))?;
+    retire(retired).await;
+    Ok(())
+}
+
+/// Remove a named plugin host and shut it down.
+///
+/// # Arguments
+///
+/// * `name` - Host name
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn host_remove(hosts: State<'_, HostRegistry>, name: String) -> CommandResult<()> {
+    log::info!("Command: host_remove name={name}");
+    let retired = hosts
+        .remove(&name)
+        .map_err(|e| host_error("HOST_NOT_FOUND", e))?;
+    retire(retired).await;
+    Ok(())
+}
+
+/// Get how a plugin runs: the isolation its manifest declares, the user's
+/// choice, and the one that applies.
+///
+/// # Arguments
+///
+/// * `plugin` - Plugin name
+#[tauri::command]
+pub fn plugin_isolation(
+    hosts: State<'_, HostRegistry>,
+    plugin: String,
+) -> CommandResult<PluginIsolation> {
+    log::debug!("Command: plugin_isolation plugin={plugin}");
+    Ok(hosts.plugin_isolation(&plugin))
+}
+
+/// Choose how a plugin runs, overriding its manifest.
+///
+/// Applies the next time the plugin is loaded. A plugin that no longer
+/// runs sandboxed has its sandboxed host shut down.
+///
+/// # Arguments
+///
+/// * `plugin` - Plugin name
+/// * `isolation` - `standard` or `sandboxed` (optional; none follows the manifest)
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn set_plugin_isolation(
+    hosts: State<'_, HostRegistry>,
+    plugin: String,
+    isolation: Option<Isolation>,
+) -> CommandResult<()> {
+    log::info!("Command: set_plugin_isolation plugin={plugin} isolation={isolation:?}");
+    let retired = hosts
+        .set_isolation(&plugin, isolation)
+        .map_err(|e| host_error("IO_ERROR", e))?;
+    retire(retired).await;
+    Ok(())
+}
@@ -0,0 +1,77 @@
+//! src-tauri/src/commands/http.rs
+//! ===============================
+//! Tauri command for HTTP requests made through the app.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! `http_fetch` lets the frontend reach web APIs that don't allow the
+//! webview's origin (CORS). Only hosts in `http.allowed_domains` can be
+//! contacted, within the `http.*` timeout and size limits (see
+//! `crate::http_fetch`).
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const { status, headers, body } = await invoke('http_fetch', {
+//!         url: 'https://api.example.com/v1/items',
+//!         method: 'POST',
+//!         headers: { 'Content-Type': 'application/json' },
+//!         body: JSON.stringify({ name: 'item' })
+//!     });
+//!     ```
+
+use std::collections::BTreeMap;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::http_fetch::{self, FetchRequest, FetchResponse};
+use crate::settings::SettingsStore;
+
+/// Make an HTTP request to an allowed domain.
+///
+/// # Arguments
+///
+/// * `url` - HTTP(S) URL
+/// * `method` - HTTP method (optional, defaults to GET)
+/// * `headers` - Request headers (optional)
+/// * `body` - Request body (optional)
+/// * `timeout_secs` - Timeout (optional, at most `http.timeout_secs`)
+///
+/// # Returns
+///
+/// The status, headers, and body of the response (also for error
+/// statuses), or `DOMAIN_NOT_ALLOWED`, `INVALID_REQUEST`, `OFFLINE`,
+/// `REQUEST_TOO_LARGE`, `RESPONSE_TOO_LARGE`, `FETCH_TIMEOUT`, or
+/// `FETCH_FAILED`.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn http_fetch(
+    settings: State<'_, SettingsStore>,
+    url: String,
+    method: Option<String>,
+    headers: Option<BTreeMap<String, String>>,
+    body: Option<String>,
+    timeout_secs: Option<u64>,
+) -> CommandResult<FetchResponse> {
+    log::info!(
+        "Command: http_fetch {} {url}",
+        method.as_deref().unwrap_or("GET")
+    );
+    let policy = settings.get().http.policy();
+    let request = FetchRequest {
+        url,
+        method,
+        headers: headers.unwrap_or_default(),
+        body,
+        timeout_secs,
+    };
+    http_fetch::fetch(&policy, request)
+        .await
+        .map_err(|e| CommandError {
+            code: e.code.to_string(),
+            message: e.message,
+            details: None,
+            source_chain: None,
+        })
+}
@@ -0,0 +1,157 @@
+//! src-tauri/src/commands/jobs.rs
+//! ===============================
+//! Tauri commands for long-running background jobs.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Each job emits `job/<id>/progress` events while running and a single
+//! `job/<id>/finished` event when it completes, fails, or is cancelled.
+//! The event payload is the full `Job`. `job_result` returns the outcome of
+//! a finished job, for callers that start listening after it finished.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!     import { listen } from '@tauri-apps/api/event';
+//!
+//!     const job = await invoke('job_start', { method: 'tts/synthesize', params: { text } });
+//!     await listen(`job/${job.id}/progress`, (e) => setProgress(e.payload.progress));
+//!     await invoke('job_cancel', { id: job.id });
+//!
+//!     const call = await invoke('plugin_call_async', { plugin, method: 'train', args });
+//!     await listen(`job/${call.id}/finished`, async () => {
+//!         const result = await invoke('job_result', { id: call.id });
+//!     });
+//!     ```
+
+use serde_json::{json, Value};
+use tauri::{AppHandle, Manager, State};
+
+use super::{request_manager_for, CommandError, CommandResult};
+use crate::ipc::hosts::HostRegistry;
+use crate::ipc::IpcError;
+use crate::jobs::{Job, JobManager, JobStatus};
+use crate::workspace::WorkspaceRegistry;
+
+/// Build a job error with the given code.
+fn job_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Event callback emitting a job's events to all windows.
+pub(super) fn emit_job_events(app: AppHandle) -> impl Fn(String, &Job) + Send + 'static {
+    move |event, job| {
+        if let Err(e) = app.emit_all(&event, job) {
+            log::warn!("Failed to emit {event}: {e}");
+        }
+    }
+}
+
+/// Start a plugin call as a background job.
+///
+/// # Arguments
+///
+/// * `method` - JSON-RPC method name
+/// * `params` - Method parameters (optional)
+/// * `workspace` - Workspace id (optional, default workspace if omitted)
+///
+/// # Returns
+///
+/// The running job (poll with `job_status` or listen for job events).
+/// Methods are routed and refused like `ipc_call`'s (see
+/// `request_manager_for`).
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn job_start(
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    jobs: State<'_, JobManager>,
+    method: String,
+    params: Option<Value>,
+    workspace: Option<String>,
+) -> CommandResult<Job> {
+    log::info!("Command: job_start method={method}");
+    let params = params.unwrap_or(json!({}));
+    let state = request_manager_for(
+        &workspaces,
+        &hosts,
+        workspace.as_deref(),
+        None,
+        &method,
+        &params,
+    )
+    .await?;
+
+    if !state.is_ready().await {
+        return Err(CommandError::from(IpcError::NotRunning));
+    }
+
+    Ok(jobs.start(state, method, params, None, workspace, emit_job_events(app)))
+}
+
+/// Get a job by id.
+#[tauri::command]
+pub fn job_status(jobs: State<'_, JobManager>, id: String) -> CommandResult<Job> {
+    log::debug!("Command: job_status id={id}");
+    jobs.get(&id)
+        .ok_or_else(|| job_error("JOB_NOT_FOUND", format!("Unknown job: {id}")))
+}
+
+/// Get the result of a finished job.
+///
+/// # Returns
+///
+/// The call result, `JOB_RUNNING` while the job runs, `JOB_FAILED` with the
+/// call's error message, or `JOB_CANCELLED`.
+#[tauri::command]
+pub fn job_result(jobs: State<'_, JobManager>, id: String) -> CommandResult<Value> {
+    log::debug!("Command: job_result id={id}");
+    let job = jobs
+        .get(&id)
+        .ok_or_else(|| job_error("JOB_NOT_FOUND", format!("Unknown job: {id}")))?;
+    match job.status {
+        JobStatus::Completed => Ok(job.result.unwrap_or(Value::Null)),
+        JobStatus::Running => Err(job_error(
+            "JOB_RUNNING",
+            format!("Job {id} is still running"),
+        )),
+        JobStatus::Failed => Err(job_error(
+            "JOB_FAILED",
+            job.error.unwrap_or_else(|| format!("Job {id} failed")),
+        )),
+        JobStatus::Cancelled => Err(job_error(
+            "JOB_CANCELLED",
+            format!("Job {id} was cancelled"),
+        )),
+    }
+}
+
+/// List running and recently finished jobs (most recent first).
+#[tauri::command]
+pub fn job_list(jobs: State<'_, JobManager>) -> CommandResult<Vec<Job>> {
+    log::debug!("Command: job_list");
+    Ok(jobs.list())
+}
+
+/// Cancel a running job.
+///
+/// # Returns
+///
+/// The cancelled job.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn job_cancel(jobs: State<'_, JobManager>, id: String) -> CommandResult<Job> {
+    log::info!("Command: job_cancel id={id}");
+    if jobs.get(&id).is_none() {
+        return Err(job_error("JOB_NOT_FOUND", format!("Unknown job: {id}")));
+    }
+    jobs.cancel(&id)
+        .await
+        .map_err(|e| job_error("JOB_CANCEL_ERROR", e))
+}
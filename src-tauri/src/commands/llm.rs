@@ -0,0 +1,327 @@
+//! src-tauri/src/commands/llm.rs
+//! ==============================
+//! Tauri commands for LLM generation through the backend proxy.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The active API key for the service is resolved and decrypted in Rust
+//! and the provider call is made by `crate::llm::LlmProxy`, so key values
+//! never enter the webview; no command returns a raw key value.
+//! Ollama needs no key and is routed the same way, so the app works fully
+//! offline with a local model. In offline mode (see `crate::offline`) other
+//! services fail with `OFFLINE` before a key is resolved.
+//!
+//! `llm_stream` forwards the reply as it is generated: `llm/<id>/delta`
+//! events carry new text and one `llm/<id>/finished` event carries the
+//! outcome (`LlmStreamEvent`). The caller picks the stream id and listens
+//! before invoking, so no text is missed.
+//!
+//! `provider_models` lists the models the active key can use, which both
+//! fills model pickers and confirms the key is accepted.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const reply = await invoke('llm_generate', {
+//!         service: 'gemini',
+//!         model: 'gemini-2.5-flash',
+//!         messages: [
+//!             { role: 'system', content: 'You are concise.' },
+//!             { role: 'user', content: 'Hello', images: [] }
+//!         ],
+//!         options: { temperature: 0.3 }
+//!     });
+//!     console.log(reply.text, reply.usage?.total_tokens);
+//!
+//!     const id = crypto.randomUUID();
+//!     await listen(`llm/${id}/delta`, e => append(e.payload.text));
+//!     await listen(`llm/${id}/finished`, e => done(e.payload));
+//!     await invoke('llm_stream', { streamId: id, service, model, messages });
+//!     await invoke('llm_cancel', { streamId: id });
+//!
+//!     const { models } = await invoke('provider_models', { service: 'openai' });
+//!     const vectors = await invoke('llm_embed', {
+//!         service: 'openai', model: 'text-embedding-3-small', inputs: ['Hello']
+//!     });
+//!     ```
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use super::secrets;
+use super::{CommandError, CommandResult};
+use crate::llm::{LlmMessage, LlmOptions, LlmProxy, LlmResponse, ProviderKey};
+use crate::offline;
+use crate::project::ProjectManager;
+use crate::providers::{self, ModelInfo, Provider};
+use crate::secret_store::SecretBackends;
+use crate::vault::KeyVault;
+
+/// Models returned by `provider_models`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderModels {
+    /// Service the models belong to
+    pub service: String,
+    /// ID of the key used (None for keyless Ollama)
+    pub key_id: Option<String>,
+    /// Available models, sorted by ID
+    pub models: Vec<ModelInfo>,
+}
+
+/// Resolve the provider of a service and its active key.
+///
+/// Keyless services (Ollama) get an empty key when none is configured.
+/// Services on other machines fail with `OFFLINE` in offline mode.
+fn resolve(
+    project: &ProjectManager,
+    vault: &KeyVault,
+    backends: &SecretBackends,
+    service: &str,
+    profile: Option<String>,
+) -> CommandResult<(Box<dyn Provider>, ProviderKey)> {
+    let service = service.to_lowercase();
+    let env_vars = secrets::parse_env_file(&project.env_path());
+    let Some(provider) = providers::get(&service, &env_vars) else {
+        return Err(CommandError {
+            code: "UNSUPPORTED_SERVICE".to_string(),
+            message: format!(
+                "No provider for {service} (supported: {})",
+                providers::SERVICES.join(", ")
+            ),
+            details: None,
+            source_chain: None,
+        });
+    };
+    if offline::is_offline() && !provider.is_local() {
+        return Err(CommandError {
+            code: "OFFLINE".to_string(),
+            message: format!("Offline mode is on; {service} is not reachable"),
+            details: None,
+            source_chain: None,
+        });
+    }
+
+    let active = secrets::active_key(&project.env_path(), vault, backends, &service, profile)?;
+    let (id, key) = match active {
+        Some(active) => active,
+        None if !provider.requires_key() => (String::new(), String::new()),
+        None => {
+            return Err(CommandError {
+                code: "NO_ACTIVE_KEY".to_string(),
+                message: format!("No API key configured for {service}"),
+                details: None,
+                source_chain: None,
+            })
+        }
+    };
+    Ok((provider, ProviderKey { service, id, key }))
+}
+
+/// Generate a reply with the service's active API key.
+///
+/// # Arguments
+///
+/// * `service` - Provider (`gemini`, `openai`, `anthropic`, `ollama`)
+/// * `model` - Provider model ID
+/// * `messages` - Conversation, oldest first (system messages become the system prompt)
+/// * `options` - Temperature, token limit, and key profile (optional)
+///
+/// # Returns
+///
+/// The generated text with the provider's finish reason and token usage.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn llm_generate(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    backends: State<'_, SecretBackends>,
+    proxy: State<'_, LlmProxy>,
+    service: String,
+    model: String,
+    messages: Vec<LlmMessage>,
+    options: Option<LlmOptions>,
+) -> CommandResult<LlmResponse> {
+    log::info!("Command: llm_generate service={service} model={model}");
+
+    let options = options.unwrap_or_default();
+    let (provider, key) = resolve(
+        &project,
+        &vault,
+        &backends,
+        &service,
+        options.profile.clone(),
+    )?;
+
+    proxy
+        .generate(provider.as_ref(), &key, &model, &messages, &options)
+        .await
+        .map_err(|e| CommandError {
+            code: "LLM_REQUEST_ERROR".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })
+}
+
+/// Embed texts with the service's active API key.
+///
+/// # Arguments
+///
+/// * `service` - Provider (`gemini`, `openai`, `ollama`; Anthropic has no embeddings API)
+/// * `model` - Embedding model ID (e.g. `text-embedding-3-small`)
+/// * `inputs` - Texts to embed
+/// * `profile` - Key profile (default: the active profile)
+///
+/// # Returns
+///
+/// One vector per input, in order.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn llm_embed(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    backends: State<'_, SecretBackends>,
+    proxy: State<'_, LlmProxy>,
+    service: String,
+    model: String,
+    inputs: Vec<String>,
+    profile: Option<String>,
+) -> CommandResult<Vec<Vec<f32>>> {
+    log::info!(
+        "Command: llm_embed service={service} model={model} inputs={}",
+        inputs.len()
+    );
+
+    let (provider, key) = resolve(&project, &vault, &backends, &service, profile)?;
+
+    proxy
+        .embed(provider.as_ref(), &key, &model, &inputs)
+        .await
+        .map_err(|e| CommandError {
+            code: "LLM_REQUEST_ERROR".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })
+}
+
+/// Stream a reply with the service's active API key.
+///
+/// Text arrives as `llm/<stream_id>/delta` events; the outcome follows as
+/// one `llm/<stream_id>/finished` event.
+///
+/// # Arguments
+///
+/// * `stream_id` - Caller-chosen id (letters, digits, and dashes, e.g. a UUID)
+/// * `service` - Provider (`gemini`, `openai`, `anthropic`, `ollama`)
+/// * `model` - Provider model ID
+/// * `messages` - Conversation, oldest first (system messages become the system prompt)
+/// * `options` - Temperature, token limit, and key profile (optional)
+#[tauri::command]
+pub fn llm_stream(
+    app: AppHandle,
+    proxy: State<'_, LlmProxy>,
+    stream_id: String,
+    service: String,
+    model: String,
+    messages: Vec<LlmMessage>,
+    options: Option<LlmOptions>,
+) -> CommandResult<()> {
+    log::info!("Command: llm_stream id={stream_id} service={service} model={model}");
+
+    let options = options.unwrap_or_default();
+    let (provider, key) = resolve(
+        &app.state::<ProjectManager>(),
+        &app.state::<KeyVault>(),
+        &app.state::<SecretBackends>(),
+        &service,
+        options.profile.clone(),
+    )?;
+
+    proxy
+        .stream(
+            &stream_id,
+            provider.as_ref(),
+            key,
+            &model,
+            &messages,
+            &options,
+            move |event, payload| {
+                if let Err(e) = app.emit_all(&event, payload) {
+                    log::warn!("Failed to emit {event}: {e}");
+                }
+            },
+        )
+        .map_err(|e| CommandError {
+            code: "LLM_STREAM_ERROR".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })
+}
+
+/// Cancel a running stream.
+///
+/// The stream ends with a `cancelled` finished event carrying the text
+/// received so far.
+#[tauri::command]
+pub fn llm_cancel(proxy: State<'_, LlmProxy>, stream_id: String) -> CommandResult<()> {
+    log::info!("Command: llm_cancel id={stream_id}");
+    proxy.cancel(&stream_id).map_err(|e| CommandError {
+        code: "STREAM_NOT_FOUND".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// List the models available with the service's active API key.
+///
+/// A successful listing also confirms the provider accepts the key.
+///
+/// # Arguments
+///
+/// * `service` - Provider (`gemini`, `openai`, `anthropic`, `ollama`)
+/// * `profile` - Key profile (default: the active profile)
+///
+/// # Returns
+///
+/// The models with the ID of the key used. A rejected key fails with
+/// `INVALID_API_KEY`.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn provider_models(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    backends: State<'_, SecretBackends>,
+    proxy: State<'_, LlmProxy>,
+    service: String,
+    profile: Option<String>,
+) -> CommandResult<ProviderModels> {
+    log::info!("Command: provider_models service={service}");
+
+    let (provider, key) = resolve(&project, &vault, &backends, &service, profile)?;
+
+    let models = provider
+        .list_models(proxy.client(), &key.key)
+        .await
+        .map_err(|e| CommandError {
+            code: if e.is_auth() {
+                "INVALID_API_KEY"
+            } else {
+                "PROVIDER_ERROR"
+            }
+            .to_string(),
+            message: e.to_string(),
+            details: None,
+            source_chain: None,
+        })?;
+
+    log::info!("{}: {} models available", key.service, models.len());
+    Ok(ProviderModels {
+        key_id: (!key.id.is_empty()).then_some(key.id),
+        service: key.service,
+        models,
+    })
+}
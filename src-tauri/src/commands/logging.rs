@@ -0,0 +1,65 @@
+//! src-tauri/src/commands/logging.rs
+//! ==================================
+//! Tauri commands for runtime log level control and log retrieval.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     await invoke('log_set_level', { level: 'debug' });
+//!     const logs = await invoke('get_app_logs', { level: 'warn', limit: 200 });
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::logging::{parse_level, LogEntry, LogSink};
+
+/// Convert a log level error into a `CommandError`.
+fn level_error(message: String) -> CommandError {
+    CommandError {
+        code: "INVALID_LOG_LEVEL".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Change the application log level at runtime.
+///
+/// # Arguments
+///
+/// * `level` - off, error, warn, info, debug, or trace
+///
+/// # Returns
+///
+/// The new log level.
+#[tauri::command]
+pub fn log_set_level(sink: State<'_, LogSink>, level: String) -> CommandResult<String> {
+    let filter = sink.set_level(&level).map_err(level_error)?;
+    log::info!("Command: log_set_level level={filter}");
+    Ok(filter.to_string())
+}
+
+/// Get recent application log entries (oldest first).
+///
+/// # Arguments
+///
+/// * `level` - Minimum level to include (optional, default: all)
+/// * `limit` - Maximum number of most recent entries (optional)
+#[tauri::command]
+pub fn get_app_logs(
+    sink: State<'_, LogSink>,
+    level: Option<String>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<LogEntry>> {
+    let min_level = level
+        .as_deref()
+        .map(parse_level)
+        .transpose()
+        .map_err(level_error)?;
+
+    Ok(sink.entries(min_level, limit))
+}
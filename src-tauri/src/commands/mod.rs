@@ -8,9 +8,43 @@
 //! This module provides:
 //! - Tauri commands exposed to React frontend via `invoke()`
 //! - IPC proxy commands for plugin communication
-//! - Health and status commands
+//! - Health and status commands (with a session history of request stats)
+//! - Error catalog command (D009 entries with retry and docs hints)
+//! - Pending request inspection and abort commands
+//! - Persisted health history command
 //! - Plugin management commands
+//! - Dynamic dispatch of commands declared in plugin manifests
+//! - Plugin permission commands
 //! - API key management commands (D079)
+//! - API key service catalog commands
+//! - External secret backend commands
+//! - OAuth provider connection commands
+//! - LLM generation through the backend proxy and provider model listing
+//! - Local Ollama detection and model pull commands
+//! - Project open/close commands
+//! - Workspace commands for multiple concurrently open projects
+//! - Named plugin host commands
+//! - Application settings commands (and the error message locale and offline mode)
+//! - Log level and log retrieval commands
+//! - Crash report commands
+//! - Startup progress command
+//! - Background job commands
+//! - Scheduled invocation commands
+//! - Hardware detection command
+//! - Model/file download commands
+//! - HTTP fetch command (domain allowlist, no CORS)
+//! - Project-scoped file and file watch commands for generated apps
+//! - Artifact cache commands
+//! - Structured storage and key-value store commands
+//! - Chat history commands
+//! - Generated app save/load/versioning/export commands
+//! - App preview server commands
+//! - Developer IPC console and fault injection commands (debug builds)
+//!
+//! IPC, plugin, health, and discovery commands accept an optional
+//! `workspace` id; when omitted they target the default workspace.
+//! `ipc_call` and `plugin_call` also accept an optional `host` name to
+//! route to a named plugin host (see `crate::ipc::hosts`).
 //!
 //! Dependencies:
 //!     - D035: manager.rs (`IpcManagerState`)
@@ -53,16 +87,68 @@
 //!     const status = await invoke('ipc_status');
 //!     ```
 
+pub mod apps;
+pub mod artifacts;
+pub mod catalog;
 pub mod compiler;
+pub mod crash;
+pub mod css;
+pub mod devtools;
+pub mod downloads;
+pub mod events;
+pub mod fs;
+pub mod hardware;
+pub mod health_history;
+pub mod history;
+pub mod hosts;
+pub mod http;
+pub mod jobs;
+pub mod llm;
+pub mod logging;
+pub mod oauth;
+pub mod ollama;
+pub mod permissions;
+pub mod preview;
+pub mod project;
+pub mod reverse_rpc;
+pub mod scheduler;
+pub mod secret_backends;
 pub mod secrets;
+pub mod settings;
+pub mod startup;
+pub mod storage;
+pub mod workspace;
 
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::value::RawValue;
 use serde_json::{json, Value};
-use tauri::State;
+use std::collections::BTreeSet;
+use tauri::{AppHandle, Manager, State};
 
-use crate::ipc::manager::{IpcManagerState, ManagerStats};
+use crate::error_catalog::{catalog, ErrorCategory, ErrorEntry};
+use crate::error_messages;
+use crate::ipc::hosts::{HostRegistry, CORE_HOST};
+use crate::ipc::manager::{IpcManagerState, ManagerStats, PendingInfo};
 use crate::ipc::health::HealthStatus;
+use crate::ipc::metrics::{merge_stats, PluginStats};
+use crate::ipc::sandbox::Isolation;
+use crate::ipc::schemas::{FieldError, SchemaRegistry};
 use crate::ipc::IpcError;
+use crate::jobs::{Job, JobManager, LONG_CALL_TIMEOUT_SECS};
+use crate::migration::{self, ExportedState, MigrationOutcome, PluginUpdate};
+use crate::packages::{
+    ensure_allowed, installed_signature, is_trusted_bundled, verify_package, InstalledPlugin,
+    PackageBackup, SignatureStatus, StagedPackage,
+};
+use crate::permissions::PermissionStore;
+use crate::plugin_data::{self, PluginDataUsage};
+use crate::plugin_commands::{CommandRegistry, PluginCommand};
+use crate::plugin_graph::DependencyGraph;
+use crate::quotas::{PluginQuotas, QuotaExceeded, QuotaPermit};
+use crate::settings::{PluginSettings, SettingsStore};
+use crate::stats_history::{StatsHistory, StatsSample};
+use crate::workspace::{WorkspaceRegistry, DEFAULT_WORKSPACE};
 
 // ============================================
 // COMMAND ERROR TYPE
@@ -70,16 +156,73 @@ use crate::ipc::IpcError;
 
 /// Error type for Tauri commands.
 ///
-/// Implements `serde::Serialize` as required by Tauri.
-#[derive(Debug, Clone, Serialize)]
+/// Implements `serde::Serialize` as required by Tauri. The serialized error
+/// also carries `category`, `retryable`, and `docs_url` for its code (see
+/// `crate::error_catalog`), so the frontend can decide whether to retry or
+/// what kind of message to show without knowing every code, and a
+/// `localized_message` in the locale set with `set_locale` (see
+/// `crate::error_messages`). `code` stays the same in every locale.
+#[derive(Debug, Clone)]
 pub struct CommandError {
     /// Error code
     pub code: String,
     /// Error message
     pub message: String,
     /// Additional details
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<Value>,
+    /// Underlying errors, outermost first (see `error_chain`)
+    pub source_chain: Option<Vec<String>>,
+}
+
+impl CommandError {
+    /// Category of the error code.
+    pub fn category(&self) -> ErrorCategory {
+        catalog().category(&self.code)
+    }
+
+    /// Whether the same call may succeed when retried.
+    pub fn retryable(&self) -> bool {
+        catalog().retryable(&self.code)
+    }
+
+    /// The message in the current locale.
+    pub fn localized_message(&self) -> String {
+        error_messages::localize(&self.code, &self.message)
+    }
+}
+
+impl Serialize for CommandError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let docs_url = catalog()
+            .lookup(&self.code)
+            .and_then(|entry| entry.docs_url.as_ref());
+        let mut state = serializer.serialize_struct("CommandError", 8)?;
+        state.serialize_field("code", &self.code)?;
+        state.serialize_field("message", &self.message)?;
+        state.serialize_field("localized_message", &self.localized_message())?;
+        match &self.details {
+            Some(details) => state.serialize_field("details", details)?,
+            None => state.skip_field("details")?,
+        }
+        state.serialize_field("category", &self.category())?;
+        state.serialize_field("retryable", &self.retryable())?;
+        match &self.source_chain {
+            Some(chain) => state.serialize_field("source_chain", chain)?,
+            None => state.skip_field("source_chain")?,
+        }
+        match docs_url {
+            Some(docs_url) => state.serialize_field("docs_url", docs_url)?,
+            None => state.skip_field("docs_url")?,
+        }
+        state.end()
+    }
+}
+
+/// An error and its sources (`std::error::Error::source`), outermost first.
+pub fn error_chain(error: &dyn std::error::Error) -> Vec<String> {
+    std::iter::successors(Some(error), |error| error.source())
+        .map(ToString::to_string)
+        .collect()
 }
 
 impl From<IpcError> for CommandError {
@@ -95,6 +238,7 @@ impl From<IpcError> for CommandError {
                     code: format!("RPC_ERROR_{code}"),
                     message: message.clone(),
                     details: Some(json!({ "rpc_code": code })),
+                    source_chain: Some(error_chain(&e)),
                 };
             }
             IpcError::ResponseMissing(id) => ("RESPONSE_MISSING", format!("Response missing for request {id}")),
@@ -104,12 +248,42 @@ impl From<IpcError> for CommandError {
             IpcError::ChannelClosed => ("CHANNEL_CLOSED", "Communication channel closed".to_string()),
             IpcError::NotInitialized => ("NOT_INITIALIZED", "IPC not initialized".to_string()),
             IpcError::ShuttingDown => ("SHUTTING_DOWN", "System is shutting down".to_string()),
+            IpcError::QueueFull(max) => ("QUEUE_FULL", format!("Too many pending requests (limit {max})")),
+            IpcError::Aborted(id) => ("REQUEST_ABORTED", format!("Request {id} aborted")),
+            IpcError::IdempotencyConflict(key) => (
+                "IDEMPOTENCY_CONFLICT",
+                format!("Idempotency key {key} is in use by a different request"),
+            ),
+            IpcError::RequestIdInUse(id) => (
+                "REQUEST_ID_IN_USE",
+                format!("Request id {id} is pending or was not reserved"),
+            ),
+            IpcError::MessageTooLarge { length, limit } => {
+                return Self {
+                    code: "MESSAGE_TOO_LARGE".to_string(),
+                    message: e.to_string(),
+                    details: Some(json!({ "length": length, "limit": limit })),
+                    source_chain: Some(error_chain(&e)),
+                };
+            }
+            IpcError::InterpreterNotFound(path) => ("INTERPRETER_NOT_FOUND", format!("Python interpreter not found: {path}")),
+            IpcError::ModuleNotFound { module } => ("MODULE_NOT_FOUND", format!("Python module not found: {module}")),
+            IpcError::HostSyntaxError { detail } => ("HOST_SYNTAX_ERROR", detail.clone()),
+            IpcError::StartupExit { exit_code, reason } => {
+                return Self {
+                    code: "HOST_STARTUP_FAILED".to_string(),
+                    message: format!("Plugin host exited during startup: {reason}"),
+                    details: Some(json!({ "exit_code": exit_code, "suggestion": e.suggestion() })),
+                    source_chain: Some(error_chain(&e)),
+                };
+            }
         };
 
         Self {
             code: code.to_string(),
             message,
-            details: None,
+            details: e.suggestion().map(|suggestion| json!({ "suggestion": suggestion })),
+            source_chain: Some(error_chain(&e)),
         }
     }
 }
@@ -127,6 +301,545 @@ impl std::fmt::Display for CommandError {
 /// Result type for Tauri commands.
 pub type CommandResult<T> = Result<T, CommandError>;
 
+/// Resolve the IPC manager for an optional workspace id.
+fn manager_for(
+    workspaces: &WorkspaceRegistry,
+    workspace: Option<&str>,
+) -> CommandResult<IpcManagerState> {
+    workspaces.get(workspace).map_err(|e| CommandError {
+        code: "WORKSPACE_NOT_FOUND".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// Resolve the IPC manager for an optional workspace id and host name,
+/// starting a named host on first use.
+///
+/// Named hosts run alongside the default workspace's host.
+async fn host_manager_for(
+    workspaces: &WorkspaceRegistry,
+    hosts: &HostRegistry,
+    workspace: Option<&str>,
+    host: Option<&str>,
+) -> CommandResult<IpcManagerState> {
+    let Some(host) = host.filter(|name| *name != CORE_HOST) else {
+        return manager_for(workspaces, workspace);
+    };
+    if workspace.is_some_and(|id| id != DEFAULT_WORKSPACE) {
+        return Err(CommandError {
+            code: "INVALID_HOST".to_string(),
+            message: format!("Host {host} belongs to the default workspace"),
+            details: None,
+            source_chain: None,
+        });
+    }
+
+    let state = hosts.get(host).map_err(|e| CommandError {
+        code: "HOST_NOT_FOUND".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })?;
+    hosts.ensure_started(host, &state).await?;
+    Ok(state)
+}
+
+/// Resolve the IPC manager for a plugin, like `host_manager_for`.
+///
+/// A sandboxed plugin in the default workspace without an explicit host
+/// runs in its own sandboxed host, started on first use.
+async fn plugin_manager_for(
+    workspaces: &WorkspaceRegistry,
+    hosts: &HostRegistry,
+    workspace: Option<&str>,
+    host: Option<&str>,
+    plugin: &str,
+) -> CommandResult<IpcManagerState> {
+    if let Some((name, state)) = sandbox_host_for(hosts, workspace, host, plugin) {
+        hosts.ensure_started(&name, &state).await?;
+        return Ok(state);
+    }
+    host_manager_for(workspaces, hosts, workspace, host).await
+}
+
+/// The sandboxed host (name and manager, not started) a plugin resolves to,
+/// or None if it runs in the workspace's or the named host.
+fn sandbox_host_for(
+    hosts: &HostRegistry,
+    workspace: Option<&str>,
+    host: Option<&str>,
+    plugin: &str,
+) -> Option<(String, IpcManagerState)> {
+    let default_workspace = workspace.unwrap_or(DEFAULT_WORKSPACE) == DEFAULT_WORKSPACE;
+    (host.is_none() && default_workspace && hosts.isolation(plugin) == Isolation::Sandboxed)
+        .then(|| hosts.sandbox_host(plugin))
+}
+
+/// Methods that only run through their own commands, which check
+/// signatures, permissions, dependencies, and isolation.
+const PLUGIN_COMMAND_METHODS: [(&str, &str); 3] = [
+    ("plugin/load", "plugin_load"),
+    ("plugin/unload", "plugin_unload"),
+    ("plugin/swap", "plugin_swap"),
+];
+
+/// Resolve the IPC manager for a request sent through a generic path
+/// (`ipc_call`, `ipc_batch`, `job_start`, the devtools console).
+///
+/// Loading, unloading, and swapping plugins fail with `USE_PLUGIN_COMMAND`
+/// (`details.command` names the command to use instead). Other `plugin/*`
+/// requests naming a plugin go to that plugin's host like `plugin_call`,
+/// so a sandboxed plugin is never reached in the core host.
+pub(crate) async fn request_manager_for(
+    workspaces: &WorkspaceRegistry,
+    hosts: &HostRegistry,
+    workspace: Option<&str>,
+    host: Option<&str>,
+    method: &str,
+    params: &Value,
+) -> CommandResult<IpcManagerState> {
+    if let Some((_, command)) = PLUGIN_COMMAND_METHODS.iter().find(|(m, _)| *m == method) {
+        return Err(CommandError {
+            code: "USE_PLUGIN_COMMAND".to_string(),
+            message: format!("{method} can't be sent directly; use the {command} command"),
+            details: Some(json!({ "method": method, "command": command })),
+            source_chain: None,
+        });
+    }
+    match requested_plugin(method, params) {
+        Some(plugin) => plugin_manager_for(workspaces, hosts, workspace, host, plugin).await,
+        None => host_manager_for(workspaces, hosts, workspace, host).await,
+    }
+}
+
+/// The plugin a `plugin/*` request is about (`plugin` or `name` param).
+fn requested_plugin<'a>(method: &str, params: &'a Value) -> Option<&'a str> {
+    if !method.starts_with("plugin/") {
+        return None;
+    }
+    ["plugin", "name"]
+        .iter()
+        .find_map(|key| params.get(*key).and_then(Value::as_str))
+}
+
+/// Project root whose `plugins` directory a host loads plugins from.
+fn plugin_root(state: &IpcManagerState) -> std::path::PathBuf {
+    state
+        .config()
+        .working_dir
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+/// Fail unless a plugin's package signature is trusted or the user allowed
+/// it unsigned (see `crate::packages`).
+///
+/// The error (`UNSIGNED_PLUGIN` or `INVALID_SIGNATURE`) carries the
+/// signature status in `details.signature`.
+fn check_signature(
+    plugin: &str,
+    status: &SignatureStatus,
+    settings: &PluginSettings,
+) -> CommandResult<()> {
+    ensure_allowed(plugin, status, settings).map_err(|message| CommandError {
+        code: match status {
+            SignatureStatus::Invalid { .. } => "INVALID_SIGNATURE",
+            _ => "UNSIGNED_PLUGIN",
+        }
+        .to_string(),
+        message,
+        details: Some(json!({ "plugin": plugin, "signature": status })),
+        source_chain: None,
+    })
+}
+
+/// Check the signature of a plugin and ask for the permissions it declares
+/// before `plugin/load` or `plugin/swap`, and pass the granted ones and the
+/// plugin's data directory (see `crate::plugin_data`) to the host.
+///
+/// Other methods are passed through unchanged.
+async fn authorize_plugin_load(
+    permissions: &PermissionStore,
+    settings: &SettingsStore,
+    state: &IpcManagerState,
+    method: &str,
+    mut params: Value,
+) -> CommandResult<Value> {
+    let key = match method {
+        "plugin/load" => "name",
+        "plugin/swap" => "new",
+        _ => return Ok(params),
+    };
+    let Some(plugin) = params.get(key).and_then(Value::as_str).map(str::to_string) else {
+        return Ok(params);
+    };
+    let root = plugin_root(state);
+
+    // Plugins without a manifest are left to the host to reject
+    let plugins = settings.get().plugins;
+    if let Some(status) = installed_signature(&root, &plugin, &plugins.trusted_keys) {
+        if !is_trusted_bundled(&plugin, &status, &plugins) {
+            check_signature(&plugin, &status, &plugins)?;
+        }
+    }
+
+    let granted = permissions
+        .authorize_load(&root, &plugin)
+        .await
+        .map_err(|message| CommandError {
+            code: "PERMISSION_DENIED".to_string(),
+            message,
+            details: Some(json!({ "plugin": plugin })),
+            source_chain: None,
+        })?;
+    let data_dir = state
+        .config()
+        .plugin_data_dir
+        .map(|data_root| plugin_data::provision(&data_root, &plugin))
+        .transpose()
+        .map_err(|message| CommandError {
+            code: "PLUGIN_DATA_ERROR".to_string(),
+            message,
+            details: Some(json!({ "plugin": plugin })),
+            source_chain: None,
+        })?;
+    if let Some(object) = params.as_object_mut() {
+        object.insert("permissions".to_string(), json!(granted));
+        if let Some(dir) = data_dir {
+            object.insert("data_dir".to_string(), json!(dir));
+        }
+    }
+    Ok(params)
+}
+
+/// Build the error for a call refused by a plugin's limits.
+///
+/// `details.retry_after_ms` tells rate-limited callers when to retry.
+fn quota_error(error: QuotaExceeded) -> CommandError {
+    CommandError {
+        code: error.code.to_string(),
+        message: error.message,
+        details: Some(json!({
+            "plugin": error.plugin,
+            "retry_after_ms": error.retry_after_ms,
+        })),
+        source_chain: None,
+    }
+}
+
+/// Admit a call into plugin code within the plugin's limits (see
+/// `crate::quotas`).
+///
+/// Plugin management methods and calls that can't be attributed to a
+/// plugin are not limited (`None`).
+fn admit_call(
+    quotas: &PluginQuotas,
+    settings: &SettingsStore,
+    state: &IpcManagerState,
+    method: &str,
+    params: &Value,
+) -> CommandResult<Option<QuotaPermit>> {
+    if method.starts_with("plugin/") && method != "plugin/call" {
+        return Ok(None);
+    }
+    let Some(plugin) = state.metrics().plugin_for(method, params) else {
+        return Ok(None);
+    };
+    let limits = settings.get().plugins.limits_for(&plugin);
+    if limits.is_unlimited() {
+        return Ok(None);
+    }
+    quotas
+        .acquire(&plugin, &limits, params)
+        .map(Some)
+        .map_err(quota_error)
+}
+
+/// Refuse a result larger than the admitted plugin's response limit.
+fn check_response(permit: Option<&QuotaPermit>, result: &RawValue) -> CommandResult<()> {
+    match permit {
+        Some(permit) => permit
+            .check_response(result.get().len())
+            .map_err(quota_error),
+        None => Ok(()),
+    }
+}
+
+/// Send a request within the limits of the plugin it calls.
+async fn call_limited(
+    quotas: &PluginQuotas,
+    settings: &SettingsStore,
+    state: &IpcManagerState,
+    method: &str,
+    params: Value,
+) -> CommandResult<Box<RawValue>> {
+    let permit = admit_call(quotas, settings, state, method, &params)?;
+    let result = state.call_raw(method, params).await?;
+    check_response(permit.as_ref(), &result)?;
+    Ok(result)
+}
+
+/// Build the error for params or a result that violate a method's schema.
+///
+/// `details.errors` lists the offending fields as `{ path, message }`.
+fn schema_error(code: &str, message: String, method: &str, errors: &[FieldError]) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: Some(json!({ "method": method, "errors": errors })),
+        source_chain: None,
+    }
+}
+
+/// Names of the plugins a host has loaded.
+async fn loaded_plugins(state: &IpcManagerState) -> CommandResult<BTreeSet<String>> {
+    let plugins = state.call("plugin/list", json!({})).await?;
+    Ok(plugins
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|plugin| plugin.get("loaded").and_then(Value::as_bool) == Some(true))
+        .filter_map(|plugin| plugin.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Load a plugin after the plugins it depends on (see `crate::plugin_graph`).
+///
+/// Dependencies that are already loaded are left alone.
+async fn load_with_dependencies(
+    workspaces: &WorkspaceRegistry,
+    hosts: &HostRegistry,
+    permissions: &PermissionStore,
+    settings: &SettingsStore,
+    workspace: Option<&str>,
+    name: &str,
+) -> CommandResult<Value> {
+    let state = plugin_manager_for(workspaces, hosts, workspace, None, name).await?;
+    let order = DependencyGraph::scan(&plugin_root(&state))
+        .load_order(&[name.to_string()])
+        .map_err(|message| CommandError {
+            code: "DEPENDENCY_ERROR".to_string(),
+            message,
+            details: Some(json!({ "plugin": name })),
+            source_chain: None,
+        })?;
+
+    for dependency in order.iter().filter(|plugin| *plugin != name) {
+        let dependency_state =
+            plugin_manager_for(workspaces, hosts, workspace, None, dependency).await?;
+        let loaded = loaded_plugins(&dependency_state).await?;
+        if loaded.contains(dependency) {
+            continue;
+        }
+        log::info!("Loading plugin {dependency} (needed by {name})");
+        let params = json!({ "name": dependency });
+        let params = authorize_plugin_load(
+            permissions,
+            settings,
+            &dependency_state,
+            "plugin/load",
+            params,
+        )
+        .await?;
+        dependency_state.call("plugin/load", params).await?;
+    }
+
+    let params = json!({ "name": name });
+    let params =
+        authorize_plugin_load(permissions, settings, &state, "plugin/load", params).await?;
+    state
+        .call("plugin/load", params)
+        .await
+        .map_err(CommandError::from)
+}
+
+/// Fail if loaded plugins depend on a plugin that is about to be unloaded.
+///
+/// The error (`PLUGIN_HAS_DEPENDENTS`) lists them in `details.dependents`.
+async fn ensure_no_dependents(state: &IpcManagerState, plugin: &str) -> CommandResult<()> {
+    let loaded = loaded_plugins(state).await?;
+    let dependents: Vec<String> = DependencyGraph::scan(&plugin_root(state))
+        .dependents(plugin)
+        .into_iter()
+        .filter(|dependent| loaded.contains(dependent))
+        .collect();
+    if dependents.is_empty() {
+        return Ok(());
+    }
+    Err(CommandError {
+        code: "PLUGIN_HAS_DEPENDENTS".to_string(),
+        message: format!(
+            "Plugin {plugin} is needed by {}; unload them first",
+            dependents.join(", ")
+        ),
+        details: Some(json!({ "plugin": plugin, "dependents": dependents })),
+        source_chain: None,
+    })
+}
+
+/// Export a plugin's state before it is replaced (see `crate::migration`).
+async fn export_plugin_state(
+    state: &IpcManagerState,
+    plugin: &str,
+) -> CommandResult<ExportedState> {
+    migration::export_state(state, plugin)
+        .await
+        .map_err(|e| CommandError {
+            code: "STATE_EXPORT_FAILED".to_string(),
+            message: format!("Failed to export the state of {plugin}: {e}"),
+            details: Some(json!({ "plugin": plugin })),
+            source_chain: None,
+        })
+}
+
+/// Hand the exported state to the plugin that replaced its exporter.
+async fn migrate_plugin_state(
+    state: &IpcManagerState,
+    plugin: &str,
+    exported: &ExportedState,
+) -> CommandResult<MigrationOutcome> {
+    migration::migrate_state(state, plugin, exported)
+        .await
+        .map_err(|e| CommandError {
+            code: "MIGRATION_FAILED".to_string(),
+            message: format!(
+                "Plugin {plugin} failed to migrate the state of {}: {e}",
+                exported.plugin
+            ),
+            details: None,
+            source_chain: None,
+        })
+}
+
+/// Give a plugin that was put back its exported state again.
+///
+/// A plugin that doesn't take it keeps running with a fresh state.
+async fn give_back_state(state: &IpcManagerState, exported: &ExportedState) {
+    if let Err(e) = migration::restore_state(state, exported).await {
+        log::warn!("Plugin {} didn't take back its state: {e}", exported.plugin);
+    }
+}
+
+/// Record the outcome of a rollback in the error that caused it.
+///
+/// `details.rolled_back` tells whether the old plugin is back.
+fn rollback_error(
+    mut error: CommandError,
+    plugin: &str,
+    rollback: Result<(), String>,
+) -> CommandError {
+    if let Err(e) = &rollback {
+        log::error!("Failed to roll back plugin {plugin}: {e}");
+    }
+    error.details = Some(json!({
+        "plugin": plugin,
+        "rolled_back": rollback.is_ok(),
+        "rollback_error": rollback.err(),
+    }));
+    error
+}
+
+/// Swap a plugin back after its replacement failed to migrate its state.
+async fn swap_back(
+    state: &IpcManagerState,
+    current: &str,
+    old_load: Option<&Value>,
+    exported: &ExportedState,
+) -> Result<(), String> {
+    let params = migration::swap_back_params(current, &exported.plugin, old_load);
+    let result = state
+        .call("plugin/swap", params)
+        .await
+        .map_err(|e| e.to_string())?;
+    if result.get("success").and_then(Value::as_bool) != Some(true) {
+        return Err(format!("Swapping back failed: {}", result["errors"]));
+    }
+    give_back_state(state, exported).await;
+    Ok(())
+}
+
+/// Replace a loaded plugin with its updated files and migrate its state.
+async fn upgrade_loaded(
+    permissions: &PermissionStore,
+    settings: &SettingsStore,
+    state: &IpcManagerState,
+    old_load: &Value,
+    exported: &ExportedState,
+) -> CommandResult<MigrationOutcome> {
+    let name = exported.plugin.as_str();
+    state.call("plugin/unload", json!({ "name": name })).await?;
+    let mut load = json!({ "name": name });
+    if let Some(config) = old_load.get("config") {
+        load["config"] = config.clone();
+    }
+    let params = authorize_plugin_load(permissions, settings, state, "plugin/load", load).await?;
+    state.call("plugin/load", params).await?;
+    migrate_plugin_state(state, name, exported).await
+}
+
+/// Put the previous version of an updated plugin back and load it the way
+/// it was loaded before.
+async fn reinstall_previous(
+    state: &IpcManagerState,
+    backup: PackageBackup,
+    old_load: &Value,
+    exported: &ExportedState,
+) -> Result<(), String> {
+    let name = exported.plugin.as_str();
+    if let Err(e) = state.call("plugin/unload", json!({ "name": name })).await {
+        log::debug!("Updated plugin {name} was not loaded: {e}");
+    }
+    backup.restore()?;
+    state
+        .call("plugin/load", old_load.clone())
+        .await
+        .map_err(|e| e.to_string())?;
+    give_back_state(state, exported).await;
+    Ok(())
+}
+
+/// Load the plugins in `plugins.load_on_startup` and their dependencies,
+/// dependencies first, into the default workspace.
+///
+/// Failures are logged; the other plugins are still loaded.
+pub async fn load_startup_plugins(
+    workspaces: &WorkspaceRegistry,
+    hosts: &HostRegistry,
+    permissions: &PermissionStore,
+    settings: &SettingsStore,
+) {
+    let plugins = settings.get().plugins.load_on_startup;
+    if plugins.is_empty() {
+        return;
+    }
+    let root = match workspaces.get(None) {
+        Ok(state) => plugin_root(&state),
+        Err(e) => {
+            log::warn!("Not loading startup plugins: {e}");
+            return;
+        }
+    };
+    let order = match DependencyGraph::scan(&root).load_order(&plugins) {
+        Ok(order) => order,
+        Err(e) => {
+            log::warn!("Not loading startup plugins: {e}");
+            return;
+        }
+    };
+
+    log::info!("Loading startup plugins: {}", order.join(", "));
+    for name in order {
+        if let Err(e) =
+            load_with_dependencies(workspaces, hosts, permissions, settings, None, &name).await
+        {
+            log::warn!("Failed to load startup plugin {name}: {}", e.message);
+        }
+    }
+}
+
 // ============================================
 // IPC LIFECYCLE COMMANDS
 // ============================================
@@ -145,8 +858,12 @@ pub type CommandResult<T> = Result<T, CommandError>;
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn ipc_start(state: State<'_, IpcManagerState>) -> CommandResult<()> {
+pub async fn ipc_start(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<()> {
     log::info!("Command: ipc_start");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     state.start().await.map_err(CommandError::from)
 }
 
@@ -164,11 +881,51 @@ pub async fn ipc_start(state: State<'_, IpcManagerState>) -> CommandResult<()> {
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn ipc_stop(state: State<'_, IpcManagerState>) -> CommandResult<()> {
+pub async fn ipc_stop(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<()> {
     log::info!("Command: ipc_stop");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     state.shutdown().await.map_err(CommandError::from)
 }
 
+/// Restart the Python subprocess.
+///
+/// # Arguments
+///
+/// * `warm` - Start a standby host, load the same plugins into it, and
+///   switch to it before stopping the old one, so in-flight requests are
+///   not interrupted (optional, defaults to a stop-and-start restart)
+///
+/// # Returns
+///
+/// * `Ok(())` - The new subprocess is serving requests
+/// * `Err(CommandError)` - Failed to restart; after a failed warm restart
+///   the old subprocess keeps serving
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// await invoke('ipc_restart', { warm: true });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn ipc_restart(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+    warm: Option<bool>,
+) -> CommandResult<()> {
+    let warm = warm.unwrap_or(false);
+    log::info!("Command: ipc_restart warm={warm}");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
+    if warm {
+        state.restart_warm().await.map_err(CommandError::from)
+    } else {
+        state.restart().await.map_err(CommandError::from)
+    }
+}
+
 /// Get IPC Manager status and statistics.
 ///
 /// # Returns
@@ -183,11 +940,101 @@ pub async fn ipc_stop(state: State<'_, IpcManagerState>) -> CommandResult<()> {
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn ipc_status(state: State<'_, IpcManagerState>) -> CommandResult<ManagerStats> {
+pub async fn ipc_status(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<ManagerStats> {
     log::debug!("Command: ipc_status");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     Ok(state.stats().await)
 }
 
+/// Get the default plugin host's statistics over the session.
+///
+/// Samples are taken every 5 seconds and kept for an hour (see
+/// stats_history.rs).
+///
+/// # Arguments
+///
+/// * `since` - Only return samples taken after this time (RFC 3339, optional)
+///
+/// # Returns
+///
+/// Samples, oldest first, with request rate, error rate, and latency.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const samples = await invoke('ipc_status_history', { since: lastSample?.at });
+/// chart.append(samples.map((s) => [s.at, s.request_rate, s.error_rate, s.avg_latency_ms]));
+/// ```
+#[tauri::command]
+pub fn ipc_status_history(
+    history: State<'_, StatsHistory>,
+    since: Option<String>,
+) -> CommandResult<Vec<StatsSample>> {
+    log::debug!("Command: ipc_status_history since={since:?}");
+    history.since(since.as_deref()).map_err(|e| CommandError {
+        code: "INVALID_TIMESTAMP".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// List the requests waiting for a response from the plugin host.
+///
+/// # Returns
+///
+/// Each pending request's id, method, age, and timeout, oldest first.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const pending = await invoke('ipc_pending_list');
+/// const stuck = pending.filter((request) => request.age_ms > 30_000);
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn ipc_pending_list(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<Vec<PendingInfo>> {
+    log::debug!("Command: ipc_pending_list");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
+    Ok(state.pending_requests().await)
+}
+
+/// Abort a pending request without waiting for the plugin host.
+///
+/// The caller gets a `REQUEST_ABORTED` error. The host is not notified,
+/// so work it started keeps running; use this when the host is stuck.
+///
+/// # Arguments
+///
+/// * `id` - Request id (from `ipc_pending_list`)
+///
+/// # Returns
+///
+/// `true` if the request was pending.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// await invoke('ipc_pending_kill', { id: stuck[0].id });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn ipc_pending_kill(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+    id: u64,
+) -> CommandResult<bool> {
+    log::info!("Command: ipc_pending_kill id={id}");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
+    Ok(state.abort_request(id).await)
+}
+
 /// Check if IPC is ready to accept requests.
 ///
 /// # Returns
@@ -204,7 +1051,11 @@ pub async fn ipc_status(state: State<'_, IpcManagerState>) -> CommandResult<Mana
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn ipc_ready(state: State<'_, IpcManagerState>) -> CommandResult<bool> {
+pub async fn ipc_ready(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<bool> {
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     Ok(state.is_ready().await)
 }
 
@@ -216,12 +1067,30 @@ pub async fn ipc_ready(state: State<'_, IpcManagerState>) -> CommandResult<bool>
 ///
 /// # Arguments
 ///
+/// * `workspace` - Workspace id (optional, defaults to the default workspace)
+/// * `host` - Named plugin host (optional, defaults to the core host)
 /// * `method` - JSON-RPC method name
 /// * `params` - Method parameters (optional, defaults to empty object)
+/// * `request_id` - Id reserved with `ipc_reserve_request_id` (optional); progress
+///   reported by the host is emitted as `ipc/progress/{request_id}` events; an id
+///   that is pending or was never reserved fails with `REQUEST_ID_IN_USE`
+/// * `idempotency_key` - Key identifying the request across retries (optional);
+///   while a call with the same key, method, and params is in flight, a retry
+///   waits for its response instead of running the method again (progress
+///   goes to the first call's `request_id`)
 ///
 /// # Returns
 ///
-/// JSON-RPC result value.
+/// JSON-RPC result value, passed through as received from the host.
+///
+/// Params and results of methods with a schema (see `crate::ipc::schemas`)
+/// are validated; violations fail with `INVALID_PARAMS` or
+/// `CONTRACT_MISMATCH` and list the offending fields in `details.errors`.
+/// Calls over the plugin's limits (see `crate::quotas`) fail with
+/// `REQUEST_TOO_LARGE`, `RESPONSE_TOO_LARGE`, `TOO_MANY_CONCURRENT_CALLS`,
+/// or `RATE_LIMITED`. `plugin/load`, `plugin/unload`, and `plugin/swap` fail
+/// with `USE_PLUGIN_COMMAND`; other `plugin/*` methods go to the named
+/// plugin's host (see `request_manager_for`).
 ///
 /// # Example (TypeScript)
 ///
@@ -230,17 +1099,122 @@ pub async fn ipc_ready(state: State<'_, IpcManagerState>) -> CommandResult<bool>
 ///     method: 'plugin/list',
 ///     params: { filter: 'tts' }
 /// });
+///
+/// const requestId = await invoke('ipc_reserve_request_id');
+/// const unlisten = await listen(`ipc/progress/${requestId}`, (e) => {
+///     setProgress(e.payload.percentage, e.payload.message, e.payload.stage);
+/// });
+/// await invoke('ipc_call', { method: 'stt/transcribe', params, requestId });
+/// unlisten();
+///
+/// await invoke('ipc_call', {
+///     method: 'llm/generate',
+///     params,
+///     idempotencyKey: crypto.randomUUID()
+/// });
 /// ```
 #[tauri::command]
-#[allow(clippy::used_underscore_binding)]
+#[allow(clippy::used_underscore_binding, clippy::too_many_arguments)]
+#[tracing::instrument(name = "command.ipc_call", skip_all, fields(method = %method))]
 pub async fn ipc_call(
-    state: State<'_, IpcManagerState>,
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    settings: State<'_, SettingsStore>,
+    schemas: State<'_, SchemaRegistry>,
+    quotas: State<'_, PluginQuotas>,
+    workspace: Option<String>,
+    host: Option<String>,
     method: String,
     params: Option<Value>,
-) -> CommandResult<Value> {
+    request_id: Option<u64>,
+    idempotency_key: Option<String>,
+) -> CommandResult<Box<RawValue>> {
     log::debug!("Command: ipc_call method={method}");
     let params = params.unwrap_or(json!({}));
-    state.call(method, params).await.map_err(CommandError::from)
+    let state = request_manager_for(
+        &workspaces,
+        &hosts,
+        workspace.as_deref(),
+        host.as_deref(),
+        &method,
+        &params,
+    )
+    .await?;
+    schemas
+        .validate_params(&method, &params)
+        .map_err(|errors| {
+            let message = format!("Invalid params for {method}");
+            schema_error("INVALID_PARAMS", message, &method, &errors)
+        })?;
+    let permit = admit_call(&quotas, &settings, &state, &method, &params)?;
+    let result = match idempotency_key {
+        Some(key) => {
+            let call = send_ipc_call(
+                app,
+                state.clone(),
+                method.clone(),
+                params.clone(),
+                request_id,
+            );
+            state.call_idempotent(&key, &method, &params, call).await?
+        }
+        None => send_ipc_call(app, state, method.clone(), params, request_id).await?,
+    };
+    check_response(permit.as_ref(), &result)?;
+    schemas
+        .validate_result(&method, &result)
+        .map_err(|errors| {
+            let message = format!("Result of {method} does not match its schema");
+            schema_error("CONTRACT_MISMATCH", message, &method, &errors)
+        })?;
+    Ok(result)
+}
+
+/// Send the request of an `ipc_call`, emitting its progress if `request_id`
+/// is set.
+async fn send_ipc_call(
+    app: AppHandle,
+    state: IpcManagerState,
+    method: String,
+    params: Value,
+    request_id: Option<u64>,
+) -> Result<Box<RawValue>, IpcError> {
+    match request_id {
+        Some(id) => {
+            state
+                .call_with_progress(id, method, params, move |update| {
+                    if let Err(e) = app.emit_all(&update.event_name(), &update) {
+                        log::warn!("Failed to emit {}: {e}", update.event_name());
+                    }
+                })
+                .await
+        }
+        None => state.call_raw(method, params).await,
+    }
+}
+
+/// Reserve a request id for an `ipc_call` whose progress should be followed.
+///
+/// # Arguments
+///
+/// * `workspace` - Workspace id (optional, defaults to the default workspace)
+/// * `host` - Named plugin host (optional, defaults to the core host)
+///
+/// # Returns
+///
+/// A request id unique within that host; pass it as `requestId` to `ipc_call`.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn ipc_reserve_request_id(
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    workspace: Option<String>,
+    host: Option<String>,
+) -> CommandResult<u64> {
+    log::debug!("Command: ipc_reserve_request_id");
+    let state = host_manager_for(&workspaces, &hosts, workspace.as_deref(), host.as_deref()).await?;
+    Ok(state.next_request_id())
 }
 
 /// Batch IPC call - send multiple requests.
@@ -279,16 +1253,39 @@ pub struct BatchResult {
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
 pub async fn ipc_batch(
-    state: State<'_, IpcManagerState>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    settings: State<'_, SettingsStore>,
+    quotas: State<'_, PluginQuotas>,
+    workspace: Option<String>,
     requests: Vec<BatchRequest>,
 ) -> CommandResult<Vec<BatchResult>> {
     log::debug!("Command: ipc_batch count={}", requests.len());
+    // Fail for an unknown workspace rather than per request
+    manager_for(&workspaces, workspace.as_deref())?;
 
     let mut results = Vec::with_capacity(requests.len());
 
     for req in requests {
         let params = req.params.unwrap_or(json!({}));
-        let result = match state.call(&req.method, params).await {
+        let state = request_manager_for(
+            &workspaces,
+            &hosts,
+            workspace.as_deref(),
+            None,
+            &req.method,
+            &params,
+        )
+        .await;
+        let result = match state {
+            Ok(state) => call_limited(&quotas, &settings, &state, &req.method, params)
+                .await
+                .and_then(|raw| {
+                    serde_json::from_str(raw.get()).map_err(|e| IpcError::from(e).into())
+                }),
+            Err(e) => Err(e),
+        };
+        let result = match result {
             Ok(value) => BatchResult {
                 success: true,
                 result: Some(value),
@@ -297,7 +1294,7 @@ pub async fn ipc_batch(
             Err(e) => BatchResult {
                 success: false,
                 result: None,
-                error: Some(CommandError::from(e)),
+                error: Some(e),
             },
         };
         results.push(result);
@@ -326,8 +1323,12 @@ pub async fn ipc_batch(
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn plugin_list(state: State<'_, IpcManagerState>) -> CommandResult<Value> {
+pub async fn plugin_list(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<Value> {
     log::debug!("Command: plugin_list");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     state.call("plugin/list", json!({})).await.map_err(CommandError::from)
 }
 
@@ -349,10 +1350,12 @@ pub async fn plugin_list(state: State<'_, IpcManagerState>) -> CommandResult<Val
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
 pub async fn plugin_info(
-    state: State<'_, IpcManagerState>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
     name: String,
 ) -> CommandResult<Value> {
     log::debug!("Command: plugin_info name={name}");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     state.call("plugin/info", json!({ "name": name })).await.map_err(CommandError::from)
 }
 
@@ -364,7 +1367,10 @@ pub async fn plugin_info(
 ///
 /// # Returns
 ///
-/// Plugin load result.
+/// Plugin load result, `PERMISSION_DENIED` if the user denied a
+/// permission the plugin declares, or `DEPENDENCY_ERROR` if a plugin it
+/// depends on is missing or the dependencies form a cycle. Dependencies
+/// that are not loaded yet are loaded first.
 ///
 /// # Example (TypeScript)
 ///
@@ -374,11 +1380,23 @@ pub async fn plugin_info(
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
 pub async fn plugin_load(
-    state: State<'_, IpcManagerState>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    permissions: State<'_, PermissionStore>,
+    settings: State<'_, SettingsStore>,
+    workspace: Option<String>,
     name: String,
 ) -> CommandResult<Value> {
     log::info!("Command: plugin_load name={name}");
-    state.call("plugin/load", json!({ "name": name })).await.map_err(CommandError::from)
+    load_with_dependencies(
+        &workspaces,
+        &hosts,
+        &permissions,
+        &settings,
+        workspace.as_deref(),
+        &name,
+    )
+    .await
 }
 
 /// Unload a plugin.
@@ -389,7 +1407,8 @@ pub async fn plugin_load(
 ///
 /// # Returns
 ///
-/// Plugin unload result.
+/// Plugin unload result, or `PLUGIN_HAS_DEPENDENTS` (listing them in
+/// `details.dependents`) if loaded plugins depend on it.
 ///
 /// # Example (TypeScript)
 ///
@@ -399,15 +1418,23 @@ pub async fn plugin_load(
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
 pub async fn plugin_unload(
-    state: State<'_, IpcManagerState>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    workspace: Option<String>,
     name: String,
 ) -> CommandResult<Value> {
     log::info!("Command: plugin_unload name={name}");
+    let state = plugin_manager_for(&workspaces, &hosts, workspace.as_deref(), None, &name).await?;
+    ensure_no_dependents(&state, &name).await?;
     state.call("plugin/unload", json!({ "name": name })).await.map_err(CommandError::from)
 }
 
 /// Hot-swap a plugin with another.
 ///
+/// The old plugin's state is exported first and handed to the new one
+/// (see `crate::migration`). If the new plugin fails to migrate it, the
+/// old plugin is swapped back in and gets its state back.
+///
 /// # Arguments
 ///
 /// * `old_name` - Plugin to unload
@@ -415,7 +1442,12 @@ pub async fn plugin_unload(
 ///
 /// # Returns
 ///
-/// Swap result.
+/// Swap result with the `migration` outcome, `PERMISSION_DENIED` if the
+/// user denied a permission the new plugin declares,
+/// `PLUGIN_HAS_DEPENDENTS` if loaded plugins depend on the old one,
+/// `STATE_EXPORT_FAILED`, `MIGRATION_FAILED` (`details.rolled_back` tells
+/// whether the old plugin is back), or `SANDBOXED_PLUGIN` if either plugin
+/// runs in its own sandboxed host (unload and load it instead).
 ///
 /// # Example (TypeScript)
 ///
@@ -428,28 +1460,71 @@ pub async fn plugin_unload(
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
 pub async fn plugin_swap(
-    state: State<'_, IpcManagerState>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    permissions: State<'_, PermissionStore>,
+    settings: State<'_, SettingsStore>,
+    workspace: Option<String>,
     old_name: String,
     new_name: String,
 ) -> CommandResult<Value> {
     log::info!("Command: plugin_swap {old_name} -> {new_name}");
-    state.call("plugin/swap", json!({
+    if workspace.as_deref().unwrap_or(DEFAULT_WORKSPACE) == DEFAULT_WORKSPACE {
+        let sandboxed = [&old_name, &new_name]
+            .into_iter()
+            .find(|name| hosts.isolation(name) == Isolation::Sandboxed);
+        if let Some(name) = sandboxed {
+            return Err(CommandError {
+                code: "SANDBOXED_PLUGIN".to_string(),
+                message: format!("Plugin {name} runs sandboxed and can't be swapped"),
+                details: None,
+                source_chain: None,
+            });
+        }
+    }
+    let state = manager_for(&workspaces, workspace.as_deref())?;
+    ensure_no_dependents(&state, &old_name).await?;
+    let params = authorize_plugin_load(&permissions, &settings, &state, "plugin/swap", json!({
         "old": old_name,
         "new": new_name
-    })).await.map_err(CommandError::from)
+    })).await?;
+    let old_load = state.load_params(&old_name);
+    let exported = export_plugin_state(&state, &old_name).await?;
+
+    let mut result = state
+        .call("plugin/swap", params)
+        .await
+        .map_err(CommandError::from)?;
+    if result.get("success").and_then(Value::as_bool) != Some(true) {
+        // The host kept (or put back) the old plugin
+        return Ok(result);
+    }
+    match migrate_plugin_state(&state, &new_name, &exported).await {
+        Ok(outcome) => {
+            result["migration"] = json!(outcome);
+            Ok(result)
+        }
+        Err(error) => {
+            log::warn!("{}; swapping back to {old_name}", error.message);
+            let rollback = swap_back(&state, &new_name, old_load.as_ref(), &exported).await;
+            Err(rollback_error(error, &new_name, rollback))
+        }
+    }
 }
 
 /// Call a method on a specific plugin.
 ///
 /// # Arguments
 ///
+/// * `host` - Named plugin host (optional, defaults to the core host)
 /// * `plugin` - Plugin name
 /// * `method` - Method name
 /// * `args` - Method arguments
 ///
 /// # Returns
 ///
-/// Method result.
+/// Method result. Calls over the plugin's limits (see `crate::quotas`)
+/// fail like in `ipc_call`.
 ///
 /// # Example (TypeScript)
 ///
@@ -461,19 +1536,523 @@ pub async fn plugin_swap(
 /// });
 /// ```
 #[tauri::command]
-#[allow(clippy::used_underscore_binding)]
+#[allow(clippy::used_underscore_binding, clippy::too_many_arguments)]
+#[tracing::instrument(
+    name = "command.plugin_call",
+    skip_all,
+    fields(plugin = %plugin, method = %method)
+)]
 pub async fn plugin_call(
-    state: State<'_, IpcManagerState>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    settings: State<'_, SettingsStore>,
+    quotas: State<'_, PluginQuotas>,
+    workspace: Option<String>,
+    host: Option<String>,
     plugin: String,
     method: String,
     args: Option<Value>,
-) -> CommandResult<Value> {
+) -> CommandResult<Box<RawValue>> {
     log::debug!("Command: plugin_call plugin={plugin} method={method}");
-    state.call("plugin/call", json!({
+    let state = plugin_manager_for(
+        &workspaces,
+        &hosts,
+        workspace.as_deref(),
+        host.as_deref(),
+        &plugin,
+    )
+    .await?;
+    let params = json!({
         "plugin": plugin,
         "method": method,
         "args": args.unwrap_or(json!({}))
-    })).await.map_err(CommandError::from)
+    });
+    call_limited(&quotas, &settings, &state, "plugin/call", params).await
+}
+
+/// Invoke a command declared in a plugin's manifest (see
+/// `crate::plugin_commands`).
+///
+/// # Arguments
+///
+/// * `host` - Named plugin host (optional, defaults to the core host)
+/// * `command` - Qualified command name (`<plugin>.<command>`)
+/// * `args` - Arguments passed to the plugin method
+///
+/// # Returns
+///
+/// Method result. A command no manifest declares fails with
+/// `UNKNOWN_COMMAND`; calls over the plugin's limits fail like in
+/// `plugin_call`.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const audio = await invoke('ipc_invoke_dynamic', {
+///     command: 'tts_kokoro.preview_voice',
+///     args: { voice: 'af_bella' }
+/// });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding, clippy::too_many_arguments)]
+#[tracing::instrument(
+    name = "command.ipc_invoke_dynamic",
+    skip_all,
+    fields(command = %command)
+)]
+pub async fn ipc_invoke_dynamic(
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    settings: State<'_, SettingsStore>,
+    quotas: State<'_, PluginQuotas>,
+    workspace: Option<String>,
+    host: Option<String>,
+    command: String,
+    args: Option<Value>,
+) -> CommandResult<Box<RawValue>> {
+    log::debug!("Command: ipc_invoke_dynamic command={command}");
+    let plugin = command
+        .rsplit_once('.')
+        .map_or(command.as_str(), |(plugin, _)| plugin);
+    let state = plugin_manager_for(
+        &workspaces,
+        &hosts,
+        workspace.as_deref(),
+        host.as_deref(),
+        plugin,
+    )
+    .await?;
+    let registry = CommandRegistry::scan(&plugin_root(&state));
+    let Some(declared) = registry.get(&command) else {
+        return Err(CommandError {
+            code: "UNKNOWN_COMMAND".to_string(),
+            message: format!("No plugin declares the command {command}"),
+            details: None,
+            source_chain: None,
+        });
+    };
+    let params = json!({
+        "plugin": declared.plugin,
+        "method": declared.method,
+        "args": args.unwrap_or(json!({}))
+    });
+    call_limited(&quotas, &settings, &state, "plugin/call", params).await
+}
+
+/// List the commands declared in plugin manifests.
+///
+/// # Arguments
+///
+/// * `workspace` - Workspace id (optional, defaults to the default workspace)
+///
+/// # Returns
+///
+/// The commands of the installed plugins (loaded or not), sorted by
+/// qualified name.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const commands = await invoke<PluginCommand[]>('ipc_dynamic_commands');
+/// ```
+#[tauri::command]
+pub fn ipc_dynamic_commands(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<Vec<PluginCommand>> {
+    log::debug!("Command: ipc_dynamic_commands");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
+    Ok(CommandRegistry::scan(&plugin_root(&state)).list())
+}
+
+/// Call a method on a specific plugin as a background job.
+///
+/// For plugin methods that take minutes: returns at once with the running
+/// job. Progress arrives as `job/<id>/progress` events, completion and
+/// failure as a `job/<id>/finished` event; `job_result` returns the result.
+///
+/// # Arguments
+///
+/// * `host` - Named plugin host (optional, defaults to the core host)
+/// * `plugin` - Plugin name
+/// * `method` - Method name
+/// * `args` - Method arguments
+/// * `timeout_secs` - Time the call may take (optional, defaults to an hour)
+///
+/// # Returns
+///
+/// The running job. The job counts against the plugin's concurrency limit
+/// until it finishes; the response size limit does not apply.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const job = await invoke('plugin_call_async', {
+///     plugin: 'llm_finetune',
+///     method: 'train',
+///     args: { epochs: 3 }
+/// });
+/// await listen(`job/${job.id}/finished`, (e) => showResult(e.payload));
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding, clippy::too_many_arguments)]
+pub async fn plugin_call_async(
+    app: AppHandle,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    jobs: State<'_, JobManager>,
+    settings: State<'_, SettingsStore>,
+    quotas: State<'_, PluginQuotas>,
+    workspace: Option<String>,
+    host: Option<String>,
+    plugin: String,
+    method: String,
+    args: Option<Value>,
+    timeout_secs: Option<u64>,
+) -> CommandResult<Job> {
+    log::info!("Command: plugin_call_async plugin={plugin} method={method}");
+    let state = plugin_manager_for(
+        &workspaces,
+        &hosts,
+        workspace.as_deref(),
+        host.as_deref(),
+        &plugin,
+    )
+    .await?;
+    if !state.is_ready().await {
+        return Err(CommandError::from(IpcError::NotRunning));
+    }
+    let params = json!({
+        "plugin": plugin,
+        "method": method,
+        "args": args.unwrap_or(json!({}))
+    });
+    let permit = admit_call(&quotas, &settings, &state, "plugin/call", &params)?;
+
+    // The event callback lives as long as the job, so the permit it holds
+    // keeps the call in flight until the job finishes
+    let emit = jobs::emit_job_events(app);
+    let on_event = move |event: String, job: &Job| {
+        let _in_flight = &permit;
+        emit(event, job);
+    };
+    Ok(jobs.start(
+        state,
+        "plugin/call".to_string(),
+        params,
+        Some(timeout_secs.unwrap_or(LONG_CALL_TIMEOUT_SECS)),
+        workspace,
+        on_event,
+    ))
+}
+
+/// Get per-plugin call statistics (see `crate::ipc::metrics`).
+///
+/// Without a host, the default workspace's statistics include the plugins
+/// running in sandboxed hosts.
+///
+/// # Arguments
+///
+/// * `workspace` - Workspace id (optional, defaults to the default workspace)
+/// * `host` - Named plugin host (optional, defaults to the core host)
+///
+/// # Returns
+///
+/// Call count, error rate, average and maximum latency, and last error of
+/// every plugin that received requests, sorted by name.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const stats = await invoke('plugin_stats');
+/// const flaky = stats.filter((s) => s.error_rate > 0.05);
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn plugin_stats(
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    workspace: Option<String>,
+    host: Option<String>,
+) -> CommandResult<Vec<PluginStats>> {
+    log::debug!("Command: plugin_stats");
+    let state =
+        host_manager_for(&workspaces, &hosts, workspace.as_deref(), host.as_deref()).await?;
+    let mut managers = vec![state];
+    if host.is_none() && workspace.as_deref().unwrap_or(DEFAULT_WORKSPACE) == DEFAULT_WORKSPACE {
+        managers.extend(hosts.sandbox_managers());
+    }
+    Ok(merge_stats(
+        managers.iter().map(|state| state.metrics().snapshot()),
+    ))
+}
+
+/// Install a plugin package into the project's `plugins` directory.
+///
+/// The package is copied, its signature checked against the trusted
+/// publisher keys (see `crate::packages`), and only then moved into place.
+///
+/// # Arguments
+///
+/// * `path` - Package directory (containing `manifest.json`)
+/// * `replace` - Replace an installed plugin with the same name (optional, defaults to false)
+///
+/// # Returns
+///
+/// The installed plugin, `UNSIGNED_PLUGIN` if it is not signed by a trusted
+/// publisher and not allowed unsigned, or `INVALID_SIGNATURE`.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const { name, signature } = await invoke('plugin_install', { path: '/downloads/tts_piper' });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn plugin_install(
+    workspaces: State<'_, WorkspaceRegistry>,
+    settings: State<'_, SettingsStore>,
+    workspace: Option<String>,
+    path: String,
+    replace: Option<bool>,
+) -> CommandResult<InstalledPlugin> {
+    log::info!("Command: plugin_install path={path}");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
+    let install_error = |message: String| CommandError {
+        code: "INSTALL_ERROR".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    };
+
+    let staged = StagedPackage::stage(std::path::Path::new(&path), &plugin_root(&state))
+        .map_err(install_error)?;
+    let plugins = settings.get().plugins;
+    let signature = verify_package(staged.dir(), &plugins.trusted_keys);
+    check_signature(staged.name(), &signature, &plugins)?;
+
+    let name = staged.name().to_string();
+    let path = staged
+        .commit(replace.unwrap_or(false))
+        .map_err(install_error)?;
+    Ok(InstalledPlugin {
+        name,
+        path,
+        signature,
+    })
+}
+
+/// Update an installed plugin from a newer package.
+///
+/// The package is checked like in `plugin_install`. If the plugin is
+/// loaded, its state is exported, the new version is loaded in its place
+/// with the same config, and the state is handed to it (see
+/// `crate::migration`). If loading or migrating fails, the previous
+/// version is put back, loaded as before, and gets its state back.
+///
+/// # Arguments
+///
+/// * `path` - Package directory (containing `manifest.json`)
+///
+/// # Returns
+///
+/// The installed plugin with the `migration` outcome (null if it was not
+/// loaded), `UNSIGNED_PLUGIN`, `INVALID_SIGNATURE`, `UPDATE_ERROR` if the
+/// plugin is not installed, `PLUGIN_HAS_DEPENDENTS`,
+/// `STATE_EXPORT_FAILED`, or the load or `MIGRATION_FAILED` error with
+/// `details.rolled_back`.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const { migration } = await invoke('plugin_update', { path: '/downloads/tts_piper' });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn plugin_update(
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    permissions: State<'_, PermissionStore>,
+    settings: State<'_, SettingsStore>,
+    workspace: Option<String>,
+    path: String,
+) -> CommandResult<PluginUpdate> {
+    log::info!("Command: plugin_update path={path}");
+    let root = plugin_root(&manager_for(&workspaces, workspace.as_deref())?);
+    let update_error = |message: String| CommandError {
+        code: "UPDATE_ERROR".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    };
+
+    let staged = StagedPackage::stage(std::path::Path::new(&path), &root).map_err(update_error)?;
+    let plugins = settings.get().plugins;
+    let signature = verify_package(staged.dir(), &plugins.trusted_keys);
+    check_signature(staged.name(), &signature, &plugins)?;
+
+    let name = staged.name().to_string();
+    let state = plugin_manager_for(&workspaces, &hosts, workspace.as_deref(), None, &name).await?;
+    let Some(old_load) = state.load_params(&name) else {
+        let (path, _) = staged.commit_update().map_err(update_error)?;
+        return Ok(PluginUpdate {
+            installed: InstalledPlugin {
+                name,
+                path,
+                signature,
+            },
+            migration: None,
+        });
+    };
+
+    ensure_no_dependents(&state, &name).await?;
+    let exported = export_plugin_state(&state, &name).await?;
+    let (path, backup) = staged.commit_update().map_err(update_error)?;
+    match upgrade_loaded(&permissions, &settings, &state, &old_load, &exported).await {
+        Ok(outcome) => Ok(PluginUpdate {
+            installed: InstalledPlugin {
+                name,
+                path,
+                signature,
+            },
+            migration: Some(outcome),
+        }),
+        Err(error) => {
+            log::warn!(
+                "Update of plugin {name} failed, rolling back: {}",
+                error.message
+            );
+            let rollback = reinstall_previous(&state, backup, &old_load, &exported).await;
+            Err(rollback_error(error, &name, rollback))
+        }
+    }
+}
+
+/// Check the signature of an installed plugin.
+///
+/// # Arguments
+///
+/// * `name` - Plugin name
+///
+/// # Returns
+///
+/// The signature status (`verified`, `unsigned`, `untrusted`, or
+/// `invalid`) and whether the plugin may be loaded.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const { signature, allowed } = await invoke('plugin_signature', { name: 'tts_kokoro' });
+/// ```
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn plugin_signature(
+    workspaces: State<'_, WorkspaceRegistry>,
+    settings: State<'_, SettingsStore>,
+    workspace: Option<String>,
+    name: String,
+) -> CommandResult<Value> {
+    log::debug!("Command: plugin_signature name={name}");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
+    let plugins = settings.get().plugins;
+    let signature = installed_signature(&plugin_root(&state), &name, &plugins.trusted_keys)
+        .ok_or_else(|| CommandError {
+            code: "PLUGIN_NOT_FOUND".to_string(),
+            message: format!("Plugin {name} has no manifest"),
+            details: None,
+            source_chain: None,
+        })?;
+    Ok(json!({
+        "signature": signature,
+        "allowed": signature.allows(&name, &plugins)
+            || is_trusted_bundled(&name, &signature, &plugins),
+    }))
+}
+
+/// Root of the per-plugin data directories.
+fn plugin_data_root(state: &IpcManagerState) -> CommandResult<std::path::PathBuf> {
+    state.config().plugin_data_dir.ok_or_else(|| CommandError {
+        code: "PLUGIN_DATA_UNAVAILABLE".to_string(),
+        message: "No app data directory for plugin data".to_string(),
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// Build a plugin data `CommandError`.
+fn plugin_data_error(message: String) -> CommandError {
+    CommandError {
+        code: "PLUGIN_DATA_ERROR".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Get the size of a plugin's data directory.
+///
+/// # Arguments
+///
+/// * `name` - Plugin name
+///
+/// # Returns
+///
+/// The directory path, total size in bytes, and number of files.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const { path, size_bytes } = await invoke('plugin_data_usage', { name: 'tts_kokoro' });
+/// ```
+#[tauri::command]
+pub fn plugin_data_usage(
+    state: State<'_, IpcManagerState>,
+    name: String,
+) -> CommandResult<PluginDataUsage> {
+    log::debug!("Command: plugin_data_usage name={name}");
+    plugin_data::usage(&plugin_data_root(&state)?, &name).map_err(plugin_data_error)
+}
+
+/// Delete everything in a plugin's data directory.
+///
+/// # Arguments
+///
+/// * `name` - Plugin name
+///
+/// # Returns
+///
+/// The usage before clearing, or `PLUGIN_LOADED` while any workspace or
+/// host has the plugin loaded.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// await invoke('plugin_unload', { name: 'tts_kokoro' });
+/// const { size_bytes } = await invoke('plugin_data_clear', { name: 'tts_kokoro' });
+/// ```
+#[tauri::command]
+pub fn plugin_data_clear(
+    state: State<'_, IpcManagerState>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    name: String,
+) -> CommandResult<PluginDataUsage> {
+    log::info!("Command: plugin_data_clear name={name}");
+    let loaded = workspaces
+        .entries()
+        .into_iter()
+        .map(|(_, manager)| manager)
+        .chain(hosts.managers())
+        .any(|manager| manager.loaded_plugins().contains(&name));
+    if loaded {
+        return Err(CommandError {
+            code: "PLUGIN_LOADED".to_string(),
+            message: format!("Unload plugin {name} before clearing its data"),
+            details: Some(json!({ "plugin": name })),
+            source_chain: None,
+        });
+    }
+    plugin_data::clear(&plugin_data_root(&state)?, &name).map_err(plugin_data_error)
 }
 
 // ============================================
@@ -494,8 +2073,12 @@ pub async fn plugin_call(
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn health_check(state: State<'_, IpcManagerState>) -> CommandResult<HealthStatus> {
+pub async fn health_check(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<HealthStatus> {
     log::debug!("Command: health_check");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     Ok(state.health().status())
 }
 
@@ -514,8 +2097,12 @@ pub async fn health_check(state: State<'_, IpcManagerState>) -> CommandResult<He
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn ping(state: State<'_, IpcManagerState>) -> CommandResult<Value> {
+pub async fn ping(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<Value> {
     log::debug!("Command: ping");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     state.call("ping", json!({})).await.map_err(CommandError::from)
 }
 
@@ -536,8 +2123,12 @@ pub async fn ping(state: State<'_, IpcManagerState>) -> CommandResult<Value> {
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn discover_plugins(state: State<'_, IpcManagerState>) -> CommandResult<Value> {
+pub async fn discover_plugins(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<Value> {
     log::info!("Command: discover_plugins");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     state.call("plugin/discover", json!({})).await.map_err(CommandError::from)
 }
 
@@ -555,11 +2146,42 @@ pub async fn discover_plugins(state: State<'_, IpcManagerState>) -> CommandResul
 /// ```
 #[tauri::command]
 #[allow(clippy::used_underscore_binding)]
-pub async fn scan_plugins(state: State<'_, IpcManagerState>) -> CommandResult<Value> {
+pub async fn scan_plugins(
+    workspaces: State<'_, WorkspaceRegistry>,
+    workspace: Option<String>,
+) -> CommandResult<Value> {
     log::info!("Command: scan_plugins");
+    let state = manager_for(&workspaces, workspace.as_deref())?;
     state.call("plugin/scan", json!({})).await.map_err(CommandError::from)
 }
 
+// ============================================
+// ERROR CATALOG COMMANDS
+// ============================================
+
+/// Get the error catalog (D009, `config/error_codes.yaml`).
+///
+/// # Returns
+///
+/// Every entry, sorted by name: JSON-RPC errors with their `code`, and the
+/// string codes of command errors (`section: "command"`). `category` is the
+/// error category (`transport`, `plugin`, `user`, or `internal`). Command
+/// errors returned by `invoke` carry the `retryable` and `docs_url` of their
+/// entry.
+///
+/// # Example (TypeScript)
+///
+/// ```typescript
+/// const catalog = await invoke<ErrorEntry[]>('error_catalog');
+/// const entry = catalog.find((e) => e.name === error.code);
+/// showHint(entry?.action);
+/// ```
+#[tauri::command]
+pub fn error_catalog() -> CommandResult<Vec<ErrorEntry>> {
+    log::debug!("Command: error_catalog");
+    Ok(catalog().entries())
+}
+
 // ============================================
 // HELPER FUNCTIONS
 // ============================================
@@ -580,9 +2202,14 @@ macro_rules! generate_command_handler {
             // IPC lifecycle commands
             $crate::commands::ipc_start,
             $crate::commands::ipc_stop,
+            $crate::commands::ipc_restart,
             $crate::commands::ipc_status,
+            $crate::commands::ipc_status_history,
+            $crate::commands::ipc_pending_list,
+            $crate::commands::ipc_pending_kill,
             $crate::commands::ipc_ready,
             $crate::commands::ipc_call,
+            $crate::commands::ipc_reserve_request_id,
             $crate::commands::ipc_batch,
             // Plugin management commands
             $crate::commands::plugin_list,
@@ -590,13 +2217,30 @@ macro_rules! generate_command_handler {
             $crate::commands::plugin_load,
             $crate::commands::plugin_unload,
             $crate::commands::plugin_swap,
+            $crate::commands::plugin_install,
+            $crate::commands::plugin_update,
+            $crate::commands::plugin_signature,
+            $crate::commands::plugin_data_usage,
+            $crate::commands::plugin_data_clear,
             $crate::commands::plugin_call,
+            $crate::commands::plugin_call_async,
+            $crate::commands::ipc_invoke_dynamic,
+            $crate::commands::ipc_dynamic_commands,
+            $crate::commands::plugin_stats,
+            // Plugin permission commands
+            $crate::commands::permissions::plugin_permissions,
+            $crate::commands::permissions::set_plugin_permission,
+            $crate::commands::permissions::reset_plugin_permissions,
+            $crate::commands::reverse_rpc::reverse_rpc_audit,
             // Health commands
             $crate::commands::health_check,
             $crate::commands::ping,
+            $crate::commands::health_history::health_history,
             // Discovery commands
             $crate::commands::discover_plugins,
             $crate::commands::scan_plugins,
+            // Error catalog command
+            $crate::commands::error_catalog,
             // API Key management commands (D079)
             $crate::commands::secrets::get_api_keys,
             $crate::commands::secrets::add_api_key,
@@ -604,10 +2248,161 @@ macro_rules! generate_command_handler {
             $crate::commands::secrets::delete_api_key,
             $crate::commands::secrets::get_active_api_key,
             $crate::commands::secrets::set_active_api_key,
-            $crate::commands::secrets::get_active_api_key_value,
             $crate::commands::secrets::get_configured_services,
-            // Compiler command
+            $crate::commands::secrets::list_key_profiles,
+            $crate::commands::secrets::create_key_profile,
+            $crate::commands::secrets::delete_key_profile,
+            $crate::commands::secrets::set_active_profile,
+            $crate::commands::secrets::record_api_key_usage,
+            $crate::commands::secrets::test_api_key,
+            $crate::commands::secrets::get_key_vault_status,
+            $crate::commands::secrets::migrate_api_keys,
+            $crate::commands::secrets::export_api_keys,
+            $crate::commands::secrets::import_api_keys,
+            $crate::commands::secrets::list_env_backups,
+            $crate::commands::secrets::restore_env_backup,
+            $crate::commands::secrets::refresh_plugin_secrets,
+            $crate::commands::catalog::list_services,
+            $crate::commands::catalog::add_custom_service,
+            $crate::commands::catalog::delete_custom_service,
+            $crate::commands::catalog::validate_api_key_format,
+            $crate::commands::secret_backends::list_secret_backends,
+            $crate::commands::secret_backends::set_secret_backend,
+            $crate::commands::secret_backends::test_secret_backend,
+            $crate::commands::oauth::oauth_providers,
+            $crate::commands::oauth::oauth_add_provider,
+            $crate::commands::oauth::oauth_remove_provider,
+            $crate::commands::oauth::oauth_connect,
+            $crate::commands::oauth::oauth_refresh,
+            $crate::commands::oauth::oauth_disconnect,
+            $crate::commands::llm::llm_generate,
+            $crate::commands::llm::llm_embed,
+            $crate::commands::llm::llm_stream,
+            $crate::commands::llm::llm_cancel,
+            $crate::commands::llm::provider_models,
+            $crate::commands::ollama::ollama_status,
+            $crate::commands::ollama::ollama_models,
+            $crate::commands::ollama::ollama_pull,
+            $crate::commands::ollama::ollama_pull_list,
+            $crate::commands::ollama::ollama_pull_cancel,
+            // Compiler commands
             $crate::commands::compiler::compile_tsx,
+            $crate::commands::compiler::compile_tsx_batch,
+            $crate::commands::compiler::compile_mdx,
+            $crate::commands::compiler::compile_cache_stats,
+            $crate::commands::compiler::compile_cache_clear,
+            $crate::commands::compiler::compile_project,
+            $crate::commands::compiler::analyze_component,
+            $crate::commands::compiler::compile_watch,
+            $crate::commands::compiler::compile_unwatch,
+            $crate::commands::compiler::compile_watch_list,
+            $crate::commands::css::compile_css,
+            // Project commands
+            $crate::commands::project::project_open,
+            $crate::commands::project::project_close,
+            $crate::commands::project::project_recent,
+            $crate::commands::project::project_tree,
+            // Workspace commands
+            $crate::commands::workspace::workspace_open,
+            $crate::commands::workspace::workspace_close,
+            $crate::commands::workspace::workspace_list,
+            // Host commands
+            $crate::commands::hosts::host_list,
+            $crate::commands::hosts::host_define,
+            $crate::commands::hosts::host_remove,
+            $crate::commands::hosts::plugin_isolation,
+            $crate::commands::hosts::set_plugin_isolation,
+            // Settings commands
+            $crate::commands::settings::settings_get,
+            $crate::commands::settings::settings_set,
+            $crate::commands::settings::settings_reset,
+            $crate::commands::settings::set_locale,
+            $crate::commands::settings::set_offline,
+            // Logging commands
+            $crate::commands::logging::log_set_level,
+            $crate::commands::logging::get_app_logs,
+            // Crash report commands
+            $crate::commands::crash::crash_reports,
+            $crate::commands::crash::crash_report,
+            $crate::commands::crash::submit_crash_report,
+            // Startup commands
+            $crate::commands::startup::startup_status,
+            // Job commands
+            $crate::commands::jobs::job_start,
+            $crate::commands::jobs::job_status,
+            $crate::commands::jobs::job_result,
+            $crate::commands::jobs::job_list,
+            $crate::commands::jobs::job_cancel,
+            // Event subscription commands
+            $crate::commands::events::events_subscribe,
+            $crate::commands::events::events_unsubscribe,
+            $crate::commands::events::events_stats,
+            // Developer IPC console and fault injection commands
+            $crate::commands::devtools::devtools_open_ipc_console,
+            $crate::commands::devtools::devtools_ipc_history,
+            $crate::commands::devtools::devtools_ipc_clear,
+            $crate::commands::devtools::devtools_ipc_send,
+            $crate::commands::devtools::devtools_ipc_replay,
+            $crate::commands::devtools::devtools_set_chaos,
+            $crate::commands::devtools::devtools_chaos_status,
+            $crate::commands::devtools::devtools_generate_ipc_types,
+            // Scheduler commands
+            $crate::commands::scheduler::schedule_create,
+            $crate::commands::scheduler::schedule_list,
+            $crate::commands::scheduler::schedule_delete,
+            $crate::commands::scheduler::schedule_set_enabled,
+            // Hardware command
+            $crate::commands::hardware::detect_hardware,
+            // Download commands
+            $crate::commands::downloads::download_start,
+            $crate::commands::downloads::download_status,
+            $crate::commands::downloads::download_list,
+            $crate::commands::downloads::download_cancel,
+            $crate::commands::downloads::download_file,
+            $crate::commands::http::http_fetch,
+            $crate::commands::fs::fs_read,
+            $crate::commands::fs::fs_write,
+            $crate::commands::fs::fs_list,
+            $crate::commands::fs::fs_delete,
+            $crate::commands::fs::fs_watch,
+            $crate::commands::fs::fs_unwatch,
+            $crate::commands::fs::fs_watch_list,
+            // Artifact cache commands
+            $crate::commands::artifacts::artifact_put,
+            $crate::commands::artifacts::artifact_get,
+            $crate::commands::artifacts::artifact_lookup,
+            $crate::commands::artifacts::artifact_list,
+            $crate::commands::artifacts::artifact_pin,
+            $crate::commands::artifacts::artifact_purge,
+            $crate::commands::artifacts::artifact_stats,
+            // Storage commands
+            $crate::commands::storage::storage_put,
+            $crate::commands::storage::storage_get,
+            $crate::commands::storage::storage_list,
+            $crate::commands::storage::storage_delete,
+            $crate::commands::storage::kv_set,
+            $crate::commands::storage::kv_get,
+            $crate::commands::storage::kv_delete,
+            $crate::commands::storage::kv_list_prefix,
+            // History commands
+            $crate::commands::history::history_append,
+            $crate::commands::history::history_list,
+            $crate::commands::history::history_sessions,
+            $crate::commands::history::history_search,
+            $crate::commands::history::history_delete,
+            // Generated app commands
+            $crate::commands::apps::app_save,
+            $crate::commands::apps::app_load,
+            $crate::commands::apps::app_list,
+            $crate::commands::apps::app_versions,
+            $crate::commands::apps::app_diff,
+            $crate::commands::apps::app_rollback,
+            $crate::commands::apps::app_delete,
+            $crate::commands::apps::app_export,
+            // Preview commands
+            $crate::commands::preview::preview_start,
+            $crate::commands::preview::preview_stop,
+            $crate::commands::preview::preview_list,
         ]
     };
 }
@@ -638,6 +2433,105 @@ mod tests {
         assert_eq!(error.code, "RPC_ERROR_-32601");
     }
 
+    #[test]
+    fn test_command_error_serialization() {
+        let error = CommandError::from(IpcError::Timeout(30));
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["code"], "TIMEOUT");
+        assert_eq!(json["localized_message"], json["message"]);
+        assert_eq!(json["category"], "transport");
+        assert_eq!(json["retryable"], true);
+        assert_eq!(
+            json["source_chain"],
+            json!(["Request timed out after 30 seconds"])
+        );
+        assert!(json["docs_url"].as_str().is_some());
+        assert!(json.get("details").is_none());
+
+        let error = CommandError {
+            code: "SOMETHING_ELSE".to_string(),
+            message: "Unexpected".to_string(),
+            details: Some(json!({ "key": "value" })),
+            source_chain: None,
+        };
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(json["category"], "internal");
+        assert_eq!(json["retryable"], false);
+        assert_eq!(json["details"]["key"], "value");
+        assert!(json.get("source_chain").is_none());
+        assert!(json.get("docs_url").is_none());
+    }
+
+    #[test]
+    fn test_ipc_errors_in_catalog() {
+        for error in [
+            IpcError::SpawnError("denied".to_string()),
+            IpcError::NotRunning,
+            IpcError::SendError("broken pipe".to_string()),
+            IpcError::Timeout(30),
+            IpcError::SubprocessCrashed,
+            IpcError::RpcError {
+                code: -32601,
+                message: "Method not found".to_string(),
+            },
+            IpcError::ResponseMissing(1),
+            IpcError::IoError("broken pipe".to_string()),
+            IpcError::JsonError("eof".to_string()),
+            IpcError::RespawnFailed(3),
+            IpcError::ChannelClosed,
+            IpcError::NotInitialized,
+            IpcError::ShuttingDown,
+            IpcError::QueueFull(64),
+            IpcError::Aborted(1),
+            IpcError::IdempotencyConflict("retry-1".to_string()),
+            IpcError::RequestIdInUse(3),
+            IpcError::MessageTooLarge {
+                length: 100,
+                limit: 10,
+            },
+            IpcError::InterpreterNotFound("python3".to_string()),
+            IpcError::ModuleNotFound {
+                module: "plugins._host".to_string(),
+            },
+            IpcError::HostSyntaxError {
+                detail: "invalid syntax".to_string(),
+            },
+            IpcError::StartupExit {
+                exit_code: Some(1),
+                reason: "ImportError".to_string(),
+            },
+        ] {
+            let display = error.to_string();
+            let error = CommandError::from(error);
+            assert!(catalog().lookup(&error.code).is_some(), "{}", error.code);
+            assert_eq!(error.source_chain, Some(vec![display]));
+        }
+        assert_eq!(
+            CommandError::from(IpcError::SendError(String::new())).category(),
+            ErrorCategory::Transport
+        );
+        assert_eq!(
+            CommandError::from(IpcError::InterpreterNotFound(String::new())).category(),
+            ErrorCategory::User
+        );
+    }
+
+    #[test]
+    fn test_error_chain() {
+        #[derive(Debug, thiserror::Error)]
+        #[error("Failed to read manifest")]
+        struct ReadError(#[source] std::io::Error);
+
+        let error = ReadError(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "access denied",
+        ));
+        assert_eq!(
+            error_chain(&error),
+            ["Failed to read manifest", "access denied"]
+        );
+    }
+
     #[test]
     fn test_batch_request() {
         let json = r#"{"method": "test", "params": {"key": "value"}}"#;
@@ -659,4 +2553,41 @@ mod tests {
         assert!(json.contains("\"success\":true"));
         assert!(json.contains("\"result\":\"ok\""));
     }
+
+    #[tokio::test]
+    async fn test_generic_requests_skip_plugin_commands_and_core_host() {
+        let core = IpcManagerState::new(crate::ipc::IpcConfig::default());
+        let hosts = HostRegistry::load(core.clone(), None);
+        hosts
+            .set_isolation("untrusted", Some(Isolation::Sandboxed))
+            .unwrap();
+        let workspaces = WorkspaceRegistry::new(core.clone());
+        let params = json!({ "name": "untrusted" });
+
+        for (method, command) in PLUGIN_COMMAND_METHODS {
+            let Err(error) =
+                request_manager_for(&workspaces, &hosts, None, None, method, &params).await
+            else {
+                panic!("{method} should be refused");
+            };
+            assert_eq!(error.code, "USE_PLUGIN_COMMAND");
+            assert_eq!(error.details.unwrap()["command"], command);
+        }
+        assert_eq!(core.stats().await.total_requests, 0);
+
+        let (name, _) = sandbox_host_for(&hosts, None, None, "untrusted").unwrap();
+        assert_eq!(name, "sandbox-untrusted");
+        assert!(sandbox_host_for(&hosts, None, None, "other").is_none());
+        assert!(sandbox_host_for(&hosts, None, Some("gpu"), "untrusted").is_none());
+
+        assert_eq!(
+            requested_plugin("plugin/call", &json!({ "plugin": "untrusted" })),
+            Some("untrusted")
+        );
+        assert_eq!(
+            requested_plugin("plugin/config", &params),
+            Some("untrusted")
+        );
+        assert_eq!(requested_plugin("tts/synthesize", &params), None);
+    }
 }
@@ -0,0 +1,142 @@
+//! src-tauri/src/commands/oauth.rs
+//! ================================
+//! Tauri commands for OAuth provider connections.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! For services authorized with OAuth instead of a static API key (see
+//! `crate::oauth`). `oauth_connect` opens the provider's consent page in
+//! the system browser and resolves once the loopback redirect has been
+//! received and the code exchanged. Token values never leave the backend;
+//! plugins receive the current access token as `APP_FACTORY_OAUTH_<SERVICE>`
+//! (call `refresh_plugin_secrets` after connecting).
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     await invoke('oauth_add_provider', {
+//!         provider: {
+//!             id: 'drive',
+//!             name: 'Google Drive',
+//!             auth_url: 'https://accounts.google.com/o/oauth2/v2/auth',
+//!             token_url: 'https://oauth2.googleapis.com/token',
+//!             client_id: '1234.apps.googleusercontent.com',
+//!             scopes: ['https://www.googleapis.com/auth/drive.readonly'],
+//!             extra_params: { access_type: 'offline', prompt: 'consent' }
+//!         }
+//!     });
+//!     const status = await invoke('oauth_connect', { id: 'drive' });
+//!     const providers = await invoke('oauth_providers');
+//!     await invoke('oauth_disconnect', { id: 'drive' });
+//!     ```
+
+use tauri::{AppHandle, Manager, State};
+
+use super::{CommandError, CommandResult};
+use crate::oauth::{OAuthManager, OAuthProvider, OAuthStatus};
+
+/// Build an OAuth error with the given code.
+fn oauth_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// List OAuth providers with their connection state.
+#[tauri::command]
+pub fn oauth_providers(oauth: State<'_, OAuthManager>) -> CommandResult<Vec<OAuthStatus>> {
+    log::debug!("Command: oauth_providers");
+    Ok(oauth.list())
+}
+
+/// Register an OAuth provider (replacing one with the same id).
+///
+/// # Arguments
+///
+/// * `provider` - Service id, endpoints (HTTPS), client id, optional
+///   client secret, scopes, and extra authorization URL parameters
+///
+/// # Returns
+///
+/// The provider's status.
+#[tauri::command]
+pub fn oauth_add_provider(
+    oauth: State<'_, OAuthManager>,
+    provider: OAuthProvider,
+) -> CommandResult<OAuthStatus> {
+    log::info!("Command: oauth_add_provider id={}", provider.id);
+    oauth
+        .add_provider(provider)
+        .map_err(|e| oauth_error("INVALID_OAUTH_PROVIDER", e))
+}
+
+/// Remove an OAuth provider and its stored tokens.
+#[tauri::command]
+pub fn oauth_remove_provider(oauth: State<'_, OAuthManager>, id: String) -> CommandResult<()> {
+    log::info!("Command: oauth_remove_provider id={id}");
+    oauth
+        .remove_provider(&id)
+        .map_err(|e| oauth_error("OAUTH_PROVIDER_NOT_FOUND", e))
+}
+
+/// Authorize a provider in the system browser and store its tokens.
+///
+/// # Arguments
+///
+/// * `id` - Provider id
+///
+/// # Returns
+///
+/// The provider's status once connected. Fails with `OAUTH_FLOW_ERROR` if
+/// the user denies access or does not finish within five minutes.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn oauth_connect(
+    app: AppHandle,
+    oauth: State<'_, OAuthManager>,
+    id: String,
+) -> CommandResult<OAuthStatus> {
+    log::info!("Command: oauth_connect id={id}");
+
+    let scope = app.shell_scope();
+    oauth
+        .connect(&id, move |url| {
+            tauri::api::shell::open(&scope, url, None)
+                .map_err(|e| format!("Failed to open the browser: {e}"))
+        })
+        .await
+        .map_err(|e| oauth_error("OAUTH_FLOW_ERROR", e))
+}
+
+/// Refresh a provider's access token now.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn oauth_refresh(
+    oauth: State<'_, OAuthManager>,
+    id: String,
+) -> CommandResult<OAuthStatus> {
+    log::info!("Command: oauth_refresh id={id}");
+    oauth
+        .refresh(&id)
+        .await
+        .map_err(|e| oauth_error("OAUTH_REFRESH_ERROR", e))?;
+    oauth.status(&id).ok_or_else(|| {
+        oauth_error(
+            "OAUTH_PROVIDER_NOT_FOUND",
+            format!("Unknown OAuth provider: {id}"),
+        )
+    })
+}
+
+/// Forget a provider's stored tokens.
+#[tauri::command]
+pub fn oauth_disconnect(oauth: State<'_, OAuthManager>, id: String) -> CommandResult<()> {
+    log::info!("Command: oauth_disconnect id={id}");
+    oauth
+        .disconnect(&id)
+        .map_err(|e| oauth_error("OAUTH_NOT_CONNECTED", e))
+}
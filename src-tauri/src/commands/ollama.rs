@@ -0,0 +1,176 @@
+//! src-tauri/src/commands/ollama.rs
+//! =================================
+//! Tauri commands for managing a local Ollama daemon.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The daemon is reached at `OLLAMA_HOST` from the project .env (default
+//! `http://localhost:11434`). Generation goes through `llm_generate` and
+//! `llm_stream` with `service: 'ollama'`; these commands cover detection
+//! and model management.
+//!
+//! Each pull emits throttled `ollama/pull/<id>/progress` events and a
+//! single `ollama/pull/<id>/finished` event. The event payload is the full
+//! `OllamaPull`. The daemon pulls from the Ollama model registry, so pulls
+//! fail with `OFFLINE` in offline mode (see `crate::offline`); installed
+//! models keep working.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const status = await invoke('ollama_status');
+//!     if (status.running) {
+//!         const models = await invoke('ollama_models');
+//!         const pull = await invoke('ollama_pull', { model: 'llama3.2' });
+//!         await listen(`ollama/pull/${pull.id}/progress`, e => show(e.payload));
+//!         await invoke('ollama_pull_cancel', { id: pull.id });
+//!     }
+//!     ```
+
+use serde::Serialize;
+use tauri::{AppHandle, Manager, State};
+
+use super::secrets;
+use super::{CommandError, CommandResult};
+use crate::llm::LlmProxy;
+use crate::offline;
+use crate::ollama::{OllamaPull, OllamaPulls};
+use crate::project::ProjectManager;
+use crate::providers::ollama::{LocalModel, Ollama};
+
+/// Daemon status returned by `ollama_status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaStatus {
+    /// Whether the daemon answered
+    pub running: bool,
+    /// Host that was probed
+    pub host: String,
+    /// Daemon version (when running)
+    pub version: Option<String>,
+    /// Why the daemon could not be reached (when not running)
+    pub error: Option<String>,
+}
+
+/// Build an Ollama error with the given code.
+fn ollama_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Daemon configured for the current project.
+fn daemon(project: &ProjectManager) -> Ollama {
+    Ollama::from_env(&secrets::parse_env_file(&project.env_path()))
+}
+
+/// Detect whether an Ollama daemon is running.
+///
+/// # Returns
+///
+/// The daemon status. An unreachable daemon is not an error.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn ollama_status(
+    project: State<'_, ProjectManager>,
+    proxy: State<'_, LlmProxy>,
+) -> CommandResult<OllamaStatus> {
+    log::info!("Command: ollama_status");
+
+    let ollama = daemon(&project);
+    let status = match ollama.version(proxy.client()).await {
+        Ok(version) => OllamaStatus {
+            running: true,
+            host: ollama.host().to_string(),
+            version: Some(version),
+            error: None,
+        },
+        Err(e) => OllamaStatus {
+            running: false,
+            host: ollama.host().to_string(),
+            version: None,
+            error: Some(e.to_string()),
+        },
+    };
+
+    log::info!("Ollama at {}: running={}", status.host, status.running);
+    Ok(status)
+}
+
+/// List the models installed in the Ollama daemon.
+///
+/// # Returns
+///
+/// Installed models sorted by name, with sizes and quantization details.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn ollama_models(
+    project: State<'_, ProjectManager>,
+    proxy: State<'_, LlmProxy>,
+) -> CommandResult<Vec<LocalModel>> {
+    log::info!("Command: ollama_models");
+    daemon(&project)
+        .installed(proxy.client(), "")
+        .await
+        .map_err(|e| ollama_error("OLLAMA_ERROR", e.to_string()))
+}
+
+/// Start pulling a model into the Ollama daemon.
+///
+/// # Arguments
+///
+/// * `model` - Model name, optionally with a tag (e.g. `llama3.2:3b`)
+///
+/// # Returns
+///
+/// The pull; progress arrives as `ollama/pull/<id>/progress` events.
+/// `OFFLINE` in offline mode.
+#[tauri::command]
+pub fn ollama_pull(
+    app: AppHandle,
+    project: State<'_, ProjectManager>,
+    pulls: State<'_, OllamaPulls>,
+    model: String,
+) -> CommandResult<OllamaPull> {
+    log::info!("Command: ollama_pull model={model}");
+    if offline::is_offline() {
+        return Err(ollama_error(
+            "OFFLINE",
+            format!("Offline mode is on; cannot pull {model} from the model registry"),
+        ));
+    }
+
+    pulls
+        .start(daemon(&project), &model, move |event, pull| {
+            if let Err(e) = app.emit_all(&event, pull) {
+                log::warn!("Failed to emit {event}: {e}");
+            }
+        })
+        .map_err(|e| ollama_error("OLLAMA_PULL_ERROR", e))
+}
+
+/// List model pulls started in this session (most recent first).
+#[tauri::command]
+pub fn ollama_pull_list(pulls: State<'_, OllamaPulls>) -> CommandResult<Vec<OllamaPull>> {
+    log::debug!("Command: ollama_pull_list");
+    Ok(pulls.list())
+}
+
+/// Cancel a running pull. Layers pulled so far stay in the daemon, so
+/// pulling the same model again resumes.
+#[tauri::command]
+pub fn ollama_pull_cancel(pulls: State<'_, OllamaPulls>, id: String) -> CommandResult<OllamaPull> {
+    log::info!("Command: ollama_pull_cancel id={id}");
+    if pulls.get(&id).is_none() {
+        return Err(ollama_error(
+            "OLLAMA_PULL_NOT_FOUND",
+            format!("Unknown pull: {id}"),
+        ));
+    }
+    pulls
+        .cancel(&id)
+        .map_err(|e| ollama_error("OLLAMA_PULL_CANCEL_ERROR", e))
+}
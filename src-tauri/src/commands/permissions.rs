@@ -0,0 +1,102 @@
+//! src-tauri/src/commands/permissions.rs
+//! =====================================
+//! Tauri commands for reviewing and changing plugin permission decisions.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Decisions are normally made in the prompt shown when a plugin is loaded
+//! or asks at runtime (see `crate::permissions`). Changes apply to the next
+//! load; a plugin that is already running keeps what it was granted.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const decisions = await invoke('plugin_permissions');
+//!     await invoke('set_plugin_permission', {
+//!         plugin: 'tts_kokoro',
+//!         permission: 'network',
+//!         granted: false,
+//!     });
+//!     await invoke('reset_plugin_permissions', { plugin: 'tts_kokoro' });
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::permissions::{Permission, PermissionStore, PluginPermissions};
+
+/// Convert a permission store error into a `CommandError`.
+fn permission_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// List the stored permission decisions.
+///
+/// # Returns
+///
+/// Granted and denied permissions per plugin. Permissions missing from the
+/// list have not been decided and will be asked for.
+#[tauri::command]
+pub fn plugin_permissions(
+    store: State<'_, PermissionStore>,
+) -> CommandResult<Vec<PluginPermissions>> {
+    log::debug!("Command: plugin_permissions");
+    Ok(store.list())
+}
+
+/// Grant or deny a permission for a plugin.
+///
+/// # Arguments
+///
+/// * `plugin` - Plugin name
+/// * `permission` - `filesystem`, `network`, `subprocess`, or `microphone`
+/// * `granted` - Whether the plugin may use it
+///
+/// # Returns
+///
+/// The updated decisions.
+#[tauri::command]
+pub fn set_plugin_permission(
+    store: State<'_, PermissionStore>,
+    plugin: String,
+    permission: String,
+    granted: bool,
+) -> CommandResult<Vec<PluginPermissions>> {
+    log::info!(
+        "Command: set_plugin_permission plugin={plugin} permission={permission} granted={granted}"
+    );
+    let permission =
+        Permission::parse(&permission).map_err(|e| permission_error("INVALID_PERMISSION", e))?;
+    store
+        .set(&plugin, permission, granted)
+        .map_err(|e| permission_error("PERMISSIONS_SAVE_FAILED", e))?;
+    Ok(store.list())
+}
+
+/// Forget the decisions for one plugin, or for all plugins, so they are
+/// asked for again.
+///
+/// # Arguments
+///
+/// * `plugin` - Plugin name (optional; all plugins when omitted)
+///
+/// # Returns
+///
+/// The updated decisions.
+#[tauri::command]
+pub fn reset_plugin_permissions(
+    store: State<'_, PermissionStore>,
+    plugin: Option<String>,
+) -> CommandResult<Vec<PluginPermissions>> {
+    log::info!("Command: reset_plugin_permissions plugin={plugin:?}");
+    store
+        .reset(plugin.as_deref())
+        .map_err(|e| permission_error("PERMISSIONS_SAVE_FAILED", e))?;
+    Ok(store.list())
+}
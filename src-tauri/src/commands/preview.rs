@@ -0,0 +1,86 @@
+//! src-tauri/src/commands/preview.rs
+//! ==================================
+//! Tauri commands for the localhost preview server.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Previews reload automatically when the app is saved or rolled back.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const preview = await invoke('preview_start', { id: appId });
+//!     iframe.src = preview.url;
+//!     await invoke('preview_stop', { id: appId });
+//!     ```
+
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::apps;
+use crate::artifacts::ArtifactStore;
+use crate::export::{self, ExportTemplate};
+use crate::preview::{PreviewInfo, PreviewManager};
+use crate::storage::Storage;
+
+/// Build a preview error with the given code.
+fn preview_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Start (or refresh) the preview server for an app.
+///
+/// # Arguments
+///
+/// * `id` - App id
+/// * `version` - Version to preview (defaults to the current version)
+///
+/// # Returns
+///
+/// The preview, including the URL to load in an iframe.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn preview_start(
+    storage: State<'_, Storage>,
+    artifacts: State<'_, ArtifactStore>,
+    previews: State<'_, PreviewManager>,
+    id: String,
+    version: Option<u32>,
+) -> CommandResult<PreviewInfo> {
+    log::info!("Command: preview_start id={id} version={version:?}");
+
+    let snapshot = apps::load(&storage, &id, version)
+        .map_err(|e| preview_error("APP_ERROR", e))?
+        .ok_or_else(|| preview_error("APP_NOT_FOUND", format!("Unknown app or version: {id}")))?;
+    let files = export::project_files(&snapshot.definition, &artifacts, ExportTemplate::Html)
+        .map_err(|e| preview_error("PREVIEW_ERROR", e))?;
+
+    previews
+        .start(&id, files)
+        .await
+        .map_err(|e| preview_error("PREVIEW_ERROR", e))
+}
+
+/// Stop the preview server for an app.
+///
+/// # Returns
+///
+/// `true` if a preview was running.
+#[tauri::command]
+pub fn preview_stop(previews: State<'_, PreviewManager>, id: String) -> CommandResult<bool> {
+    log::info!("Command: preview_stop id={id}");
+    Ok(previews.stop(&id))
+}
+
+/// List running previews.
+#[tauri::command]
+pub fn preview_list(previews: State<'_, PreviewManager>) -> CommandResult<Vec<PreviewInfo>> {
+    log::debug!("Command: preview_list");
+    Ok(previews.list())
+}
@@ -0,0 +1,130 @@
+//! src-tauri/src/commands/project.rs
+//! ==================================
+//! Tauri commands for opening, closing, and listing projects.
+//!
+//! Opening a project retargets the Python plugin host working directory
+//! (and so plugin discovery) and the `.env` key store to the chosen root.
+//! The switch only happens once the host restarted in the new root; if it
+//! fails to start there, the host goes back to the previous root.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const info = await invoke('project_open', { path: 'C:/work/my_app' });
+//!     const recent = await invoke('project_recent');
+//!     const tree = await invoke('project_tree', { depth: 3, ignore: ['*.log'] });
+//!     await invoke('project_close');
+//!     ```
+
+use std::path::PathBuf;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::ipc::manager::IpcManagerState;
+use crate::project::{ProjectInfo, ProjectManager, RecentProject};
+use crate::project_tree::TreeNode;
+
+/// Open a project directory and restart the plugin host inside it.
+///
+/// # Arguments
+///
+/// * `path` - Project directory (should contain `plugins/`)
+///
+/// # Returns
+///
+/// Information about the newly active project.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn project_open(
+    state: State<'_, IpcManagerState>,
+    project: State<'_, ProjectManager>,
+    path: String,
+) -> CommandResult<ProjectInfo> {
+    log::info!("Command: project_open path={path}");
+
+    let root = project.resolve(&path).map_err(|e| CommandError {
+        code: "PROJECT_OPEN_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })?;
+
+    restart_in(&state, root.clone(), project.current_root()).await?;
+
+    Ok(project.activate(root))
+}
+
+/// Close the active project and return to the default project root.
+///
+/// # Returns
+///
+/// Information about the default project.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn project_close(
+    state: State<'_, IpcManagerState>,
+    project: State<'_, ProjectManager>,
+) -> CommandResult<ProjectInfo> {
+    log::info!("Command: project_close");
+
+    restart_in(&state, project.default_root(), project.current_root()).await?;
+
+    Ok(project.close())
+}
+
+/// Restart the plugin host in `root`.
+///
+/// On failure the host is restarted in `previous` (best effort) and the
+/// start error is returned, so the active project doesn't change.
+async fn restart_in(
+    state: &IpcManagerState,
+    root: PathBuf,
+    previous: PathBuf,
+) -> CommandResult<()> {
+    state.set_working_dir(root.clone());
+    let Err(e) = state.restart().await else {
+        return Ok(());
+    };
+
+    log::warn!("Plugin host failed to start in {root:?}, staying in {previous:?}: {e}");
+    state.set_working_dir(previous.clone());
+    if let Err(restore) = state.restart().await {
+        log::error!("Failed to restart the plugin host in {previous:?}: {restore}");
+    }
+    Err(CommandError::from(e))
+}
+
+/// Get the recently opened projects (most recent first).
+#[tauri::command]
+pub fn project_recent(project: State<'_, ProjectManager>) -> CommandResult<Vec<RecentProject>> {
+    log::debug!("Command: project_recent");
+    Ok(project.recent())
+}
+
+/// Get the active project's directory tree.
+///
+/// Entries ignored by the project's `.gitignore` or by `ignore` are left
+/// out (see `crate::project_tree`).
+///
+/// # Arguments
+///
+/// * `depth` - Levels below the root to list (optional, defaults to all)
+/// * `ignore` - Extra .gitignore-style patterns (optional)
+#[tauri::command]
+pub fn project_tree(
+    project: State<'_, ProjectManager>,
+    depth: Option<usize>,
+    ignore: Option<Vec<String>>,
+) -> CommandResult<TreeNode> {
+    log::debug!("Command: project_tree depth={depth:?}");
+    let ignore = ignore.unwrap_or_default();
+    crate::project_tree::tree(&project.current_root(), depth, &ignore).map_err(|e| CommandError {
+        code: "PROJECT_TREE_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
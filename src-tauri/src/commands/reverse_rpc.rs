@@ -0,0 +1,38 @@
+//! src-tauri/src/commands/reverse_rpc.rs
+//! =====================================
+//! Tauri command for reviewing requests plugins sent to the app.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Plugins call back into the app (secrets, artifacts, file dialogs, HTTP)
+//! through the plugin host; every call is checked against the plugin's
+//! granted permissions and recorded (see `crate::reverse_rpc`).
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const entries = await invoke('reverse_rpc_audit', { plugin: 'tts_kokoro', limit: 50 });
+//!     const denied = entries.filter((e) => e.outcome === 'denied');
+//!     ```
+
+use tauri::State;
+
+use super::CommandResult;
+use crate::reverse_rpc::{AuditEntry, ReverseRpc, MAX_AUDIT_ENTRIES};
+
+/// List audited plugin requests (most recent first).
+///
+/// # Arguments
+///
+/// * `plugin` - Only requests from this plugin (optional)
+/// * `limit` - Maximum number of entries (optional, defaults to all kept)
+#[tauri::command]
+pub fn reverse_rpc_audit(
+    reverse_rpc: State<'_, ReverseRpc>,
+    plugin: Option<String>,
+    limit: Option<usize>,
+) -> CommandResult<Vec<AuditEntry>> {
+    log::debug!("Command: reverse_rpc_audit");
+    Ok(reverse_rpc.audit(plugin.as_deref(), limit.unwrap_or(MAX_AUDIT_ENTRIES)))
+}
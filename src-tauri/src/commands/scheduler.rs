@@ -0,0 +1,98 @@
+//! src-tauri/src/commands/scheduler.rs
+//! ====================================
+//! Tauri commands for scheduled plugin invocations.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const s = await invoke('schedule_create', {
+//!         cron: '0 3 * * *',
+//!         method: 'embeddings/refresh',
+//!         params: {}
+//!     });
+//!     await invoke('schedule_set_enabled', { id: s.id, enabled: false });
+//!     await invoke('schedule_delete', { id: s.id });
+//!     ```
+
+use serde_json::{json, Value};
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::scheduler::{ScheduleEntry, Scheduler};
+
+/// Build a scheduler error with the given code.
+fn schedule_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Create a recurring plugin invocation.
+///
+/// # Arguments
+///
+/// * `cron` - Cron expression (`min hour dom mon dow`)
+/// * `method` - JSON-RPC method to call
+/// * `params` - Method parameters (optional)
+/// * `workspace` - Workspace id (optional, default workspace if omitted)
+///
+/// # Returns
+///
+/// The created schedule, including its next run time.
+#[tauri::command]
+pub fn schedule_create(
+    scheduler: State<'_, Scheduler>,
+    cron: String,
+    method: String,
+    params: Option<Value>,
+    workspace: Option<String>,
+) -> CommandResult<ScheduleEntry> {
+    log::info!("Command: schedule_create cron={cron} method={method}");
+    scheduler
+        .create(&cron, &method, params.unwrap_or(json!({})), workspace)
+        .map_err(|e| schedule_error("INVALID_SCHEDULE", e))
+}
+
+/// List all schedules.
+#[tauri::command]
+pub fn schedule_list(scheduler: State<'_, Scheduler>) -> CommandResult<Vec<ScheduleEntry>> {
+    log::debug!("Command: schedule_list");
+    Ok(scheduler.list())
+}
+
+/// Delete a schedule.
+///
+/// # Arguments
+///
+/// * `id` - Schedule id
+#[tauri::command]
+pub fn schedule_delete(scheduler: State<'_, Scheduler>, id: String) -> CommandResult<()> {
+    log::info!("Command: schedule_delete id={id}");
+    scheduler
+        .delete(&id)
+        .map_err(|e| schedule_error("SCHEDULE_NOT_FOUND", e))
+}
+
+/// Enable or disable a schedule without deleting it.
+///
+/// # Arguments
+///
+/// * `id` - Schedule id
+/// * `enabled` - Whether the schedule should run
+#[tauri::command]
+pub fn schedule_set_enabled(
+    scheduler: State<'_, Scheduler>,
+    id: String,
+    enabled: bool,
+) -> CommandResult<ScheduleEntry> {
+    log::info!("Command: schedule_set_enabled id={id} enabled={enabled}");
+    scheduler
+        .set_enabled(&id, enabled)
+        .map_err(|e| schedule_error("SCHEDULE_NOT_FOUND", e))
+}
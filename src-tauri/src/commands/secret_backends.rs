@@ -0,0 +1,95 @@
+//! src-tauri/src/commands/secret_backends.rs
+//! ==========================================
+//! Tauri commands for binding services to external secret backends.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A bound service reads its key from 1Password, HashiCorp Vault, or an
+//! environment variable instead of the project `.env` (see
+//! `crate::secret_store`). Bindings are app-wide and hold references
+//! only. Call `refresh_plugin_secrets` after changing a binding to hand the
+//! new key to running plugin hosts.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     await invoke('set_secret_backend', {
+//!         service: 'openai',
+//!         backend: { kind: 'one_password', reference: 'op://Engineering/OpenAI/credential' }
+//!     });
+//!     await invoke('test_secret_backend', { service: 'openai' });
+//!     const bindings = await invoke('list_secret_backends');
+//!     await invoke('set_secret_backend', { service: 'openai', backend: null });
+//!     ```
+
+use std::collections::BTreeMap;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::secret_store::{BackendConfig, SecretBackends};
+
+/// Build a secret backend error with the given code.
+fn backend_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// List services bound to external backends.
+///
+/// # Returns
+///
+/// Bindings keyed by service id. Services not listed use the project .env.
+#[tauri::command]
+pub fn list_secret_backends(
+    backends: State<'_, SecretBackends>,
+) -> CommandResult<BTreeMap<String, BackendConfig>> {
+    log::debug!("Command: list_secret_backends");
+    Ok(backends.list())
+}
+
+/// Bind a service to an external backend, or back to the project .env.
+///
+/// # Arguments
+///
+/// * `service` - Service id
+/// * `backend` - Backend binding (`kind` is `one_password`,
+///   `hashicorp_vault`, or `environment`); null to use the project .env
+#[tauri::command]
+pub fn set_secret_backend(
+    backends: State<'_, SecretBackends>,
+    service: String,
+    backend: Option<BackendConfig>,
+) -> CommandResult<()> {
+    log::info!("Command: set_secret_backend service={service}");
+    backends
+        .set(&service, backend)
+        .map_err(|e| backend_error("INVALID_SECRET_BACKEND", e))
+}
+
+/// Fetch a bound service's key from its backend, bypassing the cache.
+///
+/// # Returns
+///
+/// Nothing if the backend returned a key; `SECRET_BACKEND_ERROR` with the
+/// backend's error otherwise. The key itself is never returned.
+#[tauri::command]
+pub fn test_secret_backend(
+    backends: State<'_, SecretBackends>,
+    service: String,
+) -> CommandResult<()> {
+    log::info!("Command: test_secret_backend service={service}");
+    backends.clear_cache();
+    match backends.read(&service) {
+        Some(Ok(_)) => Ok(()),
+        Some(Err(e)) => Err(backend_error("SECRET_BACKEND_ERROR", e)),
+        None => Err(backend_error(
+            "SECRET_BACKEND_NOT_FOUND",
+            format!("{service} is not bound to an external backend"),
+        )),
+    }
+}
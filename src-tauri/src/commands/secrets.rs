@@ -3,11 +3,35 @@
 //! Tauri commands for secure API key management.
 //!
 //! Provides CRUD operations for API keys stored in .env file.
-//! Keys are stored in .env file and persist across app restarts/rebuilds.
+//! Each project has its own .env in the app data directory (see
+//! `crate::paths`), outside the project tree, so keys persist across app
+//! restarts/rebuilds and are never committed with the project.
 //!
 //! Architecture: Keys stored as APIKEY_<SERVICE>_<UUID>=<value>
 //! Active key tracked as `ACTIVE_APIKEY`_<SERVICE>=<UUID>
 //!
+//! Profiles (e.g. dev/staging/prod) keep separate active-key selections
+//! over the same set of keys. The `default` profile uses the entries above;
+//! named profiles are listed in `KEY_PROFILES` and store their selections
+//! as `PROFILE`_<PROFILE>_`ACTIVE_APIKEY`_<SERVICE>=<UUID>. `KEY_PROFILE`
+//! names the profile in effect; commands take an optional `profile`
+//! argument to address another one.
+//!
+//! The plugin host receives the active profile's active keys as
+//! environment variables when it is spawned (see `plugin_env`), so plugins
+//! read them locally instead of asking for key values over IPC.
+//! `refresh_plugin_secrets` restarts running hosts after keys change.
+//!
+//! Values are encrypted at rest by the `KeyVault` (see `crate::vault`).
+//! Plaintext values written by older versions are still read, flagged with
+//! `needs_migration`, and re-encrypted by `migrate_api_keys`.
+//!
+//! Writes of the .env are locked and atomic, and keep timestamped backups
+//! that `restore_env_backup` rolls back to (see `crate::env_file`).
+//!
+//! New and replaced keys are checked against the service's format in the
+//! catalog (see `crate::catalog`) before they are stored.
+//!
 //! Usage (TypeScript):
 //!     ```typescript
 //!     import { invoke } from '@tauri-apps/api/tauri';
@@ -24,16 +48,85 @@
 //!
 //!     // Set active key
 //!     await invoke('set_active_api_key', { service: 'gemini', id: 'uuid-here' });
+//!
+//!     // Switch to the sandbox accounts
+//!     await invoke('create_key_profile', { name: 'staging' });
+//!     await invoke('set_active_profile', { name: 'staging' });
+//!
+//!     // Report a provider request made with the active key
+//!     await invoke('record_api_key_usage', { service: 'gemini', tokens: 1200 });
+//!
+//!     // Hand updated keys to running plugin hosts
+//!     await invoke('refresh_plugin_secrets');
+//!
+//!     // Check a key against the provider
+//!     const check = await invoke('test_api_key', { service: 'openai', id: 'uuid-here' });
+//!
+//!     // Move keys between machines as a passphrase-encrypted bundle
+//!     const bundle = await invoke('export_api_keys', { passphrase });
+//!     await invoke('import_api_keys', { bundle, passphrase });
+//!
+//!     // Encrypt keys stored in plaintext by older versions
+//!     const migrated = await invoke('migrate_api_keys');
+//!
+//!     // Roll the .env back to an earlier state
+//!     const backups = await invoke('list_env_backups');
+//!     await invoke('restore_env_backup', { id: backups[0].id });
 //!     ```
 
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tauri::State;
 use uuid::Uuid;
 
-use super::{CommandError, CommandResult};
+use super::{error_chain, CommandError, CommandResult};
+use crate::catalog::ServiceCatalog;
+use crate::env_file::{self, EnvBackup};
+use crate::key_usage::{self, KeyUsage};
+use crate::offline;
+use crate::project::ProjectManager;
+use crate::providers::{anthropic, ollama};
+use crate::proxy;
+use crate::secret_store::SecretBackends;
+use crate::storage::Storage;
+use crate::vault::{self, KeySource, KeyVault};
+use crate::workspace::WorkspaceRegistry;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Timeout for `test_api_key` requests
+const KEY_TEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Minimum passphrase length for key export bundles
+const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Profile using the plain `ACTIVE_APIKEY`_<SERVICE> entries
+const DEFAULT_PROFILE: &str = "default";
+
+/// Env var naming the profile in effect
+const ACTIVE_PROFILE_VAR: &str = "KEY_PROFILE";
+
+/// Env var listing named profiles (comma separated)
+const PROFILES_VAR: &str = "KEY_PROFILES";
+
+/// Maximum profile name length
+const MAX_PROFILE_NAME_LEN: usize = 32;
+
+/// Prefix of the per-service key variables passed to the plugin host.
+pub const PLUGIN_KEY_PREFIX: &str = "APP_FACTORY_APIKEY_";
+
+/// Conventional variable names read by provider SDKs, by service.
+const PROVIDER_KEY_VARS: &[(&str, &str)] = &[
+    ("anthropic", "ANTHROPIC_API_KEY"),
+    ("gemini", "GEMINI_API_KEY"),
+    ("openai", "OPENAI_API_KEY"),
+];
 
 // ============================================
 // TYPES
@@ -56,6 +149,73 @@ pub struct ApiKeyEntry {
     pub is_active: bool,
     /// ISO timestamp of when key was created
     pub created_at: String,
+    /// Whether the key is stored in plaintext and should be migrated
+    pub needs_migration: bool,
+    /// Recorded usage (None if the key has never been used)
+    pub usage: Option<KeyUsage>,
+}
+
+/// Key vault status returned by `get_key_vault_status`.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyVaultStatus {
+    /// Whether new and migrated keys are encrypted
+    pub enabled: bool,
+    /// Where the encryption key comes from
+    pub source: KeySource,
+    /// Number of keys in the active project still stored in plaintext
+    pub legacy_keys: usize,
+}
+
+/// Result of `test_api_key`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKeyTestResult {
+    /// Whether the provider responded at all
+    pub reachable: bool,
+    /// Whether the provider accepted the key
+    pub valid: bool,
+    /// HTTP status code (None if unreachable)
+    pub status: Option<u16>,
+    /// Round-trip time in milliseconds
+    pub latency_ms: u64,
+    /// Human-readable outcome
+    pub message: String,
+    /// Rate-limit related response headers (e.g. `x-ratelimit-remaining-requests`)
+    pub rate_limits: BTreeMap<String, String>,
+}
+
+/// Key profiles returned by `list_key_profiles`.
+#[derive(Debug, Clone, Serialize)]
+pub struct KeyProfiles {
+    /// Profile in effect
+    pub active: String,
+    /// All profiles (`default` first)
+    pub profiles: Vec<String>,
+}
+
+/// Result of `import_api_keys`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiKeyImportSummary {
+    /// Number of keys added
+    pub imported: usize,
+    /// Number of keys skipped because the same value already exists
+    pub skipped: usize,
+}
+
+/// A key inside an export bundle (plaintext only within the sealed payload).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportedKey {
+    service: String,
+    name: String,
+    key: String,
+    created_at: String,
+    is_active: bool,
+}
+
+/// Sealed payload of `export_api_keys`.
+#[derive(Debug, Serialize, Deserialize)]
+struct KeyExport {
+    exported_at: String,
+    keys: Vec<ExportedKey>,
 }
 
 /// Internal representation with full key (never serialized to frontend).
@@ -66,6 +226,7 @@ struct ApiKeyInternal {
     name: String,
     key: String,
     created_at: String,
+    needs_migration: bool,
 }
 
 impl ApiKeyInternal {
@@ -78,6 +239,8 @@ impl ApiKeyInternal {
             key_masked: mask_key(&self.key),
             is_active,
             created_at: self.created_at.clone(),
+            needs_migration: self.needs_migration,
+            usage: None,
         }
     }
 }
@@ -86,83 +249,6 @@ impl ApiKeyInternal {
 // HELPER FUNCTIONS
 // ============================================
 
-/// Get path to .env file in project root.
-/// 
-/// The function searches for the project root by looking for the `plugins/` directory.
-/// It checks:
-/// 1. Parent directories of the executable path
-/// 2. Current working directory and its parent
-/// 3. Known development paths (src-tauri parent)
-fn get_env_path() -> PathBuf {
-    // In dev mode, log what we're looking for
-    log::debug!("get_env_path: Searching for .env file...");
-    
-    // Strategy 1: Search up from executable path
-    let exe_path = std::env::current_exe().unwrap_or_default();
-    log::debug!("get_env_path: exe_path = {exe_path:?}");
-    
-    let mut current = exe_path.parent().map(std::path::Path::to_path_buf);
-
-    for _ in 0..10 {
-        if let Some(ref dir) = current {
-            let plugins_dir = dir.join("plugins");
-            if plugins_dir.exists() && plugins_dir.is_dir() {
-                log::debug!("get_env_path: Found via exe path traversal: {dir:?}");
-                return dir.join(".env");
-            }
-            current = dir.parent().map(std::path::Path::to_path_buf);
-        } else {
-            break;
-        }
-    }
-
-    // Strategy 2: Check current working directory
-    let cwd = std::env::current_dir().unwrap_or_default();
-    log::debug!("get_env_path: cwd = {cwd:?}");
-    
-    if cwd.join("plugins").exists() {
-        log::debug!("get_env_path: Found via cwd: {cwd:?}");
-        return cwd.join(".env");
-    }
-    
-    // Strategy 3: If cwd is src-tauri, go up one level
-    if cwd.file_name().is_some_and(|n| n == "src-tauri") {
-        if let Some(parent) = cwd.parent() {
-            if parent.join("plugins").exists() {
-                log::debug!("get_env_path: Found via src-tauri parent: {parent:?}");
-                return parent.join(".env");
-            }
-        }
-    }
-    
-    // Strategy 4: Check parent of cwd
-    if let Some(parent) = cwd.parent() {
-        if parent.join("plugins").exists() {
-            log::debug!("get_env_path: Found via cwd parent: {parent:?}");
-            return parent.join(".env");
-        }
-    }
-    
-    // Strategy 5: Look for src-tauri sibling (if cwd contains target/)
-    // This handles the case where we're running from target/debug
-    let mut search = cwd.clone();
-    for _ in 0..5 {
-        if search.join("src-tauri").exists() && search.join("plugins").exists() {
-            log::debug!("get_env_path: Found via src-tauri sibling search: {search:?}");
-            return search.join(".env");
-        }
-        if let Some(parent) = search.parent() {
-            search = parent.to_path_buf();
-        } else {
-            break;
-        }
-    }
-
-    // Fallback
-    log::warn!("get_env_path: Could not find project root, using cwd: {cwd:?}");
-    cwd.join(".env")
-}
-
 /// Mask API key for display (first 3 + *** + last 3 characters).
 fn mask_key(key: &str) -> String {
     let len = key.len();
@@ -172,65 +258,97 @@ fn mask_key(key: &str) -> String {
     format!("{}***{}", &key[..3], &key[len - 3..])
 }
 
-/// Parse .env file into `HashMap`.
-fn parse_env_file(path: &PathBuf) -> HashMap<String, String> {
-    let mut map = HashMap::new();
+/// Content of a new .env: `.env.example` if it exists, else just a header.
+fn initial_env_content(path: &Path) -> String {
+    let example_path = path.with_file_name(".env.example");
+    fs::read_to_string(example_path)
+        .unwrap_or_else(|_| "# App Factory Environment Variables\n".to_string())
+}
 
-    if !path.exists() {
-        // Create .env from .env.example if it exists
-        let example_path = path.with_file_name(".env.example");
-        if example_path.exists() {
-            if let Ok(content) = fs::read_to_string(&example_path) {
-                let _ = fs::write(path, &content);
-            }
-        } else {
-            // Create empty .env
-            let _ = fs::write(path, "# App Factory Environment Variables\n");
+/// Parse .env content into `HashMap`.
+fn parse_env_content(content: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    for line in content.lines() {
+        let line = line.trim();
+        // Skip comments and empty lines
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        // Parse KEY=VALUE
+        if let Some(pos) = line.find('=') {
+            let key = line[..pos].trim().to_string();
+            let value = line[pos + 1..].trim().to_string();
+            map.insert(key, value);
         }
     }
+    map
+}
 
-    if let Ok(content) = fs::read_to_string(path) {
-        for line in content.lines() {
-            let line = line.trim();
-            // Skip comments and empty lines
-            if line.is_empty() || line.starts_with('#') {
-                continue;
-            }
-            // Parse KEY=VALUE
-            if let Some(pos) = line.find('=') {
-                let key = line[..pos].trim().to_string();
-                let value = line[pos + 1..].trim().to_string();
-                map.insert(key, value);
-            }
-        }
+/// Parse .env file into `HashMap`.
+pub fn parse_env_file(path: &PathBuf) -> HashMap<String, String> {
+    if !path.exists() {
+        let _ = fs::write(path, initial_env_content(path));
     }
+    fs::read_to_string(path)
+        .map(|content| parse_env_content(&content))
+        .unwrap_or_default()
+}
 
-    map
+/// Change the .env variables and write them back, preserving comments.
+///
+/// `change` edits the variables as they are on disk while the file lock
+/// is held, so concurrent key commands never drop each other's keys. The
+/// write is atomic and the previous file is kept as a backup (see
+/// `crate::env_file`). If `change` fails or changes nothing, the file is
+/// left as it is; failing to write is `ENV_WRITE_ERROR`.
+fn update_env_file<T>(
+    path: &Path,
+    change: impl FnOnce(&mut HashMap<String, String>) -> CommandResult<T>,
+) -> CommandResult<T> {
+    let mut outcome = None;
+    env_file::update(path, |current| {
+        // A missing .env is created under the lock too
+        let current = current.map_or_else(|| initial_env_content(path), str::to_string);
+        let before = parse_env_content(&current);
+        let mut env_vars = before.clone();
+        let result = change(&mut env_vars);
+        let changed = result.is_ok() && env_vars != before;
+        outcome = Some(result);
+        changed.then(|| render_env_file(Some(&current), &env_vars))
+    })
+    .map_err(env_write_error)?;
+    outcome.unwrap_or_else(|| Err(env_write_error(format!("Failed to update {path:?}"))))
+}
+
+/// Build an `ENV_WRITE_ERROR`.
+fn env_write_error(message: String) -> CommandError {
+    CommandError {
+        code: "ENV_WRITE_ERROR".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
 }
 
-/// Write `HashMap` back to .env file, preserving comments.
-fn write_env_file(path: &PathBuf, env_vars: &HashMap<String, String>) -> Result<(), String> {
+/// Render env vars over the current .env content, preserving comments and order.
+fn render_env_file(current: Option<&str>, env_vars: &HashMap<String, String>) -> String {
     let mut lines: Vec<String> = Vec::new();
     let mut written_keys: std::collections::HashSet<String> = std::collections::HashSet::new();
 
-    // Read existing file to preserve comments and structure
-    if path.exists() {
-        if let Ok(content) = fs::read_to_string(path) {
-            for line in content.lines() {
-                let trimmed = line.trim();
-                if trimmed.is_empty() || trimmed.starts_with('#') {
-                    // Preserve comments and empty lines
-                    lines.push(line.to_string());
-                } else if let Some(pos) = trimmed.find('=') {
-                    let key = trimmed[..pos].trim();
-                    if let Some(value) = env_vars.get(key) {
-                        // Update existing key
-                        lines.push(format!("{key}={value}"));
-                        written_keys.insert(key.to_string());
-                    }
-                    // If key is not in env_vars, it's been deleted - don't write it
-                }
+    // Use the existing file to preserve comments and structure
+    for line in current.unwrap_or_default().lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            // Preserve comments and empty lines
+            lines.push(line.to_string());
+        } else if let Some(pos) = trimmed.find('=') {
+            let key = trimmed[..pos].trim();
+            if let Some(value) = env_vars.get(key) {
+                // Update existing key
+                lines.push(format!("{key}={value}"));
+                written_keys.insert(key.to_string());
             }
+            // If key is not in env_vars, it's been deleted - don't write it
         }
     }
 
@@ -241,13 +359,16 @@ fn write_env_file(path: &PathBuf, env_vars: &HashMap<String, String>) -> Result<
         }
     }
 
-    // Write to file
-    let content = lines.join("\n") + "\n";
-    fs::write(path, content).map_err(|e| format!("Failed to write .env: {e}"))
+    lines.join("\n") + "\n"
 }
 
-/// Parse API key entries from env vars for a specific service.
-fn parse_api_keys(env_vars: &HashMap<String, String>, service: &str) -> Vec<ApiKeyInternal> {
+/// Parse API key entries from env vars for a specific service, decrypting
+/// the key values. Keys that fail to decrypt are listed with an empty value.
+fn parse_api_keys(
+    env_vars: &HashMap<String, String>,
+    service: &str,
+    vault: &KeyVault,
+) -> Vec<ApiKeyInternal> {
     let prefix = format!("APIKEY_{}_", service.to_uppercase());
     let name_prefix = format!("APIKEY_NAME_{}_", service.to_uppercase());
     let created_prefix = format!("APIKEY_CREATED_{}_", service.to_uppercase());
@@ -273,12 +394,18 @@ fn parse_api_keys(env_vars: &HashMap<String, String>, service: &str) -> Vec<ApiK
                 .cloned()
                 .unwrap_or_else(|| Utc::now().to_rfc3339());
 
+            let key = vault.decrypt(value).unwrap_or_else(|e| {
+                log::warn!("API key {id} for {service}: {e}");
+                String::new()
+            });
+
             keys.push(ApiKeyInternal {
                 id,
                 service: service.to_string(),
                 name,
-                key: value.clone(),
+                key,
                 created_at,
+                needs_migration: vault.is_enabled() && !vault::is_encrypted(value),
             });
         }
     }
@@ -286,10 +413,317 @@ fn parse_api_keys(env_vars: &HashMap<String, String>, service: &str) -> Vec<ApiK
     keys
 }
 
-/// Get the active key ID for a service.
-fn get_active_id(env_vars: &HashMap<String, String>, service: &str) -> Option<String> {
-    let key = format!("ACTIVE_APIKEY_{}", service.to_uppercase());
-    env_vars.get(&key).cloned()
+/// Whether an env var holds an API key value (`APIKEY_<SERVICE>_<UUID>`).
+fn is_key_var(name: &str) -> bool {
+    name.starts_with("APIKEY_")
+        && !name.starts_with("APIKEY_NAME_")
+        && !name.starts_with("APIKEY_CREATED_")
+}
+
+/// Reject a key that does not match the service's format (see `crate::catalog`).
+fn check_key_format(catalog: &ServiceCatalog, service: &str, key: &str) -> CommandResult<()> {
+    catalog.validate(service, key).map_err(|e| CommandError {
+        code: "INVALID_KEY_FORMAT".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// Reject storing a key locally for a service bound to an external backend.
+fn check_local_storage(backends: &SecretBackends, service: &str) -> CommandResult<()> {
+    match backends.get(service) {
+        Some(config) => Err(CommandError {
+            code: "EXTERNAL_SECRET_BACKEND".to_string(),
+            message: format!(
+                "{service} keys are read from {} and cannot be stored in the project",
+                config.store().name()
+            ),
+            details: None,
+            source_chain: None,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Encrypt a key value for storage.
+fn encrypt_key(vault: &KeyVault, key: &str) -> CommandResult<String> {
+    vault.encrypt(key).map_err(|e| CommandError {
+        code: "ENCRYPTION_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// Fail with `OFFLINE` if offline mode blocks a key test request.
+fn check_key_test_offline(request: &reqwest::Request) -> CommandResult<()> {
+    offline::check_url(request.url()).map_err(|e| CommandError {
+        code: "OFFLINE".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// Build the cheapest authenticated request for a provider.
+///
+/// Returns None for services without a known endpoint.
+fn key_test_request(
+    client: &reqwest::Client,
+    service: &str,
+    key: &str,
+    env_vars: &HashMap<String, String>,
+) -> Option<reqwest::RequestBuilder> {
+    let request = match service {
+        "openai" => client
+            .get("https://api.openai.com/v1/models")
+            .bearer_auth(key),
+        "gemini" => client
+            .get("https://generativelanguage.googleapis.com/v1beta/models?pageSize=1")
+            .header("x-goog-api-key", key),
+        "anthropic" => client
+            .get("https://api.anthropic.com/v1/models?limit=1")
+            .header("x-api-key", key)
+            .header("anthropic-version", anthropic::API_VERSION),
+        "ollama" => {
+            let request = client.get(format!("{}/api/tags", ollama::host(env_vars)));
+            // Local Ollama needs no key; a proxied one may expect a bearer token
+            if key.is_empty() {
+                request
+            } else {
+                request.bearer_auth(key)
+            }
+        }
+        _ => return None,
+    };
+    Some(request)
+}
+
+/// Interpret a provider status code: (key valid, message).
+fn classify_key_status(status: reqwest::StatusCode) -> (bool, String) {
+    match status.as_u16() {
+        200..=299 => (true, "Key is valid".to_string()),
+        401 | 403 => (false, format!("Key was rejected ({status})")),
+        // The key was accepted but the quota is exhausted
+        429 => (true, "Key is valid but rate limited".to_string()),
+        _ => (false, format!("Unexpected response ({status})")),
+    }
+}
+
+/// Collect rate-limit related headers.
+fn rate_limit_headers(headers: &reqwest::header::HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .filter(|(name, _)| name.as_str().contains("ratelimit") || name.as_str() == "retry-after")
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// Services that have at least one key (parsed from `APIKEY_<SERVICE>_<UUID>`).
+fn configured_services(env_vars: &HashMap<String, String>) -> BTreeSet<String> {
+    env_vars
+        .keys()
+        .filter(|key| is_key_var(key))
+        .filter_map(|key| {
+            let parts: Vec<&str> = key.split('_').collect();
+            (parts.len() >= 3).then(|| parts[1].to_lowercase())
+        })
+        .collect()
+}
+
+/// Reject passphrases too short to protect an export.
+fn check_passphrase(passphrase: &str) -> CommandResult<()> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(CommandError {
+            code: "WEAK_PASSPHRASE".to_string(),
+            message: format!("Passphrase must be at least {MIN_PASSPHRASE_LEN} characters"),
+            details: None,
+            source_chain: None,
+        });
+    }
+    Ok(())
+}
+
+/// All profiles, `default` first.
+fn profiles(env_vars: &HashMap<String, String>) -> Vec<String> {
+    let mut list = vec![DEFAULT_PROFILE.to_string()];
+    if let Some(names) = env_vars.get(PROFILES_VAR) {
+        for name in names.split(',').map(|n| n.trim().to_lowercase()) {
+            if !name.is_empty() && !list.contains(&name) {
+                list.push(name);
+            }
+        }
+    }
+    list
+}
+
+/// Env var holding a service's active key ID in a profile.
+fn active_var(service: &str, profile: &str) -> String {
+    if profile == DEFAULT_PROFILE {
+        format!("ACTIVE_APIKEY_{}", service.to_uppercase())
+    } else {
+        format!(
+            "PROFILE_{}_ACTIVE_APIKEY_{}",
+            profile.to_uppercase(),
+            service.to_uppercase()
+        )
+    }
+}
+
+/// Resolve the profile a command applies to: the explicit argument, else
+/// the profile in effect.
+fn resolve_profile(
+    env_vars: &HashMap<String, String>,
+    profile: Option<String>,
+) -> CommandResult<String> {
+    let profile = profile
+        .or_else(|| env_vars.get(ACTIVE_PROFILE_VAR).cloned())
+        .map_or_else(|| DEFAULT_PROFILE.to_string(), |p| p.trim().to_lowercase());
+
+    if profiles(env_vars).contains(&profile) {
+        Ok(profile)
+    } else {
+        Err(CommandError {
+            code: "PROFILE_NOT_FOUND".to_string(),
+            message: format!("Key profile {profile} not found"),
+            details: None,
+            source_chain: None,
+        })
+    }
+}
+
+/// Validate and normalize a new profile name.
+fn normalize_profile_name(name: &str) -> CommandResult<String> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty()
+        || name.len() > MAX_PROFILE_NAME_LEN
+        || !name.chars().all(|c| c.is_ascii_alphanumeric())
+    {
+        return Err(CommandError {
+            code: "INVALID_PROFILE".to_string(),
+            message: format!("Profile names must be 1-{MAX_PROFILE_NAME_LEN} letters or digits"),
+            details: None,
+            source_chain: None,
+        });
+    }
+    Ok(name)
+}
+
+/// Get the active key ID for a service in a profile.
+fn get_active_id(
+    env_vars: &HashMap<String, String>,
+    service: &str,
+    profile: &str,
+) -> Option<String> {
+    env_vars.get(&active_var(service, profile)).cloned()
+}
+
+/// Plugin host variables for the active keys in parsed env vars and the
+/// keys of services bound to external backends.
+fn plugin_env_vars(
+    env_vars: &HashMap<String, String>,
+    vault: &KeyVault,
+    backends: &SecretBackends,
+) -> Vec<(String, String)> {
+    let profile = resolve_profile(env_vars, None).unwrap_or_else(|_| DEFAULT_PROFILE.to_string());
+    let mut services = configured_services(env_vars);
+    services.extend(backends.list().into_keys());
+    let mut vars = Vec::new();
+
+    for service in services {
+        let key = if let Some(result) = backends.read(&service) {
+            result
+        } else {
+            let Some(stored) = get_active_id(env_vars, &service, &profile)
+                .and_then(|id| env_vars.get(&format!("APIKEY_{}_{id}", service.to_uppercase())))
+            else {
+                continue;
+            };
+            vault.decrypt(stored)
+        };
+
+        match key {
+            Ok(key) => {
+                if let Some((_, name)) = PROVIDER_KEY_VARS.iter().find(|(s, _)| *s == service) {
+                    vars.push(((*name).to_string(), key.clone()));
+                }
+                vars.push((
+                    format!("{PLUGIN_KEY_PREFIX}{}", service.to_uppercase()),
+                    key,
+                ));
+            }
+            Err(e) => log::warn!("Active {service} key not passed to plugins: {e}"),
+        }
+    }
+
+    vars
+}
+
+/// Environment variables exposing a project's active keys to the plugin host.
+///
+/// Each service with an active key in the profile in effect (or bound to
+/// an external backend) yields `APP_FACTORY_APIKEY_<SERVICE>`, plus the
+/// provider's conventional name (e.g. `OPENAI_API_KEY`) where there is
+/// one. Used as the IPC config's `EnvProvider`, so values are read fresh
+/// at every spawn.
+pub fn plugin_env(
+    env_path: &Path,
+    vault: &KeyVault,
+    backends: &SecretBackends,
+) -> Vec<(String, String)> {
+    let env_vars = if env_path.exists() {
+        parse_env_file(&env_path.to_path_buf())
+    } else {
+        HashMap::new()
+    };
+    plugin_env_vars(&env_vars, vault, backends)
+}
+
+/// Resolve a service's active key in a profile and decrypt it.
+///
+/// Services bound to an external backend (see `crate::secret_store`) are
+/// read from the backend instead, with the backend name as key ID.
+///
+/// Used by the LLM proxy (see `commands::llm`) so key values stay in the
+/// backend.
+///
+/// # Returns
+///
+/// The key ID and value, or None if the service has no active key.
+pub fn active_key(
+    env_path: &Path,
+    vault: &KeyVault,
+    backends: &SecretBackends,
+    service: &str,
+    profile: Option<String>,
+) -> CommandResult<Option<(String, String)>> {
+    if let Some(result) = backends.read(service) {
+        let key = result.map_err(|e| CommandError {
+            code: "SECRET_BACKEND_ERROR".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })?;
+        return Ok(Some((backends.key_id(service).unwrap_or_default(), key)));
+    }
+
+    let env_vars = parse_env_file(&env_path.to_path_buf());
+    let profile = resolve_profile(&env_vars, profile)?;
+    let Some(id) = get_active_id(&env_vars, service, &profile) else {
+        return Ok(None);
+    };
+    let Some(stored) = env_vars.get(&format!("APIKEY_{}_{id}", service.to_uppercase())) else {
+        return Ok(None);
+    };
+
+    let key = vault.decrypt(stored).map_err(|e| CommandError {
+        code: "DECRYPTION_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })?;
+    Ok(Some((id, key)))
 }
 
 // ============================================
@@ -301,24 +735,39 @@ fn get_active_id(env_vars: &HashMap<String, String>, service: &str) -> Option<St
 /// # Arguments
 ///
 /// * `service` - Service type (gemini, openai, anthropic, etc.)
+/// * `profile` - Key profile (default: the active profile)
 ///
 /// # Returns
 ///
 /// Array of `ApiKeyEntry` (with masked keys).
 #[tauri::command]
-pub fn get_api_keys(service: String) -> CommandResult<Vec<ApiKeyEntry>> {
+pub fn get_api_keys(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    storage: State<'_, Storage>,
+    service: String,
+    profile: Option<String>,
+) -> CommandResult<Vec<ApiKeyEntry>> {
     log::debug!("Command: get_api_keys service={service}");
 
-    let env_path = get_env_path();
+    let env_path = project.env_path();
     let env_vars = parse_env_file(&env_path);
-    let keys = parse_api_keys(&env_vars, &service);
-    let active_id = get_active_id(&env_vars, &service);
+    let profile = resolve_profile(&env_vars, profile)?;
+    let keys = parse_api_keys(&env_vars, &service, &vault);
+    let active_id = get_active_id(&env_vars, &service, &profile);
+    let mut usage = key_usage::for_service(&storage, &service).unwrap_or_else(|e| {
+        log::warn!("{e}");
+        HashMap::new()
+    });
 
     let entries: Vec<ApiKeyEntry> = keys
         .into_iter()
         .map(|k| {
             let is_active = active_id.as_ref() == Some(&k.id);
-            k.to_entry(is_active)
+            ApiKeyEntry {
+                usage: usage.remove(&k.id),
+                ..k.to_entry(is_active)
+            }
         })
         .collect();
 
@@ -332,16 +781,28 @@ pub fn get_api_keys(service: String) -> CommandResult<Vec<ApiKeyEntry>> {
 /// * `service` - Service type
 /// * `name` - User-friendly name
 /// * `key` - The actual API key value
+/// * `profile` - Key profile (default: the active profile)
 ///
 /// # Returns
 ///
-/// The created `ApiKeyEntry`.
+/// The created `ApiKeyEntry`. A key that does not match the service's
+/// format fails with `INVALID_KEY_FORMAT`.
 #[tauri::command]
-pub fn add_api_key(service: String, name: String, key: String) -> CommandResult<ApiKeyEntry> {
+pub fn add_api_key(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    catalog: State<'_, ServiceCatalog>,
+    backends: State<'_, SecretBackends>,
+    service: String,
+    name: String,
+    key: String,
+    profile: Option<String>,
+) -> CommandResult<ApiKeyEntry> {
     log::info!("Command: add_api_key service={service} name={name}");
+    check_local_storage(&backends, &service)?;
+    check_key_format(&catalog, &service, &key)?;
 
-    let env_path = get_env_path();
-    let mut env_vars = parse_env_file(&env_path);
+    let encrypted = encrypt_key(&vault, &key)?;
 
     // Generate new ID
     let id = Uuid::new_v4().to_string();
@@ -352,22 +813,19 @@ pub fn add_api_key(service: String, name: String, key: String) -> CommandResult<
     let name_var = format!("APIKEY_NAME_{}_{}", service.to_uppercase(), id);
     let created_var = format!("APIKEY_CREATED_{}_{}", service.to_uppercase(), id);
 
-    env_vars.insert(key_var, key.clone());
-    env_vars.insert(name_var, name.clone());
-    env_vars.insert(created_var, created_at.clone());
-
-    // If this is the first key for the service, make it active
-    let active_key = format!("ACTIVE_APIKEY_{}", service.to_uppercase());
-    let is_first = !env_vars.contains_key(&active_key);
-    if is_first {
-        env_vars.insert(active_key, id.clone());
-    }
-
-    // Write back
-    write_env_file(&env_path, &env_vars).map_err(|e| CommandError {
-        code: "ENV_WRITE_ERROR".to_string(),
-        message: e,
-        details: None,
+    let is_first = update_env_file(&project.env_path(), |env_vars| {
+        let profile = resolve_profile(env_vars, profile)?;
+        env_vars.insert(key_var, encrypted);
+        env_vars.insert(name_var, name.clone());
+        env_vars.insert(created_var, created_at.clone());
+
+        // If this is the first key for the service, make it active
+        let active_key = active_var(&service, &profile);
+        let is_first = !env_vars.contains_key(&active_key);
+        if is_first {
+            env_vars.insert(active_key, id.clone());
+        }
+        Ok(is_first)
     })?;
 
     Ok(ApiKeyEntry {
@@ -377,6 +835,8 @@ pub fn add_api_key(service: String, name: String, key: String) -> CommandResult<
         key_masked: mask_key(&key),
         is_active: is_first,
         created_at,
+        needs_migration: false,
+        usage: None,
     })
 }
 
@@ -388,60 +848,66 @@ pub fn add_api_key(service: String, name: String, key: String) -> CommandResult<
 /// * `id` - Key ID to update
 /// * `name` - New name (optional)
 /// * `key` - New key value (optional)
+/// * `profile` - Key profile (default: the active profile)
 #[tauri::command]
 pub fn update_api_key(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    catalog: State<'_, ServiceCatalog>,
     service: String,
     id: String,
     name: Option<String>,
     key: Option<String>,
+    profile: Option<String>,
 ) -> CommandResult<ApiKeyEntry> {
     log::info!("Command: update_api_key service={service} id={id}");
+    if let Some(ref new_key) = key {
+        check_key_format(&catalog, &service, new_key)?;
+    }
 
-    let env_path = get_env_path();
-    let mut env_vars = parse_env_file(&env_path);
+    let encrypted = key
+        .map(|new_key| encrypt_key(&vault, &new_key))
+        .transpose()?;
 
-    // Check key exists
-    let key_var = format!("APIKEY_{}_{}", service.to_uppercase(), id);
-    if !env_vars.contains_key(&key_var) {
-        return Err(CommandError {
-            code: "KEY_NOT_FOUND".to_string(),
-            message: format!("API key with ID {id} not found"),
-            details: None,
-        });
-    }
+    let (env_vars, profile) = update_env_file(&project.env_path(), |env_vars| {
+        let profile = resolve_profile(env_vars, profile)?;
 
-    // Update name if provided
-    if let Some(new_name) = name.clone() {
-        let name_var = format!("APIKEY_NAME_{}_{}", service.to_uppercase(), id);
-        env_vars.insert(name_var, new_name);
-    }
+        // Check key exists
+        let key_var = format!("APIKEY_{}_{}", service.to_uppercase(), id);
+        if !env_vars.contains_key(&key_var) {
+            return Err(CommandError {
+                code: "KEY_NOT_FOUND".to_string(),
+                message: format!("API key with ID {id} not found"),
+                details: None,
+                source_chain: None,
+            });
+        }
 
-    // Update key if provided
-    if let Some(new_key) = key.clone() {
-        env_vars.insert(key_var.clone(), new_key);
-    }
+        // Update name if provided
+        if let Some(new_name) = name {
+            let name_var = format!("APIKEY_NAME_{}_{}", service.to_uppercase(), id);
+            env_vars.insert(name_var, new_name);
+        }
 
-    // Write back
-    write_env_file(&env_path, &env_vars).map_err(|e| CommandError {
-        code: "ENV_WRITE_ERROR".to_string(),
-        message: e,
-        details: None,
+        // Update key if provided
+        if let Some(encrypted) = encrypted {
+            env_vars.insert(key_var, encrypted);
+        }
+        Ok((env_vars.clone(), profile))
     })?;
 
     // Get updated entry
-    let stored_key = env_vars.get(&key_var).cloned().unwrap_or_default();
-    let name_var = format!("APIKEY_NAME_{}_{}", service.to_uppercase(), id);
-    let created_var = format!("APIKEY_CREATED_{}_{}", service.to_uppercase(), id);
-    let active_id = get_active_id(&env_vars, &service);
-
-    Ok(ApiKeyEntry {
-        id: id.clone(),
-        service,
-        name: env_vars.get(&name_var).cloned().unwrap_or_else(|| name.unwrap_or_default()),
-        key_masked: mask_key(&stored_key),
-        is_active: active_id.as_ref() == Some(&id),
-        created_at: env_vars.get(&created_var).cloned().unwrap_or_default(),
-    })
+    let active_id = get_active_id(&env_vars, &service, &profile);
+    parse_api_keys(&env_vars, &service, &vault)
+        .into_iter()
+        .find(|k| k.id == id)
+        .map(|k| k.to_entry(active_id.as_ref() == Some(&id)))
+        .ok_or_else(|| CommandError {
+            code: "KEY_NOT_FOUND".to_string(),
+            message: format!("API key with ID {id} not found"),
+            details: None,
+            source_chain: None,
+        })
 }
 
 /// Delete an API key.
@@ -451,48 +917,53 @@ pub fn update_api_key(
 /// * `service` - Service type
 /// * `id` - Key ID to delete
 #[tauri::command]
-pub fn delete_api_key(service: String, id: String) -> CommandResult<()> {
+pub fn delete_api_key(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    storage: State<'_, Storage>,
+    service: String,
+    id: String,
+) -> CommandResult<()> {
     log::info!("Command: delete_api_key service={service} id={id}");
 
-    let env_path = get_env_path();
-    let mut env_vars = parse_env_file(&env_path);
-
     // Remove key, name, and created timestamp
     let key_var = format!("APIKEY_{}_{}", service.to_uppercase(), id);
     let name_var = format!("APIKEY_NAME_{}_{}", service.to_uppercase(), id);
     let created_var = format!("APIKEY_CREATED_{}_{}", service.to_uppercase(), id);
 
-    if !env_vars.contains_key(&key_var) {
-        return Err(CommandError {
-            code: "KEY_NOT_FOUND".to_string(),
-            message: format!("API key with ID {id} not found"),
-            details: None,
-        });
-    }
-
-    env_vars.remove(&key_var);
-    env_vars.remove(&name_var);
-    env_vars.remove(&created_var);
-
-    // If this was the active key, clear active or set to another key
-    let active_key = format!("ACTIVE_APIKEY_{}", service.to_uppercase());
-    if env_vars.get(&active_key) == Some(&id) {
-        // Find another key for this service
-        let remaining_keys = parse_api_keys(&env_vars, &service);
-        if let Some(first) = remaining_keys.first() {
-            env_vars.insert(active_key, first.id.clone());
-        } else {
-            env_vars.remove(&active_key);
+    update_env_file(&project.env_path(), |env_vars| {
+        if !env_vars.contains_key(&key_var) {
+            return Err(CommandError {
+                code: "KEY_NOT_FOUND".to_string(),
+                message: format!("API key with ID {id} not found"),
+                details: None,
+                source_chain: None,
+            });
         }
-    }
 
-    // Write back
-    write_env_file(&env_path, &env_vars).map_err(|e| CommandError {
-        code: "ENV_WRITE_ERROR".to_string(),
-        message: e,
-        details: None,
+        env_vars.remove(&key_var);
+        env_vars.remove(&name_var);
+        env_vars.remove(&created_var);
+
+        // In every profile where this was the active key, clear active or set to another key
+        let remaining_keys = parse_api_keys(env_vars, &service, &vault);
+        for profile in profiles(env_vars) {
+            let active_key = active_var(&service, &profile);
+            if env_vars.get(&active_key) == Some(&id) {
+                if let Some(first) = remaining_keys.first() {
+                    env_vars.insert(active_key, first.id.clone());
+                } else {
+                    env_vars.remove(&active_key);
+                }
+            }
+        }
+        Ok(())
     })?;
 
+    if let Err(e) = key_usage::delete(&storage, &id) {
+        log::warn!("{e}");
+    }
+
     Ok(())
 }
 
@@ -501,22 +972,33 @@ pub fn delete_api_key(service: String, id: String) -> CommandResult<()> {
 /// # Arguments
 ///
 /// * `service` - Service type
+/// * `profile` - Key profile (default: the active profile)
 ///
 /// # Returns
 ///
 /// The active `ApiKeyEntry` or None.
 #[tauri::command]
-pub fn get_active_api_key(service: String) -> CommandResult<Option<ApiKeyEntry>> {
+pub fn get_active_api_key(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    storage: State<'_, Storage>,
+    service: String,
+    profile: Option<String>,
+) -> CommandResult<Option<ApiKeyEntry>> {
     log::debug!("Command: get_active_api_key service={service}");
 
-    let env_path = get_env_path();
+    let env_path = project.env_path();
     let env_vars = parse_env_file(&env_path);
-    let active_id = get_active_id(&env_vars, &service);
+    let profile = resolve_profile(&env_vars, profile)?;
+    let active_id = get_active_id(&env_vars, &service, &profile);
 
     if let Some(id) = active_id {
-        let keys = parse_api_keys(&env_vars, &service);
+        let keys = parse_api_keys(&env_vars, &service, &vault);
         if let Some(key) = keys.into_iter().find(|k| k.id == id) {
-            return Ok(Some(key.to_entry(true)));
+            return Ok(Some(ApiKeyEntry {
+                usage: key_usage::get(&storage, &id).unwrap_or_default(),
+                ..key.to_entry(true)
+            }));
         }
     }
 
@@ -529,88 +1011,593 @@ pub fn get_active_api_key(service: String) -> CommandResult<Option<ApiKeyEntry>>
 ///
 /// * `service` - Service type
 /// * `id` - Key ID to set as active
+/// * `profile` - Key profile (default: the active profile)
 #[tauri::command]
-pub fn set_active_api_key(service: String, id: String) -> CommandResult<()> {
+pub fn set_active_api_key(
+    project: State<'_, ProjectManager>,
+    service: String,
+    id: String,
+    profile: Option<String>,
+) -> CommandResult<()> {
     log::info!("Command: set_active_api_key service={service} id={id}");
 
-    let env_path = get_env_path();
-    let mut env_vars = parse_env_file(&env_path);
+    update_env_file(&project.env_path(), |env_vars| {
+        let profile = resolve_profile(env_vars, profile)?;
 
-    // Verify key exists
-    let key_var = format!("APIKEY_{}_{}", service.to_uppercase(), id);
-    if !env_vars.contains_key(&key_var) {
+        // Verify key exists
+        let key_var = format!("APIKEY_{}_{}", service.to_uppercase(), id);
+        if !env_vars.contains_key(&key_var) {
+            return Err(CommandError {
+                code: "KEY_NOT_FOUND".to_string(),
+                message: format!("API key with ID {id} not found"),
+                details: None,
+                source_chain: None,
+            });
+        }
+
+        // Set active
+        env_vars.insert(active_var(&service, &profile), id);
+        Ok(())
+    })
+}
+
+/// Get all services that have API keys configured.
+///
+/// # Returns
+///
+/// Array of service names with at least one key.
+#[tauri::command]
+pub fn get_configured_services(project: State<'_, ProjectManager>) -> CommandResult<Vec<String>> {
+    log::debug!("Command: get_configured_services");
+
+    let env_path = project.env_path();
+    let env_vars = parse_env_file(&env_path);
+
+    Ok(configured_services(&env_vars).into_iter().collect())
+}
+
+/// Export API keys as a passphrase-encrypted bundle.
+///
+/// # Arguments
+///
+/// * `passphrase` - Passphrase protecting the bundle (at least 8 characters)
+/// * `services` - Services to export (default: all)
+///
+/// # Returns
+///
+/// The bundle as a JSON string, to be saved by the frontend.
+#[tauri::command]
+pub fn export_api_keys(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    passphrase: String,
+    services: Option<Vec<String>>,
+) -> CommandResult<String> {
+    log::info!("Command: export_api_keys services={services:?}");
+    check_passphrase(&passphrase)?;
+
+    let env_vars = parse_env_file(&project.env_path());
+    let profile = resolve_profile(&env_vars, None)?;
+    let services = services.map_or_else(
+        || configured_services(&env_vars),
+        |list| list.into_iter().map(|s| s.to_lowercase()).collect(),
+    );
+
+    let mut keys = Vec::new();
+    for service in &services {
+        let active_id = get_active_id(&env_vars, service, &profile);
+        for entry in parse_api_keys(&env_vars, service, &vault) {
+            if entry.key.is_empty() {
+                log::warn!("Skipping unreadable key {} for {service}", entry.id);
+                continue;
+            }
+            keys.push(ExportedKey {
+                is_active: active_id.as_ref() == Some(&entry.id),
+                service: entry.service,
+                name: entry.name,
+                key: entry.key,
+                created_at: entry.created_at,
+            });
+        }
+    }
+
+    let export = KeyExport {
+        exported_at: Utc::now().to_rfc3339(),
+        keys,
+    };
+    let payload = serde_json::to_vec(&export).map_err(|e| CommandError {
+        code: "EXPORT_ERROR".to_string(),
+        message: e.to_string(),
+        details: None,
+        source_chain: Some(error_chain(&e)),
+    })?;
+    let bundle = vault::seal(&passphrase, &payload).map_err(|e| CommandError {
+        code: "ENCRYPTION_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })?;
+
+    log::info!("Exported {} API key(s)", export.keys.len());
+    Ok(bundle)
+}
+
+/// Import API keys from a bundle produced by `export_api_keys`.
+///
+/// Keys get new IDs; keys whose value already exists for the service are
+/// skipped. An imported key becomes active only if its service has no
+/// active key yet in the profile in effect.
+///
+/// # Arguments
+///
+/// * `bundle` - Bundle JSON
+/// * `passphrase` - Passphrase used for the export
+#[tauri::command]
+pub fn import_api_keys(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    bundle: String,
+    passphrase: String,
+) -> CommandResult<ApiKeyImportSummary> {
+    log::info!("Command: import_api_keys");
+
+    let payload = vault::unseal(&passphrase, &bundle).map_err(|e| CommandError {
+        code: "DECRYPTION_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })?;
+    let mut export: KeyExport = serde_json::from_slice(&payload).map_err(|e| CommandError {
+        code: "INVALID_BUNDLE".to_string(),
+        message: format!("Invalid key bundle: {e}"),
+        details: None,
+        source_chain: Some(error_chain(&e)),
+    })?;
+    // Previously active keys claim the active slot first
+    export.keys.sort_by_key(|k| !k.is_active);
+
+    // The .env is left untouched when nothing was imported
+    let summary = update_env_file(&project.env_path(), |env_vars| {
+        let profile = resolve_profile(env_vars, None)?;
+        let mut summary = ApiKeyImportSummary {
+            imported: 0,
+            skipped: 0,
+        };
+
+        for entry in export.keys {
+            let service = entry.service.to_lowercase();
+            let exists = parse_api_keys(env_vars, &service, &vault)
+                .iter()
+                .any(|k| k.key == entry.key);
+            if exists {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let id = Uuid::new_v4().to_string();
+            let upper = service.to_uppercase();
+            env_vars.insert(
+                format!("APIKEY_{upper}_{id}"),
+                encrypt_key(&vault, &entry.key)?,
+            );
+            env_vars.insert(format!("APIKEY_NAME_{upper}_{id}"), entry.name);
+            env_vars.insert(format!("APIKEY_CREATED_{upper}_{id}"), entry.created_at);
+            env_vars.entry(active_var(&service, &profile)).or_insert(id);
+            summary.imported += 1;
+        }
+        Ok(summary)
+    })?;
+
+    log::info!(
+        "Imported {} API key(s), skipped {}",
+        summary.imported,
+        summary.skipped
+    );
+    Ok(summary)
+}
+
+/// Record a provider request made with an API key.
+///
+/// Called by the LLM services after each request so `get_api_keys` can
+/// show which keys are being consumed.
+///
+/// # Arguments
+///
+/// * `service` - Service type
+/// * `id` - Key ID (defaults to the service's active key)
+/// * `tokens` - Token count reported by the provider, if any
+/// * `profile` - Profile whose active key is meant when `id` is omitted
+///
+/// # Returns
+///
+/// The key's updated usage totals.
+#[tauri::command]
+pub fn record_api_key_usage(
+    project: State<'_, ProjectManager>,
+    storage: State<'_, Storage>,
+    service: String,
+    id: Option<String>,
+    tokens: Option<u64>,
+    profile: Option<String>,
+) -> CommandResult<KeyUsage> {
+    log::debug!("Command: record_api_key_usage service={service} id={id:?}");
+
+    let id = match id {
+        Some(id) => Some(id),
+        None => {
+            let env_vars = parse_env_file(&project.env_path());
+            let profile = resolve_profile(&env_vars, profile)?;
+            get_active_id(&env_vars, &service, &profile)
+        }
+    };
+    let Some(id) = id else {
         return Err(CommandError {
             code: "KEY_NOT_FOUND".to_string(),
-            message: format!("API key with ID {id} not found"),
+            message: format!("No active API key for {service}"),
             details: None,
+            source_chain: None,
         });
-    }
+    };
 
-    // Set active
-    let active_key = format!("ACTIVE_APIKEY_{}", service.to_uppercase());
-    env_vars.insert(active_key, id);
+    key_usage::record(&storage, &service, &id, tokens).map_err(|e| CommandError {
+        code: "STORAGE_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
 
-    // Write back
-    write_env_file(&env_path, &env_vars).map_err(|e| CommandError {
-        code: "ENV_WRITE_ERROR".to_string(),
+/// Test an API key with a minimal authenticated request to the provider.
+///
+/// Uses the models list endpoint for OpenAI, Gemini and Anthropic, and
+/// `/api/tags` for Ollama.
+///
+/// # Arguments
+///
+/// * `service` - Service type (openai, gemini, anthropic, ollama)
+/// * `id` - Key ID to test
+///
+/// # Returns
+///
+/// Reachability, validity and rate-limit headers. Network failures are
+/// reported as `reachable: false` rather than as an error; providers on other
+/// machines fail with `OFFLINE` in offline mode.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn test_api_key(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+    service: String,
+    id: String,
+) -> CommandResult<ApiKeyTestResult> {
+    log::info!("Command: test_api_key service={service} id={id}");
+
+    let env_vars = parse_env_file(&project.env_path());
+    let key_var = format!("APIKEY_{}_{}", service.to_uppercase(), id);
+    let Some(stored) = env_vars.get(&key_var) else {
+        return Err(CommandError {
+            code: "KEY_NOT_FOUND".to_string(),
+            message: format!("API key with ID {id} not found"),
+            details: None,
+            source_chain: None,
+        });
+    };
+    let key = vault.decrypt(stored).map_err(|e| CommandError {
+        code: "DECRYPTION_ERROR".to_string(),
         message: e,
         details: None,
+        source_chain: None,
     })?;
 
-    Ok(())
+    let client = proxy::apply(reqwest::Client::builder())
+        .timeout(KEY_TEST_TIMEOUT)
+        .build()
+        .map_err(|e| CommandError {
+            code: "HTTP_CLIENT_ERROR".to_string(),
+            message: e.to_string(),
+            details: None,
+            source_chain: Some(error_chain(&e)),
+        })?;
+    let Some(request) = key_test_request(&client, &service.to_lowercase(), &key, &env_vars) else {
+        return Err(CommandError {
+            code: "UNSUPPORTED_SERVICE".to_string(),
+            message: format!("Key testing is not supported for {service}"),
+            details: None,
+            source_chain: None,
+        });
+    };
+    let request = request.build().map_err(|e| CommandError {
+        code: "HTTP_CLIENT_ERROR".to_string(),
+        message: e.to_string(),
+        details: None,
+        source_chain: Some(error_chain(&e)),
+    })?;
+    check_key_test_offline(&request)?;
+
+    let started = Instant::now();
+    let response = client.execute(request).await;
+    let latency_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+    let result = match response {
+        Ok(response) => {
+            let (valid, message) = classify_key_status(response.status());
+            ApiKeyTestResult {
+                reachable: true,
+                valid,
+                status: Some(response.status().as_u16()),
+                latency_ms,
+                message,
+                rate_limits: rate_limit_headers(response.headers()),
+            }
+        }
+        Err(e) => ApiKeyTestResult {
+            reachable: false,
+            valid: false,
+            status: None,
+            latency_ms,
+            message: format!("Provider unreachable: {e}"),
+            rate_limits: BTreeMap::new(),
+        },
+    };
+
+    log::info!("API key test {service}/{id}: {}", result.message);
+    Ok(result)
+}
+
+/// List key profiles.
+///
+/// # Returns
+///
+/// The profile in effect and all profiles (`default` first).
+#[tauri::command]
+pub fn list_key_profiles(project: State<'_, ProjectManager>) -> CommandResult<KeyProfiles> {
+    log::debug!("Command: list_key_profiles");
+
+    let env_vars = parse_env_file(&project.env_path());
+    Ok(KeyProfiles {
+        active: resolve_profile(&env_vars, None)?,
+        profiles: profiles(&env_vars),
+    })
 }
 
-/// Get the actual (unmasked) value of the active API key.
-/// This is used by services to make API calls.
+/// Create a key profile.
+///
+/// The new profile starts with the active-key selections of the profile
+/// in effect.
 ///
 /// # Arguments
 ///
-/// * `service` - Service type
+/// * `name` - Profile name (letters and digits, stored lowercase)
+#[tauri::command]
+pub fn create_key_profile(
+    project: State<'_, ProjectManager>,
+    name: String,
+) -> CommandResult<KeyProfiles> {
+    log::info!("Command: create_key_profile name={name}");
+
+    let name = normalize_profile_name(&name)?;
+    update_env_file(&project.env_path(), |env_vars| {
+        let mut all = profiles(env_vars);
+        if all.contains(&name) {
+            return Err(CommandError {
+                code: "PROFILE_EXISTS".to_string(),
+                message: format!("Key profile {name} already exists"),
+                details: None,
+                source_chain: None,
+            });
+        }
+
+        let current = resolve_profile(env_vars, None)?;
+        for service in configured_services(env_vars) {
+            if let Some(id) = get_active_id(env_vars, &service, &current) {
+                env_vars.insert(active_var(&service, &name), id);
+            }
+        }
+
+        all.push(name);
+        env_vars.insert(PROFILES_VAR.to_string(), all[1..].join(","));
+
+        Ok(KeyProfiles {
+            active: current,
+            profiles: all,
+        })
+    })
+}
+
+/// Delete a key profile and its active-key selections.
 ///
-/// # Returns
+/// Deleting the profile in effect switches back to `default`. The
+/// `default` profile cannot be deleted.
+///
+/// # Arguments
 ///
-/// The actual API key value or None if no active key.
+/// * `name` - Profile name
 #[tauri::command]
-pub fn get_active_api_key_value(service: String) -> CommandResult<Option<String>> {
-    log::debug!("Command: get_active_api_key_value service={service}");
+pub fn delete_key_profile(
+    project: State<'_, ProjectManager>,
+    name: String,
+) -> CommandResult<KeyProfiles> {
+    log::info!("Command: delete_key_profile name={name}");
+
+    update_env_file(&project.env_path(), |env_vars| {
+        let name = resolve_profile(env_vars, Some(name))?;
+        if name == DEFAULT_PROFILE {
+            return Err(CommandError {
+                code: "INVALID_PROFILE".to_string(),
+                message: "The default profile cannot be deleted".to_string(),
+                details: None,
+                source_chain: None,
+            });
+        }
 
-    let env_path = get_env_path();
-    let env_vars = parse_env_file(&env_path);
-    let active_id = get_active_id(&env_vars, &service);
+        let selections = format!("PROFILE_{}_ACTIVE_APIKEY_", name.to_uppercase());
+        env_vars.retain(|key, _| !key.starts_with(&selections));
 
-    if let Some(id) = active_id {
-        let key_var = format!("APIKEY_{}_{}", service.to_uppercase(), id);
-        return Ok(env_vars.get(&key_var).cloned());
-    }
+        let remaining: Vec<String> = profiles(env_vars)
+            .into_iter()
+            .filter(|p| *p != name)
+            .collect();
+        if remaining.len() > 1 {
+            env_vars.insert(PROFILES_VAR.to_string(), remaining[1..].join(","));
+        } else {
+            env_vars.remove(PROFILES_VAR);
+        }
+        if resolve_profile(env_vars, None).is_err() {
+            env_vars.remove(ACTIVE_PROFILE_VAR);
+        }
 
-    Ok(None)
+        Ok(KeyProfiles {
+            active: resolve_profile(env_vars, None)?,
+            profiles: remaining,
+        })
+    })
 }
 
-/// Get all services that have API keys configured.
+/// Switch the profile in effect.
+///
+/// Commands and LLM requests that don't name a profile use the active
+/// keys of this profile.
+///
+/// # Arguments
+///
+/// * `name` - Profile name
+#[tauri::command]
+pub fn set_active_profile(project: State<'_, ProjectManager>, name: String) -> CommandResult<()> {
+    log::info!("Command: set_active_profile name={name}");
+
+    update_env_file(&project.env_path(), |env_vars| {
+        let name = resolve_profile(env_vars, Some(name))?;
+        if name == DEFAULT_PROFILE {
+            env_vars.remove(ACTIVE_PROFILE_VAR);
+        } else {
+            env_vars.insert(ACTIVE_PROFILE_VAR.to_string(), name);
+        }
+        Ok(())
+    })
+}
+
+/// Get the key vault status for the active project.
 ///
 /// # Returns
 ///
-/// Array of service names with at least one key.
+/// Whether encryption is enabled, the key source, and how many keys are
+/// still stored in plaintext.
 #[tauri::command]
-pub fn get_configured_services() -> CommandResult<Vec<String>> {
-    log::debug!("Command: get_configured_services");
+pub fn get_key_vault_status(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+) -> CommandResult<KeyVaultStatus> {
+    log::debug!("Command: get_key_vault_status");
+
+    let env_vars = parse_env_file(&project.env_path());
+    let legacy_keys = if vault.is_enabled() {
+        env_vars
+            .iter()
+            .filter(|(name, value)| is_key_var(name) && !vault::is_encrypted(value))
+            .count()
+    } else {
+        0
+    };
+
+    Ok(KeyVaultStatus {
+        enabled: vault.is_enabled(),
+        source: vault.source(),
+        legacy_keys,
+    })
+}
 
-    let env_path = get_env_path();
-    let env_vars = parse_env_file(&env_path);
+/// Encrypt all plaintext API keys in the active project's .env.
+///
+/// # Returns
+///
+/// Number of keys migrated.
+#[tauri::command]
+pub fn migrate_api_keys(
+    project: State<'_, ProjectManager>,
+    vault: State<'_, KeyVault>,
+) -> CommandResult<usize> {
+    log::info!("Command: migrate_api_keys");
 
-    let mut services: std::collections::HashSet<String> = std::collections::HashSet::new();
+    if !vault.is_enabled() {
+        return Err(CommandError {
+            code: "VAULT_DISABLED".to_string(),
+            message: "API key encryption is not available".to_string(),
+            details: None,
+            source_chain: None,
+        });
+    }
 
-    for key in env_vars.keys() {
-        if key.starts_with("APIKEY_") && !key.starts_with("APIKEY_NAME_") && !key.starts_with("APIKEY_CREATED_") {
-            // Parse service from APIKEY_<SERVICE>_<UUID>
-            let parts: Vec<&str> = key.split('_').collect();
-            if parts.len() >= 3 {
-                services.insert(parts[1].to_lowercase());
+    let migrated = update_env_file(&project.env_path(), |env_vars| {
+        let mut migrated = 0;
+        for (name, value) in env_vars.iter_mut() {
+            if is_key_var(name) && !vault::is_encrypted(value) {
+                *value = encrypt_key(&vault, value)?;
+                migrated += 1;
             }
         }
+        Ok(migrated)
+    })?;
+
+    log::info!("Migrated {migrated} API key(s) to encrypted storage");
+    Ok(migrated)
+}
+
+/// List backups of the active project's .env (most recent first).
+///
+/// A backup is taken before every write of the .env; the newest
+/// `env_file::MAX_BACKUPS` are kept.
+#[tauri::command]
+pub fn list_env_backups(project: State<'_, ProjectManager>) -> CommandResult<Vec<EnvBackup>> {
+    log::debug!("Command: list_env_backups");
+    Ok(env_file::list_backups(&project.env_path()))
+}
+
+/// Restore the active project's .env from a backup.
+///
+/// The current .env is backed up first, so the restore can be undone.
+/// Call `refresh_plugin_secrets` afterwards to hand the restored keys to
+/// running plugin hosts.
+///
+/// # Arguments
+///
+/// * `id` - Backup id from `list_env_backups`
+#[tauri::command]
+pub fn restore_env_backup(project: State<'_, ProjectManager>, id: String) -> CommandResult<()> {
+    log::info!("Command: restore_env_backup id={id}");
+    env_file::restore_backup(&project.env_path(), &id).map_err(|e| CommandError {
+        code: "ENV_RESTORE_ERROR".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })
+}
+
+/// Restart running plugin hosts so they pick up the current active keys.
+///
+/// Keys reach plugins as environment variables at spawn time; call this
+/// after adding keys or switching active keys or profiles.
+///
+/// # Returns
+///
+/// Number of hosts restarted.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn refresh_plugin_secrets(
+    workspaces: State<'_, WorkspaceRegistry>,
+) -> CommandResult<usize> {
+    log::info!("Command: refresh_plugin_secrets");
+
+    let mut restarted = 0;
+    for (id, manager) in workspaces.entries() {
+        if !manager.is_ready().await {
+            continue;
+        }
+        manager.restart().await.map_err(CommandError::from)?;
+        log::info!("Plugin host restarted with refreshed secrets: {id}");
+        restarted += 1;
     }
 
-    Ok(services.into_iter().collect())
+    Ok(restarted)
 }
 
 // ============================================
@@ -620,6 +1607,7 @@ pub fn get_configured_services() -> CommandResult<Vec<String>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::temp_dir;
 
     #[test]
     fn test_mask_key_normal() {
@@ -637,6 +1625,166 @@ mod tests {
         assert_eq!(mask_key("abcdefg"), "abc***efg");
     }
 
+    #[test]
+    fn test_parse_api_keys_decrypts() {
+        let vault = KeyVault::with_key(&[1u8; 32], KeySource::Machine);
+        let mut env_vars = HashMap::new();
+        env_vars.insert(
+            "APIKEY_OPENAI_a1".to_string(),
+            vault.encrypt("sk-encrypted").unwrap(),
+        );
+        env_vars.insert("APIKEY_OPENAI_b2".to_string(), "sk-plaintext".to_string());
+        env_vars.insert("APIKEY_NAME_OPENAI_a1".to_string(), "Main".to_string());
+
+        let mut keys = parse_api_keys(&env_vars, "openai", &vault);
+        keys.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(keys.len(), 2);
+        assert_eq!(
+            (keys[0].key.as_str(), keys[0].needs_migration),
+            ("sk-encrypted", false)
+        );
+        assert_eq!(
+            (keys[1].key.as_str(), keys[1].needs_migration),
+            ("sk-plaintext", true)
+        );
+        assert_eq!(keys[0].name, "Main");
+
+        // Without encryption nothing can be migrated
+        let plain = parse_api_keys(&env_vars, "openai", &KeyVault::disabled());
+        assert!(plain.iter().all(|k| !k.needs_migration));
+    }
+
+    #[test]
+    fn test_classify_key_status() {
+        use reqwest::StatusCode;
+
+        assert!(classify_key_status(StatusCode::OK).0);
+        assert!(!classify_key_status(StatusCode::UNAUTHORIZED).0);
+        assert!(classify_key_status(StatusCode::TOO_MANY_REQUESTS).0);
+        assert!(!classify_key_status(StatusCode::INTERNAL_SERVER_ERROR).0);
+    }
+
+    #[test]
+    fn test_key_tests_blocked_offline() {
+        let client = reqwest::Client::new();
+        let env_vars = HashMap::new();
+        let request = |service: &str| {
+            key_test_request(&client, service, "key", &env_vars)
+                .unwrap()
+                .build()
+                .unwrap()
+        };
+
+        crate::offline::set_offline_for_test(true);
+        for service in ["openai", "gemini", "anthropic"] {
+            let error = check_key_test_offline(&request(service)).unwrap_err();
+            assert_eq!(error.code, "OFFLINE");
+        }
+        assert!(check_key_test_offline(&request("ollama")).is_ok());
+
+        crate::offline::set_offline_for_test(false);
+        assert!(check_key_test_offline(&request("openai")).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_headers() {
+        use reqwest::header::{HeaderMap, HeaderValue};
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("59"),
+        );
+        headers.insert("retry-after", HeaderValue::from_static("2"));
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+
+        let limits = rate_limit_headers(&headers);
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits["x-ratelimit-remaining-requests"], "59");
+        assert_eq!(limits["retry-after"], "2");
+    }
+
+    #[test]
+    fn test_configured_services() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert("APIKEY_GEMINI_a1".to_string(), "x".to_string());
+        env_vars.insert("APIKEY_NAME_OPENAI_b2".to_string(), "Name only".to_string());
+        env_vars.insert("APIKEY_ANTHROPIC_c3".to_string(), "y".to_string());
+        env_vars.insert("OTHER".to_string(), "z".to_string());
+
+        let services: Vec<String> = configured_services(&env_vars).into_iter().collect();
+        assert_eq!(services, ["anthropic", "gemini"]);
+        assert!(check_passphrase("short").is_err());
+        assert!(check_passphrase("long enough").is_ok());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let mut env_vars = HashMap::new();
+        env_vars.insert(
+            PROFILES_VAR.to_string(),
+            "staging, Prod,,staging".to_string(),
+        );
+        env_vars.insert(
+            active_var("gemini", DEFAULT_PROFILE),
+            "id-default".to_string(),
+        );
+        env_vars.insert(active_var("gemini", "staging"), "id-staging".to_string());
+
+        assert_eq!(profiles(&env_vars), ["default", "staging", "prod"]);
+        assert_eq!(
+            active_var("gemini", "staging"),
+            "PROFILE_STAGING_ACTIVE_APIKEY_GEMINI"
+        );
+
+        assert_eq!(resolve_profile(&env_vars, None).unwrap(), DEFAULT_PROFILE);
+        assert!(resolve_profile(&env_vars, Some("missing".to_string())).is_err());
+        env_vars.insert(ACTIVE_PROFILE_VAR.to_string(), "staging".to_string());
+        let profile = resolve_profile(&env_vars, None).unwrap();
+        assert_eq!(
+            get_active_id(&env_vars, "gemini", &profile).as_deref(),
+            Some("id-staging")
+        );
+
+        assert!(normalize_profile_name("Dev2").is_ok());
+        assert!(normalize_profile_name("my profile").is_err());
+    }
+
+    #[test]
+    fn test_plugin_env_vars() {
+        let vault = KeyVault::with_key(&[2u8; 32], KeySource::Machine);
+        let mut env_vars = HashMap::new();
+        env_vars.insert(
+            "APIKEY_OPENAI_a1".to_string(),
+            vault.encrypt("sk-active").unwrap(),
+        );
+        env_vars.insert("APIKEY_OPENAI_b2".to_string(), "sk-other".to_string());
+        env_vars.insert("APIKEY_MISTRAL_c3".to_string(), "ms-key".to_string());
+        env_vars.insert("APIKEY_GEMINI_d4".to_string(), "unused".to_string());
+        env_vars.insert(active_var("openai", DEFAULT_PROFILE), "a1".to_string());
+        env_vars.insert(active_var("mistral", DEFAULT_PROFILE), "c3".to_string());
+
+        let backends = SecretBackends::load(None);
+        let mut vars = plugin_env_vars(&env_vars, &vault, &backends);
+        vars.sort();
+        assert_eq!(
+            vars,
+            [
+                (
+                    "APP_FACTORY_APIKEY_MISTRAL".to_string(),
+                    "ms-key".to_string()
+                ),
+                (
+                    "APP_FACTORY_APIKEY_OPENAI".to_string(),
+                    "sk-active".to_string()
+                ),
+                ("OPENAI_API_KEY".to_string(), "sk-active".to_string()),
+            ]
+        );
+        assert!(plugin_env(Path::new("/nonexistent/.env"), &vault, &backends).is_empty());
+    }
+
     #[test]
     fn test_parse_env_line() {
         let mut map = HashMap::new();
@@ -658,4 +1806,65 @@ mod tests {
         assert_eq!(map.get("ANOTHER"), Some(&"test".to_string()));
         assert_eq!(map.len(), 2);
     }
+
+    #[test]
+    fn test_render_env_file() {
+        let current = "# App Factory\nKEEP=1\nDROP=2\n\nCHANGE=old\n";
+        let env_vars: HashMap<String, String> = [("KEEP", "1"), ("CHANGE", "new"), ("ADD", "3")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+
+        assert_eq!(
+            render_env_file(Some(current), &env_vars),
+            "# App Factory\nKEEP=1\n\nCHANGE=new\nADD=3\n"
+        );
+    }
+
+    #[test]
+    fn test_update_env_file_keeps_concurrent_changes() {
+        let dir = temp_dir("secrets");
+        let env_path = dir.join(".env");
+
+        // Like concurrent `add_api_key` calls, each adding a key
+        let adds: Vec<_> = (0..8)
+            .map(|i| {
+                let env_path = env_path.clone();
+                std::thread::spawn(move || {
+                    update_env_file(&env_path, |env_vars| {
+                        let id = Uuid::new_v4().to_string();
+                        env_vars.insert(format!("APIKEY_OPENAI_{id}"), format!("sk-{i}"));
+                        env_vars.insert(format!("APIKEY_NAME_OPENAI_{id}"), format!("key {i}"));
+                        Ok(())
+                    })
+                })
+            })
+            .collect();
+        for add in adds {
+            add.join().unwrap().unwrap();
+        }
+
+        let env_vars = parse_env_file(&env_path);
+        for i in 0..8 {
+            assert!(env_vars.values().any(|v| *v == format!("sk-{i}")));
+        }
+        assert_eq!(env_vars.len(), 16);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_update_env_file_failed_change_keeps_file() {
+        let dir = temp_dir("secrets");
+        let env_path = dir.join(".env");
+        fs::write(&env_path, "KEEP=1\n").unwrap();
+
+        let result: CommandResult<()> = update_env_file(&env_path, |env_vars| {
+            env_vars.clear();
+            Err(env_write_error("failed".to_string()))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(fs::read_to_string(&env_path).unwrap(), "KEEP=1\n");
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
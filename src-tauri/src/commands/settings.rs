@@ -0,0 +1,152 @@
+//! src-tauri/src/commands/settings.rs
+//! ===================================
+//! Tauri commands for reading and writing application settings.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const all = await invoke('settings_get');
+//!     const theme = await invoke('settings_get', { key: 'ui.theme' });
+//!     await invoke('settings_set', { key: 'ipc.timeout_secs', value: 120 });
+//!     await invoke('settings_reset', { key: 'ipc.timeout_secs' });
+//!     await invoke('set_locale', { locale: 'de' });
+//!     await invoke('set_offline', { offline: true });
+//!     ```
+
+use serde_json::Value;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::error_messages;
+use crate::ipc::hosts::HostRegistry;
+use crate::offline;
+use crate::settings::{Settings, SettingsStore};
+use crate::workspace::WorkspaceRegistry;
+
+/// Convert a settings error into a `CommandError`.
+fn settings_error(message: String) -> CommandError {
+    CommandError {
+        code: "INVALID_SETTING".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Get all settings, or a single setting by dotted key.
+///
+/// # Arguments
+///
+/// * `key` - Dotted setting key (optional, e.g. `ipc.timeout_secs`)
+#[tauri::command]
+pub fn settings_get(store: State<'_, SettingsStore>, key: Option<String>) -> CommandResult<Value> {
+    log::debug!("Command: settings_get key={key:?}");
+    store.get_value(key.as_deref()).map_err(settings_error)
+}
+
+/// Validate and set a single setting.
+///
+/// IPC settings take effect the next time the plugin host starts.
+///
+/// # Arguments
+///
+/// * `key` - Dotted setting key
+/// * `value` - New value (validated against the settings schema)
+///
+/// # Returns
+///
+/// The updated settings.
+#[tauri::command]
+pub fn settings_set(
+    store: State<'_, SettingsStore>,
+    key: String,
+    value: Value,
+) -> CommandResult<Settings> {
+    log::info!("Command: settings_set key={key}");
+    store.set(&key, value).map_err(settings_error)
+}
+
+/// Reset one setting, or all settings when no key is given, to defaults.
+///
+/// # Returns
+///
+/// The updated settings.
+#[tauri::command]
+pub fn settings_reset(
+    store: State<'_, SettingsStore>,
+    key: Option<String>,
+) -> CommandResult<Settings> {
+    log::info!("Command: settings_reset key={key:?}");
+    store.reset(key.as_deref()).map_err(settings_error)
+}
+
+/// Set the locale of error messages and save it as `ui.locale`.
+///
+/// Takes effect immediately: later command errors carry a
+/// `localized_message` in this locale (see error_messages.rs).
+///
+/// # Arguments
+///
+/// * `locale` - Language tag (`de`, `pt-BR`); `en` for the built-in messages
+///
+/// # Returns
+///
+/// The normalized locale.
+#[tauri::command]
+pub fn set_locale(store: State<'_, SettingsStore>, locale: String) -> CommandResult<String> {
+    log::info!("Command: set_locale locale={locale}");
+    let locale = error_messages::normalize_locale(&locale).map_err(|message| CommandError {
+        code: "INVALID_LOCALE".to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    })?;
+    store
+        .set("ui.locale", Value::String(locale.clone()))
+        .map_err(settings_error)?;
+    error_messages::set_locale(&locale).map_err(settings_error)
+}
+
+/// Switch offline mode and save it as `network.offline`.
+///
+/// Takes effect immediately: requests to other machines fail with
+/// `OFFLINE` (see offline.rs), and running plugin hosts are told so
+/// plugins can check `host_rpc.is_offline()`.
+///
+/// # Arguments
+///
+/// * `offline` - Whether to block requests to other machines
+///
+/// # Returns
+///
+/// The new offline mode.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn set_offline(
+    store: State<'_, SettingsStore>,
+    workspaces: State<'_, WorkspaceRegistry>,
+    hosts: State<'_, HostRegistry>,
+    offline: bool,
+) -> CommandResult<bool> {
+    log::info!("Command: set_offline offline={offline}");
+    store
+        .set("network.offline", Value::Bool(offline))
+        .map_err(settings_error)?;
+    offline::set_offline(offline);
+
+    let managers: Vec<_> = workspaces
+        .entries()
+        .into_iter()
+        .map(|(_, manager)| manager)
+        .chain(hosts.managers())
+        .collect();
+    let results = futures::future::join_all(managers.iter().map(|m| m.set_offline(offline))).await;
+    for error in results.into_iter().filter_map(Result::err) {
+        // The host gets the mode from its environment when it restarts
+        log::warn!("Failed to tell a plugin host about offline mode: {error}");
+    }
+    Ok(offline)
+}
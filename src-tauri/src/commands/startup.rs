@@ -0,0 +1,36 @@
+//! src-tauri/src/commands/startup.rs
+//! ==================================
+//! Tauri command for the startup progress shown on the splash screen.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Progress is pushed through the `startup://progress` event (see
+//! `crate::startup`). Stages that finished before the frontend subscribed
+//! are only available here, so the splash screen fetches the status once
+//! and then follows the events.
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!     import { listen } from '@tauri-apps/api/event';
+//!
+//!     const status = await invoke('startup_status');
+//!     await listen('startup://progress', (event) => render(event.payload.status));
+//!     ```
+
+use tauri::State;
+
+use super::CommandResult;
+use crate::startup::{StartupProgress, StartupStatus};
+
+/// Get the startup progress so far.
+///
+/// # Returns
+///
+/// The latest step of every stage reached, and whether startup is complete
+/// or has failed.
+#[tauri::command]
+pub fn startup_status(progress: State<'_, StartupProgress>) -> CommandResult<StartupStatus> {
+    log::debug!("Command: startup_status");
+    Ok(progress.status())
+}
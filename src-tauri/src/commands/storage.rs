@@ -0,0 +1,190 @@
+//! src-tauri/src/commands/storage.rs
+//! ==================================
+//! Tauri commands for structured persistence in the embedded SQLite database.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Documents are JSON values addressed by collection and id. The `kv_*`
+//! commands are a flat key-value store for small state, with optional
+//! expiry; use a prefix per feature or app (e.g. `app:<id>:`).
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     await invoke('storage_put', { collection: 'drafts', id: 'todo', data: { title: 'Todo' } });
+//!     const doc = await invoke('storage_get', { collection: 'drafts', id: 'todo' });
+//!     const page = await invoke('storage_list', { collection: 'drafts', limit: 20 });
+//!     await invoke('storage_delete', { collection: 'drafts', id: 'todo' });
+//!
+//!     await invoke('kv_set', { key: 'app:todo:filter', value: 'done', ttlSecs: 3600 });
+//!     const filter = await invoke('kv_get', { key: 'app:todo:filter' });
+//!     const entries = await invoke('kv_list_prefix', { prefix: 'app:todo:' });
+//!     await invoke('kv_delete', { key: 'app:todo:filter' });
+//!     ```
+
+use serde_json::Value;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::storage::{Document, KvEntry, Page, Storage};
+
+/// Default page size for `storage_list`
+const DEFAULT_PAGE_SIZE: u64 = 50;
+
+/// Default number of entries returned by `kv_list_prefix`
+const DEFAULT_KV_LIMIT: u64 = 500;
+
+/// Build a storage error with the given code.
+fn storage_error(code: &str, message: String) -> CommandError {
+    CommandError {
+        code: code.to_string(),
+        message,
+        details: None,
+        source_chain: None,
+    }
+}
+
+/// Insert or replace a document.
+///
+/// # Arguments
+///
+/// * `collection` - Collection name
+/// * `id` - Document id (unique within the collection)
+/// * `data` - JSON document body
+#[tauri::command]
+pub fn storage_put(
+    storage: State<'_, Storage>,
+    collection: String,
+    id: String,
+    data: Value,
+) -> CommandResult<Document> {
+    log::debug!("Command: storage_put collection={collection} id={id}");
+    storage
+        .put_document(&collection, &id, &data)
+        .map_err(|e| storage_error("STORAGE_ERROR", e))
+}
+
+/// Get a document.
+#[tauri::command]
+pub fn storage_get(
+    storage: State<'_, Storage>,
+    collection: String,
+    id: String,
+) -> CommandResult<Document> {
+    log::debug!("Command: storage_get collection={collection} id={id}");
+    storage
+        .get_document(&collection, &id)
+        .map_err(|e| storage_error("STORAGE_ERROR", e))?
+        .ok_or_else(|| {
+            storage_error(
+                "DOCUMENT_NOT_FOUND",
+                format!("Unknown document: {collection}/{id}"),
+            )
+        })
+}
+
+/// List documents in a collection, most recently updated first.
+///
+/// # Arguments
+///
+/// * `limit` - Page size (default 50)
+/// * `offset` - Number of documents to skip (default 0)
+#[tauri::command]
+pub fn storage_list(
+    storage: State<'_, Storage>,
+    collection: String,
+    limit: Option<u64>,
+    offset: Option<u64>,
+) -> CommandResult<Page<Document>> {
+    log::debug!("Command: storage_list collection={collection}");
+    storage
+        .list_documents(
+            &collection,
+            limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            offset.unwrap_or(0),
+        )
+        .map_err(|e| storage_error("STORAGE_ERROR", e))
+}
+
+/// Delete a document.
+///
+/// # Returns
+///
+/// `true` if the document existed.
+#[tauri::command]
+pub fn storage_delete(
+    storage: State<'_, Storage>,
+    collection: String,
+    id: String,
+) -> CommandResult<bool> {
+    log::info!("Command: storage_delete collection={collection} id={id}");
+    storage
+        .delete_document(&collection, &id)
+        .map_err(|e| storage_error("STORAGE_ERROR", e))
+}
+
+/// Set a key.
+///
+/// # Arguments
+///
+/// * `key` - Non-empty key
+/// * `value` - JSON value
+/// * `ttl_secs` - Seconds until the entry expires (optional, defaults to never)
+#[tauri::command]
+pub fn kv_set(
+    storage: State<'_, Storage>,
+    key: String,
+    value: Value,
+    ttl_secs: Option<u64>,
+) -> CommandResult<KvEntry> {
+    log::debug!("Command: kv_set key={key}");
+    storage
+        .kv_set(&key, &value, ttl_secs)
+        .map_err(|e| storage_error("STORAGE_ERROR", e))
+}
+
+/// Get the value of a key.
+///
+/// # Returns
+///
+/// The value, or null if the key is unknown or expired.
+#[tauri::command]
+pub fn kv_get(storage: State<'_, Storage>, key: String) -> CommandResult<Option<Value>> {
+    log::debug!("Command: kv_get key={key}");
+    storage
+        .kv_get(&key)
+        .map(|entry| entry.map(|entry| entry.value))
+        .map_err(|e| storage_error("STORAGE_ERROR", e))
+}
+
+/// Delete a key.
+///
+/// # Returns
+///
+/// `true` if the key existed.
+#[tauri::command]
+pub fn kv_delete(storage: State<'_, Storage>, key: String) -> CommandResult<bool> {
+    log::debug!("Command: kv_delete key={key}");
+    storage
+        .kv_delete(&key)
+        .map_err(|e| storage_error("STORAGE_ERROR", e))
+}
+
+/// List the entries whose keys start with a prefix, in key order.
+///
+/// # Arguments
+///
+/// * `prefix` - Key prefix ("" lists all keys)
+/// * `limit` - Maximum number of entries (default 500)
+#[tauri::command]
+pub fn kv_list_prefix(
+    storage: State<'_, Storage>,
+    prefix: String,
+    limit: Option<u64>,
+) -> CommandResult<Vec<KvEntry>> {
+    log::debug!("Command: kv_list_prefix prefix={prefix}");
+    storage
+        .kv_list_prefix(&prefix, limit.unwrap_or(DEFAULT_KV_LIMIT))
+        .map_err(|e| storage_error("STORAGE_ERROR", e))
+}
@@ -0,0 +1,103 @@
+//! src-tauri/src/commands/workspace.rs
+//! ====================================
+//! Tauri commands for opening and closing additional workspaces.
+//!
+//! Each workspace runs its own Python plugin host. The returned workspace
+//! id is passed as the optional `workspace` argument of IPC/plugin commands.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage (TypeScript):
+//!     ```typescript
+//!     import { invoke } from '@tauri-apps/api/tauri';
+//!
+//!     const ws = await invoke('workspace_open', { path: 'C:/work/other_app' });
+//!     const plugins = await invoke('plugin_list', { workspace: ws.id });
+//!     await invoke('workspace_close', { id: ws.id });
+//!     ```
+
+use std::path::Path;
+use tauri::State;
+
+use super::{CommandError, CommandResult};
+use crate::workspace::{WorkspaceInfo, WorkspaceRegistry, DEFAULT_WORKSPACE};
+
+/// Open a project directory as an additional workspace and start its host.
+///
+/// # Arguments
+///
+/// * `path` - Project directory
+///
+/// # Returns
+///
+/// The new (or existing) workspace.
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn workspace_open(
+    workspaces: State<'_, WorkspaceRegistry>,
+    path: String,
+) -> CommandResult<WorkspaceInfo> {
+    log::info!("Command: workspace_open path={path}");
+
+    let (id, manager, created) = workspaces
+        .open(Path::new(&path))
+        .map_err(|e| CommandError {
+            code: "WORKSPACE_OPEN_ERROR".to_string(),
+            message: e,
+            details: None,
+            source_chain: None,
+        })?;
+
+    if !manager.is_ready().await {
+        if let Err(e) = manager.start().await {
+            // A workspace that was already open stays open
+            if created {
+                let _ = workspaces.close(&id);
+            }
+            return Err(CommandError::from(e));
+        }
+    }
+
+    Ok(WorkspaceInfo {
+        path: manager
+            .config()
+            .working_dir
+            .map(|p| p.to_string_lossy().to_string()),
+        lifecycle_state: manager.lifecycle_state().await,
+        is_default: id == DEFAULT_WORKSPACE,
+        id,
+    })
+}
+
+/// Close a workspace and shut down its plugin host.
+///
+/// # Arguments
+///
+/// * `id` - Workspace id (the default workspace cannot be closed)
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn workspace_close(
+    workspaces: State<'_, WorkspaceRegistry>,
+    id: String,
+) -> CommandResult<()> {
+    log::info!("Command: workspace_close id={id}");
+
+    let manager = workspaces.close(&id).map_err(|e| CommandError {
+        code: "WORKSPACE_NOT_FOUND".to_string(),
+        message: e,
+        details: None,
+        source_chain: None,
+    })?;
+
+    manager.shutdown().await.map_err(CommandError::from)
+}
+
+/// List all open workspaces (default first).
+#[tauri::command]
+#[allow(clippy::used_underscore_binding)]
+pub async fn workspace_list(
+    workspaces: State<'_, WorkspaceRegistry>,
+) -> CommandResult<Vec<WorkspaceInfo>> {
+    log::debug!("Command: workspace_list");
+    Ok(workspaces.list().await)
+}
@@ -0,0 +1,430 @@
+//! src-tauri/src/crash.rs
+//! ======================
+//! Local crash reports for Rust panics and plugin host crashes.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A panic hook records the panic message, location, thread, and backtrace
+//! before the default hook runs. Unexpected plugin host exits arrive as
+//! `ManagerEvent::SubprocessCrashed` (exit code, last stderr lines, and the
+//! methods of the requests in flight) and are recorded the same way.
+//!
+//! Reports are JSON files in the app data dir (`crashes/`), newest
+//! `MAX_REPORTS` kept, with known secrets masked (see redact.rs). Nothing
+//! leaves the machine unless the user submits a report, and only when a
+//! submit URL is configured (`crash.submit_url` in app_factory.toml) and
+//! offline mode doesn't block it.
+//!
+//! Usage:
+//!     ```rust
+//!     crash::install_panic_hook(crash_dir.clone());
+//!     let reporter = CrashReporter::new(crash_dir, app_config.crash_submit_url());
+//!     tauri::async_runtime::spawn(reporter.clone().watch(state.subscribe_events()));
+//!     let report = reporter.submit(&id).await?;
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+use crate::ipc::manager::ManagerEvent;
+use crate::offline;
+use crate::proxy;
+use crate::redact;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Directory (inside the app data dir) holding crash reports
+pub const CRASH_DIR: &str = "crashes";
+
+/// Number of reports kept
+const MAX_REPORTS: usize = 50;
+
+/// Timeout for submitting a report
+const SUBMIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ============================================
+// TYPES
+// ============================================
+
+/// What crashed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Crash {
+    /// Panic in the Rust backend
+    Panic {
+        /// Panic message
+        message: String,
+        /// Source location (`file:line:column`)
+        location: Option<String>,
+        /// Name of the panicking thread
+        thread: String,
+        /// Captured backtrace
+        backtrace: String,
+    },
+    /// Unexpected exit of the Python plugin host
+    Subprocess {
+        /// Subprocess PID
+        pid: u32,
+        /// Exit code (None if killed by a signal)
+        exit_code: Option<i32>,
+        /// Last lines written to stderr (oldest first)
+        stderr_tail: Vec<String>,
+        /// Methods of the requests in flight
+        pending_methods: Vec<String>,
+    },
+}
+
+/// A recorded crash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashReport {
+    /// Report id (file stem inside the crash directory)
+    pub id: String,
+    /// When the crash happened (RFC 3339)
+    pub created_at: String,
+    /// App version
+    pub app_version: String,
+    /// Operating system and CPU architecture
+    pub platform: String,
+    /// Crash details
+    #[serde(flatten)]
+    pub crash: Crash,
+    /// When the report was submitted (RFC 3339), if it was
+    #[serde(default)]
+    pub submitted_at: Option<String>,
+}
+
+impl CrashReport {
+    /// Build a report with secrets masked.
+    fn new(crash: Crash) -> Self {
+        let now = chrono::Utc::now();
+        let suffix = &uuid::Uuid::new_v4().simple().to_string()[..6];
+        let crash = match crash {
+            Crash::Panic {
+                message,
+                location,
+                thread,
+                backtrace,
+            } => Crash::Panic {
+                message: redact::redact(&message).into_owned(),
+                location,
+                thread,
+                backtrace: redact::redact(&backtrace).into_owned(),
+            },
+            Crash::Subprocess {
+                pid,
+                exit_code,
+                stderr_tail,
+                pending_methods,
+            } => Crash::Subprocess {
+                pid,
+                exit_code,
+                stderr_tail: stderr_tail
+                    .iter()
+                    .map(|line| redact::redact(line).into_owned())
+                    .collect(),
+                pending_methods,
+            },
+        };
+
+        Self {
+            id: format!("{}-{suffix}", now.format("%Y%m%dT%H%M%S%.3fZ")),
+            created_at: now.to_rfc3339(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            crash,
+            submitted_at: None,
+        }
+    }
+
+    /// One-line description for logs.
+    fn summary(&self) -> String {
+        match &self.crash {
+            Crash::Panic {
+                message, location, ..
+            } => match location {
+                Some(location) => format!("{message} at {location}"),
+                None => message.clone(),
+            },
+            Crash::Subprocess { pid, exit_code, .. } => match exit_code {
+                Some(code) => format!("plugin host (PID {pid}) exited with code {code}"),
+                None => format!("plugin host (PID {pid}) exited"),
+            },
+        }
+    }
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Text of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".to_string())
+}
+
+/// Path of a report, rejecting ids that could escape the directory.
+fn report_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+        return Err(format!("Invalid crash report id: {id}"));
+    }
+    Ok(dir.join(format!("{id}.json")))
+}
+
+/// Write a report and prune old ones.
+fn write_report(dir: &Path, report: &CrashReport) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {dir:?}: {e}"))?;
+    let path = report_path(dir, &report.id)?;
+    let content = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize crash report: {e}"))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+
+    for stale in read_reports(dir).iter().skip(MAX_REPORTS) {
+        let _ = fs::remove_file(dir.join(format!("{}.json", stale.id)));
+    }
+    Ok(path)
+}
+
+/// Read all reports in `dir` (newest first), skipping unreadable files.
+fn read_reports(dir: &Path) -> Vec<CrashReport> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut reports: Vec<CrashReport> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .collect();
+    reports.sort_by(|a, b| b.id.cmp(&a.id));
+    reports
+}
+
+/// Record panics in `dir` before running the previously installed hook.
+///
+/// Call once, early in `main`. With no directory, panics are only logged.
+pub fn install_panic_hook(dir: Option<PathBuf>) {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let crash = Crash::Panic {
+            message: panic_message(info.payload()),
+            location: info.location().map(ToString::to_string),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            backtrace: Backtrace::force_capture().to_string(),
+        };
+        let report = CrashReport::new(crash);
+        log::error!("Panic: {}", report.summary());
+
+        if let Some(ref dir) = dir {
+            match write_report(dir, &report) {
+                Ok(path) => log::error!("Crash report written to {path:?}"),
+                Err(e) => log::error!("Failed to write crash report: {e}"),
+            }
+        }
+        previous(info);
+    }));
+}
+
+// ============================================
+// CRASH REPORTER
+// ============================================
+
+/// Crash report store and submitter.
+#[derive(Clone)]
+pub struct CrashReporter {
+    /// Report directory (None disables recording)
+    dir: Option<PathBuf>,
+    /// Endpoint reports are POSTed to (None disables submission)
+    submit_url: Option<String>,
+    /// HTTP client for submissions
+    client: reqwest::Client,
+}
+
+impl CrashReporter {
+    /// Create a reporter for `dir`.
+    pub fn new(dir: Option<PathBuf>, submit_url: Option<String>) -> Self {
+        Self {
+            dir,
+            submit_url,
+            client: proxy::client(),
+        }
+    }
+
+    /// All reports (newest first).
+    pub fn list(&self) -> Vec<CrashReport> {
+        self.dir.as_deref().map(read_reports).unwrap_or_default()
+    }
+
+    /// Get a report by id.
+    pub fn get(&self, id: &str) -> Result<CrashReport, String> {
+        let dir = self
+            .dir
+            .as_deref()
+            .ok_or("Crash reports are not available")?;
+        let content = fs::read_to_string(report_path(dir, id)?)
+            .map_err(|_| format!("Crash report not found: {id}"))?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid crash report {id}: {e}"))
+    }
+
+    /// Record a crash.
+    pub fn record(&self, crash: Crash) -> CrashReport {
+        let report = CrashReport::new(crash);
+        log::error!("Crash: {}", report.summary());
+        if let Some(ref dir) = self.dir {
+            match write_report(dir, &report) {
+                Ok(path) => log::info!("Crash report written to {path:?}"),
+                Err(e) => log::error!("Failed to write crash report: {e}"),
+            }
+        }
+        report
+    }
+
+    /// Record plugin host crashes until the event channel closes.
+    pub async fn watch(self, mut events: broadcast::Receiver<ManagerEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(ManagerEvent::SubprocessCrashed {
+                    pid,
+                    exit_code,
+                    stderr_tail,
+                    pending_methods,
+                }) => {
+                    self.record(Crash::Subprocess {
+                        pid,
+                        exit_code,
+                        stderr_tail,
+                        pending_methods,
+                    });
+                }
+                Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Submit a report to the configured endpoint and mark it submitted.
+    pub async fn submit(&self, id: &str) -> Result<CrashReport, String> {
+        let url = self
+            .submit_url
+            .as_deref()
+            .ok_or("Crash report submission is not configured (crash.submit_url)")?;
+        let parsed =
+            reqwest::Url::parse(url).map_err(|e| format!("Invalid crash.submit_url: {e}"))?;
+        offline::check_url(&parsed)?;
+        let mut report = self.get(id)?;
+
+        let body = serde_json::to_vec(&report).map_err(|e| e.to_string())?;
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .timeout(SUBMIT_TIMEOUT)
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to submit crash report: {e}"))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Crash report endpoint returned HTTP {}",
+                response.status()
+            ));
+        }
+
+        report.submitted_at = Some(chrono::Utc::now().to_rfc3339());
+        if let Some(ref dir) = self.dir {
+            write_report(dir, &report)?;
+        }
+        log::info!("Submitted crash report {id}");
+        Ok(report)
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_record_and_read_subprocess_crash() {
+        redact::register("sk-crashreportsecret0123456789");
+        let reporter = CrashReporter::new(Some(temp_dir("crash")), None);
+
+        let report = reporter.record(Crash::Subprocess {
+            pid: 42,
+            exit_code: Some(1),
+            stderr_tail: vec!["OPENAI_API_KEY=sk-crashreportsecret0123456789".to_string()],
+            pending_methods: vec!["plugin/call".to_string()],
+        });
+
+        let stored = reporter.get(&report.id).unwrap();
+        let Crash::Subprocess {
+            stderr_tail,
+            pending_methods,
+            ..
+        } = stored.crash
+        else {
+            panic!("expected a subprocess crash");
+        };
+        assert!(!stderr_tail[0].contains("crashreportsecret"));
+        assert_eq!(pending_methods, vec!["plugin/call"]);
+        assert_eq!(reporter.list().len(), 1);
+        assert!(reporter.get("../secrets").is_err());
+    }
+
+    #[test]
+    fn test_reports_are_pruned() {
+        let dir = temp_dir("crash");
+        for i in 0..MAX_REPORTS + 2 {
+            write_report(
+                &dir,
+                &CrashReport::new(Crash::Panic {
+                    message: format!("panic {i}"),
+                    location: None,
+                    thread: "main".to_string(),
+                    backtrace: String::new(),
+                }),
+            )
+            .unwrap();
+        }
+        assert_eq!(read_reports(&dir).len(), MAX_REPORTS);
+    }
+
+    #[tokio::test]
+    async fn test_submit_blocked_offline() {
+        let reporter = CrashReporter::new(
+            Some(temp_dir("crash")),
+            Some("https://crash.example.invalid/report".to_string()),
+        );
+        let report = reporter.record(Crash::Subprocess {
+            pid: 7,
+            exit_code: None,
+            stderr_tail: Vec::new(),
+            pending_methods: Vec::new(),
+        });
+
+        offline::set_offline_for_test(true);
+        let error = reporter.submit(&report.id).await.unwrap_err();
+        assert!(error.contains("Offline mode"));
+        assert!(reporter.get(&report.id).unwrap().submitted_at.is_none());
+    }
+}
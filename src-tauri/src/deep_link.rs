@@ -0,0 +1,247 @@
+//! src-tauri/src/deep_link.rs
+//! ==========================
+//! `appfactory://` deep links for plugin installs and opening projects.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Supported links:
+//!
+//! - `appfactory://install-plugin?url=<https URL>` opens the import wizard
+//!   with the URL filled in (`INSTALL_PLUGIN_EVENT`)
+//! - `appfactory://open?project=<absolute path>` opens the project and
+//!   restarts the plugin host in it (`OPEN_PROJECT_EVENT`)
+//!
+//! Links come from web pages and other apps, so every link is confirmed
+//! in a native dialog before anything happens; the webview never sees an
+//! unconfirmed link. The scheme is registered at startup (see main.rs);
+//! a link clicked while the app is running is forwarded to the running
+//! instance, and a link that launched the app arrives as a CLI argument.
+//!
+//! Usage:
+//!     ```rust
+//!     tauri_plugin_deep_link::register(deep_link::SCHEME, move |link| {
+//!         deep_link::handle(&app_handle, &link);
+//!     })?;
+//!     ```
+
+use reqwest::Url;
+use std::path::Path;
+use tauri::{AppHandle, Manager};
+
+use crate::commands::project::project_open;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// URL scheme handled by the app
+pub const SCHEME: &str = "appfactory";
+
+/// Event carrying a confirmed plugin install (`{ url }`)
+pub const INSTALL_PLUGIN_EVENT: &str = "deep-link://install-plugin";
+
+/// Event carrying the `ProjectInfo` of a project opened from a link
+pub const OPEN_PROJECT_EVENT: &str = "deep-link://open-project";
+
+/// Title of the confirmation dialog
+const DIALOG_TITLE: &str = "App Factory link";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A parsed deep link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    /// Install a plugin from a URL
+    InstallPlugin { url: String },
+    /// Open a project directory
+    OpenProject { project: String },
+}
+
+impl DeepLink {
+    /// Confirmation prompt shown before acting on the link.
+    fn prompt(&self) -> String {
+        match self {
+            Self::InstallPlugin { url } => format!(
+                "A link wants to install a plugin from:\n\n{url}\n\n\
+                 Only continue if you trust this source."
+            ),
+            Self::OpenProject { project } => format!(
+                "A link wants to open the project:\n\n{project}\n\n\
+                 Its plugins will run with your API keys. Only continue if you trust it."
+            ),
+        }
+    }
+}
+
+// ============================================
+// PARSING
+// ============================================
+
+/// Parse an `appfactory://` link.
+///
+/// # Returns
+///
+/// * `Ok(DeepLink)` - Known action with valid parameters
+/// * `Err(String)` - Wrong scheme, unknown action, or invalid parameters
+pub fn parse(link: &str) -> Result<DeepLink, String> {
+    let url = Url::parse(link.trim()).map_err(|e| format!("Invalid link {link}: {e}"))?;
+    if url.scheme() != SCHEME {
+        return Err(format!("Not an {SCHEME}:// link: {link}"));
+    }
+
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    // `appfactory://open?..` has the action as host, `appfactory:open?..` as path
+    let action = url
+        .host_str()
+        .unwrap_or_else(|| url.path())
+        .trim_matches('/');
+    match action {
+        "install-plugin" => {
+            let source = param("url").ok_or("install-plugin link is missing `url`")?;
+            let source = Url::parse(&source).map_err(|e| format!("Invalid plugin URL: {e}"))?;
+            if source.scheme() != "https" {
+                return Err("Plugin URLs must use https".to_string());
+            }
+            Ok(DeepLink::InstallPlugin {
+                url: source.to_string(),
+            })
+        }
+        "open" => {
+            let project = param("project").ok_or("open link is missing `project`")?;
+            if !Path::new(&project).is_absolute() {
+                return Err(format!("Project path must be absolute: {project}"));
+            }
+            Ok(DeepLink::OpenProject { project })
+        }
+        other => Err(format!("Unknown {SCHEME}:// action: {other}")),
+    }
+}
+
+/// The deep link passed on the command line, if the app was launched by one.
+pub fn launch_link() -> Option<String> {
+    let prefix = format!("{SCHEME}:");
+    std::env::args()
+        .skip(1)
+        .find(|arg| arg.starts_with(&prefix))
+}
+
+// ============================================
+// HANDLING
+// ============================================
+
+/// Confirm a link with the user and route it.
+///
+/// Invalid links are logged and reported in a dialog.
+pub fn handle(app: &AppHandle, link: &str) {
+    log::info!("Deep link received: {link}");
+
+    let window = app.get_window("main");
+    if let Some(ref window) = window {
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+
+    let request = match parse(link) {
+        Ok(request) => request,
+        Err(e) => {
+            log::warn!("Ignoring deep link: {e}");
+            tauri::api::dialog::message(window.as_ref(), DIALOG_TITLE, e);
+            return;
+        }
+    };
+
+    let app = app.clone();
+    tauri::api::dialog::ask(
+        window.as_ref(),
+        DIALOG_TITLE,
+        request.prompt(),
+        move |confirmed| {
+            if confirmed {
+                dispatch(&app, request);
+            } else {
+                log::info!("Deep link declined");
+            }
+        },
+    );
+}
+
+/// Act on a confirmed link.
+fn dispatch(app: &AppHandle, request: DeepLink) {
+    match request {
+        DeepLink::InstallPlugin { url } => {
+            log::info!("Deep link: installing plugin from {url}");
+            if let Err(e) = app.emit_all(INSTALL_PLUGIN_EVENT, serde_json::json!({ "url": url })) {
+                log::warn!("Failed to emit {INSTALL_PLUGIN_EVENT}: {e}");
+            }
+        }
+        DeepLink::OpenProject { project } => {
+            log::info!("Deep link: opening project {project}");
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                match project_open(app.state(), app.state(), project).await {
+                    Ok(info) => {
+                        if let Err(e) = app.emit_all(OPEN_PROJECT_EVENT, info) {
+                            log::warn!("Failed to emit {OPEN_PROJECT_EVENT}: {e}");
+                        }
+                    }
+                    Err(e) => {
+                        log::error!("Deep link: failed to open project: {}", e.message);
+                        let window = app.get_window("main");
+                        tauri::api::dialog::message(window.as_ref(), DIALOG_TITLE, e.message);
+                    }
+                }
+            });
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_install_plugin() {
+        let link = "appfactory://install-plugin?url=https%3A%2F%2Fexample.com%2Fplugin.zip";
+        assert_eq!(
+            parse(link).unwrap(),
+            DeepLink::InstallPlugin {
+                url: "https://example.com/plugin.zip".to_string()
+            }
+        );
+
+        assert!(parse("appfactory://install-plugin?url=http://example.com/p.zip").is_err());
+        assert!(parse("appfactory://install-plugin?url=file:///etc/passwd").is_err());
+        assert!(parse("appfactory://install-plugin").is_err());
+    }
+
+    #[test]
+    fn test_parse_open_project() {
+        let root = std::env::temp_dir();
+        let link = format!(
+            "appfactory://open/?project={}",
+            root.to_string_lossy().replace(' ', "%20")
+        );
+        assert_eq!(
+            parse(&link).unwrap(),
+            DeepLink::OpenProject {
+                project: root.to_string_lossy().into_owned()
+            }
+        );
+
+        assert!(parse("appfactory://open?project=relative/dir").is_err());
+        assert!(parse("appfactory://delete?project=/tmp").is_err());
+        assert!(parse("https://open?project=/tmp").is_err());
+    }
+}
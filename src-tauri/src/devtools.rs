@@ -0,0 +1,168 @@
+//! src-tauri/src/devtools.rs
+//! =========================
+//! Developer IPC console: a live log of plugin host requests.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! In debug builds the console records every request sent to the core
+//! plugin host (see `IpcManagerState::subscribe_traffic`), keeping the last
+//! `MAX_CONSOLE_ENTRIES`, and emits each one to the console window
+//! (`IPC_CONSOLE_LABEL`) as `IPC_CONSOLE_EVENT`. The window lists them,
+//! sends hand-written calls, and replays recorded ones through the
+//! `devtools_*` commands.
+//!
+//! Release builds never start the recorder, so the manager doesn't copy
+//! request params, and the commands report `DEVTOOLS_DISABLED`.
+//!
+//! Usage:
+//!     ```rust
+//!     let console = IpcConsole::new();
+//!     if devtools::enabled() {
+//!         tauri::async_runtime::spawn(console.clone().forward(app.handle(), state));
+//!     }
+//!     let recent = console.entries(100);
+//!     ```
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::ipc::manager::{IpcManagerState, TrafficEntry};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Label of the IPC console window
+pub const IPC_CONSOLE_LABEL: &str = "ipc-console";
+
+/// App URL the console window loads
+pub const IPC_CONSOLE_URL: &str = "index.html#/ipc-console";
+
+/// Event carrying each recorded request to the console window
+pub const IPC_CONSOLE_EVENT: &str = "devtools/ipc";
+
+/// Number of requests kept for the console
+pub const MAX_CONSOLE_ENTRIES: usize = 500;
+
+/// Whether developer tools are available (debug builds only).
+pub fn enabled() -> bool {
+    cfg!(debug_assertions)
+}
+
+// ============================================
+// IPC CONSOLE
+// ============================================
+
+/// Recent plugin host requests shown in the IPC console.
+///
+/// Cloning shares the log.
+#[derive(Clone, Default)]
+pub struct IpcConsole {
+    /// Recorded requests (oldest first)
+    entries: Arc<Mutex<VecDeque<TrafficEntry>>>,
+}
+
+impl IpcConsole {
+    /// Create an empty console log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Most recent requests (newest first).
+    pub fn entries(&self, limit: usize) -> Vec<TrafficEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Find a recorded request by its JSON-RPC id.
+    pub fn find(&self, id: u64) -> Option<TrafficEntry> {
+        self.entries
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .find(|entry| entry.id == id)
+            .cloned()
+    }
+
+    /// Forget the recorded requests.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Append a request, dropping the oldest when full.
+    fn record(&self, entry: TrafficEntry) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_CONSOLE_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// Record the manager's requests and emit them to the console window
+    /// until the channel closes.
+    pub async fn forward(self, app: AppHandle, state: IpcManagerState) {
+        let mut traffic = state.subscribe_traffic();
+        loop {
+            let entry = match traffic.recv().await {
+                Ok(entry) => entry,
+                Err(RecvError::Lagged(skipped)) => {
+                    log::debug!("IPC console missed {skipped} requests");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            if app.get_window(IPC_CONSOLE_LABEL).is_some() {
+                let _ = app.emit_to(IPC_CONSOLE_LABEL, IPC_CONSOLE_EVENT, &entry);
+            }
+            self.record(entry);
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u64) -> TrafficEntry {
+        TrafficEntry {
+            id,
+            method: "echo".to_string(),
+            params: serde_json::json!({"n": id}),
+            result: None,
+            error: None,
+            sent_at: String::new(),
+            duration_ms: 0,
+        }
+    }
+
+    #[test]
+    fn test_console_keeps_recent_entries() {
+        let console = IpcConsole::new();
+        for id in 1..=502 {
+            console.record(entry(id));
+        }
+
+        let recent = console.entries(3);
+        let ids: Vec<u64> = recent.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![502, 501, 500]);
+        assert_eq!(console.entries(usize::MAX).len(), MAX_CONSOLE_ENTRIES);
+        assert!(console.find(1).is_none());
+        assert_eq!(console.find(3).unwrap().params["n"], 3);
+
+        console.clear();
+        assert!(console.entries(10).is_empty());
+    }
+}
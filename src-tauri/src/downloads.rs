@@ -0,0 +1,680 @@
+//! src-tauri/src/downloads.rs
+//! ==========================
+//! Download manager for model weights and other large plugin files.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Files are downloaded into a managed cache directory. Data is written to
+//! `<name>.part` first; an interrupted or cancelled download resumes from
+//! the partial file using an HTTP `Range` request. When a SHA-256 checksum
+//! is given, the finished file is verified before it is moved into place;
+//! a file that doesn't match is deleted.
+//!
+//! Generated apps fetch their runtime assets with `fetch_asset`, into a
+//! relative path under the assets directory and always against a checksum.
+//!
+//! In offline mode (see offline.rs) files already in place are still
+//! returned, but new downloads from other machines are refused.
+//!
+//! Usage:
+//!     ```rust
+//!     let downloads = DownloadManager::new(cache_dir).with_assets_dir(assets_dir);
+//!     let dl = downloads.start(url, None, Some(sha256), |event, dl| {
+//!         app.emit_all(&event, dl).ok();
+//!     })?;
+//!     downloads.cancel(&dl.id)?;
+//!     let asset = downloads.fetch_asset(url, "fonts/inter.woff2", sha256, on_event)?;
+//!     ```
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+use tokio::io::AsyncWriteExt;
+
+use crate::offline;
+use crate::proxy;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Suffix of in-progress download files
+const PARTIAL_SUFFIX: &str = ".part";
+
+/// Minimum time between progress events
+const PROGRESS_EVENT_INTERVAL: Duration = Duration::from_millis(500);
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Download lifecycle status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadStatus {
+    /// Transferring data
+    Downloading,
+    /// Checking the SHA-256 checksum
+    Verifying,
+    /// File is in the cache
+    Completed,
+    /// Transfer or verification failed
+    Failed,
+    /// Cancelled by the user (partial file kept for resume)
+    Cancelled,
+}
+
+impl DownloadStatus {
+    /// Check if the download has finished.
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            DownloadStatus::Completed | DownloadStatus::Failed | DownloadStatus::Cancelled
+        )
+    }
+}
+
+/// A tracked download.
+#[derive(Debug, Clone, Serialize)]
+pub struct Download {
+    /// Download id
+    pub id: String,
+    /// Source URL
+    pub url: String,
+    /// Destination path in the cache
+    pub path: String,
+    /// Current status
+    pub status: DownloadStatus,
+    /// Bytes on disk so far (including resumed bytes)
+    pub downloaded_bytes: u64,
+    /// Total size, if known
+    pub total_bytes: Option<u64>,
+    /// Expected SHA-256 (lowercase hex), if given
+    pub sha256: Option<String>,
+    /// Error message (when failed)
+    pub error: Option<String>,
+    /// Start timestamp (RFC 3339)
+    pub started_at: String,
+    /// Finish timestamp (RFC 3339)
+    pub finished_at: Option<String>,
+}
+
+impl Download {
+    /// Event name for progress updates of this download.
+    pub fn progress_event(&self) -> String {
+        format!("download/{}/progress", self.id)
+    }
+
+    /// Event name emitted once when this download finishes.
+    pub fn finished_event(&self) -> String {
+        format!("download/{}/finished", self.id)
+    }
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Validate a cache file name (no directories or traversal).
+fn sanitize_file_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains(['/', '\\', ':'])
+        || name.ends_with(PARTIAL_SUFFIX)
+    {
+        return Err(format!("Invalid file name: {name}"));
+    }
+    Ok(name.to_string())
+}
+
+/// Validate a relative asset path (no absolute paths or traversal).
+fn sanitize_asset_path(path: &str) -> Result<PathBuf, String> {
+    let relative = Path::new(path.trim());
+    let valid = !path.trim().is_empty()
+        && !path.trim().ends_with(PARTIAL_SUFFIX)
+        && relative
+            .components()
+            .all(|component| matches!(component, std::path::Component::Normal(_)));
+    if valid {
+        Ok(relative.to_path_buf())
+    } else {
+        Err(format!("Invalid asset path: {path}"))
+    }
+}
+
+/// Validate a SHA-256 checksum, returning it in lowercase.
+fn normalize_sha256(hash: &str) -> Result<String, String> {
+    let hash = hash.trim().to_lowercase();
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("Invalid SHA-256 checksum: {hash}"));
+    }
+    Ok(hash)
+}
+
+/// Derive a file name from the last URL path segment.
+fn file_name_from_url(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let name = path.rsplit('/').next()?;
+    sanitize_file_name(name).ok()
+}
+
+/// Compute the SHA-256 of a file as lowercase hex.
+pub fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open {path:?}: {e}"))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+
+    loop {
+        let n = file
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Path of the partial file for a destination.
+fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.as_os_str().to_owned();
+    name.push(PARTIAL_SUFFIX);
+    PathBuf::from(name)
+}
+
+// ============================================
+// DOWNLOAD MANAGER
+// ============================================
+
+/// Registry of downloads into the managed cache directory.
+///
+/// Clones share the download table, so transfer tasks can report progress.
+#[derive(Clone)]
+pub struct DownloadManager {
+    /// Cache directory downloads are stored in (None disables downloads)
+    cache_dir: Option<PathBuf>,
+
+    /// Directory assets are stored in (None disables `fetch_asset`)
+    assets_dir: Option<PathBuf>,
+
+    /// HTTP client
+    client: reqwest::Client,
+
+    /// Downloads keyed by id
+    downloads: Arc<RwLock<HashMap<String, Download>>>,
+
+    /// Running transfer tasks
+    tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+impl DownloadManager {
+    /// Create a download manager storing files in `cache_dir`.
+    pub fn new(cache_dir: Option<PathBuf>) -> Self {
+        Self {
+            cache_dir,
+            assets_dir: None,
+            client: proxy::client(),
+            downloads: Arc::new(RwLock::new(HashMap::new())),
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Store assets fetched by `fetch_asset` in `assets_dir`.
+    pub fn with_assets_dir(mut self, assets_dir: Option<PathBuf>) -> Self {
+        self.assets_dir = assets_dir;
+        self
+    }
+
+    /// Get the cache directory.
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    /// Start (or resume) downloading `url` into the cache.
+    ///
+    /// `on_event(event_name, download)` is called for throttled progress
+    /// updates and once when the download finishes.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - HTTP(S) URL
+    /// * `file_name` - Cache file name (defaults to the last URL segment)
+    /// * `sha256` - Expected checksum (lowercase or uppercase hex)
+    /// * `on_event` - Event callback
+    pub fn start<F>(
+        &self,
+        url: &str,
+        file_name: Option<&str>,
+        sha256: Option<&str>,
+        on_event: F,
+    ) -> Result<Download, String>
+    where
+        F: Fn(String, &Download) + Send + Sync + 'static,
+    {
+        let cache_dir = self
+            .cache_dir
+            .clone()
+            .ok_or_else(|| "No download cache directory available".to_string())?;
+
+        let file_name = match file_name {
+            Some(name) => sanitize_file_name(name)?,
+            None => file_name_from_url(url)
+                .ok_or_else(|| format!("Cannot derive a file name from {url}"))?,
+        };
+
+        let sha256 = sha256.map(normalize_sha256).transpose()?;
+        self.start_at(url, cache_dir.join(file_name), sha256, on_event)
+    }
+
+    /// Download `url` to a path relative to the assets directory, checking
+    /// it against a SHA-256 checksum.
+    ///
+    /// Progress and finished events are the same as for `start`. A
+    /// download that doesn't match the checksum is deleted, including the
+    /// partial file it resumed from.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - HTTP(S) URL
+    /// * `dest` - Relative destination path (e.g. `fonts/inter.woff2`)
+    /// * `sha256` - Expected checksum (lowercase or uppercase hex)
+    /// * `on_event` - Event callback
+    pub fn fetch_asset<F>(
+        &self,
+        url: &str,
+        dest: &str,
+        sha256: &str,
+        on_event: F,
+    ) -> Result<Download, String>
+    where
+        F: Fn(String, &Download) + Send + Sync + 'static,
+    {
+        let assets_dir = self
+            .assets_dir
+            .clone()
+            .ok_or_else(|| "No assets directory available".to_string())?;
+        let dest = assets_dir.join(sanitize_asset_path(dest)?);
+        let sha256 = normalize_sha256(sha256)?;
+        self.start_at(url, dest, Some(sha256), on_event)
+    }
+
+    /// Start (or resume) downloading `url` to `dest`.
+    fn start_at<F>(
+        &self,
+        url: &str,
+        dest: PathBuf,
+        sha256: Option<String>,
+        on_event: F,
+    ) -> Result<Download, String>
+    where
+        F: Fn(String, &Download) + Send + Sync + 'static,
+    {
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            return Err(format!("Unsupported URL: {url}"));
+        }
+        let path = dest.to_string_lossy().to_string();
+
+        // Refuse to run two transfers into the same file
+        if self
+            .list()
+            .iter()
+            .any(|d| d.path == path && !d.status.is_finished())
+        {
+            return Err(format!("{path} is already downloading"));
+        }
+
+        let mut download = Download {
+            id: uuid::Uuid::new_v4().to_string(),
+            url: url.to_string(),
+            path,
+            status: DownloadStatus::Downloading,
+            downloaded_bytes: 0,
+            total_bytes: None,
+            sha256,
+            error: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+        };
+
+        // Already cached (and valid, if a checksum was given)
+        if dest.is_file() {
+            let valid = match download.sha256 {
+                Some(ref expected) => sha256_file(&dest).is_ok_and(|h| &h == expected),
+                None => true,
+            };
+            if valid {
+                let size = fs::metadata(&dest).map(|m| m.len()).unwrap_or(0);
+                download.status = DownloadStatus::Completed;
+                download.downloaded_bytes = size;
+                download.total_bytes = Some(size);
+                download.finished_at = Some(chrono::Utc::now().to_rfc3339());
+                self.insert(download.clone());
+                log::info!("Download already cached: {}", download.path);
+                return Ok(download);
+            }
+        }
+
+        if let Ok(parsed) = reqwest::Url::parse(url) {
+            offline::check_url(&parsed)?;
+        }
+
+        self.insert(download.clone());
+        log::info!("Download started: {} -> {}", download.url, download.path);
+
+        let registry = self.clone();
+        let id = download.id.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let result = registry.transfer(&id, &dest, &on_event).await;
+
+            let finished = registry.update(&id, |dl| {
+                match result {
+                    Ok(()) => dl.status = DownloadStatus::Completed,
+                    Err(e) => {
+                        dl.status = DownloadStatus::Failed;
+                        dl.error = Some(e);
+                    }
+                }
+                dl.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            });
+
+            registry.tasks.lock().unwrap().remove(&id);
+
+            if let Some(dl) = finished {
+                log::info!("Download {}: {:?}", dl.id, dl.status);
+                on_event(dl.finished_event(), &dl);
+            }
+        });
+
+        // Only track the task if it has not already finished
+        let mut tasks = self.tasks.lock().unwrap();
+        if self.get(&download.id).is_some_and(|d| !d.status.is_finished()) {
+            tasks.insert(download.id.clone(), handle);
+        }
+
+        Ok(download)
+    }
+
+    /// Transfer the file, resuming from a partial download if present.
+    async fn transfer<F>(&self, id: &str, dest: &Path, on_event: &F) -> Result<(), String>
+    where
+        F: Fn(String, &Download),
+    {
+        let url = self.get(id).map(|d| d.url).unwrap_or_default();
+        let partial = partial_path(dest);
+
+        if let Some(parent) = dest.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+        }
+
+        let existing = tokio::fs::metadata(&partial).await.map(|m| m.len()).unwrap_or(0);
+
+        let mut request = self.client.get(&url);
+        if existing > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={existing}-"));
+        }
+
+        let mut response = request
+            .send()
+            .await
+            .map_err(|e| format!("Request failed: {e}"))?;
+
+        let status = response.status();
+        let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT;
+
+        if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && existing > 0 {
+            // Partial file already holds the whole body
+            log::debug!("Download {id}: partial file already complete");
+        } else {
+            if !status.is_success() {
+                return Err(format!("HTTP {status} for {url}"));
+            }
+
+            let offset = if resumed { existing } else { 0 };
+            let total = response.content_length().map(|len| len + offset);
+            log::debug!("Download {id}: resumed={resumed} offset={offset} total={total:?}");
+
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(resumed)
+                .truncate(!resumed)
+                .open(&partial)
+                .await
+                .map_err(|e| format!("Failed to open {partial:?}: {e}"))?;
+
+            let mut downloaded = offset;
+            let mut last_event = Instant::now();
+            self.update(id, |dl| {
+                dl.downloaded_bytes = downloaded;
+                dl.total_bytes = total;
+            });
+
+            while let Some(chunk) = response
+                .chunk()
+                .await
+                .map_err(|e| format!("Transfer interrupted: {e}"))?
+            {
+                file.write_all(&chunk)
+                    .await
+                    .map_err(|e| format!("Failed to write {partial:?}: {e}"))?;
+                downloaded += chunk.len() as u64;
+
+                if last_event.elapsed() >= PROGRESS_EVENT_INTERVAL {
+                    last_event = Instant::now();
+                    if let Some(dl) = self.update(id, |dl| dl.downloaded_bytes = downloaded) {
+                        on_event(dl.progress_event(), &dl);
+                    }
+                }
+            }
+
+            file.flush()
+                .await
+                .map_err(|e| format!("Failed to flush {partial:?}: {e}"))?;
+            self.update(id, |dl| dl.downloaded_bytes = downloaded);
+        }
+
+        // Verify the checksum before exposing the file
+        if let Some(expected) = self.get(id).and_then(|d| d.sha256) {
+            self.update(id, |dl| dl.status = DownloadStatus::Verifying);
+
+            let to_hash = partial.clone();
+            let actual = tauri::async_runtime::spawn_blocking(move || sha256_file(&to_hash))
+                .await
+                .map_err(|e| format!("Verification task failed: {e}"))??;
+
+            if actual != expected {
+                let _ = tokio::fs::remove_file(&partial).await;
+                return Err(format!(
+                    "Checksum mismatch (expected {expected}, got {actual})"
+                ));
+            }
+        }
+
+        tokio::fs::rename(&partial, dest)
+            .await
+            .map_err(|e| format!("Failed to move {partial:?} into place: {e}"))
+    }
+
+    /// Get a download by id.
+    pub fn get(&self, id: &str) -> Option<Download> {
+        self.downloads.read().unwrap().get(id).cloned()
+    }
+
+    /// List all downloads (most recent first).
+    pub fn list(&self) -> Vec<Download> {
+        let mut downloads: Vec<Download> =
+            self.downloads.read().unwrap().values().cloned().collect();
+        downloads.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        downloads
+    }
+
+    /// Cancel a running download, keeping the partial file for resume.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Download)` - The cancelled download
+    /// * `Err(String)` - Unknown download or already finished
+    pub fn cancel(&self, id: &str) -> Result<Download, String> {
+        let download = self.get(id).ok_or_else(|| format!("Unknown download: {id}"))?;
+        if download.status.is_finished() {
+            return Err(format!("Download {id} already finished ({:?})", download.status));
+        }
+
+        if let Some(handle) = self.tasks.lock().unwrap().remove(id) {
+            handle.abort();
+        }
+
+        log::info!("Download cancelled: {id}");
+        self.update(id, |dl| {
+            dl.status = DownloadStatus::Cancelled;
+            dl.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        })
+        .ok_or_else(|| format!("Unknown download: {id}"))
+    }
+
+    /// Register a download.
+    fn insert(&self, download: Download) {
+        self.downloads
+            .write()
+            .unwrap()
+            .insert(download.id.clone(), download);
+    }
+
+    /// Apply an edit to an unfinished download, returning the updated copy.
+    fn update<F>(&self, id: &str, edit: F) -> Option<Download>
+    where
+        F: FnOnce(&mut Download),
+    {
+        let mut downloads = self.downloads.write().unwrap();
+        let download = downloads.get_mut(id)?;
+        if download.status.is_finished() {
+            return None;
+        }
+        edit(download);
+        Some(download.clone())
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_file_names() {
+        assert_eq!(sanitize_file_name("model.bin").unwrap(), "model.bin");
+        assert!(sanitize_file_name("../etc/passwd").is_err());
+        assert!(sanitize_file_name("a\\b").is_err());
+        assert!(sanitize_file_name("model.bin.part").is_err());
+
+        assert_eq!(
+            file_name_from_url("https://host/models/kokoro.onnx?download=1").as_deref(),
+            Some("kokoro.onnx")
+        );
+        assert!(file_name_from_url("https://host/").is_none());
+    }
+
+    #[test]
+    fn test_asset_paths() {
+        assert_eq!(
+            sanitize_asset_path("fonts/inter.woff2").unwrap(),
+            PathBuf::from("fonts/inter.woff2")
+        );
+        assert!(sanitize_asset_path("../secrets/.env").is_err());
+        assert!(sanitize_asset_path("fonts/../../x").is_err());
+        assert!(sanitize_asset_path("/etc/passwd").is_err());
+        assert!(sanitize_asset_path("./inter.woff2").is_err());
+        assert!(sanitize_asset_path("inter.woff2.part").is_err());
+        assert!(sanitize_asset_path(" ").is_err());
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let dir = temp_dir("downloads");
+        let path = dir.join("hello.txt");
+        fs::write(&path, b"hello").unwrap();
+
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+    }
+
+    #[test]
+    fn test_cached_file_completes_without_network() {
+        let dir = temp_dir("downloads");
+        fs::write(dir.join("hello.txt"), b"hello").unwrap();
+        let downloads = DownloadManager::new(Some(dir));
+
+        let dl = downloads
+            .start(
+                "https://example.invalid/hello.txt",
+                None,
+                Some("2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824"),
+                |_, _| {},
+            )
+            .unwrap();
+
+        assert_eq!(dl.status, DownloadStatus::Completed);
+        assert_eq!(dl.downloaded_bytes, 5);
+        assert!(downloads.cancel(&dl.id).is_err());
+    }
+
+    #[test]
+    fn test_start_rejects_bad_input() {
+        let downloads = DownloadManager::new(Some(temp_dir("downloads")));
+
+        assert!(downloads.start("ftp://host/file", None, None, |_, _| {}).is_err());
+        assert!(downloads
+            .start("https://host/file", None, Some("abc"), |_, _| {})
+            .is_err());
+        assert!(DownloadManager::new(None)
+            .start("https://host/file", None, None, |_, _| {})
+            .is_err());
+    }
+
+    #[test]
+    fn test_fetch_asset() {
+        let dir = temp_dir("downloads");
+        fs::create_dir_all(dir.join("img")).unwrap();
+        fs::write(dir.join("img").join("hello.txt"), b"hello").unwrap();
+        let downloads = DownloadManager::new(None).with_assets_dir(Some(dir));
+        let hash = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+        let dl = downloads
+            .fetch_asset(
+                "https://example.invalid/hello.txt",
+                "img/hello.txt",
+                hash,
+                |_, _| {},
+            )
+            .unwrap();
+        assert_eq!(dl.status, DownloadStatus::Completed);
+
+        assert!(downloads
+            .fetch_asset("https://host/x", "../x", hash, |_, _| {})
+            .is_err());
+        assert!(downloads
+            .fetch_asset("https://host/x", "x", "abc", |_, _| {})
+            .is_err());
+        assert!(DownloadManager::new(None)
+            .fetch_asset("https://host/x", "x", hash, |_, _| {})
+            .is_err());
+    }
+}
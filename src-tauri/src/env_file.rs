@@ -0,0 +1,309 @@
+//! src-tauri/src/env_file.rs
+//! =========================
+//! Locked, atomic writes of the project `.env` with timestamped backups.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Key commands rewrite the whole `.env`. Each write holds an exclusive
+//! lock on `.env.lock` (so concurrent commands, or two app windows, never
+//! interleave) while it reads the current content and renders the new one
+//! from it (so no command drops another's change), copies the current file
+//! into `.env.backups/`, writes the new content to a temporary file in the
+//! same directory, and renames it over `.env`. A crash mid-write leaves
+//! either the old or the new file, never a truncated one.
+//!
+//! The newest `MAX_BACKUPS` backups are kept. Backups hold the same
+//! (vault-encrypted) values as the `.env` itself.
+//!
+//! Usage:
+//!     ```rust
+//!     env_file::write(&env_path, |current| render(current, &env_vars))?;
+//!     env_file::update(&env_path, |current| current.map(|c| c.replace("A=1", "A=2")))?;
+//!     let backups = env_file::list_backups(&env_path);
+//!     env_file::restore_backup(&env_path, &backups[0].id)?;
+//!     ```
+
+use fs2::FileExt;
+use serde::Serialize;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Number of backups kept per `.env`
+pub const MAX_BACKUPS: usize = 10;
+
+/// Directory (next to the `.env`) holding backups
+const BACKUP_DIR: &str = ".env.backups";
+
+/// Extension of backup files
+const BACKUP_EXTENSION: &str = "bak";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A timestamped `.env` backup.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvBackup {
+    /// Backup id (file name inside the backup directory)
+    pub id: String,
+    /// When the backup was taken (RFC 3339)
+    pub created_at: String,
+    /// File size in bytes
+    pub size: u64,
+}
+
+/// Exclusive lock on a `.env`, released on drop.
+struct EnvLock {
+    file: File,
+}
+
+impl Drop for EnvLock {
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Sibling path of `path` with `suffix` appended to its file name.
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| ".env".to_string());
+    path.with_file_name(format!("{name}{suffix}"))
+}
+
+/// Backup directory for `path`.
+fn backup_dir(path: &Path) -> PathBuf {
+    path.with_file_name(BACKUP_DIR)
+}
+
+/// Take the exclusive lock for `path`, waiting for other writers.
+fn lock(path: &Path) -> Result<EnvLock, String> {
+    let lock_path = sibling(path, ".lock");
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| format!("Failed to open {lock_path:?}: {e}"))?;
+    file.lock_exclusive()
+        .map_err(|e| format!("Failed to lock {lock_path:?}: {e}"))?;
+    Ok(EnvLock { file })
+}
+
+/// Write `content` to `tmp_path`, flush it to disk, and rename it over `path`.
+fn write_and_rename(tmp_path: &Path, path: &Path, content: &str) -> std::io::Result<()> {
+    let mut file = File::create(tmp_path)?;
+    // Keep the permissions of the file being replaced (e.g. 0600)
+    if let Ok(metadata) = fs::metadata(path) {
+        fs::set_permissions(tmp_path, metadata.permissions())?;
+    }
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?;
+    fs::rename(tmp_path, path)
+}
+
+/// Replace `path` atomically with `content`.
+fn replace(path: &Path, content: &str) -> Result<(), String> {
+    let tmp_path = sibling(path, &format!(".tmp-{}", uuid::Uuid::new_v4()));
+    write_and_rename(&tmp_path, path, content).map_err(|e| {
+        let _ = fs::remove_file(&tmp_path);
+        format!("Failed to write {path:?}: {e}")
+    })
+}
+
+/// Copy the current file into the backup directory and prune old backups.
+fn backup(path: &Path) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let dir = backup_dir(path);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {dir:?}: {e}"))?;
+
+    // Timestamps sort lexically; the suffix keeps same-instant writes apart
+    let stamp = chrono::Utc::now().format("%Y%m%dT%H%M%S%.6fZ");
+    let suffix = &uuid::Uuid::new_v4().simple().to_string()[..6];
+    let target = dir.join(format!("{stamp}-{suffix}.{BACKUP_EXTENSION}"));
+    fs::copy(path, &target).map_err(|e| format!("Failed to back up {path:?}: {e}"))?;
+
+    for stale in list_backups(path).iter().skip(MAX_BACKUPS) {
+        if let Err(e) = fs::remove_file(dir.join(&stale.id)) {
+            log::warn!("Failed to remove old .env backup {}: {e}", stale.id);
+        }
+    }
+    Ok(())
+}
+
+// ============================================
+// PUBLIC API
+// ============================================
+
+/// Rewrite `path` under the lock.
+///
+/// `render` receives the current content (`None` if the file does not
+/// exist yet) read while the lock is held, and returns the new content.
+/// Missing parent directories are created.
+pub fn write<F>(path: &Path, render: F) -> Result<(), String>
+where
+    F: FnOnce(Option<&str>) -> String,
+{
+    update(path, |current| Some(render(current)))
+}
+
+/// Rewrite `path` under the lock, unless `render` returns `None`.
+///
+/// Like `write`, but `render` can leave the file as it is, for example
+/// when the change it was asked to make turns out to be invalid.
+pub fn update<F>(path: &Path, render: F) -> Result<(), String>
+where
+    F: FnOnce(Option<&str>) -> Option<String>,
+{
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let _lock = lock(path)?;
+
+    let current = fs::read_to_string(path).ok();
+    let Some(content) = render(current.as_deref()) else {
+        return Ok(());
+    };
+    if current.as_deref() == Some(content.as_str()) {
+        return Ok(());
+    }
+
+    backup(path)?;
+    replace(path, &content)
+}
+
+/// List the backups of `path` (most recent first).
+pub fn list_backups(path: &Path) -> Vec<EnvBackup> {
+    let Ok(entries) = fs::read_dir(backup_dir(path)) else {
+        return Vec::new();
+    };
+
+    let mut backups: Vec<EnvBackup> = entries
+        .flatten()
+        .filter(|entry| entry.path().extension().and_then(|e| e.to_str()) == Some(BACKUP_EXTENSION))
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let created_at = metadata
+                .modified()
+                .map(|t| chrono::DateTime::<chrono::Utc>::from(t).to_rfc3339())
+                .unwrap_or_default();
+            Some(EnvBackup {
+                id: entry.file_name().to_string_lossy().into_owned(),
+                created_at,
+                size: metadata.len(),
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.id.cmp(&a.id));
+    backups
+}
+
+/// Replace `path` with one of its backups.
+///
+/// The current content is backed up first, so a restore can be undone.
+pub fn restore_backup(path: &Path, id: &str) -> Result<(), String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+        return Err(format!("Invalid backup id: {id}"));
+    }
+    let source = backup_dir(path).join(id);
+    let content = fs::read_to_string(&source).map_err(|_| format!("Backup not found: {id}"))?;
+
+    write(path, |_| content)
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn temp_env() -> PathBuf {
+        temp_dir("env").join(".env")
+    }
+
+    #[test]
+    fn test_write_backs_up_and_prunes() {
+        let path = temp_env();
+        write(&path, |current| {
+            assert!(current.is_none());
+            "A=1\n".to_string()
+        })
+        .unwrap();
+        assert!(list_backups(&path).is_empty());
+
+        for i in 0..MAX_BACKUPS + 3 {
+            write(&path, |_| format!("A={i}\n")).unwrap();
+        }
+        let backups = list_backups(&path);
+        assert_eq!(backups.len(), MAX_BACKUPS);
+
+        // Most recent backup holds the content before the last write
+        let latest = fs::read_to_string(backup_dir(&path).join(&backups[0].id)).unwrap();
+        assert_eq!(latest, format!("A={}\n", MAX_BACKUPS + 1));
+    }
+
+    #[test]
+    fn test_restore_backup() {
+        let path = temp_env();
+        write(&path, |_| "A=1\n".to_string()).unwrap();
+        write(&path, |_| "A=2\n".to_string()).unwrap();
+
+        let id = list_backups(&path)[0].id.clone();
+        restore_backup(&path, &id).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A=1\n");
+
+        assert!(restore_backup(&path, "../.env").is_err());
+        assert!(restore_backup(&path, "missing.bak").is_err());
+    }
+
+    #[test]
+    fn test_concurrent_writes_do_not_interleave() {
+        let path = temp_env();
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    write(&path, |current| {
+                        format!("{}K{i}=v\n", current.unwrap_or_default())
+                    })
+                    .unwrap();
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert_eq!(content.lines().count(), 8);
+    }
+
+    #[test]
+    fn test_update_can_decline() {
+        let path = temp_env();
+        write(&path, |_| "A=1\n".to_string()).unwrap();
+        update(&path, |current| {
+            assert_eq!(current, Some("A=1\n"));
+            None
+        })
+        .unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "A=1\n");
+        assert!(list_backups(&path).is_empty());
+    }
+}
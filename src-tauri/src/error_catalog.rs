@@ -0,0 +1,331 @@
+//! src-tauri/src/error_catalog.rs
+//! ==============================
+//! Error catalog from D009 (`config/error_codes.yaml`).
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The YAML file is embedded at build time and parsed on first use, so the
+//! catalog always matches the docs. Entries are found by name (`QUEUE_FULL`,
+//! `PLUGIN_NOT_FOUND`) or, for `RPC_ERROR_<code>` command errors, by their
+//! JSON-RPC code. Command errors (see `crate::commands::CommandError`)
+//! are serialized with the `category`, `retryable` flag, and `docs_url` of
+//! their entry, and `error_catalog` returns every entry to the frontend.
+//!
+//! Every error falls in one of four categories, so the frontend can react
+//! without knowing each code:
+//!
+//!     transport   talking to the plugin host failed (timeouts, crashes)
+//!     plugin      the plugin host or a plugin reported the error
+//!     user        the request or the configuration needs fixing
+//!     internal    a bug or an environment problem in the app itself
+//!
+//! An entry may set its category. Otherwise JSON-RPC errors are `plugin`,
+//! and command errors are classified by name (`category_for_code`):
+//! `INVALID_*`, `UNKNOWN_*`, `*_NOT_FOUND`, `*_EXISTS`, `*_TOO_LARGE`,
+//! `*_NOT_ALLOWED`, and `PERMISSION_DENIED` are `user`, `RPC_ERROR_*` is
+//! `plugin`, anything else `internal`. Codes without an entry are not
+//! retryable.
+//!
+//! Usage:
+//!     ```rust
+//!     let entry = catalog().lookup("RPC_ERROR_-32060");
+//!     let retryable = entry.is_some_and(|entry| entry.retryable);
+//!     let category = catalog().category("WORKSPACE_NOT_FOUND"); // User
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// D009, embedded at build time
+const ERROR_CODES_YAML: &str = include_str!("../../config/error_codes.yaml");
+
+/// Prefix of command error codes carrying a JSON-RPC code
+const RPC_ERROR_PREFIX: &str = "RPC_ERROR_";
+
+/// Name prefixes of `user` command errors
+const USER_PREFIXES: [&str; 2] = ["INVALID_", "UNKNOWN_"];
+
+/// Name suffixes of `user` command errors
+const USER_SUFFIXES: [&str; 4] = ["_NOT_FOUND", "_EXISTS", "_TOO_LARGE", "_NOT_ALLOWED"];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// What an error is about, for generic handling in the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorCategory {
+    /// Talking to the plugin host failed
+    Transport,
+    /// The plugin host or a plugin reported the error
+    Plugin,
+    /// The request or the configuration needs fixing
+    User,
+    /// A bug or an environment problem in the app
+    Internal,
+}
+
+/// One catalog entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ErrorEntry {
+    /// Error name (`PLUGIN_NOT_FOUND`)
+    pub name: String,
+    /// Catalog section (`standard`, `server`, `application`, or `command`)
+    pub section: String,
+    /// Error category
+    pub category: ErrorCategory,
+    /// JSON-RPC code (None for command errors)
+    pub code: Option<i32>,
+    /// Short message
+    pub message: String,
+    /// What went wrong
+    pub description: Option<String>,
+    /// What the user can do about it
+    pub action: Option<String>,
+    /// Contract the error belongs to (application errors)
+    pub contract: Option<String>,
+    /// Whether the same call may succeed when retried
+    pub retryable: bool,
+    /// Page documenting the error
+    pub docs_url: Option<String>,
+}
+
+/// An entry as written in the YAML file.
+#[derive(Debug, Deserialize)]
+struct RawEntry {
+    code: Option<i32>,
+    message: String,
+    description: Option<String>,
+    action: Option<String>,
+    contract: Option<String>,
+    category: Option<ErrorCategory>,
+    #[serde(default)]
+    retryable: bool,
+    docs: Option<String>,
+}
+
+/// The sections of the YAML file (other keys are ignored).
+#[derive(Debug, Deserialize)]
+struct RawCatalog {
+    #[serde(default)]
+    docs_base_url: String,
+    #[serde(default)]
+    standard_errors: BTreeMap<String, RawEntry>,
+    #[serde(default)]
+    server_errors: BTreeMap<String, RawEntry>,
+    #[serde(default)]
+    application_errors: BTreeMap<String, RawEntry>,
+    #[serde(default)]
+    command_errors: BTreeMap<String, RawEntry>,
+}
+
+// ============================================
+// ERROR CATALOG
+// ============================================
+
+/// Error entries by name.
+#[derive(Debug, Clone, Default)]
+pub struct ErrorCatalog {
+    entries: BTreeMap<String, ErrorEntry>,
+}
+
+impl ErrorCatalog {
+    /// Parse a catalog in the format of `config/error_codes.yaml`.
+    pub fn parse(yaml: &str) -> Result<Self, String> {
+        let raw: RawCatalog =
+            serde_yaml::from_str(yaml).map_err(|e| format!("Invalid error catalog: {e}"))?;
+
+        let mut entries = BTreeMap::new();
+        for (section_name, section) in [
+            ("standard", raw.standard_errors),
+            ("server", raw.server_errors),
+            ("application", raw.application_errors),
+            ("command", raw.command_errors),
+        ] {
+            for (name, entry) in section {
+                let docs_url = entry
+                    .docs
+                    .map(|docs| format!("{}{docs}", raw.docs_base_url));
+                let category = entry.category.unwrap_or_else(|| match section_name {
+                    "command" => category_for_code(&name),
+                    _ => ErrorCategory::Plugin,
+                });
+                entries.insert(
+                    name.clone(),
+                    ErrorEntry {
+                        name,
+                        section: section_name.to_string(),
+                        category,
+                        code: entry.code,
+                        message: entry.message,
+                        description: entry.description,
+                        action: entry.action,
+                        contract: entry.contract,
+                        retryable: entry.retryable,
+                        docs_url,
+                    },
+                );
+            }
+        }
+        Ok(Self { entries })
+    }
+
+    /// Entry for a command error code: by name, or by JSON-RPC code for
+    /// `RPC_ERROR_<code>`.
+    pub fn lookup(&self, code: &str) -> Option<&ErrorEntry> {
+        if let Some(entry) = self.entries.get(code) {
+            return Some(entry);
+        }
+        let rpc_code = code.strip_prefix(RPC_ERROR_PREFIX)?.parse::<i32>().ok()?;
+        self.entries
+            .values()
+            .find(|entry| entry.code == Some(rpc_code))
+    }
+
+    /// Category of a command error code: from its entry, or by name.
+    pub fn category(&self, code: &str) -> ErrorCategory {
+        self.lookup(code)
+            .map_or_else(|| category_for_code(code), |entry| entry.category)
+    }
+
+    /// Whether a command error code may succeed when retried.
+    pub fn retryable(&self, code: &str) -> bool {
+        self.lookup(code).is_some_and(|entry| entry.retryable)
+    }
+
+    /// All entries, sorted by name.
+    pub fn entries(&self) -> Vec<ErrorEntry> {
+        self.entries.values().cloned().collect()
+    }
+}
+
+/// Category of a command error code without a catalog entry.
+pub fn category_for_code(code: &str) -> ErrorCategory {
+    if code.starts_with(RPC_ERROR_PREFIX) {
+        ErrorCategory::Plugin
+    } else if code == "PERMISSION_DENIED"
+        || USER_PREFIXES.iter().any(|prefix| code.starts_with(prefix))
+        || USER_SUFFIXES.iter().any(|suffix| code.ends_with(suffix))
+    {
+        ErrorCategory::User
+    } else {
+        ErrorCategory::Internal
+    }
+}
+
+/// The catalog embedded from `config/error_codes.yaml`.
+///
+/// Parsed once; an invalid file is logged and yields an empty catalog.
+pub fn catalog() -> &'static ErrorCatalog {
+    static CATALOG: OnceLock<ErrorCatalog> = OnceLock::new();
+    CATALOG.get_or_init(|| {
+        ErrorCatalog::parse(ERROR_CODES_YAML).unwrap_or_else(|e| {
+            log::error!("{e}");
+            ErrorCatalog::default()
+        })
+    })
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::response::error_codes;
+
+    #[test]
+    fn test_embedded_catalog_parses() {
+        let catalog = ErrorCatalog::parse(ERROR_CODES_YAML).unwrap();
+
+        let timeout = catalog.lookup("RPC_ERROR_-32060").unwrap();
+        assert_eq!(timeout.name, "EXECUTION_TIMEOUT");
+        assert!(timeout.retryable);
+        assert!(timeout.docs_url.as_deref().is_some_and(
+            |url| url.starts_with("https://") && url.ends_with("#plugin-method-timeout")
+        ));
+
+        let queue_full = catalog.lookup("QUEUE_FULL").unwrap();
+        assert_eq!(queue_full.section, "command");
+        assert_eq!(queue_full.category, ErrorCategory::Transport);
+        assert_eq!(queue_full.code, None);
+        assert!(queue_full.retryable);
+
+        assert!(!catalog.lookup("PLUGIN_NOT_FOUND").unwrap().retryable);
+        assert!(catalog.lookup("RPC_ERROR_12345").is_none());
+        assert!(catalog.lookup("NO_SUCH_ERROR").is_none());
+    }
+
+    #[test]
+    fn test_categories() {
+        let catalog = ErrorCatalog::parse(ERROR_CODES_YAML).unwrap();
+
+        // Set in the catalog
+        assert_eq!(catalog.category("TIMEOUT"), ErrorCategory::Transport);
+        assert_eq!(catalog.category("RPC_ERROR_-32602"), ErrorCategory::User);
+        assert_eq!(catalog.category("ENV_WRITE_ERROR"), ErrorCategory::Internal);
+        assert_eq!(catalog.category("COMPILE_TIMEOUT"), ErrorCategory::User);
+        // JSON-RPC default
+        assert_eq!(catalog.category("RPC_ERROR_-32000"), ErrorCategory::Plugin);
+        assert_eq!(catalog.category("RPC_ERROR_12345"), ErrorCategory::Plugin);
+        // By name
+        assert_eq!(catalog.category("KEY_NOT_FOUND"), ErrorCategory::User);
+        assert_eq!(catalog.category("INVALID_SETTING"), ErrorCategory::User);
+        assert_eq!(catalog.category("PROFILE_EXISTS"), ErrorCategory::User);
+        assert_eq!(catalog.category("STORAGE_ERROR"), ErrorCategory::Internal);
+
+        assert!(catalog.retryable("SECRET_BACKEND_ERROR"));
+        assert!(!catalog.retryable("KEY_NOT_FOUND"));
+    }
+
+    #[test]
+    fn test_rust_codes_match_catalog() {
+        let catalog = ErrorCatalog::parse(ERROR_CODES_YAML).unwrap();
+        for (name, code) in [
+            ("PARSE_ERROR", error_codes::PARSE_ERROR),
+            ("INVALID_REQUEST", error_codes::INVALID_REQUEST),
+            ("METHOD_NOT_FOUND", error_codes::METHOD_NOT_FOUND),
+            ("INVALID_PARAMS", error_codes::INVALID_PARAMS),
+            ("INTERNAL_ERROR", error_codes::INTERNAL_ERROR),
+            ("PLUGIN_NOT_FOUND", error_codes::PLUGIN_NOT_FOUND),
+            ("PLUGIN_NOT_READY", error_codes::PLUGIN_NOT_READY),
+            ("PLUGIN_LOAD_FAILED", error_codes::PLUGIN_LOAD_FAILED),
+            (
+                "PLUGIN_INITIALIZE_FAILED",
+                error_codes::PLUGIN_INITIALIZE_FAILED,
+            ),
+            (
+                "PLUGIN_SHUTDOWN_FAILED",
+                error_codes::PLUGIN_SHUTDOWN_FAILED,
+            ),
+            ("PLUGIN_ALREADY_LOADED", error_codes::PLUGIN_ALREADY_LOADED),
+            ("CONTRACT_MISMATCH", error_codes::CONTRACT_MISMATCH),
+            ("CONTRACT_NOT_FOUND", error_codes::CONTRACT_NOT_FOUND),
+            ("MANIFEST_INVALID", error_codes::MANIFEST_INVALID),
+            ("MANIFEST_MISSING", error_codes::MANIFEST_MISSING),
+            ("HOTSWAP_FAILED", error_codes::HOTSWAP_FAILED),
+            (
+                "HOTSWAP_ROLLBACK_FAILED",
+                error_codes::HOTSWAP_ROLLBACK_FAILED,
+            ),
+            ("DISCOVERY_FAILED", error_codes::DISCOVERY_FAILED),
+            ("HEALTH_CHECK_TIMEOUT", error_codes::HEALTH_CHECK_TIMEOUT),
+            ("RESOURCE_EXHAUSTED", error_codes::RESOURCE_EXHAUSTED),
+            ("DEPENDENCY_MISSING", error_codes::DEPENDENCY_MISSING),
+            ("MODEL_NOT_FOUND", error_codes::MODEL_NOT_FOUND),
+            ("EXECUTION_TIMEOUT", error_codes::EXECUTION_TIMEOUT),
+            ("PLUGIN_EXCEPTION", error_codes::PLUGIN_EXCEPTION),
+        ] {
+            let entry = catalog.lookup(name).unwrap();
+            assert_eq!(entry.code, Some(code), "{name}");
+            assert_eq!(error_codes::description(code), entry.message, "{name}");
+        }
+    }
+}
@@ -0,0 +1,222 @@
+//! src-tauri/src/error_messages.rs
+//! ===============================
+//! Translated error messages from `config/error_messages.yaml`.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Error codes are stable and English error messages are built in the code.
+//! This catalog holds translations keyed by code and locale, so apps built
+//! with App Factory can show failures in the user's language. Command errors
+//! (see `crate::commands::CommandError`) are serialized with a
+//! `localized_message` in the current locale, which is set with the
+//! `set_locale` command and stored as `ui.locale`.
+//!
+//! Locales are language tags (`de`, `pt-BR`), compared case-insensitively.
+//! A regional locale without its own translation uses its language's, and a
+//! code without a translation keeps the English message. `RPC_ERROR_<code>`
+//! errors are translated under the error catalog name of their JSON-RPC
+//! code (`RPC_ERROR_-32601` as `METHOD_NOT_FOUND`).
+//!
+//! Usage:
+//!     ```rust
+//!     error_messages::set_locale("de-AT")?;
+//!     let message = error_messages::localize("TIMEOUT", "Request timed out after 30 seconds");
+//!     assert_eq!(message, "Das Plugin hat nicht rechtzeitig geantwortet.");
+//!     ```
+
+use std::collections::BTreeMap;
+use std::sync::{OnceLock, RwLock};
+
+use crate::error_catalog::catalog;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Translations, embedded at build time
+const ERROR_MESSAGES_YAML: &str = include_str!("../../config/error_messages.yaml");
+
+/// Locale of the messages built in the code
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Maximum length of a locale tag
+const MAX_LOCALE_LEN: usize = 35;
+
+/// Current locale
+static LOCALE: RwLock<String> = RwLock::new(String::new());
+
+// ============================================
+// MESSAGE CATALOG
+// ============================================
+
+/// Translated messages by error code, then by locale.
+#[derive(Debug, Clone, Default)]
+pub struct MessageCatalog {
+    messages: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl MessageCatalog {
+    /// Parse a catalog in the format of `config/error_messages.yaml`.
+    pub fn parse(yaml: &str) -> Result<Self, String> {
+        let raw: BTreeMap<String, BTreeMap<String, String>> =
+            serde_yaml::from_str(yaml).map_err(|e| format!("Invalid error messages: {e}"))?;
+
+        let mut messages = BTreeMap::new();
+        for (code, translations) in raw {
+            let mut by_locale = BTreeMap::new();
+            for (locale, message) in translations {
+                let locale = normalize_locale(&locale)
+                    .map_err(|e| format!("Invalid error messages for {code}: {e}"))?;
+                by_locale.insert(locale, message);
+            }
+            messages.insert(code, by_locale);
+        }
+        Ok(Self { messages })
+    }
+
+    /// Translation of an error code into a (normalized) locale, if any.
+    pub fn get(&self, code: &str, locale: &str) -> Option<&str> {
+        let translations = self.messages.get(code).or_else(|| {
+            let entry = catalog().lookup(code)?;
+            self.messages.get(&entry.name)
+        })?;
+        translations
+            .get(locale)
+            .or_else(|| translations.get(locale.split('-').next()?))
+            .map(String::as_str)
+    }
+
+    /// Locales with at least one translation, sorted.
+    pub fn locales(&self) -> Vec<String> {
+        let mut locales: Vec<String> = self
+            .messages
+            .values()
+            .flat_map(|translations| translations.keys().cloned())
+            .collect();
+        locales.sort();
+        locales.dedup();
+        locales
+    }
+}
+
+/// The catalog embedded from `config/error_messages.yaml`.
+///
+/// Parsed once; an invalid file is logged and yields an empty catalog.
+pub fn messages() -> &'static MessageCatalog {
+    static MESSAGES: OnceLock<MessageCatalog> = OnceLock::new();
+    MESSAGES.get_or_init(|| {
+        MessageCatalog::parse(ERROR_MESSAGES_YAML).unwrap_or_else(|e| {
+            log::error!("{e}");
+            MessageCatalog::default()
+        })
+    })
+}
+
+// ============================================
+// LOCALE
+// ============================================
+
+/// Normalize a locale tag: lowercase, `-` separated (`pt_BR` -> `pt-br`).
+pub fn normalize_locale(locale: &str) -> Result<String, String> {
+    let normalized = locale.trim().replace('_', "-").to_ascii_lowercase();
+    let valid = !normalized.is_empty()
+        && normalized.len() <= MAX_LOCALE_LEN
+        && normalized
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()));
+    if valid {
+        Ok(normalized)
+    } else {
+        Err(format!(
+            "Invalid locale {locale:?} (expected a tag like \"de\" or \"pt-BR\")"
+        ))
+    }
+}
+
+/// Set the locale of error messages.
+///
+/// # Returns
+///
+/// The normalized locale.
+pub fn set_locale(locale: &str) -> Result<String, String> {
+    let locale = normalize_locale(locale)?;
+    *LOCALE.write().unwrap() = locale.clone();
+    Ok(locale)
+}
+
+/// The locale of error messages.
+pub fn locale() -> String {
+    let locale = LOCALE.read().unwrap();
+    if locale.is_empty() {
+        DEFAULT_LOCALE.to_string()
+    } else {
+        locale.clone()
+    }
+}
+
+/// `message` for `code` in the current locale: the translation if there is
+/// one, `message` otherwise.
+pub fn localize(code: &str, message: &str) -> String {
+    let locale = locale();
+    if locale == DEFAULT_LOCALE {
+        return message.to_string();
+    }
+    messages()
+        .get(code, &locale)
+        .map_or_else(|| message.to_string(), str::to_string)
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_messages_parse() {
+        let messages = MessageCatalog::parse(ERROR_MESSAGES_YAML).unwrap();
+        assert!(messages.locales().contains(&"de".to_string()));
+        assert!(!messages.locales().contains(&DEFAULT_LOCALE.to_string()));
+        assert_eq!(
+            messages.get("RPC_ERROR_-32601", "fr"),
+            Some("Le plugin ne prend pas en charge cette fonction.")
+        );
+    }
+
+    #[test]
+    fn test_lookup() {
+        let messages = MessageCatalog::parse(
+            "
+TIMEOUT:
+  de: Zeitüberschreitung
+  pt-BR: Tempo esgotado
+METHOD_NOT_FOUND:
+  de: Unbekannt
+",
+        )
+        .unwrap();
+
+        assert_eq!(messages.get("TIMEOUT", "de"), Some("Zeitüberschreitung"));
+        assert_eq!(messages.get("TIMEOUT", "de-at"), Some("Zeitüberschreitung"));
+        assert_eq!(messages.get("TIMEOUT", "pt-br"), Some("Tempo esgotado"));
+        assert_eq!(messages.get("TIMEOUT", "pt"), None);
+        assert_eq!(messages.get("TIMEOUT", "fr"), None);
+        // By JSON-RPC code
+        assert_eq!(messages.get("RPC_ERROR_-32601", "de"), Some("Unbekannt"));
+        assert_eq!(messages.get("QUEUE_FULL", "de"), None);
+        assert_eq!(messages.locales(), ["de", "pt-br"]);
+    }
+
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(normalize_locale("de").unwrap(), "de");
+        assert_eq!(normalize_locale(" pt_BR ").unwrap(), "pt-br");
+        assert_eq!(normalize_locale("zh-Hant-TW").unwrap(), "zh-hant-tw");
+        assert!(normalize_locale("").is_err());
+        assert!(normalize_locale("de-").is_err());
+        assert!(normalize_locale("../etc").is_err());
+        assert!(normalize_locale(&"a".repeat(MAX_LOCALE_LEN + 1)).is_err());
+    }
+}
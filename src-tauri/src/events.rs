@@ -0,0 +1,244 @@
+//! src-tauri/src/events.rs
+//! =======================
+//! Topic-based subscriptions to plugin host events.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Plugins send events as JSON-RPC notifications whose method is the topic
+//! (`stt/partial`, `tts/segment`, ...). The webview subscribes to the topics
+//! it wants; the core host is told which topics have listeners (see
+//! `IpcManagerState::set_event_topics`) so plugins can skip the rest, and
+//! matching notifications are emitted to the webview as `events/<topic>`
+//! with the notification params as payload.
+//!
+//! Listeners are counted per topic, so several components can subscribe to
+//! the same topic and each unsubscribes once. Delivery stats are kept for
+//! every topic that was subscribed to since startup.
+//!
+//! Usage:
+//!     ```rust
+//!     let subscriptions = EventSubscriptions::new();
+//!     if subscriptions.add_listener("stt/partial")? {
+//!         state.set_event_topics(subscriptions.topics()).await?;
+//!     }
+//!     tauri::async_runtime::spawn(subscriptions.forward(app.handle(), state));
+//!     ```
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::ipc::manager::IpcManagerState;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Prefix of the Tauri events carrying topic notifications
+pub const EVENT_PREFIX: &str = "events/";
+
+/// Maximum topic length
+const MAX_TOPIC_LEN: usize = 128;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Listeners and delivery stats of one topic.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TopicStats {
+    /// Topic (notification method)
+    pub topic: String,
+    /// Current subscriptions
+    pub listeners: u32,
+    /// Notifications emitted to the webview
+    pub delivered: u64,
+    /// Notifications that failed to emit
+    pub failed: u64,
+    /// When the last notification was emitted (RFC 3339)
+    pub last_delivered_at: Option<String>,
+}
+
+/// Tauri event name for a topic.
+pub fn event_name(topic: &str) -> String {
+    format!("{EVENT_PREFIX}{topic}")
+}
+
+/// Check that a topic can be used as a Tauri event name.
+///
+/// Topics are made of letters, digits, `-`, `_`, `/`, and `:`; `progress/`
+/// is reserved for request progress.
+pub fn validate_topic(topic: &str) -> Result<(), String> {
+    if topic.is_empty() || topic.len() > MAX_TOPIC_LEN {
+        return Err(format!("Topic must be 1-{MAX_TOPIC_LEN} characters"));
+    }
+    if !topic
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '/' | ':'))
+    {
+        return Err(format!(
+            "Invalid topic {topic:?}: use letters, digits, '-', '_', '/', ':'"
+        ));
+    }
+    if topic.starts_with("progress/") {
+        return Err(format!("Topic {topic:?} is reserved for request progress"));
+    }
+    Ok(())
+}
+
+// ============================================
+// EVENT SUBSCRIPTIONS
+// ============================================
+
+/// Topic subscriptions of the webview.
+///
+/// Cloning shares the subscriptions.
+#[derive(Clone, Default)]
+pub struct EventSubscriptions {
+    /// Stats by topic (topics without listeners are kept for their stats)
+    topics: Arc<Mutex<BTreeMap<String, TopicStats>>>,
+}
+
+impl EventSubscriptions {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a listener to a topic.
+    ///
+    /// Returns true if it is the topic's first listener (the host's topic
+    /// list changed).
+    pub fn add_listener(&self, topic: &str) -> Result<bool, String> {
+        validate_topic(topic)?;
+        let mut topics = self.topics.lock().unwrap();
+        let stats = topics
+            .entry(topic.to_string())
+            .or_insert_with(|| TopicStats {
+                topic: topic.to_string(),
+                ..TopicStats::default()
+            });
+        stats.listeners += 1;
+        Ok(stats.listeners == 1)
+    }
+
+    /// Remove a listener from a topic.
+    ///
+    /// Returns true if it was the topic's last listener.
+    pub fn remove_listener(&self, topic: &str) -> Result<bool, String> {
+        let mut topics = self.topics.lock().unwrap();
+        let Some(stats) = topics.get_mut(topic).filter(|stats| stats.listeners > 0) else {
+            return Err(format!("No subscription to topic {topic}"));
+        };
+        stats.listeners -= 1;
+        Ok(stats.listeners == 0)
+    }
+
+    /// Topics that have listeners.
+    pub fn topics(&self) -> Vec<String> {
+        self.topics
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|stats| stats.listeners > 0)
+            .map(|stats| stats.topic.clone())
+            .collect()
+    }
+
+    /// Stats of every topic subscribed to since startup.
+    pub fn stats(&self) -> Vec<TopicStats> {
+        self.topics.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Whether a notification method is a topic with listeners.
+    fn is_subscribed(&self, topic: &str) -> bool {
+        self.topics
+            .lock()
+            .unwrap()
+            .get(topic)
+            .is_some_and(|stats| stats.listeners > 0)
+    }
+
+    /// Count a delivery attempt.
+    fn record_delivery(&self, topic: &str, delivered: bool) {
+        if let Some(stats) = self.topics.lock().unwrap().get_mut(topic) {
+            if delivered {
+                stats.delivered += 1;
+                stats.last_delivered_at = Some(chrono::Utc::now().to_rfc3339());
+            } else {
+                stats.failed += 1;
+            }
+        }
+    }
+
+    /// Emit the manager's notifications for subscribed topics until the
+    /// channel closes.
+    pub async fn forward(self, app: AppHandle, state: IpcManagerState) {
+        let mut notifications = state.subscribe();
+        loop {
+            let notification = match notifications.recv().await {
+                Ok(notification) => notification,
+                Err(RecvError::Lagged(skipped)) => {
+                    log::warn!("Event forwarding missed {skipped} notifications");
+                    continue;
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let topic = notification.method;
+            if !self.is_subscribed(&topic) {
+                continue;
+            }
+            let result = app.emit_all(&event_name(&topic), &notification.params);
+            if let Err(ref e) = result {
+                log::warn!("Failed to emit event for topic {topic}: {e}");
+            }
+            self.record_delivery(&topic, result.is_ok());
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_topic() {
+        assert!(validate_topic("stt/partial").is_ok());
+        assert!(validate_topic("tts:segment_2").is_ok());
+        assert!(validate_topic("").is_err());
+        assert!(validate_topic("stt.partial").is_err());
+        assert!(validate_topic("progress/42").is_err());
+        assert!(validate_topic(&"a".repeat(MAX_TOPIC_LEN + 1)).is_err());
+        assert_eq!(event_name("stt/partial"), "events/stt/partial");
+    }
+
+    #[test]
+    fn test_listener_counts_and_stats() {
+        let subscriptions = EventSubscriptions::new();
+        assert_eq!(subscriptions.add_listener("stt/partial"), Ok(true));
+        assert_eq!(subscriptions.add_listener("stt/partial"), Ok(false));
+        assert_eq!(subscriptions.add_listener("tts/segment"), Ok(true));
+        assert_eq!(subscriptions.topics(), vec!["stt/partial", "tts/segment"]);
+
+        subscriptions.record_delivery("stt/partial", true);
+        subscriptions.record_delivery("stt/partial", false);
+        assert_eq!(subscriptions.remove_listener("stt/partial"), Ok(false));
+        assert_eq!(subscriptions.remove_listener("stt/partial"), Ok(true));
+        assert!(subscriptions.remove_listener("stt/partial").is_err());
+        assert!(!subscriptions.is_subscribed("stt/partial"));
+        assert_eq!(subscriptions.topics(), vec!["tts/segment"]);
+
+        let stats = subscriptions.stats();
+        assert_eq!(stats[0].topic, "stt/partial");
+        assert_eq!(stats[0].listeners, 0);
+        assert_eq!(stats[0].delivered, 1);
+        assert_eq!(stats[0].failed, 1);
+        assert!(stats[0].last_delivered_at.is_some());
+    }
+}
@@ -0,0 +1,368 @@
+//! src-tauri/src/export.rs
+//! =======================
+//! Export a generated app as a standalone project on disk.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Two templates are supported:
+//! - `vite`: a Vite + React + TypeScript project with the TSX source in
+//!   `src/App.tsx` and assets in `public/`. Run with `npm install && npm run dev`.
+//! - `html`: a dependency-free folder with `index.html`, the compiled
+//!   `app.js` and assets; React is loaded from esm.sh via an import map.
+//!
+//! The app's TSX source is expected to `export default` its root component.
+//! Assets are copied out of the artifact store by hash.
+//!
+//! Usage:
+//!     ```rust
+//!     let snapshot = apps::load(&storage, &id, None)?.ok_or("Unknown app")?;
+//!     let files = export::export_app(&snapshot.definition, &artifacts, &dest, ExportTemplate::Vite)?;
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+use std::path::{Component, Path};
+
+use crate::apps::AppDefinition;
+use crate::artifacts::ArtifactStore;
+use crate::commands::compiler::{compile_tsx_internal, CompileOptions};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// React version pinned in exported projects
+const REACT_VERSION: &str = "18.3.1";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Project layout to generate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportTemplate {
+    /// Vite + React + TypeScript project
+    #[default]
+    Vite,
+    /// Plain HTML + compiled JavaScript
+    Html,
+}
+
+// ============================================
+// EXPORT
+// ============================================
+
+/// Write a standalone project for `definition` into `dest`.
+///
+/// `dest` must not exist or be an empty directory.
+///
+/// # Returns
+///
+/// Paths of the written files, relative to `dest`.
+pub fn export_app(
+    definition: &AppDefinition,
+    artifacts: &ArtifactStore,
+    dest: &Path,
+    template: ExportTemplate,
+) -> Result<Vec<String>, String> {
+    if dest.exists() {
+        let mut entries = fs::read_dir(dest).map_err(|e| format!("Cannot use {dest:?}: {e}"))?;
+        if entries.next().is_some() {
+            return Err(format!("Destination is not empty: {dest:?}"));
+        }
+    }
+
+    let files = project_files(definition, artifacts, template)?;
+
+    for (relative, data) in &files {
+        let path = dest.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+        }
+        fs::write(&path, data).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+    }
+
+    log::info!(
+        "Exported app '{}' to {dest:?} ({} files)",
+        definition.name,
+        files.len()
+    );
+    Ok(files.into_iter().map(|(relative, _)| relative).collect())
+}
+
+/// Build every file of the project in memory as (relative path, content).
+///
+/// Shared with the preview server, which serves the `html` layout.
+pub fn project_files(
+    definition: &AppDefinition,
+    artifacts: &ArtifactStore,
+    template: ExportTemplate,
+) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let (mut files, asset_dir) = match template {
+        ExportTemplate::Vite => (vite_files(definition), "public/"),
+        ExportTemplate::Html => (html_files(definition)?, ""),
+    };
+
+    for (asset_path, hash) in &definition.assets {
+        if !is_safe_relative(asset_path) {
+            return Err(format!("Invalid asset path: {asset_path}"));
+        }
+        let data = artifacts.read(hash)?;
+        files.push((format!("{asset_dir}{asset_path}"), data));
+    }
+
+    Ok(files)
+}
+
+/// Files for the Vite template.
+fn vite_files(definition: &AppDefinition) -> Vec<(String, Vec<u8>)> {
+    let package = json!({
+        "name": package_name(&definition.name),
+        "private": true,
+        "version": "0.1.0",
+        "type": "module",
+        "scripts": {
+            "dev": "vite",
+            "build": "tsc && vite build",
+            "preview": "vite preview"
+        },
+        "dependencies": {
+            "react": format!("^{REACT_VERSION}"),
+            "react-dom": format!("^{REACT_VERSION}")
+        },
+        "devDependencies": {
+            "@types/react": "^18.3.0",
+            "@types/react-dom": "^18.3.0",
+            "@vitejs/plugin-react": "^4.3.0",
+            "typescript": "^5.4.0",
+            "vite": "^5.4.0"
+        }
+    });
+    let tsconfig = json!({
+        "compilerOptions": {
+            "target": "ES2020",
+            "module": "ESNext",
+            "moduleResolution": "bundler",
+            "jsx": "react-jsx",
+            "strict": false,
+            "skipLibCheck": true,
+            "noEmit": true
+        },
+        "include": ["src"]
+    });
+
+    let index_html = format!(
+        r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="UTF-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1.0" />
+    <title>{}</title>
+  </head>
+  <body>
+    <div id="root"></div>
+    <script type="module" src="/src/main.tsx"></script>
+  </body>
+</html>
+"#,
+        escape_html(&definition.name)
+    );
+
+    let main_tsx = r#"import React from 'react';
+import ReactDOM from 'react-dom/client';
+import App from './App';
+
+ReactDOM.createRoot(document.getElementById('root')!).render(
+  <React.StrictMode>
+    <App />
+  </React.StrictMode>
+);
+"#;
+
+    let vite_config = r"import { defineConfig } from 'vite';
+import react from '@vitejs/plugin-react';
+
+export default defineConfig({
+  plugins: [react()],
+});
+";
+
+    vec![
+        ("package.json".to_string(), pretty(&package)),
+        ("tsconfig.json".to_string(), pretty(&tsconfig)),
+        ("vite.config.ts".to_string(), vite_config.into()),
+        ("index.html".to_string(), index_html.into_bytes()),
+        ("src/main.tsx".to_string(), main_tsx.into()),
+        ("src/App.tsx".to_string(), definition.source.clone().into_bytes()),
+        ("README.md".to_string(), readme(definition, "npm install\nnpm run dev").into_bytes()),
+    ]
+}
+
+/// Files for the plain HTML template.
+fn html_files(definition: &AppDefinition) -> Result<Vec<(String, Vec<u8>)>, String> {
+    let compiled = match &definition.compiled {
+        Some(code) => code.clone(),
+        None => {
+            let options = CompileOptions {
+                minify: true,
+                ..Default::default()
+            };
+            compile_tsx_internal(&definition.source, &options)
+                .map_err(|e| format!("Compilation failed: {e}"))?
+        }
+    };
+
+    let index_html = format!(
+        r#"<!doctype html>
+<html lang="en">
+  <head>
+    <meta charset="UTF-8" />
+    <meta name="viewport" content="width=device-width, initial-scale=1.0" />
+    <title>{title}</title>
+    <script type="importmap">
+      {{
+        "imports": {{
+          "react": "https://esm.sh/react@{REACT_VERSION}",
+          "react-dom/client": "https://esm.sh/react-dom@{REACT_VERSION}/client"
+        }}
+      }}
+    </script>
+  </head>
+  <body>
+    <div id="root"></div>
+    <script type="module">
+      import React from 'react';
+      import {{ createRoot }} from 'react-dom/client';
+      import App from './app.js';
+
+      window.React = React;
+      createRoot(document.getElementById('root')).render(React.createElement(App));
+    </script>
+  </body>
+</html>
+"#,
+        title = escape_html(&definition.name)
+    );
+
+    Ok(vec![
+        ("index.html".to_string(), index_html.into_bytes()),
+        ("app.js".to_string(), compiled.into_bytes()),
+        (
+            "README.md".to_string(),
+            readme(definition, "npx serve .").into_bytes(),
+        ),
+    ])
+}
+
+/// README for an exported project.
+fn readme(definition: &AppDefinition, run: &str) -> String {
+    format!(
+        "# {}\n\nExported from App Factory.\n\n## Run\n\n```sh\n{run}\n```\n",
+        definition.name
+    )
+}
+
+/// npm-compatible package name derived from the app name.
+fn package_name(name: &str) -> String {
+    let slug: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if slug.is_empty() {
+        "app".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Escape text for an HTML element body.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Whether a path stays inside the export directory.
+fn is_safe_relative(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+}
+
+/// Pretty-printed JSON bytes with a trailing newline.
+fn pretty(value: &serde_json::Value) -> Vec<u8> {
+    let mut bytes = serde_json::to_vec_pretty(value).unwrap_or_default();
+    bytes.push(b'\n');
+    bytes
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use std::collections::BTreeMap;
+
+    fn definition() -> AppDefinition {
+        AppDefinition {
+            name: "My Todo App!".to_string(),
+            component_tree: serde_json::Value::Null,
+            source: "export default function App() { return <h1>Hi</h1>; }\n".to_string(),
+            compiled: None,
+            assets: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_export_vite_with_assets() {
+        let artifacts = ArtifactStore::open(Some(temp_dir("export_artifacts")));
+        let logo = artifacts.put(b"png", "image", None).unwrap();
+        let mut def = definition();
+        def.assets.insert("img/logo.png".to_string(), logo.hash);
+
+        let dest = temp_dir("export_vite");
+        let files = export_app(&def, &artifacts, &dest, ExportTemplate::Vite).unwrap();
+        assert!(files.contains(&"src/App.tsx".to_string()));
+        assert_eq!(fs::read(dest.join("public/img/logo.png")).unwrap(), b"png");
+
+        let package: serde_json::Value =
+            serde_json::from_slice(&fs::read(dest.join("package.json")).unwrap()).unwrap();
+        assert_eq!(package["name"], "my-todo-app");
+
+        // Refuses to overwrite
+        assert!(export_app(&def, &artifacts, &dest, ExportTemplate::Vite).is_err());
+    }
+
+    #[test]
+    fn test_export_html_compiles_source() {
+        let artifacts = ArtifactStore::open(None);
+        let dest = temp_dir("export_html");
+        export_app(&definition(), &artifacts, &dest, ExportTemplate::Html).unwrap();
+
+        let app_js = fs::read_to_string(dest.join("app.js")).unwrap();
+        assert!(app_js.contains("React.createElement"));
+        let index = fs::read_to_string(dest.join("index.html")).unwrap();
+        assert!(index.contains("<title>My Todo App!</title>"));
+    }
+
+    #[test]
+    fn test_rejects_unsafe_asset_paths() {
+        assert!(is_safe_relative("img/logo.png"));
+        assert!(!is_safe_relative("../escape.png"));
+        assert!(!is_safe_relative("/etc/passwd"));
+        assert!(!is_safe_relative(""));
+    }
+}
@@ -0,0 +1,355 @@
+//! src-tauri/src/fs_watch.rs
+//! =========================
+//! File change watches with debounced events for the frontend.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A watch recursively observes a directory (within the project or a
+//! granted directory, see `crate::project_fs`). Changes are debounced and
+//! reported in batches, so an editor saving several files at once causes
+//! one re-preview:
+//!
+//!     fs://changed   { watch_id, changes: [{ path, kind }] }
+//!
+//! `path` is relative to the watched directory and `kind` is `created`,
+//! `modified`, or `removed`. With patterns (.gitignore syntax, see
+//! `crate::project_tree::PathPatterns`) only matching files are reported;
+//! without, every file is. Directories and `.git` are never reported.
+//! Dropping the watch stops the file system watcher, which ends its
+//! worker thread.
+//!
+//! Unlike `compile_watch` (see `crate::watch`), nothing is compiled; the
+//! frontend decides what to do with a change.
+//!
+//! Usage:
+//!     ```rust
+//!     let patterns = PathPatterns::parse(&["src/**", "*.css"])?;
+//!     let info = watches.start(dir, patterns, |batch| {
+//!         app.emit_all(CHANGED_EVENT, batch).ok();
+//!     })?;
+//!     watches.stop(&info.id);
+//!     ```
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::project_tree::PathPatterns;
+use crate::watch::WatchInfo;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Event emitted with each batch of changes
+pub const CHANGED_EVENT: &str = "fs://changed";
+
+/// Quiet period before a burst of changes is reported
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+// ============================================
+// TYPES
+// ============================================
+
+/// What happened to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// The file was created
+    Created,
+    /// The file's content or metadata changed
+    Modified,
+    /// The file was deleted (or renamed away)
+    Removed,
+}
+
+/// A changed file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileChange {
+    /// Path relative to the watched directory (`/` separated)
+    pub path: String,
+    /// What happened
+    pub kind: ChangeKind,
+}
+
+/// Payload of `fs://changed`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChangeBatch {
+    /// Watch id
+    pub watch_id: String,
+    /// Changed files, sorted by path
+    pub changes: Vec<FileChange>,
+}
+
+/// Watch bookkeeping.
+struct Watch {
+    info: WatchInfo,
+    /// Dropping the watcher disconnects the worker's channel
+    _watcher: RecommendedWatcher,
+}
+
+// ============================================
+// FILE WATCHER
+// ============================================
+
+/// Manages file change watches.
+#[derive(Clone, Default)]
+pub struct FileWatcher {
+    watches: Arc<Mutex<HashMap<String, Watch>>>,
+}
+
+impl FileWatcher {
+    /// Create an empty watcher registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching a directory.
+    ///
+    /// `patterns` selects the reported files (empty reports all).
+    /// `on_change` is called from the watch's worker thread for every
+    /// batch of changes.
+    pub fn start<F>(
+        &self,
+        dir: &Path,
+        patterns: PathPatterns,
+        on_change: F,
+    ) -> Result<WatchInfo, String>
+    where
+        F: Fn(&ChangeBatch) + Send + 'static,
+    {
+        let root = dir
+            .canonicalize()
+            .map_err(|e| format!("Cannot watch {dir:?}: {e}"))?;
+        if !root.is_dir() {
+            return Err(format!("Not a directory: {root:?}"));
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)
+            .map_err(|e| format!("Failed to create watcher: {e}"))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {root:?}: {e}"))?;
+
+        let info = WatchInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            path: root.to_string_lossy().to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let worker = Worker {
+            watch_id: info.id.clone(),
+            root,
+            patterns,
+        };
+        std::thread::Builder::new()
+            .name(format!("fs-watch-{}", info.id))
+            .spawn(move || worker.run(&rx, &on_change))
+            .map_err(|e| format!("Failed to start watch thread: {e}"))?;
+
+        self.watches.lock().unwrap().insert(
+            info.id.clone(),
+            Watch {
+                info: info.clone(),
+                _watcher: watcher,
+            },
+        );
+        log::info!("File watch {} started on {}", info.id, info.path);
+        Ok(info)
+    }
+
+    /// Stop a watch.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no watch has the id.
+    pub fn stop(&self, id: &str) -> bool {
+        let stopped = self.watches.lock().unwrap().remove(id).is_some();
+        if stopped {
+            log::info!("File watch {id} stopped");
+        }
+        stopped
+    }
+
+    /// List active watches.
+    pub fn list(&self) -> Vec<WatchInfo> {
+        self.watches
+            .lock()
+            .unwrap()
+            .values()
+            .map(|watch| watch.info.clone())
+            .collect()
+    }
+}
+
+// ============================================
+// WORKER
+// ============================================
+
+/// Per-watch state owned by the worker thread.
+struct Worker {
+    watch_id: String,
+    root: PathBuf,
+    patterns: PathPatterns,
+}
+
+impl Worker {
+    /// Report debounced batches of changes until the watcher is dropped.
+    fn run(
+        self,
+        rx: &mpsc::Receiver<notify::Result<notify::Event>>,
+        on_change: &impl Fn(&ChangeBatch),
+    ) {
+        while let Ok(first) = rx.recv() {
+            let mut changed = BTreeMap::new();
+            let mut collect = |event: notify::Result<notify::Event>| match event {
+                Ok(event) => {
+                    let created = matches!(event.kind, EventKind::Create(_));
+                    if !matches!(event.kind, EventKind::Access(_)) {
+                        for path in event.paths {
+                            // A file created in this batch stays created
+                            *changed.entry(path).or_insert(created) |= created;
+                        }
+                    }
+                }
+                Err(e) => log::warn!("File watch {}: {e}", self.watch_id),
+            };
+            collect(first);
+            while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+                collect(event);
+            }
+
+            let changes: Vec<FileChange> = changed
+                .into_iter()
+                .filter_map(|(path, created)| self.change(&path, created))
+                .collect();
+            if !changes.is_empty() {
+                log::debug!("File watch {}: {} changes", self.watch_id, changes.len());
+                on_change(&ChangeBatch {
+                    watch_id: self.watch_id.clone(),
+                    changes,
+                });
+            }
+        }
+        log::debug!("File watch {} worker exiting", self.watch_id);
+    }
+
+    /// Describe a changed path; None for directories and unselected files.
+    fn change(&self, path: &Path, created: bool) -> Option<FileChange> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        let relative = relative_path(relative)?;
+        let kind = match path.metadata() {
+            Ok(metadata) if metadata.is_dir() => return None,
+            Ok(_) if created => ChangeKind::Created,
+            Ok(_) => ChangeKind::Modified,
+            Err(_) => ChangeKind::Removed,
+        };
+        if !selected(&self.patterns, &relative) {
+            return None;
+        }
+        Some(FileChange {
+            path: relative,
+            kind,
+        })
+    }
+}
+
+/// Whether a file is reported: no patterns, or it or a directory it is in
+/// matches (as a directory matched by .gitignore covers its contents).
+fn selected(patterns: &PathPatterns, relative: &str) -> bool {
+    patterns.is_empty()
+        || patterns.matches(relative, false)
+        || relative
+            .match_indices('/')
+            .any(|(end, _)| patterns.matches(&relative[..end], true))
+}
+
+/// `/`-separated relative path; None inside `.git`.
+fn relative_path(relative: &Path) -> Option<String> {
+    let mut parts = Vec::new();
+    for component in relative.components() {
+        let Component::Normal(name) = component else {
+            return None;
+        };
+        let name = name.to_string_lossy();
+        if name == ".git" {
+            return None;
+        }
+        parts.push(name);
+    }
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_relative_path() {
+        assert_eq!(
+            relative_path(Path::new("src/App.tsx")).as_deref(),
+            Some("src/App.tsx")
+        );
+        assert_eq!(relative_path(Path::new(".git/index")), None);
+        assert_eq!(relative_path(Path::new("")), None);
+    }
+
+    #[test]
+    fn test_selected() {
+        let patterns = PathPatterns::parse(&["src/", "*.css"]).unwrap();
+        assert!(selected(&patterns, "src/App.tsx"));
+        assert!(selected(&patterns, "src/components/Button.tsx"));
+        assert!(selected(&patterns, "styles/main.css"));
+        assert!(!selected(&patterns, "README.md"));
+        assert!(selected(&PathPatterns::default(), "README.md"));
+    }
+
+    #[test]
+    fn test_watch_reports_changes() {
+        let dir = std::env::temp_dir().join(format!("af_fs_watch_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        let watches = FileWatcher::new();
+        let (tx, rx) = mpsc::channel();
+        let patterns = PathPatterns::parse(&["src/**"]).unwrap();
+        let info = watches
+            .start(&dir, patterns, move |batch| {
+                let _ = tx.send(batch.clone());
+            })
+            .unwrap();
+        assert_eq!(watches.list().len(), 1);
+
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        fs::write(dir.join("src/App.tsx"), "export default 1;").unwrap();
+        let batch = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(batch.watch_id, info.id);
+        assert!(batch
+            .changes
+            .iter()
+            .all(|change| change.path == "src/App.tsx"));
+
+        fs::remove_file(dir.join("src/App.tsx")).unwrap();
+        let batch = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(
+            batch.changes,
+            vec![FileChange {
+                path: "src/App.tsx".to_string(),
+                kind: ChangeKind::Removed,
+            }]
+        );
+
+        assert!(watches.stop(&info.id));
+        assert!(watches.list().is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,198 @@
+//! src-tauri/src/hardware.rs
+//! =========================
+//! Hardware capability detection.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Reports CPU, memory, GPU, and disk capacity so the frontend and plugins
+//! can pick a model that fits before a load attempt fails or times out.
+//!
+//! GPUs are probed through NVML, which is loaded at runtime; machines
+//! without an NVIDIA driver simply report no GPUs.
+//!
+//! Usage:
+//!     ```rust
+//!     let info = detect_hardware();
+//!     let fits = info.gpus.iter().any(|g| g.vram_bytes.unwrap_or(0) >= 8 << 30);
+//!     ```
+
+use serde::Serialize;
+use sysinfo::{Disks, System};
+
+// ============================================
+// TYPES
+// ============================================
+
+/// CPU information.
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuInfo {
+    /// CPU brand string
+    pub brand: String,
+    /// Physical core count (if known)
+    pub physical_cores: Option<usize>,
+    /// Logical core count
+    pub logical_cores: usize,
+    /// CPU architecture (e.g. x86_64, aarch64)
+    pub arch: String,
+}
+
+/// Memory information.
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryInfo {
+    /// Total RAM in bytes
+    pub total_bytes: u64,
+    /// Available RAM in bytes
+    pub available_bytes: u64,
+}
+
+/// GPU information.
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuInfo {
+    /// Vendor name (e.g. NVIDIA)
+    pub vendor: String,
+    /// Device name
+    pub name: String,
+    /// Total VRAM in bytes (if known)
+    pub vram_bytes: Option<u64>,
+    /// Driver version (if known)
+    pub driver_version: Option<String>,
+}
+
+/// Disk information.
+#[derive(Debug, Clone, Serialize)]
+pub struct DiskInfo {
+    /// Mount point
+    pub mount_point: String,
+    /// Total space in bytes
+    pub total_bytes: u64,
+    /// Available space in bytes
+    pub available_bytes: u64,
+}
+
+/// Hardware capability report.
+#[derive(Debug, Clone, Serialize)]
+pub struct HardwareInfo {
+    /// Operating system description
+    pub os: String,
+    /// CPU details
+    pub cpu: CpuInfo,
+    /// Memory details
+    pub memory: MemoryInfo,
+    /// Detected GPUs (empty if none or not probeable)
+    pub gpus: Vec<GpuInfo>,
+    /// Mounted disks
+    pub disks: Vec<DiskInfo>,
+}
+
+// ============================================
+// DETECTION
+// ============================================
+
+/// Detect hardware capabilities.
+///
+/// This is blocking (GPU probing loads the NVML library); call it from a
+/// blocking task in async contexts.
+pub fn detect_hardware() -> HardwareInfo {
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    sys.refresh_memory();
+
+    let cpu = CpuInfo {
+        brand: sys
+            .cpus()
+            .first()
+            .map(|c| c.brand().trim().to_string())
+            .unwrap_or_default(),
+        physical_cores: sys.physical_core_count(),
+        logical_cores: sys.cpus().len(),
+        arch: std::env::consts::ARCH.to_string(),
+    };
+
+    let memory = MemoryInfo {
+        total_bytes: sys.total_memory(),
+        available_bytes: sys.available_memory(),
+    };
+
+    let disks = Disks::new_with_refreshed_list()
+        .iter()
+        .map(|d| DiskInfo {
+            mount_point: d.mount_point().to_string_lossy().to_string(),
+            total_bytes: d.total_space(),
+            available_bytes: d.available_space(),
+        })
+        .collect();
+
+    let os = format!(
+        "{} {}",
+        System::name().unwrap_or_else(|| std::env::consts::OS.to_string()),
+        System::os_version().unwrap_or_default()
+    )
+    .trim()
+    .to_string();
+
+    HardwareInfo {
+        os,
+        cpu,
+        memory,
+        gpus: detect_nvidia_gpus(),
+        disks,
+    }
+}
+
+/// Probe NVIDIA GPUs via NVML.
+fn detect_nvidia_gpus() -> Vec<GpuInfo> {
+    let nvml = match nvml_wrapper::Nvml::init() {
+        Ok(nvml) => nvml,
+        Err(e) => {
+            log::debug!("NVML unavailable, skipping NVIDIA GPU probe: {e}");
+            return Vec::new();
+        }
+    };
+
+    let driver_version = nvml.sys_driver_version().ok();
+    let count = nvml.device_count().unwrap_or(0);
+
+    (0..count)
+        .filter_map(|index| match nvml.device_by_index(index) {
+            Ok(device) => Some(GpuInfo {
+                vendor: "NVIDIA".to_string(),
+                name: device.name().unwrap_or_else(|_| format!("GPU {index}")),
+                vram_bytes: device.memory_info().ok().map(|m| m.total),
+                driver_version: driver_version.clone(),
+            }),
+            Err(e) => {
+                log::warn!("Failed to query GPU {index}: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_hardware_reports_cpu_and_memory() {
+        let info = detect_hardware();
+
+        assert!(info.cpu.logical_cores > 0);
+        assert!(info.memory.total_bytes > 0);
+        assert!(info.memory.available_bytes <= info.memory.total_bytes);
+        assert!(!info.cpu.arch.is_empty());
+    }
+
+    #[test]
+    fn test_hardware_info_serialization() {
+        let info = detect_hardware();
+        let json = serde_json::to_value(&info).unwrap();
+
+        assert!(json["cpu"]["logical_cores"].is_u64());
+        assert!(json["gpus"].is_array());
+        assert!(json["disks"].is_array());
+    }
+}
@@ -0,0 +1,443 @@
+//! src-tauri/src/health_history.rs
+//! ===============================
+//! Plugin host health history that survives restarts.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! `HealthMonitor` only keeps recent check results in memory. This module
+//! follows its events and the manager's crash events and stores a compacted
+//! record in the `health_events` table of the embedded database (see
+//! storage.rs):
+//!
+//! - `state_change`: every subprocess state transition
+//! - `failure_burst`: consecutive failed health checks, stored as one row
+//!   with a count, the first and last failure time, and the latest error.
+//!   A successful check or a new start closes the burst.
+//! - `respawn`: every start after the first since the app launched
+//! - `crash`: the subprocess exited without being asked to
+//!
+//! Rows older than `RETENTION_DAYS` are pruned on startup. `summary`
+//! counts the events of the last few days, so the UI can say e.g.
+//! "crashed 3 times in the last week".
+//!
+//! Usage:
+//!     ```rust
+//!     let history = HealthHistory::new(storage.clone());
+//!     tauri::async_runtime::spawn(history.clone().watch(
+//!         state.health().subscribe(),
+//!         state.subscribe_events(),
+//!     ));
+//!     let summary = history.summary(7)?;
+//!     ```
+
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+use crate::ipc::health::{HealthEvent, SubprocessState};
+use crate::ipc::manager::ManagerEvent;
+use crate::storage::Storage;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Days of history kept
+const RETENTION_DAYS: i64 = 30;
+
+/// Events returned with a summary
+const SUMMARY_EVENT_LIMIT: u32 = 50;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Kind of a stored health event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthEventKind {
+    StateChange,
+    FailureBurst,
+    Respawn,
+    Crash,
+}
+
+impl HealthEventKind {
+    /// Value stored in the `kind` column.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::StateChange => "state_change",
+            Self::FailureBurst => "failure_burst",
+            Self::Respawn => "respawn",
+            Self::Crash => "crash",
+        }
+    }
+
+    /// Parse a `kind` column value.
+    fn parse(value: &str) -> Option<Self> {
+        [
+            Self::StateChange,
+            Self::FailureBurst,
+            Self::Respawn,
+            Self::Crash,
+        ]
+        .into_iter()
+        .find(|kind| kind.as_str() == value)
+    }
+}
+
+/// A stored health event.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthRecord {
+    /// Row id
+    pub id: i64,
+    /// Event kind
+    pub kind: HealthEventKind,
+    /// Transition (`RUNNING -> DEGRADED`), latest error, or exit code
+    pub detail: String,
+    /// Failed checks in a burst (1 for other kinds)
+    pub count: u32,
+    /// When it happened, or the first failure of a burst (RFC 3339)
+    pub started_at: String,
+    /// Last failure of a burst (same as `started_at` for other kinds)
+    pub ended_at: String,
+}
+
+/// Health history of the last few days.
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthSummary {
+    /// Days covered
+    pub days: u32,
+    /// Start of the covered period (RFC 3339)
+    pub since: String,
+    /// Unexpected exits
+    pub crashes: u32,
+    /// Starts after the first
+    pub respawns: u32,
+    /// Times the host became degraded
+    pub degraded: u32,
+    /// Bursts of failed health checks
+    pub failure_bursts: u32,
+    /// Failed health checks across all bursts
+    pub failed_checks: u32,
+    /// Most recent events, newest first
+    pub events: Vec<HealthRecord>,
+}
+
+/// What the recorder remembers between events.
+#[derive(Debug, Default)]
+struct Tracker {
+    /// Row of the failure burst still open
+    open_burst: Option<i64>,
+    /// Whether the host has been started since the app launched
+    started: bool,
+}
+
+// ============================================
+// HEALTH HISTORY
+// ============================================
+
+/// Persisted health history.
+#[derive(Clone)]
+pub struct HealthHistory {
+    storage: Storage,
+    tracker: Arc<Mutex<Tracker>>,
+}
+
+impl HealthHistory {
+    /// Use the given storage, pruning events past the retention period.
+    pub fn new(storage: Storage) -> Self {
+        let history = Self {
+            storage,
+            tracker: Arc::new(Mutex::new(Tracker::default())),
+        };
+        if let Err(e) = history.prune(Utc::now() - Duration::days(RETENTION_DAYS)) {
+            log::warn!("{e}");
+        }
+        history
+    }
+
+    /// Record events until either channel closes.
+    pub async fn watch(
+        self,
+        mut health: broadcast::Receiver<HealthEvent>,
+        mut events: broadcast::Receiver<ManagerEvent>,
+    ) {
+        loop {
+            let result = tokio::select! {
+                event = health.recv() => match event {
+                    Ok(event) => self.record_health(&event),
+                    Err(broadcast::error::RecvError::Lagged(n)) => {
+                        log::warn!("Health history missed {n} events");
+                        Ok(())
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                event = events.recv() => match event {
+                    Ok(event) => self.record_manager(&event),
+                    Err(broadcast::error::RecvError::Lagged(_)) => Ok(()),
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+            };
+            if let Err(e) = result {
+                log::warn!("{e}");
+            }
+        }
+    }
+
+    /// Record a health monitor event.
+    pub fn record_health(&self, event: &HealthEvent) -> Result<(), String> {
+        let at = Utc::now();
+        let mut tracker = self.tracker.lock().unwrap();
+        match event {
+            HealthEvent::StateChanged { from, to } => {
+                if *to == SubprocessState::Starting {
+                    tracker.open_burst = None;
+                    if tracker.started {
+                        self.insert(HealthEventKind::Respawn, &format!("After {from}"), at)?;
+                    }
+                    tracker.started = true;
+                }
+                self.insert(HealthEventKind::StateChange, &format!("{from} -> {to}"), at)?;
+            }
+            // Failures while stopping or after an exit are not check failures
+            HealthEvent::CheckFailed { error, state } if state.is_running() => {
+                match tracker.open_burst {
+                    Some(id) => self.extend_burst(id, error, at)?,
+                    None => {
+                        tracker.open_burst =
+                            Some(self.insert(HealthEventKind::FailureBurst, error, at)?);
+                    }
+                }
+            }
+            HealthEvent::CheckFailed { .. } => {}
+            HealthEvent::CheckSucceeded => tracker.open_burst = None,
+        }
+        Ok(())
+    }
+
+    /// Record a manager event (only crashes are kept).
+    pub fn record_manager(&self, event: &ManagerEvent) -> Result<(), String> {
+        if let ManagerEvent::SubprocessCrashed { exit_code, .. } = event {
+            let detail = exit_code.map_or_else(
+                || "Exited without an exit code".to_string(),
+                |code| format!("Exit code {code}"),
+            );
+            self.insert(HealthEventKind::Crash, &detail, Utc::now())?;
+        }
+        Ok(())
+    }
+
+    /// Summarize the last `days` days.
+    pub fn summary(&self, days: u32) -> Result<HealthSummary, String> {
+        let since = (Utc::now() - Duration::days(i64::from(days))).to_rfc3339();
+        let conn = self.storage.conn();
+
+        let (crashes, respawns, degraded, failure_bursts, failed_checks) = conn
+            .query_row(
+                "SELECT
+                     COALESCE(SUM(kind = 'crash'), 0),
+                     COALESCE(SUM(kind = 'respawn'), 0),
+                     COALESCE(SUM(kind = 'state_change' AND detail LIKE '% -> DEGRADED'), 0),
+                     COALESCE(SUM(kind = 'failure_burst'), 0),
+                     COALESCE(SUM(CASE WHEN kind = 'failure_burst' THEN count ELSE 0 END), 0)
+                 FROM health_events WHERE ended_at >= ?1",
+                params![since],
+                |row| {
+                    Ok((
+                        row.get::<_, u32>(0)?,
+                        row.get::<_, u32>(1)?,
+                        row.get::<_, u32>(2)?,
+                        row.get::<_, u32>(3)?,
+                        row.get::<_, u32>(4)?,
+                    ))
+                },
+            )
+            .map_err(|e| format!("Failed to summarize health history: {e}"))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, kind, detail, count, started_at, ended_at FROM health_events
+                 WHERE ended_at >= ?1 ORDER BY ended_at DESC, id DESC LIMIT ?2",
+            )
+            .map_err(|e| e.to_string())?;
+        let events = stmt
+            .query_map(params![since, SUMMARY_EVENT_LIMIT], row_to_record)
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to list health history: {e}"))?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        Ok(HealthSummary {
+            days,
+            since,
+            crashes,
+            respawns,
+            degraded,
+            failure_bursts,
+            failed_checks,
+            events,
+        })
+    }
+
+    /// Insert an event.
+    ///
+    /// # Returns
+    ///
+    /// The row id.
+    fn insert(
+        &self,
+        kind: HealthEventKind,
+        detail: &str,
+        at: DateTime<Utc>,
+    ) -> Result<i64, String> {
+        let at = at.to_rfc3339();
+        let conn = self.storage.conn();
+        conn.execute(
+            "INSERT INTO health_events (kind, detail, count, started_at, ended_at)
+             VALUES (?1, ?2, 1, ?3, ?3)",
+            params![kind.as_str(), detail, at],
+        )
+        .map_err(|e| format!("Failed to record health event: {e}"))?;
+        Ok(conn.last_insert_rowid())
+    }
+
+    /// Add a failure to an open burst.
+    fn extend_burst(&self, id: i64, error: &str, at: DateTime<Utc>) -> Result<(), String> {
+        self.storage
+            .conn()
+            .execute(
+                "UPDATE health_events SET count = count + 1, detail = ?2, ended_at = ?3
+                 WHERE id = ?1",
+                params![id, error, at.to_rfc3339()],
+            )
+            .map(|_| ())
+            .map_err(|e| format!("Failed to record health event: {e}"))
+    }
+
+    /// Delete events that ended before `cutoff`.
+    fn prune(&self, cutoff: DateTime<Utc>) -> Result<(), String> {
+        self.storage
+            .conn()
+            .execute(
+                "DELETE FROM health_events WHERE ended_at < ?1",
+                params![cutoff.to_rfc3339()],
+            )
+            .map(|_| ())
+            .map_err(|e| format!("Failed to prune health history: {e}"))
+    }
+}
+
+/// Map a `health_events` row (None for an unknown kind).
+fn row_to_record(row: &rusqlite::Row<'_>) -> rusqlite::Result<Option<HealthRecord>> {
+    let kind: String = row.get(1)?;
+    let Some(kind) = HealthEventKind::parse(&kind) else {
+        return Ok(None);
+    };
+    Ok(Some(HealthRecord {
+        id: row.get(0)?,
+        kind,
+        detail: row.get(2)?,
+        count: row.get(3)?,
+        started_at: row.get(4)?,
+        ended_at: row.get(5)?,
+    }))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failed(error: &str) -> HealthEvent {
+        HealthEvent::CheckFailed {
+            error: error.to_string(),
+            state: SubprocessState::Running,
+        }
+    }
+
+    #[test]
+    fn test_failure_bursts_are_compacted() {
+        let history = HealthHistory::new(Storage::open(None).unwrap());
+        history.record_health(&failed("Timeout 1")).unwrap();
+        history.record_health(&failed("Timeout 2")).unwrap();
+        history.record_health(&failed("Timeout 3")).unwrap();
+        history.record_health(&HealthEvent::CheckSucceeded).unwrap();
+        history.record_health(&failed("Timeout 4")).unwrap();
+        // Not a check failure: the host already exited
+        history
+            .record_health(&HealthEvent::CheckFailed {
+                error: "Subprocess stdout closed".to_string(),
+                state: SubprocessState::Crashed,
+            })
+            .unwrap();
+
+        let summary = history.summary(7).unwrap();
+        assert_eq!(summary.failure_bursts, 2);
+        assert_eq!(summary.failed_checks, 4);
+        assert_eq!(summary.events[1].count, 3);
+        assert_eq!(summary.events[1].detail, "Timeout 3");
+    }
+
+    #[test]
+    fn test_summary_counts_crashes_and_respawns() {
+        let history = HealthHistory::new(Storage::open(None).unwrap());
+        let changed = |from, to| HealthEvent::StateChanged { from, to };
+
+        history
+            .record_health(&changed(
+                SubprocessState::NotStarted,
+                SubprocessState::Starting,
+            ))
+            .unwrap();
+        history
+            .record_health(&changed(
+                SubprocessState::Running,
+                SubprocessState::Degraded,
+            ))
+            .unwrap();
+        history
+            .record_manager(&ManagerEvent::SubprocessCrashed {
+                pid: 1,
+                exit_code: Some(1),
+                stderr_tail: Vec::new(),
+                pending_methods: Vec::new(),
+            })
+            .unwrap();
+        history
+            .record_health(&changed(
+                SubprocessState::Crashed,
+                SubprocessState::Starting,
+            ))
+            .unwrap();
+
+        let summary = history.summary(7).unwrap();
+        assert_eq!(summary.crashes, 1);
+        assert_eq!(summary.respawns, 1);
+        assert_eq!(summary.degraded, 1);
+    }
+
+    #[test]
+    fn test_old_events_are_pruned() {
+        let history = HealthHistory::new(Storage::open(None).unwrap());
+        let old = Utc::now() - Duration::days(RETENTION_DAYS + 1);
+        history
+            .insert(HealthEventKind::Crash, "Exit code 1", old)
+            .unwrap();
+        history
+            .insert(HealthEventKind::Crash, "Exit code 2", Utc::now())
+            .unwrap();
+
+        assert_eq!(history.summary(365).unwrap().crashes, 2);
+        history
+            .prune(Utc::now() - Duration::days(RETENTION_DAYS))
+            .unwrap();
+        assert_eq!(history.summary(365).unwrap().crashes, 1);
+    }
+}
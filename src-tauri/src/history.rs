@@ -0,0 +1,330 @@
+//! src-tauri/src/history.rs
+//! ========================
+//! Persistent LLM chat history for app generation sessions.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Messages live in the `history_messages` table of the embedded database
+//! (see storage.rs). An FTS5 index (`history_fts`) kept in sync by triggers
+//! backs full-text search across all sessions.
+//!
+//! Usage:
+//!     ```rust
+//!     let msg = history::append(&storage, "session-1", "user", "Build a todo app", None)?;
+//!     let page = history::list_messages(&storage, "session-1", 50, 0)?;
+//!     let hits = history::search(&storage, "todo", None, 20)?;
+//!     ```
+
+use chrono::Utc;
+use rusqlite::params;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::storage::{Page, Storage};
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A single chat message.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryMessage {
+    /// Message id (monotonic across sessions)
+    pub id: i64,
+    /// Session the message belongs to
+    pub session_id: String,
+    /// Speaker role (user, assistant, system, tool)
+    pub role: String,
+    /// Message text
+    pub content: String,
+    /// Optional metadata (model, token counts, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+}
+
+/// Summary of a chat session.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistorySession {
+    /// Session id
+    pub session_id: String,
+    /// Number of messages in the session
+    pub message_count: u64,
+    /// Timestamp of the first message
+    pub started_at: String,
+    /// Timestamp of the latest message
+    pub updated_at: String,
+}
+
+/// A full-text search hit.
+#[derive(Debug, Clone, Serialize)]
+pub struct HistoryHit {
+    /// The matching message
+    pub message: HistoryMessage,
+    /// Excerpt with matches wrapped in `[` `]`
+    pub snippet: String,
+}
+
+// ============================================
+// OPERATIONS
+// ============================================
+
+/// Append a message to a session (the session is created implicitly).
+pub fn append(
+    storage: &Storage,
+    session_id: &str,
+    role: &str,
+    content: &str,
+    metadata: Option<&Value>,
+) -> Result<HistoryMessage, String> {
+    let created_at = Utc::now().to_rfc3339();
+    let metadata_json = metadata.map(Value::to_string);
+
+    let conn = storage.conn();
+    conn.execute(
+        "INSERT INTO history_messages (session_id, role, content, metadata, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![session_id, role, content, metadata_json, created_at],
+    )
+    .map_err(|e| format!("Failed to append message: {e}"))?;
+
+    Ok(HistoryMessage {
+        id: conn.last_insert_rowid(),
+        session_id: session_id.to_string(),
+        role: role.to_string(),
+        content: content.to_string(),
+        metadata: metadata.cloned(),
+        created_at,
+    })
+}
+
+/// List a session's messages in chronological order.
+pub fn list_messages(
+    storage: &Storage,
+    session_id: &str,
+    limit: u64,
+    offset: u64,
+) -> Result<Page<HistoryMessage>, String> {
+    let conn = storage.conn();
+
+    let total: u64 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM history_messages WHERE session_id = ?1",
+            params![session_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count messages: {e}"))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, session_id, role, content, metadata, created_at
+             FROM history_messages WHERE session_id = ?1
+             ORDER BY id LIMIT ?2 OFFSET ?3",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let items = stmt
+        .query_map(params![session_id, limit, offset], row_to_message)
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("Failed to list messages: {e}"))?;
+
+    Ok(Page {
+        items,
+        total,
+        offset,
+    })
+}
+
+/// List sessions, most recently active first.
+pub fn list_sessions(
+    storage: &Storage,
+    limit: u64,
+    offset: u64,
+) -> Result<Page<HistorySession>, String> {
+    let conn = storage.conn();
+
+    let total: u64 = conn
+        .query_row(
+            "SELECT COUNT(DISTINCT session_id) FROM history_messages",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| format!("Failed to count sessions: {e}"))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT session_id, COUNT(*), MIN(created_at), MAX(created_at)
+             FROM history_messages GROUP BY session_id
+             ORDER BY MAX(id) DESC LIMIT ?1 OFFSET ?2",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let items = stmt
+        .query_map(params![limit, offset], |row| {
+            Ok(HistorySession {
+                session_id: row.get(0)?,
+                message_count: row.get(1)?,
+                started_at: row.get(2)?,
+                updated_at: row.get(3)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("Failed to list sessions: {e}"))?;
+
+    Ok(Page {
+        items,
+        total,
+        offset,
+    })
+}
+
+/// Full-text search across messages, best matches first.
+///
+/// The query is matched as a set of terms (all must appear); FTS5 operators
+/// in user input are treated as plain text.
+pub fn search(
+    storage: &Storage,
+    query: &str,
+    session_id: Option<&str>,
+    limit: u64,
+) -> Result<Vec<HistoryHit>, String> {
+    let Some(fts_query) = to_fts_query(query) else {
+        return Ok(Vec::new());
+    };
+
+    let conn = storage.conn();
+    let mut stmt = conn
+        .prepare(
+            "SELECT m.id, m.session_id, m.role, m.content, m.metadata, m.created_at,
+                    snippet(history_fts, 0, '[', ']', '…', 12)
+             FROM history_fts
+             JOIN history_messages m ON m.id = history_fts.rowid
+             WHERE history_fts MATCH ?1 AND (?2 IS NULL OR m.session_id = ?2)
+             ORDER BY bm25(history_fts) LIMIT ?3",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let hits = stmt
+        .query_map(params![fts_query, session_id, limit], |row| {
+            Ok(HistoryHit {
+                message: row_to_message(row)?,
+                snippet: row.get(6)?,
+            })
+        })
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("Search failed: {e}"))?;
+
+    Ok(hits)
+}
+
+/// Delete a whole session, or a single message when `message_id` is given.
+///
+/// # Returns
+///
+/// Number of messages deleted.
+pub fn delete(
+    storage: &Storage,
+    session_id: &str,
+    message_id: Option<i64>,
+) -> Result<usize, String> {
+    let conn = storage.conn();
+    let result = match message_id {
+        Some(id) => conn.execute(
+            "DELETE FROM history_messages WHERE session_id = ?1 AND id = ?2",
+            params![session_id, id],
+        ),
+        None => conn.execute(
+            "DELETE FROM history_messages WHERE session_id = ?1",
+            params![session_id],
+        ),
+    };
+    result.map_err(|e| format!("Failed to delete history: {e}"))
+}
+
+/// Quote each whitespace-separated term so user input can't inject FTS5
+/// syntax. Returns `None` for an empty query.
+fn to_fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Map a `history_messages` row to a `HistoryMessage`.
+fn row_to_message(row: &rusqlite::Row<'_>) -> rusqlite::Result<HistoryMessage> {
+    let metadata: Option<String> = row.get(4)?;
+    Ok(HistoryMessage {
+        id: row.get(0)?,
+        session_id: row.get(1)?,
+        role: row.get(2)?,
+        content: row.get(3)?,
+        metadata: metadata.and_then(|m| serde_json::from_str(&m).ok()),
+        created_at: row.get(5)?,
+    })
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_append_and_list() {
+        let storage = Storage::open(None).unwrap();
+        append(&storage, "s1", "user", "Build a todo app", None).unwrap();
+        append(&storage, "s1", "assistant", "Here it is", Some(&json!({"model": "x"}))).unwrap();
+        append(&storage, "s2", "user", "Weather widget", None).unwrap();
+
+        let page = list_messages(&storage, "s1", 10, 0).unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.items[0].role, "user");
+        assert_eq!(page.items[1].metadata, Some(json!({"model": "x"})));
+
+        let page = list_messages(&storage, "s1", 1, 1).unwrap();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].content, "Here it is");
+
+        let sessions = list_sessions(&storage, 10, 0).unwrap();
+        assert_eq!(sessions.total, 2);
+        assert_eq!(sessions.items[0].session_id, "s2");
+        assert_eq!(sessions.items[1].message_count, 2);
+    }
+
+    #[test]
+    fn test_search() {
+        let storage = Storage::open(None).unwrap();
+        append(&storage, "s1", "user", "Build a todo app with dark mode", None).unwrap();
+        append(&storage, "s2", "user", "A todo list for groceries", None).unwrap();
+        append(&storage, "s2", "user", "Something else", None).unwrap();
+
+        assert_eq!(search(&storage, "todo", None, 10).unwrap().len(), 2);
+        assert_eq!(search(&storage, "todo", Some("s2"), 10).unwrap().len(), 1);
+
+        let hits = search(&storage, "dark mode", None, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert!(hits[0].snippet.contains("[dark]"));
+
+        // FTS syntax in user input is treated literally
+        assert!(search(&storage, "todo OR", None, 10).unwrap().is_empty());
+        assert!(search(&storage, "   ", None, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_delete_updates_index() {
+        let storage = Storage::open(None).unwrap();
+        let msg = append(&storage, "s1", "user", "alpha", None).unwrap();
+        append(&storage, "s1", "user", "beta", None).unwrap();
+
+        assert_eq!(delete(&storage, "s1", Some(msg.id)).unwrap(), 1);
+        assert!(search(&storage, "alpha", None, 10).unwrap().is_empty());
+
+        assert_eq!(delete(&storage, "s1", None).unwrap(), 1);
+        assert_eq!(list_messages(&storage, "s1", 10, 0).unwrap().total, 0);
+    }
+}
@@ -0,0 +1,384 @@
+//! src-tauri/src/http_fetch.rs
+//! ===========================
+//! HTTP requests made by the app on behalf of the frontend and plugins.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Requests sent from Rust are not bound by the webview's CORS rules, so
+//! they are restricted by a `FetchPolicy` instead:
+//!
+//! - Only `http` and `https` URLs are fetched.
+//! - With an allowlist (`http.allowed_domains`, used by the `http_fetch`
+//!   command), the host of the URL and of every redirect must match an
+//!   entry: `example.com` matches that host only, `*.example.com` any of
+//!   its subdomains. An empty allowlist blocks every request.
+//! - Requests time out after `timeout_secs` (a request may ask for less).
+//! - Request bodies over `max_request_bytes` are refused, and responses are
+//!   read until `max_response_bytes` and then dropped.
+//!
+//! Plugins reach the same code through `http/fetch` (see reverse_rpc.rs),
+//! gated by their `network` permission instead of an allowlist.
+//! `download_file` checks its URL against the same allowlist.
+//!
+//! In offline mode (see offline.rs) only loopback hosts are fetched; other
+//! requests and redirects fail with `OFFLINE`.
+//!
+//! Usage:
+//!     ```rust
+//!     let policy = settings.get().http.policy();
+//!     let response = http_fetch::fetch(&policy, FetchRequest::get(url)).await?;
+//!     println!("{} {}", response.status, response.body);
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::time::Duration;
+
+use crate::offline;
+use crate::proxy;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Default request timeout
+pub const FETCH_TIMEOUT_SECS: u64 = 30;
+
+/// Default largest request body
+pub const MAX_REQUEST_BYTES: u64 = 1024 * 1024;
+
+/// Default largest response body
+pub const MAX_RESPONSE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Redirects followed before a request fails
+const MAX_REDIRECTS: usize = 10;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// An HTTP request to make.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FetchRequest {
+    /// HTTP(S) URL
+    pub url: String,
+    /// HTTP method (defaults to GET)
+    pub method: Option<String>,
+    /// Request headers
+    pub headers: BTreeMap<String, String>,
+    /// Request body
+    pub body: Option<String>,
+    /// Timeout in seconds (capped by the policy)
+    pub timeout_secs: Option<u64>,
+}
+
+impl FetchRequest {
+    /// A GET request for `url`.
+    pub fn get(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// The response to a `FetchRequest`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FetchResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers (values that aren't valid text are left out)
+    pub headers: BTreeMap<String, String>,
+    /// Response body (invalid UTF-8 replaced)
+    pub body: String,
+}
+
+/// Restrictions on the requests made for a caller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchPolicy {
+    /// Hosts that may be contacted (None allows any)
+    pub allowed_domains: Option<Vec<String>>,
+    /// Longest a request may take
+    pub timeout_secs: u64,
+    /// Largest request body
+    pub max_request_bytes: u64,
+    /// Largest response body
+    pub max_response_bytes: u64,
+}
+
+/// A request that was refused or failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchError {
+    /// Error code (`INVALID_REQUEST`, `OFFLINE`, `DOMAIN_NOT_ALLOWED`,
+    /// `REQUEST_TOO_LARGE`, `RESPONSE_TOO_LARGE`, `FETCH_TIMEOUT`, or
+    /// `FETCH_FAILED`)
+    pub code: &'static str,
+    /// Human-readable message
+    pub message: String,
+}
+
+impl FetchError {
+    fn new(code: &'static str, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+// ============================================
+// DOMAIN ALLOWLIST
+// ============================================
+
+/// Check an allowlist entry (`example.com` or `*.example.com`).
+pub fn validate_domain_pattern(pattern: &str) -> Result<(), String> {
+    let host = pattern.strip_prefix("*.").unwrap_or(pattern);
+    let valid = !host.is_empty()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '[' | ']' | ':'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid domain: {pattern} (expected e.g. example.com or *.example.com)"
+        ))
+    }
+}
+
+/// Whether `host` matches an entry of the allowlist.
+pub fn domain_allowed(allowed: &[String], host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowed.iter().any(|pattern| {
+        let pattern = pattern.trim_end_matches('.').to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(parent) => host.ends_with(&format!(".{parent}")),
+            None => host == pattern,
+        }
+    })
+}
+
+/// Fail unless the policy allows contacting the host of `url`.
+fn check_domain(policy: &FetchPolicy, url: &reqwest::Url) -> Result<(), FetchError> {
+    let Some(allowed) = &policy.allowed_domains else {
+        return Ok(());
+    };
+    match url.host_str() {
+        Some(host) if domain_allowed(allowed, host) => Ok(()),
+        _ => Err(FetchError::new(
+            "DOMAIN_NOT_ALLOWED",
+            format!(
+                "{} is not in the allowed domains",
+                url.host_str().unwrap_or(url.as_str())
+            ),
+        )),
+    }
+}
+
+// ============================================
+// FETCH
+// ============================================
+
+/// Whether `len` bytes exceed a limit.
+fn exceeds(len: usize, max: u64) -> bool {
+    u64::try_from(len).map_or(true, |len| len > max)
+}
+
+/// Client that only follows redirects the policy allows.
+fn client_for(policy: &FetchPolicy) -> Result<reqwest::Client, FetchError> {
+    let redirect_policy = policy.clone();
+    let redirect = reqwest::redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            return attempt.error(format!("More than {MAX_REDIRECTS} redirects"));
+        }
+        if let Err(e) = offline::check_url(attempt.url()) {
+            return attempt.error(format!("Redirect refused: {e}"));
+        }
+        match check_domain(&redirect_policy, attempt.url()) {
+            Ok(()) => attempt.follow(),
+            Err(e) => attempt.error(format!("Redirect refused: {e}")),
+        }
+    });
+    proxy::apply(reqwest::Client::builder())
+        .redirect(redirect)
+        .build()
+        .map_err(|e| FetchError::new("FETCH_FAILED", format!("Failed to create client: {e}")))
+}
+
+/// Make a request within a policy.
+///
+/// # Returns
+///
+/// The response, whatever its status, or a `FetchError` if the request
+/// was refused, timed out, failed, or its response was too large.
+pub async fn fetch(
+    policy: &FetchPolicy,
+    request: FetchRequest,
+) -> Result<FetchResponse, FetchError> {
+    let invalid = |message: String| FetchError::new("INVALID_REQUEST", message);
+    let url = reqwest::Url::parse(&request.url)
+        .map_err(|e| invalid(format!("Invalid URL {}: {e}", request.url)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(format!("Unsupported URL scheme: {}", url.scheme())));
+    }
+    offline::check_url(&url).map_err(|e| FetchError::new("OFFLINE", e))?;
+    check_domain(policy, &url)?;
+
+    let method = request.method.as_deref().unwrap_or("GET").to_uppercase();
+    let method = reqwest::Method::from_bytes(method.as_bytes())
+        .map_err(|_| invalid(format!("Invalid HTTP method: {method}")))?;
+    if request
+        .body
+        .as_ref()
+        .is_some_and(|body| exceeds(body.len(), policy.max_request_bytes))
+    {
+        return Err(FetchError::new(
+            "REQUEST_TOO_LARGE",
+            format!(
+                "Request body is larger than {} bytes",
+                policy.max_request_bytes
+            ),
+        ));
+    }
+
+    let timeout_secs = request
+        .timeout_secs
+        .map_or(policy.timeout_secs, |secs| secs.min(policy.timeout_secs));
+    let mut builder = client_for(policy)?
+        .request(method, url)
+        .timeout(Duration::from_secs(timeout_secs));
+    for (name, value) in &request.headers {
+        builder = builder.header(name, value);
+    }
+    if let Some(body) = request.body {
+        builder = builder.body(body);
+    }
+
+    let failed = |e: reqwest::Error| {
+        if e.is_timeout() {
+            FetchError::new(
+                "FETCH_TIMEOUT",
+                format!("Request to {} timed out after {timeout_secs}s", request.url),
+            )
+        } else {
+            FetchError::new(
+                "FETCH_FAILED",
+                format!("Request to {} failed: {e}", request.url),
+            )
+        }
+    };
+    let too_large = || {
+        FetchError::new(
+            "RESPONSE_TOO_LARGE",
+            format!(
+                "Response from {} is larger than {} bytes",
+                request.url, policy.max_response_bytes
+            ),
+        )
+    };
+
+    let mut response = builder.send().await.map_err(failed)?;
+    if response
+        .content_length()
+        .is_some_and(|len| len > policy.max_response_bytes)
+    {
+        return Err(too_large());
+    }
+    let status = response.status().as_u16();
+    let headers: BTreeMap<String, String> = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(failed)? {
+        if exceeds(body.len() + chunk.len(), policy.max_response_bytes) {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(FetchResponse {
+        status,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allowed: &[&str]) -> FetchPolicy {
+        FetchPolicy {
+            allowed_domains: Some(allowed.iter().map(ToString::to_string).collect()),
+            timeout_secs: 5,
+            max_request_bytes: 16,
+            max_response_bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_domain_allowed() {
+        let allowed = vec!["api.example.com".to_string(), "*.cdn.net".to_string()];
+        assert!(domain_allowed(&allowed, "api.example.com"));
+        assert!(domain_allowed(&allowed, "API.Example.com."));
+        assert!(!domain_allowed(&allowed, "example.com"));
+        assert!(!domain_allowed(&allowed, "evil-api.example.com"));
+        assert!(domain_allowed(&allowed, "eu.cdn.net"));
+        assert!(domain_allowed(&allowed, "a.b.cdn.net"));
+        assert!(!domain_allowed(&allowed, "cdn.net"));
+        assert!(!domain_allowed(&allowed, "evilcdn.net"));
+        assert!(!domain_allowed(&[], "api.example.com"));
+    }
+
+    #[test]
+    fn test_validate_domain_pattern() {
+        assert!(validate_domain_pattern("example.com").is_ok());
+        assert!(validate_domain_pattern("*.example.com").is_ok());
+        assert!(validate_domain_pattern("127.0.0.1").is_ok());
+        assert!(validate_domain_pattern("").is_err());
+        assert!(validate_domain_pattern("*").is_err());
+        assert!(validate_domain_pattern("https://example.com").is_err());
+        assert!(validate_domain_pattern("example.com/path").is_err());
+        assert!(validate_domain_pattern("*.*.example.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_refused_before_sending() {
+        let allowed = policy(&["example.com"]);
+
+        let error = fetch(&allowed, FetchRequest::get("https://other.com/x"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, "DOMAIN_NOT_ALLOWED");
+
+        let error = fetch(&policy(&[]), FetchRequest::get("https://example.com"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, "DOMAIN_NOT_ALLOWED");
+
+        let error = fetch(&allowed, FetchRequest::get("file:///etc/passwd"))
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, "INVALID_REQUEST");
+
+        let request = FetchRequest {
+            method: Some("POST".to_string()),
+            body: Some("x".repeat(17)),
+            ..FetchRequest::get("https://example.com")
+        };
+        let error = fetch(&allowed, request).await.unwrap_err();
+        assert_eq!(error.code, "REQUEST_TOO_LARGE");
+    }
+}
@@ -0,0 +1,347 @@
+//! src-tauri/src/ipc/chaos.rs
+//! ==========================
+//! Fault injection on the plugin host transport, for resilience testing.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//! Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+//!
+//! Each `IpcManagerState` owns a `Chaos` layer that is off by default. Once
+//! configured (debug builds only, see `devtools_set_chaos`) it can:
+//!
+//! - delay each request by up to `latency_ms` before it is written
+//! - drop responses, so the request times out
+//! - corrupt lines read from the host, so they fail to parse
+//! - kill the host right after a request is written, so crash detection
+//!   and respawn run
+//!
+//! Rolls use a small seeded generator; set `seed` to repeat a run. Every
+//! injected fault is logged and counted.
+//!
+//! Usage:
+//!     ```rust
+//!     state.chaos().configure(ChaosConfig {
+//!         drop_rate: 0.1,
+//!         kill_rate: 0.01,
+//!         ..ChaosConfig::default()
+//!     })?;
+//!     let status = state.chaos().status();
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Largest injected latency
+const MAX_LATENCY_MS: u64 = 60_000;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Faults to inject (all off by default).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    /// Maximum delay added before each request (uniform from 0)
+    pub latency_ms: u64,
+    /// Probability that a response is dropped
+    pub drop_rate: f64,
+    /// Probability that a line from the host is corrupted
+    pub malformed_rate: f64,
+    /// Probability that the host is killed after a request is written
+    pub kill_rate: f64,
+    /// Seed for reproducible runs (random if unset)
+    pub seed: Option<u64>,
+}
+
+impl ChaosConfig {
+    /// Whether any fault is enabled.
+    pub fn is_active(&self) -> bool {
+        self.latency_ms > 0
+            || self.drop_rate > 0.0
+            || self.malformed_rate > 0.0
+            || self.kill_rate > 0.0
+    }
+
+    /// Check that rates are probabilities and the latency is bounded.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, rate) in [
+            ("drop_rate", self.drop_rate),
+            ("malformed_rate", self.malformed_rate),
+            ("kill_rate", self.kill_rate),
+        ] {
+            if !(0.0..=1.0).contains(&rate) {
+                return Err(format!("{name} must be between 0 and 1"));
+            }
+        }
+        if self.latency_ms > MAX_LATENCY_MS {
+            return Err(format!("latency_ms must be at most {MAX_LATENCY_MS}"));
+        }
+        Ok(())
+    }
+}
+
+/// Faults injected since the layer was last configured.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ChaosStats {
+    /// Requests delayed
+    pub delayed: u64,
+    /// Responses dropped
+    pub dropped: u64,
+    /// Lines corrupted
+    pub malformed: u64,
+    /// Hosts killed
+    pub killed: u64,
+}
+
+/// Current configuration and counts.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChaosStatus {
+    /// Faults being injected
+    pub config: ChaosConfig,
+    /// Faults injected so far
+    pub stats: ChaosStats,
+}
+
+// ============================================
+// CHAOS LAYER
+// ============================================
+
+/// Configuration, counts, and generator state.
+#[derive(Default)]
+struct ChaosState {
+    /// Faults to inject
+    config: ChaosConfig,
+    /// Faults injected so far
+    stats: ChaosStats,
+    /// Generator state
+    rng: u64,
+}
+
+impl ChaosState {
+    /// Next value of a splitmix64 sequence.
+    fn next_u64(&mut self) -> u64 {
+        self.rng = self.rng.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.rng;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// True with probability `rate`.
+    fn roll(&mut self, rate: f64) -> bool {
+        if rate <= 0.0 {
+            return false;
+        }
+        let high = u32::try_from(self.next_u64() >> 32).unwrap_or(u32::MAX);
+        f64::from(high) / (f64::from(u32::MAX) + 1.0) < rate
+    }
+}
+
+/// Fault injection layer of one plugin host.
+///
+/// Cloning shares the layer.
+#[derive(Clone, Default)]
+pub struct Chaos {
+    /// Set while any fault is enabled (checked before locking)
+    active: Arc<AtomicBool>,
+    /// Configuration, counts, and generator
+    state: Arc<Mutex<ChaosState>>,
+}
+
+impl Chaos {
+    /// Create a layer that injects nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the configuration and reset the counts.
+    pub fn configure(&self, config: ChaosConfig) -> Result<(), String> {
+        config.validate()?;
+        let active = config.is_active();
+        if active {
+            log::warn!("Fault injection enabled: {config:?}");
+        } else {
+            log::info!("Fault injection disabled");
+        }
+        let seed = config
+            .seed
+            .unwrap_or_else(|| uuid::Uuid::new_v4().as_u64_pair().0);
+        *self.state.lock().unwrap() = ChaosState {
+            config,
+            stats: ChaosStats::default(),
+            rng: seed,
+        };
+        self.active.store(active, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Current configuration and counts.
+    pub fn status(&self) -> ChaosStatus {
+        let state = self.state.lock().unwrap();
+        ChaosStatus {
+            config: state.config.clone(),
+            stats: state.stats.clone(),
+        }
+    }
+
+    /// Run `inject` on the state if any fault is enabled.
+    fn with_state<T: Default>(&self, inject: impl FnOnce(&mut ChaosState) -> T) -> T {
+        if !self.active.load(Ordering::Relaxed) {
+            return T::default();
+        }
+        inject(&mut self.state.lock().unwrap())
+    }
+
+    /// Delay to add before writing a request, if any.
+    pub fn latency(&self) -> Option<Duration> {
+        self.with_state(|state| {
+            let max = state.config.latency_ms;
+            if max == 0 {
+                return None;
+            }
+            let delay = state.next_u64() % (max + 1);
+            state.stats.delayed += 1;
+            Some(Duration::from_millis(delay))
+        })
+    }
+
+    /// Whether to drop the response to request `id`.
+    pub fn drop_response(&self, id: u64) -> bool {
+        self.with_state(|state| {
+            let dropped = state.roll(state.config.drop_rate);
+            if dropped {
+                state.stats.dropped += 1;
+                log::warn!("Fault injection: dropped response to request {id}");
+            }
+            dropped
+        })
+    }
+
+    /// Corrupt a line read from the host, returning whether it was changed.
+    pub fn corrupt(&self, line: &mut String) -> bool {
+        self.with_state(|state| {
+            if !state.roll(state.config.malformed_rate) {
+                return false;
+            }
+            state.stats.malformed += 1;
+            log::warn!("Fault injection: corrupted a host message");
+            // Cut the message in half (on a char boundary) so it can't parse
+            let mut end = line.trim_end().len() / 2;
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            line.truncate(end);
+            line.push_str("\u{fffd}{\n");
+            true
+        })
+    }
+
+    /// Whether to kill the host after writing a request.
+    pub fn kill(&self) -> bool {
+        self.with_state(|state| {
+            let kill = state.roll(state.config.kill_rate);
+            if kill {
+                state.stats.killed += 1;
+            }
+            kill
+        })
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ChaosConfig::default().validate().is_ok());
+        assert!(!ChaosConfig::default().is_active());
+
+        let config = ChaosConfig {
+            drop_rate: 1.5,
+            ..ChaosConfig::default()
+        };
+        assert!(config.validate().is_err());
+        let config = ChaosConfig {
+            latency_ms: MAX_LATENCY_MS + 1,
+            ..ChaosConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_inactive_layer_injects_nothing() {
+        let chaos = Chaos::new();
+        let mut line = r#"{"jsonrpc":"2.0","id":1,"result":null}"#.to_string();
+        assert_eq!(chaos.latency(), None);
+        assert!(!chaos.drop_response(1));
+        assert!(!chaos.corrupt(&mut line));
+        assert!(!chaos.kill());
+        assert_eq!(chaos.status().stats, ChaosStats::default());
+    }
+
+    #[test]
+    fn test_certain_faults_are_injected_and_counted() {
+        let chaos = Chaos::new();
+        chaos
+            .configure(ChaosConfig {
+                latency_ms: 50,
+                drop_rate: 1.0,
+                malformed_rate: 1.0,
+                kill_rate: 1.0,
+                seed: Some(7),
+            })
+            .unwrap();
+
+        assert!(chaos.latency().unwrap() <= Duration::from_millis(50));
+        assert!(chaos.drop_response(1));
+        let mut line = r#"{"jsonrpc":"2.0","id":1,"result":"héllo"}"#.to_string();
+        assert!(chaos.corrupt(&mut line));
+        assert!(serde_json::from_str::<serde_json::Value>(&line).is_err());
+        assert!(chaos.kill());
+
+        let stats = chaos.status().stats;
+        assert_eq!(
+            stats,
+            ChaosStats {
+                delayed: 1,
+                dropped: 1,
+                malformed: 1,
+                killed: 1,
+            }
+        );
+
+        chaos.configure(ChaosConfig::default()).unwrap();
+        assert!(!chaos.kill());
+        assert_eq!(chaos.status().stats, ChaosStats::default());
+    }
+
+    #[test]
+    fn test_seeded_rolls_repeat() {
+        let config = ChaosConfig {
+            drop_rate: 0.5,
+            seed: Some(42),
+            ..ChaosConfig::default()
+        };
+        let rolls = || {
+            let chaos = Chaos::new();
+            chaos.configure(config.clone()).unwrap();
+            (0..64)
+                .map(|id| chaos.drop_response(id))
+                .collect::<Vec<_>>()
+        };
+        let first = rolls();
+        assert_eq!(first, rolls());
+        assert!(first.contains(&true) && first.contains(&false));
+    }
+}
@@ -0,0 +1,556 @@
+//! src-tauri/src/ipc/codegen.rs
+//! ============================
+//! TypeScript and Rust types generated from the IPC method schemas.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Each method with a schema in the `SchemaRegistry` gets a `<Method>Params`
+//! and/or `<Method>Result` type, named after the method (`plugin/load` ->
+//! `PluginLoadParams`). Object schemas with `properties` become TypeScript
+//! interfaces and Rust structs; fields missing from `required` are optional.
+//! The TypeScript output also has an `IpcMethods` map from method name to
+//! its params and result, for typing `ipc_call` wrappers.
+//!
+//! The supported subset of JSON Schema is the one method schemas use:
+//! `type` (single or list), `properties`, `required`, `items`, `enum`,
+//! `const`, `anyOf`/`oneOf`, and `description`. Anything else becomes
+//! `unknown` / `serde_json::Value`.
+//!
+//! Usage:
+//!     ```rust
+//!     let generated = codegen::generate(&schemas.snapshot());
+//!     fs::write(dir.join(TYPESCRIPT_FILE), &generated.typescript)?;
+//!     fs::write(dir.join(RUST_FILE), &generated.rust)?;
+//!     ```
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+
+use super::schemas::MethodSchemas;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Name of the generated TypeScript file
+pub const TYPESCRIPT_FILE: &str = "ipcMethods.generated.ts";
+
+/// Name of the generated Rust file
+pub const RUST_FILE: &str = "ipc_methods.rs";
+
+/// First line of every generated file
+const GENERATED_HEADER: &str =
+    "// Generated from the IPC method schemas by devtools_generate_ipc_types. Do not edit.";
+
+/// Rust keywords that can be used as raw identifiers
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "yield",
+];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Generated sources.
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedTypes {
+    /// TypeScript module
+    pub typescript: String,
+    /// Rust module
+    pub rust: String,
+}
+
+/// Generate both languages.
+pub fn generate(schemas: &BTreeMap<String, MethodSchemas>) -> GeneratedTypes {
+    GeneratedTypes {
+        typescript: typescript(schemas),
+        rust: rust(schemas),
+    }
+}
+
+// ============================================
+// NAMING
+// ============================================
+
+/// Type name for a method (`plugin/load` -> `PluginLoad`).
+pub fn type_name(method: &str) -> String {
+    let name = pascal_case(method);
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("Method{name}")
+    }
+}
+
+/// Join the alphanumeric runs of `text`, capitalizing each.
+fn pascal_case(text: &str) -> String {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Rust field name for a property (`sampleRate` -> `sample_rate`).
+fn field_name(property: &str) -> String {
+    let mut name = String::with_capacity(property.len());
+    let mut previous_lower = false;
+    for c in property.chars() {
+        if c.is_ascii_uppercase() {
+            if previous_lower {
+                name.push('_');
+            }
+            name.push(c.to_ascii_lowercase());
+            previous_lower = false;
+        } else if c.is_ascii_alphanumeric() {
+            name.push(c);
+            previous_lower = true;
+        } else {
+            name.push('_');
+            previous_lower = false;
+        }
+    }
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    match name.as_str() {
+        "self" | "super" | "crate" | "_" => format!("{name}_"),
+        keyword if RUST_KEYWORDS.contains(&keyword) => format!("r#{name}"),
+        _ => name,
+    }
+}
+
+/// Whether `key` can be written unquoted as a TypeScript property name.
+fn is_ts_identifier(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// The schema's `description`, if any.
+fn description(schema: &Value) -> Option<&str> {
+    schema.get("description").and_then(Value::as_str)
+}
+
+/// Property names listed in `required`.
+fn required(object: &Map<String, Value>) -> HashSet<&str> {
+    object
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+/// The non-empty `properties` of an object schema.
+fn properties(object: &Map<String, Value>) -> Option<&Map<String, Value>> {
+    object
+        .get("properties")
+        .and_then(Value::as_object)
+        .filter(|properties| !properties.is_empty())
+}
+
+// ============================================
+// TYPESCRIPT
+// ============================================
+
+/// TypeScript module with a type per method schema and the `IpcMethods` map.
+pub fn typescript(schemas: &BTreeMap<String, MethodSchemas>) -> String {
+    let mut out = format!("{GENERATED_HEADER}\n");
+    let mut methods = String::new();
+
+    for (method, schemas) in schemas {
+        let base = type_name(method);
+        let mut entry = Vec::new();
+        for (part, suffix, schema) in [
+            ("params", "Params", &schemas.params),
+            ("result", "Result", &schemas.result),
+        ] {
+            let Some(schema) = schema else {
+                entry.push(format!("{part}: unknown"));
+                continue;
+            };
+            let name = format!("{base}{suffix}");
+            let ty = ts_type(schema, 0);
+            out.push('\n');
+            if let Some(text) = description(schema) {
+                let _ = writeln!(out, "/** {text} */");
+            }
+            if ty.starts_with('{') {
+                let _ = writeln!(out, "export interface {name} {ty}");
+            } else {
+                let _ = writeln!(out, "export type {name} = {ty};");
+            }
+            entry.push(format!("{part}: {name}"));
+        }
+        let _ = writeln!(
+            methods,
+            "  {}: {{ {} }};",
+            Value::from(method.as_str()),
+            entry.join("; ")
+        );
+    }
+
+    out.push_str("\n/** Params and result of each method with a schema */\n");
+    let _ = write!(out, "export interface IpcMethods {{\n{methods}}}\n");
+    out
+}
+
+/// TypeScript type of a schema; `indent` is the nesting level of inline objects.
+fn ts_type(schema: &Value, indent: usize) -> String {
+    let Some(object) = schema.as_object() else {
+        return if schema.as_bool() == Some(false) {
+            "never".to_string()
+        } else {
+            "unknown".to_string()
+        };
+    };
+    if let Some(value) = object.get("const") {
+        return value.to_string();
+    }
+    if let Some(values) = object.get("enum").and_then(Value::as_array) {
+        return union(values.iter().map(Value::to_string).collect());
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(options) = object.get(key).and_then(Value::as_array) {
+            return union(
+                options
+                    .iter()
+                    .map(|option| ts_type(option, indent))
+                    .collect(),
+            );
+        }
+    }
+    match object.get("type") {
+        Some(Value::String(kind)) => ts_kind(kind, object, indent),
+        Some(Value::Array(kinds)) => union(
+            kinds
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|kind| ts_kind(kind, object, indent))
+                .collect(),
+        ),
+        _ if properties(object).is_some() => ts_object(object, indent),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// TypeScript type for one `type` keyword value.
+fn ts_kind(kind: &str, object: &Map<String, Value>, indent: usize) -> String {
+    match kind {
+        "string" => "string".to_string(),
+        "number" | "integer" => "number".to_string(),
+        "boolean" => "boolean".to_string(),
+        "null" => "null".to_string(),
+        "array" => {
+            let item = object
+                .get("items")
+                .map_or_else(|| "unknown".to_string(), |items| ts_type(items, indent));
+            if item.contains(" | ") {
+                format!("({item})[]")
+            } else {
+                format!("{item}[]")
+            }
+        }
+        "object" => ts_object(object, indent),
+        _ => "unknown".to_string(),
+    }
+}
+
+/// Inline TypeScript object type.
+fn ts_object(object: &Map<String, Value>, indent: usize) -> String {
+    let Some(properties) = properties(object) else {
+        return "Record<string, unknown>".to_string();
+    };
+    let required = required(object);
+    let pad = "  ".repeat(indent + 1);
+    let mut out = "{\n".to_string();
+    for (key, schema) in properties {
+        if let Some(text) = description(schema) {
+            let _ = writeln!(out, "{pad}/** {text} */");
+        }
+        let name = if is_ts_identifier(key) {
+            key.clone()
+        } else {
+            Value::from(key.as_str()).to_string()
+        };
+        let optional = if required.contains(key.as_str()) {
+            ""
+        } else {
+            "?"
+        };
+        let _ = writeln!(
+            out,
+            "{pad}{name}{optional}: {};",
+            ts_type(schema, indent + 1)
+        );
+    }
+    out.push_str(&"  ".repeat(indent));
+    out.push('}');
+    out
+}
+
+/// Join TypeScript types with `|`, dropping repeats.
+fn union(mut types: Vec<String>) -> String {
+    let mut seen = HashSet::new();
+    types.retain(|ty| seen.insert(ty.clone()));
+    if types.is_empty() {
+        "never".to_string()
+    } else {
+        types.join(" | ")
+    }
+}
+
+// ============================================
+// RUST
+// ============================================
+
+/// Rust module with a struct or alias per method schema.
+pub fn rust(schemas: &BTreeMap<String, MethodSchemas>) -> String {
+    let mut generator = RustGenerator::default();
+    for (method, schemas) in schemas {
+        let base = type_name(method);
+        for (suffix, schema) in [("Params", &schemas.params), ("Result", &schemas.result)] {
+            let Some(schema) = schema else {
+                continue;
+            };
+            let name = format!("{base}{suffix}");
+            let ty = generator.rust_type(schema, &name);
+            if ty != name {
+                let doc = description(schema).map(doc_comment).unwrap_or_default();
+                generator
+                    .items
+                    .push(format!("{doc}pub type {name} = {ty};\n"));
+            }
+        }
+    }
+
+    let mut out = format!(
+        "{GENERATED_HEADER}\n\n#![allow(dead_code)]\n\nuse serde::{{Deserialize, Serialize}};\n"
+    );
+    for item in generator.items {
+        out.push('\n');
+        out.push_str(&item);
+    }
+    out
+}
+
+/// `///` doc comment line.
+fn doc_comment(text: &str) -> String {
+    format!("/// {text}\n")
+}
+
+/// Collects the Rust items (structs before the types that use them).
+#[derive(Default)]
+struct RustGenerator {
+    /// Generated items, in order
+    items: Vec<String>,
+}
+
+impl RustGenerator {
+    /// Rust type of a schema; object schemas become a struct called `name`.
+    fn rust_type(&mut self, schema: &Value, name: &str) -> String {
+        const VALUE: &str = "serde_json::Value";
+        let Some(object) = schema.as_object() else {
+            return VALUE.to_string();
+        };
+        if ["const", "enum", "anyOf", "oneOf"]
+            .iter()
+            .any(|key| object.contains_key(*key))
+        {
+            return VALUE.to_string();
+        }
+        match object.get("type") {
+            Some(Value::String(kind)) => self.rust_kind(kind, object, name),
+            // Only `[T, "null"]` maps to a Rust type
+            Some(Value::Array(kinds)) => {
+                let kinds: Vec<&str> = kinds.iter().filter_map(Value::as_str).collect();
+                match kinds.as_slice() {
+                    [kind, "null"] | ["null", kind] => {
+                        format!("Option<{}>", self.rust_kind(kind, object, name))
+                    }
+                    [kind] => self.rust_kind(kind, object, name),
+                    _ => VALUE.to_string(),
+                }
+            }
+            _ if properties(object).is_some() => self.rust_kind("object", object, name),
+            _ => VALUE.to_string(),
+        }
+    }
+
+    /// Rust type for one `type` keyword value.
+    fn rust_kind(&mut self, kind: &str, object: &Map<String, Value>, name: &str) -> String {
+        match kind {
+            "string" => "String".to_string(),
+            "integer" => "i64".to_string(),
+            "number" => "f64".to_string(),
+            "boolean" => "bool".to_string(),
+            "null" => "()".to_string(),
+            "array" => {
+                let item = object.get("items").map_or_else(
+                    || "serde_json::Value".to_string(),
+                    |items| self.rust_type(items, &format!("{name}Item")),
+                );
+                format!("Vec<{item}>")
+            }
+            "object" => match properties(object) {
+                Some(properties) => {
+                    self.rust_struct(name, object, properties);
+                    name.to_string()
+                }
+                None => "serde_json::Map<String, serde_json::Value>".to_string(),
+            },
+            _ => "serde_json::Value".to_string(),
+        }
+    }
+
+    /// Add a struct for an object schema (after any structs its fields need).
+    fn rust_struct(
+        &mut self,
+        name: &str,
+        object: &Map<String, Value>,
+        properties: &Map<String, Value>,
+    ) {
+        let required = required(object);
+        let mut fields = String::new();
+        for (key, schema) in properties {
+            let field = field_name(key);
+            let mut ty = self.rust_type(schema, &format!("{name}{}", pascal_case(key)));
+            if let Some(text) = description(schema) {
+                let _ = write!(fields, "    {}", doc_comment(text));
+            }
+            if field.trim_start_matches("r#") != key {
+                let _ = writeln!(
+                    fields,
+                    "    #[serde(rename = {})]",
+                    Value::from(key.as_str())
+                );
+            }
+            if !required.contains(key.as_str()) {
+                if !ty.starts_with("Option<") {
+                    ty = format!("Option<{ty}>");
+                }
+                fields
+                    .push_str("    #[serde(default, skip_serializing_if = \"Option::is_none\")]\n");
+            }
+            let _ = writeln!(fields, "    pub {field}: {ty},");
+        }
+
+        let doc = object
+            .get("description")
+            .and_then(Value::as_str)
+            .map(doc_comment)
+            .unwrap_or_default();
+        let derive = "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]";
+        self.items
+            .push(format!("{doc}{derive}\npub struct {name} {{\n{fields}}}\n"));
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn plugin_load() -> BTreeMap<String, MethodSchemas> {
+        BTreeMap::from([
+            (
+                "plugin/load".to_string(),
+                MethodSchemas {
+                    params: Some(json!({
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {
+                            "name": {"type": "string", "description": "Plugin name"},
+                            "config": {
+                                "type": "object",
+                                "properties": {"sampleRate": {"type": "integer"}}
+                            },
+                            "permissions": {"type": "array", "items": {"type": "string"}}
+                        }
+                    })),
+                    result: None,
+                },
+            ),
+            (
+                "ping".to_string(),
+                MethodSchemas {
+                    params: None,
+                    result: Some(json!({"const": "pong"})),
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(type_name("plugin/load"), "PluginLoad");
+        assert_eq!(type_name("events/set_topics"), "EventsSetTopics");
+        assert_eq!(type_name("2fa/check"), "Method2faCheck");
+        assert_eq!(field_name("sampleRate"), "sample_rate");
+        assert_eq!(field_name("type"), "r#type");
+        assert_eq!(field_name("content-type"), "content_type");
+        assert_eq!(field_name("self"), "self_");
+    }
+
+    #[test]
+    fn test_typescript() {
+        let ts = typescript(&plugin_load());
+        assert!(ts.starts_with(GENERATED_HEADER));
+        assert!(ts.contains("export type PingResult = \"pong\";"));
+        assert!(ts.contains("export interface PluginLoadParams {\n"));
+        assert!(ts.contains("  /** Plugin name */\n  name: string;\n"));
+        assert!(ts.contains("  config?: {\n    sampleRate?: number;\n  };\n"));
+        assert!(ts.contains("  permissions?: string[];\n"));
+        assert!(ts.contains("  \"plugin/load\": { params: PluginLoadParams; result: unknown };\n"));
+        assert!(ts.contains("  \"ping\": { params: unknown; result: PingResult };\n"));
+    }
+
+    #[test]
+    fn test_typescript_unions() {
+        assert_eq!(ts_type(&json!({"enum": ["a", "b"]}), 0), "\"a\" | \"b\"");
+        assert_eq!(
+            ts_type(&json!({"type": ["string", "null"]}), 0),
+            "string | null"
+        );
+        let list = json!({"type": "array", "items": {"type": ["integer", "null"]}});
+        assert_eq!(ts_type(&list, 0), "(number | null)[]");
+        assert_eq!(
+            ts_type(&json!({"type": "object"}), 0),
+            "Record<string, unknown>"
+        );
+        assert_eq!(ts_type(&json!(true), 0), "unknown");
+    }
+
+    #[test]
+    fn test_rust() {
+        let rs = rust(&plugin_load());
+        assert!(rs.contains("pub type PingResult = serde_json::Value;"));
+        assert!(rs.contains("pub struct PluginLoadParamsConfig {\n"));
+        assert!(rs.contains(
+            "    #[serde(rename = \"sampleRate\")]\n    \
+             #[serde(default, skip_serializing_if = \"Option::is_none\")]\n    \
+             pub sample_rate: Option<i64>,\n"
+        ));
+        assert!(rs.contains("    /// Plugin name\n    pub name: String,\n"));
+        assert!(rs.contains("    pub config: Option<PluginLoadParamsConfig>,\n"));
+        assert!(rs.contains("    pub permissions: Option<Vec<String>>,\n"));
+        // Nested structs come before the struct that uses them
+        let nested = rs.find("pub struct PluginLoadParamsConfig").unwrap();
+        assert!(nested < rs.find("pub struct PluginLoadParams {").unwrap());
+    }
+}
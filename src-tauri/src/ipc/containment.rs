@@ -0,0 +1,181 @@
+//! src-tauri/src/ipc/containment.rs
+//! ================================
+//! Reloading plugins that keep failing.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//! Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+//!
+//! A plugin that raised an exception (`PLUGIN_EXCEPTION`) or an internal
+//! error (`INTERNAL_ERROR`) is often left in a broken state while the host
+//! and the other plugins are fine. Each `IpcManagerState` counts such
+//! failures per plugin; after `plugin_reload_failures` in a row
+//! (`ipc.plugin_reload_failures`, 0 disables) it unloads the plugin and
+//! loads it again with the params of its last load, and raises a
+//! `PluginReloaded` event (`ipc/plugin_reloaded`). Sandboxed plugins run in
+//! hosts of their own, so only that process is touched.
+//!
+//! Any other outcome (a result, or an error the plugin reported on purpose)
+//! resets the count. Failures during a reload don't start another one.
+//!
+//! Usage:
+//!     ```rust
+//!     if let Some(failures) = tracker.record("tts_kokoro", &result, 3) {
+//!         reload("tts_kokoro").await;
+//!         tracker.finish_reload("tts_kokoro");
+//!     }
+//!     ```
+
+use serde_json::value::RawValue;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+
+use super::response::error_codes;
+use super::IpcError;
+
+// ============================================
+// FAILURE CLASSIFICATION
+// ============================================
+
+/// Whether an error means the plugin itself broke.
+///
+/// Timeouts, lost hosts, and errors with other codes (bad params, missing
+/// models) are not held against the plugin.
+pub fn is_plugin_fault(error: &IpcError) -> bool {
+    matches!(
+        error,
+        IpcError::RpcError { code, .. }
+            if *code == error_codes::PLUGIN_EXCEPTION || *code == error_codes::INTERNAL_ERROR
+    )
+}
+
+// ============================================
+// FAILURE TRACKER
+// ============================================
+
+/// Consecutive failures and reloads in progress.
+#[derive(Default)]
+struct TrackerState {
+    /// Consecutive plugin faults by plugin name
+    failures: BTreeMap<String, u32>,
+    /// Plugins being reloaded
+    reloading: BTreeSet<String>,
+}
+
+/// Consecutive plugin faults of one plugin host.
+///
+/// Cloning shares the tracker.
+#[derive(Clone, Default)]
+pub struct FailureTracker {
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl FailureTracker {
+    /// Create a tracker without failures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Count the outcome of a call to `plugin`.
+    ///
+    /// # Returns
+    ///
+    /// The number of consecutive failures when they reached `threshold`
+    /// (0 disables) and the plugin should be reloaded now; the plugin is
+    /// then marked as reloading until `finish_reload`.
+    pub fn record(
+        &self,
+        plugin: &str,
+        result: &Result<Box<RawValue>, IpcError>,
+        threshold: u32,
+    ) -> Option<u32> {
+        let mut state = self.state.lock().unwrap();
+        if threshold == 0 || state.reloading.contains(plugin) {
+            return None;
+        }
+        match result {
+            Err(e) if is_plugin_fault(e) => {
+                let failures = state.failures.entry(plugin.to_string()).or_insert(0);
+                *failures += 1;
+                let failures = *failures;
+                if failures < threshold {
+                    return None;
+                }
+                state.failures.remove(plugin);
+                state.reloading.insert(plugin.to_string());
+                Some(failures)
+            }
+            _ => {
+                state.failures.remove(plugin);
+                None
+            }
+        }
+    }
+
+    /// Count failures of a reloaded plugin again.
+    pub fn finish_reload(&self, plugin: &str) {
+        self.state.lock().unwrap().reloading.remove(plugin);
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok() -> Result<Box<RawValue>, IpcError> {
+        Ok(RawValue::from_string("null".to_string()).unwrap())
+    }
+
+    fn fault() -> Result<Box<RawValue>, IpcError> {
+        Err(IpcError::RpcError {
+            code: error_codes::PLUGIN_EXCEPTION,
+            message: "Plugin exception: KeyError: 'voice'".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_is_plugin_fault() {
+        assert!(is_plugin_fault(&fault().unwrap_err()));
+        assert!(is_plugin_fault(&IpcError::RpcError {
+            code: error_codes::INTERNAL_ERROR,
+            message: "Internal error".to_string(),
+        }));
+        assert!(!is_plugin_fault(&IpcError::RpcError {
+            code: error_codes::INVALID_PARAMS,
+            message: "Missing text".to_string(),
+        }));
+        assert!(!is_plugin_fault(&IpcError::Timeout(30)));
+        assert!(!is_plugin_fault(&IpcError::SubprocessCrashed));
+    }
+
+    #[test]
+    fn test_reload_after_consecutive_failures() {
+        let tracker = FailureTracker::new();
+        assert_eq!(tracker.record("tts", &fault(), 3), None);
+        assert_eq!(tracker.record("tts", &fault(), 3), None);
+        // A success starts the count over
+        assert_eq!(tracker.record("tts", &ok(), 3), None);
+        assert_eq!(tracker.record("tts", &fault(), 3), None);
+        assert_eq!(tracker.record("stt", &fault(), 3), None);
+        assert_eq!(tracker.record("tts", &fault(), 3), None);
+        assert_eq!(tracker.record("tts", &fault(), 3), Some(3));
+
+        // Not counted while reloading
+        for _ in 0..5 {
+            assert_eq!(tracker.record("tts", &fault(), 3), None);
+        }
+        tracker.finish_reload("tts");
+        assert_eq!(tracker.record("tts", &fault(), 3), None);
+    }
+
+    #[test]
+    fn test_disabled() {
+        let tracker = FailureTracker::new();
+        for _ in 0..10 {
+            assert_eq!(tracker.record("tts", &fault(), 0), None);
+        }
+    }
+}
@@ -13,6 +13,12 @@
 //! - `HealthStatus` tracking with history
 //! - `SubprocessState` enum for lifecycle tracking
 //! - Automatic crash detection and recovery signaling
+//! - `ResourceUsage` samples (RSS, CPU%) with a memory warning threshold
+//! - `HealthEvent` broadcast of state changes and check results
+//! - Per-plugin health (`plugin/health` probes) aggregated into `HealthStatus`
+//! - `HostHeartbeat` notifications pushed by the host; missed heartbeats
+//!   degrade the subprocess sooner than failed pings, and a host that keeps
+//!   sending them while working on requests is busy rather than dead
 //!
 //! Dependencies:
 //!     - D030: mod.rs (`IpcError`, constants)
@@ -33,12 +39,17 @@
 //!     ```
 
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
 
-use super::{HEALTH_CHECK_INTERVAL_SECS, MAX_RESPAWN_ATTEMPTS};
+use super::{
+    HEALTH_CHECK_INTERVAL_SECS, MAX_CONSECUTIVE_FAILURES, MAX_MISSED_HEARTBEATS,
+    MAX_RESPAWN_ATTEMPTS,
+};
 
 // ============================================
 // SUBPROCESS STATE
@@ -156,6 +167,127 @@ impl HealthCheckResult {
     }
 }
 
+// ============================================
+// RESOURCE USAGE
+// ============================================
+
+/// Resource usage sample of the subprocess.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ResourceUsage {
+    /// Timestamp of the sample
+    pub timestamp: u64,
+    /// Resident set size in bytes
+    pub rss_bytes: u64,
+    /// CPU usage in percent (can exceed 100 on multi-core machines)
+    pub cpu_percent: f32,
+}
+
+impl ResourceUsage {
+    /// Create a sample stamped with the current time.
+    pub fn new(rss_bytes: u64, cpu_percent: f32) -> Self {
+        Self {
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            rss_bytes,
+            cpu_percent,
+        }
+    }
+}
+
+// ============================================
+// PLUGIN HEALTH
+// ============================================
+
+/// Plugin statuses (`PluginStatus` in contracts/base.py) that count as healthy
+const HEALTHY_PLUGIN_STATUSES: &[&str] = &["ready", "busy", "initializing"];
+
+/// Health reported by one loaded plugin through `plugin/health`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginHealth {
+    /// Plugin status (`ready`, `busy`, `error`, ...)
+    pub status: String,
+    /// Message from the plugin's health check
+    pub message: Option<String>,
+    /// Whether the status counts as healthy
+    pub is_healthy: bool,
+    /// Probe timestamp (Unix seconds)
+    pub checked_at: u64,
+}
+
+impl PluginHealth {
+    /// Create from a reported status.
+    pub fn new(status: impl Into<String>, message: Option<String>) -> Self {
+        let status = status.into();
+        Self {
+            is_healthy: HEALTHY_PLUGIN_STATUSES.contains(&status.as_str()),
+            status,
+            message,
+            checked_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+
+    /// Parse a `plugin/health` result for all plugins
+    /// (`{"<name>": {"status": "ready", "message": "..."}}`).
+    pub fn parse_all(result: &Value) -> BTreeMap<String, Self> {
+        result
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, health)| {
+                let status = health
+                    .get("status")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown");
+                let message = health
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                (name.clone(), Self::new(status, message))
+            })
+            .collect()
+    }
+}
+
+// ============================================
+// HOST HEARTBEAT
+// ============================================
+
+/// Heartbeat pushed by the plugin host every few seconds
+/// (`$/heartbeat` notification).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostHeartbeat {
+    /// Sequence number, counting from 1 in each host process
+    #[serde(default)]
+    pub seq: u64,
+    /// Requests the host is working on
+    #[serde(default)]
+    pub pending_requests: u64,
+    /// Names of the loaded plugins
+    #[serde(default)]
+    pub loaded_plugins: Vec<String>,
+    /// Receipt timestamp (Unix seconds)
+    #[serde(default)]
+    pub received_at: u64,
+}
+
+impl HostHeartbeat {
+    /// Parse the params of a `$/heartbeat` notification, stamped with the
+    /// current time.
+    pub fn from_params(params: &Value) -> Option<Self> {
+        let mut heartbeat: Self = serde_json::from_value(params.clone()).ok()?;
+        heartbeat.received_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        Some(heartbeat)
+    }
+}
+
 // ============================================
 // HEALTH STATUS
 // ============================================
@@ -183,6 +315,16 @@ pub struct HealthStatus {
     pub uptime_secs: Option<u64>,
     /// Current respawn attempt count
     pub respawn_attempts: u32,
+    /// Latest resource usage sample
+    pub resource_usage: Option<ResourceUsage>,
+    /// Latest probe result per loaded plugin
+    pub plugins: BTreeMap<String, PluginHealth>,
+    /// Names of plugins that reported an unhealthy status
+    pub degraded_plugins: Vec<String>,
+    /// Latest heartbeat from the host
+    pub last_heartbeat: Option<HostHeartbeat>,
+    /// Heartbeat intervals since the latest heartbeat
+    pub missed_heartbeats: u32,
 }
 
 impl Default for HealthStatus {
@@ -198,10 +340,40 @@ impl Default for HealthStatus {
             avg_latency_ms: None,
             uptime_secs: None,
             respawn_attempts: 0,
+            resource_usage: None,
+            plugins: BTreeMap::new(),
+            degraded_plugins: Vec::new(),
+            last_heartbeat: None,
+            missed_heartbeats: 0,
         }
     }
 }
 
+// ============================================
+// HEALTH EVENTS
+// ============================================
+
+/// Change observed by the health monitor, broadcast to subscribers
+/// (e.g. the persisted health history).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum HealthEvent {
+    /// Subprocess state changed
+    StateChanged {
+        from: SubprocessState,
+        to: SubprocessState,
+    },
+    /// A health check failed
+    CheckFailed {
+        /// Error message
+        error: String,
+        /// State when the check failed
+        state: SubprocessState,
+    },
+    /// A health check succeeded
+    CheckSucceeded,
+}
+
 // ============================================
 // HEALTH MONITOR
 // ============================================
@@ -265,6 +437,30 @@ pub struct HealthMonitor {
 
     /// Respawn attempt counter
     respawn_attempts: AtomicU64,
+
+    /// Latest resource usage sample
+    resource_usage: Arc<RwLock<Option<ResourceUsage>>>,
+
+    /// RSS above which a memory warning is raised (None disables)
+    memory_threshold_bytes: Option<u64>,
+
+    /// Broadcast of state changes and check results
+    events: broadcast::Sender<HealthEvent>,
+
+    /// Latest probe result per loaded plugin
+    plugin_health: Arc<RwLock<BTreeMap<String, PluginHealth>>>,
+
+    /// Interval of host heartbeats (None if the host sends none)
+    heartbeat_interval: Option<Duration>,
+
+    /// Consecutive missed heartbeats before marking degraded
+    max_missed_heartbeats: u32,
+
+    /// Latest heartbeat and when it arrived
+    last_heartbeat: Arc<RwLock<Option<(Instant, HostHeartbeat)>>>,
+
+    /// Heartbeat intervals since the latest heartbeat (or the start)
+    missed_heartbeats: AtomicU64,
 }
 
 impl HealthMonitor {
@@ -283,7 +479,7 @@ impl HealthMonitor {
         Self {
             state: Arc::new(RwLock::new(SubprocessState::NotStarted)),
             check_interval,
-            max_consecutive_failures: 3,
+            max_consecutive_failures: MAX_CONSECUTIVE_FAILURES,
             is_healthy: AtomicBool::new(false),
             consecutive_failures: AtomicU64::new(0),
             total_successes: AtomicU64::new(0),
@@ -294,6 +490,14 @@ impl HealthMonitor {
             last_latency: Arc::new(RwLock::new(None)),
             start_time: Arc::new(RwLock::new(None)),
             respawn_attempts: AtomicU64::new(0),
+            resource_usage: Arc::new(RwLock::new(None)),
+            memory_threshold_bytes: None,
+            events: broadcast::channel(64).0,
+            plugin_health: Arc::new(RwLock::new(BTreeMap::new())),
+            heartbeat_interval: None,
+            max_missed_heartbeats: MAX_MISSED_HEARTBEATS,
+            last_heartbeat: Arc::new(RwLock::new(None)),
+            missed_heartbeats: AtomicU64::new(0),
         }
     }
 
@@ -314,6 +518,25 @@ impl HealthMonitor {
         self
     }
 
+    /// Set the RSS threshold above which a memory warning is raised.
+    pub fn with_memory_threshold(mut self, bytes: Option<u64>) -> Self {
+        self.memory_threshold_bytes = bytes;
+        self
+    }
+
+    /// Expect host heartbeats every `interval` (None if the host sends
+    /// none), marking the subprocess degraded after `max_missed` are missed.
+    pub fn with_heartbeat(mut self, interval: Option<Duration>, max_missed: u32) -> Self {
+        self.heartbeat_interval = interval;
+        self.max_missed_heartbeats = max_missed;
+        self
+    }
+
+    /// Subscribe to state changes and check results.
+    pub fn subscribe(&self) -> broadcast::Receiver<HealthEvent> {
+        self.events.subscribe()
+    }
+
     /// Get current subprocess state.
     pub fn state(&self) -> SubprocessState {
         *self.state.read().unwrap()
@@ -326,6 +549,13 @@ impl HealthMonitor {
         *guard = state;
 
         log::info!("Subprocess state: {old_state} -> {state}");
+        if old_state != state {
+            // No subscribers is fine
+            let _ = self.events.send(HealthEvent::StateChanged {
+                from: old_state,
+                to: state,
+            });
+        }
 
         // Update health based on state
         match state {
@@ -358,6 +588,11 @@ impl HealthMonitor {
         self.check_interval
     }
 
+    /// Get the consecutive failures that mark the subprocess degraded.
+    pub fn max_failures(&self) -> u32 {
+        self.max_consecutive_failures
+    }
+
     /// Record a successful health check.
     ///
     /// # Arguments
@@ -374,6 +609,7 @@ impl HealthMonitor {
         // Add to history
         let result = HealthCheckResult::success(latency);
         self.add_to_history(result);
+        let _ = self.events.send(HealthEvent::CheckSucceeded);
 
         // Ensure state is Running if was Degraded
         let current_state = self.state();
@@ -397,6 +633,10 @@ impl HealthMonitor {
         // Add to history
         let result = HealthCheckResult::failure(&error);
         self.add_to_history(result);
+        let _ = self.events.send(HealthEvent::CheckFailed {
+            error: error.clone(),
+            state: self.state(),
+        });
 
         log::warn!("Health check failure #{failures}: {error}");
 
@@ -410,6 +650,168 @@ impl HealthMonitor {
         }
     }
 
+    /// Record a resource usage sample.
+    ///
+    /// # Returns
+    ///
+    /// `true` if this sample crossed the memory threshold (it was below or
+    /// unknown before), so callers warn once per crossing.
+    pub fn record_resource_usage(&self, usage: ResourceUsage) -> bool {
+        let previous = self.resource_usage.write().unwrap().replace(usage);
+
+        match self.memory_threshold_bytes {
+            Some(threshold) if usage.rss_bytes > threshold => {
+                let was_above = previous.is_some_and(|p| p.rss_bytes > threshold);
+                if !was_above {
+                    log::warn!(
+                        "Subprocess memory {} MB exceeds threshold {} MB",
+                        usage.rss_bytes / (1024 * 1024),
+                        threshold / (1024 * 1024)
+                    );
+                }
+                !was_above
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the latest resource usage sample.
+    pub fn resource_usage(&self) -> Option<ResourceUsage> {
+        *self.resource_usage.read().unwrap()
+    }
+
+    /// Get the memory warning threshold.
+    pub fn memory_threshold(&self) -> Option<u64> {
+        self.memory_threshold_bytes
+    }
+
+    /// Record a round of plugin probes, replacing the previous round
+    /// (plugins missing from it were unloaded).
+    ///
+    /// # Returns
+    ///
+    /// Plugins that became unhealthy or recovered, with their new health.
+    pub fn record_plugin_health(
+        &self,
+        results: BTreeMap<String, PluginHealth>,
+    ) -> Vec<(String, PluginHealth)> {
+        let mut current = self.plugin_health.write().unwrap();
+        let changed = results
+            .iter()
+            .filter(|(name, health)| {
+                let was_healthy = !current.get(*name).is_some_and(|p| !p.is_healthy);
+                was_healthy != health.is_healthy
+            })
+            .map(|(name, health)| (name.clone(), health.clone()))
+            .collect();
+        *current = results;
+        changed
+    }
+
+    /// Get the latest probe result per loaded plugin.
+    pub fn plugin_health(&self) -> BTreeMap<String, PluginHealth> {
+        self.plugin_health.read().unwrap().clone()
+    }
+
+    /// Get the interval of host heartbeats (None if the host sends none).
+    pub fn heartbeat_interval(&self) -> Option<Duration> {
+        self.heartbeat_interval
+    }
+
+    /// Record a heartbeat from the host.
+    ///
+    /// A subprocess degraded only by missed heartbeats (its pings still
+    /// pass) is running again.
+    pub fn record_heartbeat(&self, heartbeat: HostHeartbeat) {
+        log::trace!(
+            "Heartbeat #{}: {} pending, {} plugins",
+            heartbeat.seq,
+            heartbeat.pending_requests,
+            heartbeat.loaded_plugins.len()
+        );
+        *self.last_heartbeat.write().unwrap() = Some((Instant::now(), heartbeat));
+        let missed = self.missed_heartbeats.swap(0, Ordering::SeqCst);
+
+        let failures = self.consecutive_failures.load(Ordering::SeqCst);
+        if self.state() == SubprocessState::Degraded
+            && missed >= u64::from(self.max_missed_heartbeats)
+            && failures < u64::from(self.max_consecutive_failures)
+        {
+            log::info!("Heartbeats resumed after {missed} missed");
+            self.set_state(SubprocessState::Running);
+        }
+    }
+
+    /// Count the heartbeat intervals since the latest heartbeat (or the
+    /// start), marking the subprocess degraded when `max_missed` is reached.
+    ///
+    /// # Returns
+    ///
+    /// The number of missed heartbeats (0 without heartbeats or before the
+    /// subprocess started).
+    pub fn check_heartbeat(&self) -> u32 {
+        let Some(interval) = self.heartbeat_interval.filter(|i| i.as_millis() > 0) else {
+            return 0;
+        };
+        let since = self
+            .last_heartbeat
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|(at, _)| *at)
+            .or(*self.start_time.read().unwrap());
+        let Some(since) = since else {
+            return 0;
+        };
+
+        let missed = since.elapsed().as_millis() / interval.as_millis();
+        let missed = u64::try_from(missed).unwrap_or(u64::MAX);
+        let previous = self.missed_heartbeats.swap(missed, Ordering::SeqCst);
+
+        let max = u64::from(self.max_missed_heartbeats);
+        if max > 0 && previous < max && missed >= max {
+            let error = format!(
+                "No heartbeat for {}s ({missed} missed)",
+                since.elapsed().as_secs()
+            );
+            log::warn!("{error}");
+            let _ = self.events.send(HealthEvent::CheckFailed {
+                error,
+                state: self.state(),
+            });
+            self.is_healthy.store(false, Ordering::SeqCst);
+            if self.state() == SubprocessState::Running {
+                self.set_state(SubprocessState::Degraded);
+            }
+        }
+        u32::try_from(missed).unwrap_or(u32::MAX)
+    }
+
+    /// Whether the host is alive but working on requests: its heartbeats
+    /// are current and report pending requests.
+    ///
+    /// A ping that times out then says the host is busy, not dead.
+    pub fn host_busy(&self) -> bool {
+        let current = self.missed_heartbeats.load(Ordering::SeqCst)
+            < u64::from(self.max_missed_heartbeats.max(1));
+        current
+            && self
+                .last_heartbeat
+                .read()
+                .unwrap()
+                .as_ref()
+                .is_some_and(|(_, heartbeat)| heartbeat.pending_requests > 0)
+    }
+
+    /// Get the latest heartbeat from the host.
+    pub fn last_heartbeat(&self) -> Option<HostHeartbeat> {
+        self.last_heartbeat
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|(_, heartbeat)| heartbeat.clone())
+    }
+
     /// Add result to history ring buffer.
     fn add_to_history(&self, result: HealthCheckResult) {
         let mut history = self.recent_results.write().unwrap();
@@ -442,6 +844,12 @@ impl HealthMonitor {
             .map(|d| d.as_millis() as u64);
 
         let avg_latency = self.calculate_avg_latency();
+        let plugins = self.plugin_health();
+        let degraded_plugins = plugins
+            .iter()
+            .filter(|(_, health)| !health.is_healthy)
+            .map(|(name, _)| name.clone())
+            .collect();
 
         HealthStatus {
             state: self.state(),
@@ -454,6 +862,12 @@ impl HealthMonitor {
             avg_latency_ms: avg_latency,
             uptime_secs: uptime,
             respawn_attempts: self.respawn_attempts.load(Ordering::SeqCst) as u32,
+            resource_usage: self.resource_usage(),
+            plugins,
+            degraded_plugins,
+            last_heartbeat: self.last_heartbeat(),
+            missed_heartbeats: u32::try_from(self.missed_heartbeats.load(Ordering::SeqCst))
+                .unwrap_or(u32::MAX),
         }
     }
 
@@ -504,6 +918,10 @@ impl HealthMonitor {
         *self.last_success_time.write().unwrap() = None;
         *self.last_latency.write().unwrap() = None;
         *self.start_time.write().unwrap() = None;
+        *self.resource_usage.write().unwrap() = None;
+        self.plugin_health.write().unwrap().clear();
+        *self.last_heartbeat.write().unwrap() = None;
+        self.missed_heartbeats.store(0, Ordering::SeqCst);
     }
 
     /// Mark subprocess as started.
@@ -647,6 +1065,20 @@ mod tests {
         assert_eq!(results.len(), 5); // Max history is 5
     }
 
+    #[test]
+    fn test_memory_threshold_warns_once_per_crossing() {
+        let monitor = HealthMonitor::new(Duration::from_secs(30))
+            .with_memory_threshold(Some(1000));
+
+        assert!(!monitor.record_resource_usage(ResourceUsage::new(500, 1.0)));
+        assert!(monitor.record_resource_usage(ResourceUsage::new(1500, 1.0)));
+        assert!(!monitor.record_resource_usage(ResourceUsage::new(2000, 1.0)));
+        assert!(!monitor.record_resource_usage(ResourceUsage::new(800, 1.0)));
+        assert!(monitor.record_resource_usage(ResourceUsage::new(1200, 1.0)));
+
+        assert_eq!(monitor.status().resource_usage.unwrap().rss_bytes, 1200);
+    }
+
     #[test]
     fn test_respawn_counter() {
         let monitor = HealthMonitor::new(Duration::from_secs(30));
@@ -659,4 +1091,116 @@ mod tests {
         monitor.reset_respawn_counter();
         assert!(!monitor.respawn_limit_exceeded());
     }
+
+    #[test]
+    fn test_health_events() {
+        let monitor = HealthMonitor::new(Duration::from_secs(30));
+        let mut events = monitor.subscribe();
+
+        monitor.set_state(SubprocessState::Running);
+        monitor.set_state(SubprocessState::Running);
+        monitor.record_failure("Timeout");
+        monitor.record_success(Duration::from_millis(5));
+
+        assert_eq!(
+            events.try_recv().unwrap(),
+            HealthEvent::StateChanged {
+                from: SubprocessState::NotStarted,
+                to: SubprocessState::Running,
+            }
+        );
+        assert_eq!(
+            events.try_recv().unwrap(),
+            HealthEvent::CheckFailed {
+                error: "Timeout".to_string(),
+                state: SubprocessState::Running,
+            }
+        );
+        assert_eq!(events.try_recv().unwrap(), HealthEvent::CheckSucceeded);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_plugin_health_changes() {
+        let monitor = HealthMonitor::new(Duration::from_secs(30));
+        let round = |result: Value| monitor.record_plugin_health(PluginHealth::parse_all(&result));
+
+        let changed = round(serde_json::json!({
+            "tts": {"status": "ready", "message": "ok"},
+            "stt": {"status": "error", "message": "Model missing"}
+        }));
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].0, "stt");
+        assert_eq!(monitor.status().degraded_plugins, vec!["stt".to_string()]);
+
+        // Still failing: no new change; recovering is reported
+        let changed = round(serde_json::json!({
+            "stt": {"status": "error"},
+            "tts": {"status": "busy"}
+        }));
+        assert!(changed.is_empty());
+        let changed = round(serde_json::json!({"stt": {"status": "ready"}}));
+        assert!(changed[0].1.is_healthy);
+        assert_eq!(monitor.status().plugins.len(), 1);
+    }
+
+    #[test]
+    fn test_host_heartbeat_parse() {
+        let heartbeat = HostHeartbeat::from_params(&serde_json::json!({
+            "seq": 3,
+            "pending_requests": 2,
+            "loaded_plugins": ["stt", "tts"],
+            "timestamp": 1_700_000_000
+        }))
+        .unwrap();
+        assert_eq!(heartbeat.seq, 3);
+        assert_eq!(heartbeat.pending_requests, 2);
+        assert_eq!(heartbeat.loaded_plugins, ["stt", "tts"]);
+        assert!(heartbeat.received_at > 0);
+
+        assert_eq!(
+            HostHeartbeat::from_params(&serde_json::json!({})).map(|h| h.seq),
+            Some(0)
+        );
+        assert!(HostHeartbeat::from_params(&serde_json::json!({"seq": "x"})).is_none());
+    }
+
+    #[test]
+    fn test_missed_heartbeats() {
+        let monitor = HealthMonitor::new(Duration::from_secs(30))
+            .with_heartbeat(Some(Duration::from_millis(10)), 2);
+        assert_eq!(monitor.check_heartbeat(), 0);
+
+        monitor.set_state(SubprocessState::Starting);
+        monitor.set_state(SubprocessState::Running);
+        monitor.record_heartbeat(HostHeartbeat {
+            seq: 1,
+            pending_requests: 1,
+            loaded_plugins: Vec::new(),
+            received_at: 0,
+        });
+        assert!(monitor.host_busy());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(monitor.check_heartbeat() >= 2);
+        assert_eq!(monitor.state(), SubprocessState::Degraded);
+        assert!(!monitor.host_busy());
+        assert!(monitor.status().missed_heartbeats >= 2);
+
+        // A heartbeat brings the subprocess back
+        monitor.record_heartbeat(HostHeartbeat {
+            seq: 2,
+            pending_requests: 0,
+            loaded_plugins: Vec::new(),
+            received_at: 0,
+        });
+        assert_eq!(monitor.state(), SubprocessState::Running);
+        assert!(!monitor.host_busy());
+        assert_eq!(monitor.status().last_heartbeat.map(|h| h.seq), Some(2));
+
+        // Disabled without an interval
+        let monitor = HealthMonitor::new(Duration::from_secs(30));
+        monitor.set_state(SubprocessState::Running);
+        assert_eq!(monitor.check_heartbeat(), 0);
+    }
 }
@@ -0,0 +1,609 @@
+//! src-tauri/src/ipc/hosts.rs
+//! ==========================
+//! Registry of named plugin hosts.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//! Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+//!
+//! The default workspace's manager is the `"core"` host. Additional hosts
+//! (e.g. `"ml-gpu"` with `CUDA_VISIBLE_DEVICES=0`) are defined by a
+//! `HostSpec`: extra environment variables and, optionally, their own
+//! interpreter and interpreter flags. Everything else (module, timeouts,
+//! working directory, API key environment) comes from the core host's
+//! configuration when the host is started.
+//!
+//! Named hosts are started on first use (`ipc_call` / `plugin_call` with a
+//! `host` parameter) and shut down with the app. Specs are stored in
+//! `hosts.json` in the app config directory.
+//!
+//! A host can be sandboxed (`"isolation": "sandboxed"`, see `sandbox.rs`): it
+//! gets neither the core host's environment nor network access, and the
+//! project's `plugins` directory is read-only. Plugins that run sandboxed,
+//! by their manifest or the user's choice (stored in `plugin_isolation.json`),
+//! get a host of their own named `sandbox-<plugin>` (`sandbox_host`).
+//!
+//! Dependencies:
+//!     - D035: manager.rs (`IpcManagerState`, `IpcConfig`, `EnvProvider`)
+//!
+//! Usage:
+//!     ```rust
+//!     let hosts = HostRegistry::load(core_state.clone(), config_dir);
+//!     hosts.define("ml-gpu", HostSpec::default().with_env("CUDA_VISIBLE_DEVICES", "0"))?;
+//!     let manager = hosts.get("ml-gpu")?;
+//!     hosts.ensure_started("ml-gpu", &manager).await?;
+//!     let result = manager.call("plugin/list", json!({})).await?;
+//!
+//!     if hosts.isolation("untrusted") == Isolation::Sandboxed {
+//!         let (name, manager) = hosts.sandbox_host("untrusted");
+//!         hosts.ensure_started(&name, &manager).await?;
+//!     }
+//!     ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use super::manager::{EnvProvider, IpcConfig, IpcManagerState, LifecycleState};
+use super::sandbox::{
+    declared_isolation, sandbox_host_name, Isolation, SandboxConfig, SANDBOX_HOST_PREFIX,
+};
+use super::IpcError;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Name of the primary host (the default workspace's manager)
+pub const CORE_HOST: &str = "core";
+
+/// File name of the host specs inside the app config directory
+const HOSTS_FILE: &str = "hosts.json";
+
+/// File name of the user's plugin isolation choices inside the app config directory
+const ISOLATION_FILE: &str = "plugin_isolation.json";
+
+/// Maximum length of a host name
+const MAX_NAME_LEN: usize = 32;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// How a named host differs from the core host.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HostSpec {
+    /// Extra environment variables (e.g. `CUDA_VISIBLE_DEVICES`)
+    pub env: BTreeMap<String, String>,
+    /// Python executable (None uses the core host's)
+    pub python_path: Option<String>,
+    /// Interpreter flags (None uses the core host's)
+    pub interpreter_args: Option<Vec<String>>,
+    /// Isolation profile (sandboxed hosts get only `env`, no network, and a
+    /// read-only plugins directory)
+    pub isolation: Isolation,
+}
+
+impl HostSpec {
+    /// Add an environment variable.
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// Host summary returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct HostInfo {
+    /// Host name (used as the `host` command parameter)
+    pub name: String,
+    /// Spec (empty for the core host)
+    pub spec: HostSpec,
+    /// Lifecycle state (`UNINITIALIZED` until first use)
+    pub lifecycle_state: LifecycleState,
+    /// Whether this is the core host
+    pub is_core: bool,
+}
+
+/// How a plugin runs and why.
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginIsolation {
+    /// Plugin name
+    pub plugin: String,
+    /// Isolation its manifest asks for
+    pub declared: Isolation,
+    /// Isolation the user chose (overrides the manifest)
+    pub user: Option<Isolation>,
+    /// Isolation it runs with
+    pub effective: Isolation,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Validate a host name (lowercase letters, digits, `-`, `_`).
+fn validate_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid host name \"{name}\": use up to {MAX_NAME_LEN} lowercase letters, digits, \
+             '-' or '_'"
+        ))
+    }
+}
+
+// ============================================
+// HOST REGISTRY
+// ============================================
+
+/// Registry of named plugin hosts.
+#[derive(Clone)]
+pub struct HostRegistry {
+    /// The core host
+    core: IpcManagerState,
+    /// Specs keyed by host name
+    specs: Arc<RwLock<BTreeMap<String, HostSpec>>>,
+    /// Managers of named hosts used so far
+    managers: Arc<RwLock<HashMap<String, IpcManagerState>>>,
+    /// Serializes host starts so concurrent first calls start a host once
+    starting: Arc<tokio::sync::Mutex<()>>,
+    /// Specs file path (None disables persistence)
+    path: Option<PathBuf>,
+    /// User's isolation choices keyed by plugin name
+    isolation: Arc<RwLock<BTreeMap<String, Isolation>>>,
+    /// Isolation choices file path (None disables persistence)
+    isolation_path: Option<PathBuf>,
+}
+
+impl HostRegistry {
+    /// Load the specs from the app config directory.
+    pub fn load(core: IpcManagerState, config_dir: Option<PathBuf>) -> Self {
+        let path = config_dir.as_ref().map(|dir| dir.join(HOSTS_FILE));
+        let specs = path.as_deref().map(read_map).unwrap_or_default();
+        let isolation_path = config_dir.map(|dir| dir.join(ISOLATION_FILE));
+        let isolation = isolation_path.as_deref().map(read_map).unwrap_or_default();
+
+        Self {
+            core,
+            specs: Arc::new(RwLock::new(specs)),
+            managers: Arc::new(RwLock::new(HashMap::new())),
+            starting: Arc::new(tokio::sync::Mutex::new(())),
+            path,
+            isolation: Arc::new(RwLock::new(isolation)),
+            isolation_path,
+        }
+    }
+
+    /// Get the manager of a host, creating it (not started) on first use.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IpcManagerState)` - Handle to the host's manager
+    /// * `Err(String)` - Unknown host name
+    pub fn get(&self, name: &str) -> Result<IpcManagerState, String> {
+        if name == CORE_HOST {
+            return Ok(self.core.clone());
+        }
+
+        let mut managers = self.managers.write().unwrap();
+        if let Some(manager) = managers.get(name) {
+            return Ok(manager.clone());
+        }
+
+        let spec = self
+            .specs
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("Unknown host: {name}"))?;
+        let manager = self.new_manager(&spec);
+        managers.insert(name.to_string(), manager.clone());
+        Ok(manager)
+    }
+
+    /// Start a named host's manager if it is not running yet.
+    ///
+    /// The core host is started by the app and is left alone. On a
+    /// manager's first start its plugin events are relayed to the core
+    /// host's subscribers.
+    pub async fn ensure_started(
+        &self,
+        name: &str,
+        manager: &IpcManagerState,
+    ) -> Result<(), IpcError> {
+        if name == CORE_HOST {
+            return Ok(());
+        }
+
+        let _guard = self.starting.lock().await;
+        let lifecycle = manager.lifecycle_state().await;
+        if matches!(
+            lifecycle,
+            LifecycleState::Uninitialized | LifecycleState::Stopped
+        ) {
+            if lifecycle == LifecycleState::Uninitialized {
+                manager.relay_plugin_events(&self.core);
+            }
+            log::info!("Starting plugin host {name}");
+            manager.start().await?;
+        }
+        Ok(())
+    }
+
+    /// Define or replace a named host.
+    ///
+    /// # Returns
+    ///
+    /// The manager started with the previous spec, if any; the caller shuts
+    /// it down so the next call starts the host with the new spec.
+    pub fn define(&self, name: &str, spec: HostSpec) -> Result<Option<IpcManagerState>, String> {
+        if name == CORE_HOST {
+            return Err("The core host is configured through the settings".to_string());
+        }
+        if name.starts_with(SANDBOX_HOST_PREFIX) {
+            return Err(format!(
+                "Host names starting with {SANDBOX_HOST_PREFIX} are reserved for sandboxed plugins"
+            ));
+        }
+        validate_name(name)?;
+
+        self.specs.write().unwrap().insert(name.to_string(), spec);
+        self.persist()?;
+        log::info!("Plugin host defined: {name}");
+        Ok(self.managers.write().unwrap().remove(name))
+    }
+
+    /// Remove a named host.
+    ///
+    /// # Returns
+    ///
+    /// The host's manager, if it was used; the caller shuts it down.
+    pub fn remove(&self, name: &str) -> Result<Option<IpcManagerState>, String> {
+        if self.specs.write().unwrap().remove(name).is_none() {
+            return Err(format!("Unknown host: {name}"));
+        }
+        self.persist()?;
+        log::info!("Plugin host removed: {name}");
+        Ok(self.managers.write().unwrap().remove(name))
+    }
+
+    /// Managers of the named hosts used so far (for shutdown).
+    pub fn managers(&self) -> Vec<IpcManagerState> {
+        self.managers.read().unwrap().values().cloned().collect()
+    }
+
+    /// Build summaries for all hosts (core first).
+    pub async fn list(&self) -> Vec<HostInfo> {
+        let mut infos = vec![HostInfo {
+            name: CORE_HOST.to_string(),
+            spec: HostSpec::default(),
+            lifecycle_state: self.core.lifecycle_state().await,
+            is_core: true,
+        }];
+
+        let specs = self.specs.read().unwrap().clone();
+        for (name, spec) in specs {
+            let manager = self.managers.read().unwrap().get(&name).cloned();
+            let lifecycle_state = match manager {
+                Some(manager) => manager.lifecycle_state().await,
+                None => LifecycleState::Uninitialized,
+            };
+            infos.push(HostInfo {
+                name,
+                spec,
+                lifecycle_state,
+                is_core: false,
+            });
+        }
+
+        // Hosts of sandboxed plugins, once used
+        let mut sandboxes: Vec<(String, IpcManagerState)> = self
+            .managers
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| name.starts_with(SANDBOX_HOST_PREFIX))
+            .map(|(name, manager)| (name.clone(), manager.clone()))
+            .collect();
+        sandboxes.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, manager) in sandboxes {
+            infos.push(HostInfo {
+                name,
+                spec: sandbox_spec(),
+                lifecycle_state: manager.lifecycle_state().await,
+                is_core: false,
+            });
+        }
+        infos
+    }
+
+    /// Directory whose `plugins` folder holds the manifests.
+    fn project_root(&self) -> PathBuf {
+        self.core
+            .config()
+            .working_dir
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default()
+    }
+
+    /// How a plugin runs and why.
+    pub fn plugin_isolation(&self, plugin: &str) -> PluginIsolation {
+        let declared = declared_isolation(&self.project_root(), plugin);
+        let user = self.isolation.read().unwrap().get(plugin).copied();
+        PluginIsolation {
+            plugin: plugin.to_string(),
+            declared,
+            user,
+            effective: user.unwrap_or(declared),
+        }
+    }
+
+    /// Isolation a plugin runs with: the user's choice, else its manifest's.
+    pub fn isolation(&self, plugin: &str) -> Isolation {
+        self.plugin_isolation(plugin).effective
+    }
+
+    /// Set the user's isolation choice for a plugin (None follows the
+    /// manifest again). Applies the next time the plugin is loaded.
+    ///
+    /// # Returns
+    ///
+    /// The manager of the plugin's sandboxed host if the plugin no longer
+    /// runs sandboxed; the caller shuts it down.
+    pub fn set_isolation(
+        &self,
+        plugin: &str,
+        isolation: Option<Isolation>,
+    ) -> Result<Option<IpcManagerState>, String> {
+        {
+            let mut choices = self.isolation.write().unwrap();
+            match isolation {
+                Some(isolation) => choices.insert(plugin.to_string(), isolation),
+                None => choices.remove(plugin),
+            };
+        }
+        if let Some(ref path) = self.isolation_path {
+            write_map(path, &self.isolation.read().unwrap())?;
+        }
+        log::info!("Plugin {plugin} isolation set to {isolation:?}");
+
+        if self.isolation(plugin) == Isolation::Sandboxed {
+            return Ok(None);
+        }
+        Ok(self
+            .managers
+            .write()
+            .unwrap()
+            .remove(&sandbox_host_name(plugin)))
+    }
+
+    /// Managers of the sandboxed plugin hosts used so far.
+    pub fn sandbox_managers(&self) -> Vec<IpcManagerState> {
+        self.managers
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(name, _)| name.starts_with(SANDBOX_HOST_PREFIX))
+            .map(|(_, manager)| manager.clone())
+            .collect()
+    }
+
+    /// Get a plugin's sandboxed host, creating it (not started) on first use.
+    ///
+    /// # Returns
+    ///
+    /// The host name (for `ensure_started`) and its manager.
+    pub fn sandbox_host(&self, plugin: &str) -> (String, IpcManagerState) {
+        let name = sandbox_host_name(plugin);
+        let manager = self
+            .managers
+            .write()
+            .unwrap()
+            .entry(name.clone())
+            .or_insert_with(|| self.new_manager(&sandbox_spec()))
+            .clone();
+        (name, manager)
+    }
+
+    /// Create the manager of a named host.
+    ///
+    /// Request ids come from the core host's counter, so an id reserved
+    /// for an `ipc_call` stays valid when the call goes to a plugin's host.
+    fn new_manager(&self, spec: &HostSpec) -> IpcManagerState {
+        IpcManagerState::new(self.config_for(spec)).sharing_request_ids(&self.core)
+    }
+
+    /// Derive a named host's configuration from the core host's.
+    fn config_for(&self, spec: &HostSpec) -> IpcConfig {
+        let mut config = self.core.config();
+
+        if let Some(ref path) = spec.python_path {
+            config.python_path.clone_from(path);
+        }
+        if let Some(ref args) = spec.interpreter_args {
+            config.interpreter_args.clone_from(args);
+        }
+
+        // Sandboxed hosts can't write to the plugins and don't get the core
+        // host's environment
+        let sandboxed = spec.isolation == Isolation::Sandboxed;
+        if sandboxed {
+            let plugins_dir = config
+                .working_dir
+                .clone()
+                .unwrap_or_default()
+                .join("plugins");
+            config.sandbox = Some(SandboxConfig::new(vec![plugins_dir]));
+        }
+
+        // Keep the core host's computed environment (API keys) and add the spec's
+        let base = config.env_provider.take().filter(|_| !sandboxed);
+        let extra = spec.env.clone();
+        config.env_provider = Some(EnvProvider::new(move |dir| {
+            let mut vars = base.as_ref().map(|p| p.vars(dir)).unwrap_or_default();
+            vars.extend(extra.iter().map(|(k, v)| (k.clone(), v.clone())));
+            vars
+        }));
+
+        config
+    }
+
+    /// Write the specs to disk.
+    fn persist(&self) -> Result<(), String> {
+        match self.path {
+            Some(ref path) => write_map(path, &self.specs.read().unwrap()),
+            None => Ok(()),
+        }
+    }
+}
+
+// ============================================
+// PERSISTENCE
+// ============================================
+
+/// Spec of a plugin's sandboxed host.
+fn sandbox_spec() -> HostSpec {
+    HostSpec {
+        isolation: Isolation::Sandboxed,
+        ..HostSpec::default()
+    }
+}
+
+/// Read a map (specs or isolation choices) from disk, returning an empty
+/// one if missing or invalid.
+fn read_map<T: DeserializeOwned>(path: &Path) -> BTreeMap<String, T> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid file {path:?}: {e}");
+            BTreeMap::new()
+        }),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+/// Write a map to disk.
+fn write_map<T: Serialize>(path: &Path, map: &BTreeMap<String, T>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(map)
+        .map_err(|e| format!("Failed to serialize {path:?}: {e}"))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn core() -> IpcManagerState {
+        IpcManagerState::new(
+            IpcConfig::default()
+                .with_python_path("python3")
+                .with_env_provider(EnvProvider::new(|_| {
+                    vec![("OPENAI_API_KEY".to_string(), "sk-test".to_string())]
+                })),
+        )
+    }
+
+    #[test]
+    fn test_named_host_config() {
+        let hosts = HostRegistry::load(core(), None);
+        assert!(hosts.get(CORE_HOST).is_ok());
+        assert!(hosts.get("ml-gpu").is_err());
+        assert!(hosts.define(CORE_HOST, HostSpec::default()).is_err());
+        assert!(hosts.define("ML GPU", HostSpec::default()).is_err());
+
+        let spec = HostSpec {
+            interpreter_args: Some(vec!["-O".to_string()]),
+            ..HostSpec::default().with_env("CUDA_VISIBLE_DEVICES", "0")
+        };
+        hosts.define("ml-gpu", spec).unwrap();
+
+        let config = hosts.get("ml-gpu").unwrap().config();
+        assert_eq!(config.python_path, "python3");
+        assert_eq!(config.interpreter_args, vec!["-O".to_string()]);
+        let vars = config.env_provider.unwrap().vars(None);
+        assert!(vars.contains(&("OPENAI_API_KEY".to_string(), "sk-test".to_string())));
+        assert!(vars.contains(&("CUDA_VISIBLE_DEVICES".to_string(), "0".to_string())));
+    }
+
+    #[tokio::test]
+    async fn test_define_persists_and_retires_manager() {
+        let dir = std::env::temp_dir().join(format!("af_hosts_{}", uuid::Uuid::new_v4()));
+        let hosts = HostRegistry::load(core(), Some(dir.clone()));
+
+        hosts.define("ml-gpu", HostSpec::default()).unwrap();
+        hosts.get("ml-gpu").unwrap();
+        assert_eq!(hosts.managers().len(), 1);
+
+        // Redefining hands back the manager built from the old spec
+        let retired = hosts
+            .define(
+                "ml-gpu",
+                HostSpec::default().with_env("CUDA_VISIBLE_DEVICES", "1"),
+            )
+            .unwrap();
+        assert!(retired.is_some());
+        assert!(hosts.managers().is_empty());
+
+        let reloaded = HostRegistry::load(core(), Some(dir));
+        let infos = reloaded.list().await;
+        assert_eq!(infos.len(), 2);
+        assert!(infos[0].is_core);
+        assert_eq!(infos[1].spec.env["CUDA_VISIBLE_DEVICES"], "1");
+
+        assert!(reloaded.remove("ml-gpu").unwrap().is_none());
+        assert!(reloaded.remove("ml-gpu").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sandboxed_plugin_host() {
+        let root = std::env::temp_dir().join(format!("af_hosts_{}", uuid::Uuid::new_v4()));
+        let plugin_dir = root.join("plugins").join("untrusted");
+        fs::create_dir_all(&plugin_dir).unwrap();
+        fs::write(
+            plugin_dir.join("manifest.json"),
+            r#"{"name": "untrusted", "isolation": "sandboxed"}"#,
+        )
+        .unwrap();
+        let core = IpcManagerState::new(core().config().with_working_dir(&root));
+        let hosts = HostRegistry::load(core, Some(root.join("config")));
+        assert!(hosts.define("sandbox-x", HostSpec::default()).is_err());
+
+        assert_eq!(hosts.isolation("untrusted"), Isolation::Sandboxed);
+        assert_eq!(hosts.isolation("other"), Isolation::Standard);
+
+        // The sandboxed host doesn't inherit the core host's API keys
+        let (name, manager) = hosts.sandbox_host("untrusted");
+        assert_eq!(name, "sandbox-untrusted");
+        let config = manager.config();
+        assert!(config.sandbox.is_some());
+        assert!(config.env_provider.unwrap().vars(None).is_empty());
+        assert_eq!(hosts.list().await.len(), 2);
+
+        // Choosing standard isolation hands back the sandboxed host
+        let retired = hosts
+            .set_isolation("untrusted", Some(Isolation::Standard))
+            .unwrap();
+        assert!(retired.is_some());
+        let reloaded = HostRegistry::load(hosts.core.clone(), Some(root.join("config")));
+        let info = reloaded.plugin_isolation("untrusted");
+        assert_eq!(info.declared, Isolation::Sandboxed);
+        assert_eq!(info.effective, Isolation::Standard);
+    }
+}
@@ -0,0 +1,204 @@
+//! src-tauri/src/ipc/integration_tests.rs
+//! ======================================
+//! End-to-end tests of the IPC manager against a real Python process.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The tests spawn the echo host in `tests/fixtures/echo_host` (a plugin-free
+//! JSON-RPC host) and exercise spawn, calls, progress, timeouts, injected
+//! faults, crashes, and shutdown. They need a Python interpreter, so they
+//! are ignored by default; the interpreter is `python3` (`python` on
+//! Windows) unless `APP_FACTORY_TEST_PYTHON` is set.
+//!
+//! Usage:
+//!     ```bash
+//!     cargo test ipc::integration_tests -- --ignored
+//!     APP_FACTORY_TEST_PYTHON=/usr/bin/python3.12 cargo test -- --ignored
+//!     ```
+
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::chaos::ChaosConfig;
+use super::health::SubprocessState;
+use super::manager::{IpcConfig, IpcManagerState, LifecycleState, ManagerEvent};
+use super::IpcError;
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Environment variable overriding the test interpreter
+const PYTHON_ENV: &str = "APP_FACTORY_TEST_PYTHON";
+
+/// How long to wait for events raised by the manager
+const EVENT_WAIT: Duration = Duration::from_secs(10);
+
+/// Python interpreter used to run the echo host.
+fn python() -> String {
+    std::env::var(PYTHON_ENV).unwrap_or_else(|_| {
+        if cfg!(windows) {
+            "python".to_string()
+        } else {
+            "python3".to_string()
+        }
+    })
+}
+
+/// Directory containing the echo host module.
+fn fixture_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/echo_host")
+}
+
+/// Configuration running the echo host, without respawn.
+fn echo_config() -> IpcConfig {
+    IpcConfig::new()
+        .with_python_path(python())
+        .with_module_path("echo_host")
+        .with_working_dir(fixture_dir())
+        .with_timeout(5)
+        .with_spawn_timeout(10)
+        .with_auto_respawn(false)
+}
+
+/// Start a manager running the echo host.
+async fn start_echo_host(config: IpcConfig) -> IpcManagerState {
+    let manager = IpcManagerState::new(config);
+    manager.start().await.expect("echo host should start");
+    manager
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "spawns a Python interpreter"]
+async fn test_spawn_and_call() {
+    let manager = start_echo_host(echo_config()).await;
+    assert!(manager.is_ready().await);
+    assert!(manager.stats().await.subprocess_pid.is_some());
+
+    let params = json!({"text": "hello", "values": [1, 2, 3]});
+    let result = manager.call("echo", params.clone()).await.unwrap();
+    assert_eq!(result, params);
+
+    let raw = manager.call_raw("ping", json!({})).await.unwrap();
+    assert_eq!(raw.get(), r#""pong""#);
+
+    let error = manager.call("missing", json!({})).await.unwrap_err();
+    assert!(matches!(error, IpcError::RpcError { code: -32601, .. }));
+
+    manager.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "spawns a Python interpreter"]
+async fn test_call_with_progress() {
+    let manager = start_echo_host(echo_config()).await;
+
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&updates);
+    let id = manager.next_request_id();
+    manager
+        .call_with_progress(id, "progress", json!({"steps": 4}), move |update| {
+            sink.lock().unwrap().push(update);
+        })
+        .await
+        .unwrap();
+
+    let updates = updates.lock().unwrap().clone();
+    assert!(!updates.is_empty());
+    assert!(updates.iter().all(|update| update.request_id == id));
+    assert!(updates
+        .iter()
+        .any(|update| update.message.as_deref() == Some("step 1")));
+
+    manager.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "spawns a Python interpreter"]
+async fn test_request_timeout() {
+    let manager = start_echo_host(echo_config().with_timeout(1)).await;
+
+    let error = manager
+        .call("sleep", json!({"seconds": 3}))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, IpcError::Timeout(1)));
+    assert_eq!(manager.stats().await.pending_requests, 0);
+
+    manager.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "spawns a Python interpreter"]
+async fn test_injected_faults() {
+    let manager = start_echo_host(echo_config().with_timeout(1)).await;
+
+    let drop_all = ChaosConfig {
+        drop_rate: 1.0,
+        ..ChaosConfig::default()
+    };
+    manager.chaos().configure(drop_all).unwrap();
+    let error = manager.call("ping", json!({})).await.unwrap_err();
+    assert!(matches!(error, IpcError::Timeout(1)));
+    assert_eq!(manager.chaos().status().stats.dropped, 1);
+
+    manager.chaos().configure(ChaosConfig::default()).unwrap();
+    assert_eq!(manager.call("ping", json!({})).await.unwrap(), "pong");
+
+    manager.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "spawns a Python interpreter"]
+async fn test_crash_is_reported() {
+    let manager = start_echo_host(echo_config()).await;
+    let mut events = manager.subscribe_events();
+
+    let error = manager.call("crash", json!({"code": 3})).await.unwrap_err();
+    assert!(matches!(error, IpcError::SubprocessCrashed));
+    assert_eq!(manager.health().state(), SubprocessState::Crashed);
+
+    let event = tokio::time::timeout(EVENT_WAIT, events.recv())
+        .await
+        .expect("crash event should be raised")
+        .unwrap();
+    match event {
+        ManagerEvent::SubprocessCrashed {
+            exit_code,
+            pending_methods,
+            ..
+        } => {
+            assert_eq!(exit_code, Some(3));
+            assert_eq!(pending_methods, vec!["crash"]);
+        }
+        other => panic!("unexpected event: {other:?}"),
+    }
+
+    manager.shutdown().await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "spawns a Python interpreter"]
+async fn test_clean_shutdown() {
+    let manager = start_echo_host(echo_config()).await;
+    let pid = manager.stats().await.subprocess_pid;
+
+    manager.shutdown().await.unwrap();
+
+    assert_eq!(manager.lifecycle_state().await, LifecycleState::Stopped);
+    assert!(!manager.is_ready().await);
+    let stats = manager.stats().await;
+    let exit = stats.last_exit_status.expect("exit should be recorded");
+    assert_eq!(Some(exit.pid), pid);
+    assert!(exit.expected);
+    assert!(matches!(
+        manager.call("ping", json!({})).await,
+        Err(IpcError::NotRunning | IpcError::ShuttingDown)
+    ));
+}
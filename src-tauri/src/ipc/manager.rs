@@ -10,9 +10,41 @@
 //!
 //! This module provides:
 //! - `IpcConfig` for manager configuration
+//! - `EnvProvider` for environment variables computed at each spawn
 //! - `IpcManagerState` for Tauri state management
 //! - `LifecycleState` enum for manager lifecycle
 //! - `ManagerStats` for statistics reporting
+//! - Write coalescing: requests queued together go out in one write and flush
+//! - Borrowed parsing of host messages: response results stay raw JSON
+//!   (`call_raw`) until a caller needs a `Value`
+//! - A maximum message length: longer lines from the host are dropped while
+//!   reading, failing the request they answer with `MessageTooLarge`
+//! - `IpcNotification` broadcast for host-initiated notifications
+//! - Idempotency keys: identical requests in flight under one key are sent
+//!   once and share the response (`call_idempotent`)
+//! - `ProgressUpdate` parsed from `progress/{request id}` notifications and
+//!   routed to the originating call (`call_with_progress`)
+//! - `RequestHandler` answering host-initiated requests (reverse RPC)
+//! - `ManagerEvent` broadcast for manager-side events (e.g. memory warnings)
+//! - `TrafficEntry` broadcast of finished requests (for the IPC console),
+//!   only built while someone subscribes
+//! - Crash context (exit code, stderr tail, pending methods) on unexpected exits
+//! - An exit waiter that reaps the subprocess and records its exit status
+//! - Classified errors (missing interpreter/module, syntax error) when the host
+//!   exits during startup
+//! - Readiness handshake with a spawn timeout; a host that never answers is
+//!   killed and the manager is marked failed
+//! - Periodic health checks (configurable method, timeout, and failure threshold)
+//! - Periodic sampling of subprocess RSS/CPU into the health monitor
+//! - Host heartbeats (`$/heartbeat`): missed ones degrade the subprocess, and
+//!   a failed ping is not counted while the host reports pending requests
+//! - Slower `plugin/health` probing of loaded plugins
+//! - Reloading a plugin that keeps raising exceptions (`containment.rs`)
+//! - Warm (blue/green) restarts that load the same plugins into a standby
+//!   host before switching requests to it
+//! - Event topics with listeners, sent to every host it starts so plugins
+//!   can skip events nobody listens to
+//! - A fault injection layer (`Chaos`, off by default) on the transport
 //! - Coordination between spawn, health, and request handling
 //!
 //! Dependencies:
@@ -32,21 +64,39 @@
 //!     ```
 
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
-use std::process::ChildStdin;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::path::{Path, PathBuf};
+use std::process::{ChildStdin, ExitStatus};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 use std::time::{Duration, Instant};
-use tokio::sync::{mpsc, oneshot, RwLock};
-
-use super::health::{HealthMonitor, HealthStatus, SubprocessState};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::future::Future;
+use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
+
+use super::chaos::Chaos;
+use super::containment::FailureTracker;
+use super::metrics::PluginMetrics;
+use super::health::{
+    HealthMonitor, HealthStatus, HostHeartbeat, PluginHealth, ResourceUsage, SubprocessState,
+};
 use super::request::{JsonRpcRequest, RequestBuilder};
-use super::response::JsonRpcResponse;
-use super::spawn::{spawn_plugin_host, SubprocessConfig, SubprocessHandle};
-use super::{IpcError, DEFAULT_TIMEOUT_SECS, HEALTH_CHECK_INTERVAL_SECS};
+use super::response::JsonRpcError;
+use super::sandbox::SandboxConfig;
+use super::spawn::{
+    classify_startup_exit, spawn_plugin_host, ProcessPriority, SubprocessConfig,
+    SubprocessHandle,
+};
+use super::{
+    IpcError, DEFAULT_TIMEOUT_SECS, HEALTH_CHECK_INTERVAL_SECS, HEALTH_CHECK_METHOD,
+    HEALTH_PROBE_TIMEOUT_SECS, HEARTBEAT_ENV, HEARTBEAT_INTERVAL_SECS, HEARTBEAT_METHOD,
+    MAX_CONSECUTIVE_FAILURES, MAX_LINE_BYTES, MAX_MISSED_HEARTBEATS, MAX_PENDING_REQUESTS,
+    OFFLINE_ENV, PLUGIN_RELOAD_FAILURES, SPAWN_TIMEOUT_SECS,
+};
 
 // ============================================
 // LIFECYCLE STATE
@@ -102,6 +152,94 @@ impl std::fmt::Display for LifecycleState {
 // IPC CONFIGURATION
 // ============================================
 
+/// Environment variables computed each time the subprocess is spawned.
+///
+/// Called with the working directory, so values (e.g. API keys from the
+/// project's .env) follow project switches and restarts.
+#[derive(Clone)]
+pub struct EnvProvider(Arc<dyn Fn(Option<&Path>) -> Vec<(String, String)> + Send + Sync>);
+
+impl EnvProvider {
+    /// Wrap a provider function.
+    pub fn new(
+        provider: impl Fn(Option<&Path>) -> Vec<(String, String)> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(provider))
+    }
+
+    /// Compute the variables for a working directory.
+    pub fn vars(&self, working_dir: Option<&Path>) -> Vec<(String, String)> {
+        (self.0)(working_dir)
+    }
+}
+
+impl std::fmt::Debug for EnvProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Values may be secrets; never print them
+        f.write_str("EnvProvider(..)")
+    }
+}
+
+/// A JSON-RPC request sent by the plugin host to the app (reverse RPC).
+#[derive(Debug, Clone)]
+pub struct HostRequest {
+    /// Method name (e.g. `host/request_permission`)
+    pub method: String,
+    /// Parameters (Null if omitted)
+    pub params: Value,
+    /// Working directory of the host that sent it
+    pub working_dir: Option<PathBuf>,
+}
+
+impl HostRequest {
+    /// Parse a host request (a message with both a method and an id).
+    fn from_message(message: &Value, working_dir: Option<&Path>) -> Option<(Value, Self)> {
+        let id = message.get("id").filter(|id| !id.is_null())?.clone();
+        let method = message.get("method")?.as_str()?.to_string();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        Some((
+            id,
+            Self {
+                method,
+                params,
+                working_dir: working_dir.map(Path::to_path_buf),
+            },
+        ))
+    }
+}
+
+/// Answers requests sent by the plugin host.
+///
+/// The result is sent back as the JSON-RPC result; an error message is
+/// sent back as a JSON-RPC error.
+#[derive(Clone)]
+pub struct RequestHandler(
+    Arc<dyn Fn(HostRequest) -> BoxFuture<'static, Result<Value, String>> + Send + Sync>,
+);
+
+impl RequestHandler {
+    /// Wrap a handler function.
+    pub fn new(
+        handler: impl Fn(HostRequest) -> BoxFuture<'static, Result<Value, String>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self(Arc::new(handler))
+    }
+
+    /// Handle a request.
+    pub fn handle(&self, request: HostRequest) -> BoxFuture<'static, Result<Value, String>> {
+        (self.0)(request)
+    }
+}
+
+impl std::fmt::Debug for RequestHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RequestHandler(..)")
+    }
+}
+
 /// Configuration for the IPC Manager.
 #[derive(Debug, Clone)]
 pub struct IpcConfig {
@@ -109,18 +247,55 @@ pub struct IpcConfig {
     pub python_path: String,
     /// Plugin host module path
     pub module_path: String,
+    /// Interpreter flags placed before `-m` (e.g. `-X utf8`)
+    pub interpreter_args: Vec<String>,
     /// Working directory
     pub working_dir: Option<PathBuf>,
+    /// Root of the per-plugin data directories (see `crate::plugin_data`)
+    pub plugin_data_dir: Option<PathBuf>,
     /// Request timeout in seconds
     pub timeout_secs: u64,
     /// Health check interval in seconds
     pub health_check_interval_secs: u64,
+    /// Consecutive failed health checks before the subprocess is degraded
+    pub max_consecutive_failures: u32,
+    /// Timeout of a single health check in seconds
+    pub probe_timeout_secs: u64,
+    /// RPC method called by the health check
+    pub health_check_method: String,
+    /// Seconds a new host has to answer its first request
+    pub spawn_timeout_secs: u64,
+    /// Seconds between host heartbeats (0 disables them)
+    pub heartbeat_interval_secs: u64,
+    /// Consecutive missed heartbeats before the subprocess is degraded
+    pub max_missed_heartbeats: u32,
+    /// Scheduling priority of the host
+    pub priority: ProcessPriority,
+    /// CPUs the host may run on (bit n = CPU n; None allows all)
+    pub cpu_affinity: Option<u64>,
     /// Auto-respawn on crash
     pub auto_respawn: bool,
     /// Maximum respawn attempts
     pub max_respawn_attempts: u32,
+    /// Consecutive plugin exceptions before the plugin is reloaded (0 disables)
+    pub plugin_reload_failures: u32,
+    /// Requests waiting for a response before new ones fail with
+    /// `IpcError::QueueFull` (0 disables)
+    pub max_pending_requests: usize,
+    /// Maximum length of a message from the host in bytes
+    pub max_line_bytes: usize,
+    /// Tell the host offline mode is on (see `crate::offline`)
+    pub offline: bool,
     /// Enable verbose logging
     pub verbose: bool,
+    /// Subprocess RSS (MB) above which a memory warning is emitted
+    pub memory_warning_threshold_mb: Option<u64>,
+    /// Extra subprocess environment, computed at each spawn
+    pub env_provider: Option<EnvProvider>,
+    /// Handler for requests sent by the plugin host
+    pub request_handler: Option<RequestHandler>,
+    /// Run the host sandboxed (see `sandbox.rs`)
+    pub sandbox: Option<SandboxConfig>,
 }
 
 impl Default for IpcConfig {
@@ -128,12 +303,30 @@ impl Default for IpcConfig {
         Self {
             python_path: "python".to_string(),
             module_path: "plugins._host".to_string(),
+            interpreter_args: Vec::new(),
             working_dir: None,
+            plugin_data_dir: None,
             timeout_secs: DEFAULT_TIMEOUT_SECS,
             health_check_interval_secs: HEALTH_CHECK_INTERVAL_SECS,
+            max_consecutive_failures: MAX_CONSECUTIVE_FAILURES,
+            probe_timeout_secs: HEALTH_PROBE_TIMEOUT_SECS,
+            health_check_method: HEALTH_CHECK_METHOD.to_string(),
+            spawn_timeout_secs: SPAWN_TIMEOUT_SECS,
+            heartbeat_interval_secs: HEARTBEAT_INTERVAL_SECS,
+            max_missed_heartbeats: MAX_MISSED_HEARTBEATS,
+            priority: ProcessPriority::Normal,
+            cpu_affinity: None,
             auto_respawn: true,
             max_respawn_attempts: 3,
+            plugin_reload_failures: PLUGIN_RELOAD_FAILURES,
+            max_pending_requests: MAX_PENDING_REQUESTS,
+            max_line_bytes: MAX_LINE_BYTES,
+            offline: false,
             verbose: false,
+            memory_warning_threshold_mb: None,
+            env_provider: None,
+            request_handler: None,
+            sandbox: None,
         }
     }
 }
@@ -156,12 +349,24 @@ impl IpcConfig {
         self
     }
 
+    /// Set interpreter flags.
+    pub fn with_interpreter_args(mut self, args: Vec<String>) -> Self {
+        self.interpreter_args = args;
+        self
+    }
+
     /// Set working directory.
     pub fn with_working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
         self.working_dir = Some(dir.into());
         self
     }
 
+    /// Set the root of the per-plugin data directories (None disables them).
+    pub fn with_plugin_data_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.plugin_data_dir = dir;
+        self
+    }
+
     /// Set request timeout.
     pub fn with_timeout(mut self, secs: u64) -> Self {
         self.timeout_secs = secs;
@@ -174,19 +379,144 @@ impl IpcConfig {
         self
     }
 
+    /// Set health check interval.
+    pub fn with_health_check_interval(mut self, secs: u64) -> Self {
+        self.health_check_interval_secs = secs;
+        self
+    }
+
+    /// Set consecutive failed health checks before the subprocess is degraded.
+    pub fn with_max_consecutive_failures(mut self, failures: u32) -> Self {
+        self.max_consecutive_failures = failures;
+        self
+    }
+
+    /// Set seconds between host heartbeats (0 disables them).
+    pub fn with_heartbeat_interval(mut self, secs: u64) -> Self {
+        self.heartbeat_interval_secs = secs;
+        self
+    }
+
+    /// Set consecutive missed heartbeats before the subprocess is degraded.
+    pub fn with_max_missed_heartbeats(mut self, missed: u32) -> Self {
+        self.max_missed_heartbeats = missed;
+        self
+    }
+
+    /// Set the timeout of a single health check.
+    pub fn with_probe_timeout(mut self, secs: u64) -> Self {
+        self.probe_timeout_secs = secs;
+        self
+    }
+
+    /// Set the RPC method called by the health check.
+    pub fn with_health_check_method(mut self, method: impl Into<String>) -> Self {
+        self.health_check_method = method.into();
+        self
+    }
+
+    /// Set the readiness handshake timeout.
+    pub fn with_spawn_timeout(mut self, secs: u64) -> Self {
+        self.spawn_timeout_secs = secs;
+        self
+    }
+
+    /// Set the host's scheduling priority.
+    pub fn with_priority(mut self, priority: ProcessPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Restrict the host to a set of CPUs (None allows all).
+    pub fn with_cpu_affinity(mut self, mask: Option<u64>) -> Self {
+        self.cpu_affinity = mask;
+        self
+    }
+
+    /// Set maximum respawn attempts.
+    pub fn with_max_respawn_attempts(mut self, attempts: u32) -> Self {
+        self.max_respawn_attempts = attempts;
+        self
+    }
+
+    /// Set consecutive plugin exceptions before the plugin is reloaded (0 disables).
+    pub fn with_plugin_reload_failures(mut self, failures: u32) -> Self {
+        self.plugin_reload_failures = failures;
+        self
+    }
+
+    /// Set the number of pending requests above which calls are rejected (0 disables).
+    pub fn with_max_pending_requests(mut self, max: usize) -> Self {
+        self.max_pending_requests = max;
+        self
+    }
+
+    /// Set the maximum length of a message from the host in bytes.
+    pub fn with_max_line_bytes(mut self, bytes: usize) -> Self {
+        self.max_line_bytes = bytes;
+        self
+    }
+
+    /// Set whether the host starts in offline mode.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Set the subprocess memory warning threshold (None disables).
+    pub fn with_memory_warning_threshold(mut self, mb: Option<u64>) -> Self {
+        self.memory_warning_threshold_mb = mb;
+        self
+    }
+
+    /// Set the provider of extra subprocess environment variables.
+    pub fn with_env_provider(mut self, provider: EnvProvider) -> Self {
+        self.env_provider = Some(provider);
+        self
+    }
+
+    /// Set the handler for requests sent by the plugin host.
+    pub fn with_request_handler(mut self, handler: RequestHandler) -> Self {
+        self.request_handler = Some(handler);
+        self
+    }
+
+    /// Run the host sandboxed.
+    pub fn with_sandbox(mut self, sandbox: SandboxConfig) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
     /// Convert to `SubprocessConfig`.
     pub fn to_subprocess_config(&self) -> SubprocessConfig {
         let mut config = SubprocessConfig::new()
             .with_python_path(&self.python_path)
             .with_module(&self.module_path)
+            .with_interpreter_args(self.interpreter_args.iter().cloned())
             .with_shutdown_timeout(self.timeout_secs)
+            .with_spawn_timeout(self.spawn_timeout_secs)
+            .with_priority(self.priority)
             .with_max_respawn_attempts(self.max_respawn_attempts)
+            .with_env(HEARTBEAT_ENV, self.heartbeat_interval_secs.to_string())
+            .with_env(OFFLINE_ENV, if self.offline { "1" } else { "0" })
             .with_verbose(self.verbose);
 
         if let Some(ref dir) = self.working_dir {
             config = config.with_working_dir(dir);
         }
 
+        if let Some(mask) = self.cpu_affinity {
+            config = config.with_cpu_affinity(mask);
+        }
+
+        if let Some(ref provider) = self.env_provider {
+            config = config.with_envs(provider.vars(self.working_dir.as_deref()));
+        }
+
+        if let Some(ref sandbox) = self.sandbox {
+            config = config.with_sandbox(sandbox.clone());
+        }
+
         config
     }
 }
@@ -208,28 +538,571 @@ pub struct ManagerStats {
     pub successful_requests: u64,
     /// Total failed requests
     pub failed_requests: u64,
+    /// Requests rejected because too many were pending
+    pub rejected_requests: u64,
+    /// Messages from the host dropped for exceeding the length limit
+    pub oversized_messages: u64,
+    /// Sum of the response times of answered requests in milliseconds
+    pub total_latency_ms: u64,
     /// Current pending request count
     pub pending_requests: usize,
     /// Manager uptime in seconds
     pub uptime_secs: Option<u64>,
     /// Subprocess PID
     pub subprocess_pid: Option<u32>,
+    /// Latest subprocess resource usage sample
+    pub resource_usage: Option<ResourceUsage>,
+    /// How the most recent subprocess exited
+    pub last_exit_status: Option<ProcessExit>,
+    /// Number of times the subprocess was started after the first start
+    pub restart_count: u64,
+}
+
+/// A request waiting for its response, as reported by `pending_requests`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PendingInfo {
+    /// Request id
+    pub id: u64,
+    /// Request method
+    pub method: String,
+    /// Milliseconds since the request was registered
+    pub age_ms: u64,
+    /// Seconds the caller waits before the request times out
+    pub timeout_secs: u64,
+}
+
+/// How a subprocess exited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProcessExit {
+    /// Subprocess PID
+    pub pid: u32,
+    /// Exit code (None if killed by a signal)
+    pub exit_code: Option<i32>,
+    /// Terminating signal (Unix only)
+    pub signal: Option<i32>,
+    /// Whether the manager stopped it (shutdown, failed start, warm restart)
+    pub expected: bool,
+    /// When the exit was observed (RFC 3339)
+    pub exited_at: String,
+}
+
+impl ProcessExit {
+    /// Describe an exit observed now.
+    fn new(pid: u32, status: ExitStatus, expected: bool) -> Self {
+        Self {
+            pid,
+            exit_code: status.code(),
+            signal: exit_signal(status),
+            expected,
+            exited_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// Signal that terminated a process.
+#[cfg(unix)]
+fn exit_signal(status: ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+/// Signal that terminated a process (always None off Unix).
+#[cfg(not(unix))]
+fn exit_signal(_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+// ============================================
+// NOTIFICATIONS
+// ============================================
+
+/// Capacity of the notification broadcast channel
+const NOTIFICATION_CHANNEL_CAPACITY: usize = 256;
+
+/// Method used to ask the host to cancel an in-flight request
+pub const CANCEL_REQUEST_METHOD: &str = "$/cancelRequest";
+
+/// Number of recent stderr lines kept for crash reports
+const STDERR_TAIL_LINES: usize = 50;
+
+/// How long to wait for the exit status after stdout closes
+const EXIT_STATUS_WAIT: Duration = Duration::from_secs(1);
+
+/// How often the exit waiter polls the subprocess
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A host exiting within this time after spawn failed to start
+const STARTUP_EXIT_WINDOW: Duration = Duration::from_millis(750);
+
+/// JSON-RPC error code returned for failed host requests
+const HOST_REQUEST_ERROR: i64 = -32000;
+
+/// Loaded plugins are probed every this many health check intervals
+const PLUGIN_HEALTH_INTERVAL_FACTOR: u32 = 4;
+
+/// Host method reporting the health of every loaded plugin
+const PLUGIN_HEALTH_METHOD: &str = "plugin/health";
+
+/// Host method loading a plugin (replayed into the standby on warm restart)
+const PLUGIN_LOAD_METHOD: &str = "plugin/load";
+
+/// Host method unloading a plugin
+const PLUGIN_UNLOAD_METHOD: &str = "plugin/unload";
+
+/// Host method replacing a loaded plugin with another
+const PLUGIN_SWAP_METHOD: &str = "plugin/swap";
+
+/// Host method replacing the set of event topics that have listeners
+pub const EVENT_TOPICS_METHOD: &str = "events/set_topics";
+
+/// Host method switching offline mode
+pub const OFFLINE_METHOD: &str = "host/set_offline";
+
+/// Notification method prefix for request progress; the request id follows it
+pub const PROGRESS_METHOD_PREFIX: &str = "progress/";
+
+/// Bytes of queued messages the writer joins into a single write
+const WRITE_BATCH_MAX_BYTES: usize = 64 * 1024;
+
+/// Longest the writer spends collecting queued messages before writing
+const WRITE_BATCH_MAX_DELAY: Duration = Duration::from_millis(2);
+
+/// JSON-RPC notification sent by the Python host (no id).
+///
+/// Examples: `$/progress` with `{"id": <request id>, "progress": 0.5}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct IpcNotification {
+    /// Notification method
+    pub method: String,
+    /// Notification parameters (Null if omitted)
+    pub params: Value,
+}
+
+impl IpcNotification {
+    /// Parse a notification from a decoded JSON-RPC message.
+    ///
+    /// Returns None for responses (messages with an id or without a method).
+    fn from_message(message: &Value) -> Option<Self> {
+        if message.get("id").is_some_and(|id| !id.is_null()) {
+            return None;
+        }
+        let method = message.get("method")?.as_str()?.to_string();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        Some(Self { method, params })
+    }
+}
+
+/// Progress of an in-flight request, reported by the Python host.
+///
+/// Sent as `progress/{request id}` with
+/// `{"percentage": 40.0, "message": "Loading weights", "stage": "load"}`;
+/// only `percentage` is required.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProgressUpdate {
+    /// JSON-RPC id of the request being served
+    pub request_id: u64,
+    /// Completion from 0 to 100
+    pub percentage: f64,
+    /// What the host is doing now
+    pub message: Option<String>,
+    /// Named phase (e.g. "download", "load")
+    pub stage: Option<String>,
+}
+
+impl ProgressUpdate {
+    /// Parse a `progress/{request id}` notification.
+    ///
+    /// Returns None for other methods, non-numeric tokens, or a missing
+    /// percentage.
+    pub fn from_notification(notification: &IpcNotification) -> Option<Self> {
+        let token = notification.method.strip_prefix(PROGRESS_METHOD_PREFIX)?;
+        let request_id = token.parse().ok()?;
+        let params = &notification.params;
+        let percentage = params.get("percentage")?.as_f64()?.clamp(0.0, 100.0);
+        let text = |key: &str| params.get(key).and_then(Value::as_str).map(str::to_string);
+        Some(Self {
+            request_id,
+            percentage,
+            message: text("message"),
+            stage: text("stage"),
+        })
+    }
+
+    /// Tauri event name for progress of this request.
+    pub fn event_name(&self) -> String {
+        format!("ipc/progress/{}", self.request_id)
+    }
+}
+
+/// Event raised by the manager itself (not the Python host).
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ManagerEvent {
+    /// Subprocess RSS crossed the configured threshold
+    MemoryWarning {
+        /// Subprocess PID
+        pid: u32,
+        /// Sampled resident set size in bytes
+        rss_bytes: u64,
+        /// Configured threshold in bytes
+        threshold_bytes: u64,
+    },
+    /// Subprocess exited without being asked to
+    SubprocessCrashed {
+        /// Subprocess PID
+        pid: u32,
+        /// Exit code (None if killed by a signal or still exiting)
+        exit_code: Option<i32>,
+        /// Last lines written to stderr (oldest first)
+        stderr_tail: Vec<String>,
+        /// Methods of the requests in flight when it exited
+        pending_methods: Vec<String>,
+    },
+    /// A loaded plugin reported an unhealthy status
+    PluginDegraded {
+        /// Plugin name
+        plugin: String,
+        /// Reported status (e.g. `error`)
+        status: String,
+        /// Message from the plugin's health check
+        message: Option<String>,
+    },
+    /// A degraded plugin reported a healthy status again
+    PluginRecovered {
+        /// Plugin name
+        plugin: String,
+        /// Reported status (e.g. `ready`)
+        status: String,
+    },
+    /// A plugin that kept raising exceptions was unloaded and loaded again
+    PluginReloaded {
+        /// Plugin name
+        plugin: String,
+        /// Consecutive failures that triggered the reload
+        failures: u32,
+        /// Whether the plugin is loaded again
+        success: bool,
+        /// Why the reload failed
+        error: Option<String>,
+    },
+    /// A warm restart switched requests to a new host process
+    HostSwitched {
+        /// PID of the retired process
+        old_pid: u32,
+        /// PID of the process now serving requests
+        new_pid: u32,
+        /// Plugins loaded into the new process
+        plugins: Vec<String>,
+    },
+}
+
+impl ManagerEvent {
+    /// Tauri event name used when forwarding this event to the frontend.
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ManagerEvent::MemoryWarning { .. } => "ipc/memory_warning",
+            ManagerEvent::SubprocessCrashed { .. } => "ipc/subprocess_crashed",
+            ManagerEvent::PluginDegraded { .. } => "ipc/plugin_degraded",
+            ManagerEvent::PluginRecovered { .. } => "ipc/plugin_recovered",
+            ManagerEvent::PluginReloaded { .. } => "ipc/plugin_reloaded",
+            ManagerEvent::HostSwitched { .. } => "ipc/host_switched",
+        }
+    }
+}
+
+/// A request sent through the manager and its outcome.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrafficEntry {
+    /// JSON-RPC request id
+    pub id: u64,
+    /// Request method
+    pub method: String,
+    /// Request params
+    pub params: Value,
+    /// Result (raw JSON) if the request succeeded
+    pub result: Option<Box<RawValue>>,
+    /// Error message if it failed
+    pub error: Option<String>,
+    /// When the request was sent (RFC 3339)
+    pub sent_at: String,
+    /// Time until the response (or failure) in milliseconds
+    pub duration_ms: u64,
+}
+
+impl TrafficEntry {
+    /// Start an entry for a request being sent now.
+    fn sent(id: u64, method: &str, params: Value) -> Self {
+        Self {
+            id,
+            method: method.to_string(),
+            params,
+            result: None,
+            error: None,
+            sent_at: chrono::Utc::now().to_rfc3339(),
+            duration_ms: 0,
+        }
+    }
+
+    /// Record the outcome of the request.
+    fn finish(&mut self, result: &Result<Box<RawValue>, IpcError>, elapsed: Duration) {
+        match result {
+            Ok(raw) => self.result = Some(raw.clone()),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+        self.duration_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+    }
 }
 
 // ============================================
 // PENDING REQUEST
 // ============================================
 
+/// A request awaiting its response.
+struct PendingRequest {
+    /// Request method (reported if the subprocess crashes)
+    method: String,
+    /// When the request was registered
+    started: Instant,
+    /// Caller's timeout in seconds
+    timeout_secs: u64,
+    /// Response channel
+    tx: oneshot::Sender<Result<RawResponse, IpcError>>,
+}
+
+/// A response as received by the reader; the result is left unparsed.
+#[derive(Debug)]
+struct RawResponse {
+    /// Successful result (raw JSON)
+    result: Option<Box<RawValue>>,
+    /// Error object
+    error: Option<JsonRpcError>,
+}
+
+/// Top-level fields of a message from the host, borrowing the line.
+///
+/// Host requests and notifications (`method` set) are small and decoded
+/// again as a `Value`; a response's result is only copied as raw JSON.
+#[derive(Debug, Deserialize)]
+struct IncomingMessage<'a> {
+    #[serde(default)]
+    id: Option<Value>,
+    #[serde(default)]
+    method: Option<serde::de::IgnoredAny>,
+    #[serde(borrow, default)]
+    result: Option<&'a RawValue>,
+    #[serde(default)]
+    error: Option<JsonRpcError>,
+}
+
+/// Raw JSON `null` (the result of a response without one).
+fn raw_null() -> Box<RawValue> {
+    RawValue::from_string("null".to_string()).expect("null is valid JSON")
+}
+
 /// Pending request tracking.
-type PendingRequests = Arc<RwLock<std::collections::HashMap<u64, oneshot::Sender<Result<JsonRpcResponse, IpcError>>>>>;
+type PendingRequests = Arc<RwLock<std::collections::HashMap<u64, PendingRequest>>>;
+
+/// Response shared by the callers of an idempotent request.
+type SharedResponse = Shared<BoxFuture<'static, Result<Box<RawValue>, IpcError>>>;
+
+/// A request in flight under an idempotency key.
+#[derive(Clone)]
+struct IdempotentCall {
+    /// Method and params the key was first sent with
+    fingerprint: String,
+    /// Response, resolved for every caller using the key
+    response: SharedResponse,
+}
+
+/// Requests in flight by idempotency key.
+type IdempotentCalls = Arc<Mutex<std::collections::HashMap<String, IdempotentCall>>>;
+
+/// Recent stderr lines of the running subprocess.
+type StderrTail = Arc<Mutex<VecDeque<String>>>;
+
+/// What the reader needs to report an unexpected exit.
+struct ExitWatch {
+    /// Subprocess PID
+    pid: u32,
+    /// PID of the process requests are routed to (a retired process exits quietly)
+    active_pid: Arc<AtomicU32>,
+    /// Set while the manager is stopping the subprocess on purpose
+    is_shutting_down: Arc<AtomicBool>,
+    /// Subprocess handle (for the exit status)
+    subprocess: Arc<Mutex<Option<SubprocessHandle>>>,
+    /// Recent stderr lines
+    stderr_tail: StderrTail,
+    /// Manager event broadcast
+    events: broadcast::Sender<ManagerEvent>,
+}
+
+/// What the reader needs to bound the length of messages.
+struct LineLimit {
+    /// Maximum length of a message in bytes
+    max_bytes: usize,
+    /// Messages dropped for exceeding it
+    oversized: Arc<AtomicU64>,
+}
+
+/// How `read_line_limited` ended.
+#[derive(Debug, PartialEq, Eq)]
+enum LineRead {
+    /// End of the stream
+    Eof,
+    /// A line within the limit
+    Line,
+    /// A line of `length` bytes over the limit; only its start was kept
+    Truncated { length: usize },
+}
+
+/// Read a line into `buf` (without its `\n`), keeping at most `max_bytes`.
+///
+/// The rest of a longer line is read and dropped, so a huge message costs
+/// `max_bytes` of memory rather than its length.
+fn read_line_limited(
+    reader: &mut impl BufRead,
+    buf: &mut Vec<u8>,
+    max_bytes: usize,
+) -> std::io::Result<LineRead> {
+    let mut length = 0;
+    loop {
+        let available = match reader.fill_buf() {
+            Ok(available) => available,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if available.is_empty() && length == 0 {
+            return Ok(LineRead::Eof);
+        }
+        let newline = available.iter().position(|&b| b == b'\n');
+        let chunk = &available[..newline.unwrap_or(available.len())];
+        let keep = chunk.len().min(max_bytes.saturating_sub(buf.len()));
+        buf.extend_from_slice(&chunk[..keep]);
+        length += chunk.len();
+        let consumed = newline.map_or(available.len(), |i| i + 1);
+        let done = newline.is_some() || available.is_empty();
+        reader.consume(consumed);
+
+        if done {
+            return Ok(if length > max_bytes {
+                LineRead::Truncated { length }
+            } else {
+                LineRead::Line
+            });
+        }
+    }
+}
+
+/// Id of the response an oversized message starts, if it is one.
+///
+/// The host writes the id right after `"jsonrpc"`
+/// (`{"jsonrpc":"2.0","id":7,"result":...`), so the kept start of a
+/// response is enough to find the request it answers.
+fn truncated_response_id(start: &[u8]) -> Option<u64> {
+    let start = String::from_utf8_lossy(&start[..start.len().min(256)]);
+    let position = start.find("\"id\"")?;
+    let before = &start[..position];
+    if ["\"method\"", "\"params\"", "\"result\"", "\"error\""]
+        .iter()
+        .any(|key| before.contains(key))
+    {
+        return None;
+    }
+    let value = start[position + 4..]
+        .trim_start()
+        .strip_prefix(':')?
+        .trim_start();
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    value[..digits].parse().ok()
+}
+
+/// What the reader needs to answer host requests.
+struct HostRequests {
+    /// Handler (None answers every request with an error)
+    handler: Option<RequestHandler>,
+    /// Writer channel for the responses
+    writer: mpsc::Sender<WriterMessage>,
+    /// Runtime the handlers run on
+    runtime: tokio::runtime::Handle,
+    /// Working directory of the host
+    working_dir: Option<PathBuf>,
+}
+
+impl HostRequests {
+    /// Handle a request in the background and write the response.
+    fn dispatch(&self, id: Value, request: HostRequest) {
+        let handler = self.handler.clone();
+        let writer = self.writer.clone();
+        self.runtime.spawn(async move {
+            let method = request.method.clone();
+            let outcome = match handler {
+                Some(handler) => handler.handle(request).await,
+                None => Err(format!("Unknown host method: {method}")),
+            };
+            let response = match outcome {
+                Ok(result) => serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err(message) => {
+                    log::warn!("Host request {method} failed: {message}");
+                    serde_json::json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": HOST_REQUEST_ERROR, "message": message },
+                    })
+                }
+            };
+            if writer
+                .send(WriterMessage::Request(response.to_string()))
+                .await
+                .is_err()
+            {
+                log::warn!("Plugin host exited before the {method} response was sent");
+            }
+        });
+    }
+}
+
+impl ExitWatch {
+    /// Poll for the exit code for up to `EXIT_STATUS_WAIT`.
+    fn exit_code(&self) -> Option<i32> {
+        let deadline = Instant::now() + EXIT_STATUS_WAIT;
+        loop {
+            if let Some(handle) = self.subprocess.lock().unwrap().as_mut() {
+                if let Ok(Some(status)) = handle.try_wait() {
+                    return status.code();
+                }
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
 
 /// Writer message type.
 #[derive(Debug)]
 enum WriterMessage {
+    /// Serialized request, or response to a host request
     Request(String),
     Shutdown,
 }
 
+/// A spawned host process and its I/O threads.
+struct HostProcess {
+    /// Subprocess handle
+    handle: SubprocessHandle,
+    /// Writer channel
+    writer_tx: mpsc::Sender<WriterMessage>,
+    /// Reader thread handle
+    reader: JoinHandle<()>,
+    /// Writer thread handle
+    writer: JoinHandle<()>,
+    /// Stderr thread handle
+    stderr: JoinHandle<()>,
+}
+
 // ============================================
 // IPC MANAGER STATE
 // ============================================
@@ -237,7 +1110,8 @@ enum WriterMessage {
 /// Main IPC Manager state for Tauri.
 ///
 /// This struct is designed to be stored in Tauri's managed state
-/// and provides thread-safe access to the subprocess.
+/// and provides thread-safe access to the subprocess. Clones are cheap
+/// handles that share the same subprocess, counters, and configuration.
 ///
 /// # Example
 ///
@@ -254,8 +1128,8 @@ enum WriterMessage {
 /// }
 /// ```
 pub struct IpcManagerState {
-    /// Configuration
-    config: IpcConfig,
+    /// Configuration (shared so the working directory can be retargeted)
+    config: Arc<Mutex<IpcConfig>>,
 
     /// Current lifecycle state
     lifecycle: Arc<RwLock<LifecycleState>>,
@@ -273,22 +1147,52 @@ pub struct IpcManagerState {
     pending: PendingRequests,
 
     /// Next request ID
-    next_id: AtomicU64,
+    next_id: Arc<AtomicU64>,
 
     /// Is shutting down
-    is_shutting_down: AtomicBool,
+    is_shutting_down: Arc<AtomicBool>,
 
     /// Start time
     start_time: Arc<RwLock<Option<Instant>>>,
 
     /// Total requests
-    total_requests: AtomicU64,
+    total_requests: Arc<AtomicU64>,
 
     /// Successful requests
-    successful_requests: AtomicU64,
+    successful_requests: Arc<AtomicU64>,
 
     /// Failed requests
-    failed_requests: AtomicU64,
+    failed_requests: Arc<AtomicU64>,
+
+    /// Requests rejected by admission control
+    rejected_requests: Arc<AtomicU64>,
+
+    /// Messages from the host dropped for exceeding the length limit
+    oversized_messages: Arc<AtomicU64>,
+
+    /// Sum of response times of answered requests (ms)
+    total_latency_ms: Arc<AtomicU64>,
+
+    /// Host notification broadcast
+    notifications: broadcast::Sender<IpcNotification>,
+
+    /// Manager event broadcast
+    events: broadcast::Sender<ManagerEvent>,
+
+    /// Finished requests (only sent while there are subscribers)
+    traffic: broadcast::Sender<TrafficEntry>,
+
+    /// Fault injection on the transport
+    chaos: Chaos,
+
+    /// Per-plugin call statistics
+    metrics: PluginMetrics,
+
+    /// Consecutive plugin exceptions
+    failures: FailureTracker,
+
+    /// Requests in flight by idempotency key
+    idempotent_calls: IdempotentCalls,
 
     /// Reader thread handle
     reader_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
@@ -298,26 +1202,64 @@ pub struct IpcManagerState {
 
     /// Stderr thread handle
     stderr_handle: Arc<Mutex<Option<JoinHandle<()>>>>,
+
+    /// Recent stderr lines (for crash reports)
+    stderr_tail: StderrTail,
+
+    /// PID of the process requests are routed to (0 before the first start)
+    active_pid: Arc<AtomicU32>,
+
+    /// `plugin/load` params of the loaded plugins, by name
+    loaded_plugins: Arc<Mutex<BTreeMap<String, Value>>>,
+
+    /// Set while a warm restart is in progress
+    is_switching: Arc<AtomicBool>,
+
+    /// Event topics that have listeners (sent to every new host)
+    event_topics: Arc<Mutex<BTreeSet<String>>>,
+
+    /// How the most recent subprocess exited
+    last_exit: Arc<Mutex<Option<ProcessExit>>>,
+
+    /// Successful starts (including warm restarts)
+    starts: Arc<AtomicU64>,
 }
 
 impl Clone for IpcManagerState {
     fn clone(&self) -> Self {
         Self {
-            config: self.config.clone(),
+            config: Arc::clone(&self.config),
             lifecycle: Arc::clone(&self.lifecycle),
             health: Arc::clone(&self.health),
             subprocess: Arc::clone(&self.subprocess),
             writer_tx: Arc::clone(&self.writer_tx),
             pending: Arc::clone(&self.pending),
-            next_id: AtomicU64::new(self.next_id.load(Ordering::SeqCst)),
-            is_shutting_down: AtomicBool::new(self.is_shutting_down.load(Ordering::SeqCst)),
+            next_id: Arc::clone(&self.next_id),
+            is_shutting_down: Arc::clone(&self.is_shutting_down),
             start_time: Arc::clone(&self.start_time),
-            total_requests: AtomicU64::new(self.total_requests.load(Ordering::SeqCst)),
-            successful_requests: AtomicU64::new(self.successful_requests.load(Ordering::SeqCst)),
-            failed_requests: AtomicU64::new(self.failed_requests.load(Ordering::SeqCst)),
+            total_requests: Arc::clone(&self.total_requests),
+            successful_requests: Arc::clone(&self.successful_requests),
+            failed_requests: Arc::clone(&self.failed_requests),
+            rejected_requests: Arc::clone(&self.rejected_requests),
+            oversized_messages: Arc::clone(&self.oversized_messages),
+            total_latency_ms: Arc::clone(&self.total_latency_ms),
+            notifications: self.notifications.clone(),
+            events: self.events.clone(),
+            traffic: self.traffic.clone(),
+            chaos: self.chaos.clone(),
+            metrics: self.metrics.clone(),
+            failures: self.failures.clone(),
+            idempotent_calls: Arc::clone(&self.idempotent_calls),
             reader_handle: Arc::clone(&self.reader_handle),
             writer_handle: Arc::clone(&self.writer_handle),
             stderr_handle: Arc::clone(&self.stderr_handle),
+            stderr_tail: Arc::clone(&self.stderr_tail),
+            active_pid: Arc::clone(&self.active_pid),
+            loaded_plugins: Arc::clone(&self.loaded_plugins),
+            is_switching: Arc::clone(&self.is_switching),
+            event_topics: Arc::clone(&self.event_topics),
+            last_exit: Arc::clone(&self.last_exit),
+            starts: Arc::clone(&self.starts),
         }
     }
 }
@@ -326,23 +1268,52 @@ impl IpcManagerState {
     /// Create a new IPC Manager with the specified configuration.
     pub fn new(config: IpcConfig) -> Self {
         let health_interval = Duration::from_secs(config.health_check_interval_secs);
+        let memory_threshold = config
+            .memory_warning_threshold_mb
+            .map(|mb| mb * 1024 * 1024);
+        let max_failures = config.max_consecutive_failures;
+        let heartbeat_interval = Some(Duration::from_secs(config.heartbeat_interval_secs))
+            .filter(|interval| !interval.is_zero());
+        let max_missed_heartbeats = config.max_missed_heartbeats;
 
         Self {
-            config,
+            config: Arc::new(Mutex::new(config)),
             lifecycle: Arc::new(RwLock::new(LifecycleState::Uninitialized)),
-            health: Arc::new(HealthMonitor::new(health_interval)),
+            health: Arc::new(
+                HealthMonitor::new(health_interval)
+                    .with_max_failures(max_failures)
+                    .with_memory_threshold(memory_threshold)
+                    .with_heartbeat(heartbeat_interval, max_missed_heartbeats),
+            ),
             subprocess: Arc::new(Mutex::new(None)),
             writer_tx: Arc::new(RwLock::new(None)),
             pending: Arc::new(RwLock::new(std::collections::HashMap::new())),
-            next_id: AtomicU64::new(1),
-            is_shutting_down: AtomicBool::new(false),
+            next_id: Arc::new(AtomicU64::new(1)),
+            is_shutting_down: Arc::new(AtomicBool::new(false)),
             start_time: Arc::new(RwLock::new(None)),
-            total_requests: AtomicU64::new(0),
-            successful_requests: AtomicU64::new(0),
-            failed_requests: AtomicU64::new(0),
+            total_requests: Arc::new(AtomicU64::new(0)),
+            successful_requests: Arc::new(AtomicU64::new(0)),
+            failed_requests: Arc::new(AtomicU64::new(0)),
+            rejected_requests: Arc::new(AtomicU64::new(0)),
+            oversized_messages: Arc::new(AtomicU64::new(0)),
+            total_latency_ms: Arc::new(AtomicU64::new(0)),
+            notifications: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+            events: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+            traffic: broadcast::channel(NOTIFICATION_CHANNEL_CAPACITY).0,
+            chaos: Chaos::new(),
+            metrics: PluginMetrics::new(),
+            failures: FailureTracker::new(),
+            idempotent_calls: Arc::new(Mutex::new(std::collections::HashMap::new())),
             reader_handle: Arc::new(Mutex::new(None)),
             writer_handle: Arc::new(Mutex::new(None)),
             stderr_handle: Arc::new(Mutex::new(None)),
+            stderr_tail: Arc::new(Mutex::new(VecDeque::new())),
+            active_pid: Arc::new(AtomicU32::new(0)),
+            loaded_plugins: Arc::new(Mutex::new(BTreeMap::new())),
+            is_switching: Arc::new(AtomicBool::new(false)),
+            event_topics: Arc::new(Mutex::new(BTreeSet::new())),
+            last_exit: Arc::new(Mutex::new(None)),
+            starts: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -351,6 +1322,14 @@ impl IpcManagerState {
         Self::new(IpcConfig::default())
     }
 
+    /// Hand out request ids from `other`'s counter, so an id reserved on one
+    /// host can be used on the other (see `next_request_id`).
+    #[must_use]
+    pub fn sharing_request_ids(mut self, other: &IpcManagerState) -> Self {
+        self.next_id = Arc::clone(&other.next_id);
+        self
+    }
+
     /// Get current lifecycle state.
     pub async fn lifecycle_state(&self) -> LifecycleState {
         *self.lifecycle.read().await
@@ -369,9 +1348,16 @@ impl IpcManagerState {
         &self.health
     }
 
-    /// Get configuration.
-    pub fn config(&self) -> &IpcConfig {
-        &self.config
+    /// Get a snapshot of the current configuration.
+    pub fn config(&self) -> IpcConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Set the subprocess working directory.
+    ///
+    /// Takes effect on the next `start()`; use `restart()` to apply it immediately.
+    pub fn set_working_dir(&self, dir: impl Into<PathBuf>) {
+        self.config.lock().unwrap().working_dir = Some(dir.into());
     }
 
     /// Check if manager is ready.
@@ -381,43 +1367,182 @@ impl IpcManagerState {
 
     /// Start the IPC Manager.
     ///
-    /// Spawns the Python subprocess and starts reader/writer threads.
+    /// Spawns the Python subprocess, starts reader/writer threads, and waits
+    /// for the host to answer the health check method. A host that doesn't
+    /// answer within `spawn_timeout_secs` is killed and the manager is
+    /// marked `Failed`.
+    #[tracing::instrument(name = "ipc.start", skip_all, err(Display))]
     pub async fn start(&self) -> Result<(), IpcError> {
         let current = self.lifecycle_state().await;
-        if current != LifecycleState::Uninitialized && current != LifecycleState::Stopped {
+        if current != LifecycleState::Uninitialized && !current.is_terminal() {
             log::warn!("Cannot start from state: {current}");
             return Err(IpcError::SpawnError(format!(
                 "Cannot start from state: {current}"
             )));
         }
 
+        self.is_shutting_down.store(false, Ordering::SeqCst);
         self.set_lifecycle(LifecycleState::Starting).await;
         self.health.set_state(SubprocessState::Starting);
 
-        // Spawn subprocess
-        let subprocess_config = self.config.to_subprocess_config();
-        let mut handle = spawn_plugin_host(subprocess_config)?;
+        // Plugins loaded into a previous process are not loaded in the new one
+        self.loaded_plugins.lock().unwrap().clear();
 
-        let pid = handle.pid;
-        log::info!("Subprocess started with PID: {pid}");
+        let process = match self.launch(true) {
+            Ok(process) => process,
+            Err(error) => {
+                log::error!("Failed to spawn plugin host: {error}");
+                self.health.set_state(SubprocessState::Stopped);
+                self.set_lifecycle(LifecycleState::Failed).await;
+                return Err(error);
+            }
+        };
+        let pid = process.handle.pid;
+        let spawn_timeout = process.handle.config().spawn_timeout_secs;
+        let writer = process.writer_tx.clone();
+        self.attach(process).await;
+
+        // A host that cannot start (no interpreter, missing module, syntax
+        // error) exits right away; report why instead of a generic crash
+        let exit = self
+            .startup_exit(
+                || {
+                    self.subprocess
+                        .lock()
+                        .unwrap()
+                        .as_mut()
+                        .and_then(|handle| handle.try_wait().ok().flatten())
+                },
+                || {
+                    !self
+                        .stderr_handle
+                        .lock()
+                        .unwrap()
+                        .as_ref()
+                        .is_some_and(|handle| !handle.is_finished())
+                },
+            )
+            .await;
+        if let Some(error) = exit {
+            log::error!("Plugin host failed to start: {error}");
+            self.writer_tx.write().await.take();
+            self.set_lifecycle(LifecycleState::Stopped).await;
+            return Err(error);
+        }
 
-        // Take stdio handles
-        let stdin = handle
-            .take_stdin()
-            .ok_or_else(|| IpcError::SpawnError("Failed to get stdin".to_string()))?;
-        let stdout = handle
-            .take_stdout()
-            .ok_or_else(|| IpcError::SpawnError("Failed to get stdout".to_string()))?;
-        let stderr = handle
-            .take_stderr()
-            .ok_or_else(|| IpcError::SpawnError("Failed to get stderr".to_string()))?;
+        if let Err(error) = self.handshake(&writer, pid, spawn_timeout).await {
+            log::error!("{error}");
+            self.fail_start().await;
+            return Err(error);
+        }
+        self.send_event_topics(&writer).await;
+
+        // Update state
+        *self.start_time.write().await = Some(Instant::now());
+        self.starts.fetch_add(1, Ordering::SeqCst);
+        self.health.mark_started();
+        self.set_lifecycle(LifecycleState::Ready).await;
+        self.spawn_monitors(pid);
+
+        log::info!("IPC Manager started successfully");
+        Ok(())
+    }
+
+    /// Wait up to `timeout_secs` for the host to answer the health check
+    /// method through `writer`.
+    ///
+    /// The host reads requests once its plugin manager is initialised, so
+    /// the first answer (even an error response) means it is ready.
+    async fn handshake(
+        &self,
+        writer: &mpsc::Sender<WriterMessage>,
+        pid: u32,
+        timeout_secs: u64,
+    ) -> Result<(), IpcError> {
+        let method = self.config().health_check_method;
+        let result = self
+            .send_request(
+                writer,
+                self.next_request_id(),
+                &method,
+                serde_json::json!({}),
+                timeout_secs,
+            )
+            .await;
+        match result {
+            Ok(_) | Err(IpcError::RpcError { .. }) => Ok(()),
+            Err(IpcError::Timeout(_)) => Err(IpcError::SpawnError(format!(
+                "Plugin host (PID {pid}) did not become ready within {timeout_secs}s and was \
+                 stopped; raise ipc.spawn_timeout_secs if plugins take longer to import"
+            ))),
+            Err(e) => Err(IpcError::SpawnError(format!(
+                "Plugin host (PID {pid}) exited before becoming ready: {e}"
+            ))),
+        }
+    }
+
+    /// Kill a host that failed the readiness handshake and mark the
+    /// manager failed.
+    async fn fail_start(&self) {
+        // Detach it first so the reader doesn't report the kill as a crash
+        self.active_pid.store(0, Ordering::SeqCst);
+        self.health.set_state(SubprocessState::Killed);
+        self.writer_tx.write().await.take();
+
+        if let Some(mut handle) = self.subprocess.lock().unwrap().take() {
+            if let Err(e) = handle.kill() {
+                log::error!("Failed to kill plugin host: {e}");
+            }
+            self.record_exit(&mut handle, true);
+        }
+        if let Some(reader) = self.reader_handle.lock().unwrap().take() {
+            let _ = reader.join();
+        }
+
+        self.set_lifecycle(LifecycleState::Failed).await;
+    }
+
+    /// Spawn a host process with its writer, reader, and stderr threads.
+    ///
+    /// With `activate`, requests are routed to the process as soon as it is
+    /// attached and its exit is reported as a crash. A standby process
+    /// (warm restart) becomes active when `attach` switches to it.
+    #[tracing::instrument(name = "ipc.spawn", skip(self), fields(pid), err(Display))]
+    fn launch(&self, activate: bool) -> Result<HostProcess, IpcError> {
+        let config = self.config();
+        let mut handle = spawn_plugin_host(config.to_subprocess_config())?;
+
+        let pid = handle.pid;
+        tracing::Span::current().record("pid", pid);
+        log::info!("Subprocess started with PID: {pid}");
+        if activate {
+            self.active_pid.store(pid, Ordering::SeqCst);
+        }
+
+        // Take stdio handles
+        let stdin = handle
+            .take_stdin()
+            .ok_or_else(|| IpcError::SpawnError("Failed to get stdin".to_string()))?;
+        let stdout = handle
+            .take_stdout()
+            .ok_or_else(|| IpcError::SpawnError("Failed to get stdout".to_string()))?;
+        let stderr = handle
+            .take_stderr()
+            .ok_or_else(|| IpcError::SpawnError("Failed to get stderr".to_string()))?;
 
         // Create writer channel
         let (writer_tx, writer_rx) = mpsc::channel::<WriterMessage>(100);
-        *self.writer_tx.write().await = Some(writer_tx);
+        let host_requests = HostRequests {
+            handler: config.request_handler,
+            writer: writer_tx.clone(),
+            runtime: tokio::runtime::Handle::current(),
+            working_dir: config
+                .working_dir
+                .or_else(|| std::env::current_dir().ok()),
+        };
 
         // Start writer thread
-        let writer_handle = std::thread::Builder::new()
+        let writer = std::thread::Builder::new()
             .name("ipc-writer".to_string())
             .spawn(move || {
                 Self::writer_task(stdin, writer_rx);
@@ -427,119 +1552,405 @@ impl IpcManagerState {
         // Start reader thread
         let pending_clone = Arc::clone(&self.pending);
         let health_clone = Arc::clone(&self.health);
-        let reader_handle = std::thread::Builder::new()
+        let notifications_clone = self.notifications.clone();
+        let chaos = self.chaos.clone();
+        let line_limit = LineLimit {
+            max_bytes: config.max_line_bytes,
+            oversized: Arc::clone(&self.oversized_messages),
+        };
+        let exit_watch = ExitWatch {
+            pid,
+            active_pid: Arc::clone(&self.active_pid),
+            is_shutting_down: Arc::clone(&self.is_shutting_down),
+            subprocess: Arc::clone(&self.subprocess),
+            stderr_tail: Arc::clone(&self.stderr_tail),
+            events: self.events.clone(),
+        };
+        let reader = std::thread::Builder::new()
             .name("ipc-reader".to_string())
             .spawn(move || {
-                Self::reader_task(stdout, pending_clone, health_clone);
+                Self::reader_task(
+                    stdout,
+                    pending_clone,
+                    health_clone,
+                    notifications_clone,
+                    &host_requests,
+                    &exit_watch,
+                    &chaos,
+                    &line_limit,
+                );
             })
             .map_err(|e| IpcError::SpawnError(e.to_string()))?;
 
-        // Start stderr thread
-        let stderr_handle = std::thread::Builder::new()
+        // Start stderr thread (a standby shares the tail with the running host)
+        if activate {
+            self.stderr_tail.lock().unwrap().clear();
+        }
+        let stderr_tail = Arc::clone(&self.stderr_tail);
+        let stderr_thread = std::thread::Builder::new()
             .name("ipc-stderr".to_string())
             .spawn(move || {
-                Self::stderr_task(stderr);
+                Self::stderr_task(stderr, &stderr_tail);
             })
             .map_err(|e| IpcError::SpawnError(e.to_string()))?;
 
-        // Store handles
-        *self.subprocess.lock().unwrap() = Some(handle);
-        *self.reader_handle.lock().unwrap() = Some(reader_handle);
-        *self.writer_handle.lock().unwrap() = Some(writer_handle);
-        *self.stderr_handle.lock().unwrap() = Some(stderr_handle);
+        Ok(HostProcess {
+            handle,
+            writer_tx,
+            reader,
+            writer,
+            stderr: stderr_thread,
+        })
+    }
 
-        // Update state
-        *self.start_time.write().await = Some(Instant::now());
-        self.health.mark_started();
-        self.set_lifecycle(LifecycleState::Ready).await;
+    /// Route requests to `process`.
+    ///
+    /// Returns the previously attached process, if it was still attached.
+    async fn attach(&self, process: HostProcess) -> Option<HostProcess> {
+        // Holding the writer lock keeps callers from sending mid-switch
+        let mut writer_tx = self.writer_tx.write().await;
+        self.active_pid.store(process.handle.pid, Ordering::SeqCst);
+        let handle = self.subprocess.lock().unwrap().replace(process.handle);
+        let reader = self.reader_handle.lock().unwrap().replace(process.reader);
+        let writer = self.writer_handle.lock().unwrap().replace(process.writer);
+        let stderr = self.stderr_handle.lock().unwrap().replace(process.stderr);
+        let previous_tx = writer_tx.replace(process.writer_tx);
+
+        if let (Some(handle), Some(writer_tx), Some(reader), Some(writer), Some(stderr)) =
+            (handle, previous_tx, reader, writer, stderr)
+        {
+            Some(HostProcess {
+                handle,
+                writer_tx,
+                reader,
+                writer,
+                stderr,
+            })
+        } else {
+            None
+        }
+    }
 
-        log::info!("IPC Manager started successfully");
-        Ok(())
+    /// Shut down a process that no longer receives requests.
+    async fn retire(&self, process: HostProcess, timeout: Duration) {
+        let HostProcess {
+            mut handle,
+            writer_tx,
+            reader,
+            ..
+        } = process;
+        let _ = writer_tx.send(WriterMessage::Shutdown).await;
+        if let Err(e) = handle.shutdown(timeout) {
+            log::error!("Subprocess shutdown error: {e}");
+        }
+        self.record_exit(&mut handle, true);
+        let _ = reader.join();
+    }
+
+    /// Record the exit status of a subprocess that has exited.
+    fn record_exit(&self, handle: &mut SubprocessHandle, expected: bool) {
+        if let Ok(Some(status)) = handle.try_wait() {
+            let exit = ProcessExit::new(handle.pid, status, expected);
+            log::info!(
+                "Subprocess {} exited (code: {:?}, signal: {:?})",
+                exit.pid,
+                exit.exit_code,
+                exit.signal
+            );
+            *self.last_exit.lock().unwrap() = Some(exit);
+        }
+    }
+
+    /// Start the resource sampling, health check, and plugin health tasks
+    /// for the process with `pid`.
+    fn spawn_monitors(&self, pid: u32) {
+        // Sample subprocess resource usage every health interval
+        tokio::spawn(Self::resource_task(
+            pid,
+            Arc::clone(&self.health),
+            self.events.clone(),
+        ));
+
+        // Check the host's health every interval
+        tokio::spawn(self.clone().health_check_task(pid));
+
+        // Watch for missed heartbeats
+        if self.health.heartbeat_interval().is_some() {
+            tokio::spawn(self.clone().heartbeat_task(pid));
+        }
+
+        // Probe loaded plugins on a slower cadence
+        tokio::spawn(self.clone().plugin_health_task(pid));
+
+        // Reap the process when it exits
+        tokio::spawn(self.clone().exit_wait_task(pid));
+    }
+
+    /// Wait up to `STARTUP_EXIT_WINDOW` for a subprocess to exit and
+    /// classify why it did.
+    ///
+    /// `try_wait` polls the subprocess; `stderr_done` tells whether its
+    /// stderr thread has finished. Returns None if it is still running.
+    async fn startup_exit(
+        &self,
+        mut try_wait: impl FnMut() -> Option<std::process::ExitStatus>,
+        stderr_done: impl Fn() -> bool,
+    ) -> Option<IpcError> {
+        let deadline = Instant::now() + STARTUP_EXIT_WINDOW;
+        let exit_code = loop {
+            if let Some(status) = try_wait() {
+                break status.code();
+            }
+            if Instant::now() >= deadline {
+                return None;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        };
+
+        // Let the stderr thread read everything the host wrote
+        let deadline = Instant::now() + EXIT_STATUS_WAIT;
+        while !stderr_done() && Instant::now() < deadline {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let stderr: Vec<String> = self.stderr_tail.lock().unwrap().iter().cloned().collect();
+        Some(classify_startup_exit(
+            &self.config().python_path,
+            exit_code,
+            &stderr,
+        ))
     }
 
     /// Writer task - sends requests to subprocess stdin.
+    ///
+    /// Messages already queued behind the first one are joined into one
+    /// write and flush. Only messages that are already waiting are taken,
+    /// so a single request is written right away; the batch is cut at
+    /// `WRITE_BATCH_MAX_BYTES` or `WRITE_BATCH_MAX_DELAY`.
     fn writer_task(mut stdin: ChildStdin, mut rx: mpsc::Receiver<WriterMessage>) {
         log::debug!("Writer task started");
 
-        while let Some(msg) = rx.blocking_recv() {
+        let mut batch = Vec::new();
+
+        while let Some(first) = rx.blocking_recv() {
+            let deadline = Instant::now() + WRITE_BATCH_MAX_DELAY;
+            let (frames, shutdown) = Self::collect_batch(first, &mut rx, &mut batch, deadline);
+
+            if !batch.is_empty() {
+                if frames > 1 {
+                    log::trace!("Writing {frames} coalesced messages");
+                }
+                if let Err(e) = stdin.write_all(&batch) {
+                    log::error!("Failed to write: {e}");
+                    break;
+                }
+                if let Err(e) = stdin.flush() {
+                    log::error!("Failed to flush: {e}");
+                    break;
+                }
+            }
+            if shutdown {
+                break;
+            }
+        }
+
+        log::debug!("Writer task exited");
+    }
+
+    /// Frame `first` and the messages queued behind it into `batch`, until
+    /// the queue is empty, the batch is full, or `deadline` passes.
+    ///
+    /// # Returns
+    ///
+    /// The number of framed messages and whether a shutdown was received.
+    fn collect_batch(
+        first: WriterMessage,
+        rx: &mut mpsc::Receiver<WriterMessage>,
+        batch: &mut Vec<u8>,
+        deadline: Instant,
+    ) -> (usize, bool) {
+        batch.clear();
+        let mut frames = 0;
+        let mut next = Some(first);
+
+        while let Some(msg) = next.take() {
             match msg {
                 WriterMessage::Request(json) => {
                     log::debug!("Sending: {json}");
-                    if let Err(e) = writeln!(stdin, "{json}") {
-                        log::error!("Failed to write: {e}");
-                        break;
-                    }
-                    if let Err(e) = stdin.flush() {
-                        log::error!("Failed to flush: {e}");
-                        break;
-                    }
+                    batch.extend_from_slice(json.as_bytes());
+                    batch.push(b'\n');
+                    frames += 1;
                 }
                 WriterMessage::Shutdown => {
                     log::debug!("Writer received shutdown");
-                    break;
+                    return (frames, true);
                 }
             }
+            if batch.len() < WRITE_BATCH_MAX_BYTES && Instant::now() < deadline {
+                next = rx.try_recv().ok();
+            }
         }
-
-        log::debug!("Writer task exited");
+        (frames, false)
     }
 
-    /// Reader task - reads responses from subprocess stdout.
+    /// Reader task - reads responses and notifications from subprocess stdout
+    /// and dispatches requests from the host.
+    ///
+    /// Messages longer than the line limit are dropped; if one is a
+    /// response, its request fails with `IpcError::MessageTooLarge`.
+    #[allow(clippy::too_many_arguments)]
     fn reader_task(
         stdout: std::process::ChildStdout,
         pending: PendingRequests,
         health: Arc<HealthMonitor>,
+        notifications: broadcast::Sender<IpcNotification>,
+        host_requests: &HostRequests,
+        exit_watch: &ExitWatch,
+        chaos: &Chaos,
+        line_limit: &LineLimit,
     ) {
         log::debug!("Reader task started");
 
-        let reader = BufReader::new(stdout);
+        let mut reader = BufReader::new(stdout);
+        // Reused for every line to avoid an allocation per message
+        let mut bytes = Vec::new();
+        let mut line = String::new();
 
-        for line in reader.lines() {
-            match line {
-                Ok(json) => {
-                    if json.trim().is_empty() {
-                        continue;
-                    }
-
-                    log::debug!("Received: {json}");
-
-                    match serde_json::from_str::<JsonRpcResponse>(&json) {
-                        Ok(response) => {
-                            if let Some(id) = response.id {
-                                let mut pending_guard =
-                                    futures::executor::block_on(pending.write());
-                                if let Some(tx) = pending_guard.remove(&id) {
-                                    let _ = tx.send(Ok(response));
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            log::error!("Failed to parse response: {e}");
-                        }
-                    }
-                }
+        loop {
+            bytes.clear();
+            let read = match read_line_limited(&mut reader, &mut bytes, line_limit.max_bytes) {
+                Ok(LineRead::Eof) => break,
+                Ok(read) => read,
                 Err(e) => {
                     log::error!("Read error: {e}");
                     break;
                 }
+            };
+
+            if let LineRead::Truncated { length } = read {
+                line_limit.oversized.fetch_add(1, Ordering::SeqCst);
+                let limit = line_limit.max_bytes;
+                let id = truncated_response_id(&bytes);
+                log::error!(
+                    "Dropped a {length} byte message from the host (limit {limit} bytes, \
+                     response to request {id:?})"
+                );
+                let request =
+                    id.and_then(|id| futures::executor::block_on(pending.write()).remove(&id));
+                if let Some(request) = request {
+                    let _ = request
+                        .tx
+                        .send(Err(IpcError::MessageTooLarge { length, limit }));
+                }
+                continue;
+            }
+
+            line.clear();
+            match std::str::from_utf8(&bytes) {
+                Ok(text) => line.push_str(text),
+                Err(e) => {
+                    log::error!("Failed to parse message: {e}");
+                    continue;
+                }
+            }
+            chaos.corrupt(&mut line);
+            let json = line.trim();
+            if json.is_empty() {
+                continue;
+            }
+
+            log::debug!("Received: {json}");
+
+            let message = match serde_json::from_str::<IncomingMessage>(json) {
+                Ok(message) => message,
+                Err(e) => {
+                    log::error!("Failed to parse response: {e}");
+                    continue;
+                }
+            };
+
+            if message.method.is_some() {
+                let value = match serde_json::from_str::<Value>(json) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        log::error!("Failed to parse message: {e}");
+                        continue;
+                    }
+                };
+
+                if let Some((id, request)) =
+                    HostRequest::from_message(&value, host_requests.working_dir.as_deref())
+                {
+                    host_requests.dispatch(id, request);
+                } else if value.get("method").and_then(Value::as_str) == Some(HEARTBEAT_METHOD) {
+                    match value.get("params").and_then(HostHeartbeat::from_params) {
+                        Some(heartbeat) => health.record_heartbeat(heartbeat),
+                        None => log::warn!("Invalid heartbeat: {json}"),
+                    }
+                } else if let Some(notification) = IpcNotification::from_message(&value) {
+                    // No subscribers is fine; the notification is dropped
+                    let _ = notifications.send(notification);
+                }
+                continue;
+            }
+
+            let Some(id) = message.id else {
+                continue;
+            };
+            let Some(id) = id.as_u64() else {
+                log::error!("Failed to parse response: invalid id {id}");
+                continue;
+            };
+            if chaos.drop_response(id) {
+                continue;
+            }
+            let response = RawResponse {
+                result: message.result.map(RawValue::to_owned),
+                error: message.error,
+            };
+            let mut pending_guard = futures::executor::block_on(pending.write());
+            if let Some(request) = pending_guard.remove(&id) {
+                let _ = request.tx.send(Ok(response));
             }
         }
 
+        // A process retired by a warm restart exits on purpose, and the
+        // pending requests belong to its replacement
+        if exit_watch.active_pid.load(Ordering::SeqCst) != exit_watch.pid {
+            log::debug!("Retired subprocess {} exited", exit_watch.pid);
+            return;
+        }
+
         log::warn!("Reader detected subprocess exit");
         health.mark_crashed("Subprocess stdout closed");
 
         // Cancel pending requests
+        let mut pending_methods = Vec::new();
         let mut pending_guard = futures::executor::block_on(pending.write());
-        for (id, tx) in pending_guard.drain() {
+        for (id, request) in pending_guard.drain() {
             log::warn!("Cancelling request {id}");
-            let _ = tx.send(Err(IpcError::SubprocessCrashed));
+            pending_methods.push(request.method);
+            let _ = request.tx.send(Err(IpcError::SubprocessCrashed));
+        }
+        drop(pending_guard);
+
+        if !exit_watch.is_shutting_down.load(Ordering::SeqCst) {
+            // Waiting for the exit status also lets the stderr thread drain
+            let exit_code = exit_watch.exit_code();
+            let stderr_tail = exit_watch.stderr_tail.lock().unwrap().iter().cloned().collect();
+            let _ = exit_watch.events.send(ManagerEvent::SubprocessCrashed {
+                pid: exit_watch.pid,
+                exit_code,
+                stderr_tail,
+                pending_methods,
+            });
         }
 
         log::debug!("Reader task exited");
     }
 
-    /// Stderr task - logs stderr output.
-    fn stderr_task(stderr: std::process::ChildStderr) {
+    /// Stderr task - logs stderr output and keeps the most recent lines.
+    fn stderr_task(stderr: std::process::ChildStderr, tail: &StderrTail) {
         log::debug!("Stderr task started");
 
         let reader = BufReader::new(stderr);
@@ -547,6 +1958,13 @@ impl IpcManagerState {
         for line in reader.lines() {
             match line {
                 Ok(text) => {
+                    {
+                        let mut tail = tail.lock().unwrap();
+                        if tail.len() == STDERR_TAIL_LINES {
+                            tail.pop_front();
+                        }
+                        tail.push_back(text.clone());
+                    }
                     if text.contains("ERROR") {
                         log::error!("[Python] {text}");
                     } else if text.contains("WARNING") {
@@ -567,17 +1985,394 @@ impl IpcManagerState {
         log::debug!("Stderr task exited");
     }
 
+    /// Resource task - samples subprocess RSS/CPU until the process exits.
+    async fn resource_task(
+        pid: u32,
+        health: Arc<HealthMonitor>,
+        events: broadcast::Sender<ManagerEvent>,
+    ) {
+        use sysinfo::{Pid, System};
+
+        let mut sys = System::new();
+        let sys_pid = Pid::from_u32(pid);
+        let period = health.check_interval().max(Duration::from_secs(1));
+        let mut interval = tokio::time::interval(period);
+
+        loop {
+            interval.tick().await;
+
+            if !sys.refresh_process(sys_pid) || !health.state().is_running() {
+                break;
+            }
+            let Some(process) = sys.process(sys_pid) else {
+                break;
+            };
+
+            let usage = ResourceUsage::new(process.memory(), process.cpu_usage());
+            log::debug!(
+                "Subprocess {pid}: rss={} MB cpu={:.1}%",
+                usage.rss_bytes / (1024 * 1024),
+                usage.cpu_percent
+            );
+
+            if health.record_resource_usage(usage) {
+                let _ = events.send(ManagerEvent::MemoryWarning {
+                    pid,
+                    rss_bytes: usage.rss_bytes,
+                    threshold_bytes: health.memory_threshold().unwrap_or_default(),
+                });
+            }
+        }
+
+        log::debug!("Resource sampling for PID {pid} stopped");
+    }
+
+    /// Exit waiter task - reaps the subprocess when it exits and records
+    /// its exit status.
+    ///
+    /// Stops once the process exits or is detached; `shutdown()` and warm
+    /// restarts record the exit of the process they stop.
+    async fn exit_wait_task(self, pid: u32) {
+        loop {
+            tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+
+            let mut subprocess = self.subprocess.lock().unwrap();
+            let Some(handle) = subprocess.as_mut().filter(|handle| handle.pid == pid) else {
+                break;
+            };
+            if handle.try_wait().ok().flatten().is_some() {
+                let expected = self.is_shutting_down.load(Ordering::SeqCst);
+                self.record_exit(handle, expected);
+                break;
+            }
+        }
+
+        log::debug!("Exit waiter for PID {pid} stopped");
+    }
+
+    /// Health check task - calls the health check method every interval
+    /// and records the result in the health monitor.
+    ///
+    /// Stops when the subprocess it was started for is no longer running.
+    async fn health_check_task(self, pid: u32) {
+        let period = self.health.check_interval().max(Duration::from_secs(1));
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+
+        loop {
+            interval.tick().await;
+
+            let current_pid = self.subprocess.lock().unwrap().as_ref().map(|h| h.pid);
+            if current_pid != Some(pid) || !self.health.state().is_running() {
+                break;
+            }
+
+            let config = self.config();
+            let started = Instant::now();
+            let result = self
+                .request(
+                    self.next_request_id(),
+                    config.health_check_method,
+                    serde_json::json!({}),
+                    config.probe_timeout_secs,
+                )
+                .await;
+            match result {
+                Ok(_) => self.health.record_success(started.elapsed()),
+                // The host exiting or stopping is not a failed check
+                Err(
+                    IpcError::SubprocessCrashed | IpcError::ShuttingDown | IpcError::NotRunning,
+                ) => break,
+                // Heartbeats say the host is alive, just busy with requests
+                Err(e) if self.health.host_busy() => {
+                    log::debug!("Health check skipped, host busy: {e}");
+                }
+                Err(e) => self.health.record_failure(e.to_string()),
+            }
+        }
+
+        log::debug!("Health checks for PID {pid} stopped");
+    }
+
+    /// Heartbeat task - counts the heartbeats the host missed every
+    /// heartbeat interval, degrading the subprocess after too many.
+    ///
+    /// Stops when the subprocess it was started for is no longer running.
+    async fn heartbeat_task(self, pid: u32) {
+        let Some(period) = self.health.heartbeat_interval() else {
+            return;
+        };
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+
+        loop {
+            interval.tick().await;
+
+            let current_pid = self.subprocess.lock().unwrap().as_ref().map(|h| h.pid);
+            if current_pid != Some(pid) || !self.health.state().is_running() {
+                break;
+            }
+
+            self.health.check_heartbeat();
+        }
+
+        log::debug!("Heartbeat watch for PID {pid} stopped");
+    }
+
+    /// Plugin health task - calls `plugin/health` every few health intervals
+    /// and raises an event when a plugin degrades or recovers.
+    ///
+    /// Stops when the subprocess it was started for is no longer running.
+    /// A failed probe is skipped; host-level health covers an unresponsive
+    /// host.
+    async fn plugin_health_task(self, pid: u32) {
+        let period = self.health.check_interval().max(Duration::from_secs(1))
+            * PLUGIN_HEALTH_INTERVAL_FACTOR;
+        // Plugins are loaded after startup, so skip the immediate first tick
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+
+        loop {
+            interval.tick().await;
+
+            let current_pid = self.subprocess.lock().unwrap().as_ref().map(|h| h.pid);
+            if current_pid != Some(pid) || !self.health.state().is_running() {
+                break;
+            }
+
+            let results = match self.call(PLUGIN_HEALTH_METHOD, serde_json::json!({})).await {
+                Ok(result) => PluginHealth::parse_all(&result),
+                Err(e) => {
+                    log::debug!("Plugin health probe failed: {e}");
+                    continue;
+                }
+            };
+
+            for (plugin, health) in self.health.record_plugin_health(results) {
+                let event = if health.is_healthy {
+                    log::info!("Plugin {plugin} recovered ({})", health.status);
+                    ManagerEvent::PluginRecovered {
+                        plugin,
+                        status: health.status,
+                    }
+                } else {
+                    log::warn!(
+                        "Plugin {plugin} degraded ({}): {}",
+                        health.status,
+                        health.message.as_deref().unwrap_or("no message")
+                    );
+                    ManagerEvent::PluginDegraded {
+                        plugin,
+                        status: health.status,
+                        message: health.message,
+                    }
+                };
+                let _ = self.events.send(event);
+            }
+        }
+
+        log::debug!("Plugin health probing for PID {pid} stopped");
+    }
+
     /// Generate next request ID.
-    fn next_request_id(&self) -> u64 {
+    ///
+    /// Callers that need to correlate notifications with a request can
+    /// reserve an id here and pass it to `call_with_id`. Ids that are pending
+    /// or were never reserved are rejected with `IpcError::RequestIdInUse`.
+    pub fn next_request_id(&self) -> u64 {
         self.next_id.fetch_add(1, Ordering::SeqCst)
     }
 
+    /// Whether `id` was handed out by `next_request_id`.
+    fn is_reserved(&self, id: u64) -> bool {
+        id >= 1 && id < self.next_id.load(Ordering::SeqCst)
+    }
+
+    /// Subscribe to notifications sent by the Python host.
+    pub fn subscribe(&self) -> broadcast::Receiver<IpcNotification> {
+        self.notifications.subscribe()
+    }
+
+    /// Subscribe to events raised by the manager.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<ManagerEvent> {
+        self.events.subscribe()
+    }
+
+    /// Subscribe to finished requests.
+    ///
+    /// Requests are only recorded while a subscriber exists, so the params
+    /// are not copied when nobody is watching.
+    pub fn subscribe_traffic(&self) -> broadcast::Receiver<TrafficEntry> {
+        self.traffic.subscribe()
+    }
+
+    /// Fault injection layer of this host (off unless configured).
+    pub fn chaos(&self) -> &Chaos {
+        &self.chaos
+    }
+
+    /// Per-plugin call statistics of this host.
+    pub fn metrics(&self) -> &PluginMetrics {
+        &self.metrics
+    }
+
     /// Send a JSON-RPC request.
     pub async fn call(
         &self,
         method: impl Into<String>,
         params: Value,
     ) -> Result<Value, IpcError> {
+        let id = self.next_request_id();
+        self.call_with_id(id, method, params).await
+    }
+
+    /// Send a JSON-RPC request and return the result as raw JSON.
+    ///
+    /// For results passed on (e.g. to the frontend) without inspection, this
+    /// skips building a `Value`.
+    pub async fn call_raw(
+        &self,
+        method: impl Into<String>,
+        params: Value,
+    ) -> Result<Box<RawValue>, IpcError> {
+        let timeout_secs = self.config().timeout_secs;
+        self.request_raw(self.next_request_id(), method, params, timeout_secs)
+            .await
+    }
+
+    /// Await `call` unless a request with the same idempotency key is in
+    /// flight, in which case wait for that request's response instead.
+    ///
+    /// Callers retrying a request (e.g. a UI resubmitting a generation)
+    /// pass the same `key`, `method`, and `params`, and the host runs it
+    /// once. The key is released when the response arrives, so a later call
+    /// with it is sent again. Reusing a key in flight for a different
+    /// method or params fails with `IpcError::IdempotencyConflict`.
+    pub async fn call_idempotent<Fut>(
+        &self,
+        key: &str,
+        method: &str,
+        params: &Value,
+        call: Fut,
+    ) -> Result<Box<RawValue>, IpcError>
+    where
+        Fut: Future<Output = Result<Box<RawValue>, IpcError>> + Send + 'static,
+    {
+        let fingerprint = format!("{method} {params}");
+        let response = {
+            let mut calls = self.idempotent_calls.lock().unwrap();
+            match calls.get(key) {
+                Some(existing) if existing.fingerprint != fingerprint => {
+                    return Err(IpcError::IdempotencyConflict(key.to_string()));
+                }
+                Some(existing) => {
+                    log::debug!("Joining in-flight {method} request with key {key}");
+                    existing.response.clone()
+                }
+                None => {
+                    let in_flight = Arc::clone(&self.idempotent_calls);
+                    let owned_key = key.to_string();
+                    let response = async move {
+                        let result = call.await;
+                        in_flight.lock().unwrap().remove(&owned_key);
+                        result
+                    }
+                    .boxed()
+                    .shared();
+                    calls.insert(
+                        key.to_string(),
+                        IdempotentCall {
+                            fingerprint,
+                            response: response.clone(),
+                        },
+                    );
+                    response
+                }
+            }
+        };
+        response.await
+    }
+
+    /// Send a JSON-RPC request using a reserved request id.
+    pub async fn call_with_id(
+        &self,
+        id: u64,
+        method: impl Into<String>,
+        params: Value,
+    ) -> Result<Value, IpcError> {
+        let timeout_secs = self.config().timeout_secs;
+        self.request(id, method, params, timeout_secs).await
+    }
+
+    /// Like `call_with_id`, but waits up to `timeout_secs` instead of the
+    /// configured timeout (for calls known to take long).
+    pub async fn call_with_id_timeout(
+        &self,
+        id: u64,
+        method: impl Into<String>,
+        params: Value,
+        timeout_secs: u64,
+    ) -> Result<Value, IpcError> {
+        self.request(id, method, params, timeout_secs).await
+    }
+
+    /// Send a JSON-RPC request and pass its progress notifications to
+    /// `on_progress` until the response arrives.
+    ///
+    /// The result is returned as raw JSON (see `call_raw`).
+    pub async fn call_with_progress<F>(
+        &self,
+        id: u64,
+        method: impl Into<String>,
+        params: Value,
+        on_progress: F,
+    ) -> Result<Box<RawValue>, IpcError>
+    where
+        F: Fn(ProgressUpdate) + Send,
+    {
+        // Subscribe before sending so early progress is not missed
+        let mut notifications = self.subscribe();
+        let timeout_secs = self.config().timeout_secs;
+        let call = self.request_raw(id, method, params, timeout_secs);
+        tokio::pin!(call);
+
+        loop {
+            tokio::select! {
+                result = &mut call => return result,
+                notification = notifications.recv() => match notification {
+                    Ok(n) => {
+                        if let Some(update) = ProgressUpdate::from_notification(&n)
+                            .filter(|update| update.request_id == id)
+                        {
+                            on_progress(update);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        log::warn!("Request {id} missed {skipped} notifications");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => return (&mut call).await,
+                },
+            }
+        }
+    }
+
+    /// Send a JSON-RPC request and wait up to `timeout_secs` for the response.
+    async fn request(
+        &self,
+        id: u64,
+        method: impl Into<String>,
+        params: Value,
+        timeout_secs: u64,
+    ) -> Result<Value, IpcError> {
+        let raw = self.request_raw(id, method, params, timeout_secs).await?;
+        Ok(serde_json::from_str(raw.get())?)
+    }
+
+    /// Like `request`, but returns the result as raw JSON.
+    async fn request_raw(
+        &self,
+        id: u64,
+        method: impl Into<String>,
+        params: Value,
+        timeout_secs: u64,
+    ) -> Result<Box<RawValue>, IpcError> {
         if !self.is_ready().await {
             return Err(IpcError::NotRunning);
         }
@@ -587,41 +2382,126 @@ impl IpcManagerState {
         }
 
         let method = method.into();
-        let id = self.next_request_id();
+        let writer = self
+            .writer_tx
+            .read()
+            .await
+            .clone()
+            .ok_or(IpcError::NotInitialized)?;
 
+        // Remember plugin loads so a warm restart can replay them
+        let plugin_change = matches!(
+            method.as_str(),
+            PLUGIN_LOAD_METHOD | PLUGIN_UNLOAD_METHOD | PLUGIN_SWAP_METHOD
+        )
+        .then(|| params.clone());
+        let traced = (self.traffic.receiver_count() > 0)
+            .then(|| TrafficEntry::sent(id, &method, params.clone()));
+        let plugin = self.metrics.plugin_for(&method, &params);
+        let started = Instant::now();
+
+        let result = self
+            .send_request_raw(&writer, id, &method, params, timeout_secs)
+            .await;
+        if let Some(plugin) = plugin {
+            self.metrics.record(&plugin, &result, started.elapsed());
+            self.contain_failure(&plugin, &method, &result);
+        }
+        if let (Ok(raw), Some(params)) = (&result, plugin_change) {
+            self.record_plugin_change(&method, &params);
+            self.metrics.track_contract(&method, &params, raw);
+        }
+        if let Some(mut entry) = traced {
+            entry.finish(&result, started.elapsed());
+            let _ = self.traffic.send(entry);
+        }
+        result
+    }
+
+    /// Send a request through `writer` and wait up to `timeout_secs` for
+    /// the response.
+    async fn send_request(
+        &self,
+        writer: &mpsc::Sender<WriterMessage>,
+        id: u64,
+        method: &str,
+        params: Value,
+        timeout_secs: u64,
+    ) -> Result<Value, IpcError> {
+        let raw = self
+            .send_request_raw(writer, id, method, params, timeout_secs)
+            .await?;
+        Ok(serde_json::from_str(raw.get())?)
+    }
+
+    /// Like `send_request`, but returns the result as raw JSON.
+    #[tracing::instrument(name = "ipc.call", skip(self, writer, params), err(Display))]
+    async fn send_request_raw(
+        &self,
+        writer: &mpsc::Sender<WriterMessage>,
+        id: u64,
+        method: &str,
+        params: Value,
+        timeout_secs: u64,
+    ) -> Result<Box<RawValue>, IpcError> {
         log::debug!("Calling: id={id}, method={method}");
 
         // Build request
-        let request = JsonRpcRequest::new(id, &method, params);
+        let request = JsonRpcRequest::new(id, method, params);
         let json = request.to_json()?;
 
         // Create response channel
         let (tx, rx) = oneshot::channel();
 
-        // Register pending
+        // Register pending, unless the id is taken or too many requests are
+        // already waiting
         {
+            let max_pending = self.config.lock().unwrap().max_pending_requests;
             let mut pending = self.pending.write().await;
-            pending.insert(id, tx);
+            if !self.is_reserved(id) || pending.contains_key(&id) {
+                log::warn!("Rejecting {method}: request id {id} is pending or not reserved");
+                return Err(IpcError::RequestIdInUse(id));
+            }
+            if max_pending > 0 && pending.len() >= max_pending {
+                self.rejected_requests.fetch_add(1, Ordering::SeqCst);
+                log::warn!("Rejecting {method}: {} requests pending", pending.len());
+                return Err(IpcError::QueueFull(max_pending));
+            }
+            pending.insert(
+                id,
+                PendingRequest {
+                    method: method.to_string(),
+                    started: Instant::now(),
+                    timeout_secs,
+                    tx,
+                },
+            );
+        }
+
+        if let Some(delay) = self.chaos.latency() {
+            tokio::time::sleep(delay).await;
         }
 
         // Send request
-        let writer_tx = self.writer_tx.read().await;
-        let writer = writer_tx
-            .as_ref()
-            .ok_or(IpcError::NotInitialized)?;
+        if writer.send(WriterMessage::Request(json)).await.is_err() {
+            self.pending.write().await.remove(&id);
+            return Err(IpcError::ChannelClosed);
+        }
 
-        writer
-            .send(WriterMessage::Request(json))
-            .await
-            .map_err(|_| IpcError::ChannelClosed)?;
+        if self.chaos.kill() {
+            self.inject_kill(method);
+        }
 
         self.total_requests.fetch_add(1, Ordering::SeqCst);
+        let sent = Instant::now();
 
         // Wait with timeout
-        let timeout = Duration::from_secs(self.config.timeout_secs);
+        let timeout = Duration::from_secs(timeout_secs);
         match tokio::time::timeout(timeout, rx).await {
             Ok(Ok(Ok(response))) => {
                 self.successful_requests.fetch_add(1, Ordering::SeqCst);
+                let latency_ms = u64::try_from(sent.elapsed().as_millis()).unwrap_or(u64::MAX);
+                self.total_latency_ms.fetch_add(latency_ms, Ordering::SeqCst);
 
                 if let Some(error) = response.error {
                     self.failed_requests.fetch_add(1, Ordering::SeqCst);
@@ -631,7 +2511,7 @@ impl IpcManagerState {
                     });
                 }
 
-                Ok(response.result.unwrap_or(Value::Null))
+                Ok(response.result.unwrap_or_else(raw_null))
             }
             Ok(Ok(Err(e))) => {
                 self.failed_requests.fetch_add(1, Ordering::SeqCst);
@@ -645,11 +2525,268 @@ impl IpcManagerState {
             Err(_) => {
                 self.failed_requests.fetch_add(1, Ordering::SeqCst);
                 self.pending.write().await.remove(&id);
-                Err(IpcError::Timeout(self.config.timeout_secs))
+                Err(IpcError::Timeout(timeout_secs))
+            }
+        }
+    }
+
+    /// Kill the running host as an injected fault; the reader reports it
+    /// like any other crash.
+    fn inject_kill(&self, method: &str) {
+        if let Some(handle) = self.subprocess.lock().unwrap().as_mut() {
+            log::warn!(
+                "Fault injection: killing plugin host (PID {}) after {method}",
+                handle.pid
+            );
+            if let Err(e) = handle.kill() {
+                log::warn!("Fault injection kill failed: {e}");
             }
         }
     }
 
+    /// Track the loaded plugins after a successful load, unload, or swap.
+    fn record_plugin_change(&self, method: &str, params: &Value) {
+        let name = |key: &str| params.get(key).and_then(Value::as_str).map(str::to_string);
+        let mut loaded = self.loaded_plugins.lock().unwrap();
+        match method {
+            PLUGIN_LOAD_METHOD => {
+                if let Some(plugin) = name("name") {
+                    loaded.insert(plugin, params.clone());
+                }
+            }
+            PLUGIN_UNLOAD_METHOD => {
+                if let Some(plugin) = name("name") {
+                    loaded.remove(&plugin);
+                }
+            }
+            PLUGIN_SWAP_METHOD => {
+                if let Some(old) = name("old") {
+                    loaded.remove(&old);
+                }
+                if let Some(new) = name("new") {
+                    let mut load = serde_json::json!({ "name": new });
+                    for key in ["config", "permissions", "data_dir"] {
+                        if let Some(value) = params.get(key) {
+                            load[key] = value.clone();
+                        }
+                    }
+                    loaded.insert(new, load);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Count a finished call into plugin code and reload the plugin in the
+    /// background once it failed too often in a row.
+    ///
+    /// Management requests (`plugin/load` etc.) are not counted, so a
+    /// failed reload doesn't start another one.
+    fn contain_failure(
+        &self,
+        plugin: &str,
+        method: &str,
+        result: &Result<Box<RawValue>, IpcError>,
+    ) {
+        if method.starts_with("plugin/") && method != "plugin/call" {
+            return;
+        }
+        let threshold = self.config.lock().unwrap().plugin_reload_failures;
+        if let Some(failures) = self.failures.record(plugin, result, threshold) {
+            tokio::spawn(self.clone().reload_plugin(plugin.to_string(), failures));
+        }
+    }
+
+    /// Unload a plugin and load it again with the params of its last load,
+    /// then raise a `PluginReloaded` event.
+    async fn reload_plugin(self, plugin: String, failures: u32) {
+        log::warn!("Plugin {plugin} failed {failures} times in a row; reloading it");
+        let load = self.loaded_plugins.lock().unwrap().get(&plugin).cloned();
+        let outcome = match load {
+            Some(params) => {
+                let unload = serde_json::json!({ "name": plugin });
+                match self.call(PLUGIN_UNLOAD_METHOD, unload).await {
+                    Ok(_) => self.call(PLUGIN_LOAD_METHOD, params).await.map(|_| ()),
+                    Err(e) => Err(e),
+                }
+                .map_err(|e| e.to_string())
+            }
+            None => Err(format!("Plugin {plugin} was not loaded through this host")),
+        };
+        self.failures.finish_reload(&plugin);
+
+        match &outcome {
+            Ok(()) => log::info!("Plugin {plugin} reloaded"),
+            Err(e) => log::error!("Failed to reload plugin {plugin}: {e}"),
+        }
+        let _ = self.events.send(ManagerEvent::PluginReloaded {
+            plugin,
+            failures,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    /// Pass this manager's plugin events (degraded, recovered, reloaded)
+    /// on to another manager's subscribers, until this manager is dropped.
+    ///
+    /// Used to surface events of named and sandboxed hosts through the
+    /// core host, whose events reach the frontend.
+    pub fn relay_plugin_events(&self, to: &IpcManagerState) {
+        let mut events = self.subscribe_events();
+        let target = to.events.clone();
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(
+                        event @ (ManagerEvent::PluginDegraded { .. }
+                        | ManagerEvent::PluginRecovered { .. }
+                        | ManagerEvent::PluginReloaded { .. }),
+                    ) => {
+                        let _ = target.send(event);
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Names of the plugins loaded through this manager.
+    pub fn loaded_plugins(&self) -> Vec<String> {
+        self.loaded_plugins
+            .lock()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Params of a plugin's last load through this manager (to load it
+    /// again the same way).
+    pub fn load_params(&self, plugin: &str) -> Option<Value> {
+        self.loaded_plugins.lock().unwrap().get(plugin).cloned()
+    }
+
+    /// Event topics that currently have listeners.
+    pub fn event_topics(&self) -> Vec<String> {
+        self.event_topics.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Replace the event topics that have listeners and tell the host.
+    ///
+    /// The topics are kept and sent again whenever a host starts, so this
+    /// succeeds while the host is not running.
+    pub async fn set_event_topics(&self, topics: Vec<String>) -> Result<(), IpcError> {
+        *self.event_topics.lock().unwrap() = topics.into_iter().collect();
+        if !self.is_ready().await {
+            return Ok(());
+        }
+        let params = serde_json::json!({ "topics": self.event_topics() });
+        self.call(EVENT_TOPICS_METHOD, params).await.map(|_| ())
+    }
+
+    /// Switch offline mode and tell the host.
+    ///
+    /// Hosts started later get it from `IpcConfig::offline`, so this
+    /// succeeds while the host is not running.
+    pub async fn set_offline(&self, offline: bool) -> Result<(), IpcError> {
+        self.config.lock().unwrap().offline = offline;
+        if !self.is_ready().await {
+            return Ok(());
+        }
+        let params = serde_json::json!({ "offline": offline });
+        self.call(OFFLINE_METHOD, params).await.map(|_| ())
+    }
+
+    /// Send the event topics to a new host; a failure only costs the host
+    /// some unneeded events.
+    async fn send_event_topics(&self, writer: &mpsc::Sender<WriterMessage>) {
+        let topics = self.event_topics();
+        if topics.is_empty() {
+            return;
+        }
+        let timeout_secs = self.config().timeout_secs;
+        let result = self
+            .send_request(
+                writer,
+                self.next_request_id(),
+                EVENT_TOPICS_METHOD,
+                serde_json::json!({ "topics": topics }),
+                timeout_secs,
+            )
+            .await;
+        if let Err(e) = result {
+            log::warn!("Failed to send event topics to the plugin host: {e}");
+        }
+    }
+
+    /// Send a JSON-RPC notification (no response expected).
+    pub async fn notify(&self, method: impl Into<String>, params: Value) -> Result<(), IpcError> {
+        if !self.is_ready().await {
+            return Err(IpcError::NotRunning);
+        }
+
+        let json = JsonRpcRequest::notification(method, params).to_json()?;
+
+        let writer_tx = self.writer_tx.read().await;
+        let writer = writer_tx.as_ref().ok_or(IpcError::NotInitialized)?;
+
+        writer
+            .send(WriterMessage::Request(json))
+            .await
+            .map_err(|_| IpcError::ChannelClosed)
+    }
+
+    /// Cancel an in-flight request.
+    ///
+    /// The pending caller is released with `ChannelClosed` and the host is
+    /// sent a `$/cancelRequest` notification so it can stop the work.
+    pub async fn cancel_request(&self, id: u64) -> Result<(), IpcError> {
+        let removed = self.pending.write().await.remove(&id).is_some();
+        if removed {
+            log::info!("Cancelling request {id}");
+        }
+        self.notify(CANCEL_REQUEST_METHOD, serde_json::json!({ "id": id }))
+            .await
+    }
+
+    /// List the requests waiting for a response, oldest first.
+    pub async fn pending_requests(&self) -> Vec<PendingInfo> {
+        let mut requests: Vec<PendingInfo> = self
+            .pending
+            .read()
+            .await
+            .iter()
+            .map(|(&id, request)| PendingInfo {
+                id,
+                method: request.method.clone(),
+                age_ms: u64::try_from(request.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+                timeout_secs: request.timeout_secs,
+            })
+            .collect();
+        requests.sort_by_key(|request| request.id);
+        requests
+    }
+
+    /// Abort an in-flight request locally.
+    ///
+    /// Unlike `cancel_request`, the host is not told: the caller is released
+    /// with `IpcError::Aborted` right away, which helps when the host is
+    /// stuck. A late response to the request is ignored.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no request with the id is pending.
+    pub async fn abort_request(&self, id: u64) -> bool {
+        let Some(request) = self.pending.write().await.remove(&id) else {
+            return false;
+        };
+        log::warn!("Aborting request {id} ({})", request.method);
+        let _ = request.tx.send(Err(IpcError::Aborted(id)));
+        true
+    }
+
     /// Send using `RequestBuilder`.
     pub async fn send_builder(&self, builder: RequestBuilder) -> Result<Value, IpcError> {
         let id = self.next_request_id();
@@ -676,10 +2813,16 @@ impl IpcManagerState {
 
         // Shutdown subprocess
         if let Some(mut handle) = self.subprocess.lock().unwrap().take() {
-            let timeout = Duration::from_secs(self.config.timeout_secs);
+            let timeout = Duration::from_secs(self.config().timeout_secs);
             if let Err(e) = handle.shutdown(timeout) {
                 log::error!("Subprocess shutdown error: {e}");
             }
+            self.record_exit(&mut handle, true);
+        }
+
+        // Wait for the reader to drain so it can't report on a later process
+        if let Some(reader) = self.reader_handle.lock().unwrap().take() {
+            let _ = reader.join();
         }
 
         self.set_lifecycle(LifecycleState::Stopped).await;
@@ -689,6 +2832,156 @@ impl IpcManagerState {
         Ok(())
     }
 
+    /// Wait for in-flight requests to finish, up to `timeout`.
+    ///
+    /// Returns the number of requests still pending. New requests are not
+    /// blocked; call `shutdown()` afterwards.
+    pub async fn drain(&self, timeout: Duration) -> usize {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let pending = self.pending.read().await.len();
+            if pending == 0 || Instant::now() >= deadline {
+                return pending;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+
+    /// Restart the IPC Manager.
+    ///
+    /// Shuts down the running subprocess (if any) and starts a new one using
+    /// the current configuration.
+    #[tracing::instrument(name = "ipc.respawn", skip_all, fields(warm = false), err(Display))]
+    pub async fn restart(&self) -> Result<(), IpcError> {
+        let current = self.lifecycle_state().await;
+        if current != LifecycleState::Uninitialized && !current.is_terminal() {
+            self.shutdown().await?;
+        }
+        self.start().await
+    }
+
+    /// Restart without interrupting callers (blue/green).
+    ///
+    /// Spawns a standby host with the current configuration, loads the
+    /// plugins loaded in the running host into it, then switches new
+    /// requests to it. Requests already in flight finish on the old host
+    /// (up to the request timeout) before it is shut down. If the standby
+    /// fails to start or to load a plugin, it is shut down and the running
+    /// host keeps serving.
+    ///
+    /// Falls back to `restart()` when no host is running.
+    #[tracing::instrument(name = "ipc.respawn", skip_all, fields(warm = true), err(Display))]
+    pub async fn restart_warm(&self) -> Result<(), IpcError> {
+        if !self.is_ready().await {
+            return self.restart().await;
+        }
+        if self.is_switching.swap(true, Ordering::SeqCst) {
+            return Err(IpcError::SpawnError(
+                "A warm restart is already in progress".to_string(),
+            ));
+        }
+        let result = self.switch_to_standby().await;
+        self.is_switching.store(false, Ordering::SeqCst);
+        result
+    }
+
+    /// Start a standby host, replay plugin loads into it, and switch to it.
+    async fn switch_to_standby(&self) -> Result<(), IpcError> {
+        let timeout = self.config().timeout_secs;
+        let mut standby = self.launch(false)?;
+        let pid = standby.handle.pid;
+        log::info!("Warm restart: standby host started with PID {pid}");
+
+        let exit = self
+            .startup_exit(
+                || standby.handle.try_wait().ok().flatten(),
+                || standby.stderr.is_finished(),
+            )
+            .await;
+        if let Some(error) = exit {
+            log::error!("Standby host failed to start: {error}");
+            self.retire(standby, Duration::from_secs(timeout)).await;
+            return Err(error);
+        }
+
+        let spawn_timeout = standby.handle.config().spawn_timeout_secs;
+        if let Err(error) = self.handshake(&standby.writer_tx, pid, spawn_timeout).await {
+            log::error!("{error}");
+            self.retire(standby, Duration::from_secs(timeout)).await;
+            return Err(error);
+        }
+
+        let loads: Vec<(String, Value)> = self
+            .loaded_plugins
+            .lock()
+            .unwrap()
+            .clone()
+            .into_iter()
+            .collect();
+        for (plugin, params) in &loads {
+            let id = self.next_request_id();
+            let result = self
+                .send_request(
+                    &standby.writer_tx,
+                    id,
+                    PLUGIN_LOAD_METHOD,
+                    params.clone(),
+                    timeout,
+                )
+                .await;
+            if let Err(e) = result {
+                log::error!("Standby host failed to load {plugin}: {e}");
+                self.retire(standby, Duration::from_secs(timeout)).await;
+                return Err(e);
+            }
+        }
+        self.send_event_topics(&standby.writer_tx).await;
+
+        // Requests sent before the switch are answered by the old host
+        let in_flight: Vec<u64> = self.pending.read().await.keys().copied().collect();
+        let previous = self.attach(standby).await;
+        *self.start_time.write().await = Some(Instant::now());
+        self.starts.fetch_add(1, Ordering::SeqCst);
+        self.health.mark_started();
+        self.spawn_monitors(pid);
+
+        let Some(previous) = previous else {
+            return Ok(());
+        };
+        let old_pid = previous.handle.pid;
+        log::info!("Warm restart: switched from PID {old_pid} to {pid}");
+        let _ = self.events.send(ManagerEvent::HostSwitched {
+            old_pid,
+            new_pid: pid,
+            plugins: loads.into_iter().map(|(plugin, _)| plugin).collect(),
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(timeout);
+        loop {
+            let waiting = {
+                let pending = self.pending.read().await;
+                in_flight.iter().any(|id| pending.contains_key(id))
+            };
+            if !waiting || Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        {
+            let mut pending = self.pending.write().await;
+            for id in &in_flight {
+                if let Some(request) = pending.remove(id) {
+                    log::warn!("Cancelling request {id} on retired host");
+                    let _ = request.tx.send(Err(IpcError::ShuttingDown));
+                }
+            }
+        }
+
+        self.retire(previous, Duration::from_secs(timeout)).await;
+        log::info!("Warm restart complete");
+        Ok(())
+    }
+
     /// Get manager statistics.
     pub async fn stats(&self) -> ManagerStats {
         let uptime = self
@@ -712,9 +3005,15 @@ impl IpcManagerState {
             total_requests: self.total_requests.load(Ordering::SeqCst),
             successful_requests: self.successful_requests.load(Ordering::SeqCst),
             failed_requests: self.failed_requests.load(Ordering::SeqCst),
+            rejected_requests: self.rejected_requests.load(Ordering::SeqCst),
+            oversized_messages: self.oversized_messages.load(Ordering::SeqCst),
+            total_latency_ms: self.total_latency_ms.load(Ordering::SeqCst),
             pending_requests: pending_count,
             uptime_secs: uptime,
             subprocess_pid: pid,
+            resource_usage: self.health.resource_usage(),
+            last_exit_status: self.last_exit.lock().unwrap().clone(),
+            restart_count: self.starts.load(Ordering::SeqCst).saturating_sub(1),
         }
     }
 }
@@ -778,13 +3077,25 @@ mod tests {
             .with_module_path("my.module")
             .with_working_dir("/tmp")
             .with_timeout(30)
-            .with_auto_respawn(false);
+            .with_auto_respawn(false)
+            .with_max_consecutive_failures(5)
+            .with_probe_timeout(45)
+            .with_health_check_method("health/deep")
+            .with_spawn_timeout(120)
+            .with_max_pending_requests(10);
 
         assert_eq!(config.python_path, "python3.11");
         assert_eq!(config.module_path, "my.module");
         assert_eq!(config.working_dir, Some(PathBuf::from("/tmp")));
         assert_eq!(config.timeout_secs, 30);
         assert!(!config.auto_respawn);
+        assert_eq!(config.max_consecutive_failures, 5);
+        assert_eq!(config.probe_timeout_secs, 45);
+        assert_eq!(config.health_check_method, "health/deep");
+        assert_eq!(config.spawn_timeout_secs, 120);
+        assert_eq!(config.max_pending_requests, 10);
+        assert_eq!(config.to_subprocess_config().spawn_timeout_secs, 120);
+        assert_eq!(IpcManagerState::new(config).health().max_failures(), 5);
     }
 
     #[test]
@@ -801,6 +3112,29 @@ mod tests {
         assert_eq!(subprocess_config.working_dir, Some(PathBuf::from("/tmp")));
     }
 
+    #[test]
+    fn test_env_provider_applied_at_spawn() {
+        let config = IpcConfig::new()
+            .with_working_dir("/tmp")
+            .with_env_provider(EnvProvider::new(|dir| {
+                vec![(
+                    "PROJECT".to_string(),
+                    dir.map(|d| d.display().to_string()).unwrap_or_default(),
+                )]
+            }));
+
+        let subprocess_config = config.to_subprocess_config();
+
+        assert_eq!(
+            subprocess_config.env_vars,
+            vec![("PROJECT".to_string(), "/tmp".to_string())]
+        );
+        assert_eq!(
+            format!("{:?}", config.env_provider),
+            "Some(EnvProvider(..))"
+        );
+    }
+
     #[tokio::test]
     async fn test_manager_state_creation() {
         let config = IpcConfig::default();
@@ -808,5 +3142,377 @@ mod tests {
 
         assert_eq!(state.lifecycle_state().await, LifecycleState::Uninitialized);
         assert!(!state.is_ready().await);
+
+        let stats = state.stats().await;
+        assert_eq!(stats.restart_count, 0);
+        assert!(stats.last_exit_status.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pending_requests_listed_and_aborted() {
+        let state = IpcManagerState::new(IpcConfig::default());
+        let (tx, rx) = oneshot::channel();
+        state.pending.write().await.insert(
+            7,
+            PendingRequest {
+                method: "tts/synthesize".to_string(),
+                started: Instant::now(),
+                timeout_secs: 30,
+                tx,
+            },
+        );
+
+        let pending = state.pending_requests().await;
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, 7);
+        assert_eq!(pending[0].method, "tts/synthesize");
+        assert_eq!(pending[0].timeout_secs, 30);
+
+        assert!(state.abort_request(7).await);
+        assert!(matches!(rx.await, Ok(Err(IpcError::Aborted(7)))));
+        assert!(state.pending_requests().await.is_empty());
+        assert!(!state.abort_request(7).await);
+    }
+
+    #[tokio::test]
+    async fn test_request_id_in_use_rejected() {
+        let state = IpcManagerState::new(IpcConfig::default());
+        let (writer, _requests) = mpsc::channel(4);
+        let id = state.next_request_id();
+        let (tx, _rx) = oneshot::channel();
+        state.pending.write().await.insert(
+            id,
+            PendingRequest {
+                method: "tts/synthesize".to_string(),
+                started: Instant::now(),
+                timeout_secs: 30,
+                tx,
+            },
+        );
+
+        let pending = state
+            .send_request_raw(&writer, id, "plugin/call", serde_json::json!({}), 30)
+            .await;
+        assert!(matches!(pending, Err(IpcError::RequestIdInUse(i)) if i == id));
+        let unreserved = state
+            .send_request_raw(&writer, id + 100, "plugin/call", serde_json::json!({}), 30)
+            .await;
+        assert!(matches!(unreserved, Err(IpcError::RequestIdInUse(_))));
+        assert_eq!(state.pending_requests().await[0].method, "tts/synthesize");
+    }
+
+    #[test]
+    fn test_read_line_limited() {
+        let input = b"{\"id\":1}\n{\"jsonrpc\":\"2.0\",\"id\":2,\"result\":\"xxxxxxxx\"}\nlast";
+        let mut reader = BufReader::with_capacity(4, &input[..]);
+        let mut buf = Vec::new();
+
+        assert_eq!(
+            read_line_limited(&mut reader, &mut buf, 16).unwrap(),
+            LineRead::Line
+        );
+        assert_eq!(buf, b"{\"id\":1}");
+
+        buf.clear();
+        assert_eq!(
+            read_line_limited(&mut reader, &mut buf, 16).unwrap(),
+            LineRead::Truncated { length: 44 }
+        );
+        assert_eq!(buf.len(), 16);
+
+        buf.clear();
+        assert_eq!(
+            read_line_limited(&mut reader, &mut buf, 16).unwrap(),
+            LineRead::Line
+        );
+        assert_eq!(buf, b"last");
+        buf.clear();
+        assert_eq!(
+            read_line_limited(&mut reader, &mut buf, 16).unwrap(),
+            LineRead::Eof
+        );
+    }
+
+    #[test]
+    fn test_truncated_response_id() {
+        assert_eq!(
+            truncated_response_id(b"{\"jsonrpc\":\"2.0\",\"id\":42,\"result\":\"aaa"),
+            Some(42)
+        );
+        assert_eq!(
+            truncated_response_id(b"{\"jsonrpc\": \"2.0\", \"id\" : 7"),
+            Some(7)
+        );
+        // Notifications and host requests answer no pending request
+        assert_eq!(
+            truncated_response_id(b"{\"jsonrpc\":\"2.0\",\"method\":\"x\",\"params\":{\"id\":3"),
+            None
+        );
+        assert_eq!(
+            truncated_response_id(b"{\"jsonrpc\":\"2.0\",\"id\":\"host-1\",\"method\""),
+            None
+        );
+        assert_eq!(
+            truncated_response_id(b"{\"jsonrpc\":\"2.0\",\"result\":[1,2"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_calls_share_response() {
+        let state = IpcManagerState::new(IpcConfig::default());
+        let sent = Arc::new(AtomicU32::new(0));
+        let params = serde_json::json!({"prompt": "hi"});
+        let (tx, rx) = oneshot::channel::<()>();
+
+        let call = |sent: Arc<AtomicU32>, gate: Option<oneshot::Receiver<()>>| async move {
+            sent.fetch_add(1, Ordering::SeqCst);
+            if let Some(gate) = gate {
+                let _ = gate.await;
+            }
+            Ok(RawValue::from_string("42".to_string()).unwrap())
+        };
+
+        let first = state.call_idempotent(
+            "key-1",
+            "llm/generate",
+            &params,
+            call(Arc::clone(&sent), Some(rx)),
+        );
+        let retry = state.call_idempotent(
+            "key-1",
+            "llm/generate",
+            &params,
+            call(Arc::clone(&sent), None),
+        );
+        let conflict = state.call_idempotent(
+            "key-1",
+            "llm/generate",
+            &serde_json::json!({}),
+            call(Arc::clone(&sent), None),
+        );
+        let (first, retry, conflict, ()) = tokio::join!(first, retry, conflict, async {
+            tokio::task::yield_now().await;
+            tx.send(()).unwrap();
+        });
+
+        assert_eq!(first.unwrap().get(), "42");
+        assert_eq!(retry.unwrap().get(), "42");
+        assert!(matches!(conflict, Err(IpcError::IdempotencyConflict(_))));
+        assert_eq!(sent.load(Ordering::SeqCst), 1);
+
+        // The key is released with the response
+        state
+            .call_idempotent(
+                "key-1",
+                "llm/generate",
+                &params,
+                call(Arc::clone(&sent), None),
+            )
+            .await
+            .unwrap();
+        assert_eq!(sent.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_set_working_dir_shared_across_clones() {
+        let state = IpcManagerState::new(IpcConfig::default());
+        let clone = state.clone();
+
+        state.set_working_dir("/tmp/project");
+
+        assert_eq!(clone.config().working_dir, Some(PathBuf::from("/tmp/project")));
+    }
+
+    #[test]
+    fn test_notification_from_message() {
+        let message = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "$/progress",
+            "params": {"id": 7, "progress": 0.5}
+        });
+        let notification = IpcNotification::from_message(&message).unwrap();
+        assert_eq!(notification.method, "$/progress");
+        assert_eq!(notification.params["id"], 7);
+
+        let response = serde_json::json!({"jsonrpc": "2.0", "id": 7, "result": {}});
+        assert!(IpcNotification::from_message(&response).is_none());
+    }
+
+    #[test]
+    fn test_writer_coalesces_queued_messages() {
+        let request = |id: u64| WriterMessage::Request(format!("{{\"id\":{id}}}"));
+        let deadline = Instant::now() + Duration::from_secs(60);
+        let (tx, mut rx) = mpsc::channel(8);
+        let mut batch = Vec::new();
+
+        tx.try_send(request(2)).unwrap();
+        tx.try_send(request(3)).unwrap();
+        tx.try_send(WriterMessage::Shutdown).unwrap();
+        tx.try_send(request(4)).unwrap();
+        let (frames, shutdown) =
+            IpcManagerState::collect_batch(request(1), &mut rx, &mut batch, deadline);
+        assert_eq!((frames, shutdown), (3, true));
+        assert_eq!(batch, b"{\"id\":1}\n{\"id\":2}\n{\"id\":3}\n");
+
+        // A full batch is written before taking more
+        let large = WriterMessage::Request("x".repeat(WRITE_BATCH_MAX_BYTES));
+        let (frames, shutdown) =
+            IpcManagerState::collect_batch(large, &mut rx, &mut batch, deadline);
+        assert_eq!((frames, shutdown), (1, false));
+        assert!(rx.try_recv().is_ok());
+
+        // An expired deadline writes the first message on its own
+        tx.try_send(request(5)).unwrap();
+        let (frames, _) =
+            IpcManagerState::collect_batch(request(6), &mut rx, &mut batch, Instant::now());
+        assert_eq!(frames, 1);
+    }
+
+    #[test]
+    fn test_incoming_message_keeps_result_raw() {
+        let line = r#"{"jsonrpc": "2.0", "id": 3, "result": {"text": "a\"b", "n": [1, 2]}}"#;
+        let message: IncomingMessage = serde_json::from_str(line).unwrap();
+        assert!(message.method.is_none());
+        assert_eq!(message.id, Some(serde_json::json!(3)));
+        assert_eq!(
+            message.result.unwrap().get(),
+            r#"{"text": "a\"b", "n": [1, 2]}"#
+        );
+
+        let line = r#"{"jsonrpc": "2.0", "method": "$/progress", "params": {}}"#;
+        let message: IncomingMessage = serde_json::from_str(line).unwrap();
+        assert!(message.method.is_some());
+        assert!(message.id.is_none());
+
+        let line = r#"{"jsonrpc": "2.0", "id": 4, "error": {"code": -32601, "message": "x"}}"#;
+        let message: IncomingMessage = serde_json::from_str(line).unwrap();
+        assert!(message.result.is_none());
+        assert_eq!(message.error.unwrap().code, -32601);
+        assert_eq!(raw_null().get(), "null");
+    }
+
+    #[test]
+    fn test_progress_update_from_notification() {
+        let notification = IpcNotification {
+            method: "progress/42".to_string(),
+            params: serde_json::json!({"percentage": 140.0, "stage": "load"}),
+        };
+        let update = ProgressUpdate::from_notification(&notification).unwrap();
+        assert_eq!(update.request_id, 42);
+        assert!((update.percentage - 100.0).abs() < f64::EPSILON);
+        assert_eq!(update.message, None);
+        assert_eq!(update.stage.as_deref(), Some("load"));
+        assert_eq!(update.event_name(), "ipc/progress/42");
+
+        let parse = |method: &str, params: Value| {
+            ProgressUpdate::from_notification(&IpcNotification {
+                method: method.to_string(),
+                params,
+            })
+        };
+        let percentage = serde_json::json!({"percentage": 5});
+        assert!(parse("$/progress", percentage.clone()).is_none());
+        assert!(parse("progress/abc", percentage).is_none());
+        assert!(parse("progress/1", Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_traffic_entry_records_outcome() {
+        let mut entry = TrafficEntry::sent(7, "echo", serde_json::json!({"x": 1}));
+        let raw = RawValue::from_string("[1,2]".to_string()).unwrap();
+        entry.finish(&Ok(raw), Duration::from_millis(12));
+        assert_eq!(entry.result.as_deref().map(RawValue::get), Some("[1,2]"));
+        assert_eq!(entry.error, None);
+        assert_eq!(entry.duration_ms, 12);
+
+        let mut entry = TrafficEntry::sent(8, "sleep", Value::Null);
+        entry.finish(&Err(IpcError::Timeout(1)), Duration::from_secs(1));
+        assert!(entry.result.is_none());
+        assert_eq!(
+            entry.error.as_deref(),
+            Some("Request timed out after 1 seconds")
+        );
+        assert_eq!(entry.duration_ms, 1000);
+    }
+
+    #[test]
+    fn test_host_request_from_message() {
+        let message = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": "host-1",
+            "method": "host/request_permission",
+            "params": {"plugin": "tts", "permission": "network"}
+        });
+        let (id, request) = HostRequest::from_message(&message, Some(Path::new("/work"))).unwrap();
+        assert_eq!(id, "host-1");
+        assert_eq!(request.method, "host/request_permission");
+        assert_eq!(request.working_dir, Some(PathBuf::from("/work")));
+
+        let notification = serde_json::json!({"jsonrpc": "2.0", "method": "$/progress"});
+        assert!(HostRequest::from_message(&notification, None).is_none());
+        let response = serde_json::json!({"jsonrpc": "2.0", "id": 7, "result": {}});
+        assert!(HostRequest::from_message(&response, None).is_none());
+    }
+
+    #[test]
+    fn test_clones_share_request_ids() {
+        let state = IpcManagerState::new(IpcConfig::default());
+        let clone = state.clone();
+
+        assert_eq!(state.next_request_id(), 1);
+        assert_eq!(clone.next_request_id(), 2);
+
+        let other = IpcManagerState::new(IpcConfig::default()).sharing_request_ids(&state);
+        assert_eq!(other.next_request_id(), 3);
+        assert!(state.is_reserved(3));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_process_exit_from_status() {
+        use std::os::unix::process::ExitStatusExt;
+
+        let exited = ProcessExit::new(42, ExitStatus::from_raw(1 << 8), false);
+        assert_eq!(exited.exit_code, Some(1));
+        assert_eq!(exited.signal, None);
+
+        let killed = ProcessExit::new(42, ExitStatus::from_raw(9), true);
+        assert_eq!(killed.exit_code, None);
+        assert_eq!(killed.signal, Some(9));
+        assert!(killed.expected);
+    }
+
+    #[test]
+    fn test_loaded_plugins_tracked_for_warm_restart() {
+        let state = IpcManagerState::new(IpcConfig::default());
+        let clone = state.clone();
+
+        state.record_plugin_change(
+            PLUGIN_LOAD_METHOD,
+            &serde_json::json!({ "name": "tts_old", "permissions": ["network"] }),
+        );
+        state.record_plugin_change(PLUGIN_LOAD_METHOD, &serde_json::json!({ "name": "stt" }));
+        state.record_plugin_change(
+            PLUGIN_SWAP_METHOD,
+            &serde_json::json!({
+                "old": "tts_old",
+                "new": "tts_new",
+                "permissions": [],
+                "data_dir": "/data/tts_new"
+            }),
+        );
+        state.record_plugin_change(PLUGIN_UNLOAD_METHOD, &serde_json::json!({ "name": "stt" }));
+
+        assert_eq!(clone.loaded_plugins(), vec!["tts_new".to_string()]);
+        assert_eq!(
+            clone.load_params("tts_new"),
+            Some(serde_json::json!({
+                "name": "tts_new",
+                "permissions": [],
+                "data_dir": "/data/tts_new"
+            }))
+        );
+        assert_eq!(clone.load_params("tts_old"), None);
     }
 }
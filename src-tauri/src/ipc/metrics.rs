@@ -0,0 +1,333 @@
+//! src-tauri/src/ipc/metrics.rs
+//! ============================
+//! Per-plugin call statistics of a plugin host.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//! Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+//!
+//! Each `IpcManagerState` owns a `PluginMetrics` layer. Every request that
+//! can be attributed to a plugin is counted with its latency and, if it
+//! failed, its error:
+//!
+//! - `plugin/load`, `plugin/unload`, `plugin/health`: the `name` param
+//! - `plugin/swap`: the `new` param
+//! - `plugin/call`: the `plugin` param
+//! - contract methods (`tts/synthesize`): the loaded plugin serving the
+//!   contract, learned from `plugin/load` results and followed across
+//!   swaps and unloads
+//!
+//! Other requests (`ping`, `plugin/list`, health checks of all plugins) are
+//! not counted. Statistics live as long as the manager, so they survive
+//! respawns and warm restarts of the host.
+//!
+//! Usage:
+//!     ```rust
+//!     let plugin = metrics.plugin_for(&method, &params);
+//!     let result = send(method, params).await;
+//!     if let Some(plugin) = plugin {
+//!         metrics.record(&plugin, &result, started.elapsed());
+//!     }
+//!     let stats = metrics.snapshot();
+//!     ```
+
+use serde::Serialize;
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use super::IpcError;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Call statistics of one plugin.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PluginStats {
+    /// Plugin name
+    pub plugin: String,
+    /// Requests sent to the plugin
+    pub calls: u64,
+    /// Requests that failed (errors, timeouts, lost hosts)
+    pub errors: u64,
+    /// `errors / calls` (0 without calls)
+    pub error_rate: f64,
+    /// Sum of the request latencies in milliseconds
+    pub total_latency_ms: u64,
+    /// Average request latency in milliseconds
+    pub avg_latency_ms: f64,
+    /// Slowest request in milliseconds
+    pub max_latency_ms: u64,
+    /// Message of the most recent failure
+    pub last_error: Option<String>,
+    /// When the most recent failure happened (RFC 3339)
+    pub last_error_at: Option<String>,
+    /// When the most recent request finished (RFC 3339)
+    pub last_call_at: Option<String>,
+}
+
+impl PluginStats {
+    /// Add the counts of the same plugin on another host.
+    pub fn merge(&mut self, other: &PluginStats) {
+        self.calls += other.calls;
+        self.errors += other.errors;
+        self.total_latency_ms = self.total_latency_ms.saturating_add(other.total_latency_ms);
+        self.max_latency_ms = self.max_latency_ms.max(other.max_latency_ms);
+        if other.last_error_at > self.last_error_at {
+            self.last_error.clone_from(&other.last_error);
+            self.last_error_at.clone_from(&other.last_error_at);
+        }
+        if other.last_call_at > self.last_call_at {
+            self.last_call_at.clone_from(&other.last_call_at);
+        }
+        self.update_rates();
+    }
+
+    /// Recompute the error rate and average latency from the totals.
+    #[allow(clippy::cast_precision_loss)]
+    fn update_rates(&mut self) {
+        if self.calls == 0 {
+            self.error_rate = 0.0;
+            self.avg_latency_ms = 0.0;
+        } else {
+            self.error_rate = self.errors as f64 / self.calls as f64;
+            self.avg_latency_ms = self.total_latency_ms as f64 / self.calls as f64;
+        }
+    }
+}
+
+/// Combine statistics from several hosts, sorted by plugin name.
+pub fn merge_stats(lists: impl IntoIterator<Item = Vec<PluginStats>>) -> Vec<PluginStats> {
+    let mut merged: BTreeMap<String, PluginStats> = BTreeMap::new();
+    for stats in lists.into_iter().flatten() {
+        match merged.get_mut(&stats.plugin) {
+            Some(existing) => existing.merge(&stats),
+            None => {
+                merged.insert(stats.plugin.clone(), stats);
+            }
+        }
+    }
+    merged.into_values().collect()
+}
+
+// ============================================
+// METRICS LAYER
+// ============================================
+
+/// Counts and contract routing.
+#[derive(Default)]
+struct MetricsState {
+    /// Statistics by plugin name
+    plugins: BTreeMap<String, PluginStats>,
+    /// Loaded plugin serving each contract
+    contracts: BTreeMap<String, String>,
+}
+
+/// Per-plugin statistics of one plugin host.
+///
+/// Cloning shares the layer.
+#[derive(Clone, Default)]
+pub struct PluginMetrics {
+    state: Arc<Mutex<MetricsState>>,
+}
+
+impl PluginMetrics {
+    /// Create a layer without statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The plugin a request is for, if it can be told.
+    pub fn plugin_for(&self, method: &str, params: &Value) -> Option<String> {
+        let param = |key: &str| params.get(key).and_then(Value::as_str).map(str::to_string);
+        match method {
+            "plugin/load" | "plugin/unload" | "plugin/health" => param("name"),
+            "plugin/swap" => param("new"),
+            "plugin/call" => param("plugin"),
+            _ => {
+                let (contract, _) = method.split_once('/')?;
+                self.state.lock().unwrap().contracts.get(contract).cloned()
+            }
+        }
+    }
+
+    /// Count a finished request for `plugin`.
+    pub fn record(
+        &self,
+        plugin: &str,
+        result: &Result<Box<RawValue>, IpcError>,
+        elapsed: Duration,
+    ) {
+        let latency_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+        let now = chrono::Utc::now().to_rfc3339();
+        let mut state = self.state.lock().unwrap();
+        let stats = state
+            .plugins
+            .entry(plugin.to_string())
+            .or_insert_with(|| PluginStats {
+                plugin: plugin.to_string(),
+                ..PluginStats::default()
+            });
+        stats.calls += 1;
+        stats.total_latency_ms = stats.total_latency_ms.saturating_add(latency_ms);
+        stats.max_latency_ms = stats.max_latency_ms.max(latency_ms);
+        if let Err(e) = result {
+            stats.errors += 1;
+            stats.last_error = Some(e.to_string());
+            stats.last_error_at = Some(now.clone());
+        }
+        stats.last_call_at = Some(now);
+        stats.update_rates();
+    }
+
+    /// Follow which plugin serves which contract after a successful load,
+    /// unload, or swap.
+    pub fn track_contract(&self, method: &str, params: &Value, result: &RawValue) {
+        let param = |key: &str| params.get(key).and_then(Value::as_str);
+        let mut state = self.state.lock().unwrap();
+        match method {
+            "plugin/load" => {
+                let contract = serde_json::from_str::<Value>(result.get())
+                    .ok()
+                    .and_then(|loaded| loaded.get("contract")?.as_str().map(str::to_string));
+                if let (Some(name), Some(contract)) = (param("name"), contract) {
+                    state.contracts.insert(contract, name.to_string());
+                }
+            }
+            "plugin/unload" => {
+                if let Some(name) = param("name") {
+                    state.contracts.retain(|_, plugin| plugin != name);
+                }
+            }
+            "plugin/swap" => {
+                if let (Some(old), Some(new)) = (param("old"), param("new")) {
+                    for plugin in state.contracts.values_mut() {
+                        if plugin == old {
+                            *plugin = new.to_string();
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Statistics of every plugin with requests, sorted by name.
+    pub fn snapshot(&self) -> Vec<PluginStats> {
+        self.state
+            .lock()
+            .unwrap()
+            .plugins
+            .values()
+            .cloned()
+            .collect()
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn ok() -> Result<Box<RawValue>, IpcError> {
+        Ok(RawValue::from_string("null".to_string()).unwrap())
+    }
+
+    fn raw(value: &Value) -> Box<RawValue> {
+        RawValue::from_string(value.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_record() {
+        let metrics = PluginMetrics::new();
+        metrics.record("tts", &ok(), Duration::from_millis(10));
+        metrics.record("tts", &ok(), Duration::from_millis(30));
+        metrics.record("tts", &Err(IpcError::Timeout(5)), Duration::from_millis(50));
+        metrics.record("stt", &ok(), Duration::from_millis(1));
+
+        let stats = metrics.snapshot();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].plugin, "stt");
+        assert_eq!(stats[0].errors, 0);
+        assert!(stats[0].last_error.is_none());
+
+        let tts = &stats[1];
+        assert_eq!((tts.calls, tts.errors), (3, 1));
+        assert!((tts.error_rate - 1.0 / 3.0).abs() < 1e-9);
+        assert!((tts.avg_latency_ms - 30.0).abs() < 1e-9);
+        assert_eq!(tts.max_latency_ms, 50);
+        assert!(tts.last_error.as_deref().unwrap().contains("timed out"));
+        assert!(tts.last_error_at.is_some());
+    }
+
+    #[test]
+    fn test_plugin_for_follows_contracts() {
+        let metrics = PluginMetrics::new();
+        assert_eq!(
+            metrics.plugin_for("plugin/load", &json!({"name": "tts_a"})),
+            Some("tts_a".to_string())
+        );
+        assert_eq!(
+            metrics.plugin_for("plugin/call", &json!({"plugin": "x", "method": "m"})),
+            Some("x".to_string())
+        );
+        assert_eq!(metrics.plugin_for("plugin/health", &json!({})), None);
+        assert_eq!(metrics.plugin_for("ping", &json!({})), None);
+        assert_eq!(metrics.plugin_for("tts/synthesize", &json!({})), None);
+
+        metrics.track_contract(
+            "plugin/load",
+            &json!({"name": "tts_a"}),
+            &raw(&json!({"name": "tts_a", "contract": "tts"})),
+        );
+        assert_eq!(
+            metrics.plugin_for("tts/synthesize", &json!({})),
+            Some("tts_a".to_string())
+        );
+
+        let swap = json!({"old": "tts_a", "new": "tts_b"});
+        assert_eq!(
+            metrics.plugin_for("plugin/swap", &swap),
+            Some("tts_b".to_string())
+        );
+        metrics.track_contract("plugin/swap", &swap, &raw(&json!({"success": true})));
+        assert_eq!(
+            metrics.plugin_for("tts/synthesize", &json!({})),
+            Some("tts_b".to_string())
+        );
+
+        metrics.track_contract(
+            "plugin/unload",
+            &json!({"name": "tts_b"}),
+            &raw(&json!({"success": true})),
+        );
+        assert_eq!(metrics.plugin_for("tts/synthesize", &json!({})), None);
+    }
+
+    #[test]
+    fn test_merge_stats() {
+        let core = PluginMetrics::new();
+        core.record("tts", &ok(), Duration::from_millis(10));
+        let sandbox = PluginMetrics::new();
+        sandbox.record(
+            "tts",
+            &Err(IpcError::ChannelClosed),
+            Duration::from_millis(30),
+        );
+        sandbox.record("stt", &ok(), Duration::from_millis(5));
+
+        let merged = merge_stats([core.snapshot(), sandbox.snapshot()]);
+        assert_eq!(merged.len(), 2);
+        let tts = &merged[1];
+        assert_eq!((tts.calls, tts.errors), (2, 1));
+        assert!((tts.avg_latency_ms - 20.0).abs() < 1e-9);
+        assert!((tts.error_rate - 0.5).abs() < 1e-9);
+        assert!(tts.last_error.is_some());
+    }
+}
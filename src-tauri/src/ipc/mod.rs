@@ -13,6 +13,13 @@
 //! - JSON-RPC send/receive over stdin/stdout (D031, D032)
 //! - Request ID tracking with timeout handling (D033)
 //! - Subprocess health monitoring and crash recovery (D034)
+//! - Named plugin hosts with their own environment (hosts.rs)
+//! - Fault injection for resilience testing (chaos.rs)
+//! - Per-method JSON Schema validation of params and results (schemas.rs)
+//! - TypeScript/Rust types generated from those schemas (codegen.rs)
+//! - Sandboxed hosts for untrusted plugins (sandbox.rs)
+//! - Per-plugin call statistics (metrics.rs)
+//! - Reloading plugins that keep failing (containment.rs)
 //!
 //! Dependencies:
 //!     - D025: plugins/_host/__main__.py (Python plugin host)
@@ -37,6 +44,16 @@ pub mod response;
 pub mod spawn;
 pub mod health;
 pub mod manager;
+pub mod hosts;
+pub mod chaos;
+pub mod schemas;
+pub mod codegen;
+pub mod sandbox;
+pub mod metrics;
+pub mod containment;
+
+#[cfg(test)]
+mod integration_tests;
 
 use serde::Serialize;
 use std::collections::HashMap;
@@ -77,6 +94,42 @@ pub const RESPAWN_DELAY_MS: u64 = 1000;
 /// Health check interval in seconds
 pub const HEALTH_CHECK_INTERVAL_SECS: u64 = 30;
 
+/// Consecutive failed health checks before the subprocess is degraded
+pub const MAX_CONSECUTIVE_FAILURES: u32 = 3;
+
+/// Timeout of a single health check in seconds
+pub const HEALTH_PROBE_TIMEOUT_SECS: u64 = 10;
+
+/// RPC method called by the health check
+pub const HEALTH_CHECK_METHOD: &str = "ping";
+
+/// Seconds between heartbeats sent by the host (0 disables them)
+pub const HEARTBEAT_INTERVAL_SECS: u64 = 5;
+
+/// Consecutive missed heartbeats before the subprocess is degraded
+pub const MAX_MISSED_HEARTBEATS: u32 = 3;
+
+/// Notification method of host heartbeats
+pub const HEARTBEAT_METHOD: &str = "$/heartbeat";
+
+/// Environment variable passing the heartbeat interval to the host
+pub const HEARTBEAT_ENV: &str = "APP_FACTORY_HEARTBEAT_SECS";
+
+/// Environment variable telling the host offline mode is on (`1`)
+pub const OFFLINE_ENV: &str = "APP_FACTORY_OFFLINE";
+
+/// Seconds a new host has to answer its first request before it is killed
+pub const SPAWN_TIMEOUT_SECS: u64 = 30;
+
+/// Maximum length of a message from the host; longer ones are dropped
+pub const MAX_LINE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Consecutive plugin exceptions before the plugin is reloaded
+pub const PLUGIN_RELOAD_FAILURES: u32 = 3;
+
+/// Requests that may wait for a response at once before new ones are rejected
+pub const MAX_PENDING_REQUESTS: usize = 1000;
+
 // ============================================
 // ERROR TYPES
 // ============================================
@@ -122,6 +175,67 @@ pub enum IpcError {
 
     #[error("Shutdown in progress")]
     ShuttingDown,
+
+    #[error("Request queue full ({0} requests pending)")]
+    QueueFull(usize),
+
+    #[error("Request {0} aborted")]
+    Aborted(u64),
+
+    #[error("Idempotency key {0} is in use by a different request")]
+    IdempotencyConflict(String),
+
+    #[error("Request id {0} is pending or was not reserved")]
+    RequestIdInUse(u64),
+
+    #[error("Response of {length} bytes exceeds the {limit} byte message limit")]
+    MessageTooLarge { length: usize, limit: usize },
+
+    #[error("Python interpreter not found: {0}")]
+    InterpreterNotFound(String),
+
+    #[error("Python module not found: {module}")]
+    ModuleNotFound { module: String },
+
+    #[error("Syntax error in plugin host: {detail}")]
+    HostSyntaxError { detail: String },
+
+    #[error("Plugin host exited during startup: {reason}")]
+    StartupExit { exit_code: Option<i32>, reason: String },
+}
+
+impl IpcError {
+    /// Suggested fix for errors the user can act on.
+    pub fn suggestion(&self) -> Option<String> {
+        match self {
+            IpcError::InterpreterNotFound(path) => Some(format!(
+                "Install Python 3, or set python.path (settings or app_factory.toml) to the \
+                 full path of the interpreter instead of \"{path}\""
+            )),
+            IpcError::ModuleNotFound { module } if module.starts_with("plugins") => Some(
+                "Start App Factory from the project root, or open the project folder that \
+                 contains plugins/"
+                    .to_string(),
+            ),
+            IpcError::ModuleNotFound { module } => {
+                let package = module.split('.').next().unwrap_or(module);
+                Some(format!(
+                    "Install the package into the interpreter set in python.path \
+                     (pip install {package}), or select the virtual environment that has it"
+                ))
+            }
+            IpcError::HostSyntaxError { .. } => Some(
+                "Fix the syntax error, or check that python.path points to a Python version \
+                 the plugins support"
+                    .to_string(),
+            ),
+            IpcError::StartupExit { .. } => Some(
+                "Run the plugin host module from the project root to see the full error"
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
 }
 
 impl From<std::io::Error> for IpcError {
@@ -108,6 +108,12 @@ pub mod error_codes {
     /// Model not found: Required model file not available.
     pub const MODEL_NOT_FOUND: i32 = -32052;
     
+    /// Execution timeout: Plugin method did not finish in time.
+    pub const EXECUTION_TIMEOUT: i32 = -32060;
+    
+    /// Plugin exception: Plugin method raised an exception.
+    pub const PLUGIN_EXCEPTION: i32 = -32061;
+    
     /// Check if error code is a standard JSON-RPC error.
     pub fn is_standard_error(code: i32) -> bool {
         (-32700..=-32600).contains(&code)
@@ -143,6 +149,8 @@ pub mod error_codes {
             RESOURCE_EXHAUSTED => "Resource exhausted",
             DEPENDENCY_MISSING => "Dependency missing",
             MODEL_NOT_FOUND => "Model not found",
+            EXECUTION_TIMEOUT => "Execution timeout",
+            PLUGIN_EXCEPTION => "Plugin exception",
             _ => "Unknown error",
         }
     }
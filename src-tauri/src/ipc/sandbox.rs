@@ -0,0 +1,357 @@
+//! src-tauri/src/ipc/sandbox.rs
+//! ============================
+//! Sandboxed isolation profile for untrusted plugins.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A plugin whose manifest says `"isolation": "sandboxed"` (or that the user
+//! marked sandboxed, see `HostRegistry::set_isolation`) is loaded and called
+//! in its own plugin host, started with a `SandboxConfig`:
+//!
+//! - The environment is cleared except for `ENV_ALLOWLIST` (no API keys or
+//!   other secrets from the app's environment)
+//! - On Linux, when unprivileged user namespaces are available, the host
+//!   runs under `unshare` in a new network namespace (no network) and mount
+//!   namespace, with the read-only directories bind-mounted read-only
+//! - On macOS the host runs under `sandbox-exec` with a profile denying
+//!   network access and writes to the read-only directories
+//! - Everywhere, `APP_FACTORY_SANDBOX=1` makes the host block non-loopback
+//!   sockets and writes to the read-only directories from inside Python
+//!   (`plugins/_host/sandbox.py`); on Windows that is the only layer
+//!
+//! Plugins can still reach the network through the app (`http/fetch`), which
+//! requires the user to grant them the `network` permission.
+//!
+//! Usage:
+//!     ```rust
+//!     let config = IpcConfig::default()
+//!         .with_sandbox(SandboxConfig::new(vec![project_root.join("plugins")]));
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
+
+use crate::permissions::find_manifest;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Set to "1" for sandboxed hosts (read by `plugins/_host/sandbox.py`)
+pub const SANDBOX_ENV: &str = "APP_FACTORY_SANDBOX";
+
+/// Directories the host must not write to, joined like `PATH`
+pub const READ_ONLY_ENV: &str = "APP_FACTORY_SANDBOX_READ_ONLY";
+
+/// Prefix of the names of per-plugin sandboxed hosts
+pub const SANDBOX_HOST_PREFIX: &str = "sandbox-";
+
+/// Environment variables passed through to a sandboxed host
+pub const ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USER",
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+    "TZ",
+    "TMPDIR",
+    "TMP",
+    "TEMP",
+    "VIRTUAL_ENV",
+    "CONDA_PREFIX",
+    "PYTHONHOME",
+    "PYTHONPATH",
+    "SYSTEMROOT",
+    "SYSTEMDRIVE",
+    "WINDIR",
+    "COMSPEC",
+    "PATHEXT",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+];
+
+/// Maximum length of a host name (see `ipc::hosts`)
+const MAX_HOST_NAME_LEN: usize = 32;
+
+/// Shell script run inside the Linux namespaces: bind-mount the first `$1`
+/// directories read-only, then exec the rest of the arguments
+const LINUX_MOUNT_SCRIPT: &str = r#"n=$1; shift
+while [ "$n" -gt 0 ]; do
+  mount --bind "$1" "$1" && mount -o remount,bind,ro "$1" "$1" || exit 126
+  shift; n=$((n - 1))
+done
+exec "$@""#;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// How a plugin runs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Isolation {
+    /// In the shared plugin host with the app's environment
+    #[default]
+    Standard,
+    /// In its own host with no network, read-only plugin files, and a
+    /// scrubbed environment
+    Sandboxed,
+}
+
+/// OS-level sandbox available on this machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OsSandbox {
+    /// Linux user, network and mount namespaces via `unshare`
+    Unshare,
+    /// macOS `sandbox-exec`
+    SandboxExec,
+    /// Only the in-process restrictions
+    None,
+}
+
+/// Restrictions of a sandboxed plugin host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxConfig {
+    /// Directories the host must not modify (e.g. `<project>/plugins`)
+    pub read_only: Vec<PathBuf>,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Isolation a plugin's manifest asks for (standard if unset or unknown).
+pub fn declared_isolation(project_root: &Path, plugin: &str) -> Isolation {
+    find_manifest(project_root, plugin)
+        .and_then(|(_, manifest)| {
+            manifest
+                .get("isolation")
+                .cloned()
+                .and_then(|value| serde_json::from_value::<Isolation>(value).ok())
+        })
+        .unwrap_or_default()
+}
+
+/// Name of a plugin's sandboxed host (`sandbox-<plugin>`).
+pub fn sandbox_host_name(plugin: &str) -> String {
+    let mut name = SANDBOX_HOST_PREFIX.to_string();
+    name.extend(plugin.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+            c.to_ascii_lowercase()
+        } else {
+            '-'
+        }
+    }));
+    name.truncate(MAX_HOST_NAME_LEN);
+    name
+}
+
+/// Detect the OS-level sandbox once.
+fn os_sandbox() -> OsSandbox {
+    static DETECTED: OnceLock<OsSandbox> = OnceLock::new();
+    *DETECTED.get_or_init(|| {
+        let works = |program: &str, args: &[&str]| {
+            Command::new(program)
+                .args(args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success())
+        };
+        let detected = if cfg!(target_os = "linux")
+            && works(
+                "unshare",
+                &["--user", "--map-root-user", "--net", "--mount", "true"],
+            ) {
+            OsSandbox::Unshare
+        } else if cfg!(target_os = "macos")
+            && works(
+                "sandbox-exec",
+                &["-p", "(version 1)(allow default)", "true"],
+            )
+        {
+            OsSandbox::SandboxExec
+        } else {
+            OsSandbox::None
+        };
+        if detected == OsSandbox::None {
+            log::warn!(
+                "No OS-level sandbox available; sandboxed plugins are restricted in-process only"
+            );
+        }
+        detected
+    })
+}
+
+/// Quote a path for a `sandbox-exec` profile string.
+fn profile_string(path: &Path) -> String {
+    Value::from(path.display().to_string()).to_string()
+}
+
+// ============================================
+// SANDBOX CONFIG
+// ============================================
+
+impl SandboxConfig {
+    /// Sandbox keeping the given directories read-only.
+    pub fn new(read_only: Vec<PathBuf>) -> Self {
+        Self { read_only }
+    }
+
+    /// Environment of the sandboxed host (the current process's environment
+    /// is cleared first).
+    pub fn env(&self) -> Vec<(String, String)> {
+        let mut vars: Vec<(String, String)> = ENV_ALLOWLIST
+            .iter()
+            .filter_map(|key| {
+                std::env::var(key)
+                    .ok()
+                    .map(|value| (key.to_string(), value))
+            })
+            .collect();
+        vars.push((SANDBOX_ENV.to_string(), "1".to_string()));
+        if let Ok(dirs) = std::env::join_paths(&self.read_only) {
+            vars.push((
+                READ_ONLY_ENV.to_string(),
+                dirs.to_string_lossy().into_owned(),
+            ));
+        }
+        // The plugin directory is read-only; don't try to write bytecode
+        vars.push(("PYTHONDONTWRITEBYTECODE".to_string(), "1".to_string()));
+        vars
+    }
+
+    /// Program and arguments that run `program args` inside the OS sandbox.
+    pub fn command(&self, program: &str, args: Vec<String>) -> (String, Vec<String>) {
+        self.command_for(os_sandbox(), program, args)
+    }
+
+    /// `command` for a given OS sandbox.
+    fn command_for(
+        &self,
+        sandbox: OsSandbox,
+        program: &str,
+        args: Vec<String>,
+    ) -> (String, Vec<String>) {
+        match sandbox {
+            OsSandbox::Unshare => {
+                let mut wrapped: Vec<String> = [
+                    "--user",
+                    "--map-root-user",
+                    "--net",
+                    "--mount",
+                    "--",
+                    "sh",
+                    "-c",
+                    LINUX_MOUNT_SCRIPT,
+                    "sh",
+                ]
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+                let dirs: Vec<&PathBuf> =
+                    self.read_only.iter().filter(|dir| dir.is_dir()).collect();
+                wrapped.push(dirs.len().to_string());
+                wrapped.extend(dirs.iter().map(|dir| dir.display().to_string()));
+                wrapped.push(program.to_string());
+                wrapped.extend(args);
+                ("unshare".to_string(), wrapped)
+            }
+            OsSandbox::SandboxExec => {
+                let mut profile = "(version 1)(allow default)(deny network*)".to_string();
+                for dir in &self.read_only {
+                    let _ = write!(
+                        profile,
+                        "(deny file-write* (subpath {}))",
+                        profile_string(dir)
+                    );
+                }
+                let mut wrapped = vec!["-p".to_string(), profile, program.to_string()];
+                wrapped.extend(args);
+                ("sandbox-exec".to_string(), wrapped)
+            }
+            OsSandbox::None => (program.to_string(), args),
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_declared_isolation() {
+        let root = std::env::temp_dir().join(format!("af_sandbox_{}", uuid::Uuid::new_v4()));
+        for (folder, manifest) in [
+            ("a", r#"{"name": "untrusted", "isolation": "sandboxed"}"#),
+            ("b", r#"{"name": "plain"}"#),
+            ("c", r#"{"name": "odd", "isolation": "jail"}"#),
+        ] {
+            let dir = root.join("plugins").join(folder);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("manifest.json"), manifest).unwrap();
+        }
+
+        assert_eq!(declared_isolation(&root, "untrusted"), Isolation::Sandboxed);
+        assert_eq!(declared_isolation(&root, "plain"), Isolation::Standard);
+        assert_eq!(declared_isolation(&root, "odd"), Isolation::Standard);
+        assert_eq!(declared_isolation(&root, "missing"), Isolation::Standard);
+    }
+
+    #[test]
+    fn test_sandbox_host_name() {
+        assert_eq!(sandbox_host_name("tts_kokoro"), "sandbox-tts_kokoro");
+        assert_eq!(sandbox_host_name("My Plugin"), "sandbox-my-plugin");
+        assert_eq!(sandbox_host_name(&"x".repeat(40)).len(), MAX_HOST_NAME_LEN);
+    }
+
+    #[test]
+    fn test_env_is_scrubbed() {
+        let sandbox = SandboxConfig::new(vec![PathBuf::from("plugins")]);
+        let env = sandbox.env();
+        assert!(env
+            .iter()
+            .all(|(key, _)| ENV_ALLOWLIST.contains(&key.as_str())
+                || [SANDBOX_ENV, READ_ONLY_ENV, "PYTHONDONTWRITEBYTECODE"]
+                    .contains(&key.as_str())));
+        assert!(env.contains(&(SANDBOX_ENV.to_string(), "1".to_string())));
+        assert!(env.contains(&(READ_ONLY_ENV.to_string(), "plugins".to_string())));
+    }
+
+    #[test]
+    fn test_wrapped_commands() {
+        let dir = std::env::temp_dir();
+        let sandbox = SandboxConfig::new(vec![dir.clone(), dir.join("missing-dir")]);
+        let args = vec!["-m".to_string(), "plugins._host".to_string()];
+
+        let (program, wrapped) = sandbox.command_for(OsSandbox::None, "python", args.clone());
+        assert_eq!((program.as_str(), &wrapped), ("python", &args));
+
+        let (program, wrapped) = sandbox.command_for(OsSandbox::Unshare, "python", args.clone());
+        assert_eq!(program, "unshare");
+        assert!(wrapped.contains(&"--net".to_string()));
+        // Only existing directories are mounted, then the original command
+        let tail = &wrapped[wrapped.len() - 5..];
+        assert_eq!(tail[0], "1");
+        assert_eq!(tail[1], dir.display().to_string());
+        assert_eq!(&tail[2..], ["python", "-m", "plugins._host"]);
+
+        let (program, wrapped) = sandbox.command_for(OsSandbox::SandboxExec, "python", args);
+        assert_eq!(program, "sandbox-exec");
+        assert!(wrapped[1].contains("(deny network*)"));
+        assert!(wrapped[1].contains(&format!("(subpath {})", profile_string(&dir))));
+        assert_eq!(&wrapped[2..], ["python", "-m", "plugins._host"]);
+    }
+}
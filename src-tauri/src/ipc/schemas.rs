@@ -0,0 +1,356 @@
+//! src-tauri/src/ipc/schemas.rs
+//! ============================
+//! Per-method JSON Schemas for IPC params and results.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//! Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+//!
+//! Schemas come from two places:
+//!
+//! - `config/ipc_schemas.json` in the project root, loaded at startup
+//! - the plugin host's `rpc/schemas` reflection method, queried once the
+//!   host is ready (built-in methods and plugins that declare schemas)
+//!
+//! Both map a method name to an optional `params` and `result` schema:
+//!
+//! ```json
+//! { "plugin/load": { "params": { "type": "object", "required": ["name"] } } }
+//! ```
+//!
+//! A method described in the config file keeps that description; the host
+//! only fills in the others. `ipc_call` validates params before sending and
+//! results before returning them; violations carry field-level details
+//! (JSON pointer and message) for up to `MAX_FIELD_ERRORS` fields. Methods
+//! without a schema are not checked, and results are only parsed when a
+//! result schema exists.
+//!
+//! Usage:
+//!     ```rust
+//!     let schemas = SchemaRegistry::new();
+//!     schemas.load_file(&project_root.join(SCHEMAS_FILE))?;
+//!     schemas.load_from_host(&state).await?;
+//!     schemas.validate_params("plugin/load", &params)?;
+//!     ```
+
+use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+use super::manager::IpcManagerState;
+use super::response::error_codes;
+use super::IpcError;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Schema file, relative to the project root
+pub const SCHEMAS_FILE: &str = "config/ipc_schemas.json";
+
+/// Host method returning the schemas of its methods
+pub const SCHEMA_REFLECTION_METHOD: &str = "rpc/schemas";
+
+/// Maximum number of field errors reported per violation
+const MAX_FIELD_ERRORS: usize = 20;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Schemas of one method, as written in the config file or sent by the host.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MethodSchemas {
+    /// Schema of the request params
+    pub params: Option<Value>,
+    /// Schema of the result
+    pub result: Option<Value>,
+}
+
+/// Where a method's schemas came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SchemaSource {
+    /// `config/ipc_schemas.json`
+    Config,
+    /// The host's `rpc/schemas` method
+    Host,
+}
+
+/// A value that failed validation at one location.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FieldError {
+    /// JSON pointer to the offending value (empty for the root)
+    pub path: String,
+    /// What is wrong with it
+    pub message: String,
+}
+
+/// Compiled schemas of one method.
+struct CompiledSchemas {
+    /// Where they came from
+    source: SchemaSource,
+    /// Params validator
+    params: Option<JSONSchema>,
+    /// Result validator
+    result: Option<JSONSchema>,
+    /// The schemas as given, for code generation
+    raw: MethodSchemas,
+}
+
+/// Compile a schema, naming the method and part on error.
+fn compile(method: &str, part: &str, schema: Option<&Value>) -> Result<Option<JSONSchema>, String> {
+    schema
+        .map(|schema| {
+            JSONSchema::compile(schema)
+                .map_err(|e| format!("Invalid {part} schema for {method}: {e}"))
+        })
+        .transpose()
+}
+
+/// Validate a value, collecting the first field errors.
+fn check(schema: &JSONSchema, instance: &Value) -> Result<(), Vec<FieldError>> {
+    schema.validate(instance).map_err(|errors| {
+        errors
+            .take(MAX_FIELD_ERRORS)
+            .map(|error| FieldError {
+                path: error.instance_path.to_string(),
+                message: error.to_string(),
+            })
+            .collect()
+    })
+}
+
+// ============================================
+// SCHEMA REGISTRY
+// ============================================
+
+/// Registry of per-method schemas.
+///
+/// Cloning shares the registry.
+#[derive(Clone, Default)]
+pub struct SchemaRegistry {
+    /// Compiled schemas by method
+    methods: Arc<RwLock<BTreeMap<String, Arc<CompiledSchemas>>>>,
+}
+
+impl SchemaRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add schemas, returning how many methods were added or replaced.
+    ///
+    /// Host schemas never replace config schemas. Nothing is added if any
+    /// schema fails to compile.
+    pub fn register(
+        &self,
+        source: SchemaSource,
+        schemas: BTreeMap<String, MethodSchemas>,
+    ) -> Result<usize, String> {
+        let mut compiled = Vec::with_capacity(schemas.len());
+        for (method, schemas) in schemas {
+            let entry = CompiledSchemas {
+                source,
+                params: compile(&method, "params", schemas.params.as_ref())?,
+                result: compile(&method, "result", schemas.result.as_ref())?,
+                raw: schemas,
+            };
+            compiled.push((method, entry));
+        }
+
+        let mut methods = self.methods.write().unwrap();
+        let mut added = 0;
+        for (method, entry) in compiled {
+            let keep = source == SchemaSource::Host
+                && methods
+                    .get(&method)
+                    .is_some_and(|existing| existing.source == SchemaSource::Config);
+            if !keep {
+                methods.insert(method, Arc::new(entry));
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Load the schema file. A missing file adds nothing.
+    pub fn load_file(&self, path: &Path) -> Result<usize, String> {
+        if !path.exists() {
+            return Ok(0);
+        }
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+        let schemas =
+            serde_json::from_str(&content).map_err(|e| format!("Invalid {path:?}: {e}"))?;
+        self.register(SchemaSource::Config, schemas)
+    }
+
+    /// Ask a host for its schemas. Hosts without the reflection method add
+    /// nothing.
+    pub async fn load_from_host(&self, state: &IpcManagerState) -> Result<usize, String> {
+        let schemas = match state
+            .call(SCHEMA_REFLECTION_METHOD, serde_json::json!({}))
+            .await
+        {
+            Ok(schemas) => schemas,
+            Err(IpcError::RpcError { code, .. }) if code == error_codes::METHOD_NOT_FOUND => {
+                return Ok(0)
+            }
+            Err(e) => return Err(format!("Failed to get schemas from the plugin host: {e}")),
+        };
+        let schemas = serde_json::from_value(schemas)
+            .map_err(|e| format!("Invalid schemas from the plugin host: {e}"))?;
+        self.register(SchemaSource::Host, schemas)
+    }
+
+    /// Methods with schemas and where they came from.
+    pub fn methods(&self) -> Vec<(String, SchemaSource)> {
+        self.methods
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(method, schemas)| (method.clone(), schemas.source))
+            .collect()
+    }
+
+    /// The schemas of every method, as given.
+    pub fn snapshot(&self) -> BTreeMap<String, MethodSchemas> {
+        self.methods
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(method, schemas)| (method.clone(), schemas.raw.clone()))
+            .collect()
+    }
+
+    /// Schemas of a method, if any.
+    fn get(&self, method: &str) -> Option<Arc<CompiledSchemas>> {
+        self.methods.read().unwrap().get(method).cloned()
+    }
+
+    /// Check request params against the method's params schema.
+    pub fn validate_params(&self, method: &str, params: &Value) -> Result<(), Vec<FieldError>> {
+        match self.get(method) {
+            Some(schemas) => schemas
+                .params
+                .as_ref()
+                .map_or(Ok(()), |schema| check(schema, params)),
+            None => Ok(()),
+        }
+    }
+
+    /// Check a raw result against the method's result schema.
+    ///
+    /// The result is only parsed if the method has a result schema.
+    pub fn validate_result(&self, method: &str, result: &RawValue) -> Result<(), Vec<FieldError>> {
+        let Some(schemas) = self.get(method) else {
+            return Ok(());
+        };
+        let Some(schema) = schemas.result.as_ref() else {
+            return Ok(());
+        };
+        let value: Value = serde_json::from_str(result.get()).map_err(|e| {
+            vec![FieldError {
+                path: String::new(),
+                message: format!("Result is not valid JSON: {e}"),
+            }]
+        })?;
+        check(schema, &value)
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schemas(params: Value, result: Option<Value>) -> BTreeMap<String, MethodSchemas> {
+        BTreeMap::from([(
+            "plugin/load".to_string(),
+            MethodSchemas {
+                params: Some(params),
+                result,
+            },
+        )])
+    }
+
+    #[test]
+    fn test_params_violations_have_field_details() {
+        let registry = SchemaRegistry::new();
+        let params = json!({
+            "type": "object",
+            "required": ["name"],
+            "properties": {"name": {"type": "string"}, "config": {"type": "object"}}
+        });
+        assert_eq!(
+            registry.register(SchemaSource::Config, schemas(params, None)),
+            Ok(1)
+        );
+
+        assert!(registry
+            .validate_params("plugin/load", &json!({"name": "tts"}))
+            .is_ok());
+        let errors = registry
+            .validate_params("plugin/load", &json!({"name": 3, "config": []}))
+            .unwrap_err();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert!(paths.contains(&"/name"));
+        assert!(paths.contains(&"/config"));
+        assert!(registry
+            .validate_params("plugin/list", &json!(null))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_result_validation() {
+        let registry = SchemaRegistry::new();
+        let loaded = schemas(
+            json!({}),
+            Some(json!({"type": "object", "required": ["name"]})),
+        );
+        registry.register(SchemaSource::Host, loaded).unwrap();
+
+        let ok = RawValue::from_string(r#"{"name":"tts"}"#.to_string()).unwrap();
+        let bad = RawValue::from_string("[1]".to_string()).unwrap();
+        assert!(registry.validate_result("plugin/load", &ok).is_ok());
+        let errors = registry.validate_result("plugin/load", &bad).unwrap_err();
+        assert_eq!(errors[0].path, "");
+    }
+
+    #[test]
+    fn test_host_schemas_do_not_replace_config() {
+        let registry = SchemaRegistry::new();
+        let strict = json!({"type": "object", "required": ["name"]});
+        registry
+            .register(SchemaSource::Config, schemas(strict, None))
+            .unwrap();
+        let added = registry
+            .register(SchemaSource::Host, schemas(json!({}), None))
+            .unwrap();
+        assert_eq!(added, 0);
+        assert_eq!(
+            registry.methods(),
+            vec![("plugin/load".to_string(), SchemaSource::Config)]
+        );
+        assert!(registry.validate_params("plugin/load", &json!({})).is_err());
+    }
+
+    #[test]
+    fn test_invalid_schema_is_rejected() {
+        let registry = SchemaRegistry::new();
+        let invalid = schemas(json!({"type": 12}), None);
+        assert!(registry.register(SchemaSource::Config, invalid).is_err());
+        assert!(registry.methods().is_empty());
+    }
+}
@@ -11,9 +11,14 @@
 //! This module provides:
 //! - `SubprocessConfig` for configurable spawn parameters
 //! - Sidecar-style spawn using `tauri::api::process::Command`
-//! - Environment setup for unbuffered Python output
+//! - Environment setup for unbuffered, UTF-8 Python stdio
+//! - Extra interpreter flags (e.g. `-X utf8`, `-O`) before `-m <module>`
+//! - Optional lower scheduling priority and CPU affinity, so background work
+//!   in the host doesn't starve the UI on machines with few cores
 //! - Graceful shutdown with timeout
 //! - Process state tracking
+//! - Classification of hosts that exit during startup (`classify_startup_exit`)
+//! - Optional sandboxing of untrusted plugins' hosts (`sandbox.rs`)
 //!
 //! Dependencies:
 //!     - D030: mod.rs (`IpcError`, constants)
@@ -36,6 +41,7 @@
 //!     handle.shutdown(Duration::from_secs(5))?;
 //!     ```
 
+use serde::{Deserialize, Serialize};
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
 use std::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command, ExitStatus, Stdio};
@@ -43,7 +49,10 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use super::{IpcError, DEFAULT_TIMEOUT_SECS, MAX_RESPAWN_ATTEMPTS, RESPAWN_DELAY_MS};
+use super::sandbox::SandboxConfig;
+use super::{
+    IpcError, DEFAULT_TIMEOUT_SECS, MAX_RESPAWN_ATTEMPTS, RESPAWN_DELAY_MS, SPAWN_TIMEOUT_SECS,
+};
 
 // ============================================
 // SUBPROCESS CONFIGURATION
@@ -70,6 +79,9 @@ pub struct SubprocessConfig {
     /// Python module to run (e.g., "plugins._host")
     pub module_path: String,
 
+    /// Interpreter flags placed before `-m` (e.g., `-X utf8`, `-O`)
+    pub interpreter_args: Vec<String>,
+
     /// Working directory for the subprocess
     pub working_dir: Option<PathBuf>,
 
@@ -79,6 +91,15 @@ pub struct SubprocessConfig {
     /// Timeout for graceful shutdown in seconds
     pub shutdown_timeout_secs: u64,
 
+    /// Seconds the host has to complete the readiness handshake
+    pub spawn_timeout_secs: u64,
+
+    /// Scheduling priority
+    pub priority: ProcessPriority,
+
+    /// CPUs the host may run on (bit n = CPU n; None allows all)
+    pub cpu_affinity: Option<u64>,
+
     /// Maximum respawn attempts
     pub max_respawn_attempts: u32,
 
@@ -87,6 +108,9 @@ pub struct SubprocessConfig {
 
     /// Enable verbose logging
     pub verbose: bool,
+
+    /// Run sandboxed (scrubbed environment, OS-level sandbox if available)
+    pub sandbox: Option<SandboxConfig>,
 }
 
 impl Default for SubprocessConfig {
@@ -94,12 +118,17 @@ impl Default for SubprocessConfig {
         Self {
             python_path: "python".to_string(),
             module_path: "plugins._host".to_string(),
+            interpreter_args: Vec::new(),
             working_dir: None,
             env_vars: Vec::new(),
             shutdown_timeout_secs: DEFAULT_TIMEOUT_SECS,
+            spawn_timeout_secs: SPAWN_TIMEOUT_SECS,
+            priority: ProcessPriority::Normal,
+            cpu_affinity: None,
             max_respawn_attempts: MAX_RESPAWN_ATTEMPTS,
             respawn_delay_ms: RESPAWN_DELAY_MS,
             verbose: false,
+            sandbox: None,
         }
     }
 }
@@ -144,6 +173,27 @@ impl SubprocessConfig {
         self
     }
 
+    /// Set interpreter flags, placed before `-m <module>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - Flags (e.g., `["-X", "utf8"]`)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// let config = SubprocessConfig::new()
+    ///     .with_interpreter_args(["-X", "utf8", "-O"]);
+    /// ```
+    pub fn with_interpreter_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.interpreter_args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
     /// Set working directory.
     ///
     /// # Arguments
@@ -207,6 +257,47 @@ impl SubprocessConfig {
         self
     }
 
+    /// Set the readiness handshake timeout.
+    ///
+    /// A host that hasn't answered its first request within this time is
+    /// killed instead of being left starting.
+    ///
+    /// # Arguments
+    ///
+    /// * `secs` - Timeout in seconds
+    pub fn with_spawn_timeout(mut self, secs: u64) -> Self {
+        self.spawn_timeout_secs = secs;
+        self
+    }
+
+    /// Set the scheduling priority.
+    ///
+    /// # Arguments
+    ///
+    /// * `priority` - Priority class (Windows) / niceness (Unix)
+    pub fn with_priority(mut self, priority: ProcessPriority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Restrict the host to a set of CPUs.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - Affinity mask (bit n = CPU n; 0 allows all)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// // Keep the host off CPU 0
+    /// let config = SubprocessConfig::new()
+    ///     .with_cpu_affinity(0b1110);
+    /// ```
+    pub fn with_cpu_affinity(mut self, mask: u64) -> Self {
+        self.cpu_affinity = Some(mask).filter(|&mask| mask != 0);
+        self
+    }
+
     /// Set maximum respawn attempts.
     ///
     /// # Arguments
@@ -233,9 +324,125 @@ impl SubprocessConfig {
         self
     }
 
+    /// Run the host sandboxed.
+    ///
+    /// # Arguments
+    ///
+    /// * `sandbox` - Directories to keep read-only
+    pub fn with_sandbox(mut self, sandbox: SandboxConfig) -> Self {
+        self.sandbox = Some(sandbox);
+        self
+    }
+
     /// Build the command arguments.
     fn build_args(&self) -> Vec<String> {
-        vec!["-m".to_string(), self.module_path.clone()]
+        let mut args = self.interpreter_args.clone();
+        args.extend(["-m".to_string(), self.module_path.clone()]);
+        args
+    }
+}
+
+// ============================================
+// SCHEDULING
+// ============================================
+
+/// Scheduling priority of the plugin host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessPriority {
+    /// Same priority as the app
+    #[default]
+    Normal,
+    /// Yields to the UI under load
+    BelowNormal,
+    /// Runs only when the CPU is otherwise idle
+    Idle,
+}
+
+impl ProcessPriority {
+    /// Unix niceness.
+    pub fn niceness(self) -> i32 {
+        match self {
+            Self::Normal => 0,
+            Self::BelowNormal => 10,
+            Self::Idle => 19,
+        }
+    }
+
+    /// Windows priority class (process creation flag).
+    pub fn priority_class(self) -> u32 {
+        match self {
+            Self::Normal => 0x0000_0020,
+            Self::BelowNormal => 0x0000_4000,
+            Self::Idle => 0x0000_0040,
+        }
+    }
+}
+
+/// Run a scheduling tool (`renice`, `taskset`, `powershell`) to completion.
+fn run_scheduling_tool(program: &str, args: &[String]) -> Result<(), String> {
+    let mut cmd = Command::new(program);
+    cmd.args(args).stdin(Stdio::null());
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = cmd.output().map_err(|e| format!("{program}: {e}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "{program} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+/// Lower the priority of a spawned process.
+///
+/// Unix only; on Windows the priority class is set at creation.
+fn apply_priority(pid: u32, priority: ProcessPriority) -> Result<(), String> {
+    if !cfg!(unix) || priority == ProcessPriority::Normal {
+        return Ok(());
+    }
+    run_scheduling_tool(
+        "renice",
+        &[
+            "-n".to_string(),
+            priority.niceness().to_string(),
+            "-p".to_string(),
+            pid.to_string(),
+        ],
+    )
+}
+
+/// Restrict a spawned process to the CPUs in `mask`.
+fn apply_cpu_affinity(pid: u32, mask: u64) -> Result<(), String> {
+    if cfg!(target_os = "linux") {
+        run_scheduling_tool(
+            "taskset",
+            &[
+                "-a".to_string(),
+                "-p".to_string(),
+                format!("{mask:x}"),
+                pid.to_string(),
+            ],
+        )
+    } else if cfg!(target_os = "windows") {
+        run_scheduling_tool(
+            "powershell",
+            &[
+                "-NoProfile".to_string(),
+                "-Command".to_string(),
+                format!("(Get-Process -Id {pid}).ProcessorAffinity = {mask}"),
+            ],
+        )
+    } else {
+        Err("CPU affinity is not supported on this platform".to_string())
     }
 }
 
@@ -523,9 +730,13 @@ pub fn spawn_plugin_host(config: SubprocessConfig) -> Result<SubprocessHandle, I
         config.module_path
     );
 
-    // Build command
-    let mut cmd = Command::new(&config.python_path);
-    cmd.args(config.build_args())
+    // Build command (wrapped in the OS sandbox, if any)
+    let (program, args) = match config.sandbox {
+        Some(ref sandbox) => sandbox.command(&config.python_path, config.build_args()),
+        None => (config.python_path.clone(), config.build_args()),
+    };
+    let mut cmd = Command::new(&program);
+    cmd.args(args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
@@ -536,12 +747,25 @@ pub fn spawn_plugin_host(config: SubprocessConfig) -> Result<SubprocessHandle, I
         cmd.current_dir(dir);
     }
 
+    // A sandboxed host only sees an allowlist of the app's environment
+    if let Some(ref sandbox) = config.sandbox {
+        log::info!(
+            "Sandboxing plugin host (read-only: {:?})",
+            sandbox.read_only
+        );
+        cmd.env_clear().envs(sandbox.env());
+    }
+
     // CRITICAL: Set PYTHONUNBUFFERED for immediate stdout
     // Without this, Python buffers stdout and Tauri receives nothing
     // until the buffer fills or the process exits.
     // Reference: TECHNICAL_REFERENCES.md §5
     cmd.env("PYTHONUNBUFFERED", "1");
 
+    // Windows consoles default to a legacy code page; non-ASCII text on
+    // stdin/stderr (and stdout before the host rewraps it) must be UTF-8
+    cmd.env("PYTHONIOENCODING", "utf-8");
+
     // Add additional environment variables
     for (key, value) in &config.env_vars {
         cmd.env(key, value);
@@ -552,17 +776,31 @@ pub fn spawn_plugin_host(config: SubprocessConfig) -> Result<SubprocessHandle, I
     {
         use std::os::windows::process::CommandExt;
         const CREATE_NO_WINDOW: u32 = 0x0800_0000;
-        cmd.creation_flags(CREATE_NO_WINDOW);
+        cmd.creation_flags(CREATE_NO_WINDOW | config.priority.priority_class());
     }
 
     // Spawn the process
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| IpcError::SpawnError(format!("Failed to spawn subprocess: {e}")))?;
+    let mut child = cmd.spawn().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            IpcError::InterpreterNotFound(config.python_path.clone())
+        } else {
+            IpcError::SpawnError(format!("Failed to spawn subprocess: {e}"))
+        }
+    })?;
 
     let pid = child.id();
     log::info!("Plugin host spawned with PID: {pid}");
 
+    // Scheduling failures leave the host running with default scheduling
+    if let Err(e) = apply_priority(pid, config.priority) {
+        log::warn!("Failed to set plugin host priority: {e}");
+    }
+    if let Some(mask) = config.cpu_affinity {
+        if let Err(e) = apply_cpu_affinity(pid, mask) {
+            log::warn!("Failed to set plugin host CPU affinity: {e}");
+        }
+    }
+
     // Extract stdio handles
     let stdin = child.stdin.take();
     let stdout = child.stdout.take();
@@ -628,6 +866,69 @@ pub fn respawn_with_backoff(
     spawn_plugin_host(config)
 }
 
+// ============================================
+// STARTUP FAILURES
+// ============================================
+
+/// Exit codes of a shell or launcher that could not find the interpreter
+/// (127 on Unix shells, 9009 on Windows / the Microsoft Store alias)
+const INTERPRETER_NOT_FOUND_EXIT_CODES: &[i32] = &[127, 9009];
+
+/// Classify a plugin host that exited during startup.
+///
+/// Looks at the exit code and stderr for the common causes (missing
+/// interpreter, missing module, syntax error) and falls back to
+/// `IpcError::StartupExit` with the last stderr line.
+///
+/// # Arguments
+///
+/// * `python_path` - Interpreter the host was started with
+/// * `exit_code` - Exit code (None if killed by a signal)
+/// * `stderr` - Stderr lines, oldest first
+pub fn classify_startup_exit(
+    python_path: &str,
+    exit_code: Option<i32>,
+    stderr: &[String],
+) -> IpcError {
+    if exit_code.is_some_and(|code| INTERPRETER_NOT_FOUND_EXIT_CODES.contains(&code)) {
+        return IpcError::InterpreterNotFound(python_path.to_string());
+    }
+
+    // `python -m x` reports "No module named x"; imports raise ModuleNotFoundError
+    if let Some(module) = stderr.iter().rev().find_map(|line| {
+        let (_, rest) = line.split_once("No module named ")?;
+        Some(rest.trim().trim_matches(['\'', '"', ')']).to_string())
+    }) {
+        return IpcError::ModuleNotFound { module };
+    }
+
+    if let Some(index) = stderr.iter().rposition(|line| line.contains("SyntaxError")) {
+        // The traceback's `File "...", line N` precedes the offending source line
+        let location = stderr[..index]
+            .iter()
+            .rev()
+            .find(|line| line.trim_start().starts_with("File "))
+            .map(|line| format!(" ({})", line.trim()));
+        return IpcError::HostSyntaxError {
+            detail: format!("{}{}", stderr[index].trim(), location.unwrap_or_default()),
+        };
+    }
+
+    let reason = stderr
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty())
+        .map_or_else(
+            || match exit_code {
+                Some(code) => format!("exit code {code}"),
+                None => "terminated by a signal".to_string(),
+            },
+            str::to_string,
+        );
+    IpcError::StartupExit { exit_code, reason }
+}
+
 // ============================================
 // STDIO UTILITIES
 // ============================================
@@ -728,6 +1029,7 @@ mod tests {
             .with_working_dir("/tmp")
             .with_env("DEBUG", "1")
             .with_shutdown_timeout(30)
+            .with_spawn_timeout(90)
             .with_max_respawn_attempts(5)
             .with_respawn_delay(2000);
 
@@ -737,10 +1039,26 @@ mod tests {
         assert_eq!(config.env_vars.len(), 1);
         assert_eq!(config.env_vars[0], ("DEBUG".to_string(), "1".to_string()));
         assert_eq!(config.shutdown_timeout_secs, 30);
+        assert_eq!(config.spawn_timeout_secs, 90);
         assert_eq!(config.max_respawn_attempts, 5);
         assert_eq!(config.respawn_delay_ms, 2000);
     }
 
+    #[test]
+    fn test_scheduling_config() {
+        let config = SubprocessConfig::new();
+        assert_eq!(config.priority, ProcessPriority::Normal);
+        assert_eq!(config.cpu_affinity, None);
+
+        let config = config
+            .with_priority(ProcessPriority::BelowNormal)
+            .with_cpu_affinity(0b1110);
+        assert_eq!(config.priority.niceness(), 10);
+        assert_eq!(config.priority.priority_class(), 0x4000);
+        assert_eq!(config.cpu_affinity, Some(0b1110));
+        assert_eq!(config.with_cpu_affinity(0).cpu_affinity, None);
+    }
+
     #[test]
     fn test_subprocess_config_multiple_envs() {
         let config = SubprocessConfig::new()
@@ -758,6 +1076,9 @@ mod tests {
         let args = config.build_args();
 
         assert_eq!(args, vec!["-m", "test.module"]);
+
+        let args = config.with_interpreter_args(["-X", "utf8"]).build_args();
+        assert_eq!(args, vec!["-X", "utf8", "-m", "test.module"]);
     }
 
     #[test]
@@ -774,4 +1095,46 @@ mod tests {
         assert_eq!(ProcessState::Running, ProcessState::Running);
         assert_ne!(ProcessState::Running, ProcessState::Stopped);
     }
+
+    #[test]
+    fn test_classify_startup_exit() {
+        let classify = |code: i32, stderr: &[&str]| {
+            let stderr: Vec<String> = stderr.iter().map(|line| (*line).to_string()).collect();
+            classify_startup_exit("python", Some(code), &stderr)
+        };
+
+        assert!(matches!(
+            classify(9009, &[]),
+            IpcError::InterpreterNotFound(path) if path == "python"
+        ));
+        assert!(matches!(
+            classify(
+                1,
+                &[
+                    "Traceback (most recent call last):",
+                    "ModuleNotFoundError: No module named 'numpy'",
+                ],
+            ),
+            IpcError::ModuleNotFound { module } if module == "numpy"
+        ));
+        assert!(matches!(
+            classify(1, &["/usr/bin/python: No module named plugins._host"]),
+            IpcError::ModuleNotFound { module } if module == "plugins._host"
+        ));
+        assert!(matches!(
+            classify(
+                1,
+                &[
+                    "  File \"plugins/_host/protocol.py\", line 12",
+                    "    match x:",
+                    "SyntaxError: invalid syntax",
+                ],
+            ),
+            IpcError::HostSyntaxError { detail } if detail.contains("protocol.py")
+        ));
+        assert!(matches!(
+            classify(3, &["fatal: out of memory", ""]),
+            IpcError::StartupExit { exit_code: Some(3), reason } if reason == "fatal: out of memory"
+        ));
+    }
 }
@@ -0,0 +1,438 @@
+//! src-tauri/src/jobs.rs
+//! =====================
+//! Long-running plugin calls as background jobs.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A job wraps a single JSON-RPC call in a background task so the UI never
+//! blocks on it. While the call is in flight, `$/progress` notifications
+//! from the Python host whose `id` matches the job's request id update the
+//! job and are forwarded to the frontend as per-job events.
+//!
+//! Progress notification format (host -> Rust):
+//!     ```json
+//!     {"jsonrpc": "2.0", "method": "$/progress",
+//!      "params": {"id": 42, "progress": 0.25, "message": "Loading model"}}
+//!     ```
+//!
+//! The standard `progress/{request id}` notifications (see
+//! `crate::ipc::manager::ProgressUpdate`) are accepted as well; their
+//! 0-100 percentage is scaled to the job's 0-1 progress.
+//!
+//! Jobs use the host's request timeout unless started with their own, as
+//! `plugin_call_async` does for plugin methods that take minutes.
+//!
+//! Usage:
+//!     ```rust
+//!     let jobs = JobManager::new();
+//!     let job = jobs.start(manager, "tts/synthesize", params, None, None, |event, job| {
+//!         app.emit_all(&event, job).ok();
+//!     });
+//!     jobs.cancel(&job.id).await?;
+//!     ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use tauri::async_runtime::JoinHandle;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::ipc::manager::{IpcManagerState, IpcNotification, ProgressUpdate};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Notification method carrying job progress
+pub const PROGRESS_METHOD: &str = "$/progress";
+
+/// Maximum number of finished jobs kept for `job_list`/`job_status`
+const MAX_FINISHED_JOBS: usize = 100;
+
+/// Timeout of `plugin_call_async` jobs unless the caller sets one
+pub const LONG_CALL_TIMEOUT_SECS: u64 = 60 * 60;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Job lifecycle status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    /// Call in flight
+    Running,
+    /// Call returned a result
+    Completed,
+    /// Call returned an error
+    Failed,
+    /// Cancelled by the user
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Check if the job has finished.
+    pub fn is_finished(self) -> bool {
+        !matches!(self, JobStatus::Running)
+    }
+}
+
+/// A background job.
+#[derive(Debug, Clone, Serialize)]
+pub struct Job {
+    /// Job id
+    pub id: String,
+    /// JSON-RPC method being called
+    pub method: String,
+    /// Workspace the job runs in (None = default)
+    pub workspace: Option<String>,
+    /// JSON-RPC request id (matches progress notifications)
+    pub request_id: u64,
+    /// Current status
+    pub status: JobStatus,
+    /// Progress in 0.0..=1.0, if reported
+    pub progress: Option<f64>,
+    /// Last progress message, if reported
+    pub message: Option<String>,
+    /// Call result (when completed)
+    pub result: Option<Value>,
+    /// Error message (when failed)
+    pub error: Option<String>,
+    /// Start timestamp (RFC 3339)
+    pub started_at: String,
+    /// Finish timestamp (RFC 3339)
+    pub finished_at: Option<String>,
+}
+
+impl Job {
+    /// Event name for progress updates of this job.
+    pub fn progress_event(&self) -> String {
+        format!("job/{}/progress", self.id)
+    }
+
+    /// Event name emitted once when this job finishes.
+    pub fn finished_event(&self) -> String {
+        format!("job/{}/finished", self.id)
+    }
+
+    /// Apply a `$/progress` or `progress/{id}` notification if it belongs to this job.
+    fn apply_progress(&mut self, notification: &IpcNotification) -> bool {
+        if let Some(update) = ProgressUpdate::from_notification(notification) {
+            if update.request_id != self.request_id {
+                return false;
+            }
+            self.progress = Some(update.percentage / 100.0);
+            if update.message.is_some() {
+                self.message = update.message;
+            }
+            return true;
+        }
+
+        if notification.method != PROGRESS_METHOD
+            || notification.params.get("id").and_then(Value::as_u64) != Some(self.request_id)
+        {
+            return false;
+        }
+
+        if let Some(progress) = notification.params.get("progress").and_then(Value::as_f64) {
+            self.progress = Some(progress.clamp(0.0, 1.0));
+        }
+        if let Some(message) = notification.params.get("message").and_then(Value::as_str) {
+            self.message = Some(message.to_string());
+        }
+        true
+    }
+}
+
+// ============================================
+// JOB MANAGER
+// ============================================
+
+/// Registry of background jobs.
+///
+/// Clones share the job table, so a job's task can record its outcome.
+#[derive(Clone, Default)]
+pub struct JobManager {
+    /// Jobs keyed by id
+    jobs: Arc<RwLock<HashMap<String, Job>>>,
+
+    /// Running tasks with the manager that owns the request
+    tasks: Arc<Mutex<HashMap<String, (JoinHandle<()>, IpcManagerState)>>>,
+}
+
+impl JobManager {
+    /// Create an empty job registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a job calling `method` on `manager` in the background.
+    ///
+    /// `on_event(event_name, job)` is called for every progress update and
+    /// once when the job finishes. `timeout_secs` defaults to the host's
+    /// request timeout.
+    pub fn start<F>(
+        &self,
+        manager: IpcManagerState,
+        method: String,
+        params: Value,
+        timeout_secs: Option<u64>,
+        workspace: Option<String>,
+        on_event: F,
+    ) -> Job
+    where
+        F: Fn(String, &Job) + Send + 'static,
+    {
+        let job = Job {
+            id: uuid::Uuid::new_v4().to_string(),
+            method: method.clone(),
+            workspace,
+            request_id: manager.next_request_id(),
+            status: JobStatus::Running,
+            progress: None,
+            message: None,
+            result: None,
+            error: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+        };
+
+        self.jobs.write().unwrap().insert(job.id.clone(), job.clone());
+        log::info!("Job started: {} ({method})", job.id);
+
+        let registry = self.clone();
+        let task_manager = manager.clone();
+        let job_id = job.id.clone();
+        let request_id = job.request_id;
+        let timeout_secs = timeout_secs.unwrap_or_else(|| manager.config().timeout_secs);
+
+        // Subscribe before sending so early progress is not missed
+        let mut notifications = manager.subscribe();
+
+        let handle = tauri::async_runtime::spawn(async move {
+            let call = task_manager.call_with_id_timeout(request_id, method, params, timeout_secs);
+            tokio::pin!(call);
+
+            let result = loop {
+                tokio::select! {
+                    result = &mut call => break result,
+                    notification = notifications.recv() => match notification {
+                        Ok(n) => {
+                            if let Some(job) = registry.update(&job_id, |job| job.apply_progress(&n)) {
+                                on_event(job.progress_event(), &job);
+                            }
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            log::warn!("Job {job_id} missed {skipped} notifications");
+                        }
+                        Err(RecvError::Closed) => break (&mut call).await,
+                    },
+                }
+            };
+
+            let finished = registry.update(&job_id, |job| {
+                match result {
+                    Ok(value) => {
+                        job.status = JobStatus::Completed;
+                        job.progress = Some(1.0);
+                        job.result = Some(value);
+                    }
+                    Err(e) => {
+                        job.status = JobStatus::Failed;
+                        job.error = Some(e.to_string());
+                    }
+                }
+                job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+                true
+            });
+
+            registry.tasks.lock().unwrap().remove(&job_id);
+
+            if let Some(job) = finished {
+                log::info!("Job {}: {:?}", job.id, job.status);
+                on_event(job.finished_event(), &job);
+            }
+            registry.prune();
+        });
+
+        // Only track the task if it has not already finished
+        let mut tasks = self.tasks.lock().unwrap();
+        if self.get(&job.id).is_some_and(|j| !j.status.is_finished()) {
+            tasks.insert(job.id.clone(), (handle, manager));
+        }
+
+        job
+    }
+
+    /// Get a job by id.
+    pub fn get(&self, id: &str) -> Option<Job> {
+        self.jobs.read().unwrap().get(id).cloned()
+    }
+
+    /// List all jobs (most recent first).
+    pub fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.jobs.read().unwrap().values().cloned().collect();
+        jobs.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        jobs
+    }
+
+    /// Cancel a running job.
+    ///
+    /// Aborts the background task and asks the host to cancel the request.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Job)` - The cancelled job
+    /// * `Err(String)` - Unknown job or job already finished
+    pub async fn cancel(&self, id: &str) -> Result<Job, String> {
+        let job = self.get(id).ok_or_else(|| format!("Unknown job: {id}"))?;
+        if job.status.is_finished() {
+            return Err(format!("Job {id} already finished ({:?})", job.status));
+        }
+
+        let task = self.tasks.lock().unwrap().remove(id);
+        if let Some((handle, manager)) = task {
+            handle.abort();
+            if let Err(e) = manager.cancel_request(job.request_id).await {
+                log::warn!("Failed to send cancel for job {id}: {e}");
+            }
+        }
+
+        log::info!("Job cancelled: {id}");
+        self.update(id, |job| {
+            job.status = JobStatus::Cancelled;
+            job.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            true
+        })
+        .ok_or_else(|| format!("Unknown job: {id}"))
+    }
+
+    /// Apply an edit to a running job, returning the updated job if changed.
+    fn update<F>(&self, id: &str, edit: F) -> Option<Job>
+    where
+        F: FnOnce(&mut Job) -> bool,
+    {
+        let mut jobs = self.jobs.write().unwrap();
+        let job = jobs.get_mut(id)?;
+        if job.status.is_finished() || !edit(job) {
+            return None;
+        }
+        Some(job.clone())
+    }
+
+    /// Drop the oldest finished jobs beyond `MAX_FINISHED_JOBS`.
+    fn prune(&self) {
+        let mut jobs = self.jobs.write().unwrap();
+        let mut finished: Vec<(String, String)> = jobs
+            .values()
+            .filter(|j| j.status.is_finished())
+            .map(|j| (j.started_at.clone(), j.id.clone()))
+            .collect();
+
+        if finished.len() <= MAX_FINISHED_JOBS {
+            return;
+        }
+
+        finished.sort();
+        let excess = finished.len() - MAX_FINISHED_JOBS;
+        for (_, id) in finished.into_iter().take(excess) {
+            jobs.remove(&id);
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ipc::manager::IpcConfig;
+    use serde_json::json;
+
+    fn progress(id: u64, value: f64) -> IpcNotification {
+        IpcNotification {
+            method: PROGRESS_METHOD.to_string(),
+            params: json!({ "id": id, "progress": value, "message": "working" }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_job_fails_when_host_not_running() {
+        let jobs = JobManager::new();
+        let manager = IpcManagerState::new(IpcConfig::default());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let job = jobs.start(
+            manager,
+            "ping".to_string(),
+            json!({}),
+            None,
+            None,
+            move |event, job| {
+                let _ = tx.send((event, job.status));
+            },
+        );
+        assert_eq!(job.status, JobStatus::Running);
+
+        let (event, status) = rx.recv().await.unwrap();
+        assert_eq!(event, job.finished_event());
+        assert_eq!(status, JobStatus::Failed);
+        assert!(jobs.get(&job.id).unwrap().error.is_some());
+    }
+
+    #[test]
+    fn test_apply_progress_matches_request_id() {
+        let mut job = Job {
+            id: "job".to_string(),
+            method: "m".to_string(),
+            workspace: None,
+            request_id: 7,
+            status: JobStatus::Running,
+            progress: None,
+            message: None,
+            result: None,
+            error: None,
+            started_at: String::new(),
+            finished_at: None,
+        };
+
+        assert!(!job.apply_progress(&progress(8, 0.5)));
+        assert!(job.apply_progress(&progress(7, 1.5)));
+        assert_eq!(job.progress, Some(1.0));
+        assert_eq!(job.message.as_deref(), Some("working"));
+
+        let update = IpcNotification {
+            method: "progress/7".to_string(),
+            params: json!({ "percentage": 25.0, "stage": "load" }),
+        };
+        assert!(job.apply_progress(&update));
+        assert_eq!(job.progress, Some(0.25));
+        assert_eq!(job.message.as_deref(), Some("working"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_unknown_or_finished_job() {
+        let jobs = JobManager::new();
+        assert!(jobs.cancel("missing").await.is_err());
+
+        let manager = IpcManagerState::new(IpcConfig::default());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let job = jobs.start(
+            manager,
+            "ping".to_string(),
+            json!({}),
+            None,
+            None,
+            move |_, _| {
+                let _ = tx.send(());
+            },
+        );
+        rx.recv().await.unwrap();
+
+        assert!(jobs.cancel(&job.id).await.is_err());
+        assert_eq!(jobs.list().len(), 1);
+    }
+}
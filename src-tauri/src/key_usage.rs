@@ -0,0 +1,170 @@
+//! src-tauri/src/key_usage.rs
+//! ==========================
+//! Per-key API usage accounting.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The LLM services report each provider request they make with a key
+//! (`record_api_key_usage`), optionally with the token count returned by
+//! the provider. Totals live in the `api_key_usage` table of the embedded
+//! database (see storage.rs) and are attached to `get_api_keys` entries,
+//! so users can tell which keys are actually being consumed.
+//!
+//! Usage:
+//!     ```rust
+//!     key_usage::record(&storage, "gemini", &key_id, Some(1200))?;
+//!     let usage = key_usage::for_service(&storage, "gemini")?;
+//!     ```
+
+use chrono::Utc;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::storage::Storage;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Usage totals for one API key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyUsage {
+    /// Number of provider requests made with the key
+    pub request_count: u64,
+    /// Sum of reported token counts (requests without counts add nothing)
+    pub estimated_tokens: u64,
+    /// Timestamp of the latest request (RFC 3339)
+    pub last_used_at: String,
+}
+
+// ============================================
+// OPERATIONS
+// ============================================
+
+/// Record one request made with a key.
+///
+/// # Returns
+///
+/// The updated totals.
+pub fn record(
+    storage: &Storage,
+    service: &str,
+    key_id: &str,
+    tokens: Option<u64>,
+) -> Result<KeyUsage, String> {
+    let now = Utc::now().to_rfc3339();
+    let conn = storage.conn();
+    conn.execute(
+        "INSERT INTO api_key_usage (key_id, service, request_count, estimated_tokens, last_used_at)
+         VALUES (?1, ?2, 1, ?3, ?4)
+         ON CONFLICT (key_id) DO UPDATE SET
+             request_count = request_count + 1,
+             estimated_tokens = estimated_tokens + excluded.estimated_tokens,
+             last_used_at = excluded.last_used_at",
+        params![key_id, service.to_lowercase(), tokens.unwrap_or(0), now],
+    )
+    .map_err(|e| format!("Failed to record key usage: {e}"))?;
+
+    conn.query_row(
+        "SELECT request_count, estimated_tokens, last_used_at FROM api_key_usage WHERE key_id = ?1",
+        params![key_id],
+        row_to_usage,
+    )
+    .map_err(|e| format!("Failed to read key usage: {e}"))
+}
+
+/// Get usage for a single key.
+pub fn get(storage: &Storage, key_id: &str) -> Result<Option<KeyUsage>, String> {
+    storage
+        .conn()
+        .query_row(
+            "SELECT request_count, estimated_tokens, last_used_at FROM api_key_usage WHERE key_id = ?1",
+            params![key_id],
+            row_to_usage,
+        )
+        .optional()
+        .map_err(|e| format!("Failed to read key usage: {e}"))
+}
+
+/// Get usage for every key of a service, by key id.
+pub fn for_service(storage: &Storage, service: &str) -> Result<HashMap<String, KeyUsage>, String> {
+    let conn = storage.conn();
+    let mut stmt = conn
+        .prepare(
+            "SELECT key_id, request_count, estimated_tokens, last_used_at
+             FROM api_key_usage WHERE service = ?1",
+        )
+        .map_err(|e| e.to_string())?;
+
+    stmt.query_map(params![service.to_lowercase()], |row| {
+        Ok((
+            row.get(0)?,
+            KeyUsage {
+                request_count: row.get(1)?,
+                estimated_tokens: row.get(2)?,
+                last_used_at: row.get(3)?,
+            },
+        ))
+    })
+    .and_then(|rows| rows.collect::<rusqlite::Result<HashMap<_, _>>>())
+    .map_err(|e| format!("Failed to list key usage: {e}"))
+}
+
+/// Forget a key's usage (when the key is deleted).
+pub fn delete(storage: &Storage, key_id: &str) -> Result<(), String> {
+    storage
+        .conn()
+        .execute(
+            "DELETE FROM api_key_usage WHERE key_id = ?1",
+            params![key_id],
+        )
+        .map(|_| ())
+        .map_err(|e| format!("Failed to delete key usage: {e}"))
+}
+
+/// Map a `request_count, estimated_tokens, last_used_at` row.
+fn row_to_usage(row: &rusqlite::Row<'_>) -> rusqlite::Result<KeyUsage> {
+    Ok(KeyUsage {
+        request_count: row.get(0)?,
+        estimated_tokens: row.get(1)?,
+        last_used_at: row.get(2)?,
+    })
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_accumulates() {
+        let storage = Storage::open(None).unwrap();
+        record(&storage, "gemini", "k1", Some(100)).unwrap();
+        record(&storage, "gemini", "k1", None).unwrap();
+        let usage = record(&storage, "Gemini", "k1", Some(50)).unwrap();
+
+        assert_eq!(usage.request_count, 3);
+        assert_eq!(usage.estimated_tokens, 150);
+        assert_eq!(get(&storage, "k1").unwrap(), Some(usage));
+        assert_eq!(get(&storage, "k2").unwrap(), None);
+    }
+
+    #[test]
+    fn test_for_service_and_delete() {
+        let storage = Storage::open(None).unwrap();
+        record(&storage, "openai", "a", None).unwrap();
+        record(&storage, "openai", "b", Some(10)).unwrap();
+        record(&storage, "anthropic", "c", None).unwrap();
+
+        let usage = for_service(&storage, "openai").unwrap();
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage["b"].estimated_tokens, 10);
+
+        delete(&storage, "a").unwrap();
+        assert_eq!(for_service(&storage, "openai").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,1018 @@
+//! src-tauri/src/llm.rs
+//! ====================
+//! LLM proxy performing provider HTTPS calls on behalf of the webview.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The frontend sends a provider-neutral conversation (`LlmMessage`s) and
+//! the backend translates it to the provider's wire format, attaches the
+//! active API key, and returns the generated text. Keys are resolved in
+//! Rust (see `commands::llm`), so they never enter the JavaScript context.
+//! Single requests go through the provider implementations in
+//! `providers/`, which share the wire formats defined here.
+//! Requests are recorded for per-key usage accounting (see `key_usage.rs`).
+//!
+//! Streams read the provider's server-sent events and report each piece of
+//! text as a `Delta` event, followed by one final event (completed, failed,
+//! or cancelled). Cancelling stops reading and closes the connection.
+//!
+//! Supported services: `gemini`, `openai`, `anthropic`, `ollama` (no key;
+//! Ollama streams newline-delimited JSON rather than server-sent events).
+//!
+//! Usage:
+//!     ```rust
+//!     let proxy = LlmProxy::new(storage);
+//!     let provider = providers::get("gemini", &env_vars).unwrap();
+//!     let key = ProviderKey { service: "gemini".into(), id, key };
+//!     let response = proxy
+//!         .generate(provider.as_ref(), &key, "gemini-2.5-flash", &messages, &options)
+//!         .await?;
+//!
+//!     proxy.stream(&id, provider.as_ref(), key, model, messages, options, |event, payload| {
+//!         app.emit_all(&event, payload).ok();
+//!     })?;
+//!     proxy.cancel(&id)?;
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+use crate::key_usage;
+use crate::providers::{self, Provider};
+use crate::proxy;
+use crate::storage::Storage;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Output token limit when the caller gives none (required by Anthropic)
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Upper bound for a single generation request
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Maximum length of a caller-chosen stream id
+const MAX_STREAM_ID_LEN: usize = 64;
+
+/// MIME type assumed for images given as bare base64
+const DEFAULT_IMAGE_MIME: &str = "image/png";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Author of a conversation message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmRole {
+    /// Instructions (sent as the provider's system prompt)
+    System,
+    /// End user
+    User,
+    /// Model reply
+    Assistant,
+}
+
+/// One message of a conversation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmMessage {
+    /// Message author
+    pub role: LlmRole,
+    /// Message text
+    pub content: String,
+    /// Attached images (base64, raw or data URI)
+    #[serde(default)]
+    pub images: Vec<String>,
+}
+
+/// Generation options.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LlmOptions {
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+    /// Output token limit (default 4096)
+    pub max_tokens: Option<u32>,
+    /// Key profile to take the active key from (default: the active profile)
+    pub profile: Option<String>,
+}
+
+/// Token counts reported by the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LlmUsage {
+    /// Prompt tokens
+    pub input_tokens: u64,
+    /// Generated tokens
+    pub output_tokens: u64,
+    /// Total tokens billed
+    pub total_tokens: u64,
+}
+
+/// Result of a generation request.
+#[derive(Debug, Clone, Serialize)]
+pub struct LlmResponse {
+    /// Generated text
+    pub text: String,
+    /// Why generation stopped, as reported by the provider
+    pub finish_reason: Option<String>,
+    /// Token counts, if reported
+    pub usage: Option<LlmUsage>,
+}
+
+/// Event reported while a stream runs.
+///
+/// `Delta` is emitted as `llm/<id>/delta`; exactly one of the other
+/// variants follows as `llm/<id>/finished`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum LlmStreamEvent {
+    /// Newly generated text
+    Delta { text: String },
+    /// Generation finished
+    Completed { response: LlmResponse },
+    /// Network or provider failure (`text` is what arrived before it)
+    Failed { error: String, text: String },
+    /// Stopped by `cancel` (`text` is what arrived before it)
+    Cancelled { text: String },
+}
+
+impl LlmStreamEvent {
+    /// Event name for this event on stream `id`.
+    pub fn event_name(&self, id: &str) -> String {
+        match self {
+            LlmStreamEvent::Delta { .. } => format!("llm/{id}/delta"),
+            _ => format!("llm/{id}/finished"),
+        }
+    }
+}
+
+/// An API key resolved for a request.
+pub struct ProviderKey {
+    /// Service the key belongs to
+    pub service: String,
+    /// Key ID (for usage accounting; empty for keyless services)
+    pub id: String,
+    /// Decrypted key value (empty for keyless services)
+    pub key: String,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Validate a caller-chosen stream id (used in event names).
+fn check_stream_id(id: &str) -> Result<(), String> {
+    if id.is_empty()
+        || id.len() > MAX_STREAM_ID_LEN
+        || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(format!("Invalid stream id: {id}"));
+    }
+    Ok(())
+}
+
+/// Split an image into MIME type and base64 data.
+pub fn split_image(image: &str) -> (&str, &str) {
+    image
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .unwrap_or((DEFAULT_IMAGE_MIME, image))
+}
+
+/// Join the system messages into one prompt (None if there are none).
+pub fn system_prompt(messages: &[LlmMessage]) -> Option<String> {
+    let parts: Vec<&str> = messages
+        .iter()
+        .filter(|m| m.role == LlmRole::System && !m.content.trim().is_empty())
+        .map(|m| m.content.as_str())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
+}
+
+/// Non-system messages, in order.
+pub fn turns(messages: &[LlmMessage]) -> impl Iterator<Item = &LlmMessage> {
+    messages.iter().filter(|m| m.role != LlmRole::System)
+}
+
+/// Build the provider request body.
+pub fn request_body(
+    service: &str,
+    model: &str,
+    messages: &[LlmMessage],
+    options: &LlmOptions,
+    stream: bool,
+) -> Result<Value, String> {
+    let system = system_prompt(messages);
+    let max_tokens = options.max_tokens.unwrap_or(DEFAULT_MAX_TOKENS);
+
+    let body = match service {
+        "gemini" => {
+            let contents: Vec<Value> = turns(messages)
+                .map(|m| {
+                    let mut parts = vec![json!({ "text": m.content })];
+                    parts.extend(m.images.iter().map(|img| {
+                        let (mime, data) = split_image(img);
+                        json!({ "inlineData": { "mimeType": mime, "data": data } })
+                    }));
+                    let role = if m.role == LlmRole::Assistant {
+                        "model"
+                    } else {
+                        "user"
+                    };
+                    json!({ "role": role, "parts": parts })
+                })
+                .collect();
+
+            let mut body = json!({
+                "contents": contents,
+                "generationConfig": { "maxOutputTokens": max_tokens },
+            });
+            if let Some(temperature) = options.temperature {
+                body["generationConfig"]["temperature"] = json!(temperature);
+            }
+            if let Some(system) = system {
+                body["systemInstruction"] = json!({ "parts": [{ "text": system }] });
+            }
+            body
+        }
+        "openai" => {
+            let mut chat: Vec<Value> = system
+                .map(|s| json!({ "role": "system", "content": s }))
+                .into_iter()
+                .collect();
+            chat.extend(turns(messages).map(|m| {
+                let role = if m.role == LlmRole::Assistant {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                if m.images.is_empty() {
+                    return json!({ "role": role, "content": m.content });
+                }
+                let mut content = vec![json!({ "type": "text", "text": m.content })];
+                content.extend(m.images.iter().map(|img| {
+                    let (mime, data) = split_image(img);
+                    let url = format!("data:{mime};base64,{data}");
+                    json!({ "type": "image_url", "image_url": { "url": url } })
+                }));
+                json!({ "role": role, "content": content })
+            }));
+
+            let mut body = json!({
+                "model": model,
+                "messages": chat,
+                "max_completion_tokens": max_tokens,
+            });
+            if let Some(temperature) = options.temperature {
+                body["temperature"] = json!(temperature);
+            }
+            if stream {
+                body["stream"] = json!(true);
+                body["stream_options"] = json!({ "include_usage": true });
+            }
+            body
+        }
+        "anthropic" => {
+            let chat: Vec<Value> = turns(messages)
+                .map(|m| {
+                    let role = if m.role == LlmRole::Assistant {
+                        "assistant"
+                    } else {
+                        "user"
+                    };
+                    let mut content: Vec<Value> = m
+                        .images
+                        .iter()
+                        .map(|img| {
+                            let (mime, data) = split_image(img);
+                            json!({
+                                "type": "image",
+                                "source": { "type": "base64", "media_type": mime, "data": data },
+                            })
+                        })
+                        .collect();
+                    content.push(json!({ "type": "text", "text": m.content }));
+                    json!({ "role": role, "content": content })
+                })
+                .collect();
+
+            let mut body = json!({
+                "model": model,
+                "messages": chat,
+                "max_tokens": max_tokens,
+            });
+            if let Some(temperature) = options.temperature {
+                body["temperature"] = json!(temperature);
+            }
+            if let Some(system) = system {
+                body["system"] = json!(system);
+            }
+            if stream {
+                body["stream"] = json!(true);
+            }
+            body
+        }
+        "ollama" => {
+            let mut chat: Vec<Value> = system
+                .map(|s| json!({ "role": "system", "content": s }))
+                .into_iter()
+                .collect();
+            chat.extend(turns(messages).map(|m| {
+                let role = if m.role == LlmRole::Assistant {
+                    "assistant"
+                } else {
+                    "user"
+                };
+                // Ollama takes bare base64 images
+                let images: Vec<&str> = m.images.iter().map(|img| split_image(img).1).collect();
+                json!({ "role": role, "content": m.content, "images": images })
+            }));
+
+            let mut body = json!({
+                "model": model,
+                "messages": chat,
+                "stream": stream,
+                "options": { "num_predict": max_tokens },
+            });
+            if let Some(temperature) = options.temperature {
+                body["options"]["temperature"] = json!(temperature);
+            }
+            body
+        }
+        _ => return Err(format!("LLM proxy does not support {service}")),
+    };
+
+    Ok(body)
+}
+
+/// Read a token count from a JSON field (0 if missing).
+fn tokens(value: &Value, field: &str) -> u64 {
+    value.get(field).and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Usage from Gemini `usageMetadata`.
+fn gemini_usage(meta: &Value) -> LlmUsage {
+    LlmUsage {
+        input_tokens: tokens(meta, "promptTokenCount"),
+        output_tokens: tokens(meta, "candidatesTokenCount"),
+        total_tokens: tokens(meta, "totalTokenCount"),
+    }
+}
+
+/// Usage from an Ollama `/api/chat` response or final stream event.
+fn ollama_usage(body: &Value) -> LlmUsage {
+    let (input, output) = (
+        tokens(body, "prompt_eval_count"),
+        tokens(body, "eval_count"),
+    );
+    LlmUsage {
+        input_tokens: input,
+        output_tokens: output,
+        total_tokens: input + output,
+    }
+}
+
+/// Usage from an OpenAI `usage` object.
+fn openai_usage(usage: &Value) -> LlmUsage {
+    LlmUsage {
+        input_tokens: tokens(usage, "prompt_tokens"),
+        output_tokens: tokens(usage, "completion_tokens"),
+        total_tokens: tokens(usage, "total_tokens"),
+    }
+}
+
+/// Extract text, finish reason, and usage from a provider response.
+pub fn parse_response(service: &str, body: &Value) -> Result<LlmResponse, String> {
+    let response = match service {
+        "gemini" => {
+            let Some(candidate) = body["candidates"].get(0) else {
+                let reason = body["promptFeedback"]["blockReason"]
+                    .as_str()
+                    .unwrap_or("no candidates returned");
+                return Err(format!("Gemini returned no output: {reason}"));
+            };
+            let text = candidate["content"]["parts"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|part| part["text"].as_str())
+                .collect();
+            let meta = &body["usageMetadata"];
+            LlmResponse {
+                text,
+                finish_reason: candidate["finishReason"].as_str().map(str::to_string),
+                usage: meta.is_object().then(|| gemini_usage(meta)),
+            }
+        }
+        "openai" => {
+            let choice = body["choices"]
+                .get(0)
+                .ok_or_else(|| "OpenAI returned no choices".to_string())?;
+            let usage = &body["usage"];
+            LlmResponse {
+                text: choice["message"]["content"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string(),
+                finish_reason: choice["finish_reason"].as_str().map(str::to_string),
+                usage: usage.is_object().then(|| openai_usage(usage)),
+            }
+        }
+        "anthropic" => {
+            let text = body["content"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|block| block["type"] == "text")
+                .filter_map(|block| block["text"].as_str())
+                .collect();
+            let usage = &body["usage"];
+            LlmResponse {
+                text,
+                finish_reason: body["stop_reason"].as_str().map(str::to_string),
+                usage: usage.is_object().then(|| {
+                    let (input, output) = (
+                        tokens(usage, "input_tokens"),
+                        tokens(usage, "output_tokens"),
+                    );
+                    LlmUsage {
+                        input_tokens: input,
+                        output_tokens: output,
+                        total_tokens: input + output,
+                    }
+                }),
+            }
+        }
+        "ollama" => LlmResponse {
+            text: body["message"]["content"]
+                .as_str()
+                .ok_or_else(|| "Ollama returned no message".to_string())?
+                .to_string(),
+            finish_reason: body["done_reason"].as_str().map(str::to_string),
+            usage: body["eval_count"].is_u64().then(|| ollama_usage(body)),
+        },
+        _ => return Err(format!("LLM proxy does not support {service}")),
+    };
+
+    Ok(response)
+}
+
+/// Text, finish reason, and usage accumulated from stream events.
+#[derive(Debug, Default)]
+struct StreamState {
+    text: String,
+    finish_reason: Option<String>,
+    usage: Option<LlmUsage>,
+}
+
+/// Apply one stream event to the state.
+///
+/// # Returns
+///
+/// Newly generated text, if the event carried any.
+fn apply_stream_event(
+    service: &str,
+    event: &Value,
+    state: &mut StreamState,
+) -> Result<Option<String>, String> {
+    // Ollama reports errors as a plain string
+    let error = &event["error"];
+    if let Some(message) = error["message"].as_str().or_else(|| error.as_str()) {
+        return Err(format!("{service} stream error: {message}"));
+    }
+
+    let mut finished = |reason: &Value| {
+        if let Some(reason) = reason.as_str() {
+            state.finish_reason = Some(reason.to_string());
+        }
+    };
+    let delta: String = match service {
+        "gemini" => {
+            let candidate = &event["candidates"][0];
+            finished(&candidate["finishReason"]);
+            if event["usageMetadata"].is_object() {
+                state.usage = Some(gemini_usage(&event["usageMetadata"]));
+            }
+            candidate["content"]["parts"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|part| part["text"].as_str())
+                .collect()
+        }
+        "openai" => {
+            let choice = &event["choices"][0];
+            finished(&choice["finish_reason"]);
+            if event["usage"].is_object() {
+                state.usage = Some(openai_usage(&event["usage"]));
+            }
+            choice["delta"]["content"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string()
+        }
+        "anthropic" => match event["type"].as_str() {
+            Some("message_start") => {
+                let input = tokens(&event["message"]["usage"], "input_tokens");
+                state.usage = Some(LlmUsage {
+                    input_tokens: input,
+                    output_tokens: 0,
+                    total_tokens: input,
+                });
+                String::new()
+            }
+            Some("content_block_delta") => event["delta"]["text"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            Some("message_delta") => {
+                finished(&event["delta"]["stop_reason"]);
+                let input = state.usage.map_or(0, |u| u.input_tokens);
+                let output = tokens(&event["usage"], "output_tokens");
+                state.usage = Some(LlmUsage {
+                    input_tokens: input,
+                    output_tokens: output,
+                    total_tokens: input + output,
+                });
+                String::new()
+            }
+            _ => String::new(),
+        },
+        "ollama" => {
+            if event["done"] == true {
+                finished(&event["done_reason"]);
+                state.usage = Some(ollama_usage(event));
+            }
+            event["message"]["content"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string()
+        }
+        _ => return Err(format!("LLM proxy does not support {service}")),
+    };
+
+    if delta.is_empty() {
+        return Ok(None);
+    }
+    state.text.push_str(&delta);
+    Ok(Some(delta))
+}
+
+/// Splits a streamed response into event payloads.
+///
+/// Server-sent events yield their `data:` payloads; newline-delimited JSON
+/// (Ollama) yields every non-empty line.
+#[derive(Debug, Default)]
+struct StreamDecoder {
+    /// Bytes of the current incomplete line
+    buffer: Vec<u8>,
+    /// Whether the stream is newline-delimited JSON
+    ndjson: bool,
+}
+
+impl StreamDecoder {
+    /// Decoder for the stream format of a service.
+    fn for_service(service: &str) -> Self {
+        Self {
+            buffer: Vec::new(),
+            ndjson: service == "ollama",
+        }
+    }
+
+    /// Feed a chunk, returning the payloads of the lines it completed.
+    fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut payloads = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\r', '\n']);
+            if self.ndjson {
+                if !line.trim().is_empty() {
+                    payloads.push(line.to_string());
+                }
+            } else if let Some(data) = line.strip_prefix("data:") {
+                payloads.push(data.strip_prefix(' ').unwrap_or(data).to_string());
+            }
+        }
+        payloads
+    }
+}
+
+// ============================================
+// LLM PROXY
+// ============================================
+
+/// Performs LLM requests with keys resolved by the backend.
+#[derive(Clone)]
+pub struct LlmProxy {
+    /// HTTP client
+    client: reqwest::Client,
+
+    /// Database for per-key usage accounting
+    storage: Storage,
+
+    /// Cancellation senders of running streams, by stream id
+    streams: Arc<Mutex<HashMap<String, oneshot::Sender<()>>>>,
+}
+
+impl LlmProxy {
+    /// Create a proxy recording usage into `storage`.
+    pub fn new(storage: Storage) -> Self {
+        Self {
+            client: proxy::client(),
+            storage,
+            streams: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// HTTP client shared by proxy requests.
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Generate a reply to a conversation.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(LlmResponse)` - Generated text and usage
+    /// * `Err(String)` - Network failure or provider error (with its message)
+    pub async fn generate(
+        &self,
+        provider: &dyn Provider,
+        key: &ProviderKey,
+        model: &str,
+        messages: &[LlmMessage],
+        options: &LlmOptions,
+    ) -> Result<LlmResponse, String> {
+        let result = provider
+            .generate(&self.client, &key.key, model, messages, options)
+            .await?;
+        self.record_usage(key, result.usage);
+
+        Ok(result)
+    }
+
+    /// Embed texts with the provider's embedding model.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Vec<f32>>)` - One vector per input, in order
+    /// * `Err(String)` - Network failure, provider error, or no embeddings API
+    pub async fn embed(
+        &self,
+        provider: &dyn Provider,
+        key: &ProviderKey,
+        model: &str,
+        inputs: &[String],
+    ) -> Result<Vec<Vec<f32>>, String> {
+        let vectors = provider
+            .embed(&self.client, &key.key, model, inputs)
+            .await?;
+        self.record_usage(key, None);
+
+        Ok(vectors)
+    }
+
+    /// Start streaming a reply to a conversation.
+    ///
+    /// `on_event(event_name, event)` is called for every piece of text and
+    /// once when the stream ends. The caller picks `id` so it can subscribe
+    /// to the events before any are emitted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The stream is running
+    /// * `Err(String)` - Invalid request or duplicate stream id
+    pub fn stream<F>(
+        &self,
+        id: &str,
+        provider: &dyn Provider,
+        key: ProviderKey,
+        model: &str,
+        messages: &[LlmMessage],
+        options: &LlmOptions,
+        on_event: F,
+    ) -> Result<(), String>
+    where
+        F: Fn(String, &LlmStreamEvent) + Send + Sync + 'static,
+    {
+        check_stream_id(id)?;
+        let body = request_body(&key.service, model, messages, options, true)?;
+        let url = provider.chat_url(model, true)?;
+        let request =
+            providers::with_json(provider.authorize(self.client.post(url), &key.key), &body)?;
+
+        let (cancel, cancelled) = oneshot::channel();
+        {
+            let mut streams = self.streams.lock().unwrap();
+            if streams.contains_key(id) {
+                return Err(format!("Stream {id} is already running"));
+            }
+            streams.insert(id.to_string(), cancel);
+        }
+        log::info!("LLM stream started: {id} ({} {model})", key.service);
+
+        let proxy = self.clone();
+        let id = id.to_string();
+        tauri::async_runtime::spawn(async move {
+            let mut state = StreamState::default();
+            let on_delta = |text: String| {
+                let event = LlmStreamEvent::Delta { text };
+                on_event(event.event_name(&id), &event);
+            };
+
+            let outcome = tokio::select! {
+                result = read_stream(request, &key.service, &mut state, on_delta) => Some(result),
+                _ = cancelled => None,
+            };
+
+            proxy.streams.lock().unwrap().remove(&id);
+
+            let event = match outcome {
+                Some(Ok(())) => {
+                    proxy.record_usage(&key, state.usage);
+                    LlmStreamEvent::Completed {
+                        response: LlmResponse {
+                            text: state.text,
+                            finish_reason: state.finish_reason,
+                            usage: state.usage,
+                        },
+                    }
+                }
+                Some(Err(error)) => {
+                    log::warn!("LLM stream {id} failed: {error}");
+                    LlmStreamEvent::Failed {
+                        error,
+                        text: state.text,
+                    }
+                }
+                None => {
+                    // The provider may already have billed the request
+                    proxy.record_usage(&key, state.usage);
+                    LlmStreamEvent::Cancelled { text: state.text }
+                }
+            };
+
+            log::info!("LLM stream finished: {id}");
+            on_event(event.event_name(&id), &event);
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a running stream.
+    ///
+    /// The stream emits its final `cancelled` event with the text received so far.
+    pub fn cancel(&self, id: &str) -> Result<(), String> {
+        let cancel = self
+            .streams
+            .lock()
+            .unwrap()
+            .remove(id)
+            .ok_or_else(|| format!("Unknown or finished stream: {id}"))?;
+
+        // The stream may finish on its own before receiving this
+        let _ = cancel.send(());
+        log::info!("LLM stream cancelled: {id}");
+        Ok(())
+    }
+
+    /// Record a request made with a key (best effort).
+    fn record_usage(&self, key: &ProviderKey, usage: Option<LlmUsage>) {
+        if key.id.is_empty() {
+            return;
+        }
+        let tokens = usage.map(|u| u.total_tokens);
+        if let Err(e) = key_usage::record(&self.storage, &key.service, &key.id, tokens) {
+            log::warn!("{e}");
+        }
+    }
+}
+
+/// Send a streaming request and apply its events to `state`.
+async fn read_stream<F>(
+    request: reqwest::RequestBuilder,
+    service: &str,
+    state: &mut StreamState,
+    on_delta: F,
+) -> Result<(), String>
+where
+    F: Fn(String),
+{
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| format!("{service} request failed: {e}"))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.bytes().await.unwrap_or_default();
+        return Err(format!(
+            "{service} returned HTTP {}: {}",
+            status.as_u16(),
+            providers::error_message(&body)
+        ));
+    }
+
+    let mut decoder = StreamDecoder::for_service(service);
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("{service} stream interrupted: {e}"))?
+    {
+        for data in decoder.push(&chunk) {
+            if data == "[DONE]" {
+                continue;
+            }
+            let event: Value = serde_json::from_str(&data)
+                .map_err(|e| format!("Invalid {service} stream event: {e}"))?;
+            if let Some(text) = apply_stream_event(service, &event, state)? {
+                on_delta(text);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation() -> Vec<LlmMessage> {
+        vec![
+            LlmMessage {
+                role: LlmRole::System,
+                content: "Be brief.".to_string(),
+                images: Vec::new(),
+            },
+            LlmMessage {
+                role: LlmRole::User,
+                content: "Describe this".to_string(),
+                images: vec!["data:image/jpeg;base64,AAAA".to_string()],
+            },
+            LlmMessage {
+                role: LlmRole::Assistant,
+                content: "A square.".to_string(),
+                images: Vec::new(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_request_bodies() {
+        let messages = conversation();
+        let options = LlmOptions {
+            temperature: Some(0.5),
+            ..LlmOptions::default()
+        };
+
+        let gemini =
+            request_body("gemini", "gemini-2.5-flash", &messages, &options, false).unwrap();
+        assert_eq!(gemini["systemInstruction"]["parts"][0]["text"], "Be brief.");
+        assert_eq!(
+            gemini["contents"][0]["parts"][1]["inlineData"]["mimeType"],
+            "image/jpeg"
+        );
+        assert_eq!(gemini["contents"][1]["role"], "model");
+
+        let openai = request_body("openai", "gpt-4o", &messages, &options, true).unwrap();
+        assert_eq!(openai["messages"][0]["role"], "system");
+        assert_eq!(
+            openai["messages"][1]["content"][1]["image_url"]["url"],
+            "data:image/jpeg;base64,AAAA"
+        );
+        assert_eq!(openai["messages"][2]["content"], "A square.");
+        assert_eq!(openai["stream_options"]["include_usage"], true);
+
+        let anthropic =
+            request_body("anthropic", "claude-sonnet-4", &messages, &options, false).unwrap();
+        assert_eq!(anthropic["system"], "Be brief.");
+        assert_eq!(anthropic["max_tokens"], DEFAULT_MAX_TOKENS);
+        assert_eq!(
+            anthropic["messages"][0]["content"][0]["source"]["data"],
+            "AAAA"
+        );
+        assert_eq!(anthropic["messages"].as_array().unwrap().len(), 2);
+
+        assert!(request_body("mistral", "m", &messages, &options, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_responses() {
+        let gemini = json!({
+            "candidates": [{
+                "content": { "parts": [{ "text": "Hel" }, { "text": "lo" }] },
+                "finishReason": "STOP",
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 3,
+                "candidatesTokenCount": 2,
+                "totalTokenCount": 5,
+            },
+        });
+        let parsed = parse_response("gemini", &gemini).unwrap();
+        assert_eq!(parsed.text, "Hello");
+        assert_eq!(parsed.usage.unwrap().total_tokens, 5);
+
+        let anthropic = json!({
+            "content": [{ "type": "text", "text": "Hi" }],
+            "stop_reason": "end_turn",
+            "usage": { "input_tokens": 4, "output_tokens": 1 },
+        });
+        let parsed = parse_response("anthropic", &anthropic).unwrap();
+        assert_eq!(parsed.text, "Hi");
+        assert_eq!(parsed.usage.unwrap().total_tokens, 5);
+
+        let blocked = json!({ "promptFeedback": { "blockReason": "SAFETY" } });
+        assert!(parse_response("gemini", &blocked)
+            .unwrap_err()
+            .contains("SAFETY"));
+    }
+
+    #[test]
+    fn test_sse_stream_events() {
+        let stream = concat!(
+            "event: message_start\n",
+            r#"data: {"type":"message_start","message":{"usage":{"input_tokens":7}}}"#,
+            "\r\n\n",
+            r#"data: {"type":"content_block_delta","delta":{"text":"Hi"}}"#,
+            "\n",
+            r#"data: {"type":"message_delta","delta":{"stop_reason":"end_turn"},"#,
+            r#""usage":{"output_tokens":2}}"#,
+            "\n",
+        );
+
+        // Chunk boundaries fall inside lines
+        let mut decoder = StreamDecoder::for_service("anthropic");
+        let payloads: Vec<String> = stream
+            .as_bytes()
+            .chunks(10)
+            .flat_map(|chunk| decoder.push(chunk))
+            .collect();
+        assert_eq!(payloads.len(), 3);
+
+        let mut state = StreamState::default();
+        let deltas: Vec<String> = payloads
+            .iter()
+            .filter_map(|data| {
+                let event: Value = serde_json::from_str(data).unwrap();
+                apply_stream_event("anthropic", &event, &mut state).unwrap()
+            })
+            .collect();
+        assert_eq!(deltas, ["Hi"]);
+        assert_eq!(state.finish_reason.as_deref(), Some("end_turn"));
+        assert_eq!(state.usage.unwrap().total_tokens, 9);
+
+        let error = json!({ "error": { "message": "quota exceeded" } });
+        assert!(apply_stream_event("openai", &error, &mut state).is_err());
+        assert!(check_stream_id("3f2a-41c0").is_ok());
+        assert!(check_stream_id("a/b").is_err());
+    }
+
+    #[test]
+    fn test_ollama_ndjson_stream() {
+        let messages = conversation();
+        let body = request_body(
+            "ollama",
+            "llama3.2",
+            &messages,
+            &LlmOptions::default(),
+            true,
+        )
+        .unwrap();
+        assert_eq!(body["messages"][1]["images"][0], "AAAA");
+        assert_eq!(body["stream"], true);
+        assert_eq!(split_image("AAAA"), ("image/png", "AAAA"));
+
+        let stream = concat!(
+            r#"{"message":{"content":"Hel"},"done":false}"#,
+            "\n\n",
+            r#"{"message":{"content":"lo"},"done":true,"done_reason":"stop","#,
+            r#""prompt_eval_count":5,"eval_count":2}"#,
+            "\n",
+        );
+        let mut decoder = StreamDecoder::for_service("ollama");
+        let mut state = StreamState::default();
+        for data in decoder.push(stream.as_bytes()) {
+            let event: Value = serde_json::from_str(&data).unwrap();
+            apply_stream_event("ollama", &event, &mut state).unwrap();
+        }
+        assert_eq!(state.text, "Hello");
+        assert_eq!(state.finish_reason.as_deref(), Some("stop"));
+        assert_eq!(state.usage.unwrap().total_tokens, 7);
+
+        let error = json!({ "error": "model not found" });
+        assert!(apply_stream_event("ollama", &error, &mut state).is_err());
+    }
+}
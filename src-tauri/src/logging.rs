@@ -0,0 +1,474 @@
+//! src-tauri/src/logging.rs
+//! ========================
+//! Application log sink with runtime level control.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Records are dispatched with fern to three outputs: stderr, a bounded
+//! in-memory buffer for `get_app_logs`, and a rotating log file (`app.log`)
+//! in the app log directory (see paths.rs). Messages are passed through
+//! `redact` first, so API key values never reach any of the three.
+//!
+//! The log file is rotated (`app.log` -> `app.log.1` -> ...) when it would
+//! grow past `max_file_bytes` and, with `rotate_daily`, on the first record
+//! of a new day. `max_files` rotated files are kept, and with `max_age_days`
+//! older ones are deleted at each rotation. The limits come from the
+//! `logging.*` settings, applied once settings are loaded (`set_retention`).
+//!
+//! The active level is the global `log::max_level()`, so `log_set_level`
+//! takes effect immediately without restarting with RUST_LOG set.
+//!
+//! Usage:
+//!     ```rust
+//!     let sink = LogSink::init(paths.log_dir());
+//!     sink.set_retention(settings.get().logging.retention());
+//!     sink.set_level("debug")?;
+//!     let recent = sink.entries(Some(LevelFilter::Warn), Some(100));
+//!     ```
+
+use chrono::{Local, NaiveDate};
+use log::{Level, LevelFilter, Record};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use crate::redact;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Maximum number of entries kept in memory
+pub const LOG_BUFFER_CAPACITY: usize = 2000;
+
+/// Log file name inside the log directory
+const LOG_FILE: &str = "app.log";
+
+/// Default size at which the log file is rotated (5 MB)
+pub const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Default number of rotated files kept (app.log.1 .. app.log.N)
+pub const MAX_ROTATED_FILES: usize = 5;
+
+/// Level used when RUST_LOG is unset or not a plain level
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A single captured log record.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogEntry {
+    /// Timestamp (RFC 3339)
+    pub timestamp: String,
+    /// Level (ERROR, WARN, INFO, DEBUG, TRACE)
+    pub level: String,
+    /// Log target (usually the module path)
+    pub target: String,
+    /// Formatted message
+    pub message: String,
+}
+
+/// When the log file is rotated and how many old files are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRetention {
+    /// Size at which the log file is rotated
+    pub max_file_bytes: u64,
+    /// Rotated files kept (at least 1)
+    pub max_files: usize,
+    /// Also rotate when the date changes
+    pub rotate_daily: bool,
+    /// Delete rotated files older than this many days (0 keeps them)
+    pub max_age_days: u32,
+}
+
+impl Default for LogRetention {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: MAX_LOG_FILE_BYTES,
+            max_files: MAX_ROTATED_FILES,
+            rotate_daily: false,
+            max_age_days: 0,
+        }
+    }
+}
+
+/// Size- and date-rotated log file.
+#[derive(Debug)]
+struct RotatingFile {
+    /// Active log file path
+    path: PathBuf,
+    /// Open handle to the active file
+    file: File,
+    /// Current size of the active file
+    size: u64,
+    /// Day the active file was last written
+    day: NaiveDate,
+    /// Rotation limits
+    retention: LogRetention,
+}
+
+impl RotatingFile {
+    /// Open (or create) the log file in `dir`.
+    fn open(dir: &Path) -> std::io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOG_FILE);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let metadata = file.metadata()?;
+        let day = metadata.modified().map_or_else(
+            |_| Local::now().date_naive(),
+            |modified| chrono::DateTime::<Local>::from(modified).date_naive(),
+        );
+        Ok(Self {
+            path,
+            file,
+            size: metadata.len(),
+            day,
+            retention: LogRetention::default(),
+        })
+    }
+
+    /// Append a line, rotating first if it would exceed the size limit or
+    /// starts a new day.
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        let today = Local::now().date_naive();
+        let new_day = self.retention.rotate_daily && today != self.day;
+        if self.size > 0 && (new_day || self.size + len > self.retention.max_file_bytes) {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{line}")?;
+        self.size += len;
+        self.day = today;
+        Ok(())
+    }
+
+    /// Shift app.log -> app.log.1 -> ... -> app.log.N, dropping the oldest.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+        let max_files = self.retention.max_files.max(1);
+        for i in (1..max_files).rev() {
+            let from = rotated_path(&self.path, i);
+            if from.exists() {
+                fs::rename(&from, rotated_path(&self.path, i + 1))?;
+            }
+        }
+        fs::rename(&self.path, rotated_path(&self.path, 1))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        self.size = 0;
+        self.prune(max_files);
+        Ok(())
+    }
+
+    /// Delete rotated files past `max_files` (left by a larger limit) or
+    /// older than `max_age_days`.
+    fn prune(&self, max_files: usize) {
+        let max_age = (self.retention.max_age_days > 0)
+            .then(|| Duration::from_secs(u64::from(self.retention.max_age_days) * 24 * 60 * 60));
+        let mut index = 1;
+        loop {
+            let path = rotated_path(&self.path, index);
+            let Ok(metadata) = fs::metadata(&path) else {
+                break;
+            };
+            let expired = max_age.is_some_and(|max_age| {
+                metadata
+                    .modified()
+                    .ok()
+                    .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                    .is_some_and(|age| age > max_age)
+            });
+            if index > max_files || expired {
+                let _ = fs::remove_file(&path);
+            }
+            index += 1;
+        }
+    }
+}
+
+/// Path of the `index`-th rotated file (`app.log.1`, ...).
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{index}"));
+    PathBuf::from(name)
+}
+
+/// Parse a level name (`off`, `error`, `warn`, `info`, `debug`, `trace`).
+pub fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    LevelFilter::from_str(level.trim()).map_err(|_| {
+        format!("Invalid log level: {level} (expected off, error, warn, info, debug, or trace)")
+    })
+}
+
+// ============================================
+// LOG SINK
+// ============================================
+
+/// Global log sink: stderr + in-memory buffer + rotating file.
+#[derive(Clone)]
+pub struct LogSink {
+    /// Most recent entries (bounded by `LOG_BUFFER_CAPACITY`)
+    buffer: Arc<Mutex<VecDeque<LogEntry>>>,
+
+    /// Rotating log file (None if no log directory is available)
+    file: Arc<Mutex<Option<RotatingFile>>>,
+}
+
+impl LogSink {
+    /// Create a sink writing files to `log_dir` (None disables file output).
+    pub fn new(log_dir: Option<PathBuf>) -> Self {
+        let file = log_dir.and_then(|dir| match RotatingFile::open(&dir) {
+            Ok(f) => Some(f),
+            Err(e) => {
+                eprintln!("Failed to open log file in {dir:?}: {e}");
+                None
+            }
+        });
+
+        Self {
+            buffer: Arc::new(Mutex::new(VecDeque::with_capacity(LOG_BUFFER_CAPACITY))),
+            file: Arc::new(Mutex::new(file)),
+        }
+    }
+
+    /// Create the sink and install it as the global logger.
+    ///
+    /// The initial level comes from RUST_LOG when it is a plain level,
+    /// otherwise `info`.
+    pub fn init(log_dir: Option<PathBuf>) -> Self {
+        let sink = Self::new(log_dir);
+
+        let level = std::env::var("RUST_LOG")
+            .ok()
+            .and_then(|v| parse_level(&v).ok())
+            .unwrap_or(DEFAULT_LEVEL);
+
+        let (_, logger) = sink.dispatch().into_log();
+        if log::set_boxed_logger(logger).is_ok() {
+            log::set_max_level(level);
+        }
+
+        sink
+    }
+
+    /// Build the dispatch to stderr, the buffer, and the log file.
+    ///
+    /// It passes every level; filtering is done by `log::max_level`.
+    fn dispatch(&self) -> fern::Dispatch {
+        let sink = self.clone();
+        let stderr = fern::Dispatch::new()
+            .format(|out, message, record| out.finish(format_args!("{}", line(record, message))))
+            .chain(std::io::stderr());
+
+        fern::Dispatch::new()
+            .level(LevelFilter::Trace)
+            // Mask key values before the message reaches stderr, buffer, or file
+            .format(|out, message, _| {
+                out.finish(format_args!("{}", redact::redact(&message.to_string())));
+            })
+            .chain(stderr)
+            .chain(fern::Output::call(move |record| sink.capture(record)))
+    }
+
+    /// Get the active log level.
+    pub fn level(&self) -> LevelFilter {
+        log::max_level()
+    }
+
+    /// Change the active log level.
+    pub fn set_level(&self, level: &str) -> Result<LevelFilter, String> {
+        let filter = parse_level(level)?;
+        log::set_max_level(filter);
+        Ok(filter)
+    }
+
+    /// Change when the log file is rotated and how many old files are kept.
+    pub fn set_retention(&self, retention: LogRetention) {
+        if let Some(ref mut file) = *self.file.lock().unwrap() {
+            file.retention = retention;
+        }
+    }
+
+    /// Path of the active log file, if file logging is enabled.
+    pub fn file_path(&self) -> Option<PathBuf> {
+        self.file.lock().unwrap().as_ref().map(|f| f.path.clone())
+    }
+
+    /// Get buffered entries at or above `min_level`, keeping the most
+    /// recent `limit` (oldest first).
+    pub fn entries(&self, min_level: Option<LevelFilter>, limit: Option<usize>) -> Vec<LogEntry> {
+        let min_level = min_level.unwrap_or(LevelFilter::Trace);
+        let buffer = self.buffer.lock().unwrap();
+
+        let mut entries: Vec<LogEntry> = buffer
+            .iter()
+            .filter(|e| Level::from_str(&e.level).is_ok_and(|l| l <= min_level))
+            .cloned()
+            .collect();
+
+        if let Some(limit) = limit {
+            let skip = entries.len().saturating_sub(limit);
+            entries.drain(..skip);
+        }
+        entries
+    }
+
+    /// Clear the in-memory buffer.
+    pub fn clear(&self) {
+        self.buffer.lock().unwrap().clear();
+    }
+
+    /// Capture a (redacted) record into the buffer and log file.
+    fn capture(&self, record: &Record) {
+        let entry = LogEntry {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        };
+
+        if let Some(ref mut file) = *self.file.lock().unwrap() {
+            let _ = file.write_line(&line(record, record.args()));
+        }
+
+        let mut buffer = self.buffer.lock().unwrap();
+        if buffer.len() >= LOG_BUFFER_CAPACITY {
+            buffer.pop_front();
+        }
+        buffer.push_back(entry);
+    }
+}
+
+/// Format a record as a stderr or log file line.
+fn line(record: &Record, message: &std::fmt::Arguments) -> String {
+    format!(
+        "[{} {:<5} {}] {message}",
+        Local::now().format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+        record.level(),
+        record.target()
+    )
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn capture(sink: &LogSink, level: Level, message: &str) {
+        sink.capture(
+            &Record::builder()
+                .args(format_args!("{message}"))
+                .level(level)
+                .target("test")
+                .build(),
+        );
+    }
+
+    #[test]
+    fn test_parse_level() {
+        assert_eq!(parse_level("debug").unwrap(), LevelFilter::Debug);
+        assert_eq!(parse_level(" WARN ").unwrap(), LevelFilter::Warn);
+        assert_eq!(parse_level("off").unwrap(), LevelFilter::Off);
+        assert!(parse_level("verbose").is_err());
+    }
+
+    #[test]
+    fn test_buffer_is_bounded() {
+        let sink = LogSink::new(None);
+        for i in 0..LOG_BUFFER_CAPACITY + 10 {
+            capture(&sink, Level::Info, &format!("line {i}"));
+        }
+
+        let entries = sink.entries(None, None);
+        assert_eq!(entries.len(), LOG_BUFFER_CAPACITY);
+        assert_eq!(entries[0].message, "line 10");
+    }
+
+    #[test]
+    fn test_entries_filter_and_limit() {
+        let sink = LogSink::new(None);
+        capture(&sink, Level::Debug, "debug");
+        capture(&sink, Level::Warn, "warn 1");
+        capture(&sink, Level::Error, "error");
+        capture(&sink, Level::Warn, "warn 2");
+
+        let warnings = sink.entries(Some(LevelFilter::Warn), None);
+        assert_eq!(warnings.len(), 3);
+
+        let last = sink.entries(Some(LevelFilter::Warn), Some(1));
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].message, "warn 2");
+
+        sink.clear();
+        assert!(sink.entries(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_file_written_and_rotated() {
+        let dir = temp_dir("logs");
+        let sink = LogSink::new(Some(dir.clone()));
+        capture(&sink, Level::Info, "hello file");
+
+        let path = sink.file_path().unwrap();
+        assert!(fs::read_to_string(&path).unwrap().contains("hello file"));
+
+        sink.file.lock().unwrap().as_mut().unwrap().rotate().unwrap();
+        assert!(rotated_path(&path, 1).exists());
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_retention_limits() {
+        let dir = temp_dir("logs");
+        let sink = LogSink::new(Some(dir));
+        sink.set_retention(LogRetention {
+            max_file_bytes: 64,
+            max_files: 2,
+            rotate_daily: true,
+            max_age_days: 0,
+        });
+        let path = sink.file_path().unwrap();
+
+        // Size: each line is over half the limit
+        for i in 0..5 {
+            capture(&sink, Level::Info, &format!("line {i}"));
+        }
+        assert!(rotated_path(&path, 2).exists());
+        assert!(!rotated_path(&path, 3).exists());
+
+        // Date: a file last written yesterday is rotated on the next record
+        {
+            let mut file = sink.file.lock().unwrap();
+            let file = file.as_mut().unwrap();
+            file.retention.max_file_bytes = MAX_LOG_FILE_BYTES;
+            file.day = file.day.pred_opt().unwrap();
+        }
+        let before = fs::read_to_string(&path).unwrap();
+        capture(&sink, Level::Info, "new day");
+        assert_eq!(fs::read_to_string(rotated_path(&path, 1)).unwrap(), before);
+        assert!(fs::read_to_string(&path).unwrap().contains("new day"));
+
+        // A lower limit removes the extra files at the next rotation
+        sink.set_retention(LogRetention {
+            max_files: 1,
+            ..LogRetention::default()
+        });
+        sink.file.lock().unwrap().as_mut().unwrap().rotate().unwrap();
+        assert!(rotated_path(&path, 1).exists());
+        assert!(!rotated_path(&path, 2).exists());
+    }
+}
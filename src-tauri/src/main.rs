@@ -9,6 +9,60 @@
 //!     - D030: ipc/mod.rs (IPC module)
 //!     - D035: ipc/manager.rs (`IpcManagerState`)
 //!     - D036: commands/mod.rs (Tauri commands)
+//!     - project.rs (project root selection)
+//!     - project_fs.rs (file access confined to the project and granted dirs)
+//!     - project_tree.rs (project directory tree with .gitignore-style patterns)
+//!     - deep_link.rs (appfactory:// links for plugin installs and opening projects)
+//!     - workspace.rs (per-workspace IPC managers)
+//!     - shutdown.rs (coordinated shutdown on window close)
+//!     - permissions.rs (plugin permission prompts and stored decisions)
+//!     - packages.rs (plugin package signatures and installation)
+//!     - migration.rs (plugin state migration on swap and update)
+//!     - plugin_graph.rs (plugin dependencies and load order)
+//!     - plugin_commands.rs (frontend commands declared in plugin manifests)
+//!     - plugin_data.rs (per-plugin data directories in the app data dir)
+//!     - quotas.rs (per-plugin payload limits and call throttling)
+//!     - startup.rs (startup progress events for the splash screen)
+//!     - error_catalog.rs (D009 error catalog for command error hints)
+//!     - error_messages.rs (translated command error messages by locale)
+//!     - settings.rs (persistent settings used to build `IpcConfig`)
+//!     - app_config.rs (app_factory.toml, env, and CLI overrides at startup)
+//!     - paths.rs (per-OS config/data/cache/log directories with overrides)
+//!     - logging.rs (log sink with runtime level control)
+//!     - tracing_export.rs (optional OTLP export of IPC and compile spans)
+//!     - crash.rs (crash reports for panics and plugin host crashes)
+//!     - health_history.rs (plugin host health history persisted across restarts)
+//!     - stats_history.rs (in-memory time series of plugin host request stats)
+//!     - alerts.rs (notification, webhook, and alert file when the host is unhealthy)
+//!     - jobs.rs (background jobs with progress and cancellation)
+//!     - scheduler.rs (cron-scheduled plugin invocations)
+//!     - hardware.rs (hardware capability detection)
+//!     - history.rs (persisted chat history)
+//!     - downloads.rs (model downloads into the app cache dir)
+//!     - http_fetch.rs (HTTP requests with a domain allowlist and limits)
+//!     - offline.rs (offline mode blocking the app's network requests)
+//!     - proxy.rs (HTTP(S) proxies for the app's clients and plugin hosts)
+//!     - artifacts.rs (content-addressed artifact cache)
+//!     - storage.rs (embedded SQLite database)
+//!     - bundler.rs (multi-file compilation for compile_project)
+//!     - apps.rs (versioned generated-app definitions)
+//!     - export.rs (standalone project export)
+//!     - preview.rs (localhost preview server with live reload)
+//!     - typecheck.rs (tsc type checking for compile_tsx)
+//!     - analyzer.rs (component prop metadata for analyze_component)
+//!     - mdx.rs (MDX to TSX conversion for compile_mdx)
+//!     - watch.rs (watch-mode recompilation for compile_watch)
+//!     - fs_watch.rs (debounced file change events for fs_watch)
+//!     - vault.rs (encryption at rest for stored API keys)
+//!     - env_file.rs (locked, atomic .env writes with backups)
+//!     - redact.rs (masking of API key values in logs)
+//!     - catalog.rs (API key service catalog with key-format validation)
+//!     - secret_store.rs (1Password, HashiCorp Vault, and environment key backends)
+//!     - oauth.rs (OAuth connections with loopback redirect and token refresh)
+//!     - key_usage.rs (per-key API usage accounting)
+//!     - llm.rs (LLM proxy keeping API keys out of the webview)
+//!     - providers/ (OpenAI, Anthropic, Gemini, and Ollama provider implementations)
+//!     - ollama.rs (model pulls into a local Ollama daemon)
 
 #![deny(unsafe_code)]
 // Clippy pedantic lint suppressions for this crate:
@@ -41,89 +95,301 @@
     windows_subsystem = "windows"
 )]
 
+mod alerts;
+mod analyzer;
+mod app_config;
+mod apps;
+mod artifacts;
+mod bundler;
+mod catalog;
 mod commands;
+mod crash;
+mod deep_link;
+mod devtools;
+mod downloads;
+mod env_file;
+mod error_catalog;
+mod error_messages;
+mod events;
+mod export;
+mod fs_watch;
+mod hardware;
+mod health_history;
+mod history;
+mod http_fetch;
 mod ipc;
+mod jobs;
+mod key_usage;
+mod llm;
+mod logging;
+mod mdx;
+mod migration;
+mod oauth;
+mod offline;
+mod ollama;
+mod packages;
+mod paths;
+mod permissions;
+mod plugin_commands;
+mod plugin_data;
+mod plugin_graph;
+mod preview;
+mod project;
+mod project_fs;
+mod project_tree;
+mod providers;
+mod proxy;
+mod quotas;
+mod redact;
+mod reverse_rpc;
+mod scheduler;
+mod secret_store;
+mod settings;
+mod shutdown;
+mod startup;
+mod stats_history;
+mod storage;
+#[cfg(test)]
+mod test_util;
+mod tracing_export;
+mod typecheck;
+mod vault;
+mod watch;
+mod workspace;
 
-use ipc::manager::{IpcConfig, IpcManagerState};
-use std::path::PathBuf;
+use alerts::HealthAlerts;
+use app_config::AppConfig;
+use artifacts::ArtifactStore;
+use catalog::ServiceCatalog;
+use commands::compiler::CompileCache;
+use crash::CrashReporter;
+use devtools::IpcConsole;
+use downloads::DownloadManager;
+use events::EventSubscriptions;
+use fs_watch::FileWatcher;
+use health_history::HealthHistory;
+use ipc::hosts::HostRegistry;
+use ipc::manager::{EnvProvider, IpcManagerState};
+use ipc::schemas::{SchemaRegistry, SCHEMAS_FILE};
+use jobs::JobManager;
+use llm::LlmProxy;
+use logging::LogSink;
+use oauth::OAuthManager;
+use ollama::OllamaPulls;
+use paths::AppPaths;
+use permissions::PermissionStore;
+use preview::PreviewManager;
+use project::ProjectManager;
+use quotas::PluginQuotas;
+use reverse_rpc::ReverseRpc;
+use scheduler::Scheduler;
+use secret_store::SecretBackends;
+use settings::SettingsStore;
+use shutdown::ShutdownCoordinator;
+use startup::{StartupProgress, StartupStage};
+use stats_history::StatsHistory;
+use storage::Storage;
+use vault::KeyVault;
+use watch::CompileWatcher;
+use workspace::WorkspaceRegistry;
 use tauri::Manager;
 
-/// Get the project root directory (parent of src-tauri).
-///
-/// In development, this is the directory containing both `src-tauri` and `plugins`.
-/// In production builds, this will need to be adjusted based on bundle structure.
-fn get_project_root() -> PathBuf {
-    // During development, the executable runs from src-tauri/target/debug
-    // We need to go up to find the project root (where plugins/ is)
-    let exe_path = std::env::current_exe().unwrap_or_default();
-    log::debug!("Executable path: {exe_path:?}");
-
-    // Try to find project root by looking for plugins directory
-    let mut current = exe_path.parent().map(std::path::Path::to_path_buf);
-
-    // Walk up the directory tree looking for the plugins directory
-    for _ in 0..10 {
-        if let Some(ref dir) = current {
-            let plugins_dir = dir.join("plugins");
-            if plugins_dir.exists() && plugins_dir.is_dir() {
-                log::info!("Found project root: {dir:?}");
-                return dir.clone();
-            }
-            current = dir.parent().map(std::path::Path::to_path_buf);
-        } else {
-            break;
-        }
-    }
+fn main() {
+    let context = tauri::generate_context!();
 
-    // Fallback: try current working directory
-    let cwd = std::env::current_dir().unwrap_or_default();
-    log::warn!(
-        "Could not find plugins directory, using current working directory: {cwd:?}"
-    );
+    // Forward appfactory:// links to an already running instance (exits if one is)
+    tauri_plugin_deep_link::prepare(&context.config().tauri.bundle.identifier);
+
+    // Resolve the per-OS app directories (or their --home/--*-dir overrides)
+    let paths = AppPaths::resolve(&context.config().tauri.bundle.identifier);
+
+    // Initialize logging (rotating files in the app log dir)
+    let log_sink = LogSink::init(paths.log_dir());
 
-    // Check if cwd has plugins, if not try parent
-    if cwd.join("plugins").exists() {
-        return cwd;
+    log::info!("Starting App Factory v1.0.0");
+    log::info!("App directories: {paths:?}");
+
+    // Record panics as crash reports in the app data dir
+    let crash_dir = paths.data_dir().map(|dir| dir.join(crash::CRASH_DIR));
+    crash::install_panic_hook(crash_dir.clone());
+
+    // Settings and recents live in the app config dir
+    let config_dir = paths.config_dir();
+    let settings = SettingsStore::load(config_dir.clone());
+    // Parse the embedded error catalog now rather than on the first command error
+    let _ = error_catalog::catalog();
+    log_sink.set_retention(settings.get().logging.retention());
+    if let Err(e) = error_messages::set_locale(&settings.get().ui.locale) {
+        log::warn!("Ignoring ui.locale: {e}");
     }
+    offline::set_offline(settings.get().network.offline);
+    // Before any HTTP client is created
+    proxy::set_proxy(settings.get().network.proxy());
+
+    // Determine project root (where plugins/ directory is located)
+    let progress = StartupProgress::new();
+    progress.start(StartupStage::ResolvingProjectRoot, None);
+    let project_root = project::discover_project_root();
+    log::info!("Project root: {project_root:?}");
+    progress.finish(
+        StartupStage::ResolvingProjectRoot,
+        Some(project_root.display().to_string()),
+    );
 
-    // Try parent of cwd (common when running from src-tauri)
-    if let Some(parent) = cwd.parent() {
-        if parent.join("plugins").exists() {
-            return parent.to_path_buf();
+    // Layer app_factory.toml, APP_FACTORY_* variables, and CLI flags over the settings
+    let app_config = AppConfig::load(&project_root, config_dir.as_deref());
+    if let Some(level) = app_config.log_level() {
+        if let Err(e) = log_sink.set_level(level) {
+            log::warn!("{e}");
         }
     }
+    if let Some(options) = app_config.compiler() {
+        commands::compiler::set_default_options(options.clone());
+    }
+    let crash_reporter = CrashReporter::new(crash_dir, app_config.crash_submit_url());
+    let projects =
+        ProjectManager::new(project_root.clone(), config_dir.clone()).with_secrets(paths.clone());
+    let vault = KeyVault::load(config_dir.as_deref());
+    let catalog = ServiceCatalog::load(config_dir.clone());
+    let secret_backends = SecretBackends::load(config_dir.clone());
+    let oauth = OAuthManager::load(config_dir.clone(), vault.clone());
+    let permissions = PermissionStore::load(config_dir.clone());
+    let scheduler = Scheduler::load(config_dir);
 
-    cwd
-}
+    // Structured persistence (falls back to in-memory if the file can't be opened)
+    let db_path = paths
+        .data_dir()
+        .map(|dir| dir.join(storage::DATABASE_FILE));
+    let storage = Storage::open(db_path).unwrap_or_else(|e| {
+        log::error!("{e}; using in-memory storage");
+        Storage::open(None).expect("in-memory storage")
+    });
+    let llm = LlmProxy::new(storage.clone());
+    let health_history = HealthHistory::new(storage.clone());
 
-fn main() {
-    // Initialize logging
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
-        .format_timestamp_millis()
-        .init();
+    // Downloaded model weights and generated artifacts live in the app cache dir,
+    // assets fetched by generated apps in the data dir
+    let cache_dir = paths.cache_dir();
+    let downloads = DownloadManager::new(cache_dir.as_ref().map(|dir| dir.join("models")))
+        .with_assets_dir(paths.data_dir().map(|dir| dir.join("assets")));
+    let artifacts = ArtifactStore::open(cache_dir.map(|dir| dir.join("artifacts")));
 
-    log::info!("Starting App Factory v1.0.0");
+    // Pass the active API keys of the host's project and the proxy settings to
+    // plugins as environment variables, read fresh at every spawn
+    let plugin_vault = vault.clone();
+    let plugin_backends = secret_backends.clone();
+    let plugin_oauth = oauth.clone();
+    let plugin_paths = paths.clone();
+    let secrets_env = EnvProvider::new(move |dir| {
+        let mut vars = dir
+            .map(|dir| {
+                plugin_paths.migrate_secrets(dir);
+                let env_path = plugin_paths.secrets_file(dir);
+                commands::secrets::plugin_env(&env_path, &plugin_vault, &plugin_backends)
+            })
+            .unwrap_or_default();
+        vars.extend(plugin_oauth.plugin_env());
+        vars.extend(proxy::current().env_vars());
+        vars
+    });
 
-    // Determine project root (where plugins/ directory is located)
-    let project_root = get_project_root();
-    log::info!("Project root: {project_root:?}");
+    // Answer requests plugins send through the host (permissions, secrets,
+    // artifacts, file dialogs, HTTP)
+    let reverse_rpc = ReverseRpc::new(
+        permissions.clone(),
+        artifacts.clone(),
+        vault.clone(),
+        secret_backends.clone(),
+        paths.clone(),
+    );
+    let host_requests = reverse_rpc.handler();
 
-    // Create IPC configuration with correct working directory
-    let config = IpcConfig::default()
-        .with_python_path("python")
-        .with_module_path("plugins._host")
+    // Create IPC configuration from settings with correct working directory
+    let startup_settings = app_config.apply(&settings.get());
+    let shutdown = ShutdownCoordinator::new(std::time::Duration::from_secs(
+        startup_settings.ipc.shutdown_timeout_secs,
+    ));
+    let health_alerts = HealthAlerts::new(startup_settings.alerts.clone(), paths.log_dir());
+
+    // Export spans when enabled (the batch exporter runs on the async runtime)
+    if startup_settings.tracing.enabled {
+        let endpoint = &startup_settings.tracing.otlp_endpoint;
+        if let Err(e) = tauri::async_runtime::block_on(async { tracing_export::init(endpoint) }) {
+            log::warn!("{e}");
+        }
+    }
+
+    // Check the interpreter up front so the splash screen can say what is missing
+    let python = &startup_settings.python.path;
+    progress.start(StartupStage::LocatingPython, Some(python.clone()));
+    match startup::find_executable(python) {
+        Some(path) => progress.finish(
+            StartupStage::LocatingPython,
+            Some(path.display().to_string()),
+        ),
+        None => progress.fail(
+            StartupStage::LocatingPython,
+            format!("Python executable not found: {python}"),
+        ),
+    }
+
+    // Method schemas from config/; the host adds its own once it is ready
+    let schemas = SchemaRegistry::new();
+    match schemas.load_file(&project_root.join(SCHEMAS_FILE)) {
+        Ok(0) => {}
+        Ok(count) => log::info!("Loaded IPC schemas for {count} method(s)"),
+        Err(e) => log::warn!("Failed to load IPC schemas: {e}"),
+    }
+
+    let config = startup_settings
+        .to_ipc_config()
         .with_working_dir(project_root)
-        .with_timeout(60)
-        .with_auto_respawn(true);
+        .with_plugin_data_dir(paths.plugin_data_dir())
+        .with_env_provider(secrets_env)
+        .with_request_handler(host_requests);
 
-    // Create IPC Manager state
+    // Create IPC Manager state (the default workspace)
     let ipc_state = IpcManagerState::new(config);
+    let workspaces = WorkspaceRegistry::new(ipc_state.clone());
+    let hosts = HostRegistry::load(ipc_state.clone(), paths.config_dir());
 
     log::info!("IPC Manager configured");
 
     // Build and run Tauri application
     tauri::Builder::default()
         .manage(ipc_state)
+        .manage(workspaces)
+        .manage(hosts)
+        .manage(projects)
+        .manage(vault)
+        .manage(catalog)
+        .manage(secret_backends)
+        .manage(oauth)
+        .manage(settings)
+        .manage(log_sink)
+        .manage(crash_reporter)
+        .manage(shutdown)
+        .manage(permissions)
+        .manage(reverse_rpc)
+        .manage(progress)
+        .manage(JobManager::new())
+        .manage(PluginQuotas::new())
+        .manage(EventSubscriptions::new())
+        .manage(IpcConsole::new())
+        .manage(schemas)
+        .manage(scheduler)
+        .manage(downloads)
+        .manage(artifacts)
+        .manage(storage)
+        .manage(health_history)
+        .manage(health_alerts)
+        .manage(StatsHistory::new())
+        .manage(llm)
+        .manage(OllamaPulls::new())
+        .manage(PreviewManager::new())
+        .manage(CompileCache::new())
+        .manage(CompileWatcher::new())
+        .manage(FileWatcher::new())
         .invoke_handler(commands::generate_command_handler!())
         .setup(|app| {
             log::info!("Tauri application setup complete");
@@ -131,23 +397,151 @@ fn main() {
             // Get the IPC state and start it
             let state = app.state::<IpcManagerState>();
 
-            // Start IPC in a background task
+            // Forward manager events (e.g. memory warnings) to the frontend
+            let mut events = state.subscribe_events();
+            let app_handle = app.handle();
+            tauri::async_runtime::spawn(async move {
+                loop {
+                    match events.recv().await {
+                        Ok(event) => {
+                            let _ = app_handle.emit_all(event.event_name(), event);
+                        }
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+            });
+
+            // Forward plugin events for the topics the webview subscribed to
+            let subscriptions = app.state::<EventSubscriptions>().inner().clone();
+            tauri::async_runtime::spawn(subscriptions.forward(app.handle(), state.inner().clone()));
+
+            // Record plugin host requests for the IPC console (debug builds)
+            if devtools::enabled() {
+                let console = app.state::<IpcConsole>().inner().clone();
+                tauri::async_runtime::spawn(console.forward(app.handle(), state.inner().clone()));
+            }
+
+            // Record plugin host crashes
+            let crash_reporter = app.state::<CrashReporter>().inner().clone();
+            tauri::async_runtime::spawn(crash_reporter.watch(state.subscribe_events()));
+
+            // Keep a compacted health history across restarts
+            let health_history = app.state::<HealthHistory>().inner().clone();
+            tauri::async_runtime::spawn(
+                health_history.watch(state.health().subscribe(), state.subscribe_events()),
+            );
+
+            // Sample request rate, errors, and latency for the status charts
+            let stats_history = app.state::<StatsHistory>().inner().clone();
+            tauri::async_runtime::spawn(stats_history.watch(state.inner().clone()));
+
+            // Alert on an unhealthy plugin host (if any alert action is configured)
+            let health_alerts = app.state::<HealthAlerts>().inner().clone();
+            tauri::async_runtime::spawn(health_alerts.watch(app.handle(), state.inner().clone()));
+
+            // Permission prompts need the main window
+            app.state::<PermissionStore>().attach(app.handle());
+
+            // Start IPC in a background task, reporting progress to the splash screen
+            let progress = app.state::<StartupProgress>().inner().clone();
+            progress.attach(app.handle());
             let state_clone = state.inner().clone();
+            let schemas = app.state::<SchemaRegistry>().inner().clone();
+            let plugin_workspaces = app.state::<WorkspaceRegistry>().inner().clone();
+            let plugin_hosts = app.state::<HostRegistry>().inner().clone();
+            let plugin_permissions = app.state::<PermissionStore>().inner().clone();
+            let plugin_settings = app.state::<SettingsStore>().inner().clone();
             tauri::async_runtime::spawn(async move {
                 log::info!("Starting IPC Manager...");
-                match state_clone.start().await {
-                    Ok(()) => log::info!("IPC Manager started successfully"),
-                    Err(e) => log::error!("Failed to start IPC Manager: {e}"),
+                progress.start(StartupStage::SpawningHost, None);
+                if let Err(e) = state_clone.start().await {
+                    log::error!("Failed to start IPC Manager: {e}");
+                    let message = match e.suggestion() {
+                        Some(suggestion) => format!("{e}. {suggestion}"),
+                        None => e.to_string(),
+                    };
+                    progress.fail(StartupStage::SpawningHost, message);
+                    return;
                 }
+                log::info!("IPC Manager started successfully");
+                let pid = state_clone.stats().await.subprocess_pid;
+                progress.finish(StartupStage::SpawningHost, pid.map(|pid| format!("PID {pid}")));
+
+                // The host answers once its protocol loop is running
+                progress.start(StartupStage::HostReady, None);
+                if let Err(e) = state_clone.call("ping", serde_json::json!({})).await {
+                    progress.fail(StartupStage::HostReady, e.to_string());
+                    return;
+                }
+                progress.finish(StartupStage::HostReady, None);
+
+                match schemas.load_from_host(&state_clone).await {
+                    Ok(count) => log::info!("Plugin host provided schemas for {count} method(s)"),
+                    Err(e) => log::warn!("{e}"),
+                }
+
+                // The host discovers plugins while starting; report what it found
+                match state_clone.call("plugin/list", serde_json::json!({})).await {
+                    Ok(plugins) => {
+                        let count = plugins.as_array().map_or(0, Vec::len);
+                        progress.finish(
+                            StartupStage::PluginScanComplete,
+                            Some(format!("{count} plugin(s) found")),
+                        );
+                    }
+                    Err(e) => progress.fail(StartupStage::PluginScanComplete, e.to_string()),
+                }
+
+                // Plugins the user wants loaded at startup, dependencies first
+                commands::load_startup_plugins(
+                    &plugin_workspaces,
+                    &plugin_hosts,
+                    &plugin_permissions,
+                    &plugin_settings,
+                )
+                .await;
             });
 
+            // Run scheduled plugin invocations against the registered workspaces
+            let scheduler = app.state::<Scheduler>().inner().clone();
+            let workspaces = app.state::<WorkspaceRegistry>().inner().clone();
+            tauri::async_runtime::spawn(scheduler.run(workspaces));
+
+            // Keep OAuth access tokens fresh
+            let oauth = app.state::<OAuthManager>().inner().clone();
+            tauri::async_runtime::spawn(oauth.run());
+
+            // Route appfactory:// links, including one the app was launched with
+            let link_handle = app.handle();
+            if let Err(e) = tauri_plugin_deep_link::register(deep_link::SCHEME, move |link| {
+                deep_link::handle(&link_handle, &link);
+            }) {
+                log::warn!("Failed to register the {}:// scheme: {e}", deep_link::SCHEME);
+            }
+            if let Some(link) = deep_link::launch_link() {
+                deep_link::handle(&app.handle(), &link);
+            }
+
             Ok(())
         })
         .on_window_event(|event| {
-            if let tauri::WindowEvent::CloseRequested { .. } = event.event() {
-                log::info!("Window close requested, shutting down...");
+            // Other windows (e.g. the IPC console) just close
+            if event.window().label() != "main" {
+                return;
+            }
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event.event() {
+                // Keep the window open until plugin hosts have shut down;
+                // the shutdown sequence exits the app when it is done
+                api.prevent_close();
+                let app = event.window().app_handle();
+                let shutdown = app.state::<ShutdownCoordinator>().inner().clone();
+                if shutdown.begin() {
+                    log::info!("Window close requested, shutting down...");
+                    tauri::async_runtime::spawn(shutdown.run(app));
+                }
             }
         })
-        .run(tauri::generate_context!())
+        .run(context)
         .expect("error while running tauri application");
 }
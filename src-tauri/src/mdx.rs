@@ -0,0 +1,322 @@
+//! src-tauri/src/mdx.rs
+//! ====================
+//! MDX (Markdown with JSX) to TSX conversion for `compile_mdx`.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The document is turned into a TSX module whose default export renders
+//! the content, and that module goes through the regular SWC pipeline:
+//!
+//! - Top-level `import` / `export` blocks are hoisted as-is.
+//! - Blocks starting with a capitalized element (`<Chart ... />`) or a
+//!   fragment are passed through as JSX.
+//! - Everything else is parsed as Markdown (with GFM tables, task lists
+//!   and strikethrough) and emitted as JSX elements. Inline `{expressions}`
+//!   in prose are evaluated; other text is emitted as string literals, so
+//!   it needs no escaping.
+//!
+//! Inline HTML is passed through and must therefore be valid JSX
+//! (`<br />`, `className`), as in MDX.
+//!
+//! Usage:
+//!     ```rust
+//!     let tsx = mdx::to_tsx("# Hello\n\n<Counter start={3} />\n");
+//!     let js = compile_tsx_internal(&tsx, &CompileOptions::default())?;
+//!     ```
+
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag};
+use std::fmt::Write;
+
+// ============================================
+// CONVERSION
+// ============================================
+
+/// Convert an MDX document to a TSX module with a default-exported component.
+pub fn to_tsx(source: &str) -> String {
+    let mut esm = Vec::new();
+    let mut body = String::new();
+    let mut markdown = String::new();
+
+    for block in blocks(source) {
+        let first = block.trim_start();
+        if first.starts_with("import ") || first.starts_with("export ") {
+            esm.push(block);
+        } else if is_jsx_block(first) {
+            render_markdown(&std::mem::take(&mut markdown), &mut body);
+            body.push_str(&block);
+            body.push('\n');
+        } else {
+            markdown.push_str(&block);
+            markdown.push_str("\n\n");
+        }
+    }
+    render_markdown(&markdown, &mut body);
+
+    let mut tsx = String::new();
+    for block in esm {
+        tsx.push_str(&block);
+        tsx.push('\n');
+    }
+    let _ = write!(
+        tsx,
+        "\nexport default function MDXContent() {{\n    return (\n<>\n{body}</>\n    );\n}}\n"
+    );
+    tsx
+}
+
+/// Split a document into blank-line separated blocks, keeping fenced code
+/// blocks (which may contain blank lines) whole.
+fn blocks(source: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut fence: Option<&str> = None;
+
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        match fence {
+            Some(marker) if trimmed.starts_with(marker) => fence = None,
+            Some(_) => {}
+            None if trimmed.starts_with("```") => fence = Some("```"),
+            None if trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None if trimmed.is_empty() => {
+                if !current.is_empty() {
+                    blocks.push(current.join("\n"));
+                    current.clear();
+                }
+                continue;
+            }
+            None => {}
+        }
+        current.push(line);
+    }
+    if !current.is_empty() {
+        blocks.push(current.join("\n"));
+    }
+    blocks
+}
+
+/// Whether a block is raw JSX (a component element or a fragment).
+fn is_jsx_block(block: &str) -> bool {
+    let mut chars = block.chars();
+    chars.next() == Some('<')
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_uppercase() || c == '>')
+}
+
+/// Render Markdown as JSX, appending to `out`.
+fn render_markdown(markdown: &str, out: &mut String) {
+    if markdown.trim().is_empty() {
+        return;
+    }
+
+    let options = Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS;
+    // Closing markup for each open tag
+    let mut closing: Vec<String> = Vec::new();
+    let mut in_code = false;
+    let mut in_table_head = false;
+    // (src, title, alt) while inside an image, and the nesting within its alt text
+    let mut image: Option<(String, String, String)> = None;
+    let mut image_depth = 0usize;
+
+    for event in Parser::new_ext(markdown, options) {
+        if let Some((_, _, alt)) = image.as_mut() {
+            match event {
+                Event::Text(text) | Event::Code(text) => {
+                    alt.push_str(&text);
+                    continue;
+                }
+                Event::Start(_) => {
+                    image_depth += 1;
+                    continue;
+                }
+                Event::End(_) if image_depth > 0 => {
+                    image_depth -= 1;
+                    continue;
+                }
+                Event::End(_) => {
+                    let (src, title, alt) = image.take().unwrap_or_default();
+                    let _ = write!(
+                        out,
+                        "<img src={} alt={}{} />",
+                        literal(&src),
+                        literal(&alt),
+                        attribute("title", &title)
+                    );
+                    continue;
+                }
+                _ => continue,
+            }
+        }
+
+        match event {
+            Event::Start(tag) => {
+                let (open, close) = match tag {
+                    Tag::Paragraph => ("<p>".to_string(), "</p>".to_string()),
+                    Tag::Heading { level, .. } => (format!("<{level}>"), format!("</{level}>")),
+                    Tag::BlockQuote(..) => ("<blockquote>".to_string(), "</blockquote>".to_string()),
+                    Tag::CodeBlock(kind) => {
+                        in_code = true;
+                        let class = match kind {
+                            CodeBlockKind::Fenced(info) => info
+                                .split_whitespace()
+                                .next()
+                                .map(|lang| attribute("className", &format!("language-{lang}")))
+                                .unwrap_or_default(),
+                            CodeBlockKind::Indented => String::new(),
+                        };
+                        (format!("<pre><code{class}>"), "</code></pre>".to_string())
+                    }
+                    Tag::List(Some(start)) => (
+                        format!("<ol start={{{start}}}>"),
+                        "</ol>".to_string(),
+                    ),
+                    Tag::List(None) => ("<ul>".to_string(), "</ul>".to_string()),
+                    Tag::Item => ("<li>".to_string(), "</li>".to_string()),
+                    Tag::Table(_) => ("<table>".to_string(), "</tbody></table>".to_string()),
+                    Tag::TableHead => {
+                        in_table_head = true;
+                        ("<thead><tr>".to_string(), "</tr></thead><tbody>".to_string())
+                    }
+                    Tag::TableRow => ("<tr>".to_string(), "</tr>".to_string()),
+                    Tag::TableCell if in_table_head => ("<th>".to_string(), "</th>".to_string()),
+                    Tag::TableCell => ("<td>".to_string(), "</td>".to_string()),
+                    Tag::Emphasis => ("<em>".to_string(), "</em>".to_string()),
+                    Tag::Strong => ("<strong>".to_string(), "</strong>".to_string()),
+                    Tag::Strikethrough => ("<del>".to_string(), "</del>".to_string()),
+                    Tag::Link { dest_url, title, .. } => (
+                        format!("<a href={}{}>", literal(&dest_url), attribute("title", &title)),
+                        "</a>".to_string(),
+                    ),
+                    Tag::Image { dest_url, title, .. } => {
+                        image = Some((dest_url.to_string(), title.to_string(), String::new()));
+                        continue;
+                    }
+                    // HTML blocks, footnotes, metadata, ...: content only
+                    _ => (String::new(), String::new()),
+                };
+                out.push_str(&open);
+                closing.push(close);
+            }
+            Event::End(_) => {
+                in_code = false;
+                if closing.last().is_some_and(|c| c.starts_with("</tr></thead>")) {
+                    in_table_head = false;
+                }
+                out.push_str(&closing.pop().unwrap_or_default());
+                out.push('\n');
+            }
+            Event::Text(text) if in_code => {
+                let _ = write!(out, "{{{}}}", literal(&text));
+            }
+            Event::Text(text) => push_text(&text, out),
+            Event::Code(code) => {
+                let _ = write!(out, "<code>{{{}}}</code>", literal(&code));
+            }
+            Event::Html(html) | Event::InlineHtml(html) => out.push_str(&html),
+            Event::SoftBreak => out.push_str("{\"\\n\"}"),
+            Event::HardBreak => out.push_str("<br />"),
+            Event::Rule => out.push_str("<hr />\n"),
+            Event::TaskListMarker(checked) => {
+                let _ = write!(out, "<input type=\"checkbox\" checked={{{checked}}} readOnly />");
+            }
+            Event::FootnoteReference(name) => {
+                let _ = write!(out, "<sup>{{{}}}</sup>", literal(&name));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Emit prose, evaluating balanced `{expression}`s and quoting the rest.
+fn push_text(text: &str, out: &mut String) {
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = matching_brace(&rest[start..]) else {
+            break;
+        };
+        if start > 0 {
+            let _ = write!(out, "{{{}}}", literal(&rest[..start]));
+        }
+        out.push_str(&rest[start..=start + end]);
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        let _ = write!(out, "{{{}}}", literal(rest));
+    }
+}
+
+/// Byte offset of the brace closing the one at the start of `text`.
+fn matching_brace(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A JavaScript string literal for `value`.
+fn literal(value: &str) -> String {
+    serde_json::Value::String(value.to_string()).to_string()
+}
+
+/// A ` name={"value"}` attribute, or nothing when `value` is empty.
+fn attribute(name: &str, value: &str) -> String {
+    if value.is_empty() {
+        String::new()
+    } else {
+        format!(" {name}={{{}}}", literal(value))
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_tsx() {
+        let source = "import { Chart } from './Chart';\n\n# Sales {year}\n\nSome *text* with `code`.\n\n<Chart data={[1, 2]} />\n\n```js\nconst a = 1;\n\nconst b = 2;\n```\n";
+        let tsx = to_tsx(source);
+
+        assert!(tsx.starts_with("import { Chart } from './Chart';\n"));
+        assert!(tsx.contains("<h1>{\"Sales \"}{year}</h1>"));
+        assert!(tsx.contains("<em>{\"text\"}</em>"));
+        assert!(tsx.contains("<code>{\"code\"}</code>"));
+        assert!(tsx.contains("<Chart data={[1, 2]} />"));
+        assert!(tsx.contains("className={\"language-js\"}"));
+        assert!(tsx.contains("const a = 1;") && tsx.contains("const b = 2;"));
+        assert!(tsx.contains("export default function MDXContent()"));
+
+        let compiled = crate::commands::compiler::compile_tsx_internal(&tsx, &Default::default());
+        assert!(compiled.is_ok(), "Generated TSX failed to compile: {tsx}");
+    }
+
+    #[test]
+    fn test_blocks_keep_fences_whole() {
+        let blocks = blocks("a\nb\n\n```\n<X />\n\n```\n\n\nc");
+        assert_eq!(blocks, ["a\nb", "```\n<X />\n\n```", "c"]);
+        assert!(is_jsx_block("<Card>"));
+        assert!(is_jsx_block("<>"));
+        assert!(!is_jsx_block("<div>"));
+    }
+
+    #[test]
+    fn test_push_text() {
+        let mut out = String::new();
+        push_text("a {b} c {unclosed", &mut out);
+        assert_eq!(out, "{\"a \"}{b}{\" c {unclosed\"}");
+    }
+}
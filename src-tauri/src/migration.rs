@@ -0,0 +1,200 @@
+//! src-tauri/src/migration.rs
+//! ==========================
+//! Carrying plugin state across swaps and upgrades.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! `plugin_swap` and `plugin_update` replace a loaded plugin. Before the
+//! old plugin goes away its state is exported (`plugin/export_state`,
+//! the plugin's optional `capture_state()` hook). Once the new plugin is
+//! loaded it gets that state together with the old and new versions
+//! (`plugin/migrate_state`, the optional `migrate_state(old_version,
+//! new_version, state)` hook).
+//!
+//! If the migration fails, the commands roll back: the old plugin is
+//! loaded again with the params of its last load (for an update, from the
+//! kept previous version, see `crate::packages::PackageBackup`) and gets
+//! its exported state back (`plugin/restore_state`, the optional
+//! `restore_state(state)` hook). Plugins without the hooks are migrated
+//! trivially.
+//!
+//! Usage:
+//!     ```rust
+//!     let exported = migration::export_state(&state, "tts_kokoro").await?;
+//!     // ... replace the plugin ...
+//!     if let Err(e) = migration::migrate_state(&state, "tts_kokoro", &exported).await {
+//!         // ... put the old plugin back ...
+//!         migration::restore_state(&state, &exported).await?;
+//!     }
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::ipc::manager::IpcManagerState;
+use crate::ipc::IpcError;
+use crate::packages::InstalledPlugin;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// State exported by a plugin before it was replaced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedState {
+    /// Plugin that exported the state
+    pub plugin: String,
+    /// Its version (from its manifest)
+    pub version: Option<String>,
+    /// The state (null if the plugin has no `capture_state` hook)
+    pub state: Value,
+}
+
+/// Outcome of a successful migration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationOutcome {
+    /// Version of the replaced plugin
+    pub old_version: Option<String>,
+    /// Version of the new plugin
+    pub new_version: Option<String>,
+    /// Whether the new plugin has a `migrate_state` hook that took the state
+    pub migrated: bool,
+}
+
+/// A plugin updated by `plugin_update`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginUpdate {
+    /// The installed new version
+    #[serde(flatten)]
+    pub installed: InstalledPlugin,
+    /// How its state was migrated (None if it was not loaded)
+    pub migration: Option<MigrationOutcome>,
+}
+
+// ============================================
+// HOST CALLS
+// ============================================
+
+/// Export a loaded plugin's state.
+pub async fn export_state(
+    state: &IpcManagerState,
+    plugin: &str,
+) -> Result<ExportedState, IpcError> {
+    let result = state
+        .call("plugin/export_state", json!({ "name": plugin }))
+        .await?;
+    Ok(serde_json::from_value(result)?)
+}
+
+/// Hand exported state to the plugin `plugin` that replaced its exporter.
+///
+/// # Errors
+///
+/// Whatever the plugin's `migrate_state` hook raised; the caller rolls
+/// back.
+pub async fn migrate_state(
+    state: &IpcManagerState,
+    plugin: &str,
+    exported: &ExportedState,
+) -> Result<MigrationOutcome, IpcError> {
+    let params = json!({
+        "name": plugin,
+        "old_version": exported.version,
+        "state": exported.state,
+    });
+    let result = state.call("plugin/migrate_state", params).await?;
+    Ok(serde_json::from_value(result)?)
+}
+
+/// Give a plugin that was put back after a failed migration its exported
+/// state again.
+///
+/// # Returns
+///
+/// Whether the plugin has a `restore_state` hook.
+pub async fn restore_state(
+    state: &IpcManagerState,
+    exported: &ExportedState,
+) -> Result<bool, IpcError> {
+    let params = json!({ "name": exported.plugin, "state": exported.state });
+    let result = state.call("plugin/restore_state", params).await?;
+    Ok(result
+        .get("restored")
+        .and_then(Value::as_bool)
+        .unwrap_or(false))
+}
+
+// ============================================
+// ROLLBACK
+// ============================================
+
+/// Params of the `plugin/swap` that puts `old` back in place of `current`.
+///
+/// `old` is loaded the way it was last loaded (`load`, its recorded
+/// `plugin/load` params), so it gets its config, permissions, and data
+/// directory back without asking the user again.
+pub fn swap_back_params(current: &str, old: &str, load: Option<&Value>) -> Value {
+    let mut params = json!({ "old": current, "new": old });
+    if let Some(load) = load {
+        for key in ["config", "permissions", "data_dir"] {
+            if let Some(value) = load.get(key) {
+                params[key] = value.clone();
+            }
+        }
+    }
+    params
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_swap_back_params() {
+        let load = json!({
+            "name": "tts_old",
+            "config": { "voice": "af_bella" },
+            "permissions": ["network"],
+            "data_dir": "/data/plugin_data/tts_old",
+        });
+        assert_eq!(
+            swap_back_params("tts_new", "tts_old", Some(&load)),
+            json!({
+                "old": "tts_new",
+                "new": "tts_old",
+                "config": { "voice": "af_bella" },
+                "permissions": ["network"],
+                "data_dir": "/data/plugin_data/tts_old",
+            })
+        );
+        assert_eq!(
+            swap_back_params("tts_new", "tts_old", None),
+            json!({ "old": "tts_new", "new": "tts_old" })
+        );
+    }
+
+    #[test]
+    fn test_parse_host_results() {
+        let exported: ExportedState = serde_json::from_value(json!({
+            "plugin": "tts_kokoro",
+            "version": "1.0.0",
+            "state": { "cache": [1, 2] },
+        }))
+        .unwrap();
+        assert_eq!(exported.version.as_deref(), Some("1.0.0"));
+
+        let outcome: MigrationOutcome = serde_json::from_value(json!({
+            "plugin": "tts_kokoro",
+            "old_version": "1.0.0",
+            "new_version": "1.1.0",
+            "migrated": true,
+        }))
+        .unwrap();
+        assert!(outcome.migrated);
+        assert_eq!(outcome.new_version.as_deref(), Some("1.1.0"));
+    }
+}
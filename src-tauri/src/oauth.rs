@@ -0,0 +1,787 @@
+//! src-tauri/src/oauth.rs
+//! ======================
+//! OAuth 2.0 connections for services without static API keys.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A provider is registered with its authorization and token endpoints and
+//! the app's client id. Connecting runs the authorization code flow with
+//! PKCE (RFC 7636) and a loopback redirect (RFC 8252):
+//! 1. a listener is bound to `127.0.0.1` on a free port,
+//! 2. the authorization URL is opened in the system browser,
+//! 3. the redirect to `http://127.0.0.1:<port>/callback` delivers the code,
+//! 4. the code is exchanged at the token endpoint.
+//!
+//! Tokens and client secrets are encrypted with the `KeyVault` and
+//! persisted as JSON (`oauth.json`) in the app config directory. A
+//! background loop refreshes access tokens shortly before they expire, so
+//! the token handed to plugin hosts (`APP_FACTORY_OAUTH_<SERVICE>`) stays
+//! valid. In offline mode, connecting and refreshing fail before any
+//! request unless the token endpoint is on this machine.
+//!
+//! Usage:
+//!     ```rust
+//!     let oauth = OAuthManager::load(config_dir, vault.clone());
+//!     oauth.add_provider(provider)?;
+//!     oauth.connect("drive", |url| open_browser(url)).await?;
+//!     tauri::async_runtime::spawn(oauth.clone().run());
+//!     ```
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::offline;
+use crate::proxy;
+use crate::vault::KeyVault;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// File name of the OAuth store inside the app config directory
+const OAUTH_FILE: &str = "oauth.json";
+
+/// Prefix of the token variables passed to plugin hosts
+pub const PLUGIN_TOKEN_PREFIX: &str = "APP_FACTORY_OAUTH_";
+
+/// How long to wait for the browser redirect
+const CALLBACK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Tokens expiring within this window are refreshed
+const REFRESH_MARGIN_SECS: i64 = 300;
+
+/// How often the background loop checks for expiring tokens
+const REFRESH_TICK_SECS: u64 = 60;
+
+/// Token endpoint request timeout
+const TOKEN_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Page shown in the browser after the redirect
+const CALLBACK_PAGE: &str = "<!doctype html><html><body style=\"font-family:sans-serif\">\
+    <h3>App Factory</h3><p>Authorization complete. You can close this window.</p>\
+    </body></html>";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// An OAuth provider registration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OAuthProvider {
+    /// Service id the tokens are stored for (lowercase letters and digits)
+    pub id: String,
+    /// Display name
+    #[serde(default)]
+    pub name: String,
+    /// Authorization endpoint
+    pub auth_url: String,
+    /// Token endpoint
+    pub token_url: String,
+    /// Client id of the app registration
+    pub client_id: String,
+    /// Client secret, if the registration has one (encrypted at rest)
+    #[serde(default)]
+    pub client_secret: Option<String>,
+    /// Requested scopes
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Extra authorization URL parameters (e.g. `access_type=offline`)
+    #[serde(default)]
+    pub extra_params: BTreeMap<String, String>,
+}
+
+/// Tokens of a connected service (values encrypted at rest).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StoredToken {
+    access_token: String,
+    #[serde(default)]
+    refresh_token: Option<String>,
+    #[serde(default)]
+    expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    scope: Option<String>,
+    connected_at: DateTime<Utc>,
+}
+
+/// On-disk layout of `oauth.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct OAuthFile {
+    #[serde(default)]
+    providers: Vec<OAuthProvider>,
+    #[serde(default)]
+    tokens: BTreeMap<String, StoredToken>,
+}
+
+/// A provider and its connection state, as shown in the UI.
+#[derive(Debug, Clone, Serialize)]
+pub struct OAuthStatus {
+    /// Service id
+    pub id: String,
+    /// Display name
+    pub name: String,
+    /// Authorization endpoint
+    pub auth_url: String,
+    /// Token endpoint
+    pub token_url: String,
+    /// Client id
+    pub client_id: String,
+    /// Whether a client secret is configured
+    pub has_client_secret: bool,
+    /// Requested scopes
+    pub scopes: Vec<String>,
+    /// Whether tokens are stored
+    pub connected: bool,
+    /// Whether the stored tokens include a refresh token
+    pub refreshable: bool,
+    /// Access token expiry (RFC 3339)
+    pub expires_at: Option<String>,
+    /// Scopes granted by the provider
+    pub granted_scope: Option<String>,
+    /// When the service was connected (RFC 3339)
+    pub connected_at: Option<String>,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Unpadded base64url encoding (RFC 4648 section 5).
+fn base64_url(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..=chunk.len() {
+            out.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 63] as char);
+        }
+    }
+    out
+}
+
+/// PKCE S256 code challenge for a verifier.
+fn code_challenge(verifier: &str) -> String {
+    base64_url(&Sha256::digest(verifier.as_bytes()))
+}
+
+/// Random PKCE code verifier (64 hex characters).
+fn code_verifier() -> String {
+    format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    )
+}
+
+/// Check a service id (same rules as the service catalog).
+fn check_id(id: &str) -> Result<(), String> {
+    if id.is_empty()
+        || id.len() > 32
+        || !id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err(format!(
+            "Invalid service id '{id}' (use lowercase letters and digits)"
+        ));
+    }
+    Ok(())
+}
+
+/// Check that an endpoint is HTTPS (plain HTTP only for localhost).
+fn check_endpoint(url: &str, what: &str) -> Result<(), String> {
+    let parsed = reqwest::Url::parse(url).map_err(|e| format!("Invalid {what} '{url}': {e}"))?;
+    let local = matches!(parsed.host_str(), Some("localhost" | "127.0.0.1"));
+    if parsed.scheme() == "https" || (parsed.scheme() == "http" && local) {
+        Ok(())
+    } else {
+        Err(format!("{what} must use https: {url}"))
+    }
+}
+
+/// Parse the request line of a loopback redirect.
+///
+/// # Returns
+///
+/// * `None` - Not a request for `/callback` (e.g. `/favicon.ico`)
+/// * `Some(Ok(code))` - The authorization code
+/// * `Some(Err(String))` - The provider reported an error or the state differs
+fn parse_callback(request_line: &str, state: &str) -> Option<Result<String, String>> {
+    let target = request_line.split_whitespace().nth(1)?;
+    let url = reqwest::Url::parse(&format!("http://127.0.0.1{target}")).ok()?;
+    if url.path() != "/callback" {
+        return None;
+    }
+
+    let params: BTreeMap<String, String> = url.query_pairs().into_owned().collect();
+    if let Some(error) = params.get("error") {
+        let description = params
+            .get("error_description")
+            .map(|d| format!(": {d}"))
+            .unwrap_or_default();
+        return Some(Err(format!("Authorization denied ({error}{description})")));
+    }
+    if params.get("state").map(String::as_str) != Some(state) {
+        return Some(Err("Authorization state mismatch".to_string()));
+    }
+    Some(
+        params
+            .get("code")
+            .cloned()
+            .ok_or_else(|| "Redirect carried no authorization code".to_string()),
+    )
+}
+
+/// Accept loopback connections until the redirect with the code arrives.
+async fn wait_for_code(listener: TcpListener, state: &str) -> Result<String, String> {
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .await
+            .map_err(|e| format!("Redirect listener failed: {e}"))?;
+
+        let mut buffer = vec![0u8; 8192];
+        let mut read = 0;
+        while read < buffer.len() {
+            match stream.read(&mut buffer[read..]).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => read += n,
+            }
+            if buffer[..read].windows(4).any(|w| w == b"\r\n\r\n") {
+                break;
+            }
+        }
+        let request = String::from_utf8_lossy(&buffer[..read]);
+        let request_line = request.lines().next().unwrap_or_default();
+
+        let Some(outcome) = parse_callback(request_line, state) else {
+            let _ = stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n")
+                .await;
+            continue;
+        };
+
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{CALLBACK_PAGE}",
+            CALLBACK_PAGE.len()
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+        return outcome;
+    }
+}
+
+// ============================================
+// OAUTH MANAGER
+// ============================================
+
+/// OAuth provider registrations and stored tokens.
+#[derive(Clone)]
+pub struct OAuthManager {
+    /// Providers and tokens
+    store: Arc<RwLock<OAuthFile>>,
+
+    /// Encrypts tokens and client secrets at rest
+    vault: KeyVault,
+
+    /// HTTP client for the token endpoints
+    client: reqwest::Client,
+
+    /// Store file path (None disables persistence)
+    path: Option<PathBuf>,
+}
+
+impl OAuthManager {
+    /// Load providers and tokens from the app config directory.
+    pub fn load(config_dir: Option<PathBuf>, vault: KeyVault) -> Self {
+        let path = config_dir.map(|dir| dir.join(OAUTH_FILE));
+        let store = path.as_deref().map(read_store).unwrap_or_default();
+
+        Self {
+            store: Arc::new(RwLock::new(store)),
+            vault,
+            client: proxy::client(),
+            path,
+        }
+    }
+
+    /// All providers with their connection state.
+    pub fn list(&self) -> Vec<OAuthStatus> {
+        let store = self.store.read().unwrap();
+        store
+            .providers
+            .iter()
+            .map(|provider| {
+                let token = store.tokens.get(&provider.id);
+                OAuthStatus {
+                    id: provider.id.clone(),
+                    name: provider.name.clone(),
+                    auth_url: provider.auth_url.clone(),
+                    token_url: provider.token_url.clone(),
+                    client_id: provider.client_id.clone(),
+                    has_client_secret: provider.client_secret.is_some(),
+                    scopes: provider.scopes.clone(),
+                    connected: token.is_some(),
+                    refreshable: token.is_some_and(|t| t.refresh_token.is_some()),
+                    expires_at: token.and_then(|t| t.expires_at).map(|t| t.to_rfc3339()),
+                    granted_scope: token.and_then(|t| t.scope.clone()),
+                    connected_at: token.map(|t| t.connected_at.to_rfc3339()),
+                }
+            })
+            .collect()
+    }
+
+    /// Status of one provider.
+    pub fn status(&self, id: &str) -> Option<OAuthStatus> {
+        self.list().into_iter().find(|s| s.id == id)
+    }
+
+    /// Register a provider (replacing one with the same id).
+    pub fn add_provider(&self, provider: OAuthProvider) -> Result<OAuthStatus, String> {
+        let id = provider.id.trim().to_lowercase();
+        check_id(&id)?;
+        check_endpoint(&provider.auth_url, "Authorization URL")?;
+        check_endpoint(&provider.token_url, "Token URL")?;
+        if provider.client_id.trim().is_empty() {
+            return Err("Client id is required".to_string());
+        }
+
+        let client_secret = match provider.client_secret.as_deref().map(str::trim) {
+            Some(secret) if !secret.is_empty() => Some(self.vault.encrypt(secret)?),
+            _ => None,
+        };
+        let provider = OAuthProvider {
+            name: if provider.name.trim().is_empty() {
+                id.clone()
+            } else {
+                provider.name.trim().to_string()
+            },
+            id: id.clone(),
+            client_id: provider.client_id.trim().to_string(),
+            client_secret,
+            ..provider
+        };
+
+        {
+            let mut store = self.store.write().unwrap();
+            store.providers.retain(|p| p.id != id);
+            store.providers.push(provider);
+        }
+        self.persist();
+
+        log::info!("OAuth provider registered: {id}");
+        self.status(&id)
+            .ok_or_else(|| format!("Unknown OAuth provider: {id}"))
+    }
+
+    /// Remove a provider and its tokens.
+    pub fn remove_provider(&self, id: &str) -> Result<(), String> {
+        {
+            let mut store = self.store.write().unwrap();
+            let before = store.providers.len();
+            store.providers.retain(|p| p.id != id);
+            if store.providers.len() == before {
+                return Err(format!("Unknown OAuth provider: {id}"));
+            }
+            store.tokens.remove(id);
+        }
+        self.persist();
+
+        log::info!("OAuth provider removed: {id}");
+        Ok(())
+    }
+
+    /// Forget the tokens of a provider.
+    pub fn disconnect(&self, id: &str) -> Result<(), String> {
+        if self.store.write().unwrap().tokens.remove(id).is_none() {
+            return Err(format!("{id} is not connected"));
+        }
+        self.persist();
+
+        log::info!("OAuth disconnected: {id}");
+        Ok(())
+    }
+
+    /// Run the authorization code flow for a provider.
+    ///
+    /// `open` receives the authorization URL and opens it in the browser.
+    /// Resolves once the redirect arrives and the code is exchanged, or
+    /// fails after `CALLBACK_TIMEOUT`.
+    pub async fn connect<F>(&self, id: &str, open: F) -> Result<OAuthStatus, String>
+    where
+        F: FnOnce(&str) -> Result<(), String> + Send,
+    {
+        let provider = self.provider(id)?;
+        check_offline(&provider)?;
+
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .map_err(|e| format!("Failed to start redirect listener: {e}"))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to start redirect listener: {e}"))?
+            .port();
+        let redirect_uri = format!("http://127.0.0.1:{port}/callback");
+
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        let verifier = code_verifier();
+        let scope = provider.scopes.join(" ");
+        let mut params = vec![
+            ("response_type", "code".to_string()),
+            ("client_id", provider.client_id.clone()),
+            ("redirect_uri", redirect_uri.clone()),
+            ("state", state.clone()),
+            ("code_challenge", code_challenge(&verifier)),
+            ("code_challenge_method", "S256".to_string()),
+        ];
+        if !scope.is_empty() {
+            params.push(("scope", scope));
+        }
+        for (name, value) in &provider.extra_params {
+            params.push((name.as_str(), value.clone()));
+        }
+        let auth_url = reqwest::Url::parse_with_params(&provider.auth_url, &params)
+            .map_err(|e| format!("Invalid authorization URL: {e}"))?;
+
+        log::info!("OAuth flow started for {id} (redirect on port {port})");
+        open(auth_url.as_str())?;
+
+        let code = tokio::time::timeout(CALLBACK_TIMEOUT, wait_for_code(listener, &state))
+            .await
+            .map_err(|_| "Timed out waiting for the authorization redirect".to_string())??;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code".to_string()),
+            ("code", code),
+            ("redirect_uri", redirect_uri),
+            ("code_verifier", verifier),
+        ];
+        let token = self.request_token(&provider, &mut form, None).await?;
+        self.store_token(id, token);
+
+        log::info!("OAuth connected: {id}");
+        self.status(id)
+            .ok_or_else(|| format!("Unknown OAuth provider: {id}"))
+    }
+
+    /// Exchange the refresh token of a provider for a new access token.
+    pub async fn refresh(&self, id: &str) -> Result<(), String> {
+        let provider = self.provider(id)?;
+        let token = self.token(id)?;
+        let Some(ref stored_refresh) = token.refresh_token else {
+            return Err(format!("{id} has no refresh token; connect again"));
+        };
+        let refresh_token = self.vault.decrypt(stored_refresh)?;
+
+        let mut form = vec![
+            ("grant_type", "refresh_token".to_string()),
+            ("refresh_token", refresh_token),
+        ];
+        let refreshed = self
+            .request_token(&provider, &mut form, Some(token))
+            .await?;
+        self.store_token(id, refreshed);
+
+        log::info!("OAuth token refreshed: {id}");
+        Ok(())
+    }
+
+    /// Unexpired access tokens as plugin host environment variables.
+    pub fn plugin_env(&self) -> Vec<(String, String)> {
+        let tokens = self.store.read().unwrap().tokens.clone();
+        tokens
+            .into_iter()
+            .filter(|(_, token)| !token.expires_at.is_some_and(|at| at <= Utc::now()))
+            .filter_map(
+                |(id, token)| match self.vault.decrypt(&token.access_token) {
+                    Ok(value) => {
+                        Some((format!("{PLUGIN_TOKEN_PREFIX}{}", id.to_uppercase()), value))
+                    }
+                    Err(e) => {
+                        log::warn!("OAuth token for {id} not passed to plugins: {e}");
+                        None
+                    }
+                },
+            )
+            .collect()
+    }
+
+    /// Refresh expiring tokens in the background (runs forever).
+    pub async fn run(self) {
+        let mut interval = tokio::time::interval(Duration::from_secs(REFRESH_TICK_SECS));
+        loop {
+            interval.tick().await;
+
+            let due: Vec<String> = self
+                .store
+                .read()
+                .unwrap()
+                .tokens
+                .iter()
+                .filter(|(_, token)| token.refresh_token.is_some() && needs_refresh(token))
+                .map(|(id, _)| id.clone())
+                .collect();
+            for id in due {
+                // Refreshed once back online rather than warning every tick
+                if self
+                    .provider(&id)
+                    .is_ok_and(|provider| check_offline(&provider).is_err())
+                {
+                    continue;
+                }
+                if let Err(e) = self.refresh(&id).await {
+                    log::warn!("OAuth refresh failed for {id}: {e}");
+                }
+            }
+        }
+    }
+
+    /// Get a provider by id.
+    fn provider(&self, id: &str) -> Result<OAuthProvider, String> {
+        self.store
+            .read()
+            .unwrap()
+            .providers
+            .iter()
+            .find(|p| p.id == id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown OAuth provider: {id}"))
+    }
+
+    /// Get the stored tokens of a provider.
+    fn token(&self, id: &str) -> Result<StoredToken, String> {
+        self.store
+            .read()
+            .unwrap()
+            .tokens
+            .get(id)
+            .cloned()
+            .ok_or_else(|| format!("{id} is not connected"))
+    }
+
+    /// Call the token endpoint and build the tokens to store.
+    ///
+    /// `previous` supplies the refresh token and connection time when the
+    /// response omits them (refresh grants).
+    async fn request_token(
+        &self,
+        provider: &OAuthProvider,
+        form: &mut Vec<(&str, String)>,
+        previous: Option<StoredToken>,
+    ) -> Result<StoredToken, String> {
+        check_offline(provider)?;
+        form.push(("client_id", provider.client_id.clone()));
+        if let Some(ref secret) = provider.client_secret {
+            form.push(("client_secret", self.vault.decrypt(secret)?));
+        }
+
+        let response = self
+            .client
+            .post(&provider.token_url)
+            .header("Accept", "application/json")
+            .form(&*form)
+            .timeout(TOKEN_REQUEST_TIMEOUT)
+            .send()
+            .await
+            .map_err(|e| format!("Token request failed: {e}"))?;
+        let status = response.status();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| format!("Token request failed: {e}"))?;
+        let json: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+        if !status.is_success() {
+            let error = json["error_description"]
+                .as_str()
+                .or_else(|| json["error"].as_str())
+                .unwrap_or("no details");
+            return Err(format!("Token endpoint returned HTTP {status}: {error}"));
+        }
+        let Some(access_token) = json["access_token"].as_str() else {
+            return Err("Token response has no access_token".to_string());
+        };
+
+        let refresh_token = match json["refresh_token"].as_str() {
+            Some(value) => Some(self.vault.encrypt(value)?),
+            None => previous.as_ref().and_then(|p| p.refresh_token.clone()),
+        };
+        Ok(StoredToken {
+            access_token: self.vault.encrypt(access_token)?,
+            refresh_token,
+            expires_at: json["expires_in"]
+                .as_i64()
+                .map(|secs| Utc::now() + chrono::Duration::seconds(secs)),
+            scope: json["scope"]
+                .as_str()
+                .map(str::to_string)
+                .or_else(|| previous.as_ref().and_then(|p| p.scope.clone())),
+            connected_at: previous.map_or_else(Utc::now, |p| p.connected_at),
+        })
+    }
+
+    /// Store the tokens of a provider.
+    fn store_token(&self, id: &str, token: StoredToken) {
+        self.store
+            .write()
+            .unwrap()
+            .tokens
+            .insert(id.to_string(), token);
+        self.persist();
+    }
+
+    /// Write the store to disk (logged on failure).
+    fn persist(&self) {
+        if let Some(ref path) = self.path {
+            if let Err(e) = write_store(path, &self.store.read().unwrap()) {
+                log::warn!("Failed to persist OAuth store: {e}");
+            }
+        }
+    }
+}
+
+/// Whether an access token expires within the refresh margin.
+fn needs_refresh(token: &StoredToken) -> bool {
+    token
+        .expires_at
+        .is_some_and(|at| at - Utc::now() < chrono::Duration::seconds(REFRESH_MARGIN_SECS))
+}
+
+/// Fail if offline mode blocks the provider's token endpoint.
+fn check_offline(provider: &OAuthProvider) -> Result<(), String> {
+    let url =
+        reqwest::Url::parse(&provider.token_url).map_err(|e| format!("Invalid token URL: {e}"))?;
+    offline::check_url(&url)
+}
+
+// ============================================
+// PERSISTENCE
+// ============================================
+
+/// Read the store from disk, returning an empty store if missing or invalid.
+fn read_store(path: &Path) -> OAuthFile {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid OAuth file {path:?}: {e}");
+            OAuthFile::default()
+        }),
+        Err(_) => OAuthFile::default(),
+    }
+}
+
+/// Write the store to disk.
+fn write_store(path: &Path, store: &OAuthFile) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(store)
+        .map_err(|e| format!("Failed to serialize OAuth store: {e}"))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge() {
+        // RFC 7636 appendix B
+        assert_eq!(
+            code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(base64_url(b"f"), "Zg");
+        assert_eq!(base64_url(b"foob"), "Zm9vYg");
+        assert_eq!(code_verifier().len(), 64);
+    }
+
+    #[test]
+    fn test_parse_callback() {
+        let ok = parse_callback("GET /callback?code=abc&state=s1 HTTP/1.1", "s1");
+        assert_eq!(ok.unwrap().unwrap(), "abc");
+
+        let wrong_state = parse_callback("GET /callback?code=abc&state=s2 HTTP/1.1", "s1");
+        assert!(wrong_state.unwrap().is_err());
+
+        let denied = parse_callback("GET /callback?error=access_denied&state=s1 HTTP/1.1", "s1");
+        assert!(denied.unwrap().unwrap_err().contains("access_denied"));
+
+        assert!(parse_callback("GET /favicon.ico HTTP/1.1", "s1").is_none());
+    }
+
+    #[test]
+    fn test_add_provider_encrypts_secret() {
+        let vault = KeyVault::with_key(&[4u8; 32], crate::vault::KeySource::Machine);
+        let oauth = OAuthManager::load(None, vault.clone());
+
+        let provider = OAuthProvider {
+            id: "Drive".to_string(),
+            name: String::new(),
+            auth_url: "https://accounts.example.com/o/oauth2/auth".to_string(),
+            token_url: "https://oauth2.example.com/token".to_string(),
+            client_id: "client-1".to_string(),
+            client_secret: Some("client-secret-value".to_string()),
+            scopes: vec!["drive.readonly".to_string()],
+            extra_params: BTreeMap::new(),
+        };
+        let status = oauth.add_provider(provider.clone()).unwrap();
+        assert_eq!(
+            (status.id.as_str(), status.name.as_str()),
+            ("drive", "drive")
+        );
+        assert!(status.has_client_secret && !status.connected);
+
+        let stored = oauth.provider("drive").unwrap().client_secret.unwrap();
+        assert!(crate::vault::is_encrypted(&stored));
+        assert_eq!(vault.decrypt(&stored).unwrap(), "client-secret-value");
+
+        let insecure = OAuthProvider {
+            token_url: "http://oauth2.example.com/token".to_string(),
+            ..provider
+        };
+        assert!(oauth.add_provider(insecure).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_connect_blocked_offline() {
+        let vault = KeyVault::with_key(&[5u8; 32], crate::vault::KeySource::Machine);
+        let oauth = OAuthManager::load(None, vault);
+        oauth
+            .add_provider(OAuthProvider {
+                id: "drive".to_string(),
+                name: String::new(),
+                auth_url: "https://accounts.example.com/o/oauth2/auth".to_string(),
+                token_url: "https://oauth2.example.com/token".to_string(),
+                client_id: "client-1".to_string(),
+                client_secret: None,
+                scopes: Vec::new(),
+                extra_params: BTreeMap::new(),
+            })
+            .unwrap();
+
+        offline::set_offline_for_test(true);
+        let error = oauth
+            .connect("drive", |_| panic!("browser opened while offline"))
+            .await
+            .unwrap_err();
+        assert!(error.contains("Offline mode"));
+    }
+}
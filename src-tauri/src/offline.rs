@@ -0,0 +1,140 @@
+//! src-tauri/src/offline.rs
+//! ========================
+//! Offline mode: blocks the app's own network requests.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! When the user switches the app offline (`set_offline` command, stored as
+//! `network.offline`), requests that would leave the machine fail at once
+//! with an `OFFLINE` error instead of waiting for a network timeout:
+//!
+//! - `http_fetch` and the plugins' `http/fetch` (see http_fetch.rs)
+//! - downloads into the model cache and assets directory (see downloads.rs)
+//! - LLM requests to hosted providers (see commands/llm.rs)
+//! - API key tests (see commands/secrets.rs)
+//! - OAuth connections and token refreshes (see oauth.rs)
+//! - crash report submission (see crash.rs) and alert webhooks (see alerts.rs)
+//!
+//! Loopback hosts (`localhost`, `127.0.0.1`, `::1`) stay reachable, so a
+//! local Ollama keeps working. Plugin hosts are told too (`APP_FACTORY_OFFLINE`
+//! at spawn, `host/set_offline` when it changes) so plugins that use the
+//! network directly can check `host_rpc.is_offline()` and degrade gracefully.
+//!
+//! Usage:
+//!     ```rust
+//!     offline::set_offline(settings.get().network.offline);
+//!     let url = reqwest::Url::parse("https://example.com/model.onnx")?;
+//!     offline::check_url(&url)?;
+//!     ```
+
+#[cfg(test)]
+use std::cell::Cell;
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether offline mode is on
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+#[cfg(test)]
+thread_local! {
+    /// Offline mode of the current test thread, so tests don't race on `OFFLINE`
+    static TEST_OFFLINE: Cell<Option<bool>> = const { Cell::new(None) };
+}
+
+/// Switch offline mode on or off.
+pub fn set_offline(offline: bool) {
+    OFFLINE.store(offline, Ordering::SeqCst);
+}
+
+/// Whether offline mode is on.
+pub fn is_offline() -> bool {
+    #[cfg(test)]
+    if let Some(offline) = TEST_OFFLINE.with(Cell::get) {
+        return offline;
+    }
+    OFFLINE.load(Ordering::SeqCst)
+}
+
+/// Switch offline mode on or off for the current test thread only.
+#[cfg(test)]
+pub fn set_offline_for_test(offline: bool) {
+    TEST_OFFLINE.with(|cell| cell.set(Some(offline)));
+}
+
+/// Whether `host` is this machine (reachable while offline).
+pub fn is_local_host(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if host.eq_ignore_ascii_case("localhost") || host.to_ascii_lowercase().ends_with(".localhost") {
+        return true;
+    }
+    host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Fail if offline mode blocks a request to `url`.
+pub fn check_url(url: &reqwest::Url) -> Result<(), String> {
+    if is_offline() {
+        check_offline(url)
+    } else {
+        Ok(())
+    }
+}
+
+/// Fail unless `url` is reachable while offline.
+fn check_offline(url: &reqwest::Url) -> Result<(), String> {
+    match url.host_str() {
+        Some(host) if is_local_host(host) => Ok(()),
+        host => Err(format!(
+            "Offline mode is on; not connecting to {}",
+            host.unwrap_or(url.as_str())
+        )),
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_host() {
+        assert!(is_local_host("localhost"));
+        assert!(is_local_host("LOCALHOST"));
+        assert!(is_local_host("ollama.localhost"));
+        assert!(is_local_host("127.0.0.1"));
+        assert!(is_local_host("127.1.2.3"));
+        assert!(is_local_host("[::1]"));
+        assert!(!is_local_host("example.com"));
+        assert!(!is_local_host("localhost.example.com"));
+        assert!(!is_local_host("192.168.1.10"));
+    }
+
+    #[test]
+    fn test_check_offline() {
+        let remote = reqwest::Url::parse("https://api.openai.com/v1/models").unwrap();
+        let local = reqwest::Url::parse("http://localhost:11434/api/chat").unwrap();
+        let ipv6 = reqwest::Url::parse("http://[::1]:8080/").unwrap();
+
+        assert!(check_offline(&remote)
+            .unwrap_err()
+            .contains("api.openai.com"));
+        assert!(check_offline(&local).is_ok());
+        assert!(check_offline(&ipv6).is_ok());
+    }
+
+    #[test]
+    fn test_offline_for_test_thread() {
+        let remote = reqwest::Url::parse("https://api.openai.com/v1/models").unwrap();
+        set_offline_for_test(true);
+        assert!(is_offline());
+        assert!(check_url(&remote).is_err());
+
+        let other = std::thread::spawn(is_offline).join().unwrap();
+        assert_eq!(other, OFFLINE.load(Ordering::SeqCst));
+
+        set_offline_for_test(false);
+        assert!(check_url(&remote).is_ok());
+    }
+}
@@ -0,0 +1,406 @@
+//! src-tauri/src/ollama.rs
+//! =======================
+//! Model pulls into a local Ollama daemon.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A pull streams `POST /api/pull` as NDJSON. Each line carries a status
+//! and, while layers download, `completed`/`total` byte counts. A line with
+//! an `error` field fails the pull; a `success` status completes it. The
+//! daemon keeps partially pulled layers, so a cancelled pull resumes when
+//! it is started again.
+//!
+//! Usage:
+//!     ```rust
+//!     let pulls = OllamaPulls::new();
+//!     let pull = pulls.start(Ollama::from_env(&env_vars), "llama3.2", |event, pull| {
+//!         app.emit_all(&event, pull).ok();
+//!     })?;
+//!     pulls.cancel(&pull.id)?;
+//!     ```
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+
+use crate::providers;
+use crate::providers::ollama::Ollama;
+use crate::proxy;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Minimum time between progress events
+const PROGRESS_EVENT_INTERVAL: Duration = Duration::from_millis(500);
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Pull lifecycle status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PullStatus {
+    /// Downloading and verifying layers
+    Pulling,
+    /// Model is installed
+    Completed,
+    /// Daemon unreachable or reported an error
+    Failed,
+    /// Cancelled by the user (pulled layers kept for resume)
+    Cancelled,
+}
+
+impl PullStatus {
+    /// Check if the pull has finished.
+    pub fn is_finished(self) -> bool {
+        matches!(
+            self,
+            PullStatus::Completed | PullStatus::Failed | PullStatus::Cancelled
+        )
+    }
+}
+
+/// A tracked model pull.
+#[derive(Debug, Clone, Serialize)]
+pub struct OllamaPull {
+    /// Pull id
+    pub id: String,
+    /// Model name (e.g. `llama3.2:3b`)
+    pub model: String,
+    /// Current status
+    pub status: PullStatus,
+    /// Latest status line from the daemon (e.g. `pulling 6a0746a1ec1a`)
+    pub message: String,
+    /// Bytes of the current layer pulled so far
+    pub completed: u64,
+    /// Size of the current layer, if known
+    pub total: Option<u64>,
+    /// Error message (when failed)
+    pub error: Option<String>,
+    /// Start timestamp (RFC 3339)
+    pub started_at: String,
+    /// Finish timestamp (RFC 3339)
+    pub finished_at: Option<String>,
+}
+
+impl OllamaPull {
+    /// Event name for progress updates of this pull.
+    pub fn progress_event(&self) -> String {
+        format!("ollama/pull/{}/progress", self.id)
+    }
+
+    /// Event name emitted once when this pull finishes.
+    pub fn finished_event(&self) -> String {
+        format!("ollama/pull/{}/finished", self.id)
+    }
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Apply one NDJSON progress line to a pull.
+///
+/// # Returns
+///
+/// * `Ok(true)` - The daemon reported success
+/// * `Ok(false)` - More lines follow
+/// * `Err(String)` - The daemon reported an error
+fn apply_progress(pull: &mut OllamaPull, line: &Value) -> Result<bool, String> {
+    if let Some(error) = line["error"].as_str() {
+        return Err(error.to_string());
+    }
+
+    let status = line["status"].as_str().unwrap_or_default();
+    if status != pull.message {
+        // A new layer (or phase) starts from zero
+        pull.completed = 0;
+        pull.total = None;
+        pull.message = status.to_string();
+    }
+    if let Some(total) = line["total"].as_u64() {
+        pull.total = Some(total);
+    }
+    if let Some(completed) = line["completed"].as_u64() {
+        pull.completed = completed;
+    }
+    Ok(status == "success")
+}
+
+// ============================================
+// PULL REGISTRY
+// ============================================
+
+/// Registry of model pulls into local Ollama daemons.
+#[derive(Clone)]
+pub struct OllamaPulls {
+    /// HTTP client
+    client: reqwest::Client,
+
+    /// Pulls keyed by id
+    pulls: Arc<RwLock<HashMap<String, OllamaPull>>>,
+
+    /// Running pull tasks
+    tasks: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+impl OllamaPulls {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self {
+            client: proxy::client(),
+            pulls: Arc::new(RwLock::new(HashMap::new())),
+            tasks: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Start pulling `model` into the daemon of `ollama`.
+    ///
+    /// `on_event(event_name, pull)` is called for throttled progress
+    /// updates and once when the pull finishes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ollama` - Daemon to pull into
+    /// * `model` - Model name, optionally with a tag
+    /// * `on_event` - Event callback
+    pub fn start<F>(&self, ollama: Ollama, model: &str, on_event: F) -> Result<OllamaPull, String>
+    where
+        F: Fn(String, &OllamaPull) + Send + Sync + 'static,
+    {
+        // Model names may carry a namespace (`library/llama3.2`, `hf.co/...`)
+        let model = model.trim();
+        if model.is_empty() || model.contains(char::is_whitespace) {
+            return Err(format!("Invalid model name: {model}"));
+        }
+
+        // Refuse to run two pulls of the same model
+        if self
+            .list()
+            .iter()
+            .any(|p| p.model == model && !p.status.is_finished())
+        {
+            return Err(format!("{model} is already being pulled"));
+        }
+
+        let pull = OllamaPull {
+            id: uuid::Uuid::new_v4().to_string(),
+            model: model.to_string(),
+            status: PullStatus::Pulling,
+            message: String::new(),
+            completed: 0,
+            total: None,
+            error: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            finished_at: None,
+        };
+        self.insert(pull.clone());
+        log::info!("Ollama pull started: {model} from {}", ollama.host());
+
+        let registry = self.clone();
+        let id = pull.id.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let result = registry.transfer(&id, &ollama, &on_event).await;
+
+            let finished = registry.update(&id, |pull| {
+                match result {
+                    Ok(()) => pull.status = PullStatus::Completed,
+                    Err(e) => {
+                        pull.status = PullStatus::Failed;
+                        pull.error = Some(e);
+                    }
+                }
+                pull.finished_at = Some(chrono::Utc::now().to_rfc3339());
+            });
+
+            registry.tasks.lock().unwrap().remove(&id);
+
+            if let Some(pull) = finished {
+                log::info!(
+                    "Ollama pull {} ({}): {:?}",
+                    pull.id,
+                    pull.model,
+                    pull.status
+                );
+                on_event(pull.finished_event(), &pull);
+            }
+        });
+
+        // Only track the task if it has not already finished
+        let mut tasks = self.tasks.lock().unwrap();
+        if self.get(&pull.id).is_some_and(|p| !p.status.is_finished()) {
+            tasks.insert(pull.id.clone(), handle);
+        }
+
+        Ok(pull)
+    }
+
+    /// Stream the pull progress until the daemon reports success.
+    async fn transfer<F>(&self, id: &str, ollama: &Ollama, on_event: &F) -> Result<(), String>
+    where
+        F: Fn(String, &OllamaPull),
+    {
+        let model = self.get(id).map(|p| p.model).unwrap_or_default();
+        let request = providers::with_json(
+            self.client.post(ollama.url("/api/pull")),
+            &json!({ "model": model, "stream": true }),
+        )?;
+
+        let mut response = request
+            .send()
+            .await
+            .map_err(|e| format!("Ollama is not reachable at {}: {e}", ollama.host()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.bytes().await.unwrap_or_default();
+            return Err(format!(
+                "HTTP {status}: {}",
+                providers::error_message(&body)
+            ));
+        }
+
+        let mut buffer = String::new();
+        let mut last_event = Instant::now();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("Pull interrupted: {e}"))?
+        {
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(end) = buffer.find('\n') {
+                let line: String = buffer.drain(..=end).collect();
+                let Ok(line) = serde_json::from_str::<Value>(line.trim()) else {
+                    continue;
+                };
+
+                let mut outcome = Ok(false);
+                let pull = self.update(id, |pull| outcome = apply_progress(pull, &line));
+                if outcome? {
+                    return Ok(());
+                }
+                if last_event.elapsed() >= PROGRESS_EVENT_INTERVAL {
+                    last_event = Instant::now();
+                    if let Some(pull) = pull {
+                        on_event(pull.progress_event(), &pull);
+                    }
+                }
+            }
+        }
+
+        Err("Ollama closed the stream before the pull finished".to_string())
+    }
+
+    /// Get a pull by id.
+    pub fn get(&self, id: &str) -> Option<OllamaPull> {
+        self.pulls.read().unwrap().get(id).cloned()
+    }
+
+    /// List all pulls (most recent first).
+    pub fn list(&self) -> Vec<OllamaPull> {
+        let mut pulls: Vec<OllamaPull> = self.pulls.read().unwrap().values().cloned().collect();
+        pulls.sort_by(|a, b| b.started_at.cmp(&a.started_at));
+        pulls
+    }
+
+    /// Cancel a running pull. Layers pulled so far stay in the daemon.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(OllamaPull)` - The cancelled pull
+    /// * `Err(String)` - Unknown pull or already finished
+    pub fn cancel(&self, id: &str) -> Result<OllamaPull, String> {
+        let pull = self.get(id).ok_or_else(|| format!("Unknown pull: {id}"))?;
+        if pull.status.is_finished() {
+            return Err(format!("Pull {id} already finished ({:?})", pull.status));
+        }
+
+        if let Some(handle) = self.tasks.lock().unwrap().remove(id) {
+            handle.abort();
+        }
+
+        log::info!("Ollama pull cancelled: {id}");
+        self.update(id, |pull| {
+            pull.status = PullStatus::Cancelled;
+            pull.finished_at = Some(chrono::Utc::now().to_rfc3339());
+        })
+        .ok_or_else(|| format!("Unknown pull: {id}"))
+    }
+
+    /// Register a pull.
+    fn insert(&self, pull: OllamaPull) {
+        self.pulls.write().unwrap().insert(pull.id.clone(), pull);
+    }
+
+    /// Apply an edit to an unfinished pull, returning the updated copy.
+    fn update<F>(&self, id: &str, edit: F) -> Option<OllamaPull>
+    where
+        F: FnOnce(&mut OllamaPull),
+    {
+        let mut pulls = self.pulls.write().unwrap();
+        let pull = pulls.get_mut(id)?;
+        if pull.status.is_finished() {
+            return None;
+        }
+        edit(pull);
+        Some(pull.clone())
+    }
+}
+
+impl Default for OllamaPulls {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pull() -> OllamaPull {
+        OllamaPull {
+            id: "p1".to_string(),
+            model: "llama3.2".to_string(),
+            status: PullStatus::Pulling,
+            message: String::new(),
+            completed: 0,
+            total: None,
+            error: None,
+            started_at: String::new(),
+            finished_at: None,
+        }
+    }
+
+    #[test]
+    fn test_apply_progress() {
+        let mut pull = pull();
+        let layer = json!({ "status": "pulling 6a0746", "total": 2000, "completed": 500 });
+        assert!(!apply_progress(&mut pull, &layer).unwrap());
+        assert_eq!((pull.completed, pull.total), (500, Some(2000)));
+
+        // The next phase resets the byte counts
+        let verify = json!({ "status": "verifying sha256 digest" });
+        assert!(!apply_progress(&mut pull, &verify).unwrap());
+        assert_eq!((pull.completed, pull.total), (0, None));
+
+        assert!(apply_progress(&mut pull, &json!({ "status": "success" })).unwrap());
+        let error = json!({ "error": "pull model manifest: file does not exist" });
+        assert!(apply_progress(&mut pull, &error).is_err());
+    }
+
+    #[test]
+    fn test_start_rejects_bad_model() {
+        let pulls = OllamaPulls::new();
+        let ollama = Ollama::from_env(&HashMap::new());
+        assert!(pulls.start(ollama, "llama 3", |_, _| {}).is_err());
+        assert!(pulls.list().is_empty());
+    }
+}
@@ -0,0 +1,684 @@
+//! src-tauri/src/packages.rs
+//! =========================
+//! Signature verification and installation of plugin packages.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A plugin package is a plugin directory (`manifest.json`, code, assets).
+//! Publishers sign it with minisign (Ed25519, prehashed): the detached
+//! signature `plugin.minisig` in the package root covers the package digest,
+//! one line per file of the form `<sha256 hex>  <path>`, with `/`-separated
+//! paths relative to the root sorted bytewise. The signature file,
+//! `__pycache__` directories and `.pyc` files are left out, so the digest is
+//! what this prints:
+//!
+//! ```text
+//! cd my_plugin && find . -type f ! -name plugin.minisig ! -name '*.pyc' \
+//!     ! -path '*/__pycache__/*' | sed 's|^\./||' | LC_ALL=C sort | xargs sha256sum
+//! ```
+//!
+//! Signing it: `minisign -S -s publisher.key -m digest.txt -x my_plugin/plugin.minisig`.
+//!
+//! Trusted publisher keys are the `plugins.trusted_keys` setting (the base64
+//! line of a `minisign.pub`). `plugin_install` and `plugin_load` accept a
+//! package signed by one of them. Unsigned packages, and packages signed by
+//! an unknown key, need an explicit override: the plugin's name in the
+//! `plugins.allow_unsigned` setting. A signature that doesn't match the
+//! contents is always rejected.
+//!
+//! The plugins shipped with the app (`BUNDLED_PLUGINS`) are not signed.
+//! `plugin_load` accepts them unsigned while `plugins.trust_bundled` is on
+//! (the default); installing a package under one of their names still
+//! needs a trusted signature.
+//!
+//! Installing copies the package into a staging directory next to the
+//! plugins, verifies the copy, and only then moves it into place. An update
+//! (`commit_update`) keeps the installed version aside as a
+//! `PackageBackup` until the new one is known to work, so it can be put
+//! back.
+//!
+//! Usage:
+//!     ```rust
+//!     let staged = StagedPackage::stage(&source, &project_root)?;
+//!     let status = verify_package(staged.dir(), &settings.trusted_keys);
+//!     ensure_allowed(staged.name(), &status, &settings)?;
+//!     let path = staged.commit(false)?;
+//!     ```
+
+use minisign_verify::{PublicKey, Signature};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::permissions::find_manifest;
+use crate::settings::PluginSettings;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Detached signature file in the package root
+pub const SIGNATURE_FILE: &str = "plugin.minisig";
+
+/// Directories left out of the digest and not installed
+const SKIPPED_DIRS: &[&str] = &["__pycache__"];
+
+/// File extensions left out of the digest and not installed
+const SKIPPED_EXTENSIONS: &[&str] = &["pyc"];
+
+/// Plugins shipped with the app (in its `plugins` directory)
+pub const BUNDLED_PLUGINS: &[&str] = &[
+    "llm_ollama",
+    "llm_ollama_v2",
+    "stt_whisper",
+    "tts_example_plugin",
+    "tts_kokoro",
+];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Result of checking a package's signature.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SignatureStatus {
+    /// Signed by a trusted publisher key
+    Verified {
+        /// The trusted key that verified it
+        key: String,
+        /// The signature's trusted comment
+        trusted_comment: String,
+    },
+    /// No signature file
+    Unsigned,
+    /// Signed, but not by a trusted key
+    Untrusted,
+    /// The signature is malformed or doesn't match the contents
+    Invalid {
+        /// What is wrong
+        reason: String,
+    },
+}
+
+impl SignatureStatus {
+    /// Whether a plugin with this status may be installed or loaded.
+    pub fn allows(&self, plugin: &str, settings: &PluginSettings) -> bool {
+        match self {
+            Self::Verified { .. } => true,
+            Self::Unsigned | Self::Untrusted => {
+                settings.allow_unsigned.iter().any(|name| name == plugin)
+            }
+            Self::Invalid { .. } => false,
+        }
+    }
+}
+
+/// A plugin installed by `plugin_install`.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstalledPlugin {
+    /// Plugin name (from its manifest)
+    pub name: String,
+    /// Directory it was installed to
+    pub path: PathBuf,
+    /// Its signature status
+    pub signature: SignatureStatus,
+}
+
+// ============================================
+// VERIFICATION
+// ============================================
+
+/// Parse a minisign public key, either the base64 line or the whole
+/// `minisign.pub` file.
+pub fn parse_public_key(key: &str) -> Result<PublicKey, String> {
+    let key = key.trim();
+    let parsed = if key.contains('\n') {
+        PublicKey::decode(key)
+    } else {
+        PublicKey::from_base64(key)
+    };
+    parsed.map_err(|e| format!("Invalid minisign public key: {e}"))
+}
+
+/// Compute the digest a package's signature covers.
+pub fn package_digest(root: &Path) -> Result<String, String> {
+    fn walk(root: &Path, dir: &Path, files: &mut BTreeMap<String, String>) -> Result<(), String> {
+        let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {dir:?}: {e}"))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() {
+                if !SKIPPED_DIRS.contains(&name.as_str()) {
+                    walk(root, &path, files)?;
+                }
+                continue;
+            }
+            if (dir == root && name == SIGNATURE_FILE) || is_skipped_file(&path) {
+                continue;
+            }
+            let content = fs::read(&path).map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+            let relative = path
+                .strip_prefix(root)
+                .map_err(|e| e.to_string())?
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.insert(relative, format!("{:x}", Sha256::digest(&content)));
+        }
+        Ok(())
+    }
+
+    let mut files = BTreeMap::new();
+    walk(root, root, &mut files)?;
+
+    let mut digest = String::new();
+    for (path, hash) in files {
+        let _ = writeln!(digest, "{hash}  {path}");
+    }
+    Ok(digest)
+}
+
+/// Check a package's signature against the trusted keys.
+pub fn verify_package(root: &Path, trusted_keys: &[String]) -> SignatureStatus {
+    let content = match fs::read_to_string(root.join(SIGNATURE_FILE)) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return SignatureStatus::Unsigned,
+        Err(e) => {
+            return SignatureStatus::Invalid {
+                reason: format!("Failed to read {SIGNATURE_FILE}: {e}"),
+            }
+        }
+    };
+    let signature = match Signature::decode(&content) {
+        Ok(signature) => signature,
+        Err(e) => {
+            return SignatureStatus::Invalid {
+                reason: format!("Malformed {SIGNATURE_FILE}: {e}"),
+            }
+        }
+    };
+    let digest = match package_digest(root) {
+        Ok(digest) => digest,
+        Err(reason) => return SignatureStatus::Invalid { reason },
+    };
+
+    for key in trusted_keys {
+        let Ok(public_key) = parse_public_key(key) else {
+            log::warn!("Ignoring invalid trusted key {key:?}");
+            continue;
+        };
+        match public_key.verify(digest.as_bytes(), &signature, false) {
+            Ok(()) => {
+                return SignatureStatus::Verified {
+                    key: key.trim().to_string(),
+                    trusted_comment: signature.trusted_comment().to_string(),
+                }
+            }
+            // Signed with another key
+            Err(minisign_verify::Error::UnexpectedKeyId) => {}
+            Err(e) => {
+                return SignatureStatus::Invalid {
+                    reason: format!("Signature does not match the package: {e}"),
+                }
+            }
+        }
+    }
+    SignatureStatus::Untrusted
+}
+
+/// Fail unless a plugin with this status may be installed or loaded.
+pub fn ensure_allowed(
+    plugin: &str,
+    status: &SignatureStatus,
+    settings: &PluginSettings,
+) -> Result<(), String> {
+    if status.allows(plugin, settings) {
+        return Ok(());
+    }
+    Err(match status {
+        SignatureStatus::Invalid { reason } => {
+            format!("Plugin {plugin} has an invalid signature: {reason}")
+        }
+        SignatureStatus::Untrusted => format!(
+            "Plugin {plugin} is not signed by a trusted publisher; \
+             add it to plugins.allow_unsigned to use it anyway"
+        ),
+        _ => format!(
+            "Plugin {plugin} is not signed; add it to plugins.allow_unsigned to use it anyway"
+        ),
+    })
+}
+
+/// Whether `plugin_load` accepts a plugin shipped with the app without a
+/// signature (see `BUNDLED_PLUGINS`).
+pub fn is_trusted_bundled(
+    plugin: &str,
+    status: &SignatureStatus,
+    settings: &PluginSettings,
+) -> bool {
+    settings.trust_bundled
+        && *status == SignatureStatus::Unsigned
+        && BUNDLED_PLUGINS.contains(&plugin)
+}
+
+/// Signature status of an installed plugin (None if it has no manifest).
+pub fn installed_signature(
+    project_root: &Path,
+    plugin: &str,
+    trusted_keys: &[String],
+) -> Option<SignatureStatus> {
+    find_manifest(project_root, plugin).map(|(dir, _)| verify_package(&dir, trusted_keys))
+}
+
+// ============================================
+// INSTALLATION
+// ============================================
+
+/// Whether a file is left out of the digest and not installed.
+fn is_skipped_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SKIPPED_EXTENSIONS.contains(&ext))
+}
+
+/// Copy a package, leaving out what the digest leaves out.
+fn copy_package(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("Failed to create {to:?}: {e}"))?;
+    let entries = fs::read_dir(from).map_err(|e| format!("Failed to read {from:?}: {e}"))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let target = to.join(entry.file_name());
+        if path.is_dir() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                copy_package(&path, &target)?;
+            }
+        } else if !is_skipped_file(&path) {
+            fs::copy(&path, &target).map_err(|e| format!("Failed to copy {path:?}: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Name of the plugin in a package, checked for use as a directory name.
+fn package_name(source: &Path) -> Result<String, String> {
+    let path = source.join("manifest.json");
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+    let manifest: Value =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid {path:?}: {e}"))?;
+    let name = manifest
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("{path:?} has no name"))?;
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if !valid {
+        return Err(format!("Invalid plugin name {name:?}"));
+    }
+    Ok(name.to_string())
+}
+
+/// A package copied next to the plugins, waiting to be verified.
+///
+/// Dropping it without `commit` removes the copy.
+pub struct StagedPackage {
+    /// Plugin name
+    name: String,
+    /// Project root
+    project_root: PathBuf,
+    /// Staging directory
+    dir: PathBuf,
+}
+
+impl StagedPackage {
+    /// Copy a package directory into the project's staging area.
+    pub fn stage(source: &Path, project_root: &Path) -> Result<Self, String> {
+        let name = package_name(source)?;
+        let dir = project_root
+            .join("plugins")
+            .join(format!(".{name}.installing"));
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| format!("Failed to remove {dir:?}: {e}"))?;
+        }
+        let staged = Self {
+            name,
+            project_root: project_root.to_path_buf(),
+            dir,
+        };
+        copy_package(source, &staged.dir)?;
+        Ok(staged)
+    }
+
+    /// Plugin name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Staging directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Move the package into `plugins/<name>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `replace` - Replace an installed plugin with the same name
+    ///   (otherwise that is an error)
+    pub fn commit(self, replace: bool) -> Result<PathBuf, String> {
+        let target = self.project_root.join("plugins").join(&self.name);
+        let mut existing: Vec<PathBuf> = find_manifest(&self.project_root, &self.name)
+            .map(|(dir, _)| dir)
+            .filter(|dir| *dir != self.dir)
+            .into_iter()
+            .collect();
+        if target.exists() && !existing.contains(&target) {
+            existing.push(target.clone());
+        }
+        if !existing.is_empty() && !replace {
+            return Err(format!("Plugin {} is already installed", self.name));
+        }
+
+        for dir in existing {
+            fs::remove_dir_all(&dir).map_err(|e| format!("Failed to remove {dir:?}: {e}"))?;
+        }
+        fs::rename(&self.dir, &target)
+            .map_err(|e| format!("Failed to move {:?} to {target:?}: {e}", self.dir))?;
+        log::info!("Installed plugin {} to {target:?}", self.name);
+        Ok(target)
+    }
+
+    /// Move the package into `plugins/<name>` in place of the installed
+    /// version, which is kept aside until the backup is dropped.
+    ///
+    /// # Returns
+    ///
+    /// The install directory and the backup of the previous version.
+    pub fn commit_update(self) -> Result<(PathBuf, PackageBackup), String> {
+        let (original, _) = find_manifest(&self.project_root, &self.name)
+            .ok_or_else(|| format!("Plugin {} is not installed", self.name))?;
+        let plugins = self.project_root.join("plugins");
+        let mut backup = PackageBackup {
+            original,
+            backup: plugins.join(format!(".{}.previous", self.name)),
+            replacement: None,
+        };
+        if backup.backup.exists() {
+            fs::remove_dir_all(&backup.backup)
+                .map_err(|e| format!("Failed to remove {:?}: {e}", backup.backup))?;
+        }
+        fs::rename(&backup.original, &backup.backup)
+            .map_err(|e| format!("Failed to move {:?} aside: {e}", backup.original))?;
+
+        let target = plugins.join(&self.name);
+        if let Err(e) = fs::rename(&self.dir, &target) {
+            let error = format!("Failed to move {:?} to {target:?}: {e}", self.dir);
+            backup.restore()?;
+            return Err(error);
+        }
+        log::info!("Updated plugin {} in {target:?}", self.name);
+        backup.replacement = Some(target.clone());
+        Ok((target, backup))
+    }
+}
+
+/// The previous version of an updated plugin.
+///
+/// Dropping it deletes the previous version; `restore` puts it back.
+pub struct PackageBackup {
+    /// Where the previous version was installed
+    original: PathBuf,
+    /// Where it is kept
+    backup: PathBuf,
+    /// The version installed in its place
+    replacement: Option<PathBuf>,
+}
+
+impl PackageBackup {
+    /// Remove the new version and move the previous one back.
+    ///
+    /// # Returns
+    ///
+    /// The directory of the restored version.
+    pub fn restore(mut self) -> Result<PathBuf, String> {
+        if let Some(replacement) = self.replacement.take() {
+            if replacement.exists() {
+                fs::remove_dir_all(&replacement)
+                    .map_err(|e| format!("Failed to remove {replacement:?}: {e}"))?;
+            }
+        }
+        fs::rename(&self.backup, &self.original)
+            .map_err(|e| format!("Failed to restore {:?}: {e}", self.original))?;
+        log::info!("Restored previous version in {:?}", self.original);
+        Ok(self.original.clone())
+    }
+}
+
+impl Drop for PackageBackup {
+    fn drop(&mut self) {
+        if self.backup.exists() {
+            if let Err(e) = fs::remove_dir_all(&self.backup) {
+                log::warn!("Failed to remove {:?}: {e}", self.backup);
+            }
+        }
+    }
+}
+
+impl Drop for StagedPackage {
+    fn drop(&mut self) {
+        if self.dir.exists() {
+            if let Err(e) = fs::remove_dir_all(&self.dir) {
+                log::warn!("Failed to remove {:?}: {e}", self.dir);
+            }
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    /// Publisher key the fixture package is signed with
+    const PUBLIC_KEY: &str = "RWQBAgMEBQYHCFO5W4XqOrv7Az3DH23EgTln6jln5rXD7U22SoW1WWf8";
+
+    /// Signature of the fixture package's digest
+    const SIGNATURE: &str = concat!(
+        "untrusted comment: signature from minisign secret key\n",
+        "RUQBAgMEBQYHCCHzzXNe+hZ8Odn7Kza3NoBE7hSOMpQ1g7kPhy0JvJ3xQy3E+K/feIc3kNtAQdmm",
+        "JDyho9YksNdtnwxsy04iiwA=\n",
+        "trusted comment: timestamp:1760000000\tfile:digest.txt\thashed\n",
+        "hKA5SSzJu8ujwbnvAA8yD64Etap+J2rTiUOjGa5rei4AX64/p3LVwWjvPP4lIgu1kOE3gqUw",
+        "ihmES8Db6XWpAg==\n",
+    );
+
+    /// Another publisher's key
+    const OTHER_KEY: &str = "RWQREhMUFRYXGI9sT+Jdb1pEpBW+aONIkR+jI49OxA4kLblAc7UiOj/6";
+
+    /// Write the fixture package.
+    fn package(root: &Path, signed: bool) {
+        fs::create_dir_all(root.join("__pycache__")).unwrap();
+        fs::write(root.join("manifest.json"), r#"{"name": "signed_demo"}"#).unwrap();
+        fs::write(root.join("plugin.py"), "VALUE = 1\n").unwrap();
+        fs::write(root.join("__pycache__").join("plugin.cpython-311.pyc"), "x").unwrap();
+        if signed {
+            fs::write(root.join(SIGNATURE_FILE), SIGNATURE).unwrap();
+        }
+    }
+
+    fn settings(trusted: &[&str], allow_unsigned: &[&str]) -> PluginSettings {
+        PluginSettings {
+            trusted_keys: trusted.iter().map(|k| (*k).to_string()).collect(),
+            allow_unsigned: allow_unsigned.iter().map(|p| (*p).to_string()).collect(),
+            ..PluginSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_package_digest() {
+        let root = temp_dir("packages");
+        package(&root, true);
+        let digest = package_digest(&root).unwrap();
+        let paths: Vec<&str> = digest
+            .lines()
+            .map(|line| line.split_once("  ").unwrap().1)
+            .collect();
+        assert_eq!(paths, vec!["manifest.json", "plugin.py"]);
+    }
+
+    #[test]
+    fn test_verify_package() {
+        let root = temp_dir("packages");
+        package(&root, true);
+
+        let status = verify_package(&root, &[OTHER_KEY.to_string(), PUBLIC_KEY.to_string()]);
+        assert!(matches!(status, SignatureStatus::Verified { ref key, .. } if key == PUBLIC_KEY));
+        assert_eq!(
+            verify_package(&root, &[OTHER_KEY.to_string()]),
+            SignatureStatus::Untrusted
+        );
+
+        // Tampering breaks the signature
+        fs::write(root.join("plugin.py"), "VALUE = 2\n").unwrap();
+        let status = verify_package(&root, &[PUBLIC_KEY.to_string()]);
+        assert!(matches!(status, SignatureStatus::Invalid { .. }));
+        assert!(!status.allows("signed_demo", &settings(&[], &["signed_demo"])));
+
+        fs::remove_file(root.join(SIGNATURE_FILE)).unwrap();
+        assert_eq!(verify_package(&root, &[]), SignatureStatus::Unsigned);
+    }
+
+    #[test]
+    fn test_unsigned_needs_override() {
+        let trusted = settings(&[PUBLIC_KEY], &[]);
+        assert!(ensure_allowed("demo", &SignatureStatus::Unsigned, &trusted).is_err());
+        assert!(ensure_allowed("demo", &SignatureStatus::Untrusted, &trusted).is_err());
+
+        let allowed = settings(&[PUBLIC_KEY], &["demo"]);
+        assert!(ensure_allowed("demo", &SignatureStatus::Unsigned, &allowed).is_ok());
+        assert!(ensure_allowed("other", &SignatureStatus::Unsigned, &allowed).is_err());
+    }
+
+    #[test]
+    fn test_bundled_plugins_load_unsigned() {
+        let defaults = PluginSettings::default();
+        let unsigned = SignatureStatus::Unsigned;
+        assert!(is_trusted_bundled("tts_kokoro", &unsigned, &defaults));
+        assert!(!is_trusted_bundled("demo", &unsigned, &defaults));
+        assert!(!is_trusted_bundled(
+            "tts_kokoro",
+            &SignatureStatus::Untrusted,
+            &defaults
+        ));
+
+        let strict = PluginSettings {
+            trust_bundled: false,
+            ..PluginSettings::default()
+        };
+        assert!(!is_trusted_bundled("tts_kokoro", &unsigned, &strict));
+
+        // Every bundled plugin ships in the app's plugins directory
+        let project_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+        for plugin in BUNDLED_PLUGINS {
+            assert!(find_manifest(&project_root, plugin).is_some(), "{plugin}");
+        }
+    }
+
+    #[test]
+    fn test_install() {
+        let source = temp_dir("packages");
+        let project = temp_dir("packages");
+        package(&source, true);
+
+        let staged = StagedPackage::stage(&source, &project).unwrap();
+        assert_eq!(staged.name(), "signed_demo");
+        let status = verify_package(staged.dir(), &[PUBLIC_KEY.to_string()]);
+        assert!(matches!(status, SignatureStatus::Verified { .. }));
+        let path = staged.commit(false).unwrap();
+        assert_eq!(path, project.join("plugins").join("signed_demo"));
+        assert!(path.join(SIGNATURE_FILE).exists());
+        assert!(!path.join("__pycache__").exists());
+
+        // Installing again needs `replace`; a rejected copy is cleaned up
+        let staged = StagedPackage::stage(&source, &project).unwrap();
+        let staging = staged.dir().to_path_buf();
+        assert!(staged.commit(false).is_err());
+        assert!(!staging.exists());
+        let staged = StagedPackage::stage(&source, &project).unwrap();
+        assert!(staged.commit(true).is_ok());
+        assert_eq!(
+            installed_signature(&project, "signed_demo", &[PUBLIC_KEY.to_string()]),
+            Some(status)
+        );
+    }
+
+    #[test]
+    fn test_update_and_restore() {
+        let source = temp_dir("packages");
+        let project = temp_dir("packages");
+        package(&source, false);
+        assert!(StagedPackage::stage(&source, &project)
+            .unwrap()
+            .commit_update()
+            .is_err());
+        let installed = StagedPackage::stage(&source, &project)
+            .unwrap()
+            .commit(false)
+            .unwrap();
+
+        fs::write(source.join("plugin.py"), "VALUE = 2\n").unwrap();
+        let (path, backup) = StagedPackage::stage(&source, &project)
+            .unwrap()
+            .commit_update()
+            .unwrap();
+        assert_eq!(path, installed);
+        assert_eq!(
+            fs::read_to_string(path.join("plugin.py")).unwrap(),
+            "VALUE = 2\n"
+        );
+        // The kept version is hidden from lookups by name
+        assert_eq!(find_manifest(&project, "signed_demo").unwrap().0, path);
+
+        assert_eq!(backup.restore().unwrap(), installed);
+        assert_eq!(
+            fs::read_to_string(installed.join("plugin.py")).unwrap(),
+            "VALUE = 1\n"
+        );
+
+        // Dropping the backup keeps the update
+        let (_, backup) = StagedPackage::stage(&source, &project)
+            .unwrap()
+            .commit_update()
+            .unwrap();
+        drop(backup);
+        assert_eq!(
+            fs::read_to_string(installed.join("plugin.py")).unwrap(),
+            "VALUE = 2\n"
+        );
+        assert!(!project
+            .join("plugins")
+            .join(".signed_demo.previous")
+            .exists());
+    }
+
+    #[test]
+    fn test_parse_public_key() {
+        assert!(parse_public_key(PUBLIC_KEY).is_ok());
+        let file = format!("untrusted comment: minisign public key\n{PUBLIC_KEY}\n");
+        assert!(parse_public_key(&file).is_ok());
+        assert!(parse_public_key("not a key").is_err());
+    }
+}
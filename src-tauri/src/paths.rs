@@ -0,0 +1,314 @@
+//! src-tauri/src/paths.rs
+//! ======================
+//! Per-OS app directories (config, data, cache, logs) with explicit overrides.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Directories are resolved once at startup, in order of precedence:
+//!
+//! 1. A per-directory flag or environment variable (see `OVERRIDES`)
+//! 2. `--home <dir>` / `APP_FACTORY_HOME`: `config/`, `data/`, `cache/`,
+//!    and `logs/` under one directory (portable installs, tests)
+//! 3. The OS default for the bundle identifier (`dirs` crate), e.g. on Linux
+//!    `$XDG_CONFIG_HOME/<id>`, `$XDG_DATA_HOME/<id>`, `$XDG_CACHE_HOME/<id>`,
+//!    and `$XDG_STATE_HOME/<id>/logs`. Logs go to `~/Library/Logs/<id>` on
+//!    macOS and `%LOCALAPPDATA%\<id>\logs` on Windows.
+//!
+//! The config, data, and cache defaults match Tauri's `app_*_dir`, so
+//! existing settings and databases stay where they are. Logs used to be
+//! written to `<data>/logs`; older files there are not moved.
+//!
+//! API keys live in the data directory, one folder per project
+//! (`secrets/<name>-<hash>/.env`), instead of inside the project where they
+//! could be committed. A project's old `.env` is copied there the first
+//! time the project is used and left in place. Plugin discovery is still
+//! project-local (see project.rs).
+//!
+//! Usage:
+//!     ```rust
+//!     let paths = AppPaths::resolve(&context.config().tauri.bundle.identifier);
+//!     let sink = LogSink::init(paths.log_dir());
+//!     let env_path = paths.secrets_file(&project_root);
+//!     ```
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::app_config::flag_values;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Environment variable placing every directory under one root
+const HOME_ENV: &str = "APP_FACTORY_HOME";
+
+/// Flag placing every directory under one root
+const HOME_FLAG: &str = "--home";
+
+/// Per-directory overrides.
+const OVERRIDES: &[Override] = &[
+    Override::new(Dir::Config, "APP_FACTORY_CONFIG_DIR", "--config-dir"),
+    Override::new(Dir::Data, "APP_FACTORY_DATA_DIR", "--data-dir"),
+    Override::new(Dir::Cache, "APP_FACTORY_CACHE_DIR", "--cache-dir"),
+    Override::new(Dir::Logs, "APP_FACTORY_LOG_DIR", "--log-dir"),
+];
+
+/// Folder for per-project API key files inside the data directory
+const SECRETS_DIR: &str = "secrets";
+
+/// Name of the API key file (in the secrets folder, or a legacy project root)
+const ENV_FILE: &str = ".env";
+
+/// Folder for per-plugin data directories inside the data directory
+const PLUGIN_DATA_DIR: &str = "plugin_data";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// An app directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dir {
+    Config,
+    Data,
+    Cache,
+    Logs,
+}
+
+impl Dir {
+    /// Subdirectory used under `--home`.
+    fn home_subdir(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Data => "data",
+            Self::Cache => "cache",
+            Self::Logs => "logs",
+        }
+    }
+
+    /// OS default for the bundle identifier.
+    fn os_default(self, identifier: &str) -> Option<PathBuf> {
+        match self {
+            Self::Config => dirs::config_dir().map(|dir| dir.join(identifier)),
+            Self::Data => dirs::data_dir().map(|dir| dir.join(identifier)),
+            Self::Cache => dirs::cache_dir().map(|dir| dir.join(identifier)),
+            Self::Logs => {
+                if cfg!(target_os = "macos") {
+                    dirs::home_dir().map(|dir| dir.join("Library/Logs").join(identifier))
+                } else {
+                    dirs::state_dir()
+                        .or_else(dirs::data_local_dir)
+                        .map(|dir| dir.join(identifier).join("logs"))
+                }
+            }
+        }
+    }
+}
+
+/// A directory settable from the environment and the command line.
+struct Override {
+    dir: Dir,
+    env: &'static str,
+    flag: &'static str,
+}
+
+impl Override {
+    const fn new(dir: Dir, env: &'static str, flag: &'static str) -> Self {
+        Self { dir, env, flag }
+    }
+}
+
+/// Resolved app directories. `None` when the OS has no such directory
+/// (e.g. no home directory), in which case the feature using it is disabled.
+#[derive(Debug, Clone, Default)]
+pub struct AppPaths {
+    config: Option<PathBuf>,
+    data: Option<PathBuf>,
+    cache: Option<PathBuf>,
+    logs: Option<PathBuf>,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Folder name for a project's API keys: readable name plus a hash of the
+/// full path, so projects with the same directory name don't collide.
+fn project_key(project_root: &Path) -> String {
+    let root = fs::canonicalize(project_root).unwrap_or_else(|_| project_root.to_path_buf());
+    let hash = format!("{:x}", Sha256::digest(root.to_string_lossy().as_bytes()));
+    let name: String = root
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{name}-{}", &hash[..12])
+}
+
+// ============================================
+// APP PATHS
+// ============================================
+
+impl AppPaths {
+    /// Resolve the directories for this process (environment and
+    /// `std::env::args`).
+    pub fn resolve(identifier: &str) -> Self {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        Self::resolve_with(identifier, &|name| std::env::var(name).ok(), &args)
+    }
+
+    /// Resolve the directories from the given environment and arguments.
+    fn resolve_with<F>(identifier: &str, env: &F, args: &[String]) -> Self
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let lookup = |flag: &str, var: &str| {
+            flag_values(args, flag)
+                .last()
+                .map(str::to_string)
+                .or_else(|| env(var))
+                .filter(|value| !value.trim().is_empty())
+                .map(PathBuf::from)
+        };
+        let home = lookup(HOME_FLAG, HOME_ENV);
+
+        let resolve = |dir: Dir| {
+            OVERRIDES
+                .iter()
+                .find(|o| o.dir == dir)
+                .and_then(|o| lookup(o.flag, o.env))
+                .or_else(|| home.as_ref().map(|home| home.join(dir.home_subdir())))
+                .or_else(|| dir.os_default(identifier))
+        };
+
+        Self {
+            config: resolve(Dir::Config),
+            data: resolve(Dir::Data),
+            cache: resolve(Dir::Cache),
+            logs: resolve(Dir::Logs),
+        }
+    }
+
+    /// Settings, recents, key vault, and other configuration.
+    pub fn config_dir(&self) -> Option<PathBuf> {
+        self.config.clone()
+    }
+
+    /// Database, crash reports, and API keys.
+    pub fn data_dir(&self) -> Option<PathBuf> {
+        self.data.clone()
+    }
+
+    /// Root of the per-plugin data directories (see plugin_data.rs).
+    pub fn plugin_data_dir(&self) -> Option<PathBuf> {
+        self.data.as_ref().map(|dir| dir.join(PLUGIN_DATA_DIR))
+    }
+
+    /// Downloaded models and artifacts.
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        self.cache.clone()
+    }
+
+    /// Rotating log files.
+    pub fn log_dir(&self) -> Option<PathBuf> {
+        self.logs.clone()
+    }
+
+    /// Path of the API key file for a project.
+    ///
+    /// Falls back to `<project>/.env` when there is no data directory.
+    pub fn secrets_file(&self, project_root: &Path) -> PathBuf {
+        match self.data {
+            Some(ref data) => data
+                .join(SECRETS_DIR)
+                .join(project_key(project_root))
+                .join(ENV_FILE),
+            None => project_root.join(ENV_FILE),
+        }
+    }
+
+    /// Copy a project's legacy `.env` into its secrets folder, unless the
+    /// folder already has one.
+    pub fn migrate_secrets(&self, project_root: &Path) {
+        let legacy = project_root.join(ENV_FILE);
+        let target = self.secrets_file(project_root);
+        if target == legacy || target.exists() || !legacy.is_file() {
+            return;
+        }
+
+        let result = target
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::copy(&legacy, &target));
+        match result {
+            Ok(_) => log::info!(
+                "Copied API keys from {legacy:?} to {target:?}; the project file is no longer used"
+            ),
+            Err(e) => log::warn!("Failed to copy API keys from {legacy:?} to {target:?}: {e}"),
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let env = |name: &str| match name {
+            "APP_FACTORY_HOME" => Some("/portable".to_string()),
+            "APP_FACTORY_CACHE_DIR" => Some("/env-cache".to_string()),
+            _ => None,
+        };
+        let args = vec!["--cache-dir=/flag-cache".to_string()];
+        let paths = AppPaths::resolve_with("com.example.app", &env, &args);
+
+        assert_eq!(paths.config_dir(), Some(PathBuf::from("/portable/config")));
+        assert_eq!(paths.log_dir(), Some(PathBuf::from("/portable/logs")));
+        assert_eq!(paths.cache_dir(), Some(PathBuf::from("/flag-cache")));
+    }
+
+    #[test]
+    fn test_migrate_secrets() {
+        let home = temp_dir("paths");
+        let env = |name: &str| (name == HOME_ENV).then(|| home.to_string_lossy().into_owned());
+        let paths = AppPaths::resolve_with("com.example.app", &env, &[]);
+
+        let project = temp_dir("paths").join("my project");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join(".env"), "OPENAI_API_KEY=sk-test\n").unwrap();
+
+        let target = paths.secrets_file(&project);
+        assert!(target.starts_with(home.join("data").join(SECRETS_DIR)));
+        assert!(target.parent().unwrap().ends_with(project_key(&project)));
+
+        paths.migrate_secrets(&project);
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "OPENAI_API_KEY=sk-test\n"
+        );
+
+        // An existing secrets file is never overwritten
+        fs::write(project.join(".env"), "OPENAI_API_KEY=sk-old\n").unwrap();
+        paths.migrate_secrets(&project);
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "OPENAI_API_KEY=sk-test\n"
+        );
+    }
+}
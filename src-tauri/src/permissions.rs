@@ -0,0 +1,521 @@
+//! src-tauri/src/permissions.rs
+//! ============================
+//! Plugin permission prompts and the store of the user's decisions.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Plugins declare the capabilities they need in `manifest.json`
+//! (`"permissions": ["network", "microphone"]`). Before a plugin is loaded,
+//! every declared permission the user has not decided yet is asked for in a
+//! native dialog; if any is denied the load fails. Granted permissions are
+//! passed to the host with `plugin/load`.
+//!
+//! At runtime a plugin can ask again through the host
+//! (`host/request_permission`, see `plugins/_host/host_rpc.py`). Declared
+//! permissions are answered from the stored decision or prompted for;
+//! undeclared ones are always denied.
+//!
+//! Decisions are stored per plugin name in `plugin_permissions.json` in the
+//! app config directory and can be reviewed, changed, or reset with the
+//! `plugin_permissions` commands. Without a window to prompt in, undecided
+//! permissions are denied and not stored.
+//!
+//! Usage:
+//!     ```rust
+//!     let store = PermissionStore::load(config_dir);
+//!     store.attach(app.handle());
+//!     let granted = store.authorize_load(&project_root, "tts_kokoro").await?;
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+use crate::ipc::manager::HostRequest;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// File name of the decision store inside the app config directory
+const PERMISSIONS_FILE: &str = "plugin_permissions.json";
+
+/// Host method asking for a permission at runtime
+pub const PERMISSION_REQUEST_METHOD: &str = "host/request_permission";
+
+/// Title of the prompt dialog
+const DIALOG_TITLE: &str = "Plugin permission";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A capability a plugin must be granted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    Filesystem,
+    Network,
+    Subprocess,
+    Microphone,
+    Secrets,
+}
+
+impl Permission {
+    /// All permissions.
+    pub const ALL: [Self; 5] = [
+        Self::Filesystem,
+        Self::Network,
+        Self::Subprocess,
+        Self::Microphone,
+        Self::Secrets,
+    ];
+
+    /// Name used in manifests.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Filesystem => "filesystem",
+            Self::Network => "network",
+            Self::Subprocess => "subprocess",
+            Self::Microphone => "microphone",
+            Self::Secrets => "secrets",
+        }
+    }
+
+    /// Parse a manifest name.
+    pub fn parse(name: &str) -> Result<Self, String> {
+        Self::ALL
+            .into_iter()
+            .find(|p| p.as_str() == name)
+            .ok_or_else(|| format!("Unknown permission: {name}"))
+    }
+
+    /// What granting it allows, for the prompt.
+    fn describe(self) -> &'static str {
+        match self {
+            Self::Filesystem => "read and write files outside its plugin folder",
+            Self::Network => "connect to the network",
+            Self::Subprocess => "run other programs",
+            Self::Microphone => "record from the microphone",
+            Self::Secrets => "read API keys stored in the app",
+        }
+    }
+}
+
+/// Stored decisions for one plugin.
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginPermissions {
+    /// Plugin name
+    pub plugin: String,
+    /// Permissions the user granted
+    pub granted: Vec<Permission>,
+    /// Permissions the user denied
+    pub denied: Vec<Permission>,
+}
+
+/// Decisions by plugin name, then permission.
+type Decisions = BTreeMap<String, BTreeMap<Permission, bool>>;
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Find a plugin's manifest by the `name` in `<project>/plugins/*/manifest.json`.
+///
+/// Hidden directories (staged installs, kept previous versions) are
+/// skipped, like the host does.
+///
+/// # Returns
+///
+/// The plugin directory and the parsed manifest, or None if no manifest
+/// names the plugin.
+pub fn find_manifest(project_root: &Path, plugin: &str) -> Option<(PathBuf, Value)> {
+    let entries = fs::read_dir(project_root.join("plugins")).ok()?;
+
+    for entry in entries.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(entry.path().join("manifest.json")) else {
+            continue;
+        };
+        let Ok(manifest) = serde_json::from_str::<Value>(&content) else {
+            continue;
+        };
+        if manifest.get("name").and_then(Value::as_str) == Some(plugin) {
+            return Some((entry.path(), manifest));
+        }
+    }
+    None
+}
+
+/// Permissions declared in a plugin's manifest.
+///
+/// A plugin without a manifest declares nothing.
+pub fn declared_permissions(project_root: &Path, plugin: &str) -> Result<Vec<Permission>, String> {
+    let Some((_, manifest)) = find_manifest(project_root, plugin) else {
+        return Ok(Vec::new());
+    };
+
+    let mut declared = Vec::new();
+    for name in manifest
+        .get("permissions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let name = name
+            .as_str()
+            .ok_or_else(|| format!("Invalid permission in {plugin} manifest: {name}"))?;
+        let permission =
+            Permission::parse(name).map_err(|e| format!("Invalid manifest for {plugin}: {e}"))?;
+        if !declared.contains(&permission) {
+            declared.push(permission);
+        }
+    }
+    Ok(declared)
+}
+
+/// Ask the user in a native dialog. Closing the dialog denies.
+async fn prompt(app: &AppHandle, plugin: &str, permission: Permission) -> bool {
+    let (tx, rx) = oneshot::channel();
+    let window = app.get_window("main");
+    tauri::api::dialog::ask(
+        window.as_ref(),
+        DIALOG_TITLE,
+        format!(
+            "Allow the plugin \"{plugin}\" to {}?",
+            permission.describe()
+        ),
+        move |granted| {
+            let _ = tx.send(granted);
+        },
+    );
+    rx.await.unwrap_or(false)
+}
+
+// ============================================
+// PERMISSION STORE
+// ============================================
+
+/// Stored permission decisions and the prompts that fill them in.
+#[derive(Clone)]
+pub struct PermissionStore {
+    decisions: Arc<Mutex<Decisions>>,
+    path: Option<PathBuf>,
+    app: Arc<Mutex<Option<AppHandle>>>,
+    /// Held while a prompt is open so one question is asked at a time
+    prompting: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl PermissionStore {
+    /// Load the decisions from the config directory (in memory only when
+    /// there is none).
+    pub fn load(config_dir: Option<PathBuf>) -> Self {
+        let path = config_dir.map(|dir| dir.join(PERMISSIONS_FILE));
+        let decisions = path
+            .as_deref()
+            .and_then(|path| {
+                let content = fs::read_to_string(path).ok()?;
+                serde_json::from_str(&content)
+                    .map_err(|e| log::warn!("Ignoring invalid permissions file {path:?}: {e}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        Self {
+            decisions: Arc::new(Mutex::new(decisions)),
+            path,
+            app: Arc::new(Mutex::new(None)),
+            prompting: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Prompt in this app's window from now on.
+    pub fn attach(&self, app: AppHandle) {
+        *self.app.lock().unwrap() = Some(app);
+    }
+
+    /// Stored decision, if any.
+    pub fn decision(&self, plugin: &str, permission: Permission) -> Option<bool> {
+        self.decisions
+            .lock()
+            .unwrap()
+            .get(plugin)
+            .and_then(|plugin| plugin.get(&permission))
+            .copied()
+    }
+
+    /// All stored decisions, by plugin name.
+    pub fn list(&self) -> Vec<PluginPermissions> {
+        self.decisions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(plugin, decisions)| {
+                let with = |wanted: bool| {
+                    decisions
+                        .iter()
+                        .filter(|(_, granted)| **granted == wanted)
+                        .map(|(permission, _)| *permission)
+                        .collect()
+                };
+                PluginPermissions {
+                    plugin: plugin.clone(),
+                    granted: with(true),
+                    denied: with(false),
+                }
+            })
+            .collect()
+    }
+
+    /// Store a decision.
+    pub fn set(&self, plugin: &str, permission: Permission, granted: bool) -> Result<(), String> {
+        self.update(|decisions| {
+            decisions
+                .entry(plugin.to_string())
+                .or_default()
+                .insert(permission, granted);
+        })
+    }
+
+    /// Forget the decisions for one plugin, or for all plugins when
+    /// `plugin` is None, so they are asked for again.
+    pub fn reset(&self, plugin: Option<&str>) -> Result<(), String> {
+        self.update(|decisions| match plugin {
+            Some(plugin) => {
+                decisions.remove(plugin);
+            }
+            None => decisions.clear(),
+        })
+    }
+
+    /// Whether a plugin may use a permission, prompting if undecided.
+    pub async fn check(&self, plugin: &str, permission: Permission) -> bool {
+        if let Some(granted) = self.decision(plugin, permission) {
+            return granted;
+        }
+
+        let _prompting = self.prompting.lock().await;
+        // Answered while waiting for another prompt
+        if let Some(granted) = self.decision(plugin, permission) {
+            return granted;
+        }
+
+        let app = self.app.lock().unwrap().clone();
+        let Some(app) = app else {
+            log::warn!(
+                "No window to ask for {plugin} {}; denying",
+                permission.as_str()
+            );
+            return false;
+        };
+
+        let granted = prompt(&app, plugin, permission).await;
+        log::info!(
+            "Plugin {plugin} {} {}",
+            if granted { "granted" } else { "denied" },
+            permission.as_str()
+        );
+        if let Err(e) = self.set(plugin, permission, granted) {
+            log::warn!("Failed to save permission decision: {e}");
+        }
+        granted
+    }
+
+    /// Check every permission a plugin declares before it is loaded.
+    ///
+    /// # Returns
+    ///
+    /// The granted permissions, or an error naming the first denied one.
+    pub async fn authorize_load(
+        &self,
+        project_root: &Path,
+        plugin: &str,
+    ) -> Result<Vec<Permission>, String> {
+        let mut granted = Vec::new();
+        for permission in declared_permissions(project_root, plugin)? {
+            if !self.check(plugin, permission).await {
+                return Err(format!(
+                    "Plugin {plugin} was denied the {} permission",
+                    permission.as_str()
+                ));
+            }
+            granted.push(permission);
+        }
+        Ok(granted)
+    }
+
+    /// Answer a `host/request_permission` request from the plugin host.
+    pub async fn handle_request(&self, request: HostRequest) -> Result<Value, String> {
+        if request.method != PERMISSION_REQUEST_METHOD {
+            return Err(format!("Unknown host method: {}", request.method));
+        }
+        let plugin = request
+            .params
+            .get("plugin")
+            .and_then(Value::as_str)
+            .ok_or("Missing 'plugin' parameter")?;
+        let permission = Permission::parse(
+            request
+                .params
+                .get("permission")
+                .and_then(Value::as_str)
+                .ok_or("Missing 'permission' parameter")?,
+        )?;
+
+        let granted = self
+            .authorize(request.working_dir.as_deref(), plugin, permission)
+            .await?;
+        Ok(json!({ "granted": granted }))
+    }
+
+    /// Whether a plugin may use a permission at runtime.
+    ///
+    /// Permissions the plugin's manifest in `project_root` doesn't declare
+    /// are denied; declared ones are answered like `check`.
+    pub async fn authorize(
+        &self,
+        project_root: Option<&Path>,
+        plugin: &str,
+        permission: Permission,
+    ) -> Result<bool, String> {
+        let declared = match project_root {
+            Some(root) => declared_permissions(root, plugin)?,
+            None => Vec::new(),
+        };
+        if !declared.contains(&permission) {
+            log::warn!(
+                "Plugin {plugin} requested undeclared permission {}; denying",
+                permission.as_str()
+            );
+            return Ok(false);
+        }
+
+        Ok(self.check(plugin, permission).await)
+    }
+
+    /// Apply an edit and persist the result.
+    fn update<F>(&self, edit: F) -> Result<(), String>
+    where
+        F: FnOnce(&mut Decisions),
+    {
+        let mut decisions = self.decisions.lock().unwrap();
+        let mut updated = decisions.clone();
+        edit(&mut updated);
+
+        if let Some(ref path) = self.path {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+            }
+            let content = serde_json::to_string_pretty(&updated)
+                .map_err(|e| format!("Failed to serialize permissions: {e}"))?;
+            fs::write(path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+        }
+
+        *decisions = updated;
+        Ok(())
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    fn write_manifest(root: &Path, folder: &str, manifest: &Value) {
+        let dir = root.join("plugins").join(folder);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_declared_permissions() {
+        let root = temp_dir("permissions");
+        write_manifest(
+            &root,
+            "tts",
+            &json!({ "name": "tts_kokoro", "permissions": ["network", "filesystem", "network"] }),
+        );
+        write_manifest(&root, "stt", &json!({ "name": "stt_whisper" }));
+        write_manifest(
+            &root,
+            "bad",
+            &json!({ "name": "bad", "permissions": ["root"] }),
+        );
+
+        assert_eq!(
+            declared_permissions(&root, "tts_kokoro").unwrap(),
+            vec![Permission::Network, Permission::Filesystem]
+        );
+        assert!(declared_permissions(&root, "stt_whisper")
+            .unwrap()
+            .is_empty());
+        assert!(declared_permissions(&root, "missing").unwrap().is_empty());
+        assert!(declared_permissions(&root, "bad").is_err());
+    }
+
+    #[test]
+    fn test_decisions_persist() {
+        let dir = temp_dir("permissions");
+        let store = PermissionStore::load(Some(dir.clone()));
+        store.set("tts_kokoro", Permission::Network, true).unwrap();
+        store
+            .set("tts_kokoro", Permission::Microphone, false)
+            .unwrap();
+
+        let reloaded = PermissionStore::load(Some(dir));
+        assert_eq!(
+            reloaded.decision("tts_kokoro", Permission::Network),
+            Some(true)
+        );
+        let listed = reloaded.list();
+        assert_eq!(listed[0].granted, vec![Permission::Network]);
+        assert_eq!(listed[0].denied, vec![Permission::Microphone]);
+
+        reloaded.reset(Some("tts_kokoro")).unwrap();
+        assert!(reloaded.list().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_requests_without_window_are_denied() {
+        let root = temp_dir("permissions");
+        write_manifest(
+            &root,
+            "tts",
+            &json!({ "name": "tts", "permissions": ["network"] }),
+        );
+        let store = PermissionStore::load(None);
+        store.set("tts", Permission::Network, true).unwrap();
+
+        let request = |permission: &str| HostRequest {
+            method: PERMISSION_REQUEST_METHOD.to_string(),
+            params: json!({ "plugin": "tts", "permission": permission }),
+            working_dir: Some(root.clone()),
+        };
+        assert_eq!(
+            store.handle_request(request("network")).await.unwrap(),
+            json!({ "granted": true })
+        );
+        // Undeclared
+        assert_eq!(
+            store.handle_request(request("microphone")).await.unwrap(),
+            json!({ "granted": false })
+        );
+
+        // Declared but undecided, with nowhere to prompt: denied and not stored
+        store.reset(None).unwrap();
+        assert!(store.authorize_load(&root, "tts").await.is_err());
+        assert_eq!(store.decision("tts", Permission::Network), None);
+    }
+}
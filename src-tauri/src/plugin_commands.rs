@@ -0,0 +1,215 @@
+//! src-tauri/src/plugin_commands.rs
+//! ================================
+//! Frontend commands declared by plugins.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Tauri commands are fixed when the binary is built. A plugin can still
+//! expose named commands to the frontend by listing them in its manifest:
+//!
+//! ```json
+//! {
+//!     "name": "tts_kokoro",
+//!     "commands": {
+//!         "preview_voice": { "method": "synthesize", "description": "Speak a sample" }
+//!     }
+//! }
+//! ```
+//!
+//! A command is addressed as `<plugin>.<command>` (`tts_kokoro.preview_voice`)
+//! and invoked through `ipc_invoke_dynamic`, which calls `method` (the
+//! command name if unset) on the plugin like `plugin_call`. Command names
+//! use lowercase letters, digits, and `_`; invalid entries are skipped.
+//!
+//! Like the dependency graph (see plugin_graph.rs), the registry is built
+//! from the manifests under `plugins/` whenever it is needed, so installed
+//! and updated plugins are picked up without a restart.
+//!
+//! Usage:
+//!     ```rust
+//!     let registry = CommandRegistry::scan(&project_root);
+//!     let command = registry.get("tts_kokoro.preview_voice")?;
+//!     call_plugin(&command.plugin, &command.method, args).await?;
+//!     ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Manifest field declaring a plugin's commands
+pub const COMMANDS_FIELD: &str = "commands";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A command declared by a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginCommand {
+    /// Qualified name (`<plugin>.<command>`)
+    pub name: String,
+    /// Plugin declaring the command
+    pub plugin: String,
+    /// Plugin method the command calls
+    pub method: String,
+    /// What the command does, if the manifest says
+    pub description: Option<String>,
+}
+
+// ============================================
+// COMMAND REGISTRY
+// ============================================
+
+/// Plugin-declared commands by qualified name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandRegistry {
+    commands: BTreeMap<String, PluginCommand>,
+}
+
+impl CommandRegistry {
+    /// Read the commands declared by the plugins in `project_root/plugins`.
+    ///
+    /// Folders starting with `.` or `_` are skipped, like the host does.
+    pub fn scan(project_root: &Path) -> Self {
+        let mut registry = Self::default();
+        let Ok(entries) = fs::read_dir(project_root.join("plugins")) else {
+            return registry;
+        };
+        for entry in entries.flatten() {
+            let folder = entry.file_name().to_string_lossy().to_string();
+            if folder.starts_with('.') || folder.starts_with('_') {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(entry.path().join("manifest.json")) else {
+                continue;
+            };
+            let Ok(manifest) = serde_json::from_str::<Value>(&content) else {
+                continue;
+            };
+            registry.add_manifest(&manifest);
+        }
+        registry
+    }
+
+    /// Add the commands declared in a manifest.
+    pub fn add_manifest(&mut self, manifest: &Value) {
+        let Some(plugin) = manifest.get("name").and_then(Value::as_str) else {
+            return;
+        };
+        let Some(commands) = manifest.get(COMMANDS_FIELD).and_then(Value::as_object) else {
+            return;
+        };
+        for (command, spec) in commands {
+            if !is_valid_name(command) {
+                log::warn!("Skipping invalid command {command:?} in {plugin} manifest");
+                continue;
+            }
+            let method = spec
+                .get("method")
+                .and_then(Value::as_str)
+                .filter(|method| !method.trim().is_empty())
+                .unwrap_or(command);
+            let name = format!("{plugin}.{command}");
+            self.commands.insert(
+                name.clone(),
+                PluginCommand {
+                    name,
+                    plugin: plugin.to_string(),
+                    method: method.to_string(),
+                    description: spec
+                        .get("description")
+                        .and_then(Value::as_str)
+                        .map(str::to_string),
+                },
+            );
+        }
+    }
+
+    /// Look up a command by qualified name.
+    pub fn get(&self, name: &str) -> Option<&PluginCommand> {
+        self.commands.get(name)
+    }
+
+    /// All commands, sorted by qualified name.
+    pub fn list(&self) -> Vec<PluginCommand> {
+        self.commands.values().cloned().collect()
+    }
+}
+
+/// Whether a command name is lowercase letters, digits, and `_`.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_manifest_commands() {
+        let mut registry = CommandRegistry::default();
+        registry.add_manifest(&json!({
+            "name": "tts_kokoro",
+            "commands": {
+                "preview_voice": { "method": "synthesize", "description": "Speak a sample" },
+                "list_voices": {},
+                "Bad-Name": { "method": "x" }
+            }
+        }));
+        registry.add_manifest(&json!({ "name": "stt_whisper" }));
+
+        let names: Vec<String> = registry.list().into_iter().map(|c| c.name).collect();
+        assert_eq!(
+            names,
+            ["tts_kokoro.list_voices", "tts_kokoro.preview_voice"]
+        );
+
+        let command = registry.get("tts_kokoro.preview_voice").unwrap();
+        assert_eq!(command.plugin, "tts_kokoro");
+        assert_eq!(command.method, "synthesize");
+        assert_eq!(command.description.as_deref(), Some("Speak a sample"));
+        assert_eq!(
+            registry.get("tts_kokoro.list_voices").unwrap().method,
+            "list_voices"
+        );
+        assert!(registry.get("preview_voice").is_none());
+    }
+
+    #[test]
+    fn test_scan_reads_plugin_folders() {
+        let root = std::env::temp_dir().join(format!("af_commands_{}", uuid::Uuid::new_v4()));
+        for (folder, manifest) in [
+            (
+                "tts",
+                json!({ "name": "tts_kokoro", "commands": { "preview": {} } }),
+            ),
+            (
+                "_host",
+                json!({ "name": "host", "commands": { "hidden": {} } }),
+            ),
+        ] {
+            let dir = root.join("plugins").join(folder);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("manifest.json"), manifest.to_string()).unwrap();
+        }
+
+        let registry = CommandRegistry::scan(&root);
+        assert!(registry.get("tts_kokoro.preview").is_some());
+        assert!(registry.get("host.hidden").is_none());
+        let _ = fs::remove_dir_all(root);
+    }
+}
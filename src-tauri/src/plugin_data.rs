@@ -0,0 +1,172 @@
+//! src-tauri/src/plugin_data.rs
+//! ============================
+//! Data directories managed for each plugin.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Every plugin gets its own folder for caches, downloaded weights, and
+//! scratch files under the app data directory, so nothing is written into
+//! the `plugins/` source tree:
+//!
+//!     <data>/plugin_data/
+//!         tts_kokoro/
+//!         stt_moonshine_plugin/
+//!
+//! The folder is created before `plugin/load` (or `plugin/swap`) and its
+//! path is passed to the host as `data_dir`; plugins get it from
+//! `host_rpc.plugin_data_dir()`. `plugin_data_usage` reports its size and
+//! `plugin_data_clear` empties it while the plugin is not loaded.
+//!
+//! Usage:
+//!     ```rust
+//!     let dir = plugin_data::provision(&root, "tts_kokoro")?;
+//!     let usage = plugin_data::usage(&root, "tts_kokoro")?;
+//!     plugin_data::clear(&root, "tts_kokoro")?;
+//!     ```
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Size of a plugin's data directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginDataUsage {
+    /// Plugin name
+    pub plugin: String,
+    /// Data directory
+    pub path: PathBuf,
+    /// Total size of the files in bytes
+    pub size_bytes: u64,
+    /// Number of files
+    pub files: u64,
+}
+
+// ============================================
+// DATA DIRECTORIES
+// ============================================
+
+/// Data directory of a plugin under `root` (not created).
+///
+/// # Errors
+///
+/// A plugin name that is empty or not a single path component.
+pub fn data_dir(root: &Path, plugin: &str) -> Result<PathBuf, String> {
+    let valid =
+        !plugin.is_empty() && !plugin.starts_with('.') && !plugin.contains(['/', '\\', ':']);
+    if valid {
+        Ok(root.join(plugin))
+    } else {
+        Err(format!("Invalid plugin name: {plugin:?}"))
+    }
+}
+
+/// Create a plugin's data directory if needed and return its path.
+pub fn provision(root: &Path, plugin: &str) -> Result<PathBuf, String> {
+    let dir = data_dir(root, plugin)?;
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {dir:?}: {e}"))?;
+    Ok(dir)
+}
+
+/// Size and file count of a plugin's data directory (zero if missing).
+pub fn usage(root: &Path, plugin: &str) -> Result<PluginDataUsage, String> {
+    let dir = data_dir(root, plugin)?;
+    let (size_bytes, files) = tally(&dir);
+    Ok(PluginDataUsage {
+        plugin: plugin.to_string(),
+        path: dir,
+        size_bytes,
+        files,
+    })
+}
+
+/// Delete everything in a plugin's data directory, keeping the directory.
+///
+/// # Returns
+///
+/// The usage before clearing.
+pub fn clear(root: &Path, plugin: &str) -> Result<PluginDataUsage, String> {
+    let before = usage(root, plugin)?;
+    let Ok(entries) = fs::read_dir(&before.path) else {
+        return Ok(before);
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let removed = if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            fs::remove_dir_all(&path)
+        } else {
+            fs::remove_file(&path)
+        };
+        removed.map_err(|e| format!("Failed to remove {path:?}: {e}"))?;
+    }
+    log::info!(
+        "Cleared data of plugin {plugin} ({} bytes)",
+        before.size_bytes
+    );
+    Ok(before)
+}
+
+/// Total size and number of files under a directory (symlinks not followed).
+fn tally(dir: &Path) -> (u64, u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return (0, 0);
+    };
+    let mut size = 0;
+    let mut files = 0;
+    for entry in entries.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            let (dir_size, dir_files) = tally(&entry.path());
+            size += dir_size;
+            files += dir_files;
+        } else {
+            size += entry.metadata().map_or(0, |m| m.len());
+            files += 1;
+        }
+    }
+    (size, files)
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_rejects_paths_as_names() {
+        let root = temp_dir("plugin_data");
+        for name in ["", "..", ".hidden", "a/b", "a\\b", "C:x"] {
+            assert!(data_dir(&root, name).is_err(), "{name}");
+        }
+        assert_eq!(data_dir(&root, "tts").unwrap(), root.join("tts"));
+    }
+
+    #[test]
+    fn test_usage_and_clear() {
+        let root = temp_dir("plugin_data");
+        assert_eq!(usage(&root, "tts").unwrap().files, 0);
+
+        let dir = provision(&root, "tts").unwrap();
+        fs::write(dir.join("a.bin"), [0u8; 10]).unwrap();
+        fs::create_dir_all(dir.join("cache/nested")).unwrap();
+        fs::write(dir.join("cache/nested/b.bin"), [0u8; 5]).unwrap();
+
+        let used = usage(&root, "tts").unwrap();
+        assert_eq!((used.size_bytes, used.files), (15, 2));
+
+        let cleared = clear(&root, "tts").unwrap();
+        assert_eq!(cleared, used);
+        assert!(dir.is_dir());
+        assert_eq!(usage(&root, "tts").unwrap().files, 0);
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,242 @@
+//! src-tauri/src/plugin_graph.rs
+//! =============================
+//! Dependencies between plugins.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A manifest lists the plugins it needs in `plugin_dependencies`:
+//!
+//! ```json
+//! { "name": "llm_voice_plugin", "plugin_dependencies": ["stt_moonshine_plugin"] }
+//! ```
+//!
+//! The graph is built from the manifests under `plugins/` whenever it is
+//! needed, so it always matches what is installed. `plugin_load` and the
+//! plugins loaded at startup (`plugins.load_on_startup`) load dependencies
+//! first, in topological order; `plugin_unload` refuses to unload a plugin
+//! that loaded plugins depend on. Missing dependencies and cycles are
+//! errors.
+//!
+//! Usage:
+//!     ```rust
+//!     let graph = DependencyGraph::scan(&project_root);
+//!     for plugin in graph.load_order(&["llm_voice_plugin".to_string()])? {
+//!         load(&plugin).await?;
+//!     }
+//!     let dependents = graph.dependents("stt_moonshine_plugin");
+//!     ```
+
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Manifest field listing the plugins a plugin needs
+pub const DEPENDENCIES_FIELD: &str = "plugin_dependencies";
+
+// ============================================
+// DEPENDENCY GRAPH
+// ============================================
+
+/// Plugins and the plugins they depend on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyGraph {
+    /// Direct dependencies by plugin name
+    dependencies: BTreeMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    /// Build a graph from direct dependencies.
+    pub fn new(dependencies: BTreeMap<String, Vec<String>>) -> Self {
+        Self { dependencies }
+    }
+
+    /// Read the manifests of the plugins in `project_root/plugins`.
+    ///
+    /// Folders starting with `.` or `_` are skipped, like the host does.
+    pub fn scan(project_root: &Path) -> Self {
+        let mut dependencies = BTreeMap::new();
+        let Ok(entries) = fs::read_dir(project_root.join("plugins")) else {
+            return Self::default();
+        };
+        for entry in entries.flatten() {
+            let folder = entry.file_name().to_string_lossy().to_string();
+            if folder.starts_with('.') || folder.starts_with('_') {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(entry.path().join("manifest.json")) else {
+                continue;
+            };
+            let Ok(manifest) = serde_json::from_str::<Value>(&content) else {
+                continue;
+            };
+            let Some(name) = manifest.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let needs = manifest
+                .get(DEPENDENCIES_FIELD)
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect();
+            dependencies.insert(name.to_string(), needs);
+        }
+        Self { dependencies }
+    }
+
+    /// Plugins a plugin depends on directly.
+    pub fn dependencies(&self, plugin: &str) -> &[String] {
+        self.dependencies.get(plugin).map_or(&[], Vec::as_slice)
+    }
+
+    /// Plugins that depend directly on a plugin, sorted by name.
+    pub fn dependents(&self, plugin: &str) -> Vec<String> {
+        self.dependencies
+            .iter()
+            .filter(|(_, needs)| needs.iter().any(|need| need == plugin))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+
+    /// The plugins and everything they depend on, dependencies first.
+    ///
+    /// # Errors
+    ///
+    /// A dependency that is not installed, or plugins that depend on each
+    /// other (the cycle is named).
+    pub fn load_order(&self, plugins: &[String]) -> Result<Vec<String>, String> {
+        let mut order = Vec::new();
+        let mut done = BTreeSet::new();
+        for plugin in plugins {
+            self.visit(plugin, &mut Vec::new(), &mut done, &mut order)?;
+        }
+        Ok(order)
+    }
+
+    /// Depth-first visit appending a plugin after its dependencies.
+    fn visit(
+        &self,
+        plugin: &str,
+        path: &mut Vec<String>,
+        done: &mut BTreeSet<String>,
+        order: &mut Vec<String>,
+    ) -> Result<(), String> {
+        if done.contains(plugin) {
+            return Ok(());
+        }
+        if let Some(start) = path.iter().position(|p| p == plugin) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(plugin.to_string());
+            return Err(format!(
+                "Plugins depend on each other: {}",
+                cycle.join(" -> ")
+            ));
+        }
+        let Some(needs) = self.dependencies.get(plugin) else {
+            return Err(match path.last() {
+                Some(dependent) => {
+                    format!("Plugin {dependent} depends on {plugin}, which is not installed")
+                }
+                None => format!("Plugin {plugin} is not installed"),
+            });
+        };
+
+        path.push(plugin.to_string());
+        for need in needs {
+            self.visit(need, path, done, order)?;
+        }
+        path.pop();
+
+        done.insert(plugin.to_string());
+        order.push(plugin.to_string());
+        Ok(())
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &[&str])]) -> DependencyGraph {
+        DependencyGraph::new(
+            edges
+                .iter()
+                .map(|(name, needs)| {
+                    let needs = needs.iter().map(|need| (*need).to_string()).collect();
+                    ((*name).to_string(), needs)
+                })
+                .collect(),
+        )
+    }
+
+    fn names(plugins: &[&str]) -> Vec<String> {
+        plugins.iter().map(|p| (*p).to_string()).collect()
+    }
+
+    #[test]
+    fn test_load_order() {
+        let graph = graph(&[
+            ("app", &["llm", "tts"]),
+            ("llm", &["embed"]),
+            ("tts", &["embed"]),
+            ("embed", &[]),
+            ("other", &[]),
+        ]);
+        assert_eq!(
+            graph.load_order(&names(&["app"])).unwrap(),
+            names(&["embed", "llm", "tts", "app"])
+        );
+        assert_eq!(
+            graph.load_order(&names(&["tts", "other", "llm"])).unwrap(),
+            names(&["embed", "tts", "other", "llm"])
+        );
+        assert_eq!(graph.dependents("embed"), names(&["llm", "tts"]));
+        assert_eq!(graph.dependencies("app"), names(&["llm", "tts"]).as_slice());
+    }
+
+    #[test]
+    fn test_missing_and_cyclic_dependencies() {
+        let graph = graph(&[
+            ("a", &["b"]),
+            ("b", &["c"]),
+            ("c", &["a"]),
+            ("d", &["gone"]),
+        ]);
+        let cycle = graph.load_order(&names(&["a"])).unwrap_err();
+        assert!(cycle.contains("a -> b -> c -> a"), "{cycle}");
+        let missing = graph.load_order(&names(&["d"])).unwrap_err();
+        assert!(missing.contains("d depends on gone"), "{missing}");
+        assert!(graph.load_order(&names(&["nope"])).is_err());
+    }
+
+    #[test]
+    fn test_scan() {
+        let root = std::env::temp_dir().join(format!("af_graph_{}", uuid::Uuid::new_v4()));
+        for (folder, manifest) in [
+            ("app", r#"{"name": "app", "plugin_dependencies": ["base"]}"#),
+            ("base", r#"{"name": "base"}"#),
+            ("_host", r#"{"name": "host"}"#),
+        ] {
+            let dir = root.join("plugins").join(folder);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("manifest.json"), manifest).unwrap();
+        }
+
+        let graph = DependencyGraph::scan(&root);
+        assert_eq!(
+            graph.load_order(&names(&["app"])).unwrap(),
+            names(&["base", "app"])
+        );
+        assert!(graph.load_order(&names(&["host"])).is_err());
+    }
+}
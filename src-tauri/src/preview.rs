@@ -0,0 +1,373 @@
+//! src-tauri/src/preview.rs
+//! ========================
+//! Localhost HTTP preview server for generated apps.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Each previewed app gets its own server on `127.0.0.1:<random port>`
+//! serving the `html` export layout (see export.rs) from memory, so the
+//! frontend can load it in an iframe instead of eval-ing blobs.
+//!
+//! Live reload: every served `index.html` subscribes to the server-sent
+//! events endpoint `/__preview/events`; `PreviewManager::update` swaps the
+//! served files and pushes a `reload` event to all open pages.
+//! `PreviewManager::stop` ends those event streams and shuts the server
+//! down gracefully, so no connection outlives its preview.
+//!
+//! Usage:
+//!     ```rust
+//!     let files = export::project_files(&definition, &artifacts, ExportTemplate::Html)?;
+//!     let info = previews.start(&app_id, files).await?;
+//!     // ... after the app is saved again
+//!     previews.update(&app_id, new_files);
+//!     previews.stop(&app_id);
+//!     ```
+
+use axum::extract::State as AxumState;
+use axum::http::{header, StatusCode, Uri};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::Router;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::Ipv4Addr;
+use std::sync::{Arc, Mutex, RwLock};
+use tokio::sync::{broadcast, watch};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Server-sent events endpoint used for live reload
+const EVENTS_PATH: &str = "/__preview/events";
+
+/// Script injected into `index.html` to reload on change
+const LIVE_RELOAD_SCRIPT: &str = "<script>new EventSource('/__preview/events')\
+    .addEventListener('reload', () => location.reload());</script>";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A running preview.
+#[derive(Debug, Clone, Serialize)]
+pub struct PreviewInfo {
+    /// App id
+    pub app_id: String,
+    /// URL to load in the iframe
+    pub url: String,
+    /// Bound port
+    pub port: u16,
+    /// Start timestamp (RFC 3339)
+    pub started_at: String,
+}
+
+/// Served files keyed by path (without leading slash).
+type Files = Arc<RwLock<HashMap<String, Vec<u8>>>>;
+
+/// State shared with the HTTP handlers.
+#[derive(Clone)]
+struct ServeState {
+    files: Files,
+    reload: broadcast::Sender<()>,
+    stopped: watch::Receiver<bool>,
+}
+
+/// Server bookkeeping.
+struct PreviewServer {
+    info: PreviewInfo,
+    state: ServeState,
+    stop: watch::Sender<bool>,
+}
+
+// ============================================
+// PREVIEW MANAGER
+// ============================================
+
+/// Manages preview servers, at most one per app.
+#[derive(Clone, Default)]
+pub struct PreviewManager {
+    servers: Arc<Mutex<HashMap<String, PreviewServer>>>,
+}
+
+impl PreviewManager {
+    /// Create an empty manager.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a preview server for an app.
+    ///
+    /// If one is already running its files are replaced and open pages reload.
+    pub async fn start(
+        &self,
+        app_id: &str,
+        files: Vec<(String, Vec<u8>)>,
+    ) -> Result<PreviewInfo, String> {
+        let files = into_served_files(files);
+        if let Some(info) = self.replace(app_id, files.clone()) {
+            return Ok(info);
+        }
+
+        let listener = tokio::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .map_err(|e| format!("Failed to bind preview server: {e}"))?;
+        let port = listener
+            .local_addr()
+            .map_err(|e| format!("Failed to read preview address: {e}"))?
+            .port();
+
+        let (stop, stopped) = watch::channel(false);
+        let state = ServeState {
+            files: Arc::new(RwLock::new(files)),
+            reload: broadcast::channel(16).0,
+            stopped,
+        };
+        let router = Router::new()
+            .route(EVENTS_PATH, get(serve_events))
+            .fallback(serve_file)
+            .with_state(state.clone());
+
+        let shutdown = wait_stopped(state.stopped.clone());
+        let task = tokio::spawn(async move {
+            let server = axum::serve(listener, router).with_graceful_shutdown(shutdown);
+            if let Err(e) = server.await {
+                log::error!("Preview server on port {port} failed: {e}");
+            }
+        });
+
+        let info = PreviewInfo {
+            app_id: app_id.to_string(),
+            url: format!("http://127.0.0.1:{port}/"),
+            port,
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let mut servers = self.servers.lock().unwrap();
+        if let Some(existing) = servers.get(app_id) {
+            // Lost a race with a concurrent start
+            task.abort();
+            return Ok(existing.info.clone());
+        }
+        servers.insert(
+            app_id.to_string(),
+            PreviewServer {
+                info: info.clone(),
+                state,
+                stop,
+            },
+        );
+
+        log::info!("Preview for {app_id} serving at {}", info.url);
+        Ok(info)
+    }
+
+    /// Replace a running preview's files and trigger a reload.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no preview is running for the app.
+    pub fn update(&self, app_id: &str, files: Vec<(String, Vec<u8>)>) -> bool {
+        self.replace(app_id, into_served_files(files)).is_some()
+    }
+
+    /// Stop a preview server, closing its live-reload streams.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no preview was running for the app.
+    pub fn stop(&self, app_id: &str) -> bool {
+        let Some(server) = self.servers.lock().unwrap().remove(app_id) else {
+            return false;
+        };
+        // No receivers just means the server already exited
+        let _ = server.stop.send(true);
+        log::info!("Preview for {app_id} stopped");
+        true
+    }
+
+    /// Whether a preview is running for an app.
+    pub fn is_running(&self, app_id: &str) -> bool {
+        self.servers.lock().unwrap().contains_key(app_id)
+    }
+
+    /// List running previews.
+    pub fn list(&self) -> Vec<PreviewInfo> {
+        self.servers
+            .lock()
+            .unwrap()
+            .values()
+            .map(|server| server.info.clone())
+            .collect()
+    }
+
+    /// Swap the files of a running preview and notify open pages.
+    fn replace(&self, app_id: &str, files: HashMap<String, Vec<u8>>) -> Option<PreviewInfo> {
+        let servers = self.servers.lock().unwrap();
+        let server = servers.get(app_id)?;
+        *server.state.files.write().unwrap() = files;
+        // No receivers just means no page is open
+        let _ = server.state.reload.send(());
+        log::debug!("Preview for {app_id} reloaded");
+        Some(server.info.clone())
+    }
+}
+
+// ============================================
+// HTTP HANDLERS
+// ============================================
+
+/// Serve a file from memory (`/` maps to `index.html`).
+async fn serve_file(AxumState(state): AxumState<ServeState>, uri: Uri) -> Response {
+    let path = match uri.path().trim_start_matches('/') {
+        "" => "index.html",
+        path => path,
+    };
+
+    let files = state.files.read().unwrap();
+    match files.get(path) {
+        Some(data) => (
+            [
+                (header::CONTENT_TYPE, content_type(path)),
+                (header::CACHE_CONTROL, "no-store"),
+            ],
+            data.clone(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Stream `reload` events to the page.
+async fn serve_events(
+    AxumState(state): AxumState<ServeState>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = futures::stream::unfold(state.reload.subscribe(), |mut rx| async move {
+        match rx.recv().await {
+            Ok(()) | Err(broadcast::error::RecvError::Lagged(_)) => {
+                Some((Ok(Event::default().event("reload").data("")), rx))
+            }
+            Err(broadcast::error::RecvError::Closed) => None,
+        }
+    })
+    .take_until(wait_stopped(state.stopped));
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+/// Resolve once the preview is stopped (or its manager entry is gone).
+async fn wait_stopped(mut stopped: watch::Receiver<bool>) {
+    // An error means the sender was dropped, which also ends the preview
+    let _ = stopped.wait_for(|stopped| *stopped).await;
+}
+
+/// Index files by path and inject the live-reload script into HTML pages.
+fn into_served_files(files: Vec<(String, Vec<u8>)>) -> HashMap<String, Vec<u8>> {
+    files
+        .into_iter()
+        .map(|(path, data)| {
+            if path.ends_with(".html") {
+                let html = String::from_utf8_lossy(&data);
+                let html = match html.rfind("</body>") {
+                    Some(at) => format!("{}{LIVE_RELOAD_SCRIPT}\n{}", &html[..at], &html[at..]),
+                    None => format!("{html}{LIVE_RELOAD_SCRIPT}\n"),
+                };
+                (path, html.into_bytes())
+            } else {
+                (path, data)
+            }
+        })
+        .collect()
+}
+
+/// Content type for a served path.
+fn content_type(path: &str) -> &'static str {
+    let extension = path.rsplit('.').next().unwrap_or_default();
+    match extension.to_ascii_lowercase().as_str() {
+        "html" => "text/html; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "json" => "application/json",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "wav" => "audio/wav",
+        "mp3" => "audio/mpeg",
+        "wasm" => "application/wasm",
+        _ => "application/octet-stream",
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(body: &str) -> Vec<(String, Vec<u8>)> {
+        vec![
+            (
+                "index.html".to_string(),
+                format!("<html><body>{body}</body></html>").into_bytes(),
+            ),
+            ("app.js".to_string(), b"export default 1;".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn test_content_type() {
+        assert_eq!(content_type("index.html"), "text/html; charset=utf-8");
+        assert_eq!(content_type("img/Logo.PNG"), "image/png");
+        assert_eq!(content_type("README"), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_live_reload_injected() {
+        let served = into_served_files(files("hi"));
+        let html = String::from_utf8(served["index.html"].clone()).unwrap();
+        assert!(html.contains("EventSource"));
+        assert!(html.ends_with("</body></html>"));
+        assert_eq!(served["app.js"], b"export default 1;");
+    }
+
+    #[tokio::test]
+    async fn test_serves_and_stops() {
+        let previews = PreviewManager::new();
+        let info = previews.start("app", files("first")).await.unwrap();
+
+        let body = reqwest::get(&info.url).await.unwrap().text().await.unwrap();
+        assert!(body.contains("first"));
+
+        // Restarting reuses the server with new content
+        let again = previews.start("app", files("second")).await.unwrap();
+        assert_eq!(again.port, info.port);
+        let body = reqwest::get(&info.url).await.unwrap().text().await.unwrap();
+        assert!(body.contains("second"));
+
+        let missing = reqwest::get(format!("{}missing.js", info.url)).await.unwrap();
+        assert_eq!(missing.status(), reqwest::StatusCode::NOT_FOUND);
+
+        assert!(previews.stop("app"));
+        assert!(!previews.is_running("app"));
+        assert!(!previews.update("app", files("third")));
+    }
+
+    #[tokio::test]
+    async fn test_stop_closes_event_streams() {
+        let previews = PreviewManager::new();
+        let info = previews.start("app", files("first")).await.unwrap();
+        let events_url = format!("http://127.0.0.1:{}{EVENTS_PATH}", info.port);
+        let events = reqwest::get(&events_url).await.unwrap();
+        assert_eq!(events.status(), reqwest::StatusCode::OK);
+
+        assert!(previews.stop("app"));
+        let body = tokio::time::timeout(std::time::Duration::from_secs(5), events.text()).await;
+        assert!(body.is_ok(), "event stream still open after stop");
+    }
+}
@@ -0,0 +1,387 @@
+//! src-tauri/src/project.rs
+//! ========================
+//! Project root selection and recent-project tracking.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A "project" is a directory containing a `plugins/` folder. The active
+//! project determines:
+//! - The working directory of the Python plugin host (and so plugin discovery)
+//! - The `.env` key store used by `commands::secrets`, which lives in the
+//!   app data directory rather than the project (see `crate::paths`)
+//!
+//! Recently opened projects are persisted as JSON in the app config directory.
+//!
+//! Usage:
+//!     ```rust
+//!     let manager = ProjectManager::new(discover_project_root(), config_dir)
+//!         .with_secrets(paths.clone());
+//!     let info = manager.open("/path/to/project")?;
+//!     let env_path = manager.env_path();
+//!     ```
+
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::paths::AppPaths;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Maximum number of entries kept in the recent-projects list
+pub const MAX_RECENT_PROJECTS: usize = 10;
+
+/// File name of the recent-projects store inside the app config directory
+const RECENTS_FILE: &str = "recent_projects.json";
+
+// ============================================
+// PROJECT ROOT DISCOVERY
+// ============================================
+
+/// Check whether a directory looks like a project root (has `plugins/`).
+fn is_project_root(dir: &Path) -> bool {
+    dir.join("plugins").is_dir()
+}
+
+/// Discover the default project root (the directory containing `plugins/`).
+///
+/// Used when no project has been explicitly opened. It checks:
+/// 1. Parent directories of the executable path
+/// 2. Current working directory and its parent (covers running from src-tauri)
+/// 3. Ancestors of the current working directory with a `src-tauri` sibling
+pub fn discover_project_root() -> PathBuf {
+    // During development, the executable runs from src-tauri/target/debug
+    // We need to go up to find the project root (where plugins/ is)
+    let exe_path = std::env::current_exe().unwrap_or_default();
+    log::debug!("Executable path: {exe_path:?}");
+
+    for dir in exe_path.ancestors().skip(1).take(10) {
+        if is_project_root(dir) {
+            log::info!("Found project root: {dir:?}");
+            return dir.to_path_buf();
+        }
+    }
+
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    if is_project_root(&cwd) {
+        return cwd;
+    }
+
+    // Try parent of cwd (common when running from src-tauri)
+    if let Some(parent) = cwd.parent() {
+        if is_project_root(parent) {
+            return parent.to_path_buf();
+        }
+    }
+
+    // Look for a src-tauri sibling (handles running from target/debug)
+    for dir in cwd.ancestors().take(5) {
+        if dir.join("src-tauri").exists() && is_project_root(dir) {
+            return dir.to_path_buf();
+        }
+    }
+
+    log::warn!("Could not find plugins directory, using current working directory: {cwd:?}");
+    cwd
+}
+
+/// Get a display name for a project directory.
+fn project_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Entry in the recent-projects list.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RecentProject {
+    /// Absolute project path
+    pub path: String,
+    /// Display name (directory name)
+    pub name: String,
+    /// ISO timestamp of when the project was last opened
+    pub last_opened: String,
+}
+
+/// Information about the active project returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectInfo {
+    /// Absolute project path
+    pub path: String,
+    /// Display name (directory name)
+    pub name: String,
+    /// Whether the project contains a `plugins/` directory
+    pub has_plugins: bool,
+    /// Whether this is the auto-discovered default project
+    pub is_default: bool,
+}
+
+// ============================================
+// PROJECT MANAGER
+// ============================================
+
+/// Tracks the active project root and the recent-projects list.
+///
+/// Clones share the active project root and the recent projects.
+#[derive(Debug, Clone)]
+pub struct ProjectManager {
+    /// Auto-discovered root used when no project is open
+    default_root: PathBuf,
+
+    /// Currently active project root
+    current: Arc<RwLock<PathBuf>>,
+
+    /// Recently opened projects (most recent first)
+    recents: Arc<RwLock<Vec<RecentProject>>>,
+
+    /// Where recents are persisted (None disables persistence)
+    recents_path: Option<PathBuf>,
+
+    /// App directories locating each project's `.env`
+    paths: AppPaths,
+}
+
+impl ProjectManager {
+    /// Create a project manager rooted at `default_root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `default_root` - Root used until a project is opened
+    /// * `config_dir` - App config directory for persisting recents
+    pub fn new(default_root: PathBuf, config_dir: Option<PathBuf>) -> Self {
+        let recents_path = config_dir.map(|dir| dir.join(RECENTS_FILE));
+        let recents = recents_path
+            .as_deref()
+            .map(load_recents)
+            .unwrap_or_default();
+
+        Self {
+            current: Arc::new(RwLock::new(default_root.clone())),
+            default_root,
+            recents: Arc::new(RwLock::new(recents)),
+            recents_path,
+            paths: AppPaths::default(),
+        }
+    }
+
+    /// Keep API keys in the app data directory, copying the default
+    /// project's legacy `.env` there.
+    pub fn with_secrets(mut self, paths: AppPaths) -> Self {
+        paths.migrate_secrets(&self.default_root);
+        self.paths = paths;
+        self
+    }
+
+    /// Get the root used when no project is open.
+    pub fn default_root(&self) -> PathBuf {
+        self.default_root.clone()
+    }
+
+    /// Get the active project root.
+    pub fn current_root(&self) -> PathBuf {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Get the `.env` path for the active project.
+    pub fn env_path(&self) -> PathBuf {
+        self.paths.secrets_file(&self.current_root())
+    }
+
+    /// Get information about the active project.
+    pub fn info(&self) -> ProjectInfo {
+        let root = self.current_root();
+        ProjectInfo {
+            path: root.to_string_lossy().to_string(),
+            name: project_name(&root),
+            has_plugins: is_project_root(&root),
+            is_default: root == self.default_root,
+        }
+    }
+
+    /// Open a project directory, making it the active root.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ProjectInfo)` - Project opened
+    /// * `Err(String)` - Path does not exist or is not a directory
+    pub fn open(&self, path: &str) -> Result<ProjectInfo, String> {
+        let root = self.resolve(path)?;
+        Ok(self.activate(root))
+    }
+
+    /// Check a project directory and copy its legacy `.env` to the secrets
+    /// folder, without making it the active root.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(PathBuf)` - Canonical project root, for `activate`
+    /// * `Err(String)` - Path does not exist or is not a directory
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        let root = fs::canonicalize(path)
+            .map_err(|e| format!("Cannot open project {path}: {e}"))?;
+
+        if !root.is_dir() {
+            return Err(format!("Project path is not a directory: {path}"));
+        }
+
+        if !is_project_root(&root) {
+            log::warn!("Opened project has no plugins/ directory: {root:?}");
+        }
+
+        self.paths.migrate_secrets(&root);
+        Ok(root)
+    }
+
+    /// Make a root from `resolve` the active one and add it to the recents.
+    pub fn activate(&self, root: PathBuf) -> ProjectInfo {
+        self.remember(&root);
+        log::info!("Project opened: {root:?}");
+        *self.current.write().unwrap() = root;
+        self.info()
+    }
+
+    /// Close the active project, reverting to the default root.
+    pub fn close(&self) -> ProjectInfo {
+        *self.current.write().unwrap() = self.default_root.clone();
+        log::info!("Project closed, reverted to default: {:?}", self.default_root);
+        self.info()
+    }
+
+    /// Get recently opened projects (most recent first).
+    pub fn recent(&self) -> Vec<RecentProject> {
+        self.recents.read().unwrap().clone()
+    }
+
+    /// Record a project at the front of the recents list and persist it.
+    fn remember(&self, root: &Path) {
+        let path = root.to_string_lossy().to_string();
+        let mut recents = self.recents.write().unwrap();
+
+        recents.retain(|r| r.path != path);
+        recents.insert(
+            0,
+            RecentProject {
+                name: project_name(root),
+                path,
+                last_opened: Utc::now().to_rfc3339(),
+            },
+        );
+        recents.truncate(MAX_RECENT_PROJECTS);
+
+        if let Some(ref file) = self.recents_path {
+            if let Err(e) = save_recents(file, &recents) {
+                log::warn!("Failed to persist recent projects: {e}");
+            }
+        }
+    }
+}
+
+// ============================================
+// PERSISTENCE
+// ============================================
+
+/// Load the recents list, returning an empty list if missing or invalid.
+fn load_recents(path: &Path) -> Vec<RecentProject> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid recent projects file {path:?}: {e}");
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Write the recents list to disk.
+fn save_recents(path: &Path, recents: &[RecentProject]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(recents)
+        .map_err(|e| format!("Failed to serialize recents: {e}"))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_open_and_close() {
+        let default_root = temp_dir("default");
+        let other = temp_dir("other");
+        fs::create_dir_all(other.join("plugins")).unwrap();
+
+        let manager = ProjectManager::new(default_root.clone(), None);
+        assert!(manager.info().is_default);
+
+        let info = manager.open(other.to_str().unwrap()).unwrap();
+        assert!(info.has_plugins);
+        assert!(!info.is_default);
+        assert_eq!(manager.env_path(), fs::canonicalize(&other).unwrap().join(".env"));
+
+        let info = manager.close();
+        assert!(info.is_default);
+        assert_eq!(manager.current_root(), default_root);
+    }
+
+    #[test]
+    fn test_resolve_keeps_active_root() {
+        let default_root = temp_dir("default");
+        let other = temp_dir("other");
+
+        let manager = ProjectManager::new(default_root.clone(), None);
+        let root = manager.resolve(other.to_str().unwrap()).unwrap();
+        assert_eq!(root, fs::canonicalize(&other).unwrap());
+        assert_eq!(manager.current_root(), default_root);
+        assert!(manager.recent().is_empty());
+
+        manager.activate(root.clone());
+        assert_eq!(manager.current_root(), root);
+        assert_eq!(manager.recent().len(), 1);
+    }
+
+    #[test]
+    fn test_open_missing_path() {
+        let manager = ProjectManager::new(temp_dir("default"), None);
+        assert!(manager.open("/definitely/not/a/real/project").is_err());
+    }
+
+    #[test]
+    fn test_recents_dedupe_and_persist() {
+        let config_dir = temp_dir("config");
+        let project = temp_dir("project");
+
+        let manager = ProjectManager::new(temp_dir("default"), Some(config_dir.clone()));
+        manager.open(project.to_str().unwrap()).unwrap();
+        manager.open(project.to_str().unwrap()).unwrap();
+        assert_eq!(manager.recent().len(), 1);
+
+        // A fresh manager reads the persisted list
+        let reloaded = ProjectManager::new(temp_dir("default"), Some(config_dir));
+        assert_eq!(reloaded.recent(), manager.recent());
+    }
+
+    #[test]
+    fn test_recents_truncated() {
+        let manager = ProjectManager::new(temp_dir("default"), None);
+        for i in 0..(MAX_RECENT_PROJECTS + 3) {
+            let dir = temp_dir(&format!("p{i}"));
+            manager.open(dir.to_str().unwrap()).unwrap();
+        }
+        assert_eq!(manager.recent().len(), MAX_RECENT_PROJECTS);
+    }
+}
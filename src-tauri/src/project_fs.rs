@@ -0,0 +1,319 @@
+//! src-tauri/src/project_fs.rs
+//! ===========================
+//! File access for generated apps, confined to the project.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The webview has no filesystem access of its own. The `fs_*` commands
+//! give generated apps read, write, list, and delete access to the active
+//! project root and the directories granted in `fs.allowed_dirs`, and
+//! nothing else.
+//!
+//! Relative paths are resolved against the project root. Every path is
+//! canonicalized (symlinks and `..` resolved) before it is checked, so a
+//! link inside the project can't lead outside it. For paths that don't
+//! exist yet (writes), the nearest existing ancestor is canonicalized and
+//! the rest may not contain `..`. The roots themselves can't be deleted.
+//!
+//! Usage:
+//!     ```rust
+//!     let scope = FsScope::new(&project.current_root(), &settings.get().fs.allowed_dirs);
+//!     scope.write("data/notes.md", "# Notes", true)?;
+//!     let text = scope.read("data/notes.md")?;
+//!     let entries = scope.list("data")?;
+//!     scope.delete("data", true)?;
+//!     ```
+
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Largest file `read` returns
+pub const MAX_READ_BYTES: u64 = 10 * 1024 * 1024;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A file or directory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FsEntry {
+    /// File name
+    pub name: String,
+    /// Full path
+    pub path: PathBuf,
+    /// Whether it is a directory
+    pub is_dir: bool,
+    /// Size in bytes (0 for directories)
+    pub size_bytes: u64,
+    /// Last modification (RFC 3339), if known
+    pub modified: Option<String>,
+}
+
+/// A refused or failed file operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FsError {
+    /// Error code (`PATH_NOT_ALLOWED`, `FILE_TOO_LARGE`, or `FS_ERROR`)
+    pub code: &'static str,
+    /// Human-readable message
+    pub message: String,
+}
+
+impl FsError {
+    fn new(code: &'static str, message: String) -> Self {
+        Self { code, message }
+    }
+
+    fn io(action: &str, path: &Path, error: &std::io::Error) -> Self {
+        Self::new("FS_ERROR", format!("Failed to {action} {path:?}: {error}"))
+    }
+}
+
+impl fmt::Display for FsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// Describe a path for `FsEntry`.
+fn entry(path: &Path) -> Result<FsEntry, FsError> {
+    let metadata = fs::metadata(path).map_err(|e| FsError::io("read", path, &e))?;
+    Ok(FsEntry {
+        name: path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: path.to_path_buf(),
+        is_dir: metadata.is_dir(),
+        size_bytes: if metadata.is_dir() { 0 } else { metadata.len() },
+        modified: metadata
+            .modified()
+            .ok()
+            .map(|time| chrono::DateTime::<chrono::Utc>::from(time).to_rfc3339()),
+    })
+}
+
+// ============================================
+// SCOPE
+// ============================================
+
+/// The directories file commands may touch.
+#[derive(Debug, Clone)]
+pub struct FsScope {
+    /// Canonical roots, the project first
+    roots: Vec<PathBuf>,
+}
+
+impl FsScope {
+    /// Scope over a project root and granted directories.
+    ///
+    /// Directories that don't exist are left out.
+    pub fn new(project_root: &Path, granted: &[String]) -> Self {
+        let roots = std::iter::once(project_root.to_path_buf())
+            .chain(granted.iter().map(PathBuf::from))
+            .filter_map(|root| match root.canonicalize() {
+                Ok(root) => Some(root),
+                Err(e) => {
+                    log::warn!("Ignoring file access root {root:?}: {e}");
+                    None
+                }
+            })
+            .collect();
+        Self { roots }
+    }
+
+    /// Resolve a path and check that it lies within a root.
+    ///
+    /// # Returns
+    ///
+    /// The canonical path, or `PATH_NOT_ALLOWED`.
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, FsError> {
+        let not_allowed = || {
+            FsError::new(
+                "PATH_NOT_ALLOWED",
+                format!("{path} is outside the project and the granted directories"),
+            )
+        };
+        let requested = Path::new(path);
+        let joined = if requested.is_absolute() {
+            requested.to_path_buf()
+        } else {
+            self.roots.first().ok_or_else(not_allowed)?.join(requested)
+        };
+
+        // Canonicalize the part that exists; the rest must be plain names
+        let mut existing = joined.as_path();
+        let mut missing = Vec::new();
+        while fs::symlink_metadata(existing).is_err() {
+            let (Some(name), Some(parent)) = (existing.file_name(), existing.parent()) else {
+                return Err(not_allowed());
+            };
+            missing.push(name);
+            existing = parent;
+        }
+        let mut resolved = existing
+            .canonicalize()
+            .map_err(|e| FsError::io("resolve", existing, &e))?;
+        resolved.extend(missing.into_iter().rev());
+
+        if self.roots.iter().any(|root| resolved.starts_with(root)) {
+            Ok(resolved)
+        } else {
+            Err(not_allowed())
+        }
+    }
+
+    /// Read a text file.
+    ///
+    /// Files over `MAX_READ_BYTES` fail with `FILE_TOO_LARGE`.
+    pub fn read(&self, path: &str) -> Result<String, FsError> {
+        let resolved = self.resolve(path)?;
+        let size = fs::metadata(&resolved)
+            .map_err(|e| FsError::io("read", &resolved, &e))?
+            .len();
+        if size > MAX_READ_BYTES {
+            return Err(FsError::new(
+                "FILE_TOO_LARGE",
+                format!("{path} is larger than {MAX_READ_BYTES} bytes"),
+            ));
+        }
+        fs::read_to_string(&resolved).map_err(|e| FsError::io("read", &resolved, &e))
+    }
+
+    /// Write a text file, replacing it if it exists.
+    ///
+    /// Missing parent directories are created when `create_dirs` is set.
+    pub fn write(&self, path: &str, contents: &str, create_dirs: bool) -> Result<FsEntry, FsError> {
+        let resolved = self.resolve(path)?;
+        if create_dirs {
+            if let Some(parent) = resolved.parent() {
+                fs::create_dir_all(parent).map_err(|e| FsError::io("create", parent, &e))?;
+            }
+        }
+        fs::write(&resolved, contents).map_err(|e| FsError::io("write", &resolved, &e))?;
+        entry(&resolved)
+    }
+
+    /// List a directory, directories first, then by name.
+    pub fn list(&self, path: &str) -> Result<Vec<FsEntry>, FsError> {
+        let resolved = self.resolve(path)?;
+        let mut entries = fs::read_dir(&resolved)
+            .map_err(|e| FsError::io("list", &resolved, &e))?
+            .flatten()
+            .filter_map(|item| entry(&item.path()).ok())
+            .collect::<Vec<_>>();
+        entries.sort_by(|a, b| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+        Ok(entries)
+    }
+
+    /// Delete a file, or a directory (with its contents when `recursive`).
+    pub fn delete(&self, path: &str, recursive: bool) -> Result<(), FsError> {
+        let resolved = self.resolve(path)?;
+        if self.roots.contains(&resolved) {
+            return Err(FsError::new(
+                "PATH_NOT_ALLOWED",
+                format!("{path} is a root and can't be deleted"),
+            ));
+        }
+        let metadata =
+            fs::symlink_metadata(&resolved).map_err(|e| FsError::io("delete", &resolved, &e))?;
+        let result = if !metadata.is_dir() {
+            fs::remove_file(&resolved)
+        } else if recursive {
+            fs::remove_dir_all(&resolved)
+        } else {
+            fs::remove_dir(&resolved)
+        };
+        result.map_err(|e| FsError::io("delete", &resolved, &e))
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_read_write_list_delete() {
+        let project = temp_dir("project_fs");
+        let scope = FsScope::new(&project, &[]);
+
+        let written = scope.write("data/notes.md", "# Notes", true).unwrap();
+        assert_eq!(written.size_bytes, 7);
+        assert_eq!(scope.read("data/notes.md").unwrap(), "# Notes");
+        assert!(scope.write("other/x.md", "x", false).is_err());
+
+        scope.write("data/sub/a.txt", "a", true).unwrap();
+        let names: Vec<String> = scope
+            .list("data")
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+        assert_eq!(names, vec!["sub", "notes.md"]);
+
+        assert!(scope.delete("data", false).is_err());
+        scope.delete("data", true).unwrap();
+        assert!(!project.join("data").exists());
+        assert_eq!(
+            scope.delete(".", true).unwrap_err().code,
+            "PATH_NOT_ALLOWED"
+        );
+    }
+
+    #[test]
+    fn test_paths_outside_are_refused() {
+        let project = temp_dir("project_fs");
+        let granted = temp_dir("project_fs");
+        let outside = temp_dir("project_fs");
+        fs::write(outside.join("secret.txt"), "secret").unwrap();
+        let scope = FsScope::new(&project, &[granted.to_string_lossy().to_string()]);
+
+        let escape = format!(
+            "../{}/secret.txt",
+            outside.file_name().unwrap().to_string_lossy()
+        );
+        assert_eq!(scope.read(&escape).unwrap_err().code, "PATH_NOT_ALLOWED");
+        let absolute = outside.join("secret.txt");
+        assert_eq!(
+            scope.read(&absolute.to_string_lossy()).unwrap_err().code,
+            "PATH_NOT_ALLOWED"
+        );
+        assert_eq!(
+            scope.write("new/../../x.txt", "x", true).unwrap_err().code,
+            "PATH_NOT_ALLOWED"
+        );
+
+        let inside = granted.join("ok.txt");
+        scope.write(&inside.to_string_lossy(), "ok", false).unwrap();
+        assert_eq!(scope.read(&inside.to_string_lossy()).unwrap(), "ok");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_out_of_the_project_are_refused() {
+        let project = temp_dir("project_fs");
+        let outside = temp_dir("project_fs");
+        fs::write(outside.join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(&outside, project.join("link")).unwrap();
+        let scope = FsScope::new(&project, &[]);
+
+        assert_eq!(
+            scope.read("link/secret.txt").unwrap_err().code,
+            "PATH_NOT_ALLOWED"
+        );
+        assert_eq!(
+            scope.write("link/new.txt", "x", false).unwrap_err().code,
+            "PATH_NOT_ALLOWED"
+        );
+    }
+}
@@ -0,0 +1,396 @@
+//! src-tauri/src/project_tree.rs
+//! =============================
+//! Directory tree of a project, honoring .gitignore-style patterns.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The file explorer shows the tree, and it tells export what to bundle.
+//! Paths are ignored by the project's root `.gitignore` plus any
+//! extra patterns, with gitignore semantics:
+//! - blank lines and `#` comments are skipped, `\` escapes a character
+//! - `!pattern` re-includes what an earlier pattern ignored (last match wins)
+//! - a trailing `/` matches directories only
+//! - a pattern containing `/` (other than at the end) is relative to the
+//!   root; otherwise it matches at any depth
+//! - `*` and `?` don't cross `/`, `**` does, `[a-z]` is a character class
+//!
+//! `.git` is always left out. Symlinked directories are listed but not
+//! entered, and the walk stops after `MAX_ENTRIES` entries.
+//!
+//! Usage:
+//!     ```rust
+//!     let tree = project_tree::tree(&project.current_root(), Some(3), &["*.log".to_string()])?;
+//!     for child in &tree.children {
+//!         println!("{} {}", child.path, child.is_dir);
+//!     }
+//!     ```
+
+use regex::Regex;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Most entries returned in one tree
+pub const MAX_ENTRIES: usize = 20_000;
+
+/// Ignore file read from the root
+const IGNORE_FILE: &str = ".gitignore";
+
+/// Directories never listed
+const ALWAYS_IGNORED: &[&str] = &[".git"];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A file or directory in the tree.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TreeNode {
+    /// File name
+    pub name: String,
+    /// Path relative to the root, `/`-separated ("" for the root)
+    pub path: String,
+    /// Whether it is a directory
+    pub is_dir: bool,
+    /// Size in bytes (0 for directories)
+    pub size_bytes: u64,
+    /// Entries, directories first, then by name
+    pub children: Vec<TreeNode>,
+    /// Whether the directory has entries that were not listed (depth or
+    /// `MAX_ENTRIES` reached)
+    pub truncated: bool,
+}
+
+/// One parsed pattern.
+#[derive(Debug, Clone)]
+struct PatternRule {
+    /// Matches the whole relative path
+    regex: Regex,
+    /// `!pattern`
+    negated: bool,
+    /// `pattern/`
+    dir_only: bool,
+}
+
+/// .gitignore-style patterns, applied in order.
+///
+/// Also selects the files `fs_watch` reports (see `crate::fs_watch`).
+#[derive(Debug, Clone, Default)]
+pub struct PathPatterns {
+    rules: Vec<PatternRule>,
+}
+
+// ============================================
+// PATTERNS
+// ============================================
+
+impl PathPatterns {
+    /// Parse gitignore-style lines.
+    ///
+    /// # Errors
+    ///
+    /// A pattern that can't be compiled (e.g. an unclosed `[`).
+    pub fn parse<S: AsRef<str>>(lines: &[S]) -> Result<Self, String> {
+        let mut rules = Vec::new();
+        for line in lines {
+            if let Some(rule) = parse_rule(line.as_ref())? {
+                rules.push(rule);
+            }
+        }
+        Ok(Self { rules })
+    }
+
+    /// Whether there are no patterns.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether `path` (relative, `/`-separated) matches, i.e. the last
+    /// pattern matching it is not negated.
+    pub fn matches(&self, path: &str, is_dir: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| (is_dir || !rule.dir_only) && rule.regex.is_match(path))
+            .is_some_and(|rule| !rule.negated)
+    }
+}
+
+/// Parse one line; `None` for blank lines and comments.
+fn parse_rule(line: &str) -> Result<Option<PatternRule>, String> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    let trimmed = line.trim_end();
+    // Trailing spaces count only when escaped
+    let mut pattern = if trimmed.ends_with('\\') && line.len() > trimmed.len() {
+        &line[..=trimmed.len()]
+    } else {
+        trimmed
+    };
+    if pattern.is_empty() || pattern.starts_with('#') {
+        return Ok(None);
+    }
+
+    let negated = if let Some(rest) = pattern.strip_prefix('!') {
+        pattern = rest;
+        true
+    } else {
+        false
+    };
+    let dir_only = match pattern.strip_suffix('/') {
+        Some(rest) if !rest.is_empty() => {
+            pattern = rest;
+            true
+        }
+        _ => false,
+    };
+    let anchored = pattern.contains('/');
+    let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+    if pattern.is_empty() {
+        return Ok(None);
+    }
+
+    let body = glob_to_regex(pattern);
+    let source = if anchored {
+        format!("^{body}$")
+    } else {
+        format!("^(?:.*/)?{body}$")
+    };
+    let regex = Regex::new(&source).map_err(|e| format!("Invalid ignore pattern {line:?}: {e}"))?;
+    Ok(Some(PatternRule {
+        regex,
+        negated,
+        dir_only,
+    }))
+}
+
+/// Translate a glob to a regex body.
+fn glob_to_regex(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                let at_start = i == 0 || chars[i - 1] == '/';
+                if at_start && chars.get(i + 2) == Some(&'/') {
+                    // `**/` matches zero or more directories
+                    out.push_str("(?:.*/)?");
+                    i += 3;
+                    continue;
+                }
+                out.push_str(".*");
+                i += 2;
+                continue;
+            }
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            '[' => match chars[i + 1..].iter().position(|&c| c == ']') {
+                Some(len) if len > 0 => {
+                    let class: String = chars[i + 1..=i + len].iter().collect();
+                    let class = class
+                        .strip_prefix('!')
+                        .map_or_else(|| class.clone(), |rest| format!("^{rest}"));
+                    out.push('[');
+                    out.push_str(&class.replace('\\', "\\\\"));
+                    out.push(']');
+                    i += len + 2;
+                    continue;
+                }
+                _ => out.push_str("\\["),
+            },
+            '\\' if i + 1 < chars.len() => {
+                out.push_str(&regex::escape(&chars[i + 1].to_string()));
+                i += 2;
+                continue;
+            }
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    out
+}
+
+// ============================================
+// TREE
+// ============================================
+
+/// List `root` as a tree.
+///
+/// # Arguments
+///
+/// * `root` - Directory to list
+/// * `depth` - Levels below the root to list (None for all; 0 lists only
+///   the root itself)
+/// * `ignore` - Patterns applied after the root's `.gitignore`
+pub fn tree(root: &Path, depth: Option<usize>, ignore: &[String]) -> Result<TreeNode, String> {
+    if !root.is_dir() {
+        return Err(format!("Not a directory: {root:?}"));
+    }
+    let mut lines: Vec<String> = match fs::read_to_string(root.join(IGNORE_FILE)) {
+        Ok(text) => text.lines().map(str::to_string).collect(),
+        Err(_) => Vec::new(),
+    };
+    lines.extend(ignore.iter().cloned());
+    let rules = PathPatterns::parse(&lines)?;
+
+    let mut node = TreeNode {
+        name: root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        path: String::new(),
+        is_dir: true,
+        size_bytes: 0,
+        children: Vec::new(),
+        truncated: false,
+    };
+    let mut remaining = MAX_ENTRIES;
+    walk(root, &mut node, depth, &rules, &mut remaining)?;
+    Ok(node)
+}
+
+/// Fill in the children of the directory `node` at `dir`.
+fn walk(
+    dir: &Path,
+    node: &mut TreeNode,
+    depth: Option<usize>,
+    rules: &PathPatterns,
+    remaining: &mut usize,
+) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to list {dir:?}: {e}"))?;
+    if depth == Some(0) {
+        node.truncated = entries.flatten().next().is_some();
+        return Ok(());
+    }
+
+    let mut children = Vec::new();
+    for item in entries.flatten() {
+        let name = item.file_name().to_string_lossy().to_string();
+        let Ok(file_type) = item.file_type() else {
+            continue;
+        };
+        // Symlinks are not followed, so a linked directory is a leaf
+        let is_dir = file_type.is_dir();
+        let path = if node.path.is_empty() {
+            name.clone()
+        } else {
+            format!("{}/{name}", node.path)
+        };
+        if (is_dir && ALWAYS_IGNORED.contains(&name.as_str())) || rules.matches(&path, is_dir) {
+            continue;
+        }
+        if *remaining == 0 {
+            node.truncated = true;
+            break;
+        }
+        *remaining -= 1;
+        let size_bytes = if is_dir {
+            0
+        } else {
+            item.metadata().map_or(0, |metadata| metadata.len())
+        };
+        children.push((
+            item.path(),
+            TreeNode {
+                name,
+                path,
+                is_dir,
+                size_bytes,
+                children: Vec::new(),
+                truncated: false,
+            },
+        ));
+    }
+    children.sort_by(|(_, a), (_, b)| b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name)));
+
+    for (child_path, mut child) in children {
+        if child.is_dir {
+            walk(
+                &child_path,
+                &mut child,
+                depth.map(|depth| depth - 1),
+                rules,
+                remaining,
+            )?;
+        }
+        node.children.push(child);
+    }
+    Ok(())
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(lines: &[&str]) -> PathPatterns {
+        PathPatterns::parse(lines).unwrap()
+    }
+
+    #[test]
+    fn test_path_patterns() {
+        let ignore = rules(&["# build output", "", "*.log", "dist/", "/root.txt"]);
+        assert!(ignore.matches("debug.log", false));
+        assert!(ignore.matches("src/debug.log", false));
+        assert!(ignore.matches("web/dist", true));
+        assert!(!ignore.matches("web/dist", false));
+        assert!(ignore.matches("root.txt", false));
+        assert!(!ignore.matches("src/root.txt", false));
+
+        let ignore = rules(&["docs/**/*.md", "**/cache", "build/**", "file?.[ch]"]);
+        assert!(ignore.matches("docs/a.md", false));
+        assert!(ignore.matches("docs/x/y/a.md", false));
+        assert!(!ignore.matches("other/docs/a.md", false));
+        assert!(ignore.matches("cache", true));
+        assert!(ignore.matches("a/b/cache", true));
+        assert!(ignore.matches("build/out.js", false));
+        assert!(ignore.matches("file1.c", false));
+        assert!(!ignore.matches("file12.c", false));
+        assert!(!ignore.matches("file1.rs", false));
+
+        let ignore = rules(&["*.log", "!keep.log", "\\#notes"]);
+        assert!(ignore.matches("debug.log", false));
+        assert!(!ignore.matches("keep.log", false));
+        assert!(ignore.matches("#notes", false));
+    }
+
+    #[test]
+    fn test_tree() {
+        let root = std::env::temp_dir().join(format!("af_project_tree_{}", uuid::Uuid::new_v4()));
+        for dir in ["src/components", "node_modules/react", ".git", "dist"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join(".gitignore"), "node_modules/\n*.log\n").unwrap();
+        fs::write(root.join("README.md"), "# App").unwrap();
+        fs::write(root.join("debug.log"), "log").unwrap();
+        fs::write(root.join("src/App.tsx"), "export default 1;").unwrap();
+        fs::write(root.join("src/components/Button.tsx"), "").unwrap();
+
+        let full = tree(&root, None, &["dist/".to_string()]).unwrap();
+        let names: Vec<&str> = full.children.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["src", ".gitignore", "README.md"]);
+        let src = &full.children[0];
+        assert_eq!(src.children[0].path, "src/components");
+        assert_eq!(
+            src.children[0].children[0].path,
+            "src/components/Button.tsx"
+        );
+        assert_eq!(src.children[1].size_bytes, 17);
+
+        let shallow = tree(&root, Some(1), &[]).unwrap();
+        let src = shallow.children.iter().find(|c| c.name == "src").unwrap();
+        assert!(src.children.is_empty());
+        assert!(src.truncated);
+        assert!(shallow.children.iter().any(|c| c.name == "dist"));
+
+        assert!(tree(&root.join("README.md"), None, &[]).is_err());
+        assert!(tree(&root, None, &["[".to_string()]).is_ok());
+    }
+}
@@ -0,0 +1,104 @@
+//! src-tauri/src/providers/anthropic.rs
+//! ====================================
+//! Anthropic provider (Messages and Models APIs).
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Anthropic has no embeddings API; `embed` returns `ProviderError::Unsupported`.
+
+use futures::future::{self, BoxFuture, FutureExt};
+use serde_json::Value;
+
+use super::{
+    send_json, sorted, ModelInfo, Provider, ProviderError, ProviderResult, METADATA_TIMEOUT,
+};
+
+/// Messages endpoint
+const MESSAGES_URL: &str = "https://api.anthropic.com/v1/messages";
+
+/// Models endpoint (one page holds every model)
+const MODELS_URL: &str = "https://api.anthropic.com/v1/models?limit=1000";
+
+/// API version header value
+pub const API_VERSION: &str = "2023-06-01";
+
+/// Anthropic provider.
+pub struct Anthropic;
+
+impl Provider for Anthropic {
+    fn service(&self) -> &'static str {
+        "anthropic"
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder, key: &str) -> reqwest::RequestBuilder {
+        request
+            .header("x-api-key", key)
+            .header("anthropic-version", API_VERSION)
+    }
+
+    fn chat_url(&self, _model: &str, _stream: bool) -> ProviderResult<String> {
+        Ok(MESSAGES_URL.to_string())
+    }
+
+    fn list_models<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+    ) -> BoxFuture<'a, ProviderResult<Vec<ModelInfo>>> {
+        async move {
+            let request = self.authorize(client.get(MODELS_URL), key);
+            let body = send_json(self.service(), request.timeout(METADATA_TIMEOUT)).await?;
+            Ok(parse_models(&body))
+        }
+        .boxed()
+    }
+
+    fn embed<'a>(
+        &'a self,
+        _client: &'a reqwest::Client,
+        _key: &'a str,
+        _model: &'a str,
+        _inputs: &'a [String],
+    ) -> BoxFuture<'a, ProviderResult<Vec<Vec<f32>>>> {
+        future::ready(Err(ProviderError::Unsupported {
+            service: self.service(),
+            operation: "embeddings",
+        }))
+        .boxed()
+    }
+}
+
+/// Models from a `/v1/models` response.
+fn parse_models(body: &Value) -> Vec<ModelInfo> {
+    sorted(
+        body["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|model| {
+                Some(ModelInfo {
+                    id: model["id"].as_str()?.to_string(),
+                    display_name: model["display_name"].as_str().map(str::to_string),
+                    context_window: None,
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_models() {
+        let body = json!({
+            "data": [{ "id": "claude-sonnet-4-5", "display_name": "Claude Sonnet 4.5" }],
+            "has_more": false,
+        });
+        let models = parse_models(&body);
+        assert_eq!(models[0].id, "claude-sonnet-4-5");
+        assert_eq!(models[0].display_name.as_deref(), Some("Claude Sonnet 4.5"));
+    }
+}
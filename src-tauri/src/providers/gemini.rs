@@ -0,0 +1,152 @@
+//! src-tauri/src/providers/gemini.rs
+//! =================================
+//! Google Gemini provider (Generative Language API).
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Model listing keeps only models that support `generateContent` or
+//! `embedContent`; Gemini also lists tuning-only and legacy models.
+
+use futures::future::{BoxFuture, FutureExt};
+use serde_json::{json, Value};
+
+use super::{
+    check_count, check_model, send_json, sorted, vector, with_json, ModelInfo, Provider,
+    ProviderResult, METADATA_TIMEOUT,
+};
+
+/// REST endpoint for model operations
+const MODELS_URL: &str = "https://generativelanguage.googleapis.com/v1beta/models";
+
+/// Models per listing page (the API maximum)
+const PAGE_SIZE: u32 = 1000;
+
+/// Generation methods that make a model worth listing
+const USABLE_METHODS: &[&str] = &["generateContent", "embedContent"];
+
+/// Google Gemini provider.
+pub struct Gemini;
+
+impl Provider for Gemini {
+    fn service(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder, key: &str) -> reqwest::RequestBuilder {
+        request.header("x-goog-api-key", key)
+    }
+
+    fn chat_url(&self, model: &str, stream: bool) -> ProviderResult<String> {
+        check_model(model)?;
+        Ok(if stream {
+            format!("{MODELS_URL}/{model}:streamGenerateContent?alt=sse")
+        } else {
+            format!("{MODELS_URL}/{model}:generateContent")
+        })
+    }
+
+    fn list_models<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+    ) -> BoxFuture<'a, ProviderResult<Vec<ModelInfo>>> {
+        async move {
+            let url = format!("{MODELS_URL}?pageSize={PAGE_SIZE}");
+            let request = self.authorize(client.get(url), key);
+            let body = send_json(self.service(), request.timeout(METADATA_TIMEOUT)).await?;
+            Ok(parse_models(&body))
+        }
+        .boxed()
+    }
+
+    fn embed<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+        model: &'a str,
+        inputs: &'a [String],
+    ) -> BoxFuture<'a, ProviderResult<Vec<Vec<f32>>>> {
+        async move {
+            check_model(model)?;
+            let service = self.service();
+            let requests: Vec<Value> = inputs
+                .iter()
+                .map(|text| {
+                    json!({
+                        "model": format!("models/{model}"),
+                        "content": { "parts": [{ "text": text }] },
+                    })
+                })
+                .collect();
+
+            let url = format!("{MODELS_URL}/{model}:batchEmbedContents");
+            let request = with_json(
+                self.authorize(client.post(url), key),
+                &json!({ "requests": requests }),
+            )?;
+            let response = send_json(service, request.timeout(METADATA_TIMEOUT)).await?;
+
+            let vectors = response["embeddings"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|embedding| vector(service, &embedding["values"]))
+                .collect::<ProviderResult<Vec<_>>>()?;
+            check_count(service, vectors, inputs.len())
+        }
+        .boxed()
+    }
+}
+
+/// Usable models from a `models.list` response.
+fn parse_models(body: &Value) -> Vec<ModelInfo> {
+    sorted(
+        body["models"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|model| {
+                model["supportedGenerationMethods"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .any(|method| USABLE_METHODS.iter().any(|m| method == m))
+            })
+            .filter_map(|model| {
+                let name = model["name"].as_str()?;
+                Some(ModelInfo {
+                    id: name.strip_prefix("models/").unwrap_or(name).to_string(),
+                    display_name: model["displayName"].as_str().map(str::to_string),
+                    context_window: model["inputTokenLimit"].as_u64(),
+                })
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_models() {
+        let body = json!({
+            "models": [
+                {
+                    "name": "models/gemini-2.5-flash",
+                    "displayName": "Gemini 2.5 Flash",
+                    "inputTokenLimit": 1_048_576,
+                    "supportedGenerationMethods": ["generateContent", "countTokens"],
+                },
+                {
+                    "name": "models/gemini-1.0-pro-tuning",
+                    "supportedGenerationMethods": ["createTunedModel"],
+                },
+            ],
+        });
+        let models = parse_models(&body);
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0].id, "gemini-2.5-flash");
+        assert_eq!(models[0].context_window, Some(1_048_576));
+    }
+}
@@ -0,0 +1,374 @@
+//! src-tauri/src/providers/mod.rs
+//! ==============================
+//! Common interface to the LLM providers.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Each provider implements `Provider`: listing the models a key can use,
+//! generating a reply to a provider-neutral conversation (see `llm.rs`),
+//! and embedding text. Keys are passed per call, so one provider value
+//! serves every key and profile. Ollama runs locally and needs no key; its
+//! host is read from `OLLAMA_HOST` in .env.
+//!
+//! Chat requests use the wire formats in `llm.rs`; a provider only names
+//! its chat endpoint (`chat_url`), which `LlmProxy` also uses for streams.
+//!
+//! Supported services: `openai`, `anthropic`, `gemini`, `ollama`.
+//!
+//! Usage:
+//!     ```rust
+//!     let provider = providers::get("openai", &env_vars).ok_or("unsupported")?;
+//!     let models = provider.list_models(&client, &key).await?;
+//!     let reply = provider.generate(&client, &key, "gpt-4o", &messages, &options).await?;
+//!     let vectors = provider.embed(&client, &key, "text-embedding-3-small", &inputs).await?;
+//!     ```
+
+pub mod anthropic;
+pub mod gemini;
+pub mod ollama;
+pub mod openai;
+
+use futures::future::{BoxFuture, FutureExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::llm::{self, LlmMessage, LlmOptions, LlmResponse};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Services with a provider implementation.
+pub const SERVICES: &[&str] = &["openai", "anthropic", "gemini", "ollama"];
+
+/// Upper bound for model listing and embedding requests
+const METADATA_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Provider request errors.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ProviderError {
+    #[error("{service} does not support {operation}")]
+    Unsupported {
+        service: &'static str,
+        operation: &'static str,
+    },
+
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error("{service} request failed: {message}")]
+    Network {
+        service: &'static str,
+        message: String,
+    },
+
+    #[error("{service} returned HTTP {status}: {message}")]
+    Http {
+        service: &'static str,
+        status: u16,
+        message: String,
+    },
+
+    #[error("Invalid {service} response: {message}")]
+    InvalidResponse {
+        service: &'static str,
+        message: String,
+    },
+}
+
+impl ProviderError {
+    /// Whether the provider rejected the API key.
+    pub fn is_auth(&self) -> bool {
+        matches!(
+            self,
+            ProviderError::Http {
+                status: 401 | 403,
+                ..
+            }
+        )
+    }
+}
+
+impl From<ProviderError> for String {
+    fn from(e: ProviderError) -> Self {
+        e.to_string()
+    }
+}
+
+/// Result of a provider request.
+pub type ProviderResult<T> = Result<T, ProviderError>;
+
+/// A model offered by a provider.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModelInfo {
+    /// Model ID as passed to `generate`
+    pub id: String,
+    /// Human-readable name, if the provider reports one
+    pub display_name: Option<String>,
+    /// Input token limit, if the provider reports one
+    pub context_window: Option<u64>,
+}
+
+impl ModelInfo {
+    /// Model with only an ID.
+    fn named(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: None,
+            context_window: None,
+        }
+    }
+}
+
+// ============================================
+// PROVIDER TRAIT
+// ============================================
+
+/// An LLM provider.
+///
+/// Methods return boxed futures so providers can be chosen at runtime
+/// (`Box<dyn Provider>`).
+pub trait Provider: Send + Sync {
+    /// Service name (e.g. `openai`).
+    fn service(&self) -> &'static str;
+
+    /// Whether requests need an API key.
+    fn requires_key(&self) -> bool {
+        true
+    }
+
+    /// Whether the provider runs on this machine (usable in offline mode).
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    /// Attach the key to a request.
+    fn authorize(&self, request: reqwest::RequestBuilder, key: &str) -> reqwest::RequestBuilder;
+
+    /// Chat endpoint for `model` taking `llm::request_body` payloads.
+    fn chat_url(&self, model: &str, stream: bool) -> ProviderResult<String>;
+
+    /// List the models available to `key`, sorted by ID.
+    fn list_models<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+    ) -> BoxFuture<'a, ProviderResult<Vec<ModelInfo>>>;
+
+    /// Generate a reply to a conversation.
+    fn generate<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+        model: &'a str,
+        messages: &'a [LlmMessage],
+        options: &'a LlmOptions,
+    ) -> BoxFuture<'a, ProviderResult<LlmResponse>> {
+        let service = self.service();
+        let request = self
+            .chat_url(model, false)
+            .map(|url| self.authorize(client.post(url), key));
+
+        async move {
+            let body = llm::request_body(service, model, messages, options, false)
+                .map_err(ProviderError::InvalidRequest)?;
+            let request = with_json(request?, &body)?;
+            let response = send_json(service, request.timeout(llm::REQUEST_TIMEOUT)).await?;
+            llm::parse_response(service, &response)
+                .map_err(|message| ProviderError::InvalidResponse { service, message })
+        }
+        .boxed()
+    }
+
+    /// Embed each input, returning one vector per input in order.
+    fn embed<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+        model: &'a str,
+        inputs: &'a [String],
+    ) -> BoxFuture<'a, ProviderResult<Vec<Vec<f32>>>>;
+}
+
+/// Get the provider for a service (`env_vars` supplies `OLLAMA_HOST`).
+pub fn get(service: &str, env_vars: &HashMap<String, String>) -> Option<Box<dyn Provider>> {
+    match service {
+        "openai" => Some(Box::new(openai::OpenAi)),
+        "anthropic" => Some(Box::new(anthropic::Anthropic)),
+        "gemini" => Some(Box::new(gemini::Gemini)),
+        "ollama" => Some(Box::new(ollama::Ollama::from_env(env_vars))),
+        _ => None,
+    }
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Error message from a provider error body (`{"error": {"message": ...}}`).
+pub fn error_message(body: &[u8]) -> String {
+    let parsed = serde_json::from_slice::<Value>(body).ok();
+    parsed
+        .as_ref()
+        .and_then(|v| {
+            v["error"]["message"]
+                .as_str()
+                .or_else(|| v["error"].as_str())
+        })
+        .map_or_else(
+            || String::from_utf8_lossy(body).chars().take(500).collect(),
+            str::to_string,
+        )
+}
+
+/// Attach a JSON body to a request.
+pub fn with_json(
+    request: reqwest::RequestBuilder,
+    body: &Value,
+) -> ProviderResult<reqwest::RequestBuilder> {
+    let body =
+        serde_json::to_vec(body).map_err(|e| ProviderError::InvalidRequest(e.to_string()))?;
+    Ok(request
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body))
+}
+
+/// Send a request and parse its JSON response.
+async fn send_json(
+    service: &'static str,
+    request: reqwest::RequestBuilder,
+) -> ProviderResult<Value> {
+    let network = |e: reqwest::Error| ProviderError::Network {
+        service,
+        message: e.to_string(),
+    };
+
+    let response = request.send().await.map_err(network)?;
+    let status = response.status();
+    let bytes = response.bytes().await.map_err(network)?;
+
+    if !status.is_success() {
+        return Err(ProviderError::Http {
+            service,
+            status: status.as_u16(),
+            message: error_message(&bytes),
+        });
+    }
+
+    serde_json::from_slice(&bytes).map_err(|e| ProviderError::InvalidResponse {
+        service,
+        message: e.to_string(),
+    })
+}
+
+/// Reject model names that could alter a request URL.
+pub fn check_model(model: &str) -> ProviderResult<()> {
+    if model.is_empty()
+        || !model
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':' | '@'))
+    {
+        return Err(ProviderError::InvalidRequest(format!(
+            "Invalid model name: {model}"
+        )));
+    }
+    Ok(())
+}
+
+/// Parse an embedding vector.
+fn vector(service: &'static str, value: &Value) -> ProviderResult<Vec<f32>> {
+    value
+        .as_array()
+        .ok_or_else(|| ProviderError::InvalidResponse {
+            service,
+            message: "embedding is not an array".to_string(),
+        })?
+        .iter()
+        .map(|x| x.as_f64().map(|x| x as f32))
+        .collect::<Option<Vec<f32>>>()
+        .ok_or_else(|| ProviderError::InvalidResponse {
+            service,
+            message: "embedding contains a non-number".to_string(),
+        })
+}
+
+/// Check that a provider returned one embedding per input.
+fn check_count(
+    service: &'static str,
+    vectors: Vec<Vec<f32>>,
+    inputs: usize,
+) -> ProviderResult<Vec<Vec<f32>>> {
+    if vectors.len() != inputs {
+        return Err(ProviderError::InvalidResponse {
+            service,
+            message: format!("expected {inputs} embeddings, got {}", vectors.len()),
+        });
+    }
+    Ok(vectors)
+}
+
+/// Sort models by ID.
+fn sorted(mut models: Vec<ModelInfo>) -> Vec<ModelInfo> {
+    models.sort_by(|a, b| a.id.cmp(&b.id));
+    models
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_get_provider() {
+        let env = HashMap::new();
+        for service in SERVICES {
+            assert_eq!(get(service, &env).unwrap().service(), *service);
+        }
+        assert!(get("mistral", &env).is_none());
+        assert!(!get("ollama", &env).unwrap().requires_key());
+    }
+
+    #[test]
+    fn test_error_message_and_auth() {
+        assert_eq!(
+            error_message(br#"{"error":{"message":"Invalid API key"}}"#),
+            "Invalid API key"
+        );
+        assert_eq!(
+            error_message(br#"{"error":"model not found"}"#),
+            "model not found"
+        );
+
+        let rejected = ProviderError::Http {
+            service: "openai",
+            status: 401,
+            message: String::new(),
+        };
+        assert!(rejected.is_auth());
+        assert!(!ProviderError::InvalidRequest(String::new()).is_auth());
+    }
+
+    #[test]
+    fn test_vector_parsing() {
+        assert_eq!(vector("openai", &json!([0.5, 1])).unwrap(), [0.5, 1.0]);
+        assert!(vector("openai", &json!([0.5, "x"])).is_err());
+        assert!(check_count("openai", vec![vec![1.0]], 2).is_err());
+    }
+
+    #[test]
+    fn test_check_model() {
+        assert!(check_model("gemini-2.5-flash").is_ok());
+        assert!(check_model("../v1/files?x=").is_err());
+        assert!(check_model("").is_err());
+    }
+}
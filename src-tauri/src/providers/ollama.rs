@@ -0,0 +1,219 @@
+//! src-tauri/src/providers/ollama.rs
+//! =================================
+//! Ollama provider (local model server).
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Local Ollama needs no key; a key, if given, is sent as a bearer token
+//! for Ollama instances behind an authenticating proxy.
+
+use futures::future::{BoxFuture, FutureExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use super::{
+    check_count, send_json, vector, with_json, ModelInfo, Provider, ProviderResult,
+    METADATA_TIMEOUT,
+};
+use crate::offline;
+
+/// Default Ollama endpoint (overridden by `OLLAMA_HOST` in .env)
+pub const DEFAULT_HOST: &str = "http://localhost:11434";
+
+/// Upper bound for detecting the daemon (it is local, so fail fast)
+const DETECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Ollama host configured in .env (without a trailing slash).
+pub fn host(env_vars: &HashMap<String, String>) -> &str {
+    env_vars
+        .get("OLLAMA_HOST")
+        .map_or(DEFAULT_HOST, String::as_str)
+        .trim_end_matches('/')
+}
+
+/// A model installed in the daemon.
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalModel {
+    /// Model name with tag (e.g. `llama3.2:latest`)
+    pub name: String,
+    /// Size on disk in bytes
+    pub size: u64,
+    /// Last modification timestamp (RFC 3339)
+    pub modified_at: Option<String>,
+    /// Parameter count (e.g. `3.2B`)
+    pub parameter_size: Option<String>,
+    /// Quantization level (e.g. `Q4_K_M`)
+    pub quantization_level: Option<String>,
+}
+
+/// Ollama provider.
+pub struct Ollama {
+    /// Server URL (e.g. `http://localhost:11434`)
+    host: String,
+}
+
+impl Ollama {
+    /// Provider for the host configured in .env.
+    pub fn from_env(env_vars: &HashMap<String, String>) -> Self {
+        Self {
+            host: host(env_vars).to_string(),
+        }
+    }
+
+    /// Server URL.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// URL of an API path (e.g. `/api/pull`).
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{path}", self.host)
+    }
+
+    /// Version of the running daemon (fails if it is not reachable).
+    pub async fn version(&self, client: &reqwest::Client) -> ProviderResult<String> {
+        let request = client.get(self.url("/api/version")).timeout(DETECT_TIMEOUT);
+        let body = send_json(self.service(), request).await?;
+        Ok(body["version"].as_str().unwrap_or_default().to_string())
+    }
+
+    /// Models installed in the daemon, sorted by name.
+    pub async fn installed(
+        &self,
+        client: &reqwest::Client,
+        key: &str,
+    ) -> ProviderResult<Vec<LocalModel>> {
+        let request = self.authorize(client.get(self.url("/api/tags")), key);
+        let body = send_json(self.service(), request.timeout(METADATA_TIMEOUT)).await?;
+        Ok(parse_tags(&body))
+    }
+}
+
+/// Installed models from an `/api/tags` response.
+fn parse_tags(body: &Value) -> Vec<LocalModel> {
+    let mut models: Vec<LocalModel> = body["models"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|model| {
+            let text = |value: &Value| value.as_str().map(str::to_string);
+            Some(LocalModel {
+                name: model["name"].as_str()?.to_string(),
+                size: model["size"].as_u64().unwrap_or(0),
+                modified_at: text(&model["modified_at"]),
+                parameter_size: text(&model["details"]["parameter_size"]),
+                quantization_level: text(&model["details"]["quantization_level"]),
+            })
+        })
+        .collect();
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    models
+}
+
+impl Provider for Ollama {
+    fn service(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn requires_key(&self) -> bool {
+        false
+    }
+
+    fn is_local(&self) -> bool {
+        reqwest::Url::parse(&self.host)
+            .is_ok_and(|url| url.host_str().is_some_and(offline::is_local_host))
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder, key: &str) -> reqwest::RequestBuilder {
+        if key.is_empty() {
+            request
+        } else {
+            request.bearer_auth(key)
+        }
+    }
+
+    fn chat_url(&self, _model: &str, _stream: bool) -> ProviderResult<String> {
+        Ok(self.url("/api/chat"))
+    }
+
+    fn list_models<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+    ) -> BoxFuture<'a, ProviderResult<Vec<ModelInfo>>> {
+        async move {
+            let installed = self.installed(client, key).await?;
+            Ok(installed
+                .iter()
+                .map(|model| ModelInfo::named(&model.name))
+                .collect())
+        }
+        .boxed()
+    }
+
+    fn embed<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+        model: &'a str,
+        inputs: &'a [String],
+    ) -> BoxFuture<'a, ProviderResult<Vec<Vec<f32>>>> {
+        async move {
+            let service = self.service();
+            let request = with_json(
+                self.authorize(client.post(self.url("/api/embed")), key),
+                &json!({ "model": model, "input": inputs }),
+            )?;
+            let response = send_json(service, request.timeout(METADATA_TIMEOUT)).await?;
+
+            let vectors = response["embeddings"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .map(|embedding| vector(service, embedding))
+                .collect::<ProviderResult<Vec<_>>>()?;
+            check_count(service, vectors, inputs.len())
+        }
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_host_from_env() {
+        let mut env = HashMap::new();
+        assert_eq!(host(&env), DEFAULT_HOST);
+        assert!(Ollama::from_env(&env).is_local());
+        env.insert(
+            "OLLAMA_HOST".to_string(),
+            "http://gpu-box:11434/".to_string(),
+        );
+        assert_eq!(Ollama::from_env(&env).host, "http://gpu-box:11434");
+        assert!(!Ollama::from_env(&env).is_local());
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        let body = json!({
+            "models": [
+                { "name": "qwen2.5:7b", "size": 4_683_087_332_u64 },
+                {
+                    "name": "llama3.2:latest",
+                    "size": 2_019_393_189_u64,
+                    "modified_at": "2025-01-10T08:00:00Z",
+                    "details": { "parameter_size": "3.2B", "quantization_level": "Q4_K_M" },
+                },
+            ],
+        });
+        let models = parse_tags(&body);
+        assert_eq!(models[0].name, "llama3.2:latest");
+        assert_eq!(models[0].parameter_size.as_deref(), Some("3.2B"));
+        assert_eq!(models[1].size, 4_683_087_332);
+        assert!(models[1].modified_at.is_none());
+    }
+}
@@ -0,0 +1,101 @@
+//! src-tauri/src/providers/openai.rs
+//! =================================
+//! OpenAI provider (Chat Completions, Models, and Embeddings APIs).
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+
+use futures::future::{BoxFuture, FutureExt};
+use serde_json::{json, Value};
+
+use super::{
+    check_count, send_json, sorted, vector, with_json, ModelInfo, Provider, ProviderResult,
+    METADATA_TIMEOUT,
+};
+
+/// Chat Completions endpoint
+const CHAT_URL: &str = "https://api.openai.com/v1/chat/completions";
+
+/// Models endpoint
+const MODELS_URL: &str = "https://api.openai.com/v1/models";
+
+/// Embeddings endpoint
+const EMBEDDINGS_URL: &str = "https://api.openai.com/v1/embeddings";
+
+/// OpenAI provider.
+pub struct OpenAi;
+
+impl Provider for OpenAi {
+    fn service(&self) -> &'static str {
+        "openai"
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder, key: &str) -> reqwest::RequestBuilder {
+        request.bearer_auth(key)
+    }
+
+    fn chat_url(&self, _model: &str, _stream: bool) -> ProviderResult<String> {
+        Ok(CHAT_URL.to_string())
+    }
+
+    fn list_models<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+    ) -> BoxFuture<'a, ProviderResult<Vec<ModelInfo>>> {
+        async move {
+            let request = self.authorize(client.get(MODELS_URL), key);
+            let body = send_json(self.service(), request.timeout(METADATA_TIMEOUT)).await?;
+            Ok(parse_models(&body))
+        }
+        .boxed()
+    }
+
+    fn embed<'a>(
+        &'a self,
+        client: &'a reqwest::Client,
+        key: &'a str,
+        model: &'a str,
+        inputs: &'a [String],
+    ) -> BoxFuture<'a, ProviderResult<Vec<Vec<f32>>>> {
+        async move {
+            let service = self.service();
+            let body = json!({ "model": model, "input": inputs });
+            let request = with_json(self.authorize(client.post(EMBEDDINGS_URL), key), &body)?;
+            let response = send_json(service, request.timeout(METADATA_TIMEOUT)).await?;
+
+            let mut data: Vec<&Value> = response["data"].as_array().into_iter().flatten().collect();
+            data.sort_by_key(|item| item["index"].as_u64());
+            let vectors = data
+                .into_iter()
+                .map(|item| vector(service, &item["embedding"]))
+                .collect::<ProviderResult<Vec<_>>>()?;
+            check_count(service, vectors, inputs.len())
+        }
+        .boxed()
+    }
+}
+
+/// Models from a `/v1/models` response.
+fn parse_models(body: &Value) -> Vec<ModelInfo> {
+    sorted(
+        body["data"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|model| model["id"].as_str())
+            .map(ModelInfo::named)
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_models() {
+        let body = json!({ "data": [{ "id": "gpt-4o" }, { "id": "gpt-4.1" }, { "object": "x" }] });
+        let ids: Vec<String> = parse_models(&body).into_iter().map(|m| m.id).collect();
+        assert_eq!(ids, ["gpt-4.1", "gpt-4o"]);
+    }
+}
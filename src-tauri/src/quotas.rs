@@ -0,0 +1,349 @@
+//! src-tauri/src/quotas.rs
+//! =======================
+//! Per-plugin payload limits and call throttling.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! All plugins of a host share one stdio channel, so a plugin flooding it
+//! with calls or multi-megabyte payloads slows every other plugin down.
+//! The commands that call into plugin code (`ipc_call`, `ipc_batch`,
+//! `plugin_call`, `plugin_call_async`) check the calling plugin's limits
+//! before sending and, for results they return, after receiving:
+//!
+//! - `max_request_bytes`: size of the serialized params
+//! - `max_response_bytes`: size of the raw result
+//! - `max_concurrent`: calls in flight at once
+//! - `max_calls_per_minute`: calls started in any 60 second window
+//!
+//! Limits come from settings (`plugins.default_limits`, overridden field by
+//! field by `plugins.limits.<plugin>`); unset fields are unlimited.
+//!
+//! Usage:
+//!     ```rust
+//!     let limits = settings.get().plugins.limits_for("tts_kokoro");
+//!     let permit = quotas.acquire("tts_kokoro", &limits, &params)?;
+//!     let result = state.call_raw(method, params).await?;
+//!     permit.check_response(result.get().len())?;
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Window of `max_calls_per_minute`
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Limits of one plugin (None = unlimited).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PluginLimits {
+    /// Largest serialized params in bytes
+    pub max_request_bytes: Option<u64>,
+    /// Largest result in bytes
+    pub max_response_bytes: Option<u64>,
+    /// Calls in flight at once
+    pub max_concurrent: Option<u32>,
+    /// Calls started per minute
+    pub max_calls_per_minute: Option<u32>,
+}
+
+impl PluginLimits {
+    /// These limits, with unset fields taken from `defaults`.
+    pub fn or(self, defaults: PluginLimits) -> PluginLimits {
+        PluginLimits {
+            max_request_bytes: self.max_request_bytes.or(defaults.max_request_bytes),
+            max_response_bytes: self.max_response_bytes.or(defaults.max_response_bytes),
+            max_concurrent: self.max_concurrent.or(defaults.max_concurrent),
+            max_calls_per_minute: self.max_calls_per_minute.or(defaults.max_calls_per_minute),
+        }
+    }
+
+    /// Whether no limit is set.
+    pub fn is_unlimited(&self) -> bool {
+        *self == PluginLimits::default()
+    }
+
+    /// Check that every set limit is positive.
+    pub fn validate(&self) -> Result<(), String> {
+        let zero = [
+            ("max_request_bytes", self.max_request_bytes == Some(0)),
+            ("max_response_bytes", self.max_response_bytes == Some(0)),
+            ("max_concurrent", self.max_concurrent == Some(0)),
+            ("max_calls_per_minute", self.max_calls_per_minute == Some(0)),
+        ];
+        match zero.iter().find(|(_, is_zero)| *is_zero) {
+            Some((name, _)) => Err(format!("{name} must be positive (omit it for no limit)")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A call refused because it would exceed a limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    /// Error code (`REQUEST_TOO_LARGE`, `RESPONSE_TOO_LARGE`,
+    /// `TOO_MANY_CONCURRENT_CALLS`, or `RATE_LIMITED`)
+    pub code: &'static str,
+    /// Human-readable message
+    pub message: String,
+    /// Plugin whose limit was hit
+    pub plugin: String,
+    /// When a rate-limited call may be retried, in milliseconds
+    pub retry_after_ms: Option<u64>,
+}
+
+impl QuotaExceeded {
+    fn new(code: &'static str, plugin: &str, message: String) -> Self {
+        Self {
+            code,
+            message,
+            plugin: plugin.to_string(),
+            retry_after_ms: None,
+        }
+    }
+}
+
+// ============================================
+// QUOTA TRACKER
+// ============================================
+
+/// Calls of one plugin.
+#[derive(Default)]
+struct Usage {
+    /// Calls in flight
+    in_flight: u32,
+    /// Start times of the calls in the rate window (oldest first)
+    started: VecDeque<Instant>,
+}
+
+/// Calls in flight and recent call times of every plugin.
+#[derive(Clone, Default)]
+pub struct PluginQuotas {
+    usage: Arc<Mutex<BTreeMap<String, Usage>>>,
+}
+
+impl PluginQuotas {
+    /// Create a tracker without calls.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Admit a call to `plugin`, or refuse it if it exceeds a limit.
+    ///
+    /// The returned permit counts the call as in flight until dropped.
+    pub fn acquire(
+        &self,
+        plugin: &str,
+        limits: &PluginLimits,
+        params: &Value,
+    ) -> Result<QuotaPermit, QuotaExceeded> {
+        self.acquire_at(plugin, limits, params, Instant::now())
+    }
+
+    fn acquire_at(
+        &self,
+        plugin: &str,
+        limits: &PluginLimits,
+        params: &Value,
+        now: Instant,
+    ) -> Result<QuotaPermit, QuotaExceeded> {
+        if let Some(max) = limits.max_request_bytes {
+            let size = serde_json::to_vec(params).map_or(0, |bytes| bytes.len());
+            if u64::try_from(size).unwrap_or(u64::MAX) > max {
+                return Err(QuotaExceeded::new(
+                    "REQUEST_TOO_LARGE",
+                    plugin,
+                    format!("Request to {plugin} is {size} bytes; the limit is {max}"),
+                ));
+            }
+        }
+
+        let mut all = self.usage.lock().unwrap();
+        let usage = all.entry(plugin.to_string()).or_default();
+        while usage
+            .started
+            .front()
+            .is_some_and(|start| now.duration_since(*start) >= RATE_WINDOW)
+        {
+            usage.started.pop_front();
+        }
+
+        if let Some(max) = limits.max_calls_per_minute {
+            if usage.started.len() >= usize::try_from(max).unwrap_or(usize::MAX) {
+                let retry_after = usage.started.front().map_or(Duration::ZERO, |oldest| {
+                    RATE_WINDOW.saturating_sub(now.duration_since(*oldest))
+                });
+                let mut error = QuotaExceeded::new(
+                    "RATE_LIMITED",
+                    plugin,
+                    format!("{plugin} is limited to {max} calls per minute"),
+                );
+                error.retry_after_ms =
+                    Some(u64::try_from(retry_after.as_millis()).unwrap_or(u64::MAX));
+                return Err(error);
+            }
+        }
+        if let Some(max) = limits.max_concurrent {
+            if usage.in_flight >= max {
+                return Err(QuotaExceeded::new(
+                    "TOO_MANY_CONCURRENT_CALLS",
+                    plugin,
+                    format!("{plugin} is limited to {max} concurrent calls"),
+                ));
+            }
+        }
+
+        usage.in_flight += 1;
+        usage.started.push_back(now);
+        Ok(QuotaPermit {
+            quotas: self.clone(),
+            plugin: plugin.to_string(),
+            max_response_bytes: limits.max_response_bytes,
+        })
+    }
+}
+
+/// An admitted call; counted as in flight until dropped.
+pub struct QuotaPermit {
+    quotas: PluginQuotas,
+    plugin: String,
+    max_response_bytes: Option<u64>,
+}
+
+impl QuotaPermit {
+    /// Refuse a result larger than the plugin's response limit.
+    pub fn check_response(&self, size: usize) -> Result<(), QuotaExceeded> {
+        match self.max_response_bytes {
+            Some(max) if u64::try_from(size).unwrap_or(u64::MAX) > max => Err(QuotaExceeded::new(
+                "RESPONSE_TOO_LARGE",
+                &self.plugin,
+                format!(
+                    "Response from {} is {size} bytes; the limit is {max}",
+                    self.plugin
+                ),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for QuotaPermit {
+    fn drop(&mut self) {
+        if let Some(usage) = self.quotas.usage.lock().unwrap().get_mut(&self.plugin) {
+            usage.in_flight = usage.in_flight.saturating_sub(1);
+        }
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_limits_fall_back_to_defaults() {
+        let defaults = PluginLimits {
+            max_concurrent: Some(4),
+            max_calls_per_minute: Some(100),
+            ..PluginLimits::default()
+        };
+        let plugin = PluginLimits {
+            max_concurrent: Some(1),
+            ..PluginLimits::default()
+        };
+        let merged = plugin.or(defaults);
+        assert_eq!(merged.max_concurrent, Some(1));
+        assert_eq!(merged.max_calls_per_minute, Some(100));
+        assert!(merged.max_request_bytes.is_none());
+        assert!(PluginLimits::default().is_unlimited());
+        assert!(PluginLimits {
+            max_concurrent: Some(0),
+            ..PluginLimits::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_payload_sizes() {
+        let quotas = PluginQuotas::new();
+        let limits = PluginLimits {
+            max_request_bytes: Some(20),
+            max_response_bytes: Some(4),
+            ..PluginLimits::default()
+        };
+        let err = quotas
+            .acquire("tts", &limits, &json!({ "text": "a long piece of text" }))
+            .err()
+            .unwrap();
+        assert_eq!(err.code, "REQUEST_TOO_LARGE");
+
+        let permit = quotas.acquire("tts", &limits, &json!({})).unwrap();
+        assert!(permit.check_response(4).is_ok());
+        assert_eq!(
+            permit.check_response(5).unwrap_err().code,
+            "RESPONSE_TOO_LARGE"
+        );
+    }
+
+    #[test]
+    fn test_concurrency_released_on_drop() {
+        let quotas = PluginQuotas::new();
+        let limits = PluginLimits {
+            max_concurrent: Some(1),
+            ..PluginLimits::default()
+        };
+        let permit = quotas.acquire("llm", &limits, &json!({})).unwrap();
+        let err = quotas.acquire("llm", &limits, &json!({})).err().unwrap();
+        assert_eq!(err.code, "TOO_MANY_CONCURRENT_CALLS");
+        assert!(quotas.acquire("other", &limits, &json!({})).is_ok());
+
+        drop(permit);
+        assert!(quotas.acquire("llm", &limits, &json!({})).is_ok());
+    }
+
+    #[test]
+    fn test_rate_window() {
+        let quotas = PluginQuotas::new();
+        let limits = PluginLimits {
+            max_calls_per_minute: Some(2),
+            ..PluginLimits::default()
+        };
+        let start = Instant::now();
+        for offset in [0, 10] {
+            quotas
+                .acquire_at(
+                    "stt",
+                    &limits,
+                    &json!({}),
+                    start + Duration::from_secs(offset),
+                )
+                .unwrap();
+        }
+        let err = quotas
+            .acquire_at("stt", &limits, &json!({}), start + Duration::from_secs(30))
+            .err()
+            .unwrap();
+        assert_eq!(err.code, "RATE_LIMITED");
+        assert_eq!(err.retry_after_ms, Some(30_000));
+
+        // The first call leaves the window after a minute
+        assert!(quotas
+            .acquire_at("stt", &limits, &json!({}), start + Duration::from_secs(60))
+            .is_ok());
+    }
+}
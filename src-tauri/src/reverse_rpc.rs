@@ -0,0 +1,530 @@
+//! src-tauri/src/reverse_rpc.rs
+//! ============================
+//! Router for requests the plugin host sends to the app (reverse RPC).
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Plugins call back into the app through `plugins/_host/host_rpc.py`. The
+//! host sends a JSON-RPC request whose method namespace picks the handler;
+//! the response is written back over the host's stdin:
+//!
+//! - `host/request_permission`: ask for a declared permission
+//! - `secrets/get`: the active key of a service (`secrets` permission)
+//! - `artifacts/get`: metadata (and path) of a cached artifact
+//! - `dialog/open`, `dialog/save`: native file dialogs (`filesystem` permission)
+//! - `http/fetch`: an HTTP request made by the app (`network` permission)
+//!
+//! Every request names the calling plugin (`plugin` param). Namespaces that
+//! need a permission are only callable by plugins that declare it in their
+//! manifest and were granted it (see `PermissionStore::authorize`).
+//!
+//! Every request is logged and recorded in an in-memory audit trail (the
+//! last `MAX_AUDIT_ENTRIES`) with its plugin, outcome, and duration; the
+//! `reverse_rpc_audit` command lists it. Params are not recorded since they
+//! may hold secrets.
+//!
+//! Usage:
+//!     ```rust
+//!     let reverse_rpc = ReverseRpc::new(permissions, artifacts, vault, backends, paths);
+//!     let config = IpcConfig::default().with_request_handler(reverse_rpc.handler());
+//!     let denied = reverse_rpc.audit(Some("tts_kokoro"), 50);
+//!     ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::api::dialog::FileDialogBuilder;
+use tokio::sync::oneshot;
+
+use crate::artifacts::ArtifactStore;
+use crate::commands::secrets::active_key;
+use crate::http_fetch::{self, FetchPolicy, FetchRequest, FETCH_TIMEOUT_SECS, MAX_RESPONSE_BYTES};
+use crate::ipc::manager::{HostRequest, RequestHandler};
+use crate::paths::AppPaths;
+use crate::permissions::{Permission, PermissionStore, PERMISSION_REQUEST_METHOD};
+use crate::secret_store::SecretBackends;
+use crate::vault::KeyVault;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Read the active key of a service
+pub const SECRET_GET_METHOD: &str = "secrets/get";
+
+/// Look up a cached artifact by hash or cache key
+pub const ARTIFACT_GET_METHOD: &str = "artifacts/get";
+
+/// Show a native open-file dialog
+pub const DIALOG_OPEN_METHOD: &str = "dialog/open";
+
+/// Show a native save-file dialog
+pub const DIALOG_SAVE_METHOD: &str = "dialog/save";
+
+/// Make an HTTP request
+pub const HTTP_FETCH_METHOD: &str = "http/fetch";
+
+/// Number of requests kept in the audit trail
+pub const MAX_AUDIT_ENTRIES: usize = 1000;
+
+// ============================================
+// AUDIT
+// ============================================
+
+/// How a host request ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// Allowed and answered
+    Succeeded,
+    /// Allowed but the handler failed
+    Failed,
+    /// Rejected by the policy (unknown method, missing or denied permission)
+    Denied,
+}
+
+/// One host request in the audit trail.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditEntry {
+    /// When it was received (RFC 3339)
+    pub at: String,
+    /// Calling plugin (None if the request didn't name one)
+    pub plugin: Option<String>,
+    /// Method name
+    pub method: String,
+    /// How it ended
+    pub outcome: AuditOutcome,
+    /// Error message sent back to the host
+    pub error: Option<String>,
+    /// Time to answer in milliseconds
+    pub duration_ms: u64,
+}
+
+// ============================================
+// PARAMS
+// ============================================
+
+/// `secrets/get` params.
+#[derive(Debug, Deserialize)]
+struct SecretParams {
+    /// Service name (e.g. `openai`)
+    service: String,
+    /// Key profile (active profile if omitted)
+    profile: Option<String>,
+}
+
+/// `artifacts/get` params (one of `hash` or `key`).
+#[derive(Debug, Deserialize)]
+struct ArtifactParams {
+    hash: Option<String>,
+    key: Option<String>,
+}
+
+/// A file type filter of a dialog.
+#[derive(Debug, Deserialize)]
+struct DialogFilter {
+    name: String,
+    extensions: Vec<String>,
+}
+
+/// `dialog/open` and `dialog/save` params.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DialogParams {
+    title: Option<String>,
+    filters: Vec<DialogFilter>,
+    /// Pick a folder instead of a file (`dialog/open`)
+    directory: bool,
+    /// Allow several files (`dialog/open`)
+    multiple: bool,
+    /// Suggested file name (`dialog/save`)
+    file_name: Option<String>,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Namespace of a method (the part before the first `/`).
+fn namespace(method: &str) -> &str {
+    method
+        .split_once('/')
+        .map_or(method, |(namespace, _)| namespace)
+}
+
+/// Permission a plugin needs to call a method.
+///
+/// # Returns
+///
+/// None if anyone may call it, or an error for unknown namespaces.
+pub fn required_permission(method: &str) -> Result<Option<Permission>, String> {
+    match namespace(method) {
+        "host" | "artifacts" => Ok(None),
+        "secrets" => Ok(Some(Permission::Secrets)),
+        "dialog" => Ok(Some(Permission::Filesystem)),
+        "http" => Ok(Some(Permission::Network)),
+        _ => Err(format!("Unknown host method: {method}")),
+    }
+}
+
+/// Name of the plugin that sent a request.
+fn plugin_param(request: &HostRequest) -> Result<&str, String> {
+    request
+        .params
+        .get("plugin")
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Missing 'plugin' parameter for {}", request.method))
+}
+
+/// Decode a request's params.
+fn parse<T: DeserializeOwned>(request: &HostRequest) -> Result<T, String> {
+    serde_json::from_value(request.params.clone())
+        .map_err(|e| format!("Invalid {} params: {e}", request.method))
+}
+
+/// Show a file dialog and wait for the chosen paths (empty if cancelled).
+async fn file_dialog(save: bool, params: &DialogParams) -> Vec<PathBuf> {
+    let mut builder = FileDialogBuilder::new();
+    if let Some(ref title) = params.title {
+        builder = builder.set_title(title);
+    }
+    for filter in &params.filters {
+        let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+        builder = builder.add_filter(&filter.name, &extensions);
+    }
+
+    let (tx, rx) = oneshot::channel();
+    if save {
+        if let Some(ref file_name) = params.file_name {
+            builder = builder.set_file_name(file_name);
+        }
+        builder.save_file(move |path| {
+            let _ = tx.send(path.into_iter().collect());
+        });
+    } else if params.directory {
+        builder.pick_folder(move |path| {
+            let _ = tx.send(path.into_iter().collect());
+        });
+    } else if params.multiple {
+        builder.pick_files(move |paths| {
+            let _ = tx.send(paths.unwrap_or_default());
+        });
+    } else {
+        builder.pick_file(move |path| {
+            let _ = tx.send(path.into_iter().collect());
+        });
+    }
+    rx.await.unwrap_or_default()
+}
+
+// ============================================
+// REVERSE RPC
+// ============================================
+
+/// Answers requests sent by the plugin host.
+///
+/// Cloning shares the underlying stores.
+#[derive(Clone)]
+pub struct ReverseRpc {
+    permissions: PermissionStore,
+    artifacts: ArtifactStore,
+    vault: KeyVault,
+    backends: SecretBackends,
+    paths: AppPaths,
+    /// Most recent requests last
+    audit: Arc<Mutex<VecDeque<AuditEntry>>>,
+}
+
+impl ReverseRpc {
+    /// Create a router over the app's stores.
+    pub fn new(
+        permissions: PermissionStore,
+        artifacts: ArtifactStore,
+        vault: KeyVault,
+        backends: SecretBackends,
+        paths: AppPaths,
+    ) -> Self {
+        Self {
+            permissions,
+            artifacts,
+            vault,
+            backends,
+            paths,
+            audit: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Request handler for `IpcConfig::with_request_handler`.
+    pub fn handler(&self) -> RequestHandler {
+        let router = self.clone();
+        RequestHandler::new(move |request| {
+            let router = router.clone();
+            Box::pin(async move { router.handle(request).await })
+        })
+    }
+
+    /// Check the policy, route a request by method, and audit it.
+    pub async fn handle(&self, request: HostRequest) -> Result<Value, String> {
+        let started = Instant::now();
+        let at = chrono::Utc::now().to_rfc3339();
+        let plugin = plugin_param(&request).ok().map(str::to_string);
+        let method = request.method.clone();
+
+        let (outcome, result) = match self.check_policy(&request).await {
+            Ok(()) => {
+                let result = self.route(request).await;
+                let outcome = if result.is_ok() {
+                    AuditOutcome::Succeeded
+                } else {
+                    AuditOutcome::Failed
+                };
+                (outcome, result)
+            }
+            Err(e) => (AuditOutcome::Denied, Err(e)),
+        };
+
+        log::info!(
+            "Host request {method} from {}: {outcome:?}",
+            plugin.as_deref().unwrap_or("unknown plugin")
+        );
+        self.record(AuditEntry {
+            at,
+            plugin,
+            method,
+            outcome,
+            error: result.as_ref().err().cloned(),
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        });
+        result
+    }
+
+    /// Audited requests, most recent first.
+    ///
+    /// # Arguments
+    ///
+    /// * `plugin` - Only requests from this plugin (all if None)
+    /// * `limit` - Maximum number of entries
+    pub fn audit(&self, plugin: Option<&str>, limit: usize) -> Vec<AuditEntry> {
+        self.audit
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .filter(|entry| plugin.is_none() || entry.plugin.as_deref() == plugin)
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Append to the audit trail, dropping the oldest entry when full.
+    fn record(&self, entry: AuditEntry) {
+        let mut audit = self.audit.lock().unwrap();
+        if audit.len() >= MAX_AUDIT_ENTRIES {
+            audit.pop_front();
+        }
+        audit.push_back(entry);
+    }
+
+    /// Reject requests the calling plugin has no permission for.
+    async fn check_policy(&self, request: &HostRequest) -> Result<(), String> {
+        let Some(permission) = required_permission(&request.method)? else {
+            return Ok(());
+        };
+        let plugin = plugin_param(request)?;
+        let allowed = self
+            .permissions
+            .authorize(request.working_dir.as_deref(), plugin, permission)
+            .await?;
+        if allowed {
+            Ok(())
+        } else {
+            Err(format!(
+                "Plugin {plugin} was denied {}: it needs the {} permission",
+                request.method,
+                permission.as_str()
+            ))
+        }
+    }
+
+    /// Answer an allowed request.
+    async fn route(&self, request: HostRequest) -> Result<Value, String> {
+        match request.method.as_str() {
+            PERMISSION_REQUEST_METHOD => self.permissions.handle_request(request).await,
+            SECRET_GET_METHOD => self.get_secret(&request),
+            ARTIFACT_GET_METHOD => self.get_artifact(&request),
+            DIALOG_OPEN_METHOD | DIALOG_SAVE_METHOD => {
+                let params: DialogParams = parse(&request)?;
+                let paths = file_dialog(request.method == DIALOG_SAVE_METHOD, &params).await;
+                Ok(json!({ "paths": paths }))
+            }
+            HTTP_FETCH_METHOD => self.fetch(&request).await,
+            method => Err(format!("Unknown host method: {method}")),
+        }
+    }
+
+    /// `secrets/get`: the active key of a service in the host's project.
+    fn get_secret(&self, request: &HostRequest) -> Result<Value, String> {
+        let params: SecretParams = parse(request)?;
+        let root = request
+            .working_dir
+            .as_deref()
+            .ok_or("secrets/get needs the plugin host's project directory")?;
+        let env_path = self.paths.secrets_file(root);
+        let key = active_key(
+            &env_path,
+            &self.vault,
+            &self.backends,
+            &params.service,
+            params.profile,
+        )
+        .map_err(|e| e.message)?;
+        log::info!(
+            "Plugin {} read the {} key",
+            plugin_param(request)?,
+            params.service
+        );
+        Ok(match key {
+            Some((key_id, value)) => json!({ "key_id": key_id, "value": value }),
+            None => Value::Null,
+        })
+    }
+
+    /// `artifacts/get`: metadata of a cached artifact, or null.
+    fn get_artifact(&self, request: &HostRequest) -> Result<Value, String> {
+        let params: ArtifactParams = parse(request)?;
+        let meta = match (params.hash, params.key) {
+            (Some(hash), _) => self.artifacts.get(&hash),
+            (None, Some(key)) => self.artifacts.lookup(&key),
+            (None, None) => return Err("Missing 'hash' or 'key' parameter".to_string()),
+        };
+        serde_json::to_value(meta).map_err(|e| e.to_string())
+    }
+
+    /// `http/fetch`: make a request and return its status, headers, and body.
+    ///
+    /// Plugins with the `network` permission may contact any host; the
+    /// timeout is theirs to choose.
+    async fn fetch(&self, request: &HostRequest) -> Result<Value, String> {
+        let params: FetchRequest = parse(request)?;
+        let policy = FetchPolicy {
+            allowed_domains: None,
+            timeout_secs: params.timeout_secs.unwrap_or(FETCH_TIMEOUT_SECS),
+            max_request_bytes: u64::MAX,
+            max_response_bytes: MAX_RESPONSE_BYTES,
+        };
+        let response = http_fetch::fetch(&policy, params)
+            .await
+            .map_err(|e| e.to_string())?;
+        serde_json::to_value(response).map_err(|e| e.to_string())
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn router(artifacts: ArtifactStore) -> ReverseRpc {
+        ReverseRpc::new(
+            PermissionStore::load(None),
+            artifacts,
+            KeyVault::disabled(),
+            SecretBackends::load(None),
+            AppPaths::default(),
+        )
+    }
+
+    fn request(method: &str, params: Value) -> HostRequest {
+        HostRequest {
+            method: method.to_string(),
+            params,
+            working_dir: None,
+        }
+    }
+
+    #[test]
+    fn test_required_permission() {
+        assert_eq!(required_permission("host/request_permission"), Ok(None));
+        assert_eq!(required_permission(ARTIFACT_GET_METHOD), Ok(None));
+        assert_eq!(
+            required_permission(SECRET_GET_METHOD),
+            Ok(Some(Permission::Secrets))
+        );
+        assert_eq!(
+            required_permission(HTTP_FETCH_METHOD),
+            Ok(Some(Permission::Network))
+        );
+        assert!(required_permission("shell/run").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_policy_denies_undeclared_permissions() {
+        let router = router(ArtifactStore::open(None));
+
+        let fetch = request(
+            HTTP_FETCH_METHOD,
+            json!({ "plugin": "tts", "url": "https://example.com" }),
+        );
+        let error = router.handle(fetch).await.unwrap_err();
+        assert!(error.contains("network permission"), "{error}");
+
+        let anonymous = request(SECRET_GET_METHOD, json!({ "service": "openai" }));
+        assert!(router.handle(anonymous).await.is_err());
+        assert!(router
+            .handle(request("shell/run", Value::Null))
+            .await
+            .is_err());
+
+        let audit = router.audit(None, 10);
+        assert_eq!(audit.len(), 3);
+        assert!(audit
+            .iter()
+            .all(|entry| entry.outcome == AuditOutcome::Denied));
+        assert_eq!(audit[2].method, HTTP_FETCH_METHOD);
+        assert_eq!(audit[2].plugin.as_deref(), Some("tts"));
+        assert_eq!(router.audit(Some("tts"), 10).len(), 1);
+        assert_eq!(router.audit(None, 1)[0].method, "shell/run");
+    }
+
+    #[tokio::test]
+    async fn test_artifact_lookup() {
+        let dir = std::env::temp_dir().join(format!("af_reverse_rpc_{}", uuid::Uuid::new_v4()));
+        let artifacts = ArtifactStore::open(Some(dir));
+        let meta = artifacts.put(b"audio", "audio", Some("tts:hello")).unwrap();
+        let router = router(artifacts);
+
+        let by_key = request(
+            ARTIFACT_GET_METHOD,
+            json!({ "plugin": "tts", "key": "tts:hello" }),
+        );
+        assert_eq!(router.handle(by_key).await.unwrap()["hash"], meta.hash);
+
+        let missing = request(ARTIFACT_GET_METHOD, json!({ "plugin": "tts", "hash": "0" }));
+        assert_eq!(router.handle(missing).await.unwrap(), Value::Null);
+        assert!(router
+            .handle(request(ARTIFACT_GET_METHOD, json!({ "plugin": "tts" })))
+            .await
+            .is_err());
+
+        let outcomes: Vec<AuditOutcome> = router
+            .audit(Some("tts"), 10)
+            .into_iter()
+            .map(|entry| entry.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                AuditOutcome::Failed,
+                AuditOutcome::Succeeded,
+                AuditOutcome::Succeeded
+            ]
+        );
+    }
+}
@@ -0,0 +1,495 @@
+//! src-tauri/src/scheduler.rs
+//! ==========================
+//! Scheduled and recurring plugin invocations.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A schedule pairs a cron expression with a JSON-RPC method and params.
+//! Schedules are persisted as JSON (`schedules.json`) in the app config
+//! directory and executed through the matching workspace's
+//! `IpcManagerState` by a background loop started at app setup.
+//!
+//! Cron expressions use the standard 5-field form (`min hour dom mon dow`);
+//! a 6-field form with leading seconds is also accepted. Days of the week
+//! are numbered as in crontab (0 or 7 = Sunday, 1 = Monday, ...) or named
+//! (`Mon-Fri`), and times are local time. Runs missed while the app was
+//! closed are skipped, not replayed.
+//!
+//! Usage:
+//!     ```rust
+//!     let scheduler = Scheduler::load(config_dir);
+//!     scheduler.create("0 3 * * *", "embeddings/refresh", json!({}), None)?;
+//!     tauri::async_runtime::spawn(scheduler.clone().run(workspaces));
+//!     ```
+
+use chrono::{DateTime, Local, Utc};
+use cron::Schedule;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::workspace::WorkspaceRegistry;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// File name of the schedule store inside the app config directory
+const SCHEDULES_FILE: &str = "schedules.json";
+
+/// How often the scheduler loop checks for due schedules
+const SCHEDULER_TICK_SECS: u64 = 5;
+
+/// Day-of-week names, indexed by crontab number (0 = Sunday)
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A persisted schedule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    /// Schedule id
+    pub id: String,
+    /// Cron expression as entered
+    pub cron: String,
+    /// JSON-RPC method to call
+    pub method: String,
+    /// Method parameters
+    pub params: Value,
+    /// Workspace to run in (None = default)
+    #[serde(default)]
+    pub workspace: Option<String>,
+    /// Whether the schedule is active
+    pub enabled: bool,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+    /// Last run timestamp (RFC 3339)
+    #[serde(default)]
+    pub last_run: Option<String>,
+    /// Error from the last run (None if it succeeded)
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Next planned run (RFC 3339, not persisted)
+    #[serde(skip_deserializing)]
+    pub next_run: Option<String>,
+}
+
+/// Parse a cron expression, accepting the 5-field form.
+///
+/// # Returns
+///
+/// * `Ok(Schedule)` - Parsed schedule
+/// * `Err(String)` - Invalid expression
+pub fn parse_cron(expr: &str) -> Result<Schedule, String> {
+    let expr = expr.trim();
+    let mut fields: Vec<String> = expr.split_whitespace().map(str::to_string).collect();
+    if fields.len() == 5 {
+        fields.insert(0, "0".to_string());
+    }
+    // The cron crate numbers days 1 (Sunday) to 7, so pass it day names
+    if let Some(dow) = fields.get_mut(5) {
+        *dow = weekday_names(dow).map_err(|e| format!("Invalid cron expression '{expr}': {e}"))?;
+    }
+
+    Schedule::from_str(&fields.join(" "))
+        .map_err(|e| format!("Invalid cron expression '{expr}': {e}"))
+}
+
+/// Rewrite a crontab day-of-week field (`1-5`, `0,6`, `*/2`, `Mon-Fri`)
+/// as a list of day names.
+fn weekday_names(field: &str) -> Result<String, String> {
+    if field == "*" || field == "?" {
+        return Ok(field.to_string());
+    }
+
+    let day = |value: &str| -> Result<usize, String> {
+        match value.parse::<usize>() {
+            Ok(n) if n <= 7 => Ok(n),
+            Ok(_) => Err(format!("day of week {value} is out of range (0-7)")),
+            Err(_) => WEEKDAYS
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(value))
+                .ok_or_else(|| format!("unknown day of week '{value}'")),
+        }
+    };
+
+    let mut days = [false; 7];
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&s| s > 0)
+                    .ok_or_else(|| format!("invalid step in '{item}'"))?;
+                (range, step)
+            }
+            None => (item, 1),
+        };
+        let (start, end) = match range.split_once('-') {
+            _ if range == "*" || range == "?" => (0, 6),
+            Some((start, end)) => (day(start)?, day(end)?),
+            // `n/step` runs from n to the end of the week
+            None if step > 1 => (day(range)?, 6),
+            None => (day(range)?, day(range)?),
+        };
+        if start > end {
+            return Err(format!("invalid day-of-week range '{range}'"));
+        }
+        for d in (start..=end).step_by(step) {
+            days[d % 7] = true;
+        }
+    }
+
+    Ok(WEEKDAYS
+        .iter()
+        .zip(days)
+        .filter(|(_, on)| *on)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Compute the next run after `after`, in local time.
+fn next_after(cron: &str, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+    parse_cron(cron)
+        .ok()?
+        .after(&after.with_timezone(&Local))
+        .next()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+// ============================================
+// SCHEDULER
+// ============================================
+
+/// Schedule store and executor.
+///
+/// Clones share the schedules, so the background loop sees changes.
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    /// Schedules keyed by id
+    entries: Arc<RwLock<HashMap<String, ScheduleEntry>>>,
+
+    /// Schedule file path (None disables persistence)
+    path: Option<PathBuf>,
+}
+
+impl Scheduler {
+    /// Load schedules from the app config directory.
+    pub fn load(config_dir: Option<PathBuf>) -> Self {
+        let path = config_dir.map(|dir| dir.join(SCHEDULES_FILE));
+        let now = Utc::now();
+
+        let entries = path
+            .as_deref()
+            .map(read_schedules)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|mut entry| {
+                entry.next_run = next_after(&entry.cron, &now).map(|t| t.to_rfc3339());
+                (entry.id.clone(), entry)
+            })
+            .collect();
+
+        Self {
+            entries: Arc::new(RwLock::new(entries)),
+            path,
+        }
+    }
+
+    /// Create a new enabled schedule.
+    pub fn create(
+        &self,
+        cron: &str,
+        method: &str,
+        params: Value,
+        workspace: Option<String>,
+    ) -> Result<ScheduleEntry, String> {
+        parse_cron(cron)?;
+        if method.trim().is_empty() {
+            return Err("Method must not be empty".to_string());
+        }
+
+        let entry = ScheduleEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            cron: cron.trim().to_string(),
+            method: method.to_string(),
+            params,
+            workspace,
+            enabled: true,
+            created_at: Utc::now().to_rfc3339(),
+            last_run: None,
+            last_error: None,
+            next_run: next_after(cron, &Utc::now()).map(|t| t.to_rfc3339()),
+        };
+
+        self.entries
+            .write()
+            .unwrap()
+            .insert(entry.id.clone(), entry.clone());
+        self.persist();
+
+        log::info!("Schedule created: {} '{}' -> {}", entry.id, entry.cron, entry.method);
+        Ok(entry)
+    }
+
+    /// Delete a schedule.
+    pub fn delete(&self, id: &str) -> Result<(), String> {
+        self.entries
+            .write()
+            .unwrap()
+            .remove(id)
+            .ok_or_else(|| format!("Unknown schedule: {id}"))?;
+        self.persist();
+
+        log::info!("Schedule deleted: {id}");
+        Ok(())
+    }
+
+    /// Enable or disable a schedule.
+    pub fn set_enabled(&self, id: &str, enabled: bool) -> Result<ScheduleEntry, String> {
+        let entry = {
+            let mut entries = self.entries.write().unwrap();
+            let entry = entries
+                .get_mut(id)
+                .ok_or_else(|| format!("Unknown schedule: {id}"))?;
+            entry.enabled = enabled;
+            entry.clone()
+        };
+        self.persist();
+        Ok(entry)
+    }
+
+    /// List all schedules (oldest first).
+    pub fn list(&self) -> Vec<ScheduleEntry> {
+        let mut entries: Vec<ScheduleEntry> =
+            self.entries.read().unwrap().values().cloned().collect();
+        entries.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+        entries
+    }
+
+    /// Take the enabled schedules due at `now`, advancing their next run.
+    fn take_due(&self, now: &DateTime<Utc>) -> Vec<ScheduleEntry> {
+        let mut entries = self.entries.write().unwrap();
+        let mut due = Vec::new();
+
+        for entry in entries.values_mut() {
+            let is_due = entry
+                .next_run
+                .as_deref()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .is_some_and(|t| t <= *now);
+
+            if is_due {
+                entry.next_run = next_after(&entry.cron, now).map(|t| t.to_rfc3339());
+                if entry.enabled {
+                    due.push(entry.clone());
+                }
+            }
+        }
+
+        due
+    }
+
+    /// Record the outcome of a run and persist it.
+    fn record_run(&self, id: &str, started: &DateTime<Utc>, error: Option<String>) {
+        if let Some(entry) = self.entries.write().unwrap().get_mut(id) {
+            entry.last_run = Some(started.to_rfc3339());
+            entry.last_error = error;
+        }
+        self.persist();
+    }
+
+    /// Run the scheduler loop forever, executing due schedules.
+    pub async fn run(self, workspaces: WorkspaceRegistry) {
+        log::info!("Scheduler started with {} schedule(s)", self.list().len());
+        let mut interval = tokio::time::interval(Duration::from_secs(SCHEDULER_TICK_SECS));
+
+        loop {
+            interval.tick().await;
+            let now = Utc::now();
+
+            for entry in self.take_due(&now) {
+                let scheduler = self.clone();
+                let workspaces = workspaces.clone();
+
+                tauri::async_runtime::spawn(async move {
+                    log::info!("Running schedule {} ({})", entry.id, entry.method);
+
+                    let result = match workspaces.get(entry.workspace.as_deref()) {
+                        Ok(manager) => manager
+                            .call(entry.method.as_str(), entry.params.clone())
+                            .await
+                            .map(|_| ())
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e),
+                    };
+
+                    if let Err(ref e) = result {
+                        log::warn!("Schedule {} failed: {e}", entry.id);
+                    }
+                    scheduler.record_run(&entry.id, &now, result.err());
+                });
+            }
+        }
+    }
+
+    /// Write all schedules to disk.
+    fn persist(&self) {
+        if let Some(ref path) = self.path {
+            if let Err(e) = write_schedules(path, &self.list()) {
+                log::warn!("Failed to persist schedules: {e}");
+            }
+        }
+    }
+}
+
+// ============================================
+// PERSISTENCE
+// ============================================
+
+/// Read schedules from disk, returning an empty list if missing or invalid.
+fn read_schedules(path: &Path) -> Vec<ScheduleEntry> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid schedules file {path:?}: {e}");
+            Vec::new()
+        }),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Write schedules to disk.
+fn write_schedules(path: &Path, entries: &[ScheduleEntry]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize schedules: {e}"))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use chrono::{Datelike, TimeZone, Timelike, Weekday};
+    use serde_json::json;
+
+    /// The next `count` runs of `cron` after `start` (local time).
+    fn runs(cron: &str, start: DateTime<Local>, count: usize) -> Vec<DateTime<Local>> {
+        parse_cron(cron)
+            .unwrap()
+            .after(&start)
+            .take(count)
+            .collect()
+    }
+
+    /// Saturday, 1 June 2024, noon local time
+    fn saturday_noon() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_cron() {
+        assert!(parse_cron("0 3 * * *").is_ok());
+        assert!(parse_cron("*/30 * * * * *").is_ok());
+        assert!(parse_cron("0 3 * * 0").is_ok());
+        assert!(parse_cron("0 3 * * mon-fri").is_ok());
+        assert!(parse_cron("0 3 * * 8").is_err());
+        assert!(parse_cron("0 3 * * 5-1").is_err());
+        assert!(parse_cron("every night").is_err());
+    }
+
+    #[test]
+    fn test_weekday_names() {
+        assert_eq!(weekday_names("1-5").unwrap(), "Mon,Tue,Wed,Thu,Fri");
+        assert_eq!(weekday_names("0,7").unwrap(), "Sun");
+        assert_eq!(weekday_names("5-7").unwrap(), "Sun,Fri,Sat");
+        assert_eq!(weekday_names("*/2").unwrap(), "Sun,Tue,Thu,Sat");
+        assert_eq!(weekday_names("1/3").unwrap(), "Mon,Thu");
+        assert_eq!(weekday_names("Sat,sun").unwrap(), "Sun,Sat");
+        assert_eq!(weekday_names("*").unwrap(), "*");
+    }
+
+    #[test]
+    fn test_weekday_schedule() {
+        // 1-5 is Monday to Friday, at 03:00 local time
+        let runs = runs("0 3 * * 1-5", saturday_noon(), 6);
+        let days: Vec<_> = runs.iter().map(Datelike::weekday).collect();
+        let weekdays = [
+            Weekday::Mon,
+            Weekday::Tue,
+            Weekday::Wed,
+            Weekday::Thu,
+            Weekday::Fri,
+        ];
+        assert_eq!(days[..5], weekdays);
+        assert_eq!(days[5], Weekday::Mon);
+        assert!(runs.iter().all(|t| t.hour() == 3 && t.minute() == 0));
+
+        // 0 and 7 are both Sunday
+        for cron in ["0 3 * * 0", "0 3 * * 7"] {
+            let sunday = runs(cron, saturday_noon(), 1)[0];
+            assert_eq!(sunday.weekday(), Weekday::Sun);
+            assert_eq!(sunday.day(), 2);
+        }
+    }
+
+    #[test]
+    fn test_create_and_delete() {
+        let scheduler = Scheduler::load(None);
+
+        let entry = scheduler
+            .create("*/5 * * * *", "embeddings/refresh", json!({}), None)
+            .unwrap();
+        assert!(entry.enabled);
+        assert!(entry.next_run.is_some());
+        assert!(scheduler.create("bad", "m", json!({}), None).is_err());
+        assert!(scheduler.create("* * * * *", " ", json!({}), None).is_err());
+
+        assert!(!scheduler.set_enabled(&entry.id, false).unwrap().enabled);
+        scheduler.delete(&entry.id).unwrap();
+        assert!(scheduler.list().is_empty());
+    }
+
+    #[test]
+    fn test_take_due_advances_next_run() {
+        let scheduler = Scheduler::load(None);
+        let entry = scheduler.create("* * * * *", "ping", json!({}), None).unwrap();
+
+        let later = Utc::now() + chrono::Duration::minutes(2);
+        let due = scheduler.take_due(&later);
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, entry.id);
+
+        // Already advanced past `later`
+        assert!(scheduler.take_due(&later).is_empty());
+    }
+
+    #[test]
+    fn test_persisted_across_loads() {
+        let dir = temp_dir("schedules");
+
+        let scheduler = Scheduler::load(Some(dir.clone()));
+        let entry = scheduler.create("0 3 * * *", "ping", json!({"a": 1}), None).unwrap();
+
+        let reloaded = Scheduler::load(Some(dir));
+        let entries = reloaded.list();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id, entry.id);
+        assert!(entries[0].next_run.is_some());
+    }
+}
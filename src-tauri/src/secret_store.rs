@@ -0,0 +1,504 @@
+//! src-tauri/src/secret_store.rs
+//! =============================
+//! External secret backends for API keys.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! By default keys are stored (encrypted) in the project `.env`. A service
+//! can instead be bound to an external backend, for setups where keys may
+//! not be stored locally:
+//! - `one_password`: `op read <reference>` through the 1Password CLI
+//!   (signed in, or `OP_SERVICE_ACCOUNT_TOKEN` set)
+//! - `hashicorp_vault`: `vault kv get` through the Vault CLI (`VAULT_TOKEN`
+//!   and `VAULT_ADDR` from the app environment; the address may be set
+//!   per backend)
+//! - `environment`: a variable injected into the app environment (CI)
+//!
+//! Bindings hold references only, never key values, and are persisted as
+//! JSON (`secret_backends.json`) in the app config directory. A bound
+//! service ignores its `.env` keys. Values are fetched on demand and
+//! cached in memory for `CACHE_TTL`.
+//!
+//! Usage:
+//!     ```rust
+//!     let backends = SecretBackends::load(config_dir);
+//!     backends.set("openai", Some(BackendConfig::OnePassword {
+//!         reference: "op://Engineering/OpenAI/credential".to_string(),
+//!     }))?;
+//!     if let Some(key) = backends.read("openai") {
+//!         let key = key?;
+//!     }
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::redact;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// File name of the backend bindings inside the app config directory
+const BACKENDS_FILE: &str = "secret_backends.json";
+
+/// How long a fetched value is reused
+pub const CACHE_TTL: Duration = Duration::from_secs(300);
+
+/// Maximum time a backend CLI may take
+const CLI_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ============================================
+// SECRET STORE TRAIT
+// ============================================
+
+/// A source of key values outside the project `.env`.
+pub trait SecretStore: Send + Sync {
+    /// Backend name used in key ids and messages (e.g. `1password`).
+    fn name(&self) -> &'static str;
+
+    /// Fetch the key value.
+    fn read(&self) -> Result<String, String>;
+}
+
+/// 1Password CLI (`op read op://<vault>/<item>/<field>`).
+pub struct OnePasswordStore {
+    /// Secret reference
+    pub reference: String,
+}
+
+impl SecretStore for OnePasswordStore {
+    fn name(&self) -> &'static str {
+        "1password"
+    }
+
+    fn read(&self) -> Result<String, String> {
+        run_cli("op", &["read", "--no-newline", &self.reference], &[])
+    }
+}
+
+/// HashiCorp Vault KV secrets engine through the Vault CLI.
+pub struct HashicorpVaultStore {
+    /// Vault address (defaults to `VAULT_ADDR`)
+    pub address: Option<String>,
+    /// KV mount
+    pub mount: String,
+    /// Secret path inside the mount
+    pub path: String,
+    /// Field holding the key
+    pub field: String,
+}
+
+impl SecretStore for HashicorpVaultStore {
+    fn name(&self) -> &'static str {
+        "vault"
+    }
+
+    fn read(&self) -> Result<String, String> {
+        let mount = format!("-mount={}", self.mount);
+        let field = format!("-field={}", self.field);
+        let envs: Vec<(&str, &str)> = self
+            .address
+            .as_deref()
+            .map(|address| vec![("VAULT_ADDR", address)])
+            .unwrap_or_default();
+        run_cli("vault", &["kv", "get", &mount, &field, &self.path], &envs)
+    }
+}
+
+/// Variable injected into the app environment (e.g. by a CI runner).
+pub struct EnvironmentStore {
+    /// Variable name
+    pub variable: String,
+}
+
+impl SecretStore for EnvironmentStore {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    fn read(&self) -> Result<String, String> {
+        std::env::var(&self.variable)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("Environment variable {} is not set", self.variable))
+    }
+}
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Backend binding of a service, as persisted and entered in the UI.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BackendConfig {
+    /// 1Password secret reference
+    OnePassword {
+        /// `op://<vault>/<item>/<field>`
+        reference: String,
+    },
+    /// HashiCorp Vault KV secret
+    HashicorpVault {
+        /// Vault address (defaults to `VAULT_ADDR`)
+        #[serde(default)]
+        address: Option<String>,
+        /// KV mount (default `secret`)
+        #[serde(default = "default_mount")]
+        mount: String,
+        /// Secret path inside the mount
+        path: String,
+        /// Field holding the key
+        field: String,
+    },
+    /// Environment variable
+    Environment {
+        /// Variable name
+        variable: String,
+    },
+}
+
+fn default_mount() -> String {
+    "secret".to_string()
+}
+
+impl BackendConfig {
+    /// Check the binding for obvious mistakes.
+    pub fn validate(&self) -> Result<(), String> {
+        let required = |value: &str, what: &str| {
+            if value.trim().is_empty() {
+                Err(format!("{what} is required"))
+            } else {
+                Ok(())
+            }
+        };
+
+        match self {
+            BackendConfig::OnePassword { reference } => {
+                if !reference.starts_with("op://") {
+                    return Err(format!(
+                        "1Password references look like op://<vault>/<item>/<field> \
+                         (got {reference})"
+                    ));
+                }
+                Ok(())
+            }
+            BackendConfig::HashicorpVault {
+                mount, path, field, ..
+            } => {
+                required(mount, "Vault mount")?;
+                required(path, "Vault secret path")?;
+                required(field, "Vault field")
+            }
+            BackendConfig::Environment { variable } => {
+                required(variable, "Variable name")?;
+                if !variable
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    return Err(format!("Invalid environment variable name: {variable}"));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// The store this binding reads from.
+    pub fn store(&self) -> Box<dyn SecretStore> {
+        match self.clone() {
+            BackendConfig::OnePassword { reference } => Box::new(OnePasswordStore { reference }),
+            BackendConfig::HashicorpVault {
+                address,
+                mount,
+                path,
+                field,
+            } => Box::new(HashicorpVaultStore {
+                address,
+                mount,
+                path,
+                field,
+            }),
+            BackendConfig::Environment { variable } => Box::new(EnvironmentStore { variable }),
+        }
+    }
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Run a backend CLI and return its trimmed stdout.
+fn run_cli(program: &str, args: &[&str], envs: &[(&str, &str)]) -> Result<String, String> {
+    let mut cmd = Command::new(program);
+    cmd.args(args)
+        .envs(envs.iter().copied())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    // Windows-specific: Prevent console window from appearing
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd.spawn().map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("{program} CLI not found on PATH"),
+        _ => format!("Failed to run {program}: {e}"),
+    })?;
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|e| format!("Failed to wait for {program}: {e}"))?
+        {
+            break status;
+        }
+        if started.elapsed() > CLI_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!(
+                "{program} timed out after {}s",
+                CLI_TIMEOUT.as_secs()
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stdout.take() {
+        let _ = pipe.read_to_string(&mut stdout);
+    }
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+
+    if !status.success() {
+        return Err(format!("{program} failed ({status}): {}", stderr.trim()));
+    }
+    let value = stdout.trim();
+    if value.is_empty() {
+        return Err(format!("{program} returned an empty value"));
+    }
+    Ok(value.to_string())
+}
+
+// ============================================
+// BACKEND REGISTRY
+// ============================================
+
+/// Service bindings to external secret backends.
+#[derive(Clone)]
+pub struct SecretBackends {
+    /// Bindings keyed by service id
+    bindings: Arc<RwLock<BTreeMap<String, BackendConfig>>>,
+
+    /// Fetched values with their fetch time, keyed by service id
+    cache: Arc<RwLock<HashMap<String, (String, Instant)>>>,
+
+    /// Bindings file path (None disables persistence)
+    path: Option<PathBuf>,
+}
+
+impl SecretBackends {
+    /// Load bindings from the app config directory.
+    pub fn load(config_dir: Option<PathBuf>) -> Self {
+        let path = config_dir.map(|dir| dir.join(BACKENDS_FILE));
+        let bindings = path.as_deref().map(read_bindings).unwrap_or_default();
+
+        Self {
+            bindings: Arc::new(RwLock::new(bindings)),
+            cache: Arc::new(RwLock::new(HashMap::new())),
+            path,
+        }
+    }
+
+    /// All bindings keyed by service id.
+    pub fn list(&self) -> BTreeMap<String, BackendConfig> {
+        self.bindings.read().unwrap().clone()
+    }
+
+    /// Binding of a service, if it uses an external backend.
+    pub fn get(&self, service: &str) -> Option<BackendConfig> {
+        self.bindings
+            .read()
+            .unwrap()
+            .get(&service.to_lowercase())
+            .cloned()
+    }
+
+    /// Bind a service to a backend, or back to the project `.env` with `None`.
+    pub fn set(&self, service: &str, config: Option<BackendConfig>) -> Result<(), String> {
+        let service = service.trim().to_lowercase();
+        if service.is_empty() {
+            return Err("Service is required".to_string());
+        }
+
+        match config {
+            Some(config) => {
+                config.validate()?;
+                log::info!("Secret backend for {service}: {}", config.store().name());
+                self.bindings
+                    .write()
+                    .unwrap()
+                    .insert(service.clone(), config);
+            }
+            None => {
+                if self.bindings.write().unwrap().remove(&service).is_some() {
+                    log::info!("Secret backend for {service}: project .env");
+                }
+            }
+        }
+        self.cache.write().unwrap().remove(&service);
+        self.persist();
+        Ok(())
+    }
+
+    /// Fetch the key of a bound service.
+    ///
+    /// # Returns
+    ///
+    /// * `None` - The service is not bound (use the project `.env`)
+    /// * `Some(Ok(key))` - The key from the backend
+    /// * `Some(Err(String))` - The backend failed
+    pub fn read(&self, service: &str) -> Option<Result<String, String>> {
+        let service = service.to_lowercase();
+        let config = self.get(&service)?;
+
+        if let Some((value, fetched)) = self.cache.read().unwrap().get(&service) {
+            if fetched.elapsed() < CACHE_TTL {
+                return Some(Ok(value.clone()));
+            }
+        }
+
+        let store = config.store();
+        let result = store
+            .read()
+            .map_err(|e| format!("{} backend for {service}: {e}", store.name()));
+        if let Ok(ref value) = result {
+            redact::register(value);
+            self.cache
+                .write()
+                .unwrap()
+                .insert(service, (value.clone(), Instant::now()));
+        }
+        Some(result)
+    }
+
+    /// Key id reported for a bound service (e.g. `1password`).
+    pub fn key_id(&self, service: &str) -> Option<String> {
+        self.get(service)
+            .map(|config| config.store().name().to_string())
+    }
+
+    /// Forget cached values so the next read fetches again.
+    pub fn clear_cache(&self) {
+        self.cache.write().unwrap().clear();
+    }
+
+    /// Write the bindings to disk (logged on failure).
+    fn persist(&self) {
+        if let Some(ref path) = self.path {
+            if let Err(e) = write_bindings(path, &self.bindings.read().unwrap()) {
+                log::warn!("Failed to persist secret backends: {e}");
+            }
+        }
+    }
+}
+
+// ============================================
+// PERSISTENCE
+// ============================================
+
+/// Read bindings from disk, returning none if missing or invalid.
+fn read_bindings(path: &Path) -> BTreeMap<String, BackendConfig> {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid secret backends file {path:?}: {e}");
+            BTreeMap::new()
+        }),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+/// Write bindings to disk.
+fn write_bindings(path: &Path, bindings: &BTreeMap<String, BackendConfig>) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(bindings)
+        .map_err(|e| format!("Failed to serialize secret backends: {e}"))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_config() {
+        let op = BackendConfig::OnePassword {
+            reference: "Engineering/OpenAI".to_string(),
+        };
+        assert!(op.validate().is_err());
+
+        let vault: BackendConfig = serde_json::from_value(serde_json::json!({
+            "kind": "hashicorp_vault",
+            "path": "ai/openai",
+            "field": "api_key"
+        }))
+        .unwrap();
+        assert!(vault.validate().is_ok());
+        assert!(
+            matches!(vault, BackendConfig::HashicorpVault { ref mount, .. } if mount == "secret")
+        );
+
+        let env = BackendConfig::Environment {
+            variable: "CI OPENAI".to_string(),
+        };
+        assert!(env.validate().is_err());
+    }
+
+    #[test]
+    fn test_environment_backend_and_persistence() {
+        let dir = std::env::temp_dir().join(format!("af_backends_{}", uuid::Uuid::new_v4()));
+        let variable = format!("AF_TEST_KEY_{}", uuid::Uuid::new_v4().simple());
+        std::env::set_var(&variable, "ci-injected-value-123");
+
+        let backends = SecretBackends::load(Some(dir.clone()));
+        assert!(backends.read("openai").is_none());
+        backends
+            .set(
+                "OpenAI",
+                Some(BackendConfig::Environment {
+                    variable: variable.clone(),
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            backends.read("openai").unwrap().unwrap(),
+            "ci-injected-value-123"
+        );
+        assert_eq!(backends.key_id("openai").as_deref(), Some("env"));
+
+        // Bindings survive a reload
+        let reloaded = SecretBackends::load(Some(dir));
+        assert!(reloaded.get("openai").is_some());
+        reloaded.set("openai", None).unwrap();
+        assert!(reloaded.read("openai").is_none());
+    }
+}
@@ -0,0 +1,829 @@
+//! src-tauri/src/settings.rs
+//! =========================
+//! Persistent application settings store.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Settings are stored as JSON (`settings.json`) in the OS app-config
+//! directory and loaded at startup to build `IpcConfig`. Individual values
+//! are addressed by dotted keys (e.g. `ipc.timeout_secs`) and validated
+//! against a fixed schema before being written.
+//!
+//! IPC-related settings take effect the next time the plugin host starts;
+//! alert settings (see alerts.rs) the next time the app starts. Plugin
+//! signature settings (see packages.rs) apply to the next install or load,
+//! `plugins.load_on_startup` the next time the app starts, and plugin call
+//! limits (see quotas.rs), HTTP fetch settings (see http_fetch.rs), and
+//! granted directories (see project_fs.rs) to the next call. Log file
+//! retention (see logging.rs) and span export (see tracing_export.rs)
+//! apply the next time the app starts, and `ui.locale` (see
+//! error_messages.rs) too unless it is changed with `set_locale`.
+//! `network.offline` (see offline.rs) likewise, unless it is changed with
+//! `set_offline`, and the proxy settings (see proxy.rs) always.
+//!
+//! Usage:
+//!     ```rust
+//!     let store = SettingsStore::load(config_dir);
+//!     let config = store.get().to_ipc_config();
+//!     store.set("ipc.timeout_secs", json!(120))?;
+//!     ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use crate::error_messages::{normalize_locale, DEFAULT_LOCALE};
+use crate::http_fetch::{
+    validate_domain_pattern, FetchPolicy, FETCH_TIMEOUT_SECS, MAX_REQUEST_BYTES, MAX_RESPONSE_BYTES,
+};
+use crate::ipc::manager::IpcConfig;
+use crate::logging::{LogRetention, MAX_LOG_FILE_BYTES, MAX_ROTATED_FILES};
+use crate::packages::parse_public_key;
+use crate::proxy::{validate_proxy_url, ProxyConfig};
+use crate::quotas::PluginLimits;
+use crate::tracing_export::DEFAULT_OTLP_ENDPOINT;
+use crate::ipc::spawn::ProcessPriority;
+use crate::ipc::{
+    DEFAULT_TIMEOUT_SECS, HEALTH_CHECK_INTERVAL_SECS, HEALTH_CHECK_METHOD,
+    HEALTH_PROBE_TIMEOUT_SECS, HEARTBEAT_INTERVAL_SECS, MAX_CONSECUTIVE_FAILURES, MAX_LINE_BYTES,
+    MAX_MISSED_HEARTBEATS, MAX_PENDING_REQUESTS, MAX_RESPAWN_ATTEMPTS, PLUGIN_RELOAD_FAILURES,
+    SPAWN_TIMEOUT_SECS,
+};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// File name of the settings store inside the app config directory
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Default for `ipc.shutdown_timeout_secs`
+const DEFAULT_SHUTDOWN_TIMEOUT_SECS: u64 = 10;
+
+/// Allowed values for `ui.theme`
+const THEMES: &[&str] = &["system", "light", "dark"];
+
+/// Allowed values for `ipc.priority`
+const PRIORITIES: &[&str] = &["normal", "below_normal", "idle"];
+
+/// CPUs addressable by `ipc.cpu_affinity`
+const MAX_AFFINITY_CPUS: u64 = 64;
+
+// ============================================
+// SETTINGS TYPES
+// ============================================
+
+/// IPC / plugin host settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IpcSettings {
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+    /// Health check interval in seconds
+    pub health_check_interval_secs: u64,
+    /// Consecutive failed health checks before the host is degraded
+    pub max_consecutive_failures: u32,
+    /// Timeout of a single health check in seconds
+    pub probe_timeout_secs: u64,
+    /// RPC method called by the health check
+    pub health_check_method: String,
+    /// Auto-respawn on crash
+    pub auto_respawn: bool,
+    /// Maximum respawn attempts
+    pub max_respawn_attempts: u32,
+    /// Consecutive plugin exceptions before the plugin is reloaded (0 disables)
+    pub plugin_reload_failures: u32,
+    /// Requests waiting for a response before new ones are rejected (0 disables)
+    pub max_pending_requests: usize,
+    /// Subprocess RSS (MB) that triggers a memory warning (0 disables)
+    pub memory_warning_mb: u64,
+    /// Maximum length (MB) of a message from the host; longer ones are dropped
+    pub max_line_mb: u64,
+    /// Seconds to wait for a clean shutdown on window close before quitting anyway
+    pub shutdown_timeout_secs: u64,
+    /// Seconds a new host has to answer its first request before it is killed
+    pub spawn_timeout_secs: u64,
+    /// Seconds between heartbeats sent by the host (0 disables them)
+    pub heartbeat_interval_secs: u64,
+    /// Consecutive missed heartbeats before the host is marked degraded
+    pub max_missed_heartbeats: u32,
+    /// Scheduling priority of the host
+    pub priority: ProcessPriority,
+    /// CPUs the host may run on (empty allows all)
+    pub cpu_affinity: Vec<u64>,
+}
+
+impl Default for IpcSettings {
+    fn default() -> Self {
+        Self {
+            timeout_secs: DEFAULT_TIMEOUT_SECS,
+            health_check_interval_secs: HEALTH_CHECK_INTERVAL_SECS,
+            max_consecutive_failures: MAX_CONSECUTIVE_FAILURES,
+            probe_timeout_secs: HEALTH_PROBE_TIMEOUT_SECS,
+            health_check_method: HEALTH_CHECK_METHOD.to_string(),
+            auto_respawn: true,
+            max_respawn_attempts: MAX_RESPAWN_ATTEMPTS,
+            plugin_reload_failures: PLUGIN_RELOAD_FAILURES,
+            max_pending_requests: MAX_PENDING_REQUESTS,
+            memory_warning_mb: 0,
+            max_line_mb: u64::try_from(MAX_LINE_BYTES / (1024 * 1024)).unwrap_or(u64::MAX),
+            shutdown_timeout_secs: DEFAULT_SHUTDOWN_TIMEOUT_SECS,
+            spawn_timeout_secs: SPAWN_TIMEOUT_SECS,
+            heartbeat_interval_secs: HEARTBEAT_INTERVAL_SECS,
+            max_missed_heartbeats: MAX_MISSED_HEARTBEATS,
+            priority: ProcessPriority::Normal,
+            cpu_affinity: Vec::new(),
+        }
+    }
+}
+
+/// Python interpreter settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PythonSettings {
+    /// Python executable path
+    pub path: String,
+    /// Plugin host module path
+    pub module: String,
+    /// Interpreter flags placed before `-m` (e.g. `["-X", "utf8"]`)
+    pub args: Vec<String>,
+}
+
+impl Default for PythonSettings {
+    fn default() -> Self {
+        Self {
+            path: "python".to_string(),
+            module: "plugins._host".to_string(),
+            args: Vec::new(),
+        }
+    }
+}
+
+/// User interface settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiSettings {
+    /// Theme: system, light, or dark
+    pub theme: String,
+    /// Locale of error messages (language tag, e.g. `de` or `pt-BR`)
+    pub locale: String,
+}
+
+impl Default for UiSettings {
+    fn default() -> Self {
+        Self {
+            theme: "system".to_string(),
+            locale: DEFAULT_LOCALE.to_string(),
+        }
+    }
+}
+
+/// Telemetry settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    /// Whether the user opted in to telemetry
+    pub enabled: bool,
+}
+
+/// Plugin host health alert settings (see alerts.rs).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlertSettings {
+    /// Show a desktop notification
+    pub notify: bool,
+    /// URL alerts are POSTed to as JSON (empty disables)
+    pub webhook_url: String,
+    /// File alerts are appended to as JSON lines (empty disables)
+    pub file: String,
+}
+
+/// Plugin settings: package signatures (see packages.rs), startup loading,
+/// and call limits (see quotas.rs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginSettings {
+    /// Minisign public keys of trusted publishers
+    pub trusted_keys: Vec<String>,
+    /// Plugins the user allowed to run without a trusted signature
+    pub allow_unsigned: Vec<String>,
+    /// Load the plugins shipped with the app although they are unsigned
+    pub trust_bundled: bool,
+    /// Plugins loaded (after their dependencies) once the host is ready
+    pub load_on_startup: Vec<String>,
+    /// Call limits of plugins without their own
+    pub default_limits: PluginLimits,
+    /// Call limits by plugin name (unset fields use `default_limits`)
+    pub limits: BTreeMap<String, PluginLimits>,
+}
+
+impl Default for PluginSettings {
+    fn default() -> Self {
+        Self {
+            trusted_keys: Vec::new(),
+            allow_unsigned: Vec::new(),
+            trust_bundled: true,
+            load_on_startup: Vec::new(),
+            default_limits: PluginLimits::default(),
+            limits: BTreeMap::new(),
+        }
+    }
+}
+
+impl PluginSettings {
+    /// Call limits of a plugin.
+    pub fn limits_for(&self, plugin: &str) -> PluginLimits {
+        self.limits
+            .get(plugin)
+            .map_or(self.default_limits, |limits| limits.or(self.default_limits))
+    }
+}
+
+/// Settings of the `http_fetch` command (see http_fetch.rs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpSettings {
+    /// Hosts that may be fetched (`example.com` or `*.example.com`)
+    pub allowed_domains: Vec<String>,
+    /// Request timeout
+    pub timeout_secs: u64,
+    /// Largest request body in bytes
+    pub max_request_bytes: u64,
+    /// Largest response body in bytes
+    pub max_response_bytes: u64,
+}
+
+impl Default for HttpSettings {
+    fn default() -> Self {
+        Self {
+            allowed_domains: Vec::new(),
+            timeout_secs: FETCH_TIMEOUT_SECS,
+            max_request_bytes: MAX_REQUEST_BYTES,
+            max_response_bytes: MAX_RESPONSE_BYTES,
+        }
+    }
+}
+
+impl HttpSettings {
+    /// Policy for requests made by `http_fetch`.
+    pub fn policy(&self) -> FetchPolicy {
+        FetchPolicy {
+            allowed_domains: Some(self.allowed_domains.clone()),
+            timeout_secs: self.timeout_secs,
+            max_request_bytes: self.max_request_bytes,
+            max_response_bytes: self.max_response_bytes,
+        }
+    }
+}
+
+/// Settings of the `fs_*` commands (see project_fs.rs).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FsSettings {
+    /// Absolute directories reachable besides the project root
+    pub allowed_dirs: Vec<String>,
+}
+
+/// Log file rotation and retention (see logging.rs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingSettings {
+    /// Size at which the log file is rotated, in MB
+    pub max_file_mb: u64,
+    /// Rotated log files kept
+    pub max_files: usize,
+    /// Also start a new log file each day
+    pub rotate_daily: bool,
+    /// Delete rotated log files older than this many days (0 keeps them)
+    pub max_age_days: u32,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            max_file_mb: MAX_LOG_FILE_BYTES / (1024 * 1024),
+            max_files: MAX_ROTATED_FILES,
+            rotate_daily: false,
+            max_age_days: 0,
+        }
+    }
+}
+
+impl LoggingSettings {
+    /// Retention limits of the log file.
+    pub fn retention(&self) -> LogRetention {
+        LogRetention {
+            max_file_bytes: self.max_file_mb.max(1) * 1024 * 1024,
+            max_files: self.max_files.max(1),
+            rotate_daily: self.rotate_daily,
+            max_age_days: self.max_age_days,
+        }
+    }
+}
+
+/// Span export to an OpenTelemetry collector (see tracing_export.rs).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TracingSettings {
+    /// Export spans
+    pub enabled: bool,
+    /// OTLP (gRPC) endpoint of the collector or Jaeger
+    pub otlp_endpoint: String,
+}
+
+impl Default for TracingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            otlp_endpoint: DEFAULT_OTLP_ENDPOINT.to_string(),
+        }
+    }
+}
+
+/// Network access settings (see offline.rs and proxy.rs).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    /// Block requests to other machines
+    pub offline: bool,
+    /// Proxy for `http://` URLs (empty uses `HTTP_PROXY`)
+    pub http_proxy: String,
+    /// Proxy for `https://` URLs (empty uses `HTTPS_PROXY`)
+    pub https_proxy: String,
+    /// Comma-separated hosts and domains that bypass the proxies
+    pub no_proxy: String,
+}
+
+impl NetworkSettings {
+    /// Proxies of the app's requests and the plugin hosts.
+    pub fn proxy(&self) -> ProxyConfig {
+        ProxyConfig {
+            http: self.http_proxy.trim().to_string(),
+            https: self.https_proxy.trim().to_string(),
+            no_proxy: self.no_proxy.trim().to_string(),
+        }
+    }
+}
+
+/// All application settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub ipc: IpcSettings,
+    pub python: PythonSettings,
+    pub ui: UiSettings,
+    pub telemetry: TelemetrySettings,
+    pub alerts: AlertSettings,
+    pub plugins: PluginSettings,
+    pub http: HttpSettings,
+    pub fs: FsSettings,
+    pub logging: LoggingSettings,
+    pub tracing: TracingSettings,
+    pub network: NetworkSettings,
+}
+
+impl Settings {
+    /// Build an `IpcConfig` from these settings.
+    pub fn to_ipc_config(&self) -> IpcConfig {
+        IpcConfig::default()
+            .with_python_path(&self.python.path)
+            .with_module_path(&self.python.module)
+            .with_interpreter_args(self.python.args.clone())
+            .with_timeout(self.ipc.timeout_secs)
+            .with_health_check_interval(self.ipc.health_check_interval_secs)
+            .with_max_consecutive_failures(self.ipc.max_consecutive_failures)
+            .with_probe_timeout(self.ipc.probe_timeout_secs)
+            .with_spawn_timeout(self.ipc.spawn_timeout_secs)
+            .with_heartbeat_interval(self.ipc.heartbeat_interval_secs)
+            .with_max_missed_heartbeats(self.ipc.max_missed_heartbeats)
+            .with_priority(self.ipc.priority)
+            .with_cpu_affinity(
+                Some(
+                    self.ipc
+                        .cpu_affinity
+                        .iter()
+                        .filter(|&&cpu| cpu < MAX_AFFINITY_CPUS)
+                        .fold(0, |mask, cpu| mask | (1 << cpu)),
+                )
+                .filter(|&mask| mask != 0),
+            )
+            .with_health_check_method(&self.ipc.health_check_method)
+            .with_auto_respawn(self.ipc.auto_respawn)
+            .with_max_respawn_attempts(self.ipc.max_respawn_attempts)
+            .with_plugin_reload_failures(self.ipc.plugin_reload_failures)
+            .with_max_pending_requests(self.ipc.max_pending_requests)
+            .with_memory_warning_threshold(
+                Some(self.ipc.memory_warning_mb).filter(|&mb| mb > 0),
+            )
+            .with_max_line_bytes(
+                usize::try_from(self.ipc.max_line_mb.saturating_mul(1024 * 1024))
+                    .unwrap_or(usize::MAX),
+            )
+            .with_offline(self.network.offline)
+    }
+}
+
+// ============================================
+// SCHEMA VALIDATION
+// ============================================
+
+/// Convert a dotted key (`ipc.timeout_secs`) to a JSON pointer.
+pub fn key_to_pointer(key: &str) -> String {
+    format!("/{}", key.replace('.', "/"))
+}
+
+/// Validate an unsigned integer within an inclusive range.
+fn expect_u64(key: &str, value: &Value, min: u64, max: u64) -> Result<(), String> {
+    match value.as_u64() {
+        Some(n) if (min..=max).contains(&n) => Ok(()),
+        _ => Err(format!("{key} must be an integer between {min} and {max}")),
+    }
+}
+
+/// Validate plugin call limits (see quotas.rs).
+fn expect_limits(key: &str, value: &Value) -> Result<(), String> {
+    let limits: PluginLimits =
+        serde_json::from_value(value.clone()).map_err(|e| format!("{key}: {e}"))?;
+    limits.validate().map_err(|e| format!("{key}: {e}"))
+}
+
+/// Validate a value against the settings schema.
+///
+/// # Returns
+///
+/// * `Ok(())` - Key is known and value is valid
+/// * `Err(String)` - Unknown key or invalid value
+pub fn validate_setting(key: &str, value: &Value) -> Result<(), String> {
+    match key {
+        "ipc.timeout_secs" => expect_u64(key, value, 1, 3600),
+        "ipc.health_check_interval_secs" => expect_u64(key, value, 1, 3600),
+        "ipc.max_respawn_attempts" => expect_u64(key, value, 0, 20),
+        "ipc.max_consecutive_failures" => expect_u64(key, value, 1, 100),
+        "ipc.plugin_reload_failures" => expect_u64(key, value, 0, 100),
+        "ipc.max_pending_requests" => expect_u64(key, value, 0, 1_000_000),
+        "ipc.probe_timeout_secs" => expect_u64(key, value, 1, 3600),
+        "ipc.memory_warning_mb" => expect_u64(key, value, 0, 1024 * 1024),
+        "ipc.max_line_mb" => expect_u64(key, value, 1, 4096),
+        "ipc.shutdown_timeout_secs" => expect_u64(key, value, 1, 300),
+        "ipc.spawn_timeout_secs" => expect_u64(key, value, 1, 3600),
+        "ipc.heartbeat_interval_secs" => expect_u64(key, value, 0, 3600),
+        "ipc.max_missed_heartbeats" => expect_u64(key, value, 1, 100),
+        "http.timeout_secs" => expect_u64(key, value, 1, 600),
+        "http.max_request_bytes" => expect_u64(key, value, 0, 100 * 1024 * 1024),
+        "http.max_response_bytes" => expect_u64(key, value, 1, 1024 * 1024 * 1024),
+        "logging.max_file_mb" => expect_u64(key, value, 1, 1024),
+        "logging.max_files" => expect_u64(key, value, 1, 100),
+        "logging.max_age_days" => expect_u64(key, value, 0, 3650),
+        "ipc.auto_respawn"
+        | "telemetry.enabled"
+        | "alerts.notify"
+        | "logging.rotate_daily"
+        | "tracing.enabled"
+        | "network.offline"
+        | "plugins.trust_bundled" => {
+            if value.is_boolean() {
+                Ok(())
+            } else {
+                Err(format!("{key} must be a boolean"))
+            }
+        }
+        "python.path" | "python.module" | "ipc.health_check_method" => match value.as_str() {
+            Some(s) if !s.trim().is_empty() => Ok(()),
+            _ => Err(format!("{key} must be a non-empty string")),
+        },
+        "alerts.webhook_url" => match value.as_str() {
+            Some(s) if s.is_empty() || s.starts_with("http://") || s.starts_with("https://") => {
+                Ok(())
+            }
+            _ => Err(format!("{key} must be empty or an http(s) URL")),
+        },
+        "network.http_proxy" | "network.https_proxy" => match value.as_str() {
+            Some(url) => validate_proxy_url(url.trim()).map_err(|e| format!("{key}: {e}")),
+            None => Err(format!("{key} must be a string")),
+        },
+        "tracing.otlp_endpoint" => match value.as_str() {
+            Some(s) if s.starts_with("http://") || s.starts_with("https://") => Ok(()),
+            _ => Err(format!("{key} must be an http(s) URL")),
+        },
+        "alerts.file" | "network.no_proxy" => {
+            if value.is_string() {
+                Ok(())
+            } else {
+                Err(format!("{key} must be a string"))
+            }
+        }
+        "plugins.trusted_keys" => match value.as_array() {
+            Some(keys) => keys.iter().try_for_each(|entry| match entry.as_str() {
+                Some(public_key) => parse_public_key(public_key).map(|_| ()),
+                None => Err(format!("{key} must be a list of minisign public keys")),
+            }),
+            None => Err(format!("{key} must be a list of minisign public keys")),
+        },
+        "python.args" | "plugins.allow_unsigned" | "plugins.load_on_startup" => {
+            match value.as_array() {
+                Some(args)
+                    if args
+                        .iter()
+                        .all(|arg| arg.as_str().is_some_and(|s| !s.is_empty())) =>
+                {
+                    Ok(())
+                }
+                _ => Err(format!("{key} must be a list of non-empty strings")),
+            }
+        }
+        "http.allowed_domains" => match value.as_array() {
+            Some(domains) => domains.iter().try_for_each(|entry| match entry.as_str() {
+                Some(pattern) => validate_domain_pattern(pattern),
+                None => Err(format!("{key} must be a list of domains")),
+            }),
+            None => Err(format!("{key} must be a list of domains")),
+        },
+        "fs.allowed_dirs" => match value.as_array() {
+            Some(dirs)
+                if dirs
+                    .iter()
+                    .all(|dir| dir.as_str().is_some_and(|dir| Path::new(dir).is_absolute())) =>
+            {
+                Ok(())
+            }
+            _ => Err(format!("{key} must be a list of absolute directories")),
+        },
+        "ipc.priority" => match value.as_str() {
+            Some(s) if PRIORITIES.contains(&s) => Ok(()),
+            _ => Err(format!("{key} must be one of: {}", PRIORITIES.join(", "))),
+        },
+        "ipc.cpu_affinity" => match value.as_array() {
+            Some(cpus)
+                if cpus
+                    .iter()
+                    .all(|cpu| cpu.as_u64().is_some_and(|cpu| cpu < MAX_AFFINITY_CPUS)) =>
+            {
+                Ok(())
+            }
+            _ => Err(format!(
+                "{key} must be a list of CPU indices below {MAX_AFFINITY_CPUS}"
+            )),
+        },
+        "plugins.default_limits" => expect_limits(key, value),
+        "plugins.limits" => match value.as_object() {
+            Some(plugins) => plugins
+                .iter()
+                .try_for_each(|(plugin, limits)| expect_limits(&format!("{key}.{plugin}"), limits)),
+            None => Err(format!("{key} must be an object of limits by plugin name")),
+        },
+        "ui.theme" => match value.as_str() {
+            Some(s) if THEMES.contains(&s) => Ok(()),
+            _ => Err(format!("{key} must be one of: {}", THEMES.join(", "))),
+        },
+        "ui.locale" => match value.as_str() {
+            Some(s) => normalize_locale(s).map(|_| ()),
+            None => Err(format!("{key} must be a language tag")),
+        },
+        _ => Err(format!("Unknown setting: {key}")),
+    }
+}
+
+// ============================================
+// SETTINGS STORE
+// ============================================
+
+/// Settings store backed by a JSON file.
+#[derive(Debug, Clone)]
+pub struct SettingsStore {
+    /// Current settings
+    settings: Arc<RwLock<Settings>>,
+
+    /// Settings file path (None disables persistence)
+    path: Option<PathBuf>,
+}
+
+impl SettingsStore {
+    /// Load settings from the app config directory, falling back to defaults.
+    pub fn load(config_dir: Option<PathBuf>) -> Self {
+        let path = config_dir.map(|dir| dir.join(SETTINGS_FILE));
+        let settings = path.as_deref().map(read_settings).unwrap_or_default();
+
+        Self {
+            settings: Arc::new(RwLock::new(settings)),
+            path,
+        }
+    }
+
+    /// Get a snapshot of all settings.
+    pub fn get(&self) -> Settings {
+        self.settings.read().unwrap().clone()
+    }
+
+    /// Get a single setting (or all settings when `key` is None) as JSON.
+    pub fn get_value(&self, key: Option<&str>) -> Result<Value, String> {
+        let all = serde_json::to_value(self.get()).map_err(|e| e.to_string())?;
+        match key {
+            None => Ok(all),
+            Some(k) => all
+                .pointer(&key_to_pointer(k))
+                .cloned()
+                .ok_or_else(|| format!("Unknown setting: {k}")),
+        }
+    }
+
+    /// Validate and set a single setting, persisting the result.
+    pub fn set(&self, key: &str, value: Value) -> Result<Settings, String> {
+        validate_setting(key, &value)?;
+        self.update(|all| {
+            let slot = all
+                .pointer_mut(&key_to_pointer(key))
+                .ok_or_else(|| format!("Unknown setting: {key}"))?;
+            *slot = value;
+            Ok(())
+        })
+    }
+
+    /// Reset one setting (or all settings when `key` is None) to defaults.
+    pub fn reset(&self, key: Option<&str>) -> Result<Settings, String> {
+        let defaults = serde_json::to_value(Settings::default()).map_err(|e| e.to_string())?;
+        match key {
+            None => self.update(|all| {
+                *all = defaults;
+                Ok(())
+            }),
+            Some(k) => {
+                let pointer = key_to_pointer(k);
+                let default = defaults
+                    .pointer(&pointer)
+                    .cloned()
+                    .ok_or_else(|| format!("Unknown setting: {k}"))?;
+                self.update(|all| {
+                    if let Some(slot) = all.pointer_mut(&pointer) {
+                        *slot = default;
+                    }
+                    Ok(())
+                })
+            }
+        }
+    }
+
+    /// Apply an edit to the JSON form of the settings and persist it.
+    fn update<F>(&self, edit: F) -> Result<Settings, String>
+    where
+        F: FnOnce(&mut Value) -> Result<(), String>,
+    {
+        let mut guard = self.settings.write().unwrap();
+        let mut all = serde_json::to_value(&*guard).map_err(|e| e.to_string())?;
+        edit(&mut all)?;
+
+        let updated: Settings =
+            serde_json::from_value(all).map_err(|e| format!("Invalid settings: {e}"))?;
+
+        if let Some(ref path) = self.path {
+            write_settings(path, &updated)?;
+        }
+
+        *guard = updated.clone();
+        Ok(updated)
+    }
+}
+
+// ============================================
+// PERSISTENCE
+// ============================================
+
+/// Read settings from disk, returning defaults if missing or invalid.
+fn read_settings(path: &Path) -> Settings {
+    match fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            log::warn!("Ignoring invalid settings file {path:?}: {e}");
+            Settings::default()
+        }),
+        Err(_) => Settings::default(),
+    }
+}
+
+/// Write settings to disk.
+fn write_settings(path: &Path, settings: &Settings) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {e}"))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {path:?}: {e}"))
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+    use serde_json::json;
+
+    #[test]
+    fn test_defaults_build_ipc_config() {
+        let config = Settings::default().to_ipc_config();
+
+        assert_eq!(config.python_path, "python");
+        assert_eq!(config.module_path, "plugins._host");
+        assert_eq!(config.timeout_secs, DEFAULT_TIMEOUT_SECS);
+        assert_eq!(config.health_check_method, "ping");
+        assert!(config.auto_respawn);
+        assert_eq!(config.cpu_affinity, None);
+        assert_eq!(config.max_line_bytes, MAX_LINE_BYTES);
+        assert!(!config.offline);
+
+        let mut settings = Settings::default();
+        settings.ipc.cpu_affinity = vec![0, 2];
+        settings.network.offline = true;
+        assert_eq!(settings.to_ipc_config().cpu_affinity, Some(0b101));
+        assert!(settings.to_ipc_config().offline);
+    }
+
+    #[test]
+    fn test_validate_setting() {
+        assert!(validate_setting("ipc.timeout_secs", &json!(120)).is_ok());
+        assert!(validate_setting("ipc.timeout_secs", &json!(0)).is_err());
+        assert!(validate_setting("ipc.timeout_secs", &json!("120")).is_err());
+        assert!(validate_setting("ipc.spawn_timeout_secs", &json!(300)).is_ok());
+        assert!(validate_setting("ipc.spawn_timeout_secs", &json!(0)).is_err());
+        assert!(validate_setting("ipc.heartbeat_interval_secs", &json!(0)).is_ok());
+        assert!(validate_setting("ipc.max_missed_heartbeats", &json!(0)).is_err());
+        assert!(validate_setting("ipc.max_line_mb", &json!(256)).is_ok());
+        assert!(validate_setting("ipc.max_line_mb", &json!(0)).is_err());
+        assert!(validate_setting("ipc.plugin_reload_failures", &json!(0)).is_ok());
+        assert!(validate_setting("ipc.plugin_reload_failures", &json!(-1)).is_err());
+        assert!(validate_setting("ipc.max_pending_requests", &json!(0)).is_ok());
+        assert!(validate_setting("ipc.max_pending_requests", &json!(2_000_000)).is_err());
+        assert!(validate_setting("ipc.priority", &json!("below_normal")).is_ok());
+        assert!(validate_setting("ipc.priority", &json!("realtime")).is_err());
+        assert!(validate_setting("ipc.cpu_affinity", &json!([1, 2, 3])).is_ok());
+        assert!(validate_setting("ipc.cpu_affinity", &json!([64])).is_err());
+        assert!(validate_setting("ui.theme", &json!("dark")).is_ok());
+        assert!(validate_setting("ui.theme", &json!("neon")).is_err());
+        assert!(validate_setting("ui.locale", &json!("pt-BR")).is_ok());
+        assert!(validate_setting("ui.locale", &json!("de/at")).is_err());
+        assert!(validate_setting("python.path", &json!("  ")).is_err());
+        assert!(validate_setting("python.args", &json!(["-X", "utf8"])).is_ok());
+        assert!(validate_setting("python.args", &json!("-O")).is_err());
+        assert!(validate_setting("plugins.allow_unsigned", &json!(["tts_kokoro"])).is_ok());
+        assert!(validate_setting("plugins.trusted_keys", &json!(["not a key"])).is_err());
+        assert!(validate_setting("plugins.trust_bundled", &json!(false)).is_ok());
+        assert!(validate_setting("plugins.trust_bundled", &json!("yes")).is_err());
+        let limits = json!({ "tts_kokoro": { "max_concurrent": 2 } });
+        assert!(validate_setting("plugins.limits", &limits).is_ok());
+        let limits = json!({ "tts_kokoro": { "max_concurrent": 0 } });
+        assert!(validate_setting("plugins.limits", &limits).is_err());
+        let limits = json!({ "max_calls_per_hour": 10 });
+        assert!(validate_setting("plugins.default_limits", &limits).is_err());
+        assert!(validate_setting("alerts.webhook_url", &json!("http://127.0.0.1:9000")).is_ok());
+        assert!(validate_setting("alerts.webhook_url", &json!("ftp://host")).is_err());
+        assert!(validate_setting("http.allowed_domains", &json!(["*.example.com"])).is_ok());
+        assert!(validate_setting("http.allowed_domains", &json!(["https://x.com"])).is_err());
+        assert!(validate_setting("http.max_response_bytes", &json!(0)).is_err());
+        assert!(validate_setting("fs.allowed_dirs", &json!(["relative/dir"])).is_err());
+        assert!(validate_setting("logging.max_file_mb", &json!(0)).is_err());
+        assert!(validate_setting("logging.max_files", &json!(10)).is_ok());
+        assert!(validate_setting("logging.rotate_daily", &json!("yes")).is_err());
+        assert!(validate_setting("tracing.enabled", &json!(true)).is_ok());
+        let endpoint = json!("http://localhost:4317");
+        assert!(validate_setting("tracing.otlp_endpoint", &endpoint).is_ok());
+        assert!(validate_setting("tracing.otlp_endpoint", &json!("")).is_err());
+        assert!(validate_setting("network.offline", &json!(true)).is_ok());
+        assert!(validate_setting("network.offline", &json!("on")).is_err());
+        let proxy = json!("http://proxy.corp:3128");
+        assert!(validate_setting("network.https_proxy", &proxy).is_ok());
+        assert!(validate_setting("network.http_proxy", &json!("")).is_ok());
+        assert!(validate_setting("network.http_proxy", &json!("proxy.corp")).is_err());
+        assert!(validate_setting("network.no_proxy", &json!("localhost,.corp")).is_ok());
+        assert!(validate_setting("network.no_proxy", &json!(["localhost"])).is_err());
+        assert!(validate_setting("nope.key", &json!(true)).is_err());
+    }
+
+    #[test]
+    fn test_set_get_and_reset() {
+        let store = SettingsStore::load(None);
+
+        store.set("ipc.timeout_secs", json!(120)).unwrap();
+        assert_eq!(store.get_value(Some("ipc.timeout_secs")).unwrap(), json!(120));
+
+        store.reset(Some("ipc.timeout_secs")).unwrap();
+        assert_eq!(store.get().ipc.timeout_secs, DEFAULT_TIMEOUT_SECS);
+
+        store.set("telemetry.enabled", json!(true)).unwrap();
+        store.reset(None).unwrap();
+        assert_eq!(store.get(), Settings::default());
+    }
+
+    #[test]
+    fn test_persisted_across_loads() {
+        let dir = temp_dir("settings");
+
+        let store = SettingsStore::load(Some(dir.clone()));
+        store.set("python.path", json!("python3.11")).unwrap();
+
+        let reloaded = SettingsStore::load(Some(dir));
+        assert_eq!(reloaded.get().python.path, "python3.11");
+    }
+}
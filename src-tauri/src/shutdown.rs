@@ -0,0 +1,124 @@
+//! src-tauri/src/shutdown.rs
+//! =========================
+//! Coordinated shutdown when the main window is closed.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Closing the window is intercepted (see main.rs). In-flight plugin calls
+//! in every workspace and named host get up to half the timeout to finish, then each
+//! plugin host is shut down, compile and file watchers and preview servers
+//! are stopped, the database WAL is checkpointed, and the log and exported
+//! spans are flushed. Only then does the app exit. If the whole sequence
+//! takes longer than `ipc.shutdown_timeout_secs`, the app quits anyway;
+//! plugin hosts exit when their stdin closes.
+//!
+//! Usage:
+//!     ```rust
+//!     if shutdown.begin() {
+//!         api.prevent_close();
+//!         tauri::async_runtime::spawn(shutdown.clone().run(app_handle));
+//!     }
+//!     ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::fs_watch::FileWatcher;
+use crate::ipc::hosts::HostRegistry;
+use crate::ipc::manager::IpcManagerState;
+use crate::preview::PreviewManager;
+use crate::storage::Storage;
+use crate::watch::CompileWatcher;
+use crate::workspace::WorkspaceRegistry;
+
+/// Shutdown sequence run once on window close.
+///
+/// Clones share the started flag, so the sequence runs only once.
+#[derive(Clone)]
+pub struct ShutdownCoordinator {
+    /// Set once the sequence has started
+    started: Arc<AtomicBool>,
+    /// Time allowed before quitting anyway
+    timeout: Duration,
+}
+
+impl ShutdownCoordinator {
+    /// Create a coordinator with the force-quit timeout.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            started: Arc::new(AtomicBool::new(false)),
+            timeout,
+        }
+    }
+
+    /// Claim the shutdown; false if it is already running.
+    pub fn begin(&self) -> bool {
+        !self.started.swap(true, Ordering::SeqCst)
+    }
+
+    /// Run the shutdown sequence and exit the app.
+    pub async fn run(self, app: AppHandle) {
+        log::info!("Shutting down (timeout {}s)", self.timeout.as_secs());
+
+        if tokio::time::timeout(self.timeout, Self::graceful(&app, self.timeout / 2))
+            .await
+            .is_err()
+        {
+            log::warn!(
+                "Shutdown did not finish within {}s, quitting anyway",
+                self.timeout.as_secs()
+            );
+        }
+
+        log::info!("Shutdown complete");
+        log::logger().flush();
+        // Flushing spans blocks on the exporter, which runs on this runtime
+        let _ = tokio::task::spawn_blocking(crate::tracing_export::shutdown).await;
+        app.exit(0);
+    }
+
+    /// Drain and stop plugin hosts, then stop background services.
+    async fn graceful(app: &AppHandle, drain_timeout: Duration) {
+        let managers: Vec<_> = app
+            .state::<WorkspaceRegistry>()
+            .entries()
+            .into_iter()
+            .map(|(_, manager)| manager)
+            .chain(app.state::<HostRegistry>().managers())
+            .collect();
+
+        let drained =
+            futures::future::join_all(managers.iter().map(|m| m.drain(drain_timeout))).await;
+        let abandoned: usize = drained.iter().sum();
+        if abandoned > 0 {
+            log::warn!("Abandoning {abandoned} in-flight plugin request(s)");
+        }
+
+        for result in
+            futures::future::join_all(managers.iter().map(IpcManagerState::shutdown)).await
+        {
+            if let Err(e) = result {
+                log::error!("Plugin host shutdown error: {e}");
+            }
+        }
+
+        let watcher = app.state::<CompileWatcher>();
+        for watch in watcher.list() {
+            watcher.stop(&watch.id);
+        }
+        let files = app.state::<FileWatcher>();
+        for watch in files.list() {
+            files.stop(&watch.id);
+        }
+        let previews = app.state::<PreviewManager>();
+        for preview in previews.list() {
+            previews.stop(&preview.app_id);
+        }
+
+        if let Err(e) = app.state::<Storage>().checkpoint() {
+            log::warn!("{e}");
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! src-tauri/src/startup.rs
+//! ========================
+//! Startup progress reporting for the splash screen.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Boot runs through a fixed list of stages (`StartupStage`). Each stage
+//! change is recorded and, once the app handle is attached, emitted as a
+//! `startup://progress` event carrying the step and the full status. Steps
+//! recorded before the window existed (or before the frontend subscribed)
+//! are available through the `startup_status` command, so the frontend
+//! fetches the status first and then follows the events.
+//!
+//! Usage:
+//!     ```rust
+//!     let progress = StartupProgress::new();
+//!     progress.start(StartupStage::ResolvingProjectRoot, None);
+//!     progress.attach(app.handle());
+//!     progress.finish(StartupStage::HostReady, Some(format!("PID {pid}")));
+//!     ```
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Event emitted on every step
+pub const PROGRESS_EVENT: &str = "startup://progress";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Boot stages, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupStage {
+    ResolvingProjectRoot,
+    LocatingPython,
+    SpawningHost,
+    HostReady,
+    PluginScanComplete,
+}
+
+impl StartupStage {
+    /// All stages, in order.
+    pub const ALL: [Self; 5] = [
+        Self::ResolvingProjectRoot,
+        Self::LocatingPython,
+        Self::SpawningHost,
+        Self::HostReady,
+        Self::PluginScanComplete,
+    ];
+}
+
+/// State of a stage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Started,
+    Done,
+    Failed,
+}
+
+/// A recorded stage change.
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupStep {
+    /// Stage
+    pub stage: StartupStage,
+    /// Stage state
+    pub status: StepStatus,
+    /// Detail (e.g. resolved path) or error
+    pub message: Option<String>,
+    /// Milliseconds since startup began
+    pub elapsed_ms: u64,
+}
+
+/// Startup status snapshot.
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupStatus {
+    /// Latest step per stage, in stage order
+    pub steps: Vec<StartupStep>,
+    /// Number of stages
+    pub total_stages: usize,
+    /// Whether the last stage is done
+    pub complete: bool,
+    /// Whether any stage failed
+    pub failed: bool,
+}
+
+/// Payload of `PROGRESS_EVENT`.
+#[derive(Debug, Clone, Serialize)]
+struct ProgressEvent {
+    step: StartupStep,
+    status: StartupStatus,
+}
+
+/// Recorded steps and the handle used to emit them.
+struct ProgressState {
+    started: Instant,
+    steps: Vec<StartupStep>,
+    app: Option<AppHandle>,
+}
+
+// ============================================
+// HELPERS
+// ============================================
+
+/// Resolve an executable the way the OS would when spawning it.
+///
+/// Paths with a directory component are checked as-is; bare names are
+/// looked up on `PATH` (with `.exe` on Windows).
+pub fn find_executable(program: &str) -> Option<PathBuf> {
+    let candidate = Path::new(program);
+    if candidate.components().count() > 1 {
+        return candidate.is_file().then(|| candidate.to_path_buf());
+    }
+
+    let names: Vec<String> = if cfg!(windows) && candidate.extension().is_none() {
+        vec![format!("{program}.exe"), program.to_string()]
+    } else {
+        vec![program.to_string()]
+    };
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .flat_map(|dir| names.iter().map(move |name| dir.join(name)))
+        .find(|path| path.is_file())
+}
+
+// ============================================
+// STARTUP PROGRESS
+// ============================================
+
+/// Startup progress recorder.
+#[derive(Clone)]
+pub struct StartupProgress {
+    state: Arc<Mutex<ProgressState>>,
+}
+
+impl StartupProgress {
+    /// Start timing the boot.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ProgressState {
+                started: Instant::now(),
+                steps: Vec::new(),
+                app: None,
+            })),
+        }
+    }
+
+    /// Emit further steps to the frontend.
+    pub fn attach(&self, app: AppHandle) {
+        self.state.lock().unwrap().app = Some(app);
+    }
+
+    /// Mark a stage as started.
+    pub fn start(&self, stage: StartupStage, message: Option<String>) {
+        self.record(stage, StepStatus::Started, message);
+    }
+
+    /// Mark a stage as done.
+    pub fn finish(&self, stage: StartupStage, message: Option<String>) {
+        self.record(stage, StepStatus::Done, message);
+    }
+
+    /// Mark a stage as failed.
+    pub fn fail(&self, stage: StartupStage, message: String) {
+        self.record(stage, StepStatus::Failed, Some(message));
+    }
+
+    /// Current status.
+    pub fn status(&self) -> StartupStatus {
+        Self::snapshot(&self.state.lock().unwrap().steps)
+    }
+
+    /// Record a step and emit it if a handle is attached.
+    fn record(&self, stage: StartupStage, status: StepStatus, message: Option<String>) {
+        let mut state = self.state.lock().unwrap();
+        let step = StartupStep {
+            stage,
+            status,
+            message,
+            elapsed_ms: u64::try_from(state.started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        };
+        log::info!(
+            "Startup: {stage:?} {status:?}{} ({} ms)",
+            step.message
+                .as_deref()
+                .map(|m| format!(": {m}"))
+                .unwrap_or_default(),
+            step.elapsed_ms
+        );
+
+        state.steps.push(step.clone());
+        if let Some(ref app) = state.app {
+            let event = ProgressEvent {
+                step,
+                status: Self::snapshot(&state.steps),
+            };
+            if let Err(e) = app.emit_all(PROGRESS_EVENT, event) {
+                log::warn!("Failed to emit {PROGRESS_EVENT}: {e}");
+            }
+        }
+    }
+
+    /// Build a status from the recorded steps.
+    fn snapshot(steps: &[StartupStep]) -> StartupStatus {
+        let latest: Vec<StartupStep> = StartupStage::ALL
+            .iter()
+            .filter_map(|stage| steps.iter().rev().find(|s| s.stage == *stage).cloned())
+            .collect();
+        let last = StartupStage::ALL[StartupStage::ALL.len() - 1];
+
+        StartupStatus {
+            complete: latest
+                .iter()
+                .any(|s| s.stage == last && s.status == StepStatus::Done),
+            failed: latest.iter().any(|s| s.status == StepStatus::Failed),
+            steps: latest,
+            total_stages: StartupStage::ALL.len(),
+        }
+    }
+}
+
+impl Default for StartupProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_keeps_latest_step_per_stage() {
+        let progress = StartupProgress::new();
+        progress.start(StartupStage::ResolvingProjectRoot, None);
+        progress.finish(
+            StartupStage::ResolvingProjectRoot,
+            Some("/work".to_string()),
+        );
+        progress.start(StartupStage::LocatingPython, None);
+
+        let status = progress.status();
+        assert_eq!(status.steps.len(), 2);
+        assert_eq!(status.steps[0].status, StepStatus::Done);
+        assert!(!status.complete);
+
+        progress.fail(StartupStage::LocatingPython, "python not found".to_string());
+        progress.finish(StartupStage::PluginScanComplete, None);
+        let status = progress.status();
+        assert!(status.failed);
+        assert!(status.complete);
+    }
+
+    #[test]
+    fn test_find_executable() {
+        let exe = std::env::current_exe().unwrap();
+        assert_eq!(find_executable(exe.to_str().unwrap()), Some(exe));
+        assert!(find_executable("af-definitely-not-a-real-program").is_none());
+    }
+}
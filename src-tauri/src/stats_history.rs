@@ -0,0 +1,253 @@
+//! src-tauri/src/stats_history.rs
+//! ==============================
+//! Time series of plugin host statistics for the current session.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! `ipc_status` only reports totals since launch. This module samples the
+//! default plugin host's `ManagerStats` every `SAMPLE_INTERVAL` and keeps
+//! the last `MAX_SAMPLES` samples in memory (one hour), so the UI can chart
+//! request rate, error rate, and latency over the session.
+//!
+//! Rates and latency are computed over the interval since the previous
+//! sample: `request_rate` is requests per second, `error_rate` the share
+//! of those requests that failed, and `avg_latency_ms` the mean response
+//! time of the requests answered in the interval.
+//!
+//! Usage:
+//!     ```rust
+//!     let history = StatsHistory::new();
+//!     tauri::async_runtime::spawn(history.clone().watch(state.clone()));
+//!     let samples = history.since(Some(&last_seen))?;
+//!     ```
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::ipc::manager::{IpcManagerState, ManagerStats};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Time between samples
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Samples kept (one hour at `SAMPLE_INTERVAL`)
+pub const MAX_SAMPLES: usize = 720;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Statistics of the plugin host at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsSample {
+    /// When the sample was taken
+    pub at: DateTime<Utc>,
+    /// Requests sent since launch
+    pub total_requests: u64,
+    /// Requests failed since launch
+    pub failed_requests: u64,
+    /// Requests waiting for a response
+    pub pending_requests: usize,
+    /// Requests per second since the previous sample
+    pub request_rate: f64,
+    /// Share of the requests since the previous sample that failed (0-1)
+    pub error_rate: f64,
+    /// Mean response time of the requests answered since the previous
+    /// sample (None if none were answered)
+    pub avg_latency_ms: Option<f64>,
+    /// Resident memory of the host process in bytes
+    pub rss_bytes: Option<u64>,
+    /// CPU usage of the host process in percent
+    pub cpu_percent: Option<f32>,
+}
+
+/// The request counters a sample is computed from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Counters {
+    total: u64,
+    failed: u64,
+    answered: u64,
+    latency_ms: u64,
+}
+
+impl From<&ManagerStats> for Counters {
+    fn from(stats: &ManagerStats) -> Self {
+        Self {
+            total: stats.total_requests,
+            failed: stats.failed_requests,
+            answered: stats.successful_requests,
+            latency_ms: stats.total_latency_ms,
+        }
+    }
+}
+
+/// Samples and the counters of the latest one.
+#[derive(Default)]
+struct HistoryState {
+    samples: VecDeque<StatsSample>,
+    previous: Option<Counters>,
+}
+
+// ============================================
+// STATS HISTORY
+// ============================================
+
+/// Bounded in-memory series of plugin host statistics.
+#[derive(Clone, Default)]
+pub struct StatsHistory {
+    state: Arc<Mutex<HistoryState>>,
+}
+
+impl StatsHistory {
+    /// Create an empty history.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sample `state` every `SAMPLE_INTERVAL` for the rest of the session.
+    pub async fn watch(self, state: IpcManagerState) {
+        let mut interval = tokio::time::interval(SAMPLE_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            self.record(&state.stats().await, SAMPLE_INTERVAL);
+        }
+    }
+
+    /// Add a sample of `stats`, taken `elapsed` after the previous one.
+    pub fn record(&self, stats: &ManagerStats, elapsed: Duration) {
+        let usage = stats.resource_usage.as_ref();
+        self.push(
+            Counters::from(stats),
+            stats.pending_requests,
+            usage.map(|usage| (usage.rss_bytes, usage.cpu_percent)),
+            elapsed,
+        );
+    }
+
+    /// Samples taken after `since` (RFC 3339), or all samples, oldest first.
+    pub fn since(&self, since: Option<&str>) -> Result<Vec<StatsSample>, String> {
+        let since = since
+            .map(|since| {
+                DateTime::parse_from_rfc3339(since)
+                    .map(|since| since.with_timezone(&Utc))
+                    .map_err(|e| format!("Invalid timestamp {since:?}: {e}"))
+            })
+            .transpose()?;
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .samples
+            .iter()
+            .filter(|sample| since.map_or(true, |since| sample.at > since))
+            .cloned()
+            .collect())
+    }
+
+    /// Compute a sample from the counters and append it.
+    #[allow(clippy::cast_precision_loss)]
+    fn push(
+        &self,
+        counters: Counters,
+        pending_requests: usize,
+        usage: Option<(u64, f32)>,
+        elapsed: Duration,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        // The first sample covers the whole session so far
+        let previous = state.previous.unwrap_or_default();
+        let requests = counters.total.saturating_sub(previous.total);
+        let failed = counters.failed.saturating_sub(previous.failed);
+        let answered = counters.answered.saturating_sub(previous.answered);
+        let latency_ms = counters.latency_ms.saturating_sub(previous.latency_ms);
+        let secs = elapsed.as_secs_f64();
+
+        let sample = StatsSample {
+            at: Utc::now(),
+            total_requests: counters.total,
+            failed_requests: counters.failed,
+            pending_requests,
+            request_rate: if secs > 0.0 {
+                requests as f64 / secs
+            } else {
+                0.0
+            },
+            error_rate: if requests > 0 {
+                failed as f64 / requests as f64
+            } else {
+                0.0
+            },
+            avg_latency_ms: (answered > 0).then(|| latency_ms as f64 / answered as f64),
+            rss_bytes: usage.map(|(rss_bytes, _)| rss_bytes),
+            cpu_percent: usage.map(|(_, cpu_percent)| cpu_percent),
+        };
+
+        state.previous = Some(counters);
+        if state.samples.len() == MAX_SAMPLES {
+            state.samples.pop_front();
+        }
+        state.samples.push_back(sample);
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counters(total: u64, failed: u64, answered: u64, latency_ms: u64) -> Counters {
+        Counters {
+            total,
+            failed,
+            answered,
+            latency_ms,
+        }
+    }
+
+    #[test]
+    fn test_samples_use_deltas() {
+        let history = StatsHistory::new();
+        let interval = Duration::from_secs(5);
+        history.push(counters(10, 0, 10, 500), 0, None, interval);
+        history.push(counters(20, 5, 18, 1300), 2, Some((1024, 12.5)), interval);
+        history.push(counters(20, 5, 18, 1300), 0, None, interval);
+
+        let samples = history.since(None).unwrap();
+        assert_eq!(samples.len(), 3);
+        assert!((samples[1].request_rate - 2.0).abs() < f64::EPSILON);
+        assert!((samples[1].error_rate - 0.5).abs() < f64::EPSILON);
+        assert_eq!(samples[1].avg_latency_ms, Some(100.0));
+        assert_eq!(samples[1].rss_bytes, Some(1024));
+        assert!(samples[2].request_rate.abs() < f64::EPSILON);
+        assert_eq!(samples[2].avg_latency_ms, None);
+
+        let since = samples[1].at.to_rfc3339();
+        assert!(history
+            .since(Some(&since))
+            .unwrap()
+            .iter()
+            .all(|sample| sample.at > samples[1].at));
+        assert!(history.since(Some("yesterday")).is_err());
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let history = StatsHistory::new();
+        for total in 0..u64::try_from(MAX_SAMPLES + 10).unwrap() {
+            history.push(counters(total, 0, total, 0), 0, None, SAMPLE_INTERVAL);
+        }
+        let samples = history.since(None).unwrap();
+        assert_eq!(samples.len(), MAX_SAMPLES);
+        assert_eq!(samples[0].total_requests, 10);
+    }
+}
@@ -0,0 +1,579 @@
+//! src-tauri/src/storage.rs
+//! ========================
+//! Embedded SQLite persistence layer.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A single SQLite database (`app_factory.db`) in the app data directory
+//! backs structured persistence (generated app definitions, chat history,
+//! job records, health history). The schema is versioned with
+//! `PRAGMA user_version` and upgraded by the ordered `MIGRATIONS` list on
+//! open.
+//!
+//! The generic `documents` table stores JSON documents by collection and
+//! id for features that don't need their own tables. The `kv` table is a
+//! flat key-value store for small state (frontend preferences, generated
+//! app state) whose entries can expire; expired entries are never returned
+//! and are purged on the next write.
+//!
+//! Usage:
+//!     ```rust
+//!     let storage = Storage::open(Some(data_dir.join(DATABASE_FILE)))?;
+//!     storage.put_document("jobs", &job.id, &json!(job))?;
+//!     let page = storage.list_documents("jobs", 50, 0)?;
+//!     storage.kv_set("app:todo:filter", &json!("done"), Some(3600))?;
+//!     let entries = storage.kv_list_prefix("app:todo:", 100)?;
+//!     ```
+
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Database file name inside the app data directory
+pub const DATABASE_FILE: &str = "app_factory.db";
+
+/// Schema migrations, applied in order. Index + 1 is the schema version.
+///
+/// Never edit a released migration; append a new one instead.
+const MIGRATIONS: &[&str] = &[
+    // 1: generic JSON documents
+    "CREATE TABLE documents (
+        collection TEXT NOT NULL,
+        id TEXT NOT NULL,
+        data TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        PRIMARY KEY (collection, id)
+    );
+    CREATE INDEX idx_documents_updated ON documents (collection, updated_at);",
+    // 2: chat history with full-text search (history.rs)
+    "CREATE TABLE history_messages (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        session_id TEXT NOT NULL,
+        role TEXT NOT NULL,
+        content TEXT NOT NULL,
+        metadata TEXT,
+        created_at TEXT NOT NULL
+    );
+    CREATE INDEX idx_history_session ON history_messages (session_id, id);
+    CREATE VIRTUAL TABLE history_fts USING fts5(
+        content, content = 'history_messages', content_rowid = 'id'
+    );
+    CREATE TRIGGER history_fts_insert AFTER INSERT ON history_messages BEGIN
+        INSERT INTO history_fts (rowid, content) VALUES (new.id, new.content);
+    END;
+    CREATE TRIGGER history_fts_delete AFTER DELETE ON history_messages BEGIN
+        INSERT INTO history_fts (history_fts, rowid, content)
+        VALUES ('delete', old.id, old.content);
+    END;",
+    // 3: generated app definitions with version history (apps.rs)
+    "CREATE TABLE apps (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        current_version INTEGER NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    CREATE TABLE app_versions (
+        app_id TEXT NOT NULL REFERENCES apps (id) ON DELETE CASCADE,
+        version INTEGER NOT NULL,
+        definition TEXT NOT NULL,
+        message TEXT,
+        created_at TEXT NOT NULL,
+        PRIMARY KEY (app_id, version)
+    );",
+    // 4: per-key API usage accounting (key_usage.rs)
+    "CREATE TABLE api_key_usage (
+        key_id TEXT PRIMARY KEY,
+        service TEXT NOT NULL,
+        request_count INTEGER NOT NULL,
+        estimated_tokens INTEGER NOT NULL,
+        last_used_at TEXT NOT NULL
+    );
+    CREATE INDEX idx_api_key_usage_service ON api_key_usage (service);",
+    // 5: compacted plugin host health history (health_history.rs)
+    "CREATE TABLE health_events (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        kind TEXT NOT NULL,
+        detail TEXT NOT NULL,
+        count INTEGER NOT NULL,
+        started_at TEXT NOT NULL,
+        ended_at TEXT NOT NULL
+    );
+    CREATE INDEX idx_health_events_ended ON health_events (ended_at);",
+    // 6: key-value store with optional expiry (Unix milliseconds)
+    "CREATE TABLE kv (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL,
+        expires_at INTEGER,
+        updated_at TEXT NOT NULL
+    );
+    CREATE INDEX idx_kv_expires ON kv (expires_at);",
+];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// A stored JSON document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Document {
+    /// Collection name
+    pub collection: String,
+    /// Document id (unique within the collection)
+    pub id: String,
+    /// Document body
+    pub data: Value,
+    /// Creation timestamp (RFC 3339)
+    pub created_at: String,
+    /// Last update timestamp (RFC 3339)
+    pub updated_at: String,
+}
+
+/// A key-value entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KvEntry {
+    /// Key
+    pub key: String,
+    /// Value
+    pub value: Value,
+    /// Expiry timestamp (RFC 3339), None if the entry doesn't expire
+    pub expires_at: Option<String>,
+    /// Last update timestamp (RFC 3339)
+    pub updated_at: String,
+}
+
+/// A page of results.
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    /// Items in this page
+    pub items: Vec<T>,
+    /// Total number of matching items
+    pub total: u64,
+    /// Offset of the first item
+    pub offset: u64,
+}
+
+// ============================================
+// STORAGE
+// ============================================
+
+/// SQLite-backed storage.
+///
+/// Clones share one connection, so writes from every command are serialized.
+#[derive(Debug, Clone)]
+pub struct Storage {
+    /// Database connection
+    conn: Arc<Mutex<Connection>>,
+}
+
+impl Storage {
+    /// Open (or create) the database and apply pending migrations.
+    ///
+    /// `None` opens an in-memory database.
+    pub fn open(path: Option<PathBuf>) -> Result<Self, String> {
+        let conn = match path {
+            Some(ref path) => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+                }
+                Connection::open(path)
+            }
+            None => Connection::open_in_memory(),
+        }
+        .map_err(|e| format!("Failed to open database: {e}"))?;
+
+        conn.execute_batch("PRAGMA journal_mode = WAL; PRAGMA foreign_keys = ON;")
+            .map_err(|e| format!("Failed to configure database: {e}"))?;
+
+        let storage = Self {
+            conn: Arc::new(Mutex::new(conn)),
+        };
+        storage.migrate()?;
+
+        log::info!(
+            "Storage opened: {} (schema v{})",
+            path.map_or_else(|| ":memory:".to_string(), |p| p.display().to_string()),
+            storage.schema_version()?
+        );
+        Ok(storage)
+    }
+
+    /// Lock the connection for a sequence of statements.
+    pub fn conn(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap()
+    }
+
+    /// Write the WAL back into the database file (used on shutdown).
+    pub fn checkpoint(&self) -> Result<(), String> {
+        self.conn()
+            .execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")
+            .map_err(|e| format!("Failed to checkpoint database: {e}"))
+    }
+
+    /// Get the current schema version.
+    pub fn schema_version(&self) -> Result<u32, String> {
+        self.conn()
+            .query_row("PRAGMA user_version", [], |row| row.get(0))
+            .map_err(|e| format!("Failed to read schema version: {e}"))
+    }
+
+    /// Apply migrations newer than the current schema version.
+    fn migrate(&self) -> Result<(), String> {
+        let current = self.schema_version()? as usize;
+        let mut conn = self.conn();
+
+        for (index, sql) in MIGRATIONS.iter().enumerate().skip(current) {
+            let version = index + 1;
+            let tx = conn
+                .transaction()
+                .map_err(|e| format!("Failed to start migration {version}: {e}"))?;
+            tx.execute_batch(sql)
+                .map_err(|e| format!("Migration {version} failed: {e}"))?;
+            tx.pragma_update(None, "user_version", version)
+                .map_err(|e| format!("Failed to record migration {version}: {e}"))?;
+            tx.commit()
+                .map_err(|e| format!("Failed to commit migration {version}: {e}"))?;
+            log::info!("Applied storage migration {version}");
+        }
+
+        Ok(())
+    }
+
+    // ============================================
+    // DOCUMENTS
+    // ============================================
+
+    /// Insert or replace a document.
+    pub fn put_document(
+        &self,
+        collection: &str,
+        id: &str,
+        data: &Value,
+    ) -> Result<Document, String> {
+        let now = Utc::now().to_rfc3339();
+        let body = serde_json::to_string(data).map_err(|e| e.to_string())?;
+
+        self.conn()
+            .execute(
+                "INSERT INTO documents (collection, id, data, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?4)
+                 ON CONFLICT (collection, id) DO UPDATE SET data = ?3, updated_at = ?4",
+                params![collection, id, body, now],
+            )
+            .map_err(|e| format!("Failed to store document: {e}"))?;
+
+        self.get_document(collection, id)?
+            .ok_or_else(|| format!("Document {collection}/{id} vanished after write"))
+    }
+
+    /// Get a document.
+    pub fn get_document(&self, collection: &str, id: &str) -> Result<Option<Document>, String> {
+        self.conn()
+            .query_row(
+                "SELECT collection, id, data, created_at, updated_at
+                 FROM documents WHERE collection = ?1 AND id = ?2",
+                params![collection, id],
+                row_to_document,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read document: {e}"))
+    }
+
+    /// List documents in a collection (most recently updated first).
+    pub fn list_documents(
+        &self,
+        collection: &str,
+        limit: u64,
+        offset: u64,
+    ) -> Result<Page<Document>, String> {
+        let conn = self.conn();
+
+        let total: u64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM documents WHERE collection = ?1",
+                params![collection],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Failed to count documents: {e}"))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT collection, id, data, created_at, updated_at
+                 FROM documents WHERE collection = ?1
+                 ORDER BY updated_at DESC LIMIT ?2 OFFSET ?3",
+            )
+            .map_err(|e| e.to_string())?;
+
+        let items = stmt
+            .query_map(params![collection, limit, offset], row_to_document)
+            .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+            .map_err(|e| format!("Failed to list documents: {e}"))?;
+
+        Ok(Page {
+            items,
+            total,
+            offset,
+        })
+    }
+
+    /// Delete a document.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a document was deleted.
+    pub fn delete_document(&self, collection: &str, id: &str) -> Result<bool, String> {
+        self.conn()
+            .execute(
+                "DELETE FROM documents WHERE collection = ?1 AND id = ?2",
+                params![collection, id],
+            )
+            .map(|n| n > 0)
+            .map_err(|e| format!("Failed to delete document: {e}"))
+    }
+
+    // ============================================
+    // KEY-VALUE
+    // ============================================
+
+    /// Set a key, replacing its value and expiry.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Non-empty key
+    /// * `value` - JSON value
+    /// * `ttl_secs` - Seconds until the entry expires (None keeps it)
+    pub fn kv_set(
+        &self,
+        key: &str,
+        value: &Value,
+        ttl_secs: Option<u64>,
+    ) -> Result<KvEntry, String> {
+        if key.is_empty() {
+            return Err("Key must not be empty".to_string());
+        }
+        let now = Utc::now();
+        let expires_at = ttl_secs
+            .map(|ttl| {
+                i64::try_from(ttl.saturating_mul(1000))
+                    .map(|ttl_ms| now.timestamp_millis().saturating_add(ttl_ms))
+                    .map_err(|_| format!("TTL too large: {ttl} seconds"))
+            })
+            .transpose()?;
+        let body = serde_json::to_string(value).map_err(|e| e.to_string())?;
+
+        let conn = self.conn();
+        conn.execute(
+            "DELETE FROM kv WHERE expires_at <= ?1",
+            params![now.timestamp_millis()],
+        )
+        .map_err(|e| format!("Failed to purge expired keys: {e}"))?;
+        conn.execute(
+            "INSERT INTO kv (key, value, expires_at, updated_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (key) DO UPDATE SET value = ?2, expires_at = ?3, updated_at = ?4",
+            params![key, body, expires_at, now.to_rfc3339()],
+        )
+        .map_err(|e| format!("Failed to store key: {e}"))?;
+
+        Ok(KvEntry {
+            key: key.to_string(),
+            value: value.clone(),
+            expires_at: expires_at.and_then(millis_to_rfc3339),
+            updated_at: now.to_rfc3339(),
+        })
+    }
+
+    /// Get a key that hasn't expired.
+    pub fn kv_get(&self, key: &str) -> Result<Option<KvEntry>, String> {
+        self.conn()
+            .query_row(
+                "SELECT key, value, expires_at, updated_at FROM kv
+                 WHERE key = ?1 AND (expires_at IS NULL OR expires_at > ?2)",
+                params![key, Utc::now().timestamp_millis()],
+                row_to_kv_entry,
+            )
+            .optional()
+            .map_err(|e| format!("Failed to read key: {e}"))
+    }
+
+    /// Delete a key.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key existed (expired or not).
+    pub fn kv_delete(&self, key: &str) -> Result<bool, String> {
+        self.conn()
+            .execute("DELETE FROM kv WHERE key = ?1", params![key])
+            .map(|n| n > 0)
+            .map_err(|e| format!("Failed to delete key: {e}"))
+    }
+
+    /// List keys that start with `prefix` and haven't expired, in key order.
+    pub fn kv_list_prefix(&self, prefix: &str, limit: u64) -> Result<Vec<KvEntry>, String> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare(
+                "SELECT key, value, expires_at, updated_at FROM kv
+                 WHERE substr(key, 1, length(?1)) = ?1
+                   AND (expires_at IS NULL OR expires_at > ?2)
+                 ORDER BY key LIMIT ?3",
+            )
+            .map_err(|e| e.to_string())?;
+
+        stmt.query_map(
+            params![prefix, Utc::now().timestamp_millis(), limit],
+            row_to_kv_entry,
+        )
+        .and_then(|rows| rows.collect::<rusqlite::Result<Vec<_>>>())
+        .map_err(|e| format!("Failed to list keys: {e}"))
+    }
+}
+
+/// Format Unix milliseconds as RFC 3339.
+fn millis_to_rfc3339(millis: i64) -> Option<String> {
+    chrono::DateTime::from_timestamp_millis(millis).map(|time| time.to_rfc3339())
+}
+
+/// Map a `kv` row to a `KvEntry`.
+fn row_to_kv_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<KvEntry> {
+    let body: String = row.get(1)?;
+    let expires_at: Option<i64> = row.get(2)?;
+    Ok(KvEntry {
+        key: row.get(0)?,
+        value: serde_json::from_str(&body).unwrap_or(Value::Null),
+        expires_at: expires_at.and_then(millis_to_rfc3339),
+        updated_at: row.get(3)?,
+    })
+}
+
+/// Map a `documents` row to a `Document`.
+fn row_to_document(row: &rusqlite::Row<'_>) -> rusqlite::Result<Document> {
+    let body: String = row.get(2)?;
+    Ok(Document {
+        collection: row.get(0)?,
+        id: row.get(1)?,
+        data: serde_json::from_str(&body).unwrap_or(Value::Null),
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrations_applied() {
+        let storage = Storage::open(None).unwrap();
+        assert_eq!(storage.schema_version().unwrap() as usize, MIGRATIONS.len());
+
+        // Re-running is a no-op
+        storage.migrate().unwrap();
+        assert_eq!(storage.schema_version().unwrap() as usize, MIGRATIONS.len());
+    }
+
+    #[test]
+    fn test_document_crud() {
+        let storage = Storage::open(None).unwrap();
+
+        let doc = storage
+            .put_document("jobs", "a", &json!({"status": "running"}))
+            .unwrap();
+        assert_eq!(doc.data["status"], "running");
+
+        let updated = storage
+            .put_document("jobs", "a", &json!({"status": "done"}))
+            .unwrap();
+        assert_eq!(updated.created_at, doc.created_at);
+        assert_eq!(updated.data["status"], "done");
+
+        assert!(storage.delete_document("jobs", "a").unwrap());
+        assert!(storage.get_document("jobs", "a").unwrap().is_none());
+        assert!(!storage.delete_document("jobs", "a").unwrap());
+    }
+
+    #[test]
+    fn test_list_documents_paginates() {
+        let storage = Storage::open(None).unwrap();
+        for i in 0..5 {
+            storage
+                .put_document("apps", &format!("app{i}"), &json!({ "i": i }))
+                .unwrap();
+        }
+        storage.put_document("other", "x", &json!({})).unwrap();
+
+        let page = storage.list_documents("apps", 2, 1).unwrap();
+        assert_eq!(page.total, 5);
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.offset, 1);
+    }
+
+    #[test]
+    fn test_kv_store() {
+        let storage = Storage::open(None).unwrap();
+
+        storage
+            .kv_set("app:todo:filter", &json!("done"), None)
+            .unwrap();
+        storage
+            .kv_set("app:todo:sort", &json!({"by": "date"}), Some(3600))
+            .unwrap();
+        storage
+            .kv_set("app:notes:draft", &json!("..."), None)
+            .unwrap();
+        assert!(storage.kv_set("", &json!(1), None).is_err());
+
+        let entry = storage.kv_get("app:todo:sort").unwrap().unwrap();
+        assert_eq!(entry.value["by"], "date");
+        assert!(entry.expires_at.is_some());
+
+        let keys: Vec<String> = storage
+            .kv_list_prefix("app:todo:", 10)
+            .unwrap()
+            .into_iter()
+            .map(|entry| entry.key)
+            .collect();
+        assert_eq!(keys, vec!["app:todo:filter", "app:todo:sort"]);
+
+        // Expired entries are hidden, then purged by the next write
+        storage
+            .kv_set("app:todo:filter", &json!("all"), Some(0))
+            .unwrap();
+        assert!(storage.kv_get("app:todo:filter").unwrap().is_none());
+        assert_eq!(storage.kv_list_prefix("app:todo:", 10).unwrap().len(), 1);
+        storage.kv_set("other", &json!(1), None).unwrap();
+        assert!(!storage.kv_delete("app:todo:filter").unwrap());
+
+        assert!(storage.kv_delete("app:todo:sort").unwrap());
+        assert!(storage.kv_get("app:todo:sort").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_persisted_on_disk() {
+        let path = std::env::temp_dir()
+            .join(format!("af_storage_{}", uuid::Uuid::new_v4()))
+            .join(DATABASE_FILE);
+
+        Storage::open(Some(path.clone()))
+            .unwrap()
+            .put_document("c", "1", &json!(1))
+            .unwrap();
+
+        let reopened = Storage::open(Some(path)).unwrap();
+        let doc = reopened.get_document("c", "1").unwrap().unwrap();
+        assert_eq!(doc.data, json!(1));
+    }
+}
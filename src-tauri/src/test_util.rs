@@ -0,0 +1,26 @@
+//! src-tauri/src/test_util.rs
+//! ==========================
+//! Helpers shared by the unit tests.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Usage:
+//!     ```rust
+//!     use crate::test_util::temp_dir;
+//!
+//!     let dir = temp_dir("settings");
+//!     let store = SettingsStore::load(Some(dir));
+//!     ```
+
+use std::fs;
+use std::path::PathBuf;
+
+/// Create an empty directory under the system temp directory.
+///
+/// The name is `af_<label>_<uuid>`, so every call gets its own directory
+/// and leftovers show which test made them.
+pub fn temp_dir(label: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("af_{label}_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,84 @@
+//! src-tauri/src/tracing_export.rs
+//! ===============================
+//! Optional export of `tracing` spans to an OpenTelemetry collector.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! The IPC manager, the compiler, and the plugin call commands record spans:
+//!
+//!     ipc.start           starting the plugin host, handshake included
+//!     ipc.spawn           spawning a host process
+//!     ipc.call            one JSON-RPC request, until its response
+//!     ipc.respawn         restarting the plugin host (cold or warm)
+//!     compile             compiling one TSX source through the cache
+//!     compile.project     bundling a multi-file project
+//!     command.ipc_call    the `ipc_call` command (also `command.plugin_call`)
+//!
+//! Without a subscriber the spans cost next to nothing. With
+//! `tracing.enabled`, `init` installs one that exports them in batches
+//! over OTLP (gRPC) to `tracing.otlp_endpoint`; Jaeger accepts OTLP
+//! directly on port 4317. Log records keep going through `log` (see
+//! logging.rs).
+//!
+//! Usage:
+//!     ```rust
+//!     if settings.tracing.enabled {
+//!         tauri::async_runtime::block_on(async { tracing_export::init(endpoint) })?;
+//!     }
+//!     tracing_export::shutdown();
+//!     ```
+
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::{runtime, trace, Resource};
+use tracing_subscriber::layer::SubscriberExt;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Default collector endpoint (OTLP over gRPC)
+pub const DEFAULT_OTLP_ENDPOINT: &str = "http://localhost:4317";
+
+/// Service name spans are reported under
+const SERVICE_NAME: &str = "app-factory";
+
+// ============================================
+// EXPORTER
+// ============================================
+
+/// Export spans to the OTLP collector at `endpoint`.
+///
+/// Must run within the Tokio runtime, which the batch exporter uses.
+/// Fails if the exporter can't be built or a subscriber is already set.
+pub fn init(endpoint: &str) -> Result<(), String> {
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(trace::config().with_resource(Resource::new([
+            KeyValue::new("service.name", SERVICE_NAME),
+            KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+        ])))
+        .install_batch(runtime::Tokio)
+        .map_err(|e| format!("Failed to start span exporter: {e}"))?;
+
+    let subscriber =
+        tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| format!("Failed to install span exporter: {e}"))?;
+
+    log::info!("Exporting spans to {endpoint}");
+    Ok(())
+}
+
+/// Export the spans still queued and stop the exporter.
+///
+/// Blocks until the queue is flushed; does nothing if `init` was not
+/// called.
+pub fn shutdown() {
+    opentelemetry::global::shutdown_tracer_provider();
+}
@@ -0,0 +1,274 @@
+//! src-tauri/src/typecheck.rs
+//! ===========================
+//! TypeScript type checking for generated TSX via a `tsc` subprocess.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! SWC only strips types, so a component that references an undefined
+//! variable or passes a string where a number is expected compiles fine
+//! and only fails in the preview. When `CompileOptions::typecheck` is set,
+//! the source is written to a scratch directory next to a permissive
+//! tsconfig and a shim declaration file, and `tsc --noEmit` reports the
+//! type errors.
+//!
+//! The shim types every imported module as `any` and provides a global
+//! `React`, so checking needs no `node_modules` and catches errors in the
+//! component's own code rather than in its use of library APIs.
+//!
+//! `tsc` is taken from the project's `node_modules/.bin` when present,
+//! otherwise from `PATH`.
+//!
+//! Usage:
+//!     ```rust
+//!     let type_errors = typecheck::typecheck(source)?;
+//!     for error in &type_errors {
+//!         println!("{}:{} {}", error.line, error.column, error.message);
+//!     }
+//!     ```
+
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::commands::compiler::{code_frame, Diagnostic};
+use crate::project;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Maximum time a `tsc` run may take
+const TYPECHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Name of the checked source file in the scratch directory
+const SOURCE_FILE: &str = "component.tsx";
+
+/// Compiler options for checking a single component
+const TSCONFIG: &str = r#"{
+  "compilerOptions": {
+    "target": "ES2020",
+    "lib": ["ES2020", "DOM", "DOM.Iterable"],
+    "module": "ESNext",
+    "moduleResolution": "node",
+    "jsx": "react",
+    "strict": false,
+    "noEmit": true,
+    "skipLibCheck": true,
+    "esModuleInterop": true,
+    "types": []
+  },
+  "files": ["component.tsx", "shims.d.ts"]
+}
+"#;
+
+/// Ambient declarations standing in for React and other libraries
+const SHIMS: &str = r"declare module '*';
+
+declare const React: any;
+declare namespace React {
+    type FC<P = {}> = any;
+    type FunctionComponent<P = {}> = any;
+    type ReactNode = any;
+    type ReactElement = any;
+    type CSSProperties = any;
+    type PropsWithChildren<P = {}> = P & { children?: any };
+    type Dispatch<A> = (value: A) => void;
+    type SetStateAction<S> = S | ((prev: S) => S);
+    type ChangeEvent<T = any> = any;
+    type FormEvent<T = any> = any;
+    type MouseEvent<T = any> = any;
+    type KeyboardEvent<T = any> = any;
+    type RefObject<T> = { readonly current: T | null };
+    type HTMLAttributes<T = any> = any;
+}
+
+declare namespace JSX {
+    interface Element {}
+    interface IntrinsicElements {
+        [name: string]: any;
+    }
+}
+";
+
+// ============================================
+// TYPE CHECKING
+// ============================================
+
+/// Type-check a TSX source.
+///
+/// # Returns
+///
+/// The type errors found (empty if the source checks cleanly), or an
+/// error if `tsc` is unavailable or could not be run.
+pub fn typecheck(code: &str) -> Result<Vec<Diagnostic>, String> {
+    let tsc = find_tsc().ok_or_else(|| "TypeScript compiler (tsc) not found".to_string())?;
+
+    let dir = std::env::temp_dir().join(format!("af_typecheck_{}", uuid::Uuid::new_v4()));
+    let result = run_tsc(&tsc, &dir, code);
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+/// Write the scratch project to `dir` and run `tsc` on it.
+fn run_tsc(tsc: &Path, dir: &Path, code: &str) -> Result<Vec<Diagnostic>, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {dir:?}: {e}"))?;
+    for (name, content) in [
+        (SOURCE_FILE, code),
+        ("tsconfig.json", TSCONFIG),
+        ("shims.d.ts", SHIMS),
+    ] {
+        fs::write(dir.join(name), content).map_err(|e| format!("Failed to write {name}: {e}"))?;
+    }
+
+    // Output goes to a file so a chatty run can't fill the pipe and stall
+    let log_path = dir.join("tsc.log");
+    let log_file = File::create(&log_path).map_err(|e| format!("Failed to create tsc log: {e}"))?;
+
+    let mut cmd = Command::new(tsc);
+    cmd.args(["--project", "tsconfig.json", "--pretty", "false"])
+        .current_dir(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::from(log_file))
+        .stderr(Stdio::null());
+
+    // Windows-specific: Prevent console window from appearing
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd.spawn().map_err(|e| format!("Failed to run tsc: {e}"))?;
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| format!("Failed to wait for tsc: {e}"))? {
+            break status;
+        }
+        if started.elapsed() > TYPECHECK_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("tsc timed out after {}s", TYPECHECK_TIMEOUT.as_secs()));
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    let output = fs::read_to_string(&log_path).unwrap_or_default();
+    let diagnostics = parse_tsc_output(&output, code);
+    if diagnostics.is_empty() && !status.success() {
+        return Err(format!("tsc failed ({status}): {}", output.trim()));
+    }
+
+    log::debug!("Type check found {} error(s)", diagnostics.len());
+    Ok(diagnostics)
+}
+
+/// Locate `tsc`: the project's `node_modules/.bin`, then `PATH`.
+fn find_tsc() -> Option<PathBuf> {
+    let name = if cfg!(target_os = "windows") { "tsc.cmd" } else { "tsc" };
+
+    let local = project::discover_project_root()
+        .join("node_modules")
+        .join(".bin")
+        .join(name);
+    if local.is_file() {
+        return Some(local);
+    }
+
+    std::env::var_os("PATH").and_then(|path| {
+        std::env::split_paths(&path)
+            .map(|dir| dir.join(name))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Parse `tsc --pretty false` output into diagnostics for the source file.
+///
+/// Lines look like `component.tsx(3,7): error TS2322: Type ...`; indented
+/// continuation lines are appended to the previous message. Errors in
+/// other files (the shims) are ignored.
+fn parse_tsc_output(output: &str, source: &str) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut in_source = false;
+
+    for line in output.lines() {
+        if line.starts_with(char::is_whitespace) {
+            if let (true, Some(last)) = (in_source, diagnostics.last_mut()) {
+                last.message.push('\n');
+                last.message.push_str(line.trim());
+            }
+            continue;
+        }
+
+        in_source = false;
+        let Some(rest) = line
+            .find(&format!("{SOURCE_FILE}("))
+            .map(|at| &line[at + SOURCE_FILE.len() + 1..])
+        else {
+            continue;
+        };
+        let Some((position, message)) = rest.split_once("): ") else {
+            continue;
+        };
+        let Some((line_number, column)) = position.split_once(',') else {
+            continue;
+        };
+        let (Ok(line_number), Ok(column)) = (line_number.parse(), column.parse()) else {
+            continue;
+        };
+
+        in_source = true;
+        diagnostics.push(Diagnostic {
+            message: message.trim_start_matches("error ").to_string(),
+            line: line_number,
+            column,
+            end_line: line_number,
+            end_column: column + 1,
+            snippet: code_frame(source, line_number, column, line_number, column + 1),
+        });
+    }
+
+    diagnostics
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tsc_output() {
+        let source = "const a: number = 1;\nconst b: number = 'x';\n";
+        let output = "component.tsx(2,7): error TS2322: Type 'string' is not assignable to type 'number'.\n\
+                      /tmp/x/shims.d.ts(1,1): error TS1234: Ignored.\n\
+                      component.tsx(2,19): error TS2345: Argument mismatch.\n  Extra detail.\n";
+
+        let diagnostics = parse_tsc_output(output, source);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (2, 7));
+        assert!(diagnostics[0].message.starts_with("TS2322: Type 'string'"));
+        assert!(diagnostics[0].snippet.contains("> 2 | const b: number = 'x';"));
+        assert_eq!(diagnostics[1].message, "TS2345: Argument mismatch.\nExtra detail.");
+    }
+
+    #[test]
+    fn test_typecheck_with_tsc() {
+        // Only meaningful where TypeScript is installed
+        if find_tsc().is_none() {
+            return;
+        }
+
+        let ok = "import { useState } from 'react';\n\
+                  const Counter: React.FC = () => { const [n] = useState(0); return <b>{n}</b>; };";
+        assert!(typecheck(ok).unwrap().is_empty());
+
+        let broken = "const total: number = 'ten';\nconsole.log(totl);";
+        let errors = typecheck(broken).unwrap();
+        assert!(errors.iter().any(|e| e.line == 1 && e.message.starts_with("TS2322")));
+        assert!(errors.iter().any(|e| e.line == 2 && e.message.contains("totl")));
+    }
+}
@@ -0,0 +1,422 @@
+//! src-tauri/src/vault.rs
+//! ======================
+//! Encryption at rest for API key values stored in the project `.env`.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! API keys live in the project's `.env` rather than an OS keychain, so
+//! anyone who can read the file (or a copy committed by mistake) gets the
+//! keys. Values are therefore encrypted with AES-256-GCM and stored as
+//!
+//!     APIKEY_GEMINI_<UUID>=enc:v1:<hex nonce>:<hex ciphertext>
+//!
+//! The key is either:
+//! - derived from a user passphrase (`APP_FACTORY_VAULT_PASSPHRASE`) with
+//!   PBKDF2-HMAC-SHA256 and a random salt kept in the app config dir, or
+//! - a random machine key generated on first use and kept in the app
+//!   config dir, so a copied `.env` is useless on another machine.
+//!
+//! Values without the `enc:v1:` prefix are legacy plaintext; they still
+//! decrypt (pass through) and are reported for migration. If no key can be
+//! loaded the vault is disabled and values are stored as plaintext.
+//!
+//! `seal` / `unseal` encrypt arbitrary payloads (key exports) with a
+//! passphrase alone, producing a self-describing JSON bundle that can be
+//! opened on any machine.
+//!
+//! Usage:
+//!     ```rust
+//!     let vault = KeyVault::load(config_dir.as_deref());
+//!     let stored = vault.encrypt("sk-...")?;
+//!     assert_eq!(vault.decrypt(&stored)?, "sk-...");
+//!
+//!     let bundle = vault::seal("correct horse", payload)?;
+//!     let payload = vault::unseal("correct horse", &bundle)?;
+//!     ```
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::redact;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Prefix marking an encrypted value
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Environment variable holding an optional vault passphrase
+pub const PASSPHRASE_ENV: &str = "APP_FACTORY_VAULT_PASSPHRASE";
+
+/// Machine key file inside the app config dir
+const KEY_FILE: &str = "vault.key";
+
+/// Passphrase salt file inside the app config dir
+const SALT_FILE: &str = "vault.salt";
+
+/// PBKDF2 iterations for passphrase-derived keys
+const PBKDF2_ROUNDS: u32 = 600_000;
+
+/// AES-GCM nonce length in bytes
+const NONCE_LEN: usize = 12;
+
+/// Salt length in bytes for passphrase-derived keys
+const SALT_LEN: usize = 16;
+
+/// `format` field of sealed bundles
+const BUNDLE_FORMAT: &str = "app-factory-sealed";
+
+/// Upper bound on bundle iteration counts (keeps hostile bundles cheap to reject)
+const MAX_PBKDF2_ROUNDS: u32 = 10_000_000;
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Where the encryption key comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    /// Derived from `APP_FACTORY_VAULT_PASSPHRASE`
+    Passphrase,
+    /// Random key stored in the app config dir
+    Machine,
+    /// No key available; values are stored in plaintext
+    None,
+}
+
+/// Passphrase-encrypted payload, serialized as JSON.
+#[derive(Debug, Serialize, Deserialize)]
+struct SealedBundle {
+    format: String,
+    version: u32,
+    /// PBKDF2-HMAC-SHA256 iterations
+    iterations: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+// ============================================
+// KEY VAULT
+// ============================================
+
+/// Encrypts and decrypts stored API key values.
+#[derive(Clone)]
+pub struct KeyVault {
+    cipher: Option<Arc<Aes256Gcm>>,
+    source: KeySource,
+}
+
+impl KeyVault {
+    /// Load the vault key from the app config directory.
+    ///
+    /// Uses the passphrase from `APP_FACTORY_VAULT_PASSPHRASE` when set,
+    /// otherwise the machine key (created on first use). Falls back to a
+    /// disabled vault if neither can be loaded.
+    pub fn load(config_dir: Option<&Path>) -> Self {
+        let passphrase = std::env::var(PASSPHRASE_ENV).ok().filter(|p| !p.is_empty());
+        let loaded = match (config_dir, passphrase) {
+            (Some(dir), Some(passphrase)) => {
+                passphrase_key(dir, &passphrase).map(|key| (key, KeySource::Passphrase))
+            }
+            (Some(dir), None) => machine_key(dir).map(|key| (key, KeySource::Machine)),
+            (None, _) => Err("no app config directory".to_string()),
+        };
+
+        match loaded {
+            Ok((key, source)) => {
+                log::info!("API key vault enabled ({source:?} key)");
+                Self::with_key(&key, source)
+            }
+            Err(e) => {
+                log::warn!("API key vault disabled, keys will be stored in plaintext: {e}");
+                Self::disabled()
+            }
+        }
+    }
+
+    /// Create a vault with an explicit 256-bit key.
+    pub fn with_key(key: &[u8; 32], source: KeySource) -> Self {
+        Self {
+            cipher: Some(Arc::new(Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)))),
+            source,
+        }
+    }
+
+    /// Create a vault that stores values in plaintext.
+    pub fn disabled() -> Self {
+        Self {
+            cipher: None,
+            source: KeySource::None,
+        }
+    }
+
+    /// Whether values are encrypted.
+    pub fn is_enabled(&self) -> bool {
+        self.cipher.is_some()
+    }
+
+    /// Where the key comes from.
+    pub fn source(&self) -> KeySource {
+        self.source
+    }
+
+    /// Encrypt a value for storage (returned unchanged if the vault is disabled).
+    pub fn encrypt(&self, value: &str) -> Result<String, String> {
+        redact::register(value);
+        let Some(cipher) = &self.cipher else {
+            return Ok(value.to_string());
+        };
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, value.as_bytes())
+            .map_err(|_| "Failed to encrypt value".to_string())?;
+        Ok(format!(
+            "{ENCRYPTED_PREFIX}{}:{}",
+            to_hex(&nonce),
+            to_hex(&ciphertext)
+        ))
+    }
+
+    /// Decrypt a stored value. Legacy plaintext values are returned as-is.
+    pub fn decrypt(&self, stored: &str) -> Result<String, String> {
+        let Some(payload) = stored.strip_prefix(ENCRYPTED_PREFIX) else {
+            redact::register(stored);
+            return Ok(stored.to_string());
+        };
+        let cipher = self
+            .cipher
+            .as_ref()
+            .ok_or_else(|| "Value is encrypted but no vault key is available".to_string())?;
+
+        let (nonce, ciphertext) = payload
+            .split_once(':')
+            .ok_or_else(|| "Malformed encrypted value".to_string())?;
+        let nonce = from_hex(nonce).filter(|n| n.len() == NONCE_LEN);
+        let (Some(nonce), Some(ciphertext)) = (nonce, from_hex(ciphertext)) else {
+            return Err("Malformed encrypted value".to_string());
+        };
+
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| "Failed to decrypt value (wrong key or passphrase?)".to_string())?;
+        let value =
+            String::from_utf8(plaintext).map_err(|_| "Decrypted value is not UTF-8".to_string())?;
+        redact::register(&value);
+        Ok(value)
+    }
+}
+
+/// Whether a stored value is encrypted.
+pub fn is_encrypted(stored: &str) -> bool {
+    stored.starts_with(ENCRYPTED_PREFIX)
+}
+
+// ============================================
+// PASSPHRASE BUNDLES
+// ============================================
+
+/// Encrypt a payload with a key derived from `passphrase`.
+///
+/// # Returns
+///
+/// A JSON bundle carrying the salt, nonce and iteration count.
+pub fn seal(passphrase: &str, plaintext: &[u8]) -> Result<String, String> {
+    seal_with_rounds(passphrase, plaintext, PBKDF2_ROUNDS)
+}
+
+/// `seal` with an explicit PBKDF2 iteration count.
+fn seal_with_rounds(passphrase: &str, plaintext: &[u8], rounds: u32) -> Result<String, String> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_key(passphrase, &salt, rounds);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| "Failed to encrypt bundle".to_string())?;
+
+    let bundle = SealedBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: 1,
+        iterations: rounds,
+        salt: to_hex(&salt),
+        nonce: to_hex(&nonce),
+        ciphertext: to_hex(&ciphertext),
+    };
+    serde_json::to_string_pretty(&bundle).map_err(|e| format!("Failed to serialize bundle: {e}"))
+}
+
+/// Decrypt a bundle produced by `seal`.
+pub fn unseal(passphrase: &str, sealed: &str) -> Result<Vec<u8>, String> {
+    let bundle: SealedBundle =
+        serde_json::from_str(sealed).map_err(|e| format!("Not a key bundle: {e}"))?;
+    if bundle.format != BUNDLE_FORMAT || bundle.version != 1 {
+        return Err(format!(
+            "Unsupported bundle format {} v{}",
+            bundle.format, bundle.version
+        ));
+    }
+    if bundle.iterations == 0 || bundle.iterations > MAX_PBKDF2_ROUNDS {
+        return Err(format!("Invalid iteration count {}", bundle.iterations));
+    }
+
+    let nonce = from_hex(&bundle.nonce).filter(|n| n.len() == NONCE_LEN);
+    let (Some(salt), Some(nonce), Some(ciphertext)) =
+        (from_hex(&bundle.salt), nonce, from_hex(&bundle.ciphertext))
+    else {
+        return Err("Malformed key bundle".to_string());
+    };
+
+    let key = derive_key(passphrase, &salt, bundle.iterations);
+    Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Wrong passphrase or corrupted bundle".to_string())
+}
+
+// ============================================
+// KEY LOADING
+// ============================================
+
+/// Read the machine key, generating it on first use.
+fn machine_key(dir: &Path) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    let bytes = read_or_create(&dir.join(KEY_FILE), key.len())?;
+    key.copy_from_slice(&bytes);
+    Ok(key)
+}
+
+/// Derive a key from a passphrase and the stored salt.
+fn passphrase_key(dir: &Path, passphrase: &str) -> Result<[u8; 32], String> {
+    let salt = read_or_create(&dir.join(SALT_FILE), SALT_LEN)?;
+    Ok(derive_key(passphrase, &salt, PBKDF2_ROUNDS))
+}
+
+/// PBKDF2-HMAC-SHA256 key derivation.
+fn derive_key(passphrase: &str, salt: &[u8], rounds: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, rounds, &mut key);
+    key
+}
+
+/// Read `len` hex-encoded random bytes from `path`, creating the file if missing.
+fn read_or_create(path: &Path, len: usize) -> Result<Vec<u8>, String> {
+    if path.exists() {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read {path:?}: {e}"))?;
+        return from_hex(content.trim())
+            .filter(|bytes| bytes.len() == len)
+            .ok_or_else(|| format!("Corrupt key file {path:?}"));
+    }
+
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {parent:?}: {e}"))?;
+    }
+    write_private(path, &to_hex(&bytes)).map_err(|e| format!("Failed to write {path:?}: {e}"))?;
+    Ok(bytes)
+}
+
+/// Write a file readable only by the current user (where supported).
+fn write_private(path: &Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write as _;
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content.as_bytes())
+}
+
+/// Lowercase hex encoding.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut out, b| {
+            let _ = write!(out, "{b:02x}");
+            out
+        })
+}
+
+/// Hex decoding; None if the input isn't valid hex.
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_roundtrip() {
+        let vault = KeyVault::with_key(&[7u8; 32], KeySource::Machine);
+        let stored = vault.encrypt("sk-secret").unwrap();
+
+        assert!(is_encrypted(&stored));
+        assert!(!stored.contains("sk-secret"));
+        assert_ne!(stored, vault.encrypt("sk-secret").unwrap());
+        assert_eq!(vault.decrypt(&stored).unwrap(), "sk-secret");
+
+        // Legacy plaintext passes through
+        assert_eq!(vault.decrypt("plain-key").unwrap(), "plain-key");
+
+        let other = KeyVault::with_key(&[8u8; 32], KeySource::Machine);
+        assert!(other.decrypt(&stored).is_err());
+        assert!(KeyVault::disabled().decrypt(&stored).is_err());
+    }
+
+    #[test]
+    fn test_machine_key_persists() {
+        let dir = std::env::temp_dir().join(format!("af_vault_{}", uuid::Uuid::new_v4()));
+
+        let first = machine_key(&dir).unwrap();
+        assert_eq!(machine_key(&dir).unwrap(), first);
+        assert_eq!(fs::read_to_string(dir.join(KEY_FILE)).unwrap().len(), 64);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_seal_roundtrip() {
+        // Few rounds keep the test fast; the count travels with the bundle
+        let bundle = seal_with_rounds("correct horse", b"{\"keys\":[]}", 1_000).unwrap();
+        assert!(bundle.contains(BUNDLE_FORMAT));
+
+        assert_eq!(unseal("correct horse", &bundle).unwrap(), b"{\"keys\":[]}");
+        assert!(unseal("wrong horse", &bundle).is_err());
+        assert!(unseal("correct horse", "{}").is_err());
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex(&[0, 15, 255]), "000fff");
+        assert_eq!(from_hex("000fff"), Some(vec![0, 15, 255]));
+        assert_eq!(from_hex("0g"), None);
+        assert_eq!(from_hex("abc"), None);
+    }
+}
@@ -0,0 +1,349 @@
+//! src-tauri/src/watch.rs
+//! ======================
+//! Watch-mode compilation for hot reload while editing exported apps.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! A watch recursively observes a source directory. Changes are debounced,
+//! and each changed TSX/TS/JSX/JS/MDX file whose content actually changed
+//! is recompiled on its own (through the compile cache) and reported to
+//! the frontend:
+//!
+//!     compiler://updated   { watch_id, path, removed, result }
+//!
+//! `path` is relative to the watched directory. `node_modules`, build
+//! output and hidden directories are ignored. Dropping the watch stops
+//! the file system watcher, which ends its worker thread.
+//!
+//! Usage:
+//!     ```rust
+//!     let info = watches.start(dir, cache, artifacts, options, |update| {
+//!         app.emit_all(UPDATED_EVENT, update).ok();
+//!     })?;
+//!     watches.stop(&info.id);
+//!     ```
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::artifacts::{content_hash, ArtifactStore};
+use crate::commands::compiler::{CompileCache, CompileOptions, CompileResult};
+use crate::mdx;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Event emitted after a watched file is recompiled
+pub const UPDATED_EVENT: &str = "compiler://updated";
+
+/// Quiet period before a burst of changes is processed
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Extensions that are compiled
+const SOURCE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mdx"];
+
+/// Directories never descended into
+const IGNORED_DIRS: &[&str] = &["node_modules", "dist", "build", "target"];
+
+// ============================================
+// TYPES
+// ============================================
+
+/// An active watch.
+#[derive(Debug, Clone, Serialize)]
+pub struct WatchInfo {
+    /// Watch id
+    pub id: String,
+    /// Watched directory
+    pub path: String,
+    /// Start timestamp (RFC 3339)
+    pub started_at: String,
+}
+
+/// Payload of `compiler://updated`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CompileUpdate {
+    /// Watch id
+    pub watch_id: String,
+    /// Changed file, relative to the watched directory (`/` separated)
+    pub path: String,
+    /// Whether the file was deleted (`result` is then None)
+    pub removed: bool,
+    /// Compilation result for the new content
+    pub result: Option<CompileResult>,
+}
+
+/// Watch bookkeeping.
+struct Watch {
+    info: WatchInfo,
+    /// Dropping the watcher disconnects the worker's channel
+    _watcher: RecommendedWatcher,
+}
+
+// ============================================
+// COMPILE WATCHER
+// ============================================
+
+/// Manages compile watches.
+#[derive(Clone, Default)]
+pub struct CompileWatcher {
+    watches: Arc<Mutex<HashMap<String, Watch>>>,
+}
+
+impl CompileWatcher {
+    /// Create an empty watcher registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start watching a directory.
+    ///
+    /// `on_update` is called from the watch's worker thread for every
+    /// recompiled or removed file.
+    pub fn start<F>(
+        &self,
+        dir: &Path,
+        cache: CompileCache,
+        artifacts: ArtifactStore,
+        options: CompileOptions,
+        on_update: F,
+    ) -> Result<WatchInfo, String>
+    where
+        F: Fn(&CompileUpdate) + Send + 'static,
+    {
+        let root = dir
+            .canonicalize()
+            .map_err(|e| format!("Cannot watch {dir:?}: {e}"))?;
+        if !root.is_dir() {
+            return Err(format!("Not a directory: {root:?}"));
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(tx).map_err(|e| format!("Failed to create watcher: {e}"))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|e| format!("Failed to watch {root:?}: {e}"))?;
+
+        let info = WatchInfo {
+            id: uuid::Uuid::new_v4().to_string(),
+            path: root.to_string_lossy().to_string(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let worker = Worker {
+            watch_id: info.id.clone(),
+            root,
+            cache,
+            artifacts,
+            options,
+            hashes: HashMap::new(),
+        };
+        std::thread::Builder::new()
+            .name(format!("compile-watch-{}", info.id))
+            .spawn(move || worker.run(&rx, &on_update))
+            .map_err(|e| format!("Failed to start watch thread: {e}"))?;
+
+        self.watches.lock().unwrap().insert(
+            info.id.clone(),
+            Watch {
+                info: info.clone(),
+                _watcher: watcher,
+            },
+        );
+        log::info!("Compile watch {} started on {}", info.id, info.path);
+        Ok(info)
+    }
+
+    /// Stop a watch.
+    ///
+    /// # Returns
+    ///
+    /// `false` if no watch has the id.
+    pub fn stop(&self, id: &str) -> bool {
+        let stopped = self.watches.lock().unwrap().remove(id).is_some();
+        if stopped {
+            log::info!("Compile watch {id} stopped");
+        }
+        stopped
+    }
+
+    /// List active watches.
+    pub fn list(&self) -> Vec<WatchInfo> {
+        self.watches
+            .lock()
+            .unwrap()
+            .values()
+            .map(|watch| watch.info.clone())
+            .collect()
+    }
+}
+
+// ============================================
+// WORKER
+// ============================================
+
+/// Per-watch state owned by the worker thread.
+struct Worker {
+    watch_id: String,
+    root: PathBuf,
+    cache: CompileCache,
+    artifacts: ArtifactStore,
+    options: CompileOptions,
+    /// Content hash of each file as last compiled
+    hashes: HashMap<PathBuf, String>,
+}
+
+impl Worker {
+    /// Process debounced batches of changes until the watcher is dropped.
+    fn run(
+        mut self,
+        rx: &mpsc::Receiver<notify::Result<notify::Event>>,
+        on_update: &impl Fn(&CompileUpdate),
+    ) {
+        while let Ok(first) = rx.recv() {
+            let mut changed = BTreeSet::new();
+            let mut collect = |event: notify::Result<notify::Event>| match event {
+                Ok(event) if !matches!(event.kind, EventKind::Access(_)) => {
+                    changed.extend(event.paths);
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Compile watch {}: {e}", self.watch_id),
+            };
+            collect(first);
+            while let Ok(event) = rx.recv_timeout(DEBOUNCE) {
+                collect(event);
+            }
+
+            for path in changed {
+                if let Some(update) = self.process(&path) {
+                    on_update(&update);
+                }
+            }
+        }
+        log::debug!("Compile watch {} worker exiting", self.watch_id);
+    }
+
+    /// Recompile a changed file; None if it isn't a source or didn't change.
+    fn process(&mut self, path: &Path) -> Option<CompileUpdate> {
+        let relative = path.strip_prefix(&self.root).ok()?;
+        if !is_watched_source(relative) {
+            return None;
+        }
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let Ok(source) = fs::read_to_string(path) else {
+            // Deleted (or unreadable)
+            self.hashes.remove(path)?;
+            return Some(CompileUpdate {
+                watch_id: self.watch_id.clone(),
+                path: relative,
+                removed: true,
+                result: None,
+            });
+        };
+
+        let hash = content_hash(source.as_bytes());
+        if self.hashes.get(path) == Some(&hash) {
+            return None;
+        }
+        self.hashes.insert(path.to_path_buf(), hash);
+
+        let code = if relative.ends_with(".mdx") {
+            mdx::to_tsx(&source)
+        } else {
+            source
+        };
+        let result = match self.cache.compile(&self.artifacts, &code, &self.options) {
+            Ok(js_code) => CompileResult::success(js_code),
+            Err(error) => error.into(),
+        };
+        log::debug!("Compile watch {}: {relative} recompiled", self.watch_id);
+        Some(CompileUpdate {
+            watch_id: self.watch_id.clone(),
+            path: relative,
+            removed: false,
+            result: Some(result),
+        })
+    }
+}
+
+/// Whether a path (relative to the watch root) is a source file to compile.
+fn is_watched_source(relative: &Path) -> bool {
+    let in_ignored_dir = relative.parent().is_some_and(|parent| {
+        parent.components().any(|c| match c {
+            Component::Normal(name) => {
+                let name = name.to_string_lossy();
+                name.starts_with('.') || IGNORED_DIRS.contains(&name.as_ref())
+            }
+            _ => false,
+        })
+    });
+    let is_source = relative
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SOURCE_EXTENSIONS.contains(&ext))
+        && !relative.to_string_lossy().ends_with(".d.ts");
+
+    is_source && !in_ignored_dir
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_watched_source() {
+        assert!(is_watched_source(Path::new("src/App.tsx")));
+        assert!(is_watched_source(Path::new("docs/intro.mdx")));
+        assert!(!is_watched_source(Path::new("src/types.d.ts")));
+        assert!(!is_watched_source(Path::new("node_modules/react/index.js")));
+        assert!(!is_watched_source(Path::new(".git/hooks/pre-commit.js")));
+        assert!(!is_watched_source(Path::new("styles.css")));
+    }
+
+    #[test]
+    fn test_watch_recompiles_changes() {
+        let dir = std::env::temp_dir().join(format!("af_watch_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join("src")).unwrap();
+
+        let watches = CompileWatcher::new();
+        let (tx, rx) = mpsc::channel();
+        let info = watches
+            .start(
+                &dir,
+                CompileCache::new(),
+                ArtifactStore::open(None),
+                CompileOptions::default(),
+                move |update| {
+                    let _ = tx.send(update.clone());
+                },
+            )
+            .unwrap();
+        assert_eq!(watches.list().len(), 1);
+
+        fs::write(dir.join("src/App.tsx"), "export const App = () => <main>hi</main>;").unwrap();
+        let update = rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(update.path, "src/App.tsx");
+        assert!(update.result.unwrap().code.unwrap().contains("React.createElement"));
+
+        assert!(watches.stop(&info.id));
+        assert!(watches.list().is_empty());
+        let _ = fs::remove_dir_all(dir);
+    }
+}
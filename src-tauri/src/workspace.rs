@@ -0,0 +1,238 @@
+//! src-tauri/src/workspace.rs
+//! ==========================
+//! Workspace registry for multiple concurrently open projects.
+//!
+//! Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+//!
+//! Each workspace owns its own `IpcManagerState` (and so its own Python
+//! plugin host) running in the workspace's project directory. The
+//! `"default"` workspace is the app's primary manager, which `project_open`
+//! retargets; additional workspaces are opened alongside it.
+//!
+//! Plugin and IPC commands accept an optional `workspace` id and are routed
+//! to the matching manager via `WorkspaceRegistry::get`.
+//!
+//! Usage:
+//!     ```rust
+//!     let registry = WorkspaceRegistry::new(default_state);
+//!     let (id, manager, created) = registry.open(Path::new("/path/to/other"))?;
+//!     manager.start().await?;
+//!     let same = registry.get(Some(&id))?;
+//!     ```
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use uuid::Uuid;
+
+use crate::ipc::manager::{IpcConfig, IpcManagerState, LifecycleState};
+use crate::offline;
+
+// ============================================
+// CONSTANTS
+// ============================================
+
+/// Id of the primary workspace (always present)
+pub const DEFAULT_WORKSPACE: &str = "default";
+
+// ============================================
+// TYPES
+// ============================================
+
+/// Workspace summary returned to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceInfo {
+    /// Workspace id (used as the `workspace` command parameter)
+    pub id: String,
+    /// Project directory the plugin host runs in
+    pub path: Option<String>,
+    /// Lifecycle state of the workspace's IPC Manager
+    pub lifecycle_state: LifecycleState,
+    /// Whether this is the primary workspace
+    pub is_default: bool,
+}
+
+// ============================================
+// WORKSPACE REGISTRY
+// ============================================
+
+/// Registry of IPC managers keyed by workspace id.
+///
+/// Clones share the workspaces, so every command sees the same ones.
+#[derive(Clone)]
+pub struct WorkspaceRegistry {
+    /// Configuration template for new workspaces
+    base_config: IpcConfig,
+
+    /// Managers keyed by workspace id
+    workspaces: Arc<RwLock<HashMap<String, IpcManagerState>>>,
+}
+
+impl WorkspaceRegistry {
+    /// Create a registry whose default workspace is `default_state`.
+    ///
+    /// New workspaces inherit the default workspace's configuration,
+    /// with the working directory and offline mode replaced.
+    pub fn new(default_state: IpcManagerState) -> Self {
+        let base_config = default_state.config();
+        let mut map = HashMap::new();
+        map.insert(DEFAULT_WORKSPACE.to_string(), default_state);
+
+        Self {
+            base_config,
+            workspaces: Arc::new(RwLock::new(map)),
+        }
+    }
+
+    /// Get the manager for a workspace (`None` selects the default).
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(IpcManagerState)` - Handle to the workspace's manager
+    /// * `Err(String)` - Unknown workspace id
+    pub fn get(&self, id: Option<&str>) -> Result<IpcManagerState, String> {
+        let id = id.unwrap_or(DEFAULT_WORKSPACE);
+        self.workspaces
+            .read()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown workspace: {id}"))
+    }
+
+    /// Register a new workspace for a project directory.
+    ///
+    /// The returned manager is not started; callers start it and call
+    /// `close` if startup fails. Opening a path that already has a
+    /// workspace returns the existing one.
+    ///
+    /// # Returns
+    ///
+    /// The workspace id, its manager, and whether this call created it.
+    pub fn open(&self, path: &Path) -> Result<(String, IpcManagerState, bool), String> {
+        let root = fs::canonicalize(path)
+            .map_err(|e| format!("Cannot open workspace {}: {e}", path.display()))?;
+
+        if !root.is_dir() {
+            return Err(format!("Workspace path is not a directory: {}", root.display()));
+        }
+
+        let mut workspaces = self.workspaces.write().unwrap();
+
+        if let Some((id, existing)) = workspaces
+            .iter()
+            .find(|(_, m)| m.config().working_dir.as_deref() == Some(root.as_path()))
+        {
+            return Ok((id.clone(), existing.clone(), false));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let config = self
+            .base_config
+            .clone()
+            .with_working_dir(root)
+            .with_offline(offline::is_offline());
+        let manager = IpcManagerState::new(config);
+        workspaces.insert(id.clone(), manager.clone());
+
+        log::info!("Workspace registered: {id}");
+        Ok((id, manager, true))
+    }
+
+    /// Remove a workspace from the registry.
+    ///
+    /// The default workspace cannot be closed.
+    pub fn close(&self, id: &str) -> Result<IpcManagerState, String> {
+        if id == DEFAULT_WORKSPACE {
+            return Err("The default workspace cannot be closed".to_string());
+        }
+
+        self.workspaces
+            .write()
+            .unwrap()
+            .remove(id)
+            .ok_or_else(|| format!("Unknown workspace: {id}"))
+    }
+
+    /// Get all workspace ids with their managers.
+    pub fn entries(&self) -> Vec<(String, IpcManagerState)> {
+        self.workspaces
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(id, m)| (id.clone(), m.clone()))
+            .collect()
+    }
+
+    /// Build summaries for all workspaces (default first).
+    pub async fn list(&self) -> Vec<WorkspaceInfo> {
+        let mut infos = Vec::new();
+
+        for (id, manager) in self.entries() {
+            infos.push(WorkspaceInfo {
+                path: manager
+                    .config()
+                    .working_dir
+                    .map(|p| p.to_string_lossy().to_string()),
+                lifecycle_state: manager.lifecycle_state().await,
+                is_default: id == DEFAULT_WORKSPACE,
+                id,
+            });
+        }
+
+        infos.sort_by(|a, b| b.is_default.cmp(&a.is_default).then(a.id.cmp(&b.id)));
+        infos
+    }
+}
+
+// ============================================
+// TESTS
+// ============================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::temp_dir;
+
+    #[test]
+    fn test_default_workspace() {
+        let registry = WorkspaceRegistry::new(IpcManagerState::new(IpcConfig::default()));
+
+        assert!(registry.get(None).is_ok());
+        assert!(registry.get(Some(DEFAULT_WORKSPACE)).is_ok());
+        assert!(registry.get(Some("missing")).is_err());
+        assert!(registry.close(DEFAULT_WORKSPACE).is_err());
+    }
+
+    #[test]
+    fn test_open_and_close_workspace() {
+        let registry = WorkspaceRegistry::new(IpcManagerState::new(IpcConfig::default()));
+        let dir = temp_dir("ws");
+
+        let (id, manager, created) = registry.open(&dir).unwrap();
+        assert!(created);
+        assert_eq!(manager.config().working_dir, Some(fs::canonicalize(&dir).unwrap()));
+        assert!(registry.get(Some(&id)).is_ok());
+
+        // Opening the same path reuses the workspace
+        let (again, _, created) = registry.open(&dir).unwrap();
+        assert_eq!(again, id);
+        assert!(!created);
+
+        registry.close(&id).unwrap();
+        assert!(registry.get(Some(&id)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_puts_default_first() {
+        let registry = WorkspaceRegistry::new(IpcManagerState::new(IpcConfig::default()));
+        registry.open(&temp_dir("ws")).unwrap();
+
+        let infos = registry.list().await;
+        assert_eq!(infos.len(), 2);
+        assert!(infos[0].is_default);
+        assert_eq!(infos[1].lifecycle_state, LifecycleState::Uninitialized);
+    }
+}
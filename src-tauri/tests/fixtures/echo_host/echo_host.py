@@ -0,0 +1,100 @@
+"""
+src-tauri/tests/fixtures/echo_host/echo_host.py
+===============================================
+Minimal JSON-RPC host used by the IPC integration tests.
+
+Speaks the same newline-delimited JSON-RPC 2.0 protocol as plugins._host,
+without plugins, so the Rust IPC manager can be exercised end-to-end with
+nothing but a Python interpreter.
+
+Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+Protocol: JSON-RPC 2.0 over stdin/stdout (newline-delimited)
+
+Methods:
+    ping            -> "pong"
+    echo            -> the request params
+    sleep           -> sleeps params.seconds, then returns null
+    progress        -> sends params.steps progress notifications, then returns null
+    crash           -> exits with params.code without answering
+    shutdown        -> answers null and exits with code 0
+
+Usage (from the fixture directory):
+    python -m echo_host
+"""
+
+import json
+import os
+import sys
+import time
+from typing import Any
+
+
+def write(message: dict[str, Any]) -> None:
+    """Write one JSON-RPC message to stdout."""
+    sys.stdout.write(json.dumps(message) + "\n")
+    sys.stdout.flush()
+
+
+def respond(request_id: Any, result: Any) -> None:
+    """Answer a request with a result."""
+    write({"jsonrpc": "2.0", "id": request_id, "result": result})
+
+
+def fail(request_id: Any, code: int, message: str) -> None:
+    """Answer a request with an error."""
+    write({"jsonrpc": "2.0", "id": request_id, "error": {"code": code, "message": message}})
+
+
+def handle(request: dict[str, Any]) -> None:
+    """Dispatch one request."""
+    request_id = request.get("id")
+    method = request.get("method")
+    params = request.get("params") or {}
+
+    if method == "ping":
+        respond(request_id, "pong")
+    elif method == "echo":
+        respond(request_id, params)
+    elif method == "sleep":
+        time.sleep(float(params.get("seconds", 1)))
+        respond(request_id, None)
+    elif method == "progress":
+        steps = int(params.get("steps", 1))
+        for step in range(1, steps + 1):
+            write(
+                {
+                    "jsonrpc": "2.0",
+                    "method": f"progress/{request_id}",
+                    "params": {"percentage": step * 100 / steps, "message": f"step {step}"},
+                }
+            )
+        respond(request_id, None)
+    elif method == "crash":
+        sys.stderr.write("echo_host: crashing on request\n")
+        sys.stderr.flush()
+        os._exit(int(params.get("code", 3)))
+    elif method == "shutdown":
+        respond(request_id, None)
+        sys.exit(0)
+    elif request_id is not None:
+        fail(request_id, -32601, f"Method not found: {method}")
+
+
+def main() -> None:
+    """Serve requests until stdin closes."""
+    for line in sys.stdin:
+        line = line.strip()
+        if not line:
+            continue
+        try:
+            message = json.loads(line)
+        except json.JSONDecodeError:
+            fail(None, -32700, "Parse error")
+            continue
+        # Responses to host-initiated requests are not expected here
+        if "method" in message:
+            handle(message)
+
+
+if __name__ == "__main__":
+    main()
@@ -21,6 +21,7 @@ import { isTauri } from './utils/tauriUtils';
 import { ThemeCustomizationPanel } from './components/ui/ThemeCustomizationPanel';
 import { WindowConfigPanel, useWindowConfigStore } from './components/ui/WindowConfigPanel';
 import { Modal } from './components/ui/Modal';
+import { StartupSplash } from './components/ui/StartupSplash';
 // ProjectDetailsPanel import removed as it does not exist
 // Sidebar Icons
 const FolderIcon = ({ className }: { className?: string }) => (
@@ -1089,6 +1090,31 @@ export const App: React.FC = () => {
 
   // Import wizard state (EUR-1.1.3b)
   const [showImportWizard, setShowImportWizard] = useState(false);
+  const [importWizardUrl, setImportWizardUrl] = useState<string | undefined>(undefined);
+
+  // Open the import wizard for confirmed appfactory://install-plugin links
+  useEffect(() => {
+    if (!isTauri()) {
+      return;
+    }
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    void import('@tauri-apps/api/event').then(async ({ listen }) => {
+      const stop = await listen<{ url: string }>('deep-link://install-plugin', (event) => {
+        setImportWizardUrl(event.payload.url);
+        setShowImportWizard(true);
+      });
+      if (cancelled) {
+        stop();
+      } else {
+        unlisten = stop;
+      }
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
 
   // Toggle handlers for Theme and Preview panels
   const handleToggleThemePanel = useCallback(() => {
@@ -1640,6 +1666,7 @@ export const App: React.FC = () => {
   if (appMode === 'launcher') {
     return (
       <ThemeProvider>
+        <StartupSplash />
         <div className="min-h-screen bg-neutral-100 flex items-center justify-center p-8">
           <div className="w-full max-w-4xl">
             <div className="text-center mb-8">
@@ -1673,6 +1700,7 @@ export const App: React.FC = () => {
   // Render editor mode (factory interface)
   return (
     <ThemeProvider>
+      <StartupSplash />
       <div className="min-h-screen bg-bg-primary text-text-primary">
         <FactoryLayout
           key={`layout-${showBackendBlueprint ? 'with-blueprint' : 'no-blueprint'}`}
@@ -2241,8 +2269,12 @@ export const App: React.FC = () => {
         {/* Import Wizard (EUR-1.1.3b) */}
         <ImportWizard
           isOpen={showImportWizard}
-          onClose={() => setShowImportWizard(false)}
+          onClose={() => {
+            setShowImportWizard(false);
+            setImportWizardUrl(undefined);
+          }}
           onImport={handleImportComponent}
+          initialUrl={importWizardUrl}
         />
       </div>
     </ThemeProvider>
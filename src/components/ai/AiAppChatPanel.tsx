@@ -333,6 +333,7 @@ export const AiAppChatPanel: React.FC<AiAppChatPanelProps> = ({
 
     // Local state
     const [inputValue, setInputValue] = useState('');
+    const [streamingText, setStreamingText] = useState('');
     const messagesEndRef = useRef<HTMLDivElement>(null);
     const textareaRef = useRef<HTMLTextAreaElement>(null);
     const inputContainerRef = useRef<HTMLDivElement>(null);
@@ -359,7 +360,7 @@ export const AiAppChatPanel: React.FC<AiAppChatPanelProps> = ({
     // Auto-scroll to bottom
     useEffect(() => {
         messagesEndRef.current?.scrollIntoView({ behavior: 'smooth' });
-    }, [messages]);
+    }, [messages, streamingText]);
 
     // Focus textarea on mount
     useEffect(() => {
@@ -477,7 +478,9 @@ export const AiAppChatPanel: React.FC<AiAppChatPanelProps> = ({
             const finalPrompt = canvasContext ? `${trimmed}\n${canvasContext}` : trimmed;
 
             console.log('[AiAppChatPanel] Calling generateWithConfig with history:', history.length, 'images:', images.length);
-            const result = await generateWithConfig(config, finalPrompt, history, images);
+            const result = await generateWithConfig(config, finalPrompt, history, images, (text) => {
+                setStreamingText(prev => prev + text);
+            });
             console.log('[AiAppChatPanel] Result:', result.success);
 
             if (result.success && result.text) {
@@ -508,6 +511,7 @@ export const AiAppChatPanel: React.FC<AiAppChatPanelProps> = ({
             const errorMessage = err instanceof Error ? err.message : 'Unknown error';
             setError(errorMessage);
         } finally {
+            setStreamingText('');
             setIsGenerating(false);
         }
     }, [inputValue, isGenerating, messages, addMessage, getCurrentConfig, setIsGenerating, setError, scope, canvasElements, getComponentCode, mode, onApplyCanvasChanges]);
@@ -615,9 +619,15 @@ export const AiAppChatPanel: React.FC<AiAppChatPanelProps> = ({
                                 <div className="flex items-center justify-center w-6 h-6 rounded-full bg-neutral-200 text-neutral-600 shrink-0">
                                     <AssistantIcon className="h-3.5 w-3.5" />
                                 </div>
-                                <div className="flex items-center">
-                                    <LoadingDots />
-                                </div>
+                                {streamingText ? (
+                                    <div className="flex-1 min-w-0 text-sm text-neutral-800 whitespace-pre-wrap break-words">
+                                        {streamingText}
+                                    </div>
+                                ) : (
+                                    <div className="flex items-center">
+                                        <LoadingDots />
+                                    </div>
+                                )}
                             </div>
                         )}
                     </>
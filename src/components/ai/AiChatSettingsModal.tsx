@@ -6,16 +6,17 @@
  * Features:
  * - Scope tabs (Backend / Frontend / Full)
  * - Provider dropdown (Gemini, OpenAI, Anthropic, Ollama)
- * - Model dropdown (dynamic based on provider)
+ * - Model dropdown (listed from the provider's API, static fallback)
  * - API Key status via apiKeyStore
  * - System prompt editor
  * - Temperature slider
  */
 
-import React, { useState, useCallback } from 'react';
+import React, { useState, useCallback, useEffect } from 'react';
 import { useAiChatStore, type AiChatScope } from '../../stores/aiChatStore';
 import { MODEL_OPTIONS, type LLMProvider } from '../../stores/settingsStore';
 import { DEFAULT_SYSTEM_PROMPTS, type AiChatProvider } from '../../services/aiChatLlmService';
+import { listProviderModels } from '../../services/llmService';
 
 /**
  * Close icon.
@@ -75,6 +76,29 @@ export const AiChatSettingsModal: React.FC = () => {
 
     const config = getCurrentTabConfig();
 
+    // Models listed by the provider (null = not loaded, use static options)
+    const [listedModels, setListedModels] = useState<{ id: string; label: string }[] | null>(null);
+    const [modelsError, setModelsError] = useState<string | null>(null);
+
+    useEffect(() => {
+        let cancelled = false;
+        setListedModels(null);
+        setModelsError(null);
+
+        listProviderModels(config.provider).then((result) => {
+            if (cancelled) return;
+            if (result.success && result.models.length > 0) {
+                setListedModels(result.models.map((m) => ({ id: m.id, label: m.display_name || m.id })));
+            } else if (!result.success) {
+                setModelsError(result.error || 'Could not list models');
+            }
+        });
+
+        return () => {
+            cancelled = true;
+        };
+    }, [config.provider]);
+
     // Handle provider change
     const handleProviderChange = useCallback((provider: AiChatProvider) => {
         // Reset model to first available for new provider
@@ -108,8 +132,11 @@ export const AiChatSettingsModal: React.FC = () => {
         });
     }, [activeTab, updateScopeConfig]);
 
-    // Get models for current provider
-    const models = MODEL_OPTIONS[config.provider as LLMProvider] || [];
+    // Get models for current provider (keep the configured model selectable)
+    const models = [...(listedModels ?? MODEL_OPTIONS[config.provider as LLMProvider] ?? [])];
+    if (config.model && !models.some((m) => m.id === config.model)) {
+        models.unshift({ id: config.model, label: config.model });
+    }
 
     return (
         <div className="fixed inset-0 z-50 flex items-center justify-center">
@@ -189,6 +216,9 @@ export const AiChatSettingsModal: React.FC = () => {
                                 </option>
                             ))}
                         </select>
+                        {modelsError && (
+                            <p className="mt-1 text-xs text-error-600">{modelsError}</p>
+                        )}
                         {config.provider !== 'ollama' && (
                             <p className="mt-1 text-xs text-neutral-500">
                                 API key configured in main Settings → API Keys
@@ -47,6 +47,8 @@ export interface SystemHealth {
     uptime?: number;
     memoryUsage?: number;
     cpuUsage?: number;
+    /** Recent incidents from the persisted history (describeHealthHistory) */
+    recentIncidents?: string | null;
   };
   ipc: {
     status: HealthStatus;
@@ -465,6 +467,11 @@ export const HealthDashboard: React.FC<HealthDashboardProps> = ({
                   </span>
                 </div>
               )}
+              {systemHealth.pluginHost.recentIncidents && (
+                <div className="text-xs text-warning-600">
+                  {systemHealth.pluginHost.recentIncidents}
+                </div>
+              )}
             </div>
           </div>
         </div>
@@ -0,0 +1,213 @@
+/**
+ * src/components/testing/IpcConsole.tsx
+ * =====================================
+ * Developer IPC console shown in its own window (debug builds only).
+ *
+ * Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+ * Dependencies: D006, D007, D010 (Button.tsx), ipcConsoleService.ts
+ *
+ * Rules:
+ *   - NO hardcoded colors, spacing, or sizes
+ *   - ALL styling via Tailwind classes referencing design tokens
+ *
+ * Lists requests sent to the core plugin host as they finish, shows the
+ * params and result of the selected one, and sends hand-written or
+ * replayed calls (which then show up in the list themselves).
+ */
+
+import React, { useCallback, useEffect, useState } from "react";
+import { Button } from "../ui/Button";
+import {
+  clearIpcHistory,
+  getIpcHistory,
+  onIpcTraffic,
+  replayIpcCall,
+  sendIpcCall,
+  type TrafficEntry,
+} from "../../services/ipcConsoleService";
+
+/** Entries kept in the list (matches the backend log) */
+const MAX_ENTRIES = 500;
+
+const formatJson = (value: unknown) => JSON.stringify(value, null, 2);
+
+/**
+ * IpcConsole component.
+ *
+ * @example
+ * ```tsx
+ * if (window.location.hash === "#/ipc-console") {
+ *   root.render(<IpcConsole />);
+ * }
+ * ```
+ */
+export const IpcConsole: React.FC = () => {
+  const [entries, setEntries] = useState<TrafficEntry[]>([]);
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const [method, setMethod] = useState("ping");
+  const [params, setParams] = useState("{}");
+  const [sending, setSending] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    void onIpcTraffic((entry) => {
+      setEntries((current) => [entry, ...current].slice(0, MAX_ENTRIES));
+    }).then((stop) => {
+      if (cancelled) {
+        stop();
+      } else {
+        unlisten = stop;
+      }
+    });
+    void getIpcHistory().then((history) => {
+      // Keep entries that arrived while the history was loading
+      setEntries((current) => {
+        const seen = new Set(current.map((entry) => entry.id));
+        return [...current, ...history.filter((entry) => !seen.has(entry.id))];
+      });
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  const run = useCallback(async (call: () => Promise<unknown>) => {
+    setSending(true);
+    setError(null);
+    try {
+      await call();
+    } catch (e) {
+      setError(typeof e === "object" && e && "message" in e ? String(e.message) : String(e));
+    } finally {
+      setSending(false);
+    }
+  }, []);
+
+  const send = () => {
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(params);
+    } catch {
+      setError("Params must be valid JSON");
+      return;
+    }
+    void run(() => sendIpcCall(method.trim(), parsed));
+  };
+
+  const clear = () => {
+    void clearIpcHistory().then(() => {
+      setEntries([]);
+      setSelectedId(null);
+    });
+  };
+
+  const selected = entries.find((entry) => entry.id === selectedId);
+
+  return (
+    <div className="flex flex-col h-screen bg-neutral-50 text-sm">
+      <div className="flex items-center gap-2 p-3 border-b border-neutral-200 bg-white">
+        <input
+          className="w-64 px-2 py-1 font-mono border border-neutral-300 rounded-md"
+          value={method}
+          onChange={(e) => setMethod(e.target.value)}
+          placeholder="method"
+          aria-label="Method"
+        />
+        <textarea
+          className="flex-1 h-9 px-2 py-1 font-mono border border-neutral-300 rounded-md resize-y"
+          value={params}
+          onChange={(e) => setParams(e.target.value)}
+          aria-label="Params (JSON)"
+        />
+        <Button size="sm" loading={sending} disabled={!method.trim()} onClick={send}>
+          Send
+        </Button>
+        <Button size="sm" variant="secondary" onClick={clear}>
+          Clear
+        </Button>
+      </div>
+      {error && <div className="px-3 py-2 text-error-600 bg-error-50">{error}</div>}
+
+      <div className="flex flex-1 min-h-0">
+        <ul className="w-2/5 overflow-y-auto border-r border-neutral-200 bg-white">
+          {entries.length === 0 && <li className="p-4 text-neutral-500">No requests yet</li>}
+          {entries.map((entry) => (
+            <li key={entry.id}>
+              <button
+                className={[
+                  "flex items-center w-full gap-2 px-3 py-1.5 text-left hover:bg-neutral-50",
+                  entry.id === selectedId ? "bg-primary-50" : "",
+                ].join(" ")}
+                onClick={() => setSelectedId(entry.id)}
+              >
+                <span
+                  className={`w-2 h-2 rounded-full ${entry.error ? "bg-error-500" : "bg-success-500"}`}
+                />
+                <span className="flex-1 truncate font-mono text-neutral-900">{entry.method}</span>
+                <span className="text-neutral-500">{entry.duration_ms} ms</span>
+              </button>
+            </li>
+          ))}
+        </ul>
+
+        <div className="flex-1 overflow-y-auto p-3">
+          {selected ? (
+            <div className="space-y-3">
+              <div className="flex items-center gap-2">
+                <span className="font-mono font-medium text-neutral-900">
+                  #{selected.id} {selected.method}
+                </span>
+                <span className="text-neutral-500">{selected.sent_at}</span>
+                <div className="flex-1" />
+                <Button
+                  size="sm"
+                  variant="secondary"
+                  onClick={() => {
+                    setMethod(selected.method);
+                    setParams(formatJson(selected.params));
+                  }}
+                >
+                  Edit
+                </Button>
+                <Button
+                  size="sm"
+                  loading={sending}
+                  onClick={() => void run(() => replayIpcCall(selected.id))}
+                >
+                  Replay
+                </Button>
+              </div>
+              <section>
+                <h2 className="mb-1 font-medium text-neutral-700">Params</h2>
+                <pre className="p-2 overflow-x-auto font-mono bg-white border border-neutral-200 rounded-md">
+                  {formatJson(selected.params)}
+                </pre>
+              </section>
+              <section>
+                <h2 className="mb-1 font-medium text-neutral-700">
+                  {selected.error ? "Error" : "Result"}
+                </h2>
+                <pre
+                  className={`p-2 overflow-x-auto font-mono bg-white border border-neutral-200 rounded-md ${
+                    selected.error ? "text-error-600" : ""
+                  }`}
+                >
+                  {selected.error ?? formatJson(selected.result)}
+                </pre>
+              </section>
+            </div>
+          ) : (
+            <div className="flex items-center justify-center h-full text-neutral-500">
+              Select a request to see its params and result
+            </div>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default IpcConsole;
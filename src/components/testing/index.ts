@@ -11,3 +11,6 @@ export { PluginTester } from './PluginTester';
 export { MethodInvoker } from './MethodInvoker';
 export { HealthDashboard } from './HealthDashboard';
 export { LogViewer } from './LogViewer';
+
+// Developer IPC console (debug builds)
+export { IpcConsole } from './IpcConsole';
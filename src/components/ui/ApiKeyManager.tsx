@@ -13,6 +13,8 @@ import {
     API_KEY_SERVICE_LABELS,
     type ApiKeyService,
     type ApiKeyEntry,
+    type CustomServiceInput,
+    type ServiceInfo,
 } from '../../stores/apiKeyStore';
 
 // ============================================
@@ -22,8 +24,17 @@ import {
 interface AddKeyModalProps {
     isOpen: boolean;
     service: ApiKeyService;
+    /** Catalog entry of the service (key hint and docs link) */
+    info: ServiceInfo | undefined;
     onClose: () => void;
     onAdd: (name: string, key: string) => Promise<void>;
+    /** Returns the key-format problem, or null if the key looks valid */
+    onValidate: (key: string) => Promise<string | null>;
+}
+
+interface CustomServiceFormProps {
+    onAdd: (service: CustomServiceInput) => Promise<void>;
+    onCancel: () => void;
 }
 
 interface EditKeyModalProps {
@@ -45,11 +56,12 @@ interface DeleteConfirmModalProps {
 // ADD KEY MODAL
 // ============================================
 
-const AddKeyModal: React.FC<AddKeyModalProps> = ({ isOpen, service, onClose, onAdd }) => {
+const AddKeyModal: React.FC<AddKeyModalProps> = ({ isOpen, service, info, onClose, onAdd, onValidate }) => {
     const [name, setName] = useState('');
     const [key, setKey] = useState('');
     const [isSubmitting, setIsSubmitting] = useState(false);
     const [error, setError] = useState('');
+    const [formatWarning, setFormatWarning] = useState<string | null>(null);
 
     // Reset form when modal opens
     useEffect(() => {
@@ -57,9 +69,29 @@ const AddKeyModal: React.FC<AddKeyModalProps> = ({ isOpen, service, onClose, onA
             setName('');
             setKey('');
             setError('');
+            setFormatWarning(null);
         }
     }, [isOpen]);
 
+    // Check the key format as it is typed
+    useEffect(() => {
+        const trimmed = key.trim();
+        if (!trimmed) {
+            setFormatWarning(null);
+            return;
+        }
+
+        let cancelled = false;
+        onValidate(trimmed).then((warning) => {
+            if (!cancelled) setFormatWarning(warning);
+        });
+        return () => {
+            cancelled = true;
+        };
+    }, [key, onValidate]);
+
+    const label = info?.name ?? API_KEY_SERVICE_LABELS[service] ?? service;
+
     const handleSubmit = async (e: React.FormEvent) => {
         e.preventDefault();
 
@@ -93,7 +125,7 @@ const AddKeyModal: React.FC<AddKeyModalProps> = ({ isOpen, service, onClose, onA
                 {/* Header */}
                 <div className="px-6 py-4 bg-neutral-50 border-b border-neutral-200">
                     <h3 className="text-lg font-semibold text-neutral-900">
-                        Add API Key - {API_KEY_SERVICE_LABELS[service]}
+                        Add API Key - {label}
                     </h3>
                 </div>
 
@@ -128,12 +160,25 @@ const AddKeyModal: React.FC<AddKeyModalProps> = ({ isOpen, service, onClose, onA
                             type="password"
                             value={key}
                             onChange={(e) => setKey(e.target.value)}
-                            placeholder="Paste your API key here"
+                            placeholder={info?.hint ? `Paste your API key (${info.hint})` : 'Paste your API key here'}
                             className="w-full px-3 py-2 border border-neutral-300 rounded-lg text-neutral-900 placeholder-neutral-400 focus:ring-2 focus:ring-primary-500 focus:border-primary-500 font-mono"
                         />
+                        {formatWarning && (
+                            <p className="mt-1 text-xs text-amber-600">{formatWarning}</p>
+                        )}
                         <p className="mt-1 text-xs text-neutral-500">
                             Your key will be stored securely and only the first 3 and last 3 characters will be displayed.
                         </p>
+                        {info?.docsUrl && (
+                            <a
+                                href={info.docsUrl}
+                                target="_blank"
+                                rel="noopener noreferrer"
+                                className="mt-1 inline-block text-xs text-primary-600 hover:underline"
+                            >
+                                Get a {label} key
+                            </a>
+                        )}
                     </div>
 
                     {/* Buttons */}
@@ -160,6 +205,94 @@ const AddKeyModal: React.FC<AddKeyModalProps> = ({ isOpen, service, onClose, onA
     );
 };
 
+// ============================================
+// CUSTOM SERVICE FORM
+// ============================================
+
+const CustomServiceForm: React.FC<CustomServiceFormProps> = ({ onAdd, onCancel }) => {
+    const [id, setId] = useState('');
+    const [name, setName] = useState('');
+    const [pattern, setPattern] = useState('');
+    const [isSubmitting, setIsSubmitting] = useState(false);
+    const [error, setError] = useState('');
+
+    const handleSubmit = async (e: React.FormEvent) => {
+        e.preventDefault();
+
+        if (!id.trim()) {
+            setError('Please enter a service id');
+            return;
+        }
+
+        setIsSubmitting(true);
+        setError('');
+
+        try {
+            const service: CustomServiceInput = { id: id.trim().toLowerCase() };
+            if (name.trim()) service.name = name.trim();
+            if (pattern.trim()) service.pattern = pattern.trim();
+            await onAdd(service);
+        } catch (err) {
+            setError(err instanceof Error ? err.message : 'Failed to add service');
+        } finally {
+            setIsSubmitting(false);
+        }
+    };
+
+    return (
+        <form onSubmit={handleSubmit} className="p-3 space-y-2 border border-neutral-200 rounded-lg bg-neutral-50">
+            {error && (
+                <div className="p-2 bg-red-50 border border-red-200 rounded text-xs text-red-700">
+                    {error}
+                </div>
+            )}
+            <div className="grid grid-cols-2 gap-2">
+                <input
+                    type="text"
+                    value={id}
+                    onChange={(e) => setId(e.target.value)}
+                    placeholder="Id (e.g., groq)"
+                    aria-label="Service id"
+                    className="px-3 py-1.5 text-sm border border-neutral-300 rounded-lg text-neutral-900 placeholder-neutral-400 focus:ring-2 focus:ring-primary-500 focus:border-primary-500"
+                />
+                <input
+                    type="text"
+                    value={name}
+                    onChange={(e) => setName(e.target.value)}
+                    placeholder="Name (optional)"
+                    aria-label="Service name"
+                    className="px-3 py-1.5 text-sm border border-neutral-300 rounded-lg text-neutral-900 placeholder-neutral-400 focus:ring-2 focus:ring-primary-500 focus:border-primary-500"
+                />
+            </div>
+            <input
+                type="text"
+                value={pattern}
+                onChange={(e) => setPattern(e.target.value)}
+                placeholder="Key format regex (optional, e.g., ^gsk_[A-Za-z0-9]+$)"
+                aria-label="Key format regex"
+                className="w-full px-3 py-1.5 text-sm border border-neutral-300 rounded-lg text-neutral-900 placeholder-neutral-400 focus:ring-2 focus:ring-primary-500 focus:border-primary-500 font-mono"
+            />
+            <div className="flex justify-end gap-2">
+                <button
+                    type="button"
+                    onClick={onCancel}
+                    disabled={isSubmitting}
+                    className="px-3 py-1 text-sm text-neutral-700 hover:bg-neutral-100 rounded-lg transition-colors"
+                >
+                    Cancel
+                </button>
+                <button
+                    type="submit"
+                    disabled={isSubmitting}
+                    className="px-3 py-1 text-sm bg-primary-500 text-white font-medium rounded-lg hover:bg-primary-600 disabled:opacity-50 transition-colors"
+                >
+                    {isSubmitting ? 'Adding...' : 'Add Service'}
+                </button>
+            </div>
+        </form>
+    );
+};
+
 // ============================================
 // EDIT KEY MODAL
 // ============================================
@@ -350,14 +483,19 @@ export const ApiKeyManager: React.FC = () => {
     const {
         keys,
         selectedService,
+        services,
         isLoading,
         error,
         setSelectedService,
         fetchKeys,
+        fetchServices,
         addKey,
         updateKey,
         deleteKey,
         setActiveKey,
+        addCustomService,
+        deleteCustomService,
+        validateKeyFormat,
         clearError,
     } = useApiKeyStore();
 
@@ -366,6 +504,12 @@ export const ApiKeyManager: React.FC = () => {
     const [editEntry, setEditEntry] = useState<ApiKeyEntry | null>(null);
     const [deleteEntry, setDeleteEntry] = useState<ApiKeyEntry | null>(null);
     const [isExpanded, setIsExpanded] = useState(true);
+    const [showCustomForm, setShowCustomForm] = useState(false);
+
+    // Fetch the service catalog once
+    useEffect(() => {
+        fetchServices();
+    }, [fetchServices]);
 
     // Fetch keys when component mounts or service changes
     useEffect(() => {
@@ -374,6 +518,13 @@ export const ApiKeyManager: React.FC = () => {
 
     // Get current service keys
     const serviceKeys = keys[selectedService] || [];
+    const serviceInfo = services.find(s => s.id === selectedService);
+    const serviceLabel = serviceInfo?.name ?? API_KEY_SERVICE_LABELS[selectedService] ?? selectedService;
+
+    // Catalog services, or the built-in labels outside the desktop app
+    const serviceOptions: [string, string][] = services.length > 0
+        ? services.map((s): [string, string] => [s.id, s.name])
+        : Object.entries(API_KEY_SERVICE_LABELS);
 
     // Handlers
     const handleServiceChange = useCallback((e: React.ChangeEvent<HTMLSelectElement>) => {
@@ -398,6 +549,17 @@ export const ApiKeyManager: React.FC = () => {
         await setActiveKey(selectedService, id);
     }, [setActiveKey, selectedService]);
 
+    const handleValidateKey = useCallback(
+        (key: string) => validateKeyFormat(selectedService, key),
+        [validateKeyFormat, selectedService]
+    );
+
+    const handleAddService = useCallback(async (service: CustomServiceInput) => {
+        const info = await addCustomService(service);
+        setShowCustomForm(false);
+        setSelectedService(info.id);
+    }, [addCustomService, setSelectedService]);
+
     return (
         <div className="border border-neutral-200 rounded-lg overflow-hidden">
             {/* Header */}
@@ -459,12 +621,40 @@ export const ApiKeyManager: React.FC = () => {
                             onChange={handleServiceChange}
                             className="w-full px-3 py-2 border border-neutral-300 rounded-lg bg-white text-neutral-900 focus:ring-2 focus:ring-primary-500 focus:border-primary-500"
                         >
-                            {Object.entries(API_KEY_SERVICE_LABELS).map(([id, label]) => (
+                            {serviceOptions.map(([id, label]) => (
                                 <option key={id} value={id}>{label}</option>
                             ))}
                         </select>
+                        <div className="flex justify-end gap-3 mt-1">
+                            {serviceInfo?.custom && (
+                                <button
+                                    type="button"
+                                    onClick={() => deleteCustomService(serviceInfo.id)}
+                                    className="text-xs text-neutral-500 hover:text-red-600"
+                                    title="Stored keys are kept"
+                                >
+                                    Remove custom service
+                                </button>
+                            )}
+                            {!showCustomForm && (
+                                <button
+                                    type="button"
+                                    onClick={() => setShowCustomForm(true)}
+                                    className="text-xs text-primary-600 hover:underline"
+                                >
+                                    + Custom service
+                                </button>
+                            )}
+                        </div>
                     </div>
 
+                    {showCustomForm && (
+                        <CustomServiceForm
+                            onAdd={handleAddService}
+                            onCancel={() => setShowCustomForm(false)}
+                        />
+                    )}
+
                     {/* Keys List */}
                     <div className="space-y-2">
                         {isLoading ? (
@@ -480,7 +670,7 @@ export const ApiKeyManager: React.FC = () => {
                                 <svg className="h-10 w-10 mx-auto mb-2 opacity-50" viewBox="0 0 24 24" fill="none" stroke="currentColor" strokeWidth="1.5">
                                     <path d="M21 2l-2 2m-7.61 7.61a5.5 5.5 0 1 1-7.778 7.778 5.5 5.5 0 0 1 7.777-7.777zm0 0L15.5 7.5m0 0l3 3L22 7l-3-3m-3.5 3.5L19 4" />
                                 </svg>
-                                <p>No API keys configured for {API_KEY_SERVICE_LABELS[selectedService]}</p>
+                                <p>No API keys configured for {serviceLabel}</p>
                                 <p className="text-xs mt-1">Add a key to get started</p>
                             </div>
                         ) : (
@@ -566,8 +756,10 @@ export const ApiKeyManager: React.FC = () => {
             <AddKeyModal
                 isOpen={showAddModal}
                 service={selectedService}
+                info={serviceInfo}
                 onClose={() => setShowAddModal(false)}
                 onAdd={handleAddKey}
+                onValidate={handleValidateKey}
             />
             <EditKeyModal
                 isOpen={!!editEntry}
@@ -0,0 +1,142 @@
+/**
+ * src/components/ui/StartupSplash.tsx
+ * ===================================
+ * Full-screen splash shown while the backend boots (project root, Python,
+ * plugin host, plugin scan).
+ *
+ * Architecture: Plugin Option C (Tauri + React + Python subprocess via stdio IPC)
+ * Dependencies: D006, D007, D010 (Button.tsx), startupService.ts
+ *
+ * Rules:
+ *   - NO hardcoded colors, spacing, or sizes
+ *   - ALL styling via Tailwind classes referencing design tokens
+ *
+ * The splash hides itself once the plugin scan completes. If a stage fails
+ * it stays up with the error until dismissed; the editor still works
+ * without plugins. Outside the desktop app it never renders.
+ */
+
+import React, { useEffect, useState } from "react";
+import { Button } from "./Button";
+import {
+  onStartupProgress,
+  STARTUP_STAGE_LABELS,
+  type StartupStage,
+  type StartupStatus,
+} from "../../services/startupService";
+import { isTauri } from "../../utils/tauriUtils";
+
+const STAGES = Object.keys(STARTUP_STAGE_LABELS) as StartupStage[];
+
+/**
+ * StartupSplash component.
+ *
+ * @example
+ * ```tsx
+ * <ThemeProvider>
+ *   <StartupSplash />
+ *   <App />
+ * </ThemeProvider>
+ * ```
+ */
+export const StartupSplash: React.FC = () => {
+  const [status, setStatus] = useState<StartupStatus | null>(null);
+  const [dismissed, setDismissed] = useState(false);
+
+  useEffect(() => {
+    if (!isTauri()) {
+      return;
+    }
+    let unlisten: (() => void) | undefined;
+    let cancelled = false;
+    void onStartupProgress(setStatus).then((stop) => {
+      if (cancelled) {
+        stop();
+      } else {
+        unlisten = stop;
+      }
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, []);
+
+  if (!isTauri() || dismissed || (status?.complete && !status.failed)) {
+    return null;
+  }
+
+  const stepFor = (stage: StartupStage) => status?.steps.find((s) => s.stage === stage);
+  const done = status?.steps.filter((s) => s.status === "done").length ?? 0;
+  const total = status?.total_stages ?? STAGES.length;
+
+  return (
+    <div
+      className="fixed inset-0 z-50 flex items-center justify-center bg-neutral-100"
+      role="status"
+      aria-live="polite"
+    >
+      <div className="w-full max-w-md p-8 bg-white rounded-2xl shadow-lg">
+        <h1 className="text-2xl font-bold text-neutral-900 text-center">App Factory</h1>
+        <p className="text-neutral-600 text-center mt-1 mb-6">
+          {status?.failed ? "Startup ran into a problem" : "Starting up..."}
+        </p>
+
+        <div className="h-2 mb-6 rounded-full bg-neutral-200 overflow-hidden">
+          <div
+            className={`h-full transition-all ${status?.failed ? "bg-error-500" : "bg-primary-500"}`}
+            style={{ width: `${(done / total) * 100}%` }}
+          />
+        </div>
+
+        <ul className="space-y-2">
+          {STAGES.map((stage) => {
+            const step = stepFor(stage);
+            return (
+              <li key={stage} className="flex items-start gap-3 text-sm">
+                {step?.status === "started" ? (
+                  <div className="mt-0.5 w-4 h-4 border-2 border-neutral-300 border-t-primary-500 rounded-full animate-spin" />
+                ) : (
+                  <div
+                    className={`mt-1 w-2.5 h-2.5 mx-[3px] rounded-full ${
+                      step?.status === "done"
+                        ? "bg-success-500"
+                        : step?.status === "failed"
+                          ? "bg-error-500"
+                          : "bg-neutral-300"
+                    }`}
+                  />
+                )}
+                <div className="min-w-0">
+                  <div className={step ? "text-neutral-900" : "text-neutral-400"}>
+                    {STARTUP_STAGE_LABELS[stage]}
+                  </div>
+                  {step?.message && (
+                    <div
+                      className={`truncate ${
+                        step.status === "failed" ? "text-error-600" : "text-neutral-500"
+                      }`}
+                      title={step.message}
+                    >
+                      {step.message}
+                    </div>
+                  )}
+                </div>
+              </li>
+            );
+          })}
+        </ul>
+
+        {status?.failed && (
+          <div className="mt-6 flex justify-end">
+            <Button variant="secondary" onClick={() => setDismissed(true)}>
+              Continue without plugins
+            </Button>
+          </div>
+        )}
+      </div>
+    </div>
+  );
+};
+
+export default StartupSplash;
@@ -47,3 +47,6 @@ export type { WindowConfigPanelProps, WindowConfig } from "./WindowConfigPanel";
 // Tabs (UJ-1.1.3)
 export { Tabs, default as TabsComponent } from "./Tabs";
 export type { TabsProps, TabItem } from "./Tabs";
+
+// Startup splash
+export { StartupSplash, default as StartupSplashComponent } from "./StartupSplash";
@@ -13,7 +13,7 @@
  *   - Fully typed props with TypeScript
  */
 
-import React, { useState, useCallback, useMemo } from "react";
+import React, { useState, useCallback, useMemo, useEffect } from "react";
 import { Button } from "../ui/Button";
 import { Input } from "../ui/Input";
 import { Modal } from "../ui/Modal";
@@ -69,6 +69,8 @@ export interface ImportWizardProps {
   onClose: () => void;
   /** Callback when import is complete */
   onImport?: (manifest: ComponentManifest, sourceUrl: string) => Promise<void>;
+  /** URL to prefill when the wizard opens (e.g. from an appfactory:// link) */
+  initialUrl?: string;
   /** Custom className */
   className?: string;
 }
@@ -340,6 +342,7 @@ export const ImportWizard: React.FC<ImportWizardProps> = ({
   isOpen,
   onClose,
   onImport,
+  initialUrl,
   className = "",
 }) => {
   // Wizard state
@@ -371,6 +374,14 @@ export const ImportWizard: React.FC<ImportWizardProps> = ({
     }
   }, []);
 
+  // Prefill the source from a link
+  useEffect(() => {
+    if (isOpen && initialUrl) {
+      setSourceType(initialUrl.includes("github.com") ? "github" : "url");
+      handleUrlChange(initialUrl);
+    }
+  }, [isOpen, initialUrl, handleUrlChange]);
+
   // Reset wizard
   const resetWizard = useCallback(() => {
     setCurrentStep("source");
@@ -99,6 +99,12 @@ export interface IpcCallOptions {
   showLoading?: boolean;
   /** Loading message */
   loadingMessage?: string;
+  /**
+   * Key identifying the request across retries: while a call with the same
+   * key, method, and params is in flight, a retry shares its result instead
+   * of running the method again
+   */
+  idempotencyKey?: string;
 }
 
 /**
@@ -126,16 +132,33 @@ export interface BatchResult {
   };
 }
 
+/**
+ * Command error category: talking to the plugin host failed (`transport`),
+ * a plugin reported it (`plugin`), the request or configuration needs
+ * fixing (`user`), or the app itself failed (`internal`).
+ */
+export type CommandErrorCategory = "transport" | "plugin" | "user" | "internal";
+
 /**
  * Command error from Rust backend.
  */
 export interface CommandError {
   /** Error code */
   code: string;
-  /** Error message */
+  /** Error message (English) */
   message: string;
+  /** Error message in the locale set with `set_locale` (`message` if untranslated) */
+  localized_message: string;
   /** Additional details */
   details?: Record<string, unknown>;
+  /** What the error is about, for generic handling (from the error catalog) */
+  category: CommandErrorCategory;
+  /** Whether the same call may succeed when retried (from the error catalog) */
+  retryable: boolean;
+  /** Underlying errors, outermost first */
+  source_chain?: string[];
+  /** Page documenting the error, if the catalog has one */
+  docs_url?: string;
 }
 
 /**
@@ -259,7 +282,8 @@ function errorToString(error: unknown): string {
   if (typeof error === "string") return error;
   if (error && typeof error === "object") {
     const err = error as CommandError;
-    if (err.message) return `[${err.code || "ERROR"}] ${err.message}`;
+    const message = err.localized_message || err.message;
+    if (message) return `[${err.code || "ERROR"}] ${message}`;
   }
   return String(error);
 }
@@ -389,7 +413,7 @@ export function useIpc(): UseIpcReturn {
       if (!isTauri()) {
         throw new Error('IPC call unavailable: Not running in Tauri environment');
       }
-      const { showLoading = false, loadingMessage = "Processing..." } = options;
+      const { showLoading = false, loadingMessage = "Processing...", idempotencyKey } = options;
 
       try {
         if (showLoading) {
@@ -400,6 +424,7 @@ export function useIpc(): UseIpcReturn {
         const result = await invoke<T>("ipc_call", {
           method,
           params,
+          idempotencyKey,
         });
 
         return result;
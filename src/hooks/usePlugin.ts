@@ -250,6 +250,27 @@ function generateId(): string {
   return `${Date.now()}-${Math.random().toString(36).substring(2, 9)}`;
 }
 
+/**
+ * Whether a command failed because a plugin is not signed by a trusted
+ * publisher (`UNSIGNED_PLUGIN`).
+ */
+function isUnsignedPluginError(err: unknown): boolean {
+  return (err as { code?: unknown } | null)?.code === "UNSIGNED_PLUGIN";
+}
+
+/**
+ * Add a plugin to the `plugins.allow_unsigned` setting.
+ */
+async function allowUnsignedPlugin(pluginId: string): Promise<void> {
+  const allowed = await invoke<string[] | null>("settings_get", {
+    key: "plugins.allow_unsigned",
+  });
+  await invoke("settings_set", {
+    key: "plugins.allow_unsigned",
+    value: [...(allowed ?? []).filter((name) => name !== pluginId), pluginId],
+  });
+}
+
 // ============================================
 // HOOK IMPLEMENTATION
 // ============================================
@@ -495,6 +516,19 @@ export function usePlugin(): UsePluginReturn {
 
         const result = await invoke<Record<string, unknown>>("plugin_load", {
           name: pluginId,
+        }).catch(async (err: unknown) => {
+          // Plugins without a trusted signature only run with the user's consent
+          const allow =
+            isUnsignedPluginError(err) &&
+            window.confirm(
+              `${pluginId} is not signed by a trusted publisher. ` +
+              "Only load it if you trust where it came from. Load it anyway?"
+            );
+          if (!allow) {
+            throw err;
+          }
+          await allowUnsignedPlugin(pluginId);
+          return invoke<Record<string, unknown>>("plugin_load", { name: pluginId });
         });
 
         const methods = parseMethods((result.methods as unknown[]) || []);
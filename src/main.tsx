@@ -10,6 +10,7 @@
 import React from 'react';
 import ReactDOM from 'react-dom/client';
 import { App } from './App';
+import { IpcConsole } from './components/testing/IpcConsole';
 import './index.css';
 
 // SAFETY: Import Mock Layer ONLY if strict Tauri (window.__TAURI__) is missing.
@@ -26,9 +27,12 @@ if (!rootElement) {
   throw new Error('Root element not found. Make sure index.html has a div with id="root".');
 }
 
+// The developer IPC console window loads the same bundle with its own route
+const isIpcConsole = window.location.hash === '#/ipc-console';
+
 // Create React root and render app
 ReactDOM.createRoot(rootElement).render(
   <React.StrictMode>
-    <App />
+    {isIpcConsole ? <IpcConsole /> : <App />}
   </React.StrictMode>
 );
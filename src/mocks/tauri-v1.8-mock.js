@@ -26,8 +26,7 @@ const mockStore = {
     components: [],
     lifecycle: "running",
     // D079 - API Key storage for mock
-    apiKeys: {},        // { service: [ApiKeyEntry, ...] }
-    apiKeyValues: {}    // { "service-id": "actual-key-value" }
+    apiKeys: {}         // { service: [ApiKeyEntry, ...] }
 };
 
 /**
@@ -153,8 +152,6 @@ window.__TAURI__.invoke = async (cmd, args = {}) => {
             // Set all other keys to inactive
             mockStore.apiKeys[args.service].forEach(k => k.is_active = false);
             mockStore.apiKeys[args.service].push(newKey);
-            // Store the actual key for get_active_api_key_value
-            mockStore.apiKeyValues[`${args.service}-${newKey.id}`] = args.key;
             console.log(`[Tauri Mock] Added API key for ${args.service}`);
             return newKey;
 
@@ -163,15 +160,6 @@ window.__TAURI__.invoke = async (cmd, args = {}) => {
             const serviceKeys = mockStore.apiKeys[args.service] || [];
             return serviceKeys.find(k => k.is_active) || null;
 
-        case "get_active_api_key_value":
-            // Return the actual API key value for making API calls
-            const keys = mockStore.apiKeys[args.service] || [];
-            const activeKey = keys.find(k => k.is_active);
-            if (activeKey) {
-                return mockStore.apiKeyValues[`${args.service}-${activeKey.id}`] || null;
-            }
-            return null;
-
         case "set_active_api_key":
             // Set a key as active
             const allKeys = mockStore.apiKeys[args.service] || [];
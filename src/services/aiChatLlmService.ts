@@ -8,11 +8,12 @@
  * multiple independent LLM configurations (Backend/Frontend/Full scopes).
  */
 
-import { GoogleGenAI } from '@google/genai';
-import { useApiKeyStore, type ApiKeyService } from '../stores/apiKeyStore';
-import { generateWithOllama } from './ollamaService';
-import type { LLMResult } from './llmService';
-import Anthropic from '@anthropic-ai/sdk';
+import {
+    generateWithProxy,
+    streamWithProxy,
+    type LLMResult,
+    type ProxyMessage,
+} from './llmService';
 
 /**
  * Supported LLM providers for AI Chat.
@@ -49,150 +50,25 @@ export interface ChatMessage {
 }
 
 /**
- * Get API key value for a provider from the apiKeyStore.
+ * Build the proxy conversation: system prompt, prior user/assistant turns,
+ * then the current prompt with its images.
  */
-async function getApiKeyValue(provider: AiChatProvider): Promise<string | null> {
-    if (provider === 'ollama') {
-        return null; // Ollama doesn't need API key
-    }
-    const store = useApiKeyStore.getState();
-    return store.getActiveKeyValue(provider as ApiKeyService);
-}
-
-/**
- * Generate text using Google Gemini with specific config.
- */
-async function generateWithGeminiConfig(
-    apiKey: string,
-    config: LLMInstanceConfig,
-    prompt: string,
-    conversationHistory?: ChatMessage[],
-    images?: string[]
-): Promise<LLMResult> {
-    const ai = new GoogleGenAI({ apiKey });
-
-    // Helper to strip data URI header from base64
-    const stripBase64Header = (img: string) => img.replace(/^data:image\/\w+;base64,/, '');
-
-    // Build current user message parts
-    const currentParts: Array<{ text: string } | { inlineData: { mimeType: string; data: string } }> = [
-        { text: prompt }
-    ];
-
-    // Add images if provided (for multimodal Vision models)
-    if (images && images.length > 0) {
-        images.forEach(img => {
-            currentParts.push({
-                inlineData: {
-                    mimeType: 'image/png',
-                    data: stripBase64Header(img)
-                }
-            });
-        });
-    }
-
-    // Build conversation contents if history provided
-    type ContentPart = { text: string } | { inlineData: { mimeType: string; data: string } };
-    type ContentMessage = { role: string; parts: ContentPart[] };
-    let contents: string | ContentMessage[];
-
-    if (conversationHistory && conversationHistory.length > 0) {
-        contents = conversationHistory.map(msg => ({
-            role: msg.role === 'assistant' ? 'model' : msg.role,
-            parts: [{ text: msg.content }]
-        }));
-        // Add current user message with text and images
-        contents.push({
-            role: 'user',
-            parts: currentParts
-        });
-    } else {
-        // Single message with potential images
-        contents = [{
-            role: 'user',
-            parts: currentParts
-        }];
-    }
-
-    const response = await ai.models.generateContent({
-        model: config.model,
-        contents,
-        config: {
-            systemInstruction: config.systemPrompt,
-            temperature: config.temperature,
-        },
-    });
-
-    return { success: true, text: response.text };
-}
-
-/**
- * Generate text using Anthropic with specific config.
- */
-async function generateWithAnthropicConfig(
-    apiKey: string,
+function buildProxyMessages(
     config: LLMInstanceConfig,
     prompt: string,
     conversationHistory?: ChatMessage[],
     images?: string[]
-): Promise<LLMResult> {
-    const anthropic = new Anthropic({ apiKey, dangerouslyAllowBrowser: true });
-
-    const messages: Anthropic.MessageParam[] = [];
-
-    // Add history
-    if (conversationHistory && conversationHistory.length > 0) {
-        conversationHistory.forEach(msg => {
-            if (msg.role === 'user' || msg.role === 'assistant') {
-                messages.push({
-                    role: msg.role,
-                    content: msg.content
-                });
-            }
-        });
-    }
-
-    // Prepare current message content
-    const currentContent: Anthropic.ContentBlockParam[] = [];
-
-    // Add images if present
-    if (images && images.length > 0) {
-        images.forEach(img => {
-            // Strip header if present
-            const base64Data = img.replace(/^data:image\/\w+;base64,/, '');
-            currentContent.push({
-                type: 'image',
-                source: {
-                    type: 'base64',
-                    media_type: 'image/png', // Assuming PNG from html2canvas
-                    data: base64Data
-                }
-            });
-        });
-    }
-
-    // Add text prompt
-    currentContent.push({ type: 'text', text: prompt });
+): ProxyMessage[] {
+    const messages: ProxyMessage[] = [{ role: 'system', content: config.systemPrompt }];
 
-    messages.push({
-        role: 'user',
-        content: currentContent
-    });
-
-    const response = await anthropic.messages.create({
-        model: config.model || 'claude-3-opus-20240229',
-        max_tokens: 4096,
-        messages,
-        system: config.systemPrompt,
-        temperature: config.temperature,
+    conversationHistory?.forEach(msg => {
+        if (msg.role === 'user' || msg.role === 'assistant') {
+            messages.push({ role: msg.role, content: msg.content });
+        }
     });
 
-    // Extract text from response
-    const textContent = response.content.find(c => c.type === 'text');
-    return {
-        success: true,
-        text: textContent?.type === 'text' ? textContent.text : ''
-    };
+    messages.push({ role: 'user', content: prompt, images });
+    return messages;
 }
 
 /**
@@ -202,57 +78,28 @@ async function generateWithAnthropicConfig(
  * @param config - The LLM instance configuration
  * @param prompt - User prompt to send
  * @param conversationHistory - Optional conversation history for context
+ * @param images - Optional images for the current prompt
+ * @param onToken - Optional callback; when given, the reply is streamed
  * @returns LLMResult with success status and text or error
  */
 export async function generateWithConfig(
     config: LLMInstanceConfig,
     prompt: string,
     conversationHistory?: ChatMessage[],
-    images?: string[]
+    images?: string[],
+    onToken?: (text: string) => void
 ): Promise<LLMResult> {
-    // Handle Ollama separately (no API key needed)
-    if (config.provider === 'ollama') {
-        try {
-            return await generateWithOllama(
-                config.model,
-                config.temperature,
-                config.systemPrompt,
-                prompt,
-                images
-            );
-        } catch (err) {
-            const message = err instanceof Error ? err.message : 'Unknown Ollama error';
-            return { success: false, error: message };
-        }
+    // Every provider goes through the Rust proxy, which holds the API keys
+    // (Ollama needs none and is reached at the project's OLLAMA_HOST)
+    const messages = buildProxyMessages(config, prompt, conversationHistory, images);
+    const options = { temperature: config.temperature };
+
+    if (onToken) {
+        const stream = await streamWithProxy(config.provider, config.model, messages, onToken, options);
+        return stream.result;
     }
 
-    // Get API key for the provider
-    const apiKey = await getApiKeyValue(config.provider);
-
-    if (!apiKey) {
-        return {
-            success: false,
-            error: `No API key configured for ${config.provider}. Please add one in AI Chat Settings.`
-        };
-    }
-
-    try {
-        if (config.provider === 'gemini') {
-            return await generateWithGeminiConfig(apiKey, config, prompt, conversationHistory, images);
-        } else if (config.provider === 'anthropic') {
-            return await generateWithAnthropicConfig(apiKey, config, prompt, conversationHistory, images);
-        } else {
-            // Placeholder for OpenAI
-            // TODO: Implement when needed
-            return {
-                success: false,
-                error: `Provider "${config.provider}" is not yet implemented.`
-            };
-        }
-    } catch (err) {
-        const message = err instanceof Error ? err.message : 'Unknown error occurred';
-        return { success: false, error: message };
-    }
+    return generateWithProxy(config.provider, config.model, messages, options);
 }
 
 /**
@@ -16,10 +16,41 @@ import { isTauri } from '../utils/tauriUtils';
 // TYPES
 // =============================================================================
 
+/** A compiler error located in the source (1-based lines and columns). */
+export interface Diagnostic {
+    message: string;
+    line: number;
+    column: number;
+    end_line: number;
+    end_column: number;
+    /** Code frame with the offending span underlined */
+    snippet: string;
+}
+
 export interface CompileResult {
     success: boolean;
     code: string | null;
     error: string | null;
+    /** Located errors (backend TSX compilation only) */
+    diagnostics?: Diagnostic[];
+    /** Set when a resource limit was hit (INPUT_TOO_LARGE, NESTING_TOO_DEEP, COMPILE_TIMEOUT, COMPILER_CRASHED) */
+    error_code?: string | null;
+    /** Type errors when `typecheck` was requested (null if not checked) */
+    type_errors?: Diagnostic[] | null;
+}
+
+/** Code generation options (backend only; ignored by the Sucrase fallback). */
+export interface CompileOptions {
+    minify?: boolean;
+    target?: 'es5' | 'es2015' | 'es2016' | 'es2017' | 'es2018' | 'es2019' | 'es2020' | 'es2021' | 'es2022' | 'esnext';
+    ascii_only?: boolean;
+    keep_comments?: boolean;
+    /** Rewrite bare imports (e.g. `react`) to CDN URLs */
+    cdn?: 'esm.sh' | 'unpkg';
+    /** Bare specifier -> URL (keys ending in `/` map prefixes); overrides `cdn` */
+    import_map?: Record<string, string>;
+    /** Also type-check with tsc; errors are returned in `type_errors` */
+    typecheck?: boolean;
 }
 
 // =============================================================================
@@ -33,6 +64,7 @@ export interface CompileResult {
  * Falls back to Sucrase in browser-only mode (e.g., during development).
  * 
  * @param code - Raw TSX/TypeScript code to compile
+ * @param options - Optional code generation options (minify, target, ...)
  * @returns Promise<CompileResult> - Compiled JavaScript or error
  * 
  * @example
@@ -45,7 +77,7 @@ export interface CompileResult {
  * }
  * ```
  */
-export async function compileTsx(code: string): Promise<CompileResult> {
+export async function compileTsx(code: string, options?: CompileOptions): Promise<CompileResult> {
     if (!isTauri()) {
         // Fallback for browser-only development
         console.log('[compilerService] Browser mode: Using Sucrase fallback');
@@ -54,7 +86,7 @@ export async function compileTsx(code: string): Promise<CompileResult> {
 
     try {
         console.log('[compilerService] Tauri mode: Using backend SWC compiler. Input length:', code.length);
-        const result = await invoke<CompileResult>('compile_tsx', { code });
+        const result = await invoke<CompileResult>('compile_tsx', { code, options });
         console.log('[compilerService] Backend compilation success:', result.success, 'Error:', result.error);
         return result;
     } catch (err) {
@@ -69,6 +101,125 @@ export async function compileTsx(code: string): Promise<CompileResult> {
     }
 }
 
+/**
+ * Compile many TSX/TypeScript sources in one call (compiled in parallel on the backend).
+ *
+ * @param sources - Raw TSX/TypeScript sources
+ * @param options - Code generation options applied to every source
+ * @returns Promise<CompileResult[]> - One result per source, in input order
+ */
+export async function compileTsxBatch(sources: string[], options?: CompileOptions): Promise<CompileResult[]> {
+    if (!isTauri()) {
+        console.log('[compilerService] Browser mode: Using Sucrase fallback for batch');
+        return Promise.all(sources.map((code) => compileTsxWithSucrase(code)));
+    }
+
+    try {
+        return await invoke<CompileResult[]>('compile_tsx_batch', { sources, options });
+    } catch (err) {
+        const errorMessage = err instanceof Error ? err.message : String(err);
+        console.error('[compilerService] Backend batch compilation failed:', errorMessage);
+        return sources.map(() => ({
+            success: false,
+            code: null,
+            error: `Backend compilation error: ${errorMessage}`,
+        }));
+    }
+}
+
+/**
+ * Compile an MDX document (Markdown with JSX) to a component module (backend only).
+ * The module's default export renders the document.
+ *
+ * @param code - MDX source
+ * @param options - Code generation options
+ * @returns Promise<CompileResult> - Compiled JavaScript or error
+ */
+export async function compileMdx(code: string, options?: CompileOptions): Promise<CompileResult> {
+    if (!isTauri()) {
+        return { success: false, code: null, error: 'MDX compilation requires the Tauri backend' };
+    }
+
+    try {
+        return await invoke<CompileResult>('compile_mdx', { code, options });
+    } catch (err) {
+        const errorMessage = err instanceof Error ? err.message : String(err);
+        console.error('[compilerService] MDX compilation failed:', errorMessage);
+        return {
+            success: false,
+            code: null,
+            error: `Backend compilation error: ${errorMessage}`,
+        };
+    }
+}
+
+/** A component prop extracted from TSX. */
+export interface PropInfo {
+    name: string;
+    type_annotation: string | null;
+    optional: boolean;
+    /** Default value when it is a literal */
+    default_value: unknown;
+    /** Source text of any default expression */
+    default_expression: string | null;
+}
+
+/** A component found by `analyzeComponent`. */
+export interface ComponentInfo {
+    name: string;
+    exported: boolean;
+    default_export: boolean;
+    props_type: string | null;
+    props: PropInfo[];
+}
+
+/**
+ * Extract component names, props and defaults from TSX (backend only).
+ *
+ * @param code - TSX source of one or more components
+ * @returns Promise<ComponentInfo[]> - Exported components (all if nothing is exported)
+ */
+export async function analyzeComponent(code: string): Promise<ComponentInfo[]> {
+    if (!isTauri()) {
+        console.log('[compilerService] Browser mode: Component analysis unavailable');
+        return [];
+    }
+    return invoke<ComponentInfo[]>('analyze_component', { code });
+}
+
+/** CSS compilation options. */
+export interface CssOptions {
+    syntax?: 'css' | 'scss';
+    /** Add vendor prefixes (default true) */
+    autoprefix?: boolean;
+    minify?: boolean;
+}
+
+/**
+ * Compile CSS or SCSS (backend only).
+ *
+ * @param code - CSS/SCSS source
+ * @param options - Syntax, autoprefixing and minification
+ * @returns Promise<CompileResult> - Compiled CSS or error
+ */
+export async function compileCss(code: string, options?: CssOptions): Promise<CompileResult> {
+    if (!isTauri()) {
+        return { success: false, code: null, error: 'CSS compilation requires the Tauri backend' };
+    }
+
+    try {
+        return await invoke<CompileResult>('compile_css', { code, options });
+    } catch (err) {
+        const errorMessage = err instanceof Error ? err.message : String(err);
+        console.error('[compilerService] CSS compilation failed:', errorMessage);
+        return {
+            success: false,
+            code: null,
+            error: `Backend compilation error: ${errorMessage}`,
+        };
+    }
+}
+
 /**
  * Fallback compiler using Sucrase for browser-only development.
  * This is used when the app is not running in Tauri.
@@ -0,0 +1,74 @@
+/**
+ * healthHistoryService.ts
+ * =======================
+ * Service for the plugin host health history kept across restarts
+ * (health_history command), e.g. "Crashed 3 times in the last 7 days".
+ */
+
+import { safeInvoke } from '../utils/tauriUtils';
+
+/**
+ * Kind of a stored health event.
+ */
+export type HealthEventKind = 'state_change' | 'failure_burst' | 'respawn' | 'crash';
+
+/**
+ * A stored health event. Failure bursts cover consecutive failed checks.
+ */
+export interface HealthRecord {
+    id: number;
+    kind: HealthEventKind;
+    detail: string;
+    count: number;
+    started_at: string;
+    ended_at: string;
+}
+
+/**
+ * Health history of the last few days.
+ */
+export interface HealthSummary {
+    days: number;
+    since: string;
+    crashes: number;
+    respawns: number;
+    degraded: number;
+    failure_bursts: number;
+    failed_checks: number;
+    events: HealthRecord[];
+}
+
+/**
+ * Get the health history.
+ * @param days - Days to cover (defaults to 7)
+ * @returns Summary, or null outside the desktop app or on error.
+ */
+export async function getHealthHistory(days = 7): Promise<HealthSummary | null> {
+    try {
+        return (await safeInvoke<HealthSummary>('health_history', { days })) ?? null;
+    } catch (error) {
+        console.error('Failed to get health history:', error);
+        return null;
+    }
+}
+
+/**
+ * Describe a summary in one line, e.g. "Crashed 3 times in the last 7 days".
+ * @param summary - Health history summary
+ * @returns Description, or null when nothing went wrong.
+ */
+export function describeHealthHistory(summary: HealthSummary): string | null {
+    const times = (n: number) => (n === 1 ? 'once' : `${n} times`);
+    const parts: string[] = [];
+    if (summary.crashes > 0) {
+        parts.push(`crashed ${times(summary.crashes)}`);
+    }
+    if (summary.degraded > 0) {
+        parts.push(`degraded ${times(summary.degraded)}`);
+    }
+    if (parts.length === 0) {
+        return null;
+    }
+    const text = `${parts.join(', ')} in the last ${summary.days} days`;
+    return text.charAt(0).toUpperCase() + text.slice(1);
+}
@@ -0,0 +1,102 @@
+/**
+ * ipcConsoleService.ts
+ * ====================
+ * Service for the developer IPC console (devtools_* commands and
+ * devtools/ipc events): recent plugin host requests, hand-written calls,
+ * replays, and types generated from the method schemas. Only available in
+ * debug builds.
+ */
+
+import { isTauri, safeInvoke } from '../utils/tauriUtils';
+
+/**
+ * A request sent to the core plugin host and its outcome.
+ */
+export interface TrafficEntry {
+    id: number;
+    method: string;
+    params: unknown;
+    result: unknown;
+    error: string | null;
+    sent_at: string;
+    duration_ms: number;
+}
+
+/**
+ * Open the IPC console window (or focus it).
+ */
+export async function openIpcConsole(): Promise<void> {
+    await safeInvoke('devtools_open_ipc_console');
+}
+
+/**
+ * Get recorded requests, most recent first.
+ * @param limit - Maximum number of entries
+ * @returns Entries, or an empty list outside the desktop app or on error.
+ */
+export async function getIpcHistory(limit?: number): Promise<TrafficEntry[]> {
+    try {
+        return (await safeInvoke<TrafficEntry[]>('devtools_ipc_history', { limit })) ?? [];
+    } catch (error) {
+        console.error('Failed to get IPC history:', error);
+        return [];
+    }
+}
+
+/**
+ * Forget the recorded requests.
+ */
+export async function clearIpcHistory(): Promise<void> {
+    await safeInvoke('devtools_ipc_clear');
+}
+
+/**
+ * Send a JSON-RPC call to the core plugin host.
+ * @param method - JSON-RPC method
+ * @param params - Method parameters
+ * @returns The call result (throws the command error on failure).
+ */
+export async function sendIpcCall(method: string, params: unknown): Promise<unknown> {
+    return safeInvoke<unknown>('devtools_ipc_send', { method, params });
+}
+
+/**
+ * Send a recorded request again.
+ * @param id - JSON-RPC id of the recorded request
+ * @returns The call result (throws the command error on failure).
+ */
+export async function replayIpcCall(id: number): Promise<unknown> {
+    return safeInvoke<unknown>('devtools_ipc_replay', { id });
+}
+
+/**
+ * Sources generated from the IPC method schemas.
+ */
+export interface GeneratedIpcTypes {
+    typescript: string;
+    rust: string;
+}
+
+/**
+ * Generate TypeScript and Rust types for the params and results of every
+ * method with a schema.
+ * @param outDir - Directory to write ipcMethods.generated.ts and ipc_methods.rs to
+ * @returns The generated sources (throws the command error on failure).
+ */
+export async function generateIpcTypes(outDir?: string): Promise<GeneratedIpcTypes | undefined> {
+    return safeInvoke<GeneratedIpcTypes>('devtools_generate_ipc_types', { outDir });
+}
+
+/**
+ * Follow requests as they finish.
+ * @param onEntry - Called with each finished request
+ * @returns Function that stops listening
+ */
+export async function onIpcTraffic(onEntry: (entry: TrafficEntry) => void): Promise<() => void> {
+    if (!isTauri()) {
+        return () => undefined;
+    }
+
+    const { listen } = await import('@tauri-apps/api/event');
+    return listen<TrafficEntry>('devtools/ipc', (event) => onEntry(event.payload));
+}
@@ -1,16 +1,15 @@
 /**
  * llmService.ts
  * =============
- * Service for LLM API calls using the @google/genai SDK.
- * Supports Gemini 3 Flash and other Google Gemini models.
+ * Service for LLM API calls through the Rust LLM proxy (`llm_generate`).
+ * Supports Gemini, OpenAI, Anthropic, and local Ollama models.
  * 
- * Updated for D079 (API Key Management) - now fetches API keys from .env via Tauri IPC.
+ * Updated for D079 (API Key Management) - API keys are resolved from .env in the
+ * backend and never reach the webview.
  */
 
-import { GoogleGenAI } from '@google/genai';
 import { useSettingsStore } from '../stores/settingsStore';
-import { useApiKeyStore } from '../stores/apiKeyStore';
-import { generateWithOllama } from './ollamaService';
+import { isTauri, safeInvoke } from '../utils/tauriUtils';
 
 /**
  * Result of an LLM generation call.
@@ -21,6 +20,73 @@ export interface LLMResult {
     error?: string | undefined;
 }
 
+/**
+ * Conversation message sent to the LLM proxy.
+ */
+export interface ProxyMessage {
+    role: 'system' | 'user' | 'assistant';
+    content: string;
+    /** Base64 encoded images (raw or data URI) */
+    images?: string[] | undefined;
+}
+
+/**
+ * Generation options for the LLM proxy.
+ */
+export interface ProxyOptions {
+    temperature?: number;
+    max_tokens?: number;
+    /** Key profile to use (default: the active profile) */
+    profile?: string;
+}
+
+/**
+ * Response from the `llm_generate` command.
+ */
+interface ProxyResponse {
+    text: string;
+    finish_reason: string | null;
+    usage: { input_tokens: number; output_tokens: number; total_tokens: number } | null;
+}
+
+/**
+ * Outcome of a proxy stream (payload of the `llm/<id>/finished` event).
+ */
+type ProxyStreamEnd =
+    | { type: 'completed'; response: ProxyResponse }
+    | { type: 'failed'; error: string; text: string }
+    | { type: 'cancelled'; text: string };
+
+/**
+ * A running proxy stream.
+ */
+export interface ProxyStream {
+    /** Stream id (for cancelProxyStream) */
+    id: string;
+    /** Resolves when the stream ends; a cancelled stream succeeds with the partial text */
+    result: Promise<LLMResult>;
+}
+
+/**
+ * A model offered by a provider (from `provider_models`).
+ */
+export interface ProviderModel {
+    id: string;
+    display_name: string | null;
+    context_window: number | null;
+}
+
+/**
+ * Result of listing a provider's models.
+ */
+export interface ProviderModelsResult {
+    success: boolean;
+    models: ProviderModel[];
+    /** Set when listing failed (e.g. INVALID_API_KEY, NO_ACTIVE_KEY) */
+    code?: string | undefined;
+    error?: string | undefined;
+}
+
 /**
  * Get the current settings from the store (non-reactive).
  */
@@ -29,89 +95,163 @@ function getSettings() {
 }
 
 /**
- * Get the active API key for a provider.
- * Uses the apiKeyStore which fetches from .env via Tauri IPC.
+ * Extract the message from an Error or a Tauri CommandError ({ code, message }).
  */
-async function getApiKey(provider: string): Promise<string | null> {
-    const store = useApiKeyStore.getState();
-    // Cast provider to ApiKeyService - validated at runtime via Tauri
-    return store.getActiveKeyValue(provider as import('../stores/apiKeyStore').ApiKeyService);
+function errorMessage(err: unknown): string {
+    if (err instanceof Error) {
+        return err.message;
+    }
+    if (typeof err === 'object' && err !== null && 'message' in err) {
+        return String((err as { message: unknown }).message);
+    }
+    return typeof err === 'string' ? err : 'Unknown error occurred';
 }
 
 /**
- * Generate text using the configured LLM.
- * @param prompt - The user prompt to send.
+ * Generate text through the Rust LLM proxy with the service's active API key.
+ * @param service - Provider ('gemini', 'openai', 'anthropic', 'ollama').
+ * @param model - Provider model ID.
+ * @param messages - Conversation, oldest first.
+ * @param options - Temperature, token limit, and key profile.
  * @returns LLMResult with success status and text or error.
  */
-export async function generateText(prompt: string): Promise<LLMResult> {
-    const { provider, model, temperature, systemPrompt } = getSettings();
+export async function generateWithProxy(
+    service: string,
+    model: string,
+    messages: ProxyMessage[],
+    options: ProxyOptions = {}
+): Promise<LLMResult> {
+    try {
+        const response = await safeInvoke<ProxyResponse>('llm_generate', {
+            service,
+            model,
+            messages,
+            options,
+        });
 
-    // Ollama doesn't require API key
-    if (provider === 'ollama') {
-        try {
-            return await generateWithOllama(model, temperature, systemPrompt, prompt);
-        } catch (err) {
-            const message = err instanceof Error ? err.message : 'Unknown error occurred';
-            return { success: false, error: message };
+        if (!response) {
+            return { success: false, error: 'LLM requests require the App Factory desktop app.' };
         }
+
+        console.log(`[LLM Service] Received response length: ${response.text.length} chars`);
+        return { success: true, text: response.text };
+    } catch (err) {
+        return { success: false, error: errorMessage(err) };
     }
+}
 
-    // Get API key from .env via apiKeyStore
-    const apiKey = await getApiKey(provider);
+/**
+ * Stream text through the Rust LLM proxy with the service's active API key.
+ * @param service - Provider ('gemini', 'openai', 'anthropic', 'ollama').
+ * @param model - Provider model ID.
+ * @param messages - Conversation, oldest first.
+ * @param onDelta - Called with each piece of generated text.
+ * @param options - Temperature, token limit, and key profile.
+ * @returns The running stream.
+ */
+export async function streamWithProxy(
+    service: string,
+    model: string,
+    messages: ProxyMessage[],
+    onDelta: (text: string) => void,
+    options: ProxyOptions = {}
+): Promise<ProxyStream> {
+    const id = crypto.randomUUID();
 
-    if (!apiKey) {
-        return {
-            success: false,
-            error: `No API key configured for ${provider}. Please add one in Settings > API Keys.`
-        };
+    if (!isTauri()) {
+        const error = 'LLM requests require the App Factory desktop app.';
+        return { id, result: Promise.resolve({ success: false, error }) };
     }
 
-    try {
-        if (provider === 'gemini') {
-            return await generateWithGemini(apiKey, model, temperature, systemPrompt, prompt);
+    const { listen } = await import('@tauri-apps/api/event');
+    let finish: (result: LLMResult) => void = () => undefined;
+    const result = new Promise<LLMResult>(resolve => {
+        finish = resolve;
+    });
+
+    // Listen before starting the stream so no text is missed
+    const unlistenDelta = await listen<{ text: string }>(`llm/${id}/delta`, event => {
+        onDelta(event.payload.text);
+    });
+    const unlistenFinished = await listen<ProxyStreamEnd>(`llm/${id}/finished`, event => {
+        unlistenDelta();
+        unlistenFinished();
+
+        const end = event.payload;
+        if (end.type === 'completed') {
+            finish({ success: true, text: end.response.text });
+        } else if (end.type === 'cancelled') {
+            finish({ success: true, text: end.text });
         } else {
-            // Placeholder for other providers (openai, anthropic)
-            return { success: false, error: `Provider "${provider}" is not yet implemented.` };
+            finish({ success: false, error: end.error });
         }
+    });
+
+    try {
+        await safeInvoke('llm_stream', { streamId: id, service, model, messages, options });
     } catch (err) {
-        const message = err instanceof Error ? err.message : 'Unknown error occurred';
-        return { success: false, error: message };
+        unlistenDelta();
+        unlistenFinished();
+        finish({ success: false, error: errorMessage(err) });
     }
+
+    return { id, result };
 }
 
 /**
- * Generate text using Google Gemini via @google/genai SDK.
+ * Cancel a proxy stream. Its result resolves with the text received so far.
+ * @param id - Stream id from streamWithProxy.
  */
-async function generateWithGemini(
-    apiKey: string,
-    modelId: string,
-    temperature: number,
-    systemPrompt: string,
-    userPrompt: string
-): Promise<LLMResult> {
-    // Initialize the GoogleGenAI client with the API key
-    const ai = new GoogleGenAI({ apiKey });
-
-    // Log the request for debugging
-    console.log(`[LLM Service] Sending request to Gemini (${modelId})`);
-    console.log(`[LLM Service] System Prompt:`, systemPrompt);
-    console.log(`[LLM Service] User Prompt:`, userPrompt);
-
-    // Generate content using the new SDK pattern
-    const response = await ai.models.generateContent({
-        model: modelId,
-        contents: userPrompt,
-        config: {
-            systemInstruction: systemPrompt,
-            temperature,
-        },
-    });
+export async function cancelProxyStream(id: string): Promise<void> {
+    try {
+        await safeInvoke('llm_cancel', { streamId: id });
+    } catch (err) {
+        // The stream may already have finished
+        console.warn('[LLM Service] cancelProxyStream:', errorMessage(err));
+    }
+}
+
+/**
+ * List the models available with the service's active API key.
+ * A successful listing also confirms the key is accepted.
+ * @param service - Provider ('gemini', 'openai', 'anthropic', 'ollama').
+ * @param profile - Key profile (default: the active profile).
+ * @returns The models, or the error code and message.
+ */
+export async function listProviderModels(service: string, profile?: string): Promise<ProviderModelsResult> {
+    try {
+        const response = await safeInvoke<{ models: ProviderModel[] }>('provider_models', { service, profile });
+        if (!response) {
+            return { success: false, models: [], error: 'Model listing requires the App Factory desktop app.' };
+        }
+        return { success: true, models: response.models };
+    } catch (err) {
+        const code = typeof err === 'object' && err !== null && 'code' in err
+            ? String((err as { code: unknown }).code)
+            : undefined;
+        return { success: false, models: [], code, error: errorMessage(err) };
+    }
+}
+
+/**
+ * Generate text using the configured LLM.
+ * @param prompt - The user prompt to send.
+ * @returns LLMResult with success status and text or error.
+ */
+export async function generateText(prompt: string): Promise<LLMResult> {
+    const { provider, model, temperature, systemPrompt } = getSettings();
 
-    // Extract text from response
-    const text = response.text;
-    console.log(`[LLM Service] Received response length: ${text?.length} chars`);
+    console.log(`[LLM Service] Sending request to ${provider} (${model})`);
 
-    return { success: true, text };
+    return generateWithProxy(
+        provider,
+        model,
+        [
+            { role: 'system', content: systemPrompt },
+            { role: 'user', content: prompt },
+        ],
+        { temperature }
+    );
 }
 
 /**
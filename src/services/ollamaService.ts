@@ -2,23 +2,141 @@
  * ollamaService.ts
  * ================
  * Service for interacting with the local Ollama instance.
- * Uses the official 'ollama' SDK.
+ * Detection, model listing, and pulls go through the Rust backend
+ * (ollama_* commands); generation goes through the LLM proxy like any
+ * other provider.
  */
 
-import ollama from 'ollama/browser';
-import { LLMResult } from './llmService';
+import { isTauri, safeInvoke } from '../utils/tauriUtils';
+import { generateWithProxy, LLMResult } from './llmService';
+
+/**
+ * Daemon status from the ollama_status command.
+ */
+export interface OllamaStatus {
+    running: boolean;
+    host: string;
+    version: string | null;
+    error: string | null;
+}
+
+/**
+ * A model installed on the local Ollama instance.
+ */
+export interface LocalModel {
+    name: string;
+    size: number;
+    modified_at: string | null;
+    parameter_size: string | null;
+    quantization_level: string | null;
+}
+
+/**
+ * A model pull, as sent with ollama/pull/<id>/* events.
+ */
+export interface OllamaPull {
+    id: string;
+    model: string;
+    status: 'pulling' | 'completed' | 'failed' | 'cancelled';
+    message: string;
+    completed: number;
+    total: number | null;
+    error: string | null;
+    started_at: string;
+    finished_at: string | null;
+}
+
+/**
+ * Check whether the local Ollama daemon is running.
+ * @returns Daemon status, or null outside the desktop app.
+ */
+export async function getOllamaStatus(): Promise<OllamaStatus | null> {
+    try {
+        return (await safeInvoke<OllamaStatus>('ollama_status')) ?? null;
+    } catch (error) {
+        console.error('Failed to check Ollama status:', error);
+        return null;
+    }
+}
+
+/**
+ * List the models installed on the local Ollama instance with their sizes.
+ */
+export async function listInstalledModels(): Promise<LocalModel[]> {
+    try {
+        return (await safeInvoke<LocalModel[]>('ollama_models')) ?? [];
+    } catch (error) {
+        console.error('Failed to list Ollama models:', error);
+        return [];
+    }
+}
 
 /**
  * List all models installed on the local Ollama instance.
  * @returns Array of model names (e.g., 'llama3.2', 'mistral')
  */
 export async function listLocalModels(): Promise<string[]> {
+    const models = await listInstalledModels();
+    return models.map((m) => m.name);
+}
+
+/**
+ * Pull a model into the local Ollama instance.
+ * @param model - Model name, optionally with a tag (e.g., 'llama3.2:3b')
+ * @param onProgress - Called with throttled progress updates
+ * @returns The pull id and a promise resolving with the finished pull
+ */
+export async function pullOllamaModel(
+    model: string,
+    onProgress?: (pull: OllamaPull) => void
+): Promise<{ id: string; result: Promise<OllamaPull> }> {
+    if (!isTauri()) {
+        throw new Error('Pulling models requires the App Factory desktop app.');
+    }
+
+    const { listen } = await import('@tauri-apps/api/event');
+    const pull = await safeInvoke<OllamaPull>('ollama_pull', { model });
+    if (!pull) {
+        throw new Error('Pulling models requires the App Factory desktop app.');
+    }
+
+    let finish: (pull: OllamaPull) => void = () => undefined;
+    const result = new Promise<OllamaPull>((resolve) => {
+        finish = resolve;
+    });
+
+    const unlistenProgress = await listen<OllamaPull>(`ollama/pull/${pull.id}/progress`, (event) => {
+        onProgress?.(event.payload);
+    });
+    const unlistenFinished = await listen<OllamaPull>(`ollama/pull/${pull.id}/finished`, (event) => {
+        unlistenProgress();
+        unlistenFinished();
+        finish(event.payload);
+    });
+
+    // The pull id is only known once it has started, so it may already be over
+    const pulls = await safeInvoke<OllamaPull[]>('ollama_pull_list');
+    const current = pulls?.find((p) => p.id === pull.id);
+    if (current && current.status !== 'pulling') {
+        unlistenProgress();
+        unlistenFinished();
+        finish(current);
+    }
+
+    return { id: pull.id, result };
+}
+
+/**
+ * Cancel a running pull. Layers pulled so far are kept, so pulling the
+ * same model again resumes.
+ * @param id - Pull id from pullOllamaModel
+ */
+export async function cancelOllamaPull(id: string): Promise<void> {
     try {
-        const response = await ollama.list();
-        return response.models.map((m) => m.name);
+        await safeInvoke('ollama_pull_cancel', { id });
     } catch (error) {
-        console.error('Failed to list Ollama models:', error);
-        return [];
+        // The pull may already have finished
+        console.warn('Failed to cancel Ollama pull:', error);
     }
 }
 
@@ -37,49 +155,28 @@ export async function generateWithOllama(
     userPrompt: string,
     images?: string[]
 ): Promise<LLMResult> {
-    try {
-        // Build user message payload
-        const userMessage: { role: 'user'; content: string; images?: string[] } = {
-            role: 'user',
-            content: userPrompt,
-        };
-
-        // Add images if provided (strip data URI header if present)
-        if (images && images.length > 0) {
-            userMessage.images = images.map(img =>
-                img.replace(/^data:image\/\w+;base64,/, '')
-            );
-        }
-
-        const response = await ollama.chat({
-            model: modelId,
-            messages: [
-                { role: 'system', content: systemPrompt },
-                userMessage,
-            ],
-            options: {
-                temperature,
-            },
-        });
-
-        return { success: true, text: response.message.content };
-    } catch (error) {
-        const message = error instanceof Error ? error.message : 'Unknown Ollama error';
-        return { success: false, error: `Ollama Error: ${message}` };
-    }
+    const result = await generateWithProxy(
+        'ollama',
+        modelId,
+        [
+            { role: 'system', content: systemPrompt },
+            { role: 'user', content: userPrompt, images },
+        ],
+        { temperature }
+    );
+    return result.success ? result : { success: false, error: `Ollama Error: ${result.error}` };
 }
 
 /**
  * Test connectivity to the local Ollama instance.
  */
 export async function testOllamaConnection(): Promise<LLMResult> {
-    try {
-        await ollama.list();
-        return { success: true, text: 'Ollama is running and accessible.' };
-    } catch (error) {
-        return {
-            success: false,
-            error: 'Could not connect to Ollama. Make sure it is running at http://localhost:11434',
-        };
+    const status = await getOllamaStatus();
+    if (status?.running) {
+        return { success: true, text: `Ollama ${status.version ?? ''} is running at ${status.host}.` };
     }
+    return {
+        success: false,
+        error: `Could not connect to Ollama. Make sure it is running at ${status?.host ?? 'http://localhost:11434'}`,
+    };
 }
@@ -0,0 +1,96 @@
+/**
+ * startupService.ts
+ * =================
+ * Service for following backend startup progress (startup_status command
+ * and startup://progress events), used by the splash screen while the
+ * project root is resolved and the plugin host boots.
+ */
+
+import { isTauri, safeInvoke } from '../utils/tauriUtils';
+
+/**
+ * Boot stages, in order.
+ */
+export type StartupStage =
+    | 'resolving_project_root'
+    | 'locating_python'
+    | 'spawning_host'
+    | 'host_ready'
+    | 'plugin_scan_complete';
+
+/**
+ * Human-readable labels for each stage.
+ */
+export const STARTUP_STAGE_LABELS: Record<StartupStage, string> = {
+    resolving_project_root: 'Resolving project root',
+    locating_python: 'Locating Python',
+    spawning_host: 'Starting plugin host',
+    host_ready: 'Waiting for plugin host',
+    plugin_scan_complete: 'Scanning plugins',
+};
+
+/**
+ * A recorded stage change.
+ */
+export interface StartupStep {
+    stage: StartupStage;
+    status: 'started' | 'done' | 'failed';
+    message: string | null;
+    elapsed_ms: number;
+}
+
+/**
+ * Startup status from the startup_status command.
+ */
+export interface StartupStatus {
+    steps: StartupStep[];
+    total_stages: number;
+    complete: boolean;
+    failed: boolean;
+}
+
+/**
+ * Get the startup progress so far.
+ * @returns Status, or null outside the desktop app.
+ */
+export async function getStartupStatus(): Promise<StartupStatus | null> {
+    try {
+        return (await safeInvoke<StartupStatus>('startup_status')) ?? null;
+    } catch (error) {
+        console.error('Failed to get startup status:', error);
+        return null;
+    }
+}
+
+/**
+ * Follow startup progress. The callback is called with the current status
+ * first and then on every step, so no stage is missed.
+ * @param onStatus - Called with the full status
+ * @returns Function that stops listening
+ */
+export async function onStartupProgress(
+    onStatus: (status: StartupStatus) => void
+): Promise<() => void> {
+    if (!isTauri()) {
+        return () => undefined;
+    }
+
+    const { listen } = await import('@tauri-apps/api/event');
+    // Subscribe before fetching so a step between the two is not lost
+    let latest = -1;
+    const unlisten = await listen<{ step: StartupStep; status: StartupStatus }>(
+        'startup://progress',
+        (event) => {
+            latest = Math.max(latest, event.payload.step.elapsed_ms);
+            onStatus(event.payload.status);
+        }
+    );
+
+    const status = await getStartupStatus();
+    const fetchedLatest = Math.max(-1, ...(status?.steps.map((s) => s.elapsed_ms) ?? []));
+    if (status && fetchedLatest >= latest) {
+        onStatus(status);
+    }
+
+    return unlisten;
+}
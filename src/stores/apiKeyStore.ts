@@ -25,7 +25,9 @@ export type ApiKeyService =
     | 'tts'
     | 'stt'
     | 'vision'
-    | 'embedding';
+    | 'embedding'
+    // Custom services defined in the service catalog
+    | (string & {});
 
 /**
  * API key entry from backend.
@@ -38,6 +40,20 @@ export interface ApiKeyEntry {
     keyMasked: string;
     isActive: boolean;
     createdAt: string;
+    /** Stored in plaintext by an older version; re-encrypt with `migrate_api_keys` */
+    needsMigration: boolean;
+    /** Recorded usage (null if the key has never been used) */
+    usage: ApiKeyUsage | null;
+}
+
+/**
+ * Usage totals for a key, reported by the LLM services.
+ */
+export interface ApiKeyUsage {
+    requestCount: number;
+    /** Sum of token counts reported by the provider */
+    estimatedTokens: number;
+    lastUsedAt: string;
 }
 
 /**
@@ -50,6 +66,8 @@ interface ApiKeyEntryRaw {
     key_masked: string;
     is_active: boolean;
     created_at: string;
+    needs_migration: boolean;
+    usage: { request_count: number; estimated_tokens: number; last_used_at: string } | null;
 }
 
 /**
@@ -63,9 +81,112 @@ function mapApiKeyEntry(raw: ApiKeyEntryRaw): ApiKeyEntry {
         keyMasked: raw.key_masked,
         isActive: raw.is_active,
         createdAt: raw.created_at,
+        needsMigration: raw.needs_migration,
+        usage: raw.usage
+            ? {
+                requestCount: raw.usage.request_count,
+                estimatedTokens: raw.usage.estimated_tokens,
+                lastUsedAt: raw.usage.last_used_at,
+            }
+            : null,
     };
 }
 
+/**
+ * A service from the catalog, with the key format add_api_key enforces.
+ */
+export interface ServiceInfo {
+    id: string;
+    name: string;
+    /** Accepted key prefixes (empty accepts any) */
+    keyPrefixes: string[];
+    minLength: number | null;
+    maxLength: number | null;
+    /** Validation regex (custom services) */
+    pattern: string | null;
+    /** Where to create a key */
+    docsUrl: string | null;
+    /** Key shape for input placeholders (e.g. "sk-...") */
+    hint: string | null;
+    requiresKey: boolean;
+    /** Defined by the user rather than built in */
+    custom: boolean;
+}
+
+/**
+ * Catalog service from Rust backend (snake_case).
+ */
+interface ServiceInfoRaw {
+    id: string;
+    name: string;
+    key_prefixes: string[];
+    min_length: number | null;
+    max_length: number | null;
+    pattern: string | null;
+    docs_url: string | null;
+    hint: string | null;
+    requires_key: boolean;
+    custom: boolean;
+}
+
+/**
+ * Convert a raw catalog service to camelCase.
+ */
+function mapServiceInfo(raw: ServiceInfoRaw): ServiceInfo {
+    return {
+        id: raw.id,
+        name: raw.name,
+        keyPrefixes: raw.key_prefixes,
+        minLength: raw.min_length,
+        maxLength: raw.max_length,
+        pattern: raw.pattern,
+        docsUrl: raw.docs_url,
+        hint: raw.hint,
+        requiresKey: raw.requires_key,
+        custom: raw.custom,
+    };
+}
+
+/**
+ * A user-defined service for addCustomService.
+ */
+export interface CustomServiceInput {
+    /** Lowercase letters and digits */
+    id: string;
+    name?: string;
+    /** Regex keys must match */
+    pattern?: string;
+    docsUrl?: string;
+    hint?: string;
+}
+
+/**
+ * Result of testing a key against its provider.
+ */
+export interface ApiKeyTestResult {
+    /** Whether the provider responded at all */
+    reachable: boolean;
+    /** Whether the provider accepted the key */
+    valid: boolean;
+    status: number | null;
+    latencyMs: number;
+    message: string;
+    /** Rate-limit response headers (e.g. x-ratelimit-remaining-requests) */
+    rateLimits: Record<string, string>;
+}
+
+/**
+ * Key test response from Rust backend (snake_case).
+ */
+interface ApiKeyTestResultRaw {
+    reachable: boolean;
+    valid: boolean;
+    status: number | null;
+    latency_ms: number;
+    message: string;
+    rate_limits: Record<string, string>;
+}
+
 // ============================================
 // STORE STATE & ACTIONS
 // ============================================
@@ -75,6 +196,8 @@ interface ApiKeyState {
     keys: Record<string, ApiKeyEntry[]>;
     /** Currently selected service in UI */
     selectedService: ApiKeyService;
+    /** Service catalog (built-in and custom services) */
+    services: ServiceInfo[];
     /** Loading state */
     isLoading: boolean;
     /** Error message */
@@ -103,8 +226,29 @@ interface ApiKeyActions {
     /** Get the active key for a service (from local cache) */
     getActiveKey: (service: ApiKeyService) => ApiKeyEntry | undefined;
 
-    /** Get the actual API key value for API calls (from backend) */
-    getActiveKeyValue: (service: ApiKeyService) => Promise<string | null>;
+    /** Record a provider request made with the active key (usage accounting) */
+    recordUsage: (service: ApiKeyService, tokens?: number) => Promise<void>;
+
+    /** Make a minimal authenticated request to check that a key works */
+    testKey: (service: ApiKeyService, id: string) => Promise<ApiKeyTestResult>;
+
+    /** Export keys as a passphrase-encrypted bundle (JSON string) */
+    exportKeys: (passphrase: string, services?: ApiKeyService[]) => Promise<string>;
+
+    /** Import a bundle from `exportKeys`; returns counts of imported and skipped keys */
+    importKeys: (bundle: string, passphrase: string) => Promise<{ imported: number; skipped: number }>;
+
+    /** Fetch the service catalog from backend */
+    fetchServices: () => Promise<void>;
+
+    /** Define a custom service with an optional key-format regex */
+    addCustomService: (service: CustomServiceInput) => Promise<ServiceInfo>;
+
+    /** Remove a custom service (its stored keys are kept) */
+    deleteCustomService: (id: string) => Promise<void>;
+
+    /** Check a key against the service's format; returns the problem, or null if it looks valid */
+    validateKeyFormat: (service: ApiKeyService, key: string) => Promise<string | null>;
 
     /** Clear error state */
     clearError: () => void;
@@ -134,6 +278,19 @@ async function safeInvoke<T>(cmd: string, args?: Record<string, unknown>): Promi
     return invoke<T>(cmd, args);
 }
 
+/**
+ * Message of an invoke rejection (backend errors are { code, message } objects).
+ */
+function errorMessage(err: unknown): string {
+    if (err instanceof Error) {
+        return err.message;
+    }
+    if (typeof err === 'object' && err !== null && 'message' in err) {
+        return String((err as { message: unknown }).message);
+    }
+    return String(err);
+}
+
 // ============================================
 // STORE IMPLEMENTATION
 // ============================================
@@ -142,6 +299,7 @@ export const useApiKeyStore = create<ApiKeyState & ApiKeyActions>()((set, get) =
     // Initial state
     keys: {},
     selectedService: 'gemini',
+    services: [],
     isLoading: false,
     error: null,
 
@@ -206,10 +364,10 @@ export const useApiKeyStore = create<ApiKeyState & ApiKeyActions>()((set, get) =
 
             return entry;
         } catch (err) {
-            const message = err instanceof Error ? err.message : String(err);
+            const message = errorMessage(err);
             console.error('[apiKeyStore] addKey error:', message);
             set({ error: message, isLoading: false });
-            throw err;
+            throw new Error(message);
         }
     },
 
@@ -234,10 +392,10 @@ export const useApiKeyStore = create<ApiKeyState & ApiKeyActions>()((set, get) =
                 };
             });
         } catch (err) {
-            const message = err instanceof Error ? err.message : String(err);
+            const message = errorMessage(err);
             console.error('[apiKeyStore] updateKey error:', message);
             set({ error: message, isLoading: false });
-            throw err;
+            throw new Error(message);
         }
     },
 
@@ -293,13 +451,121 @@ export const useApiKeyStore = create<ApiKeyState & ApiKeyActions>()((set, get) =
         return serviceKeys.find(k => k.isActive);
     },
 
-    getActiveKeyValue: async (service) => {
+    recordUsage: async (service, tokens) => {
         try {
-            const result = await safeInvoke<string | null>('get_active_api_key_value', { service });
-            return result;
+            await safeInvoke('record_api_key_usage', { service, tokens });
         } catch (err) {
-            console.error('[apiKeyStore] getActiveKeyValue error:', err);
+            console.warn('[apiKeyStore] recordUsage error:', err);
+        }
+    },
+
+    testKey: async (service, id) => {
+        const result = await safeInvoke<ApiKeyTestResultRaw>('test_api_key', { service, id });
+
+        if (result === null) {
+            throw new Error('Tauri not available - cannot test keys in browser mode');
+        }
+
+        return {
+            reachable: result.reachable,
+            valid: result.valid,
+            status: result.status,
+            latencyMs: result.latency_ms,
+            message: result.message,
+            rateLimits: result.rate_limits,
+        };
+    },
+
+    exportKeys: async (passphrase, services) => {
+        const bundle = await safeInvoke<string>('export_api_keys', { passphrase, services });
+
+        if (bundle === null) {
+            throw new Error('Tauri not available - cannot export keys in browser mode');
+        }
+
+        return bundle;
+    },
+
+    importKeys: async (bundle, passphrase) => {
+        set({ isLoading: true, error: null });
+
+        try {
+            const summary = await safeInvoke<{ imported: number; skipped: number }>(
+                'import_api_keys',
+                { bundle, passphrase }
+            );
+
+            if (summary === null) {
+                throw new Error('Tauri not available - cannot import keys in browser mode');
+            }
+
+            set({ isLoading: false });
+            // Imported keys may belong to the selected service
+            await get().fetchKeys(get().selectedService);
+            return summary;
+        } catch (err) {
+            const message = err instanceof Error ? err.message : String(err);
+            console.error('[apiKeyStore] importKeys error:', message);
+            set({ error: message, isLoading: false });
+            throw err;
+        }
+    },
+
+    fetchServices: async () => {
+        try {
+            const result = await safeInvoke<ServiceInfoRaw[]>('list_services');
+            if (result !== null) {
+                set({ services: result.map(mapServiceInfo) });
+            }
+        } catch (err) {
+            console.error('[apiKeyStore] fetchServices error:', errorMessage(err));
+        }
+    },
+
+    addCustomService: async (service) => {
+        try {
+            const result = await safeInvoke<ServiceInfoRaw>('add_custom_service', {
+                service: {
+                    id: service.id,
+                    name: service.name,
+                    pattern: service.pattern,
+                    docs_url: service.docsUrl,
+                    hint: service.hint,
+                },
+            });
+
+            if (result === null) {
+                throw new Error('Tauri not available - cannot add services in browser mode');
+            }
+
+            const info = mapServiceInfo(result);
+            set((state) => ({ services: [...state.services, info] }));
+            return info;
+        } catch (err) {
+            throw new Error(errorMessage(err));
+        }
+    },
+
+    deleteCustomService: async (id) => {
+        try {
+            await safeInvoke('delete_custom_service', { id });
+            set((state) => ({
+                services: state.services.filter(s => s.id !== id),
+                selectedService: state.selectedService === id ? 'gemini' : state.selectedService,
+            }));
+        } catch (err) {
+            const message = errorMessage(err);
+            console.error('[apiKeyStore] deleteCustomService error:', message);
+            set({ error: message });
+        }
+    },
+
+    validateKeyFormat: async (service, key) => {
+        try {
+            await safeInvoke('validate_api_key_format', { service, key });
             return null;
+        } catch (err) {
+            return errorMessage(err);
         }
     },
 
@@ -311,9 +577,10 @@ export const useApiKeyStore = create<ApiKeyState & ApiKeyActions>()((set, get) =
 // ============================================
 
 /**
- * Human-readable labels for API key services.
+ * Human-readable labels for the built-in API key services.
+ * The service catalog (`services`) also covers custom services.
  */
-export const API_KEY_SERVICE_LABELS: Record<ApiKeyService, string> = {
+export const API_KEY_SERVICE_LABELS: Record<string, string> = {
     gemini: 'Google Gemini',
     openai: 'OpenAI',
     anthropic: 'Anthropic',